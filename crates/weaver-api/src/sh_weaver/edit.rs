@@ -5,6 +5,7 @@
 // This file was automatically generated from Lexicon schemas.
 // Any manual changes will be overwritten on the next regeneration.
 
+pub mod comment;
 pub mod cursor;
 pub mod diff;
 pub mod draft;
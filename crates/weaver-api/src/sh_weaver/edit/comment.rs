@@ -0,0 +1,501 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: sh.weaver.edit.comment
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+/// An inline comment anchored to a position in a collaborative document.
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment<'a> {
+    /// Encoded Loro cursor marking the end of the anchored range. Absent for point annotations
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(default, with = "jacquard_common::opt_serde_bytes_helper")]
+    pub anchor_end: std::option::Option<bytes::Bytes>,
+    /// Encoded Loro cursor marking the start of the anchored range. Stable across concurrent edits
+    #[serde(with = "jacquard_common::serde_bytes_helper")]
+    pub anchor_start: bytes::Bytes,
+    /// Comment text, in the same extended markdown as notebook entries
+    #[serde(borrow)]
+    pub body: jacquard_common::CowStr<'a>,
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub created_at: std::option::Option<jacquard_common::types::string::Datetime>,
+    #[serde(borrow)]
+    pub doc: crate::sh_weaver::edit::DocRef<'a>,
+    /// Whether this thread has been resolved. Only meaningful on thread roots
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub resolved: std::option::Option<bool>,
+    /// Root comment of the thread this comment replies to. Absent on thread roots
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub thread: std::option::Option<crate::com_atproto::repo::strong_ref::StrongRef<'a>>,
+}
+
+pub mod comment_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type AnchorStart;
+        type Body;
+        type Doc;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type AnchorStart = Unset;
+        type Body = Unset;
+        type Doc = Unset;
+    }
+    ///State transition - sets the `anchorStart` field to Set
+    pub struct SetAnchorStart<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetAnchorStart<S> {}
+    impl<S: State> State for SetAnchorStart<S> {
+        type AnchorStart = Set<members::anchor_start>;
+        type Body = S::Body;
+        type Doc = S::Doc;
+    }
+    ///State transition - sets the `body` field to Set
+    pub struct SetBody<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetBody<S> {}
+    impl<S: State> State for SetBody<S> {
+        type AnchorStart = S::AnchorStart;
+        type Body = Set<members::body>;
+        type Doc = S::Doc;
+    }
+    ///State transition - sets the `doc` field to Set
+    pub struct SetDoc<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetDoc<S> {}
+    impl<S: State> State for SetDoc<S> {
+        type AnchorStart = S::AnchorStart;
+        type Body = S::Body;
+        type Doc = Set<members::doc>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `anchorStart` field
+        pub struct anchor_start(());
+        ///Marker type for the `body` field
+        pub struct body(());
+        ///Marker type for the `doc` field
+        pub struct doc(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct CommentBuilder<'a, S: comment_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<bytes::Bytes>,
+        ::core::option::Option<bytes::Bytes>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::types::string::Datetime>,
+        ::core::option::Option<crate::sh_weaver::edit::DocRef<'a>>,
+        ::core::option::Option<bool>,
+        ::core::option::Option<crate::com_atproto::repo::strong_ref::StrongRef<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> Comment<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> CommentBuilder<'a, comment_state::Empty> {
+        CommentBuilder::new()
+    }
+}
+
+impl<'a> CommentBuilder<'a, comment_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        CommentBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None, None, None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: comment_state::State> CommentBuilder<'a, S> {
+    /// Set the `anchorEnd` field (optional)
+    pub fn anchor_end(mut self, value: impl Into<Option<bytes::Bytes>>) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `anchorEnd` field to an Option value (optional)
+    pub fn maybe_anchor_end(mut self, value: Option<bytes::Bytes>) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S> CommentBuilder<'a, S>
+where
+    S: comment_state::State,
+    S::AnchorStart: comment_state::IsUnset,
+{
+    /// Set the `anchorStart` field (required)
+    pub fn anchor_start(
+        mut self,
+        value: impl Into<bytes::Bytes>,
+    ) -> CommentBuilder<'a, comment_state::SetAnchorStart<S>> {
+        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        CommentBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> CommentBuilder<'a, S>
+where
+    S: comment_state::State,
+    S::Body: comment_state::IsUnset,
+{
+    /// Set the `body` field (required)
+    pub fn body(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> CommentBuilder<'a, comment_state::SetBody<S>> {
+        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
+        CommentBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: comment_state::State> CommentBuilder<'a, S> {
+    /// Set the `createdAt` field (optional)
+    pub fn created_at(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::Datetime>>,
+    ) -> Self {
+        self.__unsafe_private_named.3 = value.into();
+        self
+    }
+    /// Set the `createdAt` field to an Option value (optional)
+    pub fn maybe_created_at(
+        mut self,
+        value: Option<jacquard_common::types::string::Datetime>,
+    ) -> Self {
+        self.__unsafe_private_named.3 = value;
+        self
+    }
+}
+
+impl<'a, S> CommentBuilder<'a, S>
+where
+    S: comment_state::State,
+    S::Doc: comment_state::IsUnset,
+{
+    /// Set the `doc` field (required)
+    pub fn doc(
+        mut self,
+        value: impl Into<crate::sh_weaver::edit::DocRef<'a>>,
+    ) -> CommentBuilder<'a, comment_state::SetDoc<S>> {
+        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
+        CommentBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: comment_state::State> CommentBuilder<'a, S> {
+    /// Set the `resolved` field (optional)
+    pub fn resolved(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.__unsafe_private_named.5 = value.into();
+        self
+    }
+    /// Set the `resolved` field to an Option value (optional)
+    pub fn maybe_resolved(mut self, value: Option<bool>) -> Self {
+        self.__unsafe_private_named.5 = value;
+        self
+    }
+}
+
+impl<'a, S: comment_state::State> CommentBuilder<'a, S> {
+    /// Set the `thread` field (optional)
+    pub fn thread(
+        mut self,
+        value: impl Into<Option<crate::com_atproto::repo::strong_ref::StrongRef<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.6 = value.into();
+        self
+    }
+    /// Set the `thread` field to an Option value (optional)
+    pub fn maybe_thread(
+        mut self,
+        value: Option<crate::com_atproto::repo::strong_ref::StrongRef<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.6 = value;
+        self
+    }
+}
+
+impl<'a, S> CommentBuilder<'a, S>
+where
+    S: comment_state::State,
+    S::AnchorStart: comment_state::IsSet,
+    S::Body: comment_state::IsSet,
+    S::Doc: comment_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> Comment<'a> {
+        Comment {
+            anchor_end: self.__unsafe_private_named.0,
+            anchor_start: self.__unsafe_private_named.1.unwrap(),
+            body: self.__unsafe_private_named.2.unwrap(),
+            created_at: self.__unsafe_private_named.3,
+            doc: self.__unsafe_private_named.4.unwrap(),
+            resolved: self.__unsafe_private_named.5,
+            thread: self.__unsafe_private_named.6,
+            extra_data: Default::default(),
+        }
+    }
+    /// Build the final struct with custom extra_data
+    pub fn build_with_data(
+        self,
+        extra_data: std::collections::BTreeMap<
+            jacquard_common::smol_str::SmolStr,
+            jacquard_common::types::value::Data<'a>,
+        >,
+    ) -> Comment<'a> {
+        Comment {
+            anchor_end: self.__unsafe_private_named.0,
+            anchor_start: self.__unsafe_private_named.1.unwrap(),
+            body: self.__unsafe_private_named.2.unwrap(),
+            created_at: self.__unsafe_private_named.3,
+            doc: self.__unsafe_private_named.4.unwrap(),
+            resolved: self.__unsafe_private_named.5,
+            thread: self.__unsafe_private_named.6,
+            extra_data: Some(extra_data),
+        }
+    }
+}
+
+impl<'a> Comment<'a> {
+    pub fn uri(
+        uri: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> Result<
+        jacquard_common::types::uri::RecordUri<'a, CommentRecord>,
+        jacquard_common::types::uri::UriError,
+    > {
+        jacquard_common::types::uri::RecordUri::try_from_uri(
+            jacquard_common::types::string::AtUri::new_cow(uri.into())?,
+        )
+    }
+}
+
+/// Typed wrapper for GetRecord response with this collection's record type.
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct CommentGetRecordOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cid: std::option::Option<jacquard_common::types::string::Cid<'a>>,
+    #[serde(borrow)]
+    pub uri: jacquard_common::types::string::AtUri<'a>,
+    #[serde(borrow)]
+    pub value: Comment<'a>,
+}
+
+impl From<CommentGetRecordOutput<'_>> for Comment<'_> {
+    fn from(output: CommentGetRecordOutput<'_>) -> Self {
+        use jacquard_common::IntoStatic;
+        output.value.into_static()
+    }
+}
+
+impl jacquard_common::types::collection::Collection for Comment<'_> {
+    const NSID: &'static str = "sh.weaver.edit.comment";
+    type Record = CommentRecord;
+}
+
+/// Marker type for deserializing records from this collection.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CommentRecord;
+impl jacquard_common::xrpc::XrpcResp for CommentRecord {
+    const NSID: &'static str = "sh.weaver.edit.comment";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = CommentGetRecordOutput<'de>;
+    type Err<'de> = jacquard_common::types::collection::RecordError<'de>;
+}
+
+impl jacquard_common::types::collection::Collection for CommentRecord {
+    const NSID: &'static str = "sh.weaver.edit.comment";
+    type Record = CommentRecord;
+}
+
+impl<'a> ::jacquard_lexicon::schema::LexiconSchema for Comment<'a> {
+    fn nsid() -> &'static str {
+        "sh.weaver.edit.comment"
+    }
+    fn def_name() -> &'static str {
+        "main"
+    }
+    fn lexicon_doc() -> ::jacquard_lexicon::lexicon::LexiconDoc<'static> {
+        lexicon_doc_sh_weaver_edit_comment()
+    }
+    fn validate(
+        &self,
+    ) -> ::core::result::Result<(), ::jacquard_lexicon::validation::ConstraintError> {
+        Ok(())
+    }
+}
+
+fn lexicon_doc_sh_weaver_edit_comment() -> ::jacquard_lexicon::lexicon::LexiconDoc<
+    'static,
+> {
+    ::jacquard_lexicon::lexicon::LexiconDoc {
+        lexicon: ::jacquard_lexicon::lexicon::Lexicon::Lexicon1,
+        id: ::jacquard_common::CowStr::new_static("sh.weaver.edit.comment"),
+        revision: None,
+        description: None,
+        defs: {
+            let mut map = ::alloc::collections::BTreeMap::new();
+            map.insert(
+                ::jacquard_common::smol_str::SmolStr::new_static("main"),
+                ::jacquard_lexicon::lexicon::LexUserType::Record(::jacquard_lexicon::lexicon::LexRecord {
+                    description: Some(
+                        ::jacquard_common::CowStr::new_static(
+                            "An inline comment anchored to a position in a collaborative document.",
+                        ),
+                    ),
+                    key: Some(::jacquard_common::CowStr::new_static("tid")),
+                    record: ::jacquard_lexicon::lexicon::LexRecordRecord::Object(::jacquard_lexicon::lexicon::LexObject {
+                        description: None,
+                        required: Some(
+                            vec![
+                                ::jacquard_common::smol_str::SmolStr::new_static("doc"),
+                                ::jacquard_common::smol_str::SmolStr::new_static("body"),
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "anchorStart"
+                                )
+                            ],
+                        ),
+                        nullable: None,
+                        properties: {
+                            #[allow(unused_mut)]
+                            let mut map = ::alloc::collections::BTreeMap::new();
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "anchorEnd",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Bytes(::jacquard_lexicon::lexicon::LexBytes {
+                                    description: None,
+                                    max_length: Some(512usize),
+                                    min_length: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "anchorStart",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Bytes(::jacquard_lexicon::lexicon::LexBytes {
+                                    description: None,
+                                    max_length: Some(512usize),
+                                    min_length: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("body"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: None,
+                                    format: None,
+                                    default: None,
+                                    min_length: None,
+                                    max_length: Some(100000usize),
+                                    min_graphemes: None,
+                                    max_graphemes: Some(10000usize),
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "createdAt",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: None,
+                                    format: Some(
+                                        ::jacquard_lexicon::lexicon::LexStringFormat::Datetime,
+                                    ),
+                                    default: None,
+                                    min_length: None,
+                                    max_length: None,
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("doc"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Ref(::jacquard_lexicon::lexicon::LexRef {
+                                    description: None,
+                                    r#ref: ::jacquard_common::CowStr::new_static(
+                                        "sh.weaver.edit.defs#docRef",
+                                    ),
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "resolved",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Boolean(::jacquard_lexicon::lexicon::LexBoolean {
+                                    description: None,
+                                    default: None,
+                                    r#const: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "thread",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Ref(::jacquard_lexicon::lexicon::LexRef {
+                                    description: None,
+                                    r#ref: ::jacquard_common::CowStr::new_static(
+                                        "com.atproto.repo.strongRef",
+                                    ),
+                                }),
+                            );
+                            map
+                        },
+                    }),
+                }),
+            );
+            map
+        },
+    }
+}
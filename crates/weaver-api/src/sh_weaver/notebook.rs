@@ -18,6 +18,7 @@ pub mod get_entry_by_title;
 pub mod get_entry_detail;
 pub mod get_entry_feed;
 pub mod get_entry_notebooks;
+pub mod get_feed;
 pub mod get_notebook;
 pub mod get_notebook_by_title;
 pub mod get_notebook_chapters;
@@ -28,6 +29,7 @@ pub mod get_published_versions;
 pub mod get_reading_history;
 pub mod get_similar_notebooks;
 pub mod get_suggested_notebooks;
+pub mod index;
 pub mod page;
 pub mod resolve_entry;
 pub mod resolve_global_notebook;
@@ -0,0 +1,624 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: sh.weaver.notebook.index
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+/// Sidecar title/path index for a Weaver notebook. Shares its rkey with the indexed book record so it is addressable without a lookup. Readers must treat it as a hint and fall back to scanning the notebook's entry list when it is missing or stale.
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct NotebookIndex<'a> {
+    #[serde(borrow)]
+    pub entries: Vec<IndexedEntry<'a>>,
+    #[serde(borrow)]
+    pub notebook: crate::com_atproto::repo::strong_ref::StrongRef<'a>,
+    /// Client-declared timestamp of the last index rebuild or incremental update.
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub updated_at: std::option::Option<jacquard_common::types::string::Datetime>,
+}
+
+pub mod notebook_index_state {
+
+    pub use crate::builder_types::{IsSet, IsUnset, Set, Unset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Notebook;
+        type Entries;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Notebook = Unset;
+        type Entries = Unset;
+    }
+    ///State transition - sets the `notebook` field to Set
+    pub struct SetNotebook<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetNotebook<S> {}
+    impl<S: State> State for SetNotebook<S> {
+        type Notebook = Set<members::notebook>;
+        type Entries = S::Entries;
+    }
+    ///State transition - sets the `entries` field to Set
+    pub struct SetEntries<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetEntries<S> {}
+    impl<S: State> State for SetEntries<S> {
+        type Notebook = S::Notebook;
+        type Entries = Set<members::entries>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `notebook` field
+        pub struct notebook(());
+        ///Marker type for the `entries` field
+        pub struct entries(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct NotebookIndexBuilder<'a, S: notebook_index_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<Vec<IndexedEntry<'a>>>,
+        ::core::option::Option<crate::com_atproto::repo::strong_ref::StrongRef<'a>>,
+        ::core::option::Option<jacquard_common::types::string::Datetime>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> NotebookIndex<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> NotebookIndexBuilder<'a, notebook_index_state::Empty> {
+        NotebookIndexBuilder::new()
+    }
+}
+
+impl<'a> NotebookIndexBuilder<'a, notebook_index_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        NotebookIndexBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> NotebookIndexBuilder<'a, S>
+where
+    S: notebook_index_state::State,
+    S::Entries: notebook_index_state::IsUnset,
+{
+    /// Set the `entries` field (required)
+    pub fn entries(
+        mut self,
+        value: impl Into<Vec<IndexedEntry<'a>>>,
+    ) -> NotebookIndexBuilder<'a, notebook_index_state::SetEntries<S>> {
+        self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
+        NotebookIndexBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> NotebookIndexBuilder<'a, S>
+where
+    S: notebook_index_state::State,
+    S::Notebook: notebook_index_state::IsUnset,
+{
+    /// Set the `notebook` field (required)
+    pub fn notebook(
+        mut self,
+        value: impl Into<crate::com_atproto::repo::strong_ref::StrongRef<'a>>,
+    ) -> NotebookIndexBuilder<'a, notebook_index_state::SetNotebook<S>> {
+        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        NotebookIndexBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: notebook_index_state::State> NotebookIndexBuilder<'a, S> {
+    /// Set the `updatedAt` field (optional)
+    pub fn updated_at(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::Datetime>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `updatedAt` field to an Option value (optional)
+    pub fn maybe_updated_at(
+        mut self,
+        value: Option<jacquard_common::types::string::Datetime>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
+impl<'a, S> NotebookIndexBuilder<'a, S>
+where
+    S: notebook_index_state::State,
+    S::Notebook: notebook_index_state::IsSet,
+    S::Entries: notebook_index_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> NotebookIndex<'a> {
+        NotebookIndex {
+            entries: self.__unsafe_private_named.0.unwrap(),
+            notebook: self.__unsafe_private_named.1.unwrap(),
+            updated_at: self.__unsafe_private_named.2,
+            extra_data: Default::default(),
+        }
+    }
+    /// Build the final struct with custom extra_data
+    pub fn build_with_data(
+        self,
+        extra_data: std::collections::BTreeMap<
+            jacquard_common::smol_str::SmolStr,
+            jacquard_common::types::value::Data<'a>,
+        >,
+    ) -> NotebookIndex<'a> {
+        NotebookIndex {
+            entries: self.__unsafe_private_named.0.unwrap(),
+            notebook: self.__unsafe_private_named.1.unwrap(),
+            updated_at: self.__unsafe_private_named.2,
+            extra_data: Some(extra_data),
+        }
+    }
+}
+
+impl<'a> NotebookIndex<'a> {
+    pub fn uri(
+        uri: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> Result<
+        jacquard_common::types::uri::RecordUri<'a, NotebookIndexRecord>,
+        jacquard_common::types::uri::UriError,
+    > {
+        jacquard_common::types::uri::RecordUri::try_from_uri(
+            jacquard_common::types::string::AtUri::new_cow(uri.into())?,
+        )
+    }
+}
+
+/// One title/path to rkey mapping in a notebook index.
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEntry<'a> {
+    #[serde(borrow)]
+    pub path: jacquard_common::CowStr<'a>,
+    #[serde(borrow)]
+    pub rkey: jacquard_common::CowStr<'a>,
+    #[serde(borrow)]
+    pub title: jacquard_common::CowStr<'a>,
+}
+
+pub mod indexed_entry_state {
+
+    pub use crate::builder_types::{IsSet, IsUnset, Set, Unset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Title;
+        type Path;
+        type Rkey;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Title = Unset;
+        type Path = Unset;
+        type Rkey = Unset;
+    }
+    ///State transition - sets the `title` field to Set
+    pub struct SetTitle<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetTitle<S> {}
+    impl<S: State> State for SetTitle<S> {
+        type Title = Set<members::title>;
+        type Path = S::Path;
+        type Rkey = S::Rkey;
+    }
+    ///State transition - sets the `path` field to Set
+    pub struct SetPath<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetPath<S> {}
+    impl<S: State> State for SetPath<S> {
+        type Title = S::Title;
+        type Path = Set<members::path>;
+        type Rkey = S::Rkey;
+    }
+    ///State transition - sets the `rkey` field to Set
+    pub struct SetRkey<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetRkey<S> {}
+    impl<S: State> State for SetRkey<S> {
+        type Title = S::Title;
+        type Path = S::Path;
+        type Rkey = Set<members::rkey>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `title` field
+        pub struct title(());
+        ///Marker type for the `path` field
+        pub struct path(());
+        ///Marker type for the `rkey` field
+        pub struct rkey(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct IndexedEntryBuilder<'a, S: indexed_entry_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> IndexedEntry<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> IndexedEntryBuilder<'a, indexed_entry_state::Empty> {
+        IndexedEntryBuilder::new()
+    }
+}
+
+impl<'a> IndexedEntryBuilder<'a, indexed_entry_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        IndexedEntryBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> IndexedEntryBuilder<'a, S>
+where
+    S: indexed_entry_state::State,
+    S::Path: indexed_entry_state::IsUnset,
+{
+    /// Set the `path` field (required)
+    pub fn path(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> IndexedEntryBuilder<'a, indexed_entry_state::SetPath<S>> {
+        self.__unsafe_private_named.0 = ::core::option::Option::Some(value.into());
+        IndexedEntryBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> IndexedEntryBuilder<'a, S>
+where
+    S: indexed_entry_state::State,
+    S::Rkey: indexed_entry_state::IsUnset,
+{
+    /// Set the `rkey` field (required)
+    pub fn rkey(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> IndexedEntryBuilder<'a, indexed_entry_state::SetRkey<S>> {
+        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        IndexedEntryBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> IndexedEntryBuilder<'a, S>
+where
+    S: indexed_entry_state::State,
+    S::Title: indexed_entry_state::IsUnset,
+{
+    /// Set the `title` field (required)
+    pub fn title(
+        mut self,
+        value: impl Into<jacquard_common::CowStr<'a>>,
+    ) -> IndexedEntryBuilder<'a, indexed_entry_state::SetTitle<S>> {
+        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
+        IndexedEntryBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> IndexedEntryBuilder<'a, S>
+where
+    S: indexed_entry_state::State,
+    S::Title: indexed_entry_state::IsSet,
+    S::Path: indexed_entry_state::IsSet,
+    S::Rkey: indexed_entry_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> IndexedEntry<'a> {
+        IndexedEntry {
+            path: self.__unsafe_private_named.0.unwrap(),
+            rkey: self.__unsafe_private_named.1.unwrap(),
+            title: self.__unsafe_private_named.2.unwrap(),
+            extra_data: Default::default(),
+        }
+    }
+    /// Build the final struct with custom extra_data
+    pub fn build_with_data(
+        self,
+        extra_data: std::collections::BTreeMap<
+            jacquard_common::smol_str::SmolStr,
+            jacquard_common::types::value::Data<'a>,
+        >,
+    ) -> IndexedEntry<'a> {
+        IndexedEntry {
+            path: self.__unsafe_private_named.0.unwrap(),
+            rkey: self.__unsafe_private_named.1.unwrap(),
+            title: self.__unsafe_private_named.2.unwrap(),
+            extra_data: Some(extra_data),
+        }
+    }
+}
+
+/// Typed wrapper for GetRecord response with this collection's record type.
+#[derive(
+    serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, jacquard_derive::IntoStatic,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct NotebookIndexGetRecordOutput<'a> {
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cid: std::option::Option<jacquard_common::types::string::Cid<'a>>,
+    #[serde(borrow)]
+    pub uri: jacquard_common::types::string::AtUri<'a>,
+    #[serde(borrow)]
+    pub value: NotebookIndex<'a>,
+}
+
+impl From<NotebookIndexGetRecordOutput<'_>> for NotebookIndex<'_> {
+    fn from(output: NotebookIndexGetRecordOutput<'_>) -> Self {
+        use jacquard_common::IntoStatic;
+        output.value.into_static()
+    }
+}
+
+impl jacquard_common::types::collection::Collection for NotebookIndex<'_> {
+    const NSID: &'static str = "sh.weaver.notebook.index";
+    type Record = NotebookIndexRecord;
+}
+
+/// Marker type for deserializing records from this collection.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct NotebookIndexRecord;
+impl jacquard_common::xrpc::XrpcResp for NotebookIndexRecord {
+    const NSID: &'static str = "sh.weaver.notebook.index";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = NotebookIndexGetRecordOutput<'de>;
+    type Err<'de> = jacquard_common::types::collection::RecordError<'de>;
+}
+
+impl jacquard_common::types::collection::Collection for NotebookIndexRecord {
+    const NSID: &'static str = "sh.weaver.notebook.index";
+    type Record = NotebookIndexRecord;
+}
+
+impl<'a> ::jacquard_lexicon::schema::LexiconSchema for NotebookIndex<'a> {
+    fn nsid() -> &'static str {
+        "sh.weaver.notebook.index"
+    }
+    fn def_name() -> &'static str {
+        "main"
+    }
+    fn lexicon_doc() -> ::jacquard_lexicon::lexicon::LexiconDoc<'static> {
+        lexicon_doc_sh_weaver_notebook_index()
+    }
+    fn validate(
+        &self,
+    ) -> ::core::result::Result<(), ::jacquard_lexicon::validation::ConstraintError> {
+        Ok(())
+    }
+}
+
+impl<'a> ::jacquard_lexicon::schema::LexiconSchema for IndexedEntry<'a> {
+    fn nsid() -> &'static str {
+        "sh.weaver.notebook.index"
+    }
+    fn def_name() -> &'static str {
+        "indexedEntry"
+    }
+    fn lexicon_doc() -> ::jacquard_lexicon::lexicon::LexiconDoc<'static> {
+        lexicon_doc_sh_weaver_notebook_index()
+    }
+    fn validate(
+        &self,
+    ) -> ::core::result::Result<(), ::jacquard_lexicon::validation::ConstraintError> {
+        Ok(())
+    }
+}
+
+fn lexicon_doc_sh_weaver_notebook_index() -> ::jacquard_lexicon::lexicon::LexiconDoc<'static> {
+    ::jacquard_lexicon::lexicon::LexiconDoc {
+        lexicon: ::jacquard_lexicon::lexicon::Lexicon::Lexicon1,
+        id: ::jacquard_common::CowStr::new_static("sh.weaver.notebook.index"),
+        revision: None,
+        description: None,
+        defs: {
+            let mut map = ::alloc::collections::BTreeMap::new();
+            map.insert(
+                ::jacquard_common::smol_str::SmolStr::new_static("indexedEntry"),
+                ::jacquard_lexicon::lexicon::LexUserType::Object(
+                    ::jacquard_lexicon::lexicon::LexObject {
+                        description: Some(::jacquard_common::CowStr::new_static(
+                            "One title/path to rkey mapping in a notebook index.",
+                        )),
+                        required: Some(vec![
+                            ::jacquard_common::smol_str::SmolStr::new_static("title"),
+                            ::jacquard_common::smol_str::SmolStr::new_static("path"),
+                            ::jacquard_common::smol_str::SmolStr::new_static("rkey"),
+                        ]),
+                        nullable: None,
+                        properties: {
+                            #[allow(unused_mut)]
+                            let mut map = ::alloc::collections::BTreeMap::new();
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("path"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(
+                                    ::jacquard_lexicon::lexicon::LexString {
+                                        description: None,
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    },
+                                ),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("rkey"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(
+                                    ::jacquard_lexicon::lexicon::LexString {
+                                        description: None,
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    },
+                                ),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("title"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(
+                                    ::jacquard_lexicon::lexicon::LexString {
+                                        description: None,
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    },
+                                ),
+                            );
+                            map
+                        },
+                    },
+                ),
+            );
+            map.insert(
+                ::jacquard_common::smol_str::SmolStr::new_static("main"),
+                ::jacquard_lexicon::lexicon::LexUserType::Record(::jacquard_lexicon::lexicon::LexRecord {
+                    description: Some(
+                        ::jacquard_common::CowStr::new_static(
+                            "Sidecar title/path index for a Weaver notebook. Shares its rkey with the indexed book record so it is addressable without a lookup. Readers must treat it as a hint and fall back to scanning the notebook's entry list when it is missing or stale.",
+                        ),
+                    ),
+                    key: Some(::jacquard_common::CowStr::new_static("tid")),
+                    record: ::jacquard_lexicon::lexicon::LexRecordRecord::Object(::jacquard_lexicon::lexicon::LexObject {
+                        description: None,
+                        required: Some(
+                            vec![
+                                ::jacquard_common::smol_str::SmolStr::new_static("notebook"),
+                                ::jacquard_common::smol_str::SmolStr::new_static("entries")
+                            ],
+                        ),
+                        nullable: None,
+                        properties: {
+                            #[allow(unused_mut)]
+                            let mut map = ::alloc::collections::BTreeMap::new();
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("entries"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Array(::jacquard_lexicon::lexicon::LexArray {
+                                    description: None,
+                                    items: ::jacquard_lexicon::lexicon::LexArrayItem::Ref(::jacquard_lexicon::lexicon::LexRef {
+                                        description: None,
+                                        r#ref: ::jacquard_common::CowStr::new_static(
+                                            "#indexedEntry",
+                                        ),
+                                    }),
+                                    min_length: None,
+                                    max_length: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "notebook",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Ref(::jacquard_lexicon::lexicon::LexRef {
+                                    description: None,
+                                    r#ref: ::jacquard_common::CowStr::new_static(
+                                        "com.atproto.repo.strongRef",
+                                    ),
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "updatedAt",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: Some(
+                                        ::jacquard_common::CowStr::new_static(
+                                            "Client-declared timestamp of the last index rebuild or incremental update.",
+                                        ),
+                                    ),
+                                    format: Some(
+                                        ::jacquard_lexicon::lexicon::LexStringFormat::Datetime,
+                                    ),
+                                    default: None,
+                                    min_length: None,
+                                    max_length: None,
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                            );
+                            map
+                        },
+                    }),
+                }),
+            );
+            map
+        },
+    }
+}
@@ -0,0 +1,3 @@
+DROP TABLE waitlist;
+DROP TABLE registrations;
+DROP TABLE invite_codes;
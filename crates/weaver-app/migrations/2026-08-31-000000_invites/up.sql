@@ -0,0 +1,21 @@
+-- Invite-code registration and waitlist tables.
+CREATE TABLE invite_codes (
+    code TEXT PRIMARY KEY NOT NULL,
+    created_by TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    max_uses INTEGER NOT NULL DEFAULT 1,
+    uses INTEGER NOT NULL DEFAULT 0,
+    disabled INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE registrations (
+    did TEXT PRIMARY KEY NOT NULL,
+    invite_code TEXT NOT NULL REFERENCES invite_codes (code),
+    registered_at TEXT NOT NULL
+);
+
+CREATE TABLE waitlist (
+    ident TEXT PRIMARY KEY NOT NULL,
+    note TEXT,
+    joined_at TEXT NOT NULL
+);
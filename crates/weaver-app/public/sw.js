@@ -1,18 +1,25 @@
 // Weaver Service Worker
-// Handles blob/image requests by caching immutable images
+// Handles blob/image requests by caching immutable images, and keeps
+// rendered copies of visited pages so entries stay readable offline
 //
 // URL patterns handled:
 // - /image/{ident}/draft/{blob_rkey}/{name}  - draft images (unpublished)
 // - /image/{ident}/{entry_rkey}/{name}       - published entry images
 // - /image/{notebook}/{name}                 - notebook images (legacy)
 // - /{notebook}/image/{name}                 - notebook images (legacy path)
+// - navigations                              - network-first page cache
 
 const CACHE_NAME = "weaver-blobs-v2";
+const PAGE_CACHE_NAME = "weaver-pages-v1";
 
 // Map of notebook/path -> real URL for legacy blob mappings
 // e.g., "notebook/image/foo.jpg" -> "https://pds.example.com/xrpc/com.atproto.sync.getBlob?..."
 const urlMappings = new Map();
 
+// Map of page path -> record CID reported by the app, so an entry whose
+// record changed invalidates its cached page instead of serving stale HTML.
+const pageCids = new Map();
+
 // Install and activate immediately
 self.addEventListener("install", (event) => {
   self.skipWaiting();
@@ -28,7 +35,11 @@ self.addEventListener("activate", (event) => {
         .then((names) =>
           Promise.all(
             names
-              .filter((name) => name.startsWith("weaver-blobs-") && name !== CACHE_NAME)
+              .filter(
+                (name) =>
+                  (name.startsWith("weaver-blobs-") && name !== CACHE_NAME) ||
+                  (name.startsWith("weaver-pages-") && name !== PAGE_CACHE_NAME),
+              )
               .map((name) => caches.delete(name)),
           ),
         ),
@@ -55,6 +66,16 @@ self.addEventListener("message", (event) => {
       urlMappings.set(key, url);
     }
   }
+  if (event.data.type === "cache_entry_page") {
+    const { path, cid } = event.data;
+    const known = pageCids.get(path);
+    if (known && known !== cid) {
+      // The record behind this page changed; drop the stale cached copy so
+      // the next offline visit doesn't show outdated content.
+      event.waitUntil(caches.open(PAGE_CACHE_NAME).then((cache) => cache.delete(path)));
+    }
+    pageCids.set(path, cid);
+  }
 });
 
 // Check if a path is an image request we should cache
@@ -80,6 +101,13 @@ self.addEventListener("fetch", (event) => {
     return;
   }
 
+  // Navigations go network-first with a cache fallback so visited pages
+  // (and the entries on them) stay readable offline.
+  if (event.request.mode === "navigate") {
+    event.respondWith(handleNavigationRequest(event.request, url.pathname));
+    return;
+  }
+
   // Check if this is an image request
   if (!isImagePath(url.pathname)) {
     return;
@@ -113,6 +141,30 @@ self.addEventListener("fetch", (event) => {
   event.respondWith(handleImageRequest(event.request, cacheKey));
 });
 
+// Handle navigation requests: prefer the network, fall back to the cached
+// copy of the page when offline. Pages are not immutable like blobs, so the
+// app invalidates them via cache_entry_page when a record's CID changes.
+async function handleNavigationRequest(request, cacheKey) {
+  const cache = await caches.open(PAGE_CACHE_NAME);
+  try {
+    const response = await fetch(request);
+    if (response.ok) {
+      await cache.put(cacheKey, response.clone());
+    }
+    return response;
+  } catch (error) {
+    const cached = await cache.match(cacheKey);
+    if (cached) {
+      return cached;
+    }
+    return new Response(
+      "<!doctype html><html><body><h1>Offline</h1>" +
+        "<p>This page hasn't been visited yet, so no saved copy is available.</p></body></html>",
+      { status: 503, headers: { "Content-Type": "text/html" } },
+    );
+  }
+}
+
 // Handle requests that have a direct URL mapping (legacy)
 async function handleBlobRequest(url, cacheKey) {
   try {
@@ -2,12 +2,65 @@ mod storage;
 pub use storage::AuthStore;
 
 mod state;
-pub use state::AuthState;
+pub use state::{AuthState, StoredAccount};
 
 use crate::fetch::Fetcher;
 use dioxus::prelude::*;
 #[cfg(all(feature = "fullstack-server", feature = "server"))]
 use jacquard::oauth::types::OAuthClientMetadata;
+#[cfg(target_arch = "wasm32")]
+use jacquard::types::string::Did;
+
+/// localStorage key remembering which stored account was last active, so a
+/// reload restores the same session the user was working in.
+#[cfg(target_arch = "wasm32")]
+const ACTIVE_DID_KEY: &str = "weaver_active_did";
+
+/// Persist the active-account marker; safe to call after login or switch.
+#[cfg(target_arch = "wasm32")]
+pub fn remember_active_did(did: &Did<'_>) {
+    use gloo_storage::{LocalStorage, Storage};
+
+    let _ = LocalStorage::set(ACTIVE_DID_KEY, did.as_ref());
+}
+
+/// Enumerate every restorable session recorded in localStorage
+/// (format: oauth_session_{did}_{session_id}).
+#[cfg(target_arch = "wasm32")]
+pub fn stored_accounts() -> Vec<(Did<'static>, String)> {
+    use std::collections::BTreeMap;
+
+    use gloo_storage::{LocalStorage, Storage};
+    use jacquard::smol_str::SmolStr;
+
+    let entries = match LocalStorage::get_all::<BTreeMap<SmolStr, serde_json::Value>>() {
+        Ok(e) => e,
+        Err(e) => {
+            tracing::warn!("stored_accounts: localStorage.get_all failed: {:?}", e);
+            return Vec::new();
+        }
+    };
+
+    let mut found = Vec::new();
+    for key in entries.keys() {
+        if key.starts_with("oauth_session_") {
+            let parts: Vec<&str> = key
+                .strip_prefix("oauth_session_")
+                .unwrap()
+                .split('_')
+                .collect();
+            if parts.len() >= 2 {
+                match Did::new_owned(parts[0].to_string()) {
+                    Ok(did) => found.push((did, parts[1..].join("_"))),
+                    Err(_) => {
+                        tracing::warn!("stored_accounts: invalid DID format: {}", parts[0]);
+                    }
+                }
+            }
+        }
+    }
+    found
+}
 
 /// Result of attempting to restore a session
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,6 +87,40 @@ pub async fn client_metadata() -> Result<axum::Json<serde_json::Value>> {
     )?))
 }
 
+/// Callers on gated server routes identify themselves with this header pair.
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+pub(crate) const DID_HEADER: &str = "x-weaver-did";
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+pub(crate) const SESSION_ID_HEADER: &str = "x-weaver-session-id";
+
+/// Resolve the caller's DID from their server-side OAuth session.
+///
+/// A bare DID header proves nothing — anyone can send one. The session
+/// id is the secret minted at login, so a (DID, session id) pair that
+/// matches the server's auth store shows the caller actually holds that
+/// session. Returns `None` for absent or unverifiable pairs; gated
+/// routes treat that as unauthenticated.
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+pub(crate) async fn session_did(
+    headers: &axum::http::HeaderMap,
+) -> Option<jacquard::types::string::Did<'static>> {
+    use jacquard::IntoStatic;
+    use jacquard::oauth::authstore::ClientAuthStore;
+    use jacquard::types::string::Did;
+
+    let did = headers.get(DID_HEADER)?.to_str().ok()?;
+    let session_id = headers.get(SESSION_ID_HEADER)?.to_str().ok()?;
+    let did = Did::new(did).ok()?.into_static();
+    match AuthStore::new().get_session(&did, session_id).await {
+        Ok(Some(_)) => Some(did),
+        Ok(None) => None,
+        Err(e) => {
+            tracing::warn!(error = %e, "session lookup failed while authenticating request");
+            None
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub async fn restore_session(_fetcher: Fetcher, _auth_state: Signal<AuthState>) -> RestoreResult {
     RestoreResult::NoSession
@@ -41,54 +128,41 @@ pub async fn restore_session(_fetcher: Fetcher, _auth_state: Signal<AuthState>)
 
 #[cfg(target_arch = "wasm32")]
 pub async fn restore_session(fetcher: Fetcher, mut auth_state: Signal<AuthState>) -> RestoreResult {
-    use std::collections::BTreeMap;
-
     use gloo_storage::{LocalStorage, Storage};
+    use jacquard::CowStr;
     use jacquard::oauth::authstore::ClientAuthStore;
-    use jacquard::smol_str::SmolStr;
-    use jacquard::types::string::Did;
 
     // Skip restore if already authenticated (e.g., just completed callback flow)
     if auth_state.read().is_authenticated() {
         return RestoreResult::Restored;
     }
 
-    // Look for session keys in localStorage (format: oauth_session_{did}_{session_id})
-    let entries = match LocalStorage::get_all::<BTreeMap<SmolStr, serde_json::Value>>() {
-        Ok(e) => e,
-        Err(e) => {
-            tracing::warn!("restore_session: localStorage.get_all failed: {:?}", e);
-            return RestoreResult::NoSession;
-        }
-    };
+    // Collect every stored session so each account shows up in the switcher.
+    let found_sessions = stored_accounts();
+    if found_sessions.is_empty() {
+        return RestoreResult::NoSession;
+    }
 
-    let mut found_session: Option<(String, String)> = None;
-    for key in entries.keys() {
-        if key.starts_with("oauth_session_") {
-            let parts: Vec<&str> = key
-                .strip_prefix("oauth_session_")
-                .unwrap()
-                .split('_')
-                .collect();
-            if parts.len() >= 2 {
-                found_session = Some((parts[0].to_string(), parts[1..].join("_")));
-                break;
-            }
+    {
+        let mut state = auth_state.write();
+        for (did, session_id) in &found_sessions {
+            state.upsert_account(did.clone(), CowStr::from(session_id.clone()));
         }
     }
 
-    let Some((did_str, session_id)) = found_session else {
-        return RestoreResult::NoSession;
-    };
-
-    let Ok(did) = Did::new_owned(did_str.clone()) else {
-        tracing::warn!("restore_session: invalid DID format: {}", did_str);
-        return RestoreResult::NoSession;
-    };
+    // Prefer the account that was active before the reload; fall back to the
+    // first stored session if the marker is missing or stale.
+    let active_marker = LocalStorage::get::<String>(ACTIVE_DID_KEY).ok();
+    let (did, session_id) = found_sessions
+        .iter()
+        .find(|(did, _)| Some(did.as_ref()) == active_marker.as_deref())
+        .unwrap_or(&found_sessions[0])
+        .clone();
 
     match fetcher.client.oauth_client.restore(&did, &session_id).await {
         Ok(session) => {
             let (restored_did, session_id) = session.session_info().await;
+            remember_active_did(&restored_did);
             auth_state
                 .write()
                 .set_authenticated(restored_did, session_id);
@@ -98,7 +172,65 @@ pub async fn restore_session(fetcher: Fetcher, mut auth_state: Signal<AuthState>
         Err(e) => {
             tracing::warn!("restore_session: failed, clearing dead session: {e}");
             let _ = AuthStore::new().delete_session(&did, &session_id).await;
+            auth_state.write().remove_account(&did);
             RestoreResult::SessionExpired
         }
     }
 }
+
+/// Activate a different stored account.
+///
+/// Restores the target session, swaps it into the shared client, and drops
+/// the viewer-scoped caches so nothing rendered for the old account leaks
+/// into the new one. Returns `true` on success; a dead session is deleted
+/// from storage and forgotten.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn switch_account(
+    _fetcher: Fetcher,
+    _auth_state: Signal<AuthState>,
+    _did: jacquard::types::string::Did<'static>,
+) -> bool {
+    false
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn switch_account(
+    fetcher: Fetcher,
+    mut auth_state: Signal<AuthState>,
+    did: Did<'static>,
+) -> bool {
+    use jacquard::oauth::authstore::ClientAuthStore;
+
+    if auth_state.peek().did.as_ref().map(|d| d.as_ref()) == Some(did.as_ref()) {
+        return true;
+    }
+    let Some(account) = auth_state.peek().account(&did).cloned() else {
+        return false;
+    };
+
+    match fetcher
+        .client
+        .oauth_client
+        .restore(&did, &account.session_id)
+        .await
+    {
+        Ok(session) => {
+            let (restored_did, session_id) = session.session_info().await;
+            fetcher.upgrade_to_authenticated(session).await;
+            fetcher.clear_session_caches();
+            remember_active_did(&restored_did);
+            auth_state
+                .write()
+                .set_authenticated(restored_did, session_id);
+            true
+        }
+        Err(e) => {
+            tracing::warn!("switch_account: failed, dropping dead session: {e}");
+            let _ = AuthStore::new()
+                .delete_session(&did, &account.session_id)
+                .await;
+            auth_state.write().remove_account(&did);
+            false
+        }
+    }
+}
@@ -1,9 +1,19 @@
 use jacquard::{CowStr, IntoStatic, types::string::Did};
 
+/// A restorable OAuth session, keyed by account DID.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StoredAccount {
+    pub did: Did<'static>,
+    pub session_id: CowStr<'static>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct AuthState {
     pub did: Option<Did<'static>>,
     pub session_id: Option<CowStr<'static>>,
+    /// Every restorable session, including the active one. Populated from
+    /// storage on restore and kept in sync as accounts sign in and out.
+    pub accounts: Vec<StoredAccount>,
 }
 
 impl Default for AuthState {
@@ -11,6 +21,7 @@ impl Default for AuthState {
         Self {
             did: None,
             session_id: None,
+            accounts: Vec::new(),
         }
     }
 }
@@ -20,13 +31,49 @@ impl AuthState {
         self.did.is_some()
     }
 
+    /// Make the given session active and record it in the account list.
     pub fn set_authenticated(&mut self, did: Did<'_>, session_id: CowStr<'_>) {
-        self.did = Some(did.into_static());
-        self.session_id = Some(session_id.into_static());
+        let did = did.into_static();
+        let session_id = session_id.into_static();
+
+        self.upsert_account(did.clone(), session_id.clone());
+        self.did = Some(did);
+        self.session_id = Some(session_id);
+    }
+
+    /// Record a restorable session without making it active.
+    pub fn upsert_account(&mut self, did: Did<'static>, session_id: CowStr<'static>) {
+        if let Some(existing) = self
+            .accounts
+            .iter_mut()
+            .find(|a| a.did.as_ref() == did.as_ref())
+        {
+            existing.session_id = session_id;
+        } else {
+            self.accounts.push(StoredAccount { did, session_id });
+        }
+    }
+
+    /// Look up a known account by DID.
+    pub fn account(&self, did: &Did<'_>) -> Option<&StoredAccount> {
+        self.accounts
+            .iter()
+            .find(|a| a.did.as_ref() == did.as_ref())
+    }
+
+    /// Forget one account; deactivates it first if it's the active session.
+    pub fn remove_account(&mut self, did: &Did<'_>) {
+        self.accounts.retain(|a| a.did.as_ref() != did.as_ref());
+        if self.did.as_ref().map(|d| d.as_ref()) == Some(did.as_ref()) {
+            self.did = None;
+            self.session_id = None;
+        }
     }
 
+    /// Full sign-out: forgets the active session and every stored account.
     pub fn clear(&mut self) {
         self.did = None;
         self.session_id = None;
+        self.accounts.clear();
     }
 }
@@ -0,0 +1,384 @@
+//! Size-aware storage backing the server blob cache.
+//!
+//! The shared [`cache_impl`](crate::cache_impl) caches bound entry *count*,
+//! which suits small records but not blobs: a hundred full-size images can be
+//! anywhere from a few megabytes to over a gigabyte. This store bounds the
+//! total resident *bytes* instead, evicting least-recently-used blobs when an
+//! insert would exceed the budget. Evicted blobs can optionally be spilled to
+//! a cache directory; blobs are content-addressed by CID, so a disk copy
+//! never goes stale and needs no TTL.
+
+use jacquard::bytes::Bytes;
+use jacquard::smol_str::SmolStr;
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+use std::time::{Duration, Instant};
+
+/// Tunables for [`BlobStore`], read from the environment by the server.
+#[derive(Debug, Clone)]
+pub struct BlobStoreConfig {
+    /// Total byte budget for blob bytes held in memory.
+    pub max_bytes: u64,
+    /// Lifetime applied when the caller does not choose one.
+    pub default_ttl: Duration,
+    /// Directory evicted blobs are spilled to; `None` disables disk spill.
+    pub disk_dir: Option<PathBuf>,
+}
+
+impl Default for BlobStoreConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: 256 * 1024 * 1024,
+            // Matches the TTL the blob cache used before the byte budget
+            // existed.
+            default_ttl: Duration::from_secs(12000),
+            disk_dir: None,
+        }
+    }
+}
+
+impl BlobStoreConfig {
+    /// Read overrides from `WEAVER_BLOB_CACHE_BYTES`,
+    /// `WEAVER_BLOB_CACHE_TTL_SECS`, and `WEAVER_BLOB_CACHE_DIR`.
+    ///
+    /// Unset or unparseable values fall back to the defaults: blob caching
+    /// should degrade, not abort startup, when the environment is off.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(bytes) = read_env_u64("WEAVER_BLOB_CACHE_BYTES") {
+            config.max_bytes = bytes;
+        }
+        if let Some(secs) = read_env_u64("WEAVER_BLOB_CACHE_TTL_SECS") {
+            config.default_ttl = Duration::from_secs(secs);
+        }
+        if let Ok(dir) = std::env::var("WEAVER_BLOB_CACHE_DIR") {
+            if !dir.is_empty() {
+                config.disk_dir = Some(PathBuf::from(dir));
+            }
+        }
+        config
+    }
+}
+
+fn read_env_u64(key: &str) -> Option<u64> {
+    let raw = std::env::var(key).ok()?;
+    match raw.parse() {
+        Ok(value) => Some(value),
+        Err(_) => {
+            tracing::warn!(%key, %raw, "ignoring unparseable blob cache setting");
+            None
+        }
+    }
+}
+
+/// Monotonic counters for blob cache behaviour.
+///
+/// Counters are `Relaxed`: they feed logs and the perf module, not control
+/// flow.
+#[derive(Debug, Default)]
+struct BlobStoreMetrics {
+    memory_hits: AtomicU64,
+    disk_hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+}
+
+/// Point-in-time snapshot of the store's counters plus residency gauges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlobCacheStats {
+    pub memory_hits: u64,
+    pub disk_hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub expirations: u64,
+    pub resident_bytes: u64,
+    pub resident_blobs: u64,
+}
+
+struct StoredBlob {
+    bytes: Bytes,
+    expires_at: Instant,
+    /// Monotonic use counter; the smallest value is the LRU victim.
+    last_used: u64,
+}
+
+struct StoreInner {
+    blobs: HashMap<SmolStr, StoredBlob>,
+    total_bytes: u64,
+    tick: u64,
+}
+
+/// Byte-budgeted LRU blob store with per-entry TTLs and optional disk spill.
+///
+/// Keys are CID strings, which are base32 and therefore safe to reuse as
+/// spill file names.
+pub struct BlobStore {
+    config: BlobStoreConfig,
+    inner: Mutex<StoreInner>,
+    metrics: BlobStoreMetrics,
+}
+
+impl BlobStore {
+    pub fn new(mut config: BlobStoreConfig) -> Self {
+        if let Some(dir) = &config.disk_dir {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                // Failing to create the spill directory downgrades to
+                // memory-only caching rather than failing server startup.
+                tracing::warn!(dir = %dir.display(), error = %e, "blob cache disk spill disabled");
+                config.disk_dir = None;
+            }
+        }
+        Self {
+            config,
+            inner: Mutex::new(StoreInner {
+                blobs: HashMap::new(),
+                total_bytes: 0,
+                tick: 0,
+            }),
+            metrics: BlobStoreMetrics::default(),
+        }
+    }
+
+    /// Fetch a blob, preferring memory and falling back to the spill
+    /// directory.
+    ///
+    /// A disk hit is promoted back into memory so repeated reads of a hot
+    /// blob stay cheap.
+    pub fn get(&self, key: &SmolStr) -> Option<Bytes> {
+        let resident = {
+            let mut inner = self.inner.lock().unwrap();
+            if inner
+                .blobs
+                .get(key)
+                .is_some_and(|blob| blob.expires_at <= Instant::now())
+            {
+                let blob = inner.blobs.remove(key).expect("entry checked above");
+                inner.total_bytes -= blob.bytes.len() as u64;
+                self.metrics.expirations.fetch_add(1, Relaxed);
+                None
+            } else {
+                inner.tick += 1;
+                let tick = inner.tick;
+                inner.blobs.get_mut(key).map(|blob| {
+                    blob.last_used = tick;
+                    blob.bytes.clone()
+                })
+            }
+        };
+        if let Some(bytes) = resident {
+            self.metrics.memory_hits.fetch_add(1, Relaxed);
+            return Some(bytes);
+        }
+        if let Some(dir) = &self.config.disk_dir {
+            if let Some(bytes) = disk_read(dir, key) {
+                self.metrics.disk_hits.fetch_add(1, Relaxed);
+                self.insert_with_ttl(key.clone(), bytes.clone(), self.config.default_ttl);
+                return Some(bytes);
+            }
+        }
+        self.metrics.misses.fetch_add(1, Relaxed);
+        None
+    }
+
+    /// Insert a blob with the configured default lifetime.
+    pub fn insert(&self, key: SmolStr, bytes: Bytes) {
+        self.insert_with_ttl(key, bytes, self.config.default_ttl);
+    }
+
+    /// Insert a blob with an explicit lifetime.
+    ///
+    /// The TTL only bounds memory: blobs are content-addressed, so an entry
+    /// never becomes stale, it just stops earning its keep.
+    pub fn insert_with_ttl(&self, key: SmolStr, bytes: Bytes, ttl: Duration) {
+        let size = bytes.len() as u64;
+        if size > self.config.max_bytes {
+            // A blob larger than the whole budget would evict everything
+            // else the moment it landed; keep it out of memory entirely.
+            if let Some(dir) = &self.config.disk_dir {
+                disk_write(dir, &key, &bytes);
+            }
+            return;
+        }
+        let spilled = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.tick += 1;
+            let entry = StoredBlob {
+                bytes,
+                expires_at: Instant::now() + ttl,
+                last_used: inner.tick,
+            };
+            if let Some(old) = inner.blobs.insert(key, entry) {
+                inner.total_bytes -= old.bytes.len() as u64;
+            }
+            inner.total_bytes += size;
+            self.evict_over_budget(&mut inner)
+        };
+        if !spilled.is_empty() {
+            if let Some(dir) = &self.config.disk_dir {
+                // Spill outside the lock; disk writes must not block
+                // readers.
+                for (key, bytes) in &spilled {
+                    disk_write(dir, key, bytes);
+                }
+            }
+            crate::perf::log_blob_cache_stats(&self.stats());
+        }
+    }
+
+    /// Bring `inner` back under the byte budget, returning the evicted
+    /// blobs so the caller can spill them after releasing the lock.
+    fn evict_over_budget(&self, inner: &mut StoreInner) -> Vec<(SmolStr, Bytes)> {
+        let mut spilled = Vec::new();
+        if inner.total_bytes <= self.config.max_bytes {
+            return spilled;
+        }
+        // Expired entries free budget without losing anything live.
+        let now = Instant::now();
+        let expired: Vec<SmolStr> = inner
+            .blobs
+            .iter()
+            .filter(|(_, blob)| blob.expires_at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            let blob = inner.blobs.remove(&key).expect("collected above");
+            inner.total_bytes -= blob.bytes.len() as u64;
+            self.metrics.expirations.fetch_add(1, Relaxed);
+        }
+        // The store holds at most a few hundred blobs, so a linear scan per
+        // eviction is cheaper than maintaining an ordered index.
+        while inner.total_bytes > self.config.max_bytes {
+            let Some(victim) = inner
+                .blobs
+                .iter()
+                .min_by_key(|(_, blob)| blob.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            let blob = inner.blobs.remove(&victim).expect("selected above");
+            inner.total_bytes -= blob.bytes.len() as u64;
+            self.metrics.evictions.fetch_add(1, Relaxed);
+            spilled.push((victim, blob.bytes));
+        }
+        spilled
+    }
+
+    /// Snapshot the counters and residency gauges.
+    pub fn stats(&self) -> BlobCacheStats {
+        let (resident_bytes, resident_blobs) = {
+            let inner = self.inner.lock().unwrap();
+            (inner.total_bytes, inner.blobs.len() as u64)
+        };
+        BlobCacheStats {
+            memory_hits: self.metrics.memory_hits.load(Relaxed),
+            disk_hits: self.metrics.disk_hits.load(Relaxed),
+            misses: self.metrics.misses.load(Relaxed),
+            evictions: self.metrics.evictions.load(Relaxed),
+            expirations: self.metrics.expirations.load(Relaxed),
+            resident_bytes,
+            resident_blobs,
+        }
+    }
+}
+
+fn disk_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(key)
+}
+
+fn disk_read(dir: &Path, key: &str) -> Option<Bytes> {
+    std::fs::read(disk_path(dir, key)).ok().map(Bytes::from)
+}
+
+fn disk_write(dir: &Path, key: &str, bytes: &Bytes) {
+    // Write-then-rename so a crash mid-write cannot leave a truncated blob
+    // that would later be served as an image.
+    let tmp = dir.join(format!("{key}.tmp"));
+    let result: io::Result<()> =
+        std::fs::write(&tmp, bytes).and_then(|()| std::fs::rename(&tmp, disk_path(dir, key)));
+    if let Err(e) = result {
+        tracing::warn!(%key, error = %e, "failed to spill blob to disk");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(len: usize) -> Bytes {
+        Bytes::from(vec![0xabu8; len])
+    }
+
+    fn temp_dir(tag: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("weaver-blob-store-{}-{}", tag, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn lru_eviction_respects_byte_budget() {
+        let store = BlobStore::new(BlobStoreConfig {
+            max_bytes: 100,
+            ..BlobStoreConfig::default()
+        });
+        store.insert("a".into(), blob(40));
+        store.insert("b".into(), blob(40));
+        // Touch `a` so `b` becomes the LRU victim.
+        assert!(store.get(&"a".into()).is_some());
+        store.insert("c".into(), blob(40));
+
+        assert!(store.get(&"a".into()).is_some());
+        assert!(store.get(&"b".into()).is_none());
+        assert!(store.get(&"c".into()).is_some());
+        let stats = store.stats();
+        assert_eq!(stats.evictions, 1);
+        assert!(stats.resident_bytes <= 100);
+    }
+
+    #[test]
+    fn per_entry_ttl_expires() {
+        let store = BlobStore::new(BlobStoreConfig::default());
+        store.insert_with_ttl("a".into(), blob(10), Duration::ZERO);
+        assert!(store.get(&"a".into()).is_none());
+        let stats = store.stats();
+        assert_eq!(stats.expirations, 1);
+        assert_eq!(stats.resident_bytes, 0);
+    }
+
+    #[test]
+    fn oversized_blob_stays_out_of_memory() {
+        let store = BlobStore::new(BlobStoreConfig {
+            max_bytes: 10,
+            ..BlobStoreConfig::default()
+        });
+        store.insert("big".into(), blob(20));
+        assert_eq!(store.stats().resident_bytes, 0);
+        assert!(store.get(&"big".into()).is_none());
+    }
+
+    #[test]
+    fn evicted_blobs_round_trip_through_disk() {
+        let dir = temp_dir("spill");
+        let store = BlobStore::new(BlobStoreConfig {
+            max_bytes: 40,
+            disk_dir: Some(dir.clone()),
+            ..BlobStoreConfig::default()
+        });
+        store.insert("a".into(), blob(30));
+        // Inserting `b` pushes `a` over the budget and onto disk.
+        store.insert("b".into(), blob(30));
+        assert_eq!(store.stats().evictions, 1);
+
+        // Reading `a` hits the spill file and promotes it, displacing `b`.
+        assert_eq!(store.get(&"a".into()), Some(blob(30)));
+        assert_eq!(store.get(&"b".into()), Some(blob(30)));
+        assert_eq!(store.stats().disk_hits, 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::blob_store::{BlobCacheStats, BlobStore, BlobStoreConfig};
 use crate::cache_impl;
 use crate::fetch::Fetcher;
 #[cfg(all(feature = "fullstack-server", feature = "server"))]
@@ -19,25 +20,37 @@ use weaver_api::sh_weaver::notebook::entry::Entry;
 use weaver_api::sh_weaver::publish::blob::Blob as PublishedBlob;
 use weaver_common::WeaverExt;
 
+/// Draft images are frequently re-uploaded while an entry is being edited; a
+/// shorter lifetime keeps churned uploads from sitting on the byte budget.
+const DRAFT_BLOB_TTL: Duration = Duration::from_secs(1800);
+
 #[derive(Clone)]
 pub struct BlobCache {
     fetcher: Arc<Fetcher>,
-    cache: cache_impl::Cache<Cid<'static>, Bytes>,
+    store: Arc<BlobStore>,
     map: cache_impl::Cache<SmolStr, Cid<'static>>,
 }
 
 impl BlobCache {
     pub fn new(fetcher: Arc<Fetcher>) -> Self {
-        let cache = cache_impl::new_cache(100, Duration::from_secs(12000));
+        // Blob bytes are budgeted by size; the name map stays a small
+        // count-bounded cache since its entries are just keys.
+        let store = Arc::new(BlobStore::new(BlobStoreConfig::from_env()));
         let map = cache_impl::new_cache(500, Duration::from_secs(12000));
 
         Self {
             fetcher,
-            cache,
+            store,
             map,
         }
     }
 
+    /// Blob store keys are the CID's string form, which doubles as a
+    /// filesystem-safe spill file name.
+    fn blob_key(cid: &Cid<'_>) -> SmolStr {
+        format_smolstr!("{}", cid)
+    }
+
     /// Resolve DID and PDS URL from an identifier
     async fn resolve_ident(
         &self,
@@ -104,7 +117,7 @@ impl BlobCache {
 
         let blob = self.fetch_blob(&repo_did, pds_url, &cid).await?;
 
-        self.cache.insert(cid.clone(), blob);
+        self.store.insert(Self::blob_key(&cid), blob);
         if let Some(name) = name {
             self.map.insert(name, cid);
         }
@@ -187,7 +200,7 @@ impl BlobCache {
 
         // Fetch and cache the blob
         let blob = self.fetch_blob(&repo_did, pds_url, &cid).await?;
-        self.cache.insert(cid.clone(), blob.clone());
+        self.store.insert(Self::blob_key(&cid), blob.clone());
         self.map.insert(name.into(), cid);
 
         Ok(blob)
@@ -251,7 +264,8 @@ impl BlobCache {
 
         // Fetch and cache the blob
         let blob = self.fetch_blob(&repo_did, pds_url, &cid).await?;
-        self.cache.insert(cid, blob.clone());
+        self.store
+            .insert_with_ttl(Self::blob_key(&cid), blob.clone(), DRAFT_BLOB_TTL);
 
         Ok(blob)
     }
@@ -357,7 +371,7 @@ impl BlobCache {
 
                         // Fetch and cache the blob
                         let blob = self.fetch_blob(&repo_did, pds_url, &cid).await?;
-                        self.cache.insert(cid.clone(), blob.clone());
+                        self.store.insert(Self::blob_key(&cid), blob.clone());
                         self.map.insert(cache_key, cid);
                         return Ok(blob);
                     }
@@ -378,18 +392,25 @@ impl BlobCache {
 
     /// Insert bytes directly into cache (for pre-warming after upload)
     pub fn insert_bytes(&self, cid: Cid<'static>, bytes: Bytes, name: Option<SmolStr>) {
-        self.cache.insert(cid.clone(), bytes);
+        self.store.insert(Self::blob_key(&cid), bytes);
         if let Some(name) = name {
             self.map.insert(name, cid);
         }
     }
 
     pub fn get_cid(&self, cid: &Cid<'static>) -> Option<Bytes> {
-        self.cache.get(cid)
+        self.store.get(&Self::blob_key(cid))
     }
 
     pub fn get_named(&self, name: &SmolStr) -> Option<Bytes> {
-        self.map.get(name).and_then(|cid| self.cache.get(&cid))
+        self.map
+            .get(name)
+            .and_then(|cid| self.store.get(&Self::blob_key(&cid)))
+    }
+
+    /// Snapshot of the blob store's counters, for perf logging.
+    pub fn stats(&self) -> BlobCacheStats {
+        self.store.stats()
     }
 }
 
@@ -1,5 +1,45 @@
-//! Re-export cache implementation from weaver-common.
+//! Re-export cache implementation from weaver-common, plus app-level caches.
 //!
-//! This module exists for backwards compatibility during migration.
+//! The platform-specific cache API is re-exported for backwards
+//! compatibility during migration.
 
 pub use weaver_common::cache::*;
+
+use jacquard::types::did::Did;
+use std::time::Duration;
+
+/// Rendered-HTML cache for read-only entry views, one segment per
+/// top-level markdown block.
+///
+/// Keyed by author DID plus a `hash_source` hash of the segment's
+/// markdown, so re-visiting or live-updating an entry only re-renders the
+/// segments whose source actually changed — the read-path counterpart of
+/// the editor's incremental render cache.
+#[derive(Clone)]
+pub struct ParagraphCache {
+    cache: Cache<(Did<'static>, u64), String>,
+}
+
+impl ParagraphCache {
+    pub fn new() -> Self {
+        // Cacheable segment HTML is a pure function of (author, source),
+        // so the TTL only bounds memory, not staleness.
+        Self {
+            cache: new_cache(2048, Duration::from_secs(1800)),
+        }
+    }
+
+    pub fn get(&self, did: &Did<'static>, source_hash: u64) -> Option<String> {
+        get(&self.cache, &(did.clone(), source_hash))
+    }
+
+    pub fn insert(&self, did: &Did<'static>, source_hash: u64, html: String) {
+        insert(&self.cache, (did.clone(), source_hash), html);
+    }
+}
+
+impl Default for ParagraphCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
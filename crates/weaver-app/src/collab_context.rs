@@ -23,6 +23,8 @@ pub struct CollabDebugState {
     pub is_joined: bool,
     /// Last error message
     pub last_error: Option<SmolStr>,
+    /// DIDs whose authorization lapsed mid-session (ops ignored)
+    pub revoked_peers: Vec<SmolStr>,
 }
 
 /// Hook to get the collab debug state signal.
@@ -59,6 +59,15 @@ pub enum AppLinkTarget {
     Drafts { ident: AtIdentifier<'static> },
     /// Invites page: /:ident/invites
     Invites { ident: AtIdentifier<'static> },
+    /// Private analytics dashboard: /:ident/analytics
+    Analytics { ident: AtIdentifier<'static> },
+    /// Tag index: /:ident/tags (always main domain in subdomain mode)
+    Tags { ident: AtIdentifier<'static> },
+    /// Entries for one tag: /:ident/tags/:tag
+    Tag {
+        ident: AtIdentifier<'static>,
+        tag: SmolStr,
+    },
 }
 
 #[derive(Props, Clone, PartialEq)]
@@ -121,6 +130,9 @@ pub fn AppLink(props: AppLinkProps) -> Element {
                 AppLinkTarget::NewDraft { ident, notebook } => Route::NewDraft { ident, notebook },
                 AppLinkTarget::Drafts { ident } => Route::DraftsList { ident },
                 AppLinkTarget::Invites { ident } => Route::InvitesPage { ident },
+                AppLinkTarget::Analytics { ident } => Route::AnalyticsPage { ident },
+                AppLinkTarget::Tags { ident } => Route::TagsIndex { ident },
+                AppLinkTarget::Tag { ident, tag } => Route::TagPage { ident, tag },
             };
             rsx! {
                 Link { to: route, class: "{class}", {props.children} }
@@ -193,6 +205,24 @@ pub fn AppLink(props: AppLinkProps) -> Element {
                         a { href: "{href}", class: "{class}", {props.children} }
                     }
                 }
+                AppLinkTarget::Analytics { ident } => {
+                    let href = format!("{}/{}/analytics", WEAVER_APP_HOST, ident);
+                    rsx! {
+                        a { href: "{href}", class: "{class}", {props.children} }
+                    }
+                }
+                AppLinkTarget::Tags { ident } => {
+                    let href = format!("{}/{}/tags", WEAVER_APP_HOST, ident);
+                    rsx! {
+                        a { href: "{href}", class: "{class}", {props.children} }
+                    }
+                }
+                AppLinkTarget::Tag { ident, tag } => {
+                    let href = format!("{}/{}/tags/{}", WEAVER_APP_HOST, ident, tag);
+                    rsx! {
+                        a { href: "{href}", class: "{class}", {props.children} }
+                    }
+                }
             }
         }
     }
@@ -255,6 +285,9 @@ pub fn use_main_navigator_provider() {
                 AppLinkTarget::NewDraft { ident, notebook } => Route::NewDraft { ident, notebook },
                 AppLinkTarget::Drafts { ident } => Route::DraftsList { ident },
                 AppLinkTarget::Invites { ident } => Route::InvitesPage { ident },
+                AppLinkTarget::Analytics { ident } => Route::AnalyticsPage { ident },
+                AppLinkTarget::Tags { ident } => Route::TagsIndex { ident },
+                AppLinkTarget::Tag { ident, tag } => Route::TagPage { ident, tag },
             };
             navigator.push(route);
         }) as NavigateFn
@@ -334,6 +367,36 @@ pub fn use_subdomain_navigator_provider() {
                     #[cfg(not(target_arch = "wasm32"))]
                     let _ = ident;
                 }
+                AppLinkTarget::Analytics { ident } => {
+                    #[cfg(target_arch = "wasm32")]
+                    if let Some(window) = web_sys::window() {
+                        let path = format!("{}/{}/analytics", WEAVER_APP_HOST, ident);
+                        let _ = window.location().set_href(&path);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let _ = ident;
+                }
+                AppLinkTarget::Tags { ident } => {
+                    #[cfg(target_arch = "wasm32")]
+                    if let Some(window) = web_sys::window() {
+                        let path = format!("{}/{}/tags", WEAVER_APP_HOST, ident);
+                        let _ = window.location().set_href(&path);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let _ = ident;
+                }
+                AppLinkTarget::Tag { ident, tag } => {
+                    #[cfg(target_arch = "wasm32")]
+                    if let Some(window) = web_sys::window() {
+                        let path = format!("{}/{}/tags/{}", WEAVER_APP_HOST, ident, tag);
+                        let _ = window.location().set_href(&path);
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    {
+                        let _ = ident;
+                        let _ = tag;
+                    }
+                }
             }
         }) as NavigateFn
     });
@@ -0,0 +1,185 @@
+//! Bookmarking entries and notebooks for later reading.
+//!
+//! Bookmarks are `sh.weaver.graph.bookmark` records in the viewer's own
+//! repo, so toggle state comes straight from their PDS. Aggregate counts
+//! need a global view and are only shown when the `use-index` backend is
+//! available.
+
+use dioxus::prelude::*;
+use jacquard::IntoStatic;
+use jacquard::client::AgentSessionExt;
+use jacquard::types::collection::Collection;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::nsid::Nsid;
+use jacquard::types::string::{AtUri, Cid, Datetime};
+use weaver_api::com_atproto::repo::delete_record::DeleteRecord;
+use weaver_api::com_atproto::repo::list_records::ListRecords;
+use weaver_api::com_atproto::repo::strong_ref::StrongRef;
+use weaver_api::sh_weaver::graph::bookmark::Bookmark;
+
+use crate::auth::AuthState;
+use crate::fetch::Fetcher;
+
+const BOOKMARK_CSS: Asset = asset!("/assets/styling/bookmark.css");
+
+/// One bookmark from the viewer's repo, with the subject it points at.
+#[derive(Clone, PartialEq)]
+pub struct SavedBookmark {
+    pub uri: AtUri<'static>,
+    pub subject: StrongRef<'static>,
+    pub note: Option<String>,
+    pub created_at: Datetime,
+}
+
+/// List the viewer's bookmark records, most recent first.
+///
+/// Reads from the viewer's own repo rather than an index: their PDS is
+/// the source of truth for what they saved, regardless of backend.
+pub async fn list_own_bookmarks(fetcher: &Fetcher) -> Vec<SavedBookmark> {
+    let Some(did) = fetcher.current_did().await else {
+        return Vec::new();
+    };
+
+    let request = ListRecords::new()
+        .repo(did)
+        .collection(Nsid::raw(Bookmark::NSID))
+        .limit(100)
+        .build();
+
+    let response = match fetcher.send(request).await {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::warn!("failed to list bookmarks: {e:?}");
+            return Vec::new();
+        }
+    };
+    let Ok(output) = response.into_output() else {
+        return Vec::new();
+    };
+
+    let mut bookmarks = Vec::new();
+    for record in output.records {
+        if let Ok(bookmark) = jacquard::from_data::<Bookmark>(&record.value) {
+            bookmarks.push(SavedBookmark {
+                uri: record.uri.into_static(),
+                subject: bookmark.subject.clone().into_static(),
+                note: bookmark.note.as_ref().map(|n| n.to_string()),
+                created_at: bookmark.created_at.clone(),
+            });
+        }
+    }
+    bookmarks.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    bookmarks
+}
+
+/// Save/unsave toggle for an entry or notebook, with an aggregate count
+/// when the index backend provides one.
+#[component]
+pub fn BookmarkButton(uri: AtUri<'static>, cid: Cid<'static>) -> Element {
+    let fetcher = use_context::<Fetcher>();
+    let auth_state = use_context::<Signal<AuthState>>();
+
+    // URI of the viewer's bookmark record for this subject, if any.
+    let res_uri = uri.clone();
+    let res_fetcher = fetcher.clone();
+    let mut bookmark_res = use_resource(use_reactive!(|res_uri| {
+        let fetcher = res_fetcher.clone();
+        async move {
+            list_own_bookmarks(&fetcher)
+                .await
+                .into_iter()
+                .find(|b| b.subject.uri == res_uri)
+                .map(|b| b.uri)
+        }
+    }));
+
+    #[cfg(feature = "use-index")]
+    let count_res = {
+        let count_uri = uri.clone();
+        let count_fetcher = fetcher.clone();
+        use_resource(use_reactive!(|count_uri| {
+            let fetcher = count_fetcher.clone();
+            async move {
+                fetcher
+                    .get_bookmarked_by(&count_uri)
+                    .await
+                    .ok()
+                    .map(|o| o.bookmarks.len())
+            }
+        }))
+    };
+
+    let mut busy = use_signal(|| false);
+    let is_authenticated = auth_state.read().is_authenticated();
+
+    let toggle_fetcher = fetcher.clone();
+    let toggle_uri = uri.clone();
+    let toggle = move |_| {
+        if *busy.peek() {
+            return;
+        }
+        busy.set(true);
+        let fetcher = toggle_fetcher.clone();
+        let subject_uri = toggle_uri.clone();
+        let subject_cid = cid.clone();
+        let existing = bookmark_res.peek().as_ref().and_then(|b| b.clone());
+        spawn(async move {
+            match existing {
+                Some(bookmark_uri) => {
+                    if let (Some(collection), Some(rkey), Some(did)) = (
+                        bookmark_uri.collection(),
+                        bookmark_uri.rkey(),
+                        fetcher.current_did().await,
+                    ) {
+                        let request = DeleteRecord::new()
+                            .repo(AtIdentifier::Did(did))
+                            .collection(collection.clone())
+                            .rkey(rkey.clone())
+                            .build();
+                        if let Err(e) = fetcher.send(request).await {
+                            tracing::warn!("failed to remove bookmark: {e:?}");
+                        }
+                    }
+                }
+                None => {
+                    let bookmark = Bookmark::new()
+                        .subject(StrongRef::new().uri(subject_uri).cid(subject_cid).build())
+                        .created_at(Datetime::now())
+                        .build();
+                    if let Err(e) = fetcher.create_record(bookmark, None).await {
+                        tracing::warn!("failed to save bookmark: {e:?}");
+                    }
+                }
+            }
+            bookmark_res.restart();
+            busy.set(false);
+        });
+    };
+
+    let bookmarked = matches!(&*bookmark_res.read(), Some(Some(_)));
+
+    #[cfg(feature = "use-index")]
+    let count: Option<usize> = count_res().flatten();
+    #[cfg(not(feature = "use-index"))]
+    let count: Option<usize> = None;
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: BOOKMARK_CSS }
+        div { class: "bookmark-control",
+            if is_authenticated {
+                button {
+                    class: if bookmarked { "bookmark-button bookmarked" } else { "bookmark-button" },
+                    title: if bookmarked { "Remove bookmark" } else { "Save for later" },
+                    disabled: busy(),
+                    onclick: toggle,
+                    if bookmarked { "★ Saved" } else { "☆ Save" }
+                }
+            }
+            if let Some(count) = count {
+                if count > 0 {
+                    span { class: "bookmark-count", "{count} saved" }
+                }
+            }
+        }
+    }
+}
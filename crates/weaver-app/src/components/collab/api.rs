@@ -57,6 +57,7 @@ pub async fn create_invite(
     invitee: Did<'static>,
     message: Option<String>,
 ) -> Result<AtUri<'static>, WeaverError> {
+    let resource_uri = resource.uri.clone();
     let mut invite_builder = Invite::new()
         .resource(resource)
         .invitee(invitee)
@@ -68,10 +69,14 @@ pub async fn create_invite(
 
     let invite = invite_builder.build();
 
-    let output = fetcher
-        .create_record(invite, None)
-        .await
-        .map_err(|e| WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Failed to create invite: {}", e).into()))?;
+    let output = fetcher.create_record(invite, None).await.map_err(|e| {
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Failed to create invite: {}", e).into(),
+        )
+    })?;
+
+    // The cached permission state for the resource is now stale.
+    weaver_common::permission_cache::invalidate(&resource_uri);
 
     Ok(output.uri.into_static())
 }
@@ -84,14 +89,18 @@ pub async fn accept_invite(
 ) -> Result<AtUri<'static>, WeaverError> {
     let accept = Accept::new()
         .invite(invite_ref)
-        .resource(resource_uri)
+        .resource(resource_uri.clone())
         .created_at(Datetime::now())
         .build();
 
-    let output = fetcher
-        .create_record(accept, None)
-        .await
-        .map_err(|e| WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Failed to accept invite: {}", e).into()))?;
+    let output = fetcher.create_record(accept, None).await.map_err(|e| {
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Failed to accept invite: {}", e).into(),
+        )
+    })?;
+
+    // Accepting activates the grant; drop the stale cached permissions.
+    weaver_common::permission_cache::invalidate(&resource_uri);
 
     Ok(output.uri.into_static())
 }
@@ -109,13 +118,16 @@ pub async fn fetch_sent_invites(fetcher: &Fetcher) -> Result<Vec<SentInvite>, We
         .limit(100)
         .build();
 
-    let response = fetcher
-        .send(request)
-        .await
-        .map_err(|e| WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Failed to list invites: {}", e).into()))?;
+    let response = fetcher.send(request).await.map_err(|e| {
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Failed to list invites: {}", e).into(),
+        )
+    })?;
 
     let output = response.into_output().map_err(|e| {
-        WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Failed to parse list response: {}", e).into())
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Failed to parse list response: {}", e).into(),
+        )
     })?;
 
     let mut invites = Vec::new();
@@ -175,8 +187,11 @@ pub async fn fetch_received_invites(fetcher: &Fetcher) -> Result<Vec<ReceivedInv
         .await
         .ok_or_else(|| WeaverError::InvalidNotebook("Not authenticated".into()))?;
 
-    let constellation_url = Url::parse(CONSTELLATION_URL)
-        .map_err(|e| WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Invalid constellation URL: {}", e).into()))?;
+    let constellation_url = Url::parse(CONSTELLATION_URL).map_err(|e| {
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Invalid constellation URL: {}", e).into(),
+        )
+    })?;
 
     // Query for sh.weaver.collab.invite records where .invitee = current user's DID
     let query = GetBacklinksQuery {
@@ -192,10 +207,17 @@ pub async fn fetch_received_invites(fetcher: &Fetcher) -> Result<Vec<ReceivedInv
         .xrpc(constellation_url)
         .send(&query)
         .await
-        .map_err(|e| WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Constellation query failed: {}", e).into()))?;
+        .map_err(|e| {
+            WeaverError::InvalidNotebook(
+                jacquard::smol_str::format_smolstr!("Constellation query failed: {}", e).into(),
+            )
+        })?;
 
     let output = response.into_output().map_err(|e| {
-        WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Failed to parse constellation response: {}", e).into())
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Failed to parse constellation response: {}", e)
+                .into(),
+        )
     })?;
 
     // For each RecordId, fetch the actual record from the inviter's PDS
@@ -258,8 +280,11 @@ pub async fn find_all_participants(
         return Ok(vec![]);
     };
 
-    let constellation_url = Url::parse(CONSTELLATION_URL)
-        .map_err(|e| WeaverError::InvalidNotebook(jacquard::smol_str::format_smolstr!("Invalid constellation URL: {}", e).into()))?;
+    let constellation_url = Url::parse(CONSTELLATION_URL).map_err(|e| {
+        WeaverError::InvalidNotebook(
+            jacquard::smol_str::format_smolstr!("Invalid constellation URL: {}", e).into(),
+        )
+    })?;
 
     // Query for all invite records that reference entries with this rkey
     // We search for invites where resource.uri contains the rkey
@@ -275,7 +300,12 @@ pub async fn find_all_participants(
     let mut participants: HashSet<Did<'static>> = HashSet::new();
 
     // First try with the exact URI
-    if let Ok(response) = fetcher.client.xrpc(constellation_url.clone()).send(&query).await {
+    if let Ok(response) = fetcher
+        .client
+        .xrpc(constellation_url.clone())
+        .send(&query)
+        .await
+    {
         if let Ok(output) = response.into_output() {
             for record_id in &output.records {
                 // The inviter (owner) is the DID that created the invite
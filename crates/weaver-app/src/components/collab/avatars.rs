@@ -9,8 +9,8 @@ use jacquard::types::ident::AtIdentifier;
 use jacquard::types::string::AtUri;
 use weaver_api::sh_weaver::actor::{ProfileDataView, ProfileDataViewInner};
 
-use super::api::find_all_participants;
 use super::CollaboratorsPanel;
+use super::api::find_all_participants;
 
 /// Props for the CollaboratorAvatars component.
 #[derive(Props, Clone, PartialEq)]
@@ -137,6 +137,57 @@ pub fn CollaboratorAvatars(props: CollaboratorAvatarsProps) -> Element {
     }
 }
 
+/// Live editing-session facepile driven by collab awareness data.
+///
+/// Unlike [`CollaboratorAvatars`], which lists everyone with an accepted
+/// invite, this shows only peers currently in the session and follows the
+/// presence snapshot as it changes: joins appear, leaves drop out, stale
+/// connections dim. Each face is ringed with the same per-peer colour as
+/// the remote cursor overlay so readers can match caret to person.
+#[component]
+pub fn PresenceFacepile(presence: Signal<weaver_common::transport::PresenceSnapshot>) -> Element {
+    use weaver_common::transport::ConnectionState;
+    use weaver_editor_browser::rgba_u32_to_css;
+
+    let snapshot = presence.read();
+    let active: Vec<_> = snapshot
+        .collaborators
+        .iter()
+        .filter(|c| c.connection_state != ConnectionState::Disconnected)
+        .cloned()
+        .collect();
+    if active.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div { class: "presence-facepile", aria_label: "Active editors",
+            for (i, collab) in active.iter().take(5).enumerate() {
+                {
+                    let color = rgba_u32_to_css(collab.color);
+                    let initials =
+                        get_initials(Some(collab.display_name.as_str()), &collab.display_name);
+                    rsx! {
+                        div {
+                            key: "{collab.node_id}",
+                            class: "presence-face",
+                            class: if collab.connection_state == ConnectionState::Stale { "stale" },
+                            style: "z-index: {5 - i}; --peer-color: {color};",
+                            title: "{collab.display_name}",
+                            "{initials}"
+                        }
+                    }
+                }
+            }
+            if active.len() > 5 {
+                div { class: "presence-face presence-overflow",
+                    "+{active.len() - 5}"
+                }
+            }
+        }
+    }
+}
+
 /// Get initials from display name or handle.
 fn get_initials(display_name: Option<&str>, handle: &str) -> String {
     if let Some(name) = display_name {
@@ -146,6 +197,11 @@ fn get_initials(display_name: Option<&str>, handle: &str) -> String {
             .collect::<String>()
             .to_uppercase()
     } else {
-        handle.chars().next().unwrap_or('?').to_uppercase().to_string()
+        handle
+            .chars()
+            .next()
+            .unwrap_or('?')
+            .to_uppercase()
+            .to_string()
     }
 }
@@ -5,12 +5,16 @@ mod avatars;
 mod collaborators;
 mod invite_dialog;
 mod invites_list;
+mod notifications;
 
 pub use api::{
-    accept_invite, create_invite, fetch_received_invites, fetch_sent_invites, AcceptedInvite,
-    ReceivedInvite, SentInvite,
+    AcceptedInvite, ReceivedInvite, SentInvite, accept_invite, create_invite,
+    fetch_received_invites, fetch_sent_invites,
 };
-pub use avatars::CollaboratorAvatars;
+pub use avatars::{CollaboratorAvatars, PresenceFacepile};
 pub use collaborators::CollaboratorsPanel;
 pub use invite_dialog::InviteDialog;
 pub use invites_list::InvitesList;
+pub use notifications::{
+    Notification, NotificationBell, NotificationKind, fetch_notifications, mark_all_seen,
+};
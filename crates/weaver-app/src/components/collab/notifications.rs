@@ -0,0 +1,325 @@
+//! Activity notifications aggregated from collaboration records.
+//!
+//! Pulls together three event sources: invites the current user received,
+//! acceptances of invites they sent, and new diffs on documents they
+//! collaborate on. Diff discovery goes through weaver-index when the
+//! `use-index` feature is enabled and falls back to Constellation
+//! backlinks otherwise, mirroring the editor's peer discovery split.
+//!
+//! Unread state lives entirely in localStorage: the navbar bell compares
+//! the aggregated ids against a persisted seen-set, and the notifications
+//! page marks everything seen once it has rendered.
+
+use std::collections::HashSet;
+
+use crate::Route;
+use crate::auth::AuthState;
+use crate::fetch::Fetcher;
+use dioxus::prelude::*;
+use jacquard::types::string::{AtUri, Datetime, Did};
+use weaver_common::WeaverError;
+
+use super::api::{fetch_received_invites, fetch_sent_invites};
+
+/// How often the navbar bell re-polls for activity.
+const POLL_INTERVAL_SECS: u64 = 60;
+
+/// Upper bound on how many collaborated resources we scan for diffs per
+/// poll. Each resource costs at least one network round-trip, so an
+/// unbounded scan would make the bell a background traffic generator.
+const MAX_DIFF_RESOURCES: usize = 20;
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+const SEEN_KEY: &str = "weaver_notifications_seen";
+
+/// What kind of collaboration activity a notification describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationKind {
+    /// Someone invited the current user to collaborate.
+    InviteReceived,
+    /// Someone accepted an invite the current user sent.
+    InviteAccepted,
+    /// A collaborator published a new diff on a shared document.
+    NewDiff,
+}
+
+impl NotificationKind {
+    /// Short label used in the notification card header.
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationKind::InviteReceived => "New invite",
+            NotificationKind::InviteAccepted => "Invite accepted",
+            NotificationKind::NewDiff => "New edits",
+        }
+    }
+}
+
+/// A single piece of collaboration activity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// Stable identifier used for unread tracking (the triggering
+    /// record's AT-URI, suffixed for derived events).
+    pub id: String,
+    pub kind: NotificationKind,
+    /// The other party: inviter, accepting invitee, or diff author.
+    pub actor: Did<'static>,
+    /// The document or notebook the activity concerns.
+    pub resource_uri: AtUri<'static>,
+    /// Invite message, when the source record carried one.
+    pub message: Option<String>,
+    /// When the triggering record was created. Constellation backlinks
+    /// carry no timestamps, so diff notifications discovered without the
+    /// index have none rather than a fabricated one.
+    pub created_at: Option<Datetime>,
+}
+
+/// Aggregate collaboration activity for the current user.
+///
+/// Returns notifications sorted newest-first; entries without a known
+/// timestamp sort after dated ones.
+pub async fn fetch_notifications(fetcher: &Fetcher) -> Result<Vec<Notification>, WeaverError> {
+    let my_did = fetcher
+        .current_did()
+        .await
+        .ok_or_else(|| WeaverError::InvalidNotebook("Not authenticated".into()))?;
+
+    let mut notifications = Vec::new();
+    // Resources the user collaborates on, from both directions of the
+    // invite graph: documents they were invited to and their own
+    // documents with accepted invites.
+    let mut shared_resources: Vec<AtUri<'static>> = Vec::new();
+    let mut seen_resources: HashSet<String> = HashSet::new();
+
+    let received = fetch_received_invites(fetcher).await?;
+    for invite in received {
+        if seen_resources.insert(invite.resource_uri.to_string()) {
+            shared_resources.push(invite.resource_uri.clone());
+        }
+        notifications.push(Notification {
+            id: invite.uri.to_string(),
+            kind: NotificationKind::InviteReceived,
+            actor: invite.inviter,
+            resource_uri: invite.resource_uri,
+            message: invite.message,
+            created_at: Some(invite.created_at),
+        });
+    }
+
+    let sent = fetch_sent_invites(fetcher).await?;
+    for invite in sent {
+        if !invite.accepted {
+            continue;
+        }
+        if seen_resources.insert(invite.resource_uri.to_string()) {
+            shared_resources.push(invite.resource_uri.clone());
+        }
+        notifications.push(Notification {
+            // The accept record lives in the invitee's repo and we only
+            // know *that* it exists, so key off the invite instead.
+            id: format!("{}#accepted", invite.uri),
+            kind: NotificationKind::InviteAccepted,
+            actor: invite.invitee,
+            resource_uri: invite.resource_uri,
+            message: invite.message,
+            // The invite's timestamp, not the acceptance's: fetching
+            // each accept record just for its date would double the
+            // request count.
+            created_at: Some(invite.created_at),
+        });
+    }
+
+    for resource_uri in shared_resources.iter().take(MAX_DIFF_RESOURCES) {
+        collect_diff_notifications(fetcher, &my_did, resource_uri, &mut notifications).await;
+    }
+
+    notifications.sort_by(|a, b| match (&a.created_at, &b.created_at) {
+        // RFC 3339 strings order chronologically when compared as text.
+        (Some(a), Some(b)) => b.to_string().cmp(&a.to_string()),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    });
+
+    Ok(notifications)
+}
+
+/// Collect diff notifications for one shared resource via weaver-index.
+#[cfg(feature = "use-index")]
+async fn collect_diff_notifications(
+    fetcher: &Fetcher,
+    my_did: &Did<'static>,
+    resource_uri: &AtUri<'static>,
+    notifications: &mut Vec<Notification>,
+) {
+    let history = match fetcher.get_edit_history(resource_uri).await {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::debug!("notifications: edit history failed for {resource_uri}: {e}");
+            return;
+        }
+    };
+
+    for diff in history.diffs {
+        // The user's own edits are not news to them.
+        if diff.author.did == *my_did {
+            continue;
+        }
+        notifications.push(Notification {
+            id: diff.uri.to_string(),
+            kind: NotificationKind::NewDiff,
+            actor: diff.author.did,
+            resource_uri: resource_uri.clone(),
+            message: None,
+            created_at: Some(diff.created_at),
+        });
+    }
+}
+
+/// Collect diff notifications for one shared resource via Constellation.
+#[cfg(not(feature = "use-index"))]
+async fn collect_diff_notifications(
+    fetcher: &Fetcher,
+    my_did: &Did<'static>,
+    resource_uri: &AtUri<'static>,
+    notifications: &mut Vec<Notification>,
+) {
+    let client = fetcher.get_client();
+
+    let roots = match weaver_editor_crdt::find_all_edit_roots(client.as_ref(), resource_uri, vec![])
+        .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            tracing::debug!("notifications: root discovery failed for {resource_uri}: {e}");
+            return;
+        }
+    };
+
+    for root in roots {
+        let root_uri_string = jacquard::smol_str::format_smolstr!(
+            "at://{}/{}/{}",
+            root.did,
+            root.collection,
+            root.rkey.as_ref()
+        );
+        let Ok(root_uri) = AtUri::new(&root_uri_string) else {
+            continue;
+        };
+
+        let diffs = match weaver_editor_crdt::find_diffs_for_root(client.as_ref(), &root_uri).await
+        {
+            Ok(d) => d,
+            Err(e) => {
+                tracing::debug!("notifications: diff discovery failed for {root_uri}: {e}");
+                continue;
+            }
+        };
+
+        for diff in diffs {
+            if diff.did == *my_did {
+                continue;
+            }
+            let diff_uri = jacquard::smol_str::format_smolstr!(
+                "at://{}/{}/{}",
+                diff.did,
+                diff.collection,
+                diff.rkey.as_ref()
+            );
+            notifications.push(Notification {
+                id: diff_uri.to_string(),
+                kind: NotificationKind::NewDiff,
+                actor: diff.did,
+                resource_uri: resource_uri.clone(),
+                message: None,
+                created_at: None,
+            });
+        }
+    }
+}
+
+/// Load the set of notification ids the user has already seen.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn load_seen_ids() -> Vec<String> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    LocalStorage::get(SEEN_KEY).unwrap_or_default()
+}
+
+/// Load the set of notification ids the user has already seen.
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn load_seen_ids() -> Vec<String> {
+    Vec::new()
+}
+
+/// Persist the current notification set as seen.
+///
+/// Storing exactly the ids that are currently visible is self-cleaning:
+/// ids for records that no longer surface simply fall out of the set.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn mark_all_seen(notifications: &[Notification]) {
+    use gloo_storage::{LocalStorage, Storage};
+
+    let ids: Vec<&str> = notifications.iter().map(|n| n.id.as_str()).collect();
+    if let Err(e) = LocalStorage::set(SEEN_KEY, &ids) {
+        tracing::warn!("Failed to persist seen notifications: {e}");
+    }
+}
+
+/// Persist the current notification set as seen.
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn mark_all_seen(_notifications: &[Notification]) {}
+
+/// Navbar bell linking to the notifications page, with an unread badge.
+///
+/// Renders nothing for signed-out visitors: every notification source
+/// requires an authenticated session.
+#[component]
+pub fn NotificationBell() -> Element {
+    let auth_state = use_context::<Signal<AuthState>>();
+    let fetcher = use_context::<Fetcher>();
+
+    let mut tick = use_signal(|| 0u32);
+    dioxus_sdk::time::use_interval(
+        std::time::Duration::from_secs(POLL_INTERVAL_SECS),
+        move |_| {
+            *tick.write() += 1;
+        },
+    );
+
+    let notifications = use_resource(move || {
+        let fetcher = fetcher.clone();
+        // Subscribing to the tick re-runs the fetch on every poll.
+        let _ = tick();
+        let authenticated = auth_state.read().is_authenticated();
+        async move {
+            if !authenticated {
+                return Vec::new();
+            }
+            fetch_notifications(&fetcher).await.unwrap_or_default()
+        }
+    });
+
+    // The unread count depends on localStorage, so it is computed in an
+    // effect to keep server HTML and first client paint identical.
+    let mut unread = use_signal(|| 0usize);
+    use_effect(move || {
+        let items = notifications().unwrap_or_default();
+        let seen = load_seen_ids();
+        unread.set(items.iter().filter(|n| !seen.contains(&n.id)).count());
+    });
+
+    if !auth_state.read().is_authenticated() {
+        return rsx! {};
+    }
+
+    rsx! {
+        Link {
+            to: Route::NotificationsPage {},
+            class: "notification-bell",
+            span { class: "notification-bell-glyph", "🔔" }
+            if unread() > 0 {
+                span { class: "notification-badge", "{unread}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,470 @@
+//! Global keyboard shortcuts and the Cmd/Ctrl-K command palette.
+//!
+//! The palette lists navigation targets (notebooks, drafts, recent entries)
+//! and actions (new draft, toggle theme), filtered with a small fuzzy
+//! matcher. Views can contribute their own commands through
+//! [`use_commands`]; everything lives in a shared registry provided by
+//! [`use_command_registry_provider`] from the navbar layout.
+
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use jacquard::smol_str::SmolStr;
+use jacquard::types::string::AtIdentifier;
+
+use crate::Route;
+use crate::auth::AuthState;
+
+const COMMAND_PALETTE_CSS: Asset = asset!("/assets/styling/command-palette.css");
+
+/// One palette entry: a label to match against and an action to run.
+#[derive(Clone)]
+pub struct Command {
+    /// Stable identifier; re-registering an id replaces the earlier entry.
+    pub id: SmolStr,
+    /// Human-readable label shown in the list.
+    pub label: String,
+    /// Section name shown next to the label ("Navigation", "Actions", ...).
+    pub section: &'static str,
+    /// Extra matcher terms beyond the label.
+    pub keywords: Vec<String>,
+    /// Runs when the command is picked; the palette closes first.
+    pub action: Rc<dyn Fn()>,
+}
+
+impl PartialEq for Command {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.label == other.label
+    }
+}
+
+/// Provide the shared registry for view-contributed commands.
+///
+/// Called once from the navbar layout so every page under it shares one
+/// palette.
+pub fn use_command_registry_provider() -> Signal<Vec<Command>> {
+    use_context_provider(|| Signal::new(Vec::<Command>::new()))
+}
+
+/// Contribute commands for as long as the calling component is mounted.
+///
+/// `make` runs in a reactive scope: commands are rebuilt whenever a signal
+/// it reads changes, so views can derive them from loaded data. Commands
+/// are keyed by id and removed again on unmount.
+pub fn use_commands(mut make: impl FnMut() -> Vec<Command> + 'static) {
+    // The registry only exists under the main-domain navbar; pages rendered
+    // in the subdomain shell simply don't get a palette.
+    let registry = try_use_context::<Signal<Vec<Command>>>();
+    let mut ids = use_signal(Vec::<SmolStr>::new);
+
+    use_effect(move || {
+        let Some(mut registry) = registry else { return };
+        let commands = make();
+        let old = ids.peek().clone();
+        ids.set(commands.iter().map(|c| c.id.clone()).collect());
+        let mut reg = registry.write();
+        reg.retain(|c| !old.contains(&c.id));
+        reg.extend(commands);
+    });
+    use_drop(move || {
+        let Some(mut registry) = registry else { return };
+        let old = ids.peek().clone();
+        registry.write().retain(|c| !old.contains(&c.id));
+    });
+}
+
+/// Score a case-insensitive subsequence match of `query` against `target`.
+///
+/// Returns `None` when the query is not a subsequence of the target.
+/// Higher is better: consecutive matches and matches at word boundaries
+/// score extra, and longer targets pay a small penalty so "nd" prefers
+/// "New draft" over an incidental scatter in a long title.
+pub(crate) fn fuzzy_score(query: &str, target: &str) -> Option<i32> {
+    if query.trim().is_empty() {
+        return Some(0);
+    }
+    let target: Vec<char> = target.to_lowercase().chars().collect();
+    let mut score = 0i32;
+    let mut cursor = 0usize;
+    let mut prev: Option<usize> = None;
+
+    for qc in query.to_lowercase().chars() {
+        if qc == ' ' {
+            continue;
+        }
+        let mut found = None;
+        while cursor < target.len() {
+            let i = cursor;
+            cursor += 1;
+            if target[i] == qc {
+                found = Some(i);
+                break;
+            }
+        }
+        let i = found?;
+        score += 1;
+        if prev == Some(i.wrapping_sub(1)) {
+            // Consecutive run: favor contiguous substrings.
+            score += 2;
+        }
+        if i == 0 || matches!(target[i - 1], ' ' | '-' | '_' | '/' | ':') {
+            // Word-boundary hit: favor initials and prefix matches.
+            score += 3;
+        }
+        prev = Some(i);
+    }
+    // Among equal matches, shorter targets rank first.
+    Some(score - target.len() as i32 / 8)
+}
+
+/// The Cmd/Ctrl-K command palette, mounted once in the navbar layout.
+#[component]
+pub fn CommandPalette() -> Element {
+    let registry = use_context::<Signal<Vec<Command>>>();
+    let auth_state = use_context::<Signal<AuthState>>();
+    let fetcher = use_context::<crate::fetch::Fetcher>();
+    let nav = use_navigator();
+    let theme = crate::theme::use_theme();
+
+    let mut open = use_signal(|| false);
+    let mut query = use_signal(String::new);
+    let mut selected = use_signal(|| 0usize);
+
+    // Notebook titles for the signed-in user, fetched once on first open so
+    // the palette doesn't cost a request on pages where it's never used.
+    let mut notebook_titles = use_signal(Vec::<SmolStr>::new);
+    let mut notebooks_requested = use_signal(|| false);
+    use_effect(move || {
+        if !open() || *notebooks_requested.peek() {
+            return;
+        }
+        let Some(did) = auth_state.peek().did.clone() else {
+            return;
+        };
+        notebooks_requested.set(true);
+        let fetcher = fetcher.clone();
+        spawn(async move {
+            if let Ok(books) = fetcher
+                .fetch_notebooks_for_did(&AtIdentifier::Did(did))
+                .await
+            {
+                notebook_titles.set(
+                    books
+                        .iter()
+                        .filter_map(|b| b.0.title.as_ref().map(|t| SmolStr::new(t.as_ref())))
+                        .collect(),
+                );
+            }
+        });
+    });
+
+    // Global Cmd/Ctrl-K listener on the window; dioxus key events only fire
+    // on focused elements, so the shortcut needs a real DOM listener.
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen::closure::Closure;
+
+        let mut key_closure = use_signal(|| None::<Closure<dyn FnMut(web_sys::KeyboardEvent)>>);
+        use_effect(move || {
+            if key_closure.peek().is_some() {
+                return;
+            }
+            let Some(window) = web_sys::window() else {
+                return;
+            };
+            let closure = Closure::wrap(Box::new(move |evt: web_sys::KeyboardEvent| {
+                if (evt.meta_key() || evt.ctrl_key()) && evt.key() == "k" {
+                    evt.prevent_default();
+                    let mut open = open;
+                    let now_open = !*open.peek();
+                    open.set(now_open);
+                    if now_open {
+                        let mut query = query;
+                        let mut selected = selected;
+                        query.set(String::new());
+                        selected.set(0);
+                    }
+                }
+            }) as Box<dyn FnMut(web_sys::KeyboardEvent)>);
+            let _ = window
+                .add_event_listener_with_callback("keydown", closure.as_ref().unchecked_ref());
+            key_closure.set(Some(closure));
+        });
+        use_drop(move || {
+            if let Some(closure) = key_closure.take() {
+                if let Some(window) = web_sys::window() {
+                    let _ = window.remove_event_listener_with_callback(
+                        "keydown",
+                        closure.as_ref().unchecked_ref(),
+                    );
+                }
+            }
+        });
+    }
+
+    // Built-in commands plus everything views registered, rebuilt when auth
+    // state or the registry changes.
+    let commands = use_memo(move || {
+        let mut commands: Vec<Command> = Vec::new();
+
+        commands.push(Command {
+            id: "nav-home".into(),
+            label: "Go home".into(),
+            section: "Navigation",
+            keywords: vec!["home".into()],
+            action: Rc::new(move || {
+                nav.push(Route::Home {});
+            }),
+        });
+
+        if let Some(did) = auth_state.read().did.clone() {
+            let ident = AtIdentifier::Did(did);
+            let nav_targets: [(&str, &str, Route); 6] = [
+                (
+                    "nav-profile",
+                    "Go to my profile",
+                    Route::RepositoryIndex {
+                        ident: ident.clone(),
+                    },
+                ),
+                (
+                    "nav-drafts",
+                    "Go to my drafts",
+                    Route::DraftsList {
+                        ident: ident.clone(),
+                    },
+                ),
+                (
+                    "nav-notifications",
+                    "Go to notifications",
+                    Route::NotificationsPage {},
+                ),
+                (
+                    "nav-settings",
+                    "Edit profile settings",
+                    Route::ProfileSettingsPage {},
+                ),
+                ("nav-saved", "Go to saved items", Route::SavedItemsPage {}),
+                (
+                    "nav-search",
+                    "Search my entries",
+                    Route::SearchPage {
+                        ident: ident.clone(),
+                        q: None,
+                    },
+                ),
+            ];
+            for (id, label, route) in nav_targets {
+                commands.push(Command {
+                    id: id.into(),
+                    label: label.into(),
+                    section: "Navigation",
+                    keywords: Vec::new(),
+                    action: Rc::new(move || {
+                        nav.push(route.clone());
+                    }),
+                });
+            }
+
+            commands.push(Command {
+                id: "action-new-draft".into(),
+                label: "New draft".into(),
+                section: "Actions",
+                keywords: vec!["create".into(), "write".into()],
+                action: {
+                    let ident = ident.clone();
+                    Rc::new(move || {
+                        nav.push(Route::NewDraft {
+                            ident: ident.clone(),
+                            notebook: None,
+                        });
+                    })
+                },
+            });
+
+            for title in notebook_titles.read().iter() {
+                commands.push(Command {
+                    id: SmolStr::new(format!("nav-notebook-{title}")),
+                    label: format!("Open notebook: {title}"),
+                    section: "Notebooks",
+                    keywords: vec!["book".into()],
+                    action: {
+                        let ident = ident.clone();
+                        let title = title.clone();
+                        Rc::new(move || {
+                            nav.push(Route::NotebookIndex {
+                                ident: ident.clone(),
+                                book_title: title.clone(),
+                            });
+                        })
+                    },
+                });
+            }
+        }
+
+        commands.push(Command {
+            id: "action-toggle-theme".into(),
+            label: "Toggle theme".into(),
+            section: "Actions",
+            keywords: vec!["dark".into(), "light".into()],
+            action: Rc::new(move || {
+                let mut theme = theme;
+                let next = theme.peek().next();
+                theme.set(next);
+            }),
+        });
+
+        // Recently read entries from the continue-reading store (browser
+        // only; the list is empty on the server).
+        for pos in crate::data::list_reading_positions().into_iter().take(5) {
+            let Ok(ident) = AtIdentifier::new_owned(pos.ident.clone()) else {
+                continue;
+            };
+            commands.push(Command {
+                id: SmolStr::new(format!("nav-recent-{}", pos.cid)),
+                label: format!("Resume reading: {}", pos.title),
+                section: "Recent",
+                keywords: vec![pos.book_title.clone()],
+                action: Rc::new(move || {
+                    nav.push(Route::EntryPage {
+                        ident: ident.clone(),
+                        book_title: SmolStr::new(&pos.book_title),
+                        title: SmolStr::new(&pos.entry_path),
+                    });
+                }),
+            });
+        }
+
+        // View-contributed commands last; ties in the matcher preserve this
+        // order.
+        commands.extend(registry.read().iter().cloned());
+        commands
+    });
+
+    // Fuzzy-filter against the label and keywords, best score first.
+    let filtered = use_memo(move || {
+        let q = query.read();
+        let mut scored: Vec<(i32, Command)> = commands
+            .read()
+            .iter()
+            .filter_map(|c| {
+                let best = std::iter::once(c.label.as_str())
+                    .chain(c.keywords.iter().map(|k| k.as_str()))
+                    .filter_map(|t| fuzzy_score(&q, t))
+                    .max()?;
+                Some((best, c.clone()))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0));
+        scored.into_iter().map(|(_, c)| c).collect::<Vec<_>>()
+    });
+
+    let run_command = move |command: Command| {
+        let mut open = open;
+        open.set(false);
+        (command.action)();
+    };
+
+    if !open() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: COMMAND_PALETTE_CSS }
+        div {
+            class: "command-palette-overlay",
+            onclick: move |_| open.set(false),
+            div {
+                class: "command-palette",
+                role: "dialog",
+                aria_label: "Command palette",
+                onclick: move |evt| evt.stop_propagation(),
+                input {
+                    class: "command-palette-input",
+                    r#type: "text",
+                    placeholder: "Type a command or search...",
+                    autofocus: true,
+                    value: "{query}",
+                    oninput: move |evt| {
+                        query.set(evt.value());
+                        selected.set(0);
+                    },
+                    onkeydown: move |evt| {
+                        let count = filtered.read().len();
+                        match evt.key() {
+                            Key::Escape => open.set(false),
+                            Key::ArrowDown if count > 0 => {
+                                evt.prevent_default();
+                                selected.set((selected() + 1) % count);
+                            }
+                            Key::ArrowUp if count > 0 => {
+                                evt.prevent_default();
+                                selected.set((selected() + count - 1) % count);
+                            }
+                            Key::Enter => {
+                                if let Some(command) = filtered.read().get(selected()).cloned() {
+                                    run_command(command);
+                                }
+                            }
+                            _ => {}
+                        }
+                    },
+                }
+                ul { class: "command-palette-list",
+                    if filtered.read().is_empty() {
+                        li { class: "command-palette-empty", "No matching commands" }
+                    }
+                    for (index, command) in filtered().into_iter().enumerate() {
+                        li {
+                            key: "{command.id}",
+                            class: if index == selected() {
+                                "command-palette-item command-palette-item-selected"
+                            } else {
+                                "command-palette-item"
+                            },
+                            onclick: move |_| run_command(command.clone()),
+                            onmouseenter: move |_| selected.set(index),
+                            span { class: "command-palette-label", "{command.label}" }
+                            span { class: "command-palette-section", "{command.section}" }
+                        }
+                    }
+                }
+                div { class: "command-palette-hint",
+                    kbd { "↑↓" }
+                    " navigate  "
+                    kbd { "↵" }
+                    " run  "
+                    kbd { "esc" }
+                    " close"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_score;
+
+    #[test]
+    fn fuzzy_score_rejects_non_subsequences() {
+        assert_eq!(fuzzy_score("draft", "Toggle theme"), None);
+        assert!(fuzzy_score("draft", "New draft").is_some());
+    }
+
+    #[test]
+    fn fuzzy_score_is_case_insensitive() {
+        assert_eq!(
+            fuzzy_score("HOME", "Go home"),
+            fuzzy_score("home", "Go home")
+        );
+    }
+
+    #[test]
+    fn fuzzy_score_prefers_word_starts_and_runs() {
+        let initials = fuzzy_score("nd", "New draft").unwrap();
+        let scattered = fuzzy_score("nd", "notifications and things").unwrap();
+        assert!(initials > scattered);
+    }
+}
@@ -0,0 +1,324 @@
+//! Comments under published entries.
+//!
+//! Comments are discovered through constellation backlinks rather than an
+//! index: weaver comment records reference the entry via `doc.value.entry.uri`
+//! and Bluesky posts via `embed.record.uri`. Weaver comments thread through
+//! their `thread` strong ref; discovered posts render flat.
+
+use dioxus::prelude::*;
+use jacquard::IntoStatic;
+use jacquard::client::AgentSessionExt;
+use jacquard::types::string::{AtUri, Cid, Datetime, Did};
+use weaver_api::app_bsky::feed::post::Post;
+use weaver_api::com_atproto::repo::strong_ref::StrongRef;
+use weaver_api::sh_weaver::edit::comment::Comment;
+use weaver_api::sh_weaver::edit::{DocRef, DocRefValue, EntryRef};
+use weaver_common::WeaverExt;
+
+use crate::auth::AuthState;
+use crate::components::button::{Button, ButtonVariant};
+use crate::data::use_get_handle;
+use crate::fetch::Fetcher;
+
+const COMMENTS_CSS: Asset = asset!("/assets/styling/comments.css");
+
+/// Where a discovered comment came from.
+#[derive(Clone, Copy, PartialEq)]
+pub enum CommentSource {
+    Weaver,
+    Bluesky,
+}
+
+/// A comment-like record referencing the entry.
+#[derive(Clone, PartialEq)]
+pub struct EntryComment {
+    pub uri: AtUri<'static>,
+    pub cid: Option<Cid<'static>>,
+    pub did: Did<'static>,
+    pub body: String,
+    pub created_at: Option<Datetime>,
+    /// Root comment this one replies to, if any.
+    pub thread_root: Option<AtUri<'static>>,
+    pub source: CommentSource,
+}
+
+/// Discover and hydrate comments for one entry.
+///
+/// Records that fail to fetch (deleted, private PDS) are skipped rather
+/// than failing the whole section.
+async fn load_comments(fetcher: &Fetcher, entry_uri: &AtUri<'static>) -> Vec<EntryComment> {
+    let client = fetcher.get_client();
+    let ids = match client.find_entry_comments(entry_uri).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!("comment discovery failed: {e}");
+            return Vec::new();
+        }
+    };
+
+    let mut comments = Vec::new();
+    for id in ids.into_iter().take(50) {
+        let uri_str = format!(
+            "at://{}/{}/{}",
+            id.did.as_str(),
+            id.collection.as_str(),
+            id.rkey.0.as_str()
+        );
+        match id.collection.as_str() {
+            "sh.weaver.edit.comment" => {
+                let Ok(uri) = Comment::uri(&uri_str) else {
+                    continue;
+                };
+                let Ok(record) = fetcher.fetch_record(&uri).await else {
+                    continue;
+                };
+                // Editor margin comments carry anchors; only entry-level
+                // comments (empty anchor) belong under the page.
+                if !record.value.anchor_start.is_empty() {
+                    continue;
+                }
+                comments.push(EntryComment {
+                    uri: record.uri.into_static(),
+                    cid: record.cid.map(IntoStatic::into_static),
+                    did: id.did.clone().into_static(),
+                    body: record.value.body.as_ref().to_string(),
+                    created_at: record.value.created_at.clone(),
+                    thread_root: record
+                        .value
+                        .thread
+                        .as_ref()
+                        .map(|t| t.uri.clone().into_static()),
+                    source: CommentSource::Weaver,
+                });
+            }
+            "app.bsky.feed.post" => {
+                let Ok(uri) = Post::uri(&uri_str) else {
+                    continue;
+                };
+                let Ok(record) = fetcher.fetch_record(&uri).await else {
+                    continue;
+                };
+                comments.push(EntryComment {
+                    uri: record.uri.into_static(),
+                    cid: record.cid.map(IntoStatic::into_static),
+                    did: id.did.clone().into_static(),
+                    body: record.value.text.as_ref().to_string(),
+                    created_at: Some(record.value.created_at.clone()),
+                    thread_root: None,
+                    source: CommentSource::Bluesky,
+                });
+            }
+            other => {
+                tracing::debug!("ignoring backlink from unexpected collection {other}");
+            }
+        }
+    }
+    comments
+}
+
+/// Comments section rendered under the entry content.
+#[component]
+pub fn CommentsSection(entry_uri: AtUri<'static>, entry_cid: Cid<'static>) -> Element {
+    let fetcher = use_context::<Fetcher>();
+    let auth_state = use_context::<Signal<AuthState>>();
+
+    let res_uri = entry_uri.clone();
+    let mut comments_res = use_resource(use_reactive!(|res_uri| {
+        let fetcher = fetcher.clone();
+        async move { load_comments(&fetcher, &res_uri).await }
+    }));
+
+    let mut body = use_signal(String::new);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    // Root comment being replied to, if the composer is a reply.
+    let mut reply_to = use_signal(|| None::<EntryComment>);
+
+    let is_authenticated = auth_state.read().is_authenticated();
+    let fetcher_submit = use_context::<Fetcher>();
+    let submit = move |_| {
+        let text = body.peek().trim().to_string();
+        if text.is_empty() || *busy.peek() {
+            return;
+        }
+        busy.set(true);
+        error.set(None);
+        let fetcher = fetcher_submit.clone();
+        let entry_uri = entry_uri.clone();
+        let entry_cid = entry_cid.clone();
+        let parent = reply_to.peek().clone();
+        spawn(async move {
+            let entry_ref = EntryRef::new()
+                .entry(
+                    StrongRef::new()
+                        .uri(entry_uri.clone())
+                        .cid(entry_cid.clone())
+                        .build(),
+                )
+                .build();
+            // Replies point their thread ref at the root comment so the
+            // whole thread stays one level deep.
+            let thread = parent.as_ref().and_then(|p| {
+                let cid = p.cid.clone()?;
+                Some(
+                    StrongRef::new()
+                        .uri(p.thread_root.clone().unwrap_or_else(|| p.uri.clone()))
+                        .cid(cid)
+                        .build(),
+                )
+            });
+            // An empty anchor marks this as an entry-level comment rather
+            // than an editor margin comment.
+            let comment = Comment::new()
+                .anchor_start(Vec::<u8>::new())
+                .body(jacquard::CowStr::from(text))
+                .doc(
+                    DocRef::new()
+                        .value(DocRefValue::EntryRef(Box::new(entry_ref)))
+                        .build(),
+                )
+                .maybe_thread(thread)
+                .created_at(Datetime::now())
+                .build();
+
+            match fetcher.create_record(comment, None).await {
+                Ok(_) => {
+                    body.set(String::new());
+                    reply_to.set(None);
+                    comments_res.restart();
+                }
+                Err(e) => {
+                    error.set(Some(format!("failed to post comment: {e}")));
+                }
+            }
+            busy.set(false);
+        });
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: COMMENTS_CSS }
+        section { class: "entry-comments", aria_label: "Comments",
+            match &*comments_res.read() {
+                Some(comments) => {
+                    // Roots in chronological order, replies grouped under
+                    // the thread root they reference.
+                    let mut roots: Vec<EntryComment> = comments
+                        .iter()
+                        .filter(|c| c.thread_root.is_none())
+                        .cloned()
+                        .collect();
+                    roots.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+                    let replies_for = |root: &EntryComment| {
+                        let mut replies: Vec<EntryComment> = comments
+                            .iter()
+                            .filter(|c| c.thread_root.as_ref() == Some(&root.uri))
+                            .cloned()
+                            .collect();
+                        replies.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+                        replies
+                    };
+                    rsx! {
+                        h2 { class: "entry-comments-heading", "Comments ({comments.len()})" }
+                        if comments.is_empty() {
+                            p { class: "entry-comments-empty", "No comments yet." }
+                        }
+                        ul { class: "entry-comments-list",
+                            for root in roots {
+                                li { key: "{root.uri}",
+                                    CommentItem {
+                                        comment: root.clone(),
+                                        can_reply: is_authenticated,
+                                        on_reply: move |c| reply_to.set(Some(c)),
+                                    }
+                                    ul { class: "entry-comments-replies",
+                                        for reply in replies_for(&root) {
+                                            li { key: "{reply.uri}",
+                                                CommentItem {
+                                                    comment: reply,
+                                                    can_reply: false,
+                                                    on_reply: move |_| {},
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                None => rsx! {
+                    p { class: "entry-comments-empty", "Loading comments..." }
+                },
+            }
+
+            if is_authenticated {
+                div { class: "entry-comments-composer",
+                    if reply_to().is_some() {
+                        div { class: "entry-comments-replying",
+                            "Replying to a comment"
+                            button {
+                                class: "entry-comments-cancel-reply",
+                                onclick: move |_| reply_to.set(None),
+                                "✕"
+                            }
+                        }
+                    }
+                    textarea {
+                        class: "entry-comments-input",
+                        placeholder: "Write a comment...",
+                        value: "{body}",
+                        oninput: move |evt| body.set(evt.value()),
+                    }
+                    if let Some(err) = error() {
+                        p { class: "entry-comments-error", "{err}" }
+                    }
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        disabled: busy(),
+                        onclick: submit,
+                        if busy() { "Posting..." } else { "Post comment" }
+                    }
+                }
+            } else {
+                p { class: "entry-comments-signin", "Sign in to join the conversation." }
+            }
+        }
+    }
+}
+
+/// One rendered comment with author handle and timestamp.
+#[component]
+fn CommentItem(
+    comment: EntryComment,
+    can_reply: bool,
+    on_reply: EventHandler<EntryComment>,
+) -> Element {
+    let handle = use_get_handle(comment.did.clone());
+    let formatted_date = comment
+        .created_at
+        .as_ref()
+        .map(|d| d.as_ref().format("%B %d, %Y").to_string());
+    let comment_for_reply = comment.clone();
+
+    rsx! {
+        article { class: "entry-comment",
+            header { class: "entry-comment-meta",
+                span { class: "entry-comment-author", "@{handle()}" }
+                if let Some(date) = formatted_date {
+                    time { class: "entry-comment-date", "{date}" }
+                }
+                if comment.source == CommentSource::Bluesky {
+                    span { class: "entry-comment-source", "via Bluesky" }
+                }
+            }
+            p { class: "entry-comment-body", "{comment.body}" }
+            if can_reply {
+                button {
+                    class: "entry-comment-reply",
+                    onclick: move |_| on_reply.call(comment_for_reply.clone()),
+                    "Reply"
+                }
+            }
+        }
+    }
+}
@@ -12,6 +12,15 @@ const DIALOG_CSS: Asset = asset!("./dialog.css");
 pub fn DialogRoot(props: DialogRootProps) -> Element {
     let is_open = props.open.read().unwrap_or(false);
 
+    // Move keyboard focus into the dialog when it opens so keyboard
+    // users land inside it rather than on the page behind the overlay.
+    let open_signal = props.open;
+    use_effect(move || {
+        if open_signal.read().unwrap_or(false) {
+            crate::components::focus::focus_first_in(".dialog-backdrop-inner");
+        }
+    });
+
     let overlay_style = if is_open {
         "position: fixed; inset: 0; z-index: 1000; background: rgba(0,0,0,0.3); display: flex; align-items: center; justify-content: center;"
     } else {
@@ -28,6 +37,18 @@ pub fn DialogRoot(props: DialogRootProps) -> Element {
                     on_change.call(false);
                 }
             },
+            onkeydown: {
+                let on_change = props.on_open_change.clone();
+                move |evt: KeyboardEvent| {
+                    use dioxus::prelude::keyboard_types::Key;
+                    if evt.key() == Key::Escape {
+                        evt.prevent_default();
+                        on_change.call(false);
+                    } else {
+                        crate::components::focus::trap_tab(".dialog-backdrop-inner", &evt);
+                    }
+                }
+            },
             dialog::DialogRoot {
                 class: "dialog-backdrop-inner",
                 id: props.id,
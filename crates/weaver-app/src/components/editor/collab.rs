@@ -26,7 +26,7 @@ use weaver_common::transport::PresenceSnapshot;
 #[cfg(target_arch = "wasm32")]
 use weaver_editor_crdt::{
     CoordinatorState, PEER_DISCOVERY_INTERVAL_MS, SESSION_REFRESH_INTERVAL_MS, SESSION_TTL_MINUTES,
-    compute_collab_topic,
+    compute_collab_topic, now_ms,
 };
 
 /// Props for the CollabCoordinator component.
@@ -200,6 +200,21 @@ pub fn CollabCoordinator(props: CollabCoordinatorProps) -> Element {
                             let resource_uri = resource_uri.clone();
 
                             spawn(async move {
+                                // Client-side quota check - a crash/remount
+                                // loop must not flood the PDS with session
+                                // records faster than TTL cleanup removes
+                                // them. Concurrency (0 here) is backstopped
+                                // by the index at peer discovery time.
+                                let quota_check = super::COLLAB_QUOTA
+                                    .lock()
+                                    .map(|mut q| q.try_create_session(now_ms(), 0));
+                                if let Ok(Err(e)) = quota_check {
+                                    let err = format_smolstr!("Session quota exceeded: {e}");
+                                    debug_state.with_mut(|ds| ds.last_error = Some(err.clone()));
+                                    state.set(CoordinatorState::Error(err));
+                                    return;
+                                }
+
                                 // Parse resource URI to get StrongRef
                                 let uri = match AtUri::new(&resource_uri) {
                                     Ok(u) => u.into_static(),
@@ -425,6 +440,18 @@ pub fn CollabCoordinator(props: CollabCoordinatorProps) -> Element {
                             }
                         }
 
+                        WorkerOutput::PeerRevoked { did } => {
+                            tracing::warn!(
+                                %did,
+                                "CollabCoordinator: collaborator authorization revoked"
+                            );
+                            debug_state.with_mut(|ds| {
+                                if !ds.revoked_peers.contains(&did) {
+                                    ds.revoked_peers.push(did);
+                                }
+                            });
+                        }
+
                         WorkerOutput::Error { message } => {
                             tracing::error!("CollabCoordinator: worker error: {message}");
                             debug_state.with_mut(|ds| ds.last_error = Some(message.clone()));
@@ -440,41 +467,47 @@ pub fn CollabCoordinator(props: CollabCoordinatorProps) -> Element {
             tracing::info!("CollabCoordinator: spawned worker");
         });
 
-        // Forward cursor updates to worker - memo re-runs when cursor/selection signals change
+        // Forward awareness updates to worker - memo re-runs when cursor,
+        // selection, or viewport signals change. Throttling happens in the
+        // worker, so sending on every change here is fine.
         let cursor_signal = props.document.cursor;
         let selection_signal = props.document.selection;
+        let viewport_signal = props.document.viewport;
 
-        let _cursor_broadcaster = use_memo(move || {
+        let _awareness_broadcaster = use_memo(move || {
             let cursor = cursor_signal.read();
             let selection = *selection_signal.read();
+            let viewport = *viewport_signal.read();
             let position = cursor.offset;
             let sel = selection.map(|s| (s.anchor, s.head));
 
             tracing::debug!(
                 position,
                 ?sel,
-                "CollabCoordinator: cursor changed, broadcasting"
+                ?viewport,
+                "CollabCoordinator: awareness changed, broadcasting"
             );
 
             spawn(async move {
                 if let Some(ref mut s) = *worker_sink.write() {
                     tracing::debug!(
                         position,
-                        "CollabCoordinator: sending BroadcastCursor to worker"
+                        "CollabCoordinator: sending BroadcastAwareness to worker"
                     );
                     if let Err(e) = s
-                        .send(WorkerInput::BroadcastCursor {
+                        .send(WorkerInput::BroadcastAwareness {
                             position,
                             selection: sel,
+                            viewport,
                         })
                         .await
                     {
-                        tracing::warn!("Failed to send BroadcastCursor to worker: {e}");
+                        tracing::warn!("Failed to send BroadcastAwareness to worker: {e}");
                     }
                 } else {
                     tracing::debug!(
                         position,
-                        "CollabCoordinator: worker sink not ready, skipping cursor broadcast"
+                        "CollabCoordinator: worker sink not ready, skipping awareness broadcast"
                     );
                 }
             });
@@ -545,8 +578,9 @@ pub fn CollabCoordinator(props: CollabCoordinatorProps) -> Element {
             },
         );
 
-        // Periodic session refresh
+        // Periodic session refresh + invite re-validation
         let fetcher_for_refresh = fetcher.clone();
+        let resource_uri_for_refresh = resource_uri.clone();
         dioxus_sdk::time::use_interval(
             std::time::Duration::from_millis(SESSION_REFRESH_INTERVAL_MS as u64),
             move |_| {
@@ -554,6 +588,7 @@ pub fn CollabCoordinator(props: CollabCoordinatorProps) -> Element {
 
                 if let Some(ref uri) = *session_uri.peek() {
                     let uri = uri.clone();
+                    let fetcher = fetcher.clone();
                     spawn(async move {
                         match fetcher
                             .refresh_collab_session(&uri, SESSION_TTL_MINUTES)
@@ -568,6 +603,41 @@ pub fn CollabCoordinator(props: CollabCoordinatorProps) -> Element {
                         }
                     });
                 }
+
+                // Re-validate invite+accept pairs so collaborators whose
+                // invites were revoked server-side stop being admitted
+                // mid-session. The worker drops ops from lapsed DIDs.
+                let resource_uri = resource_uri_for_refresh.clone();
+                spawn(async move {
+                    let uri = match AtUri::new(&resource_uri) {
+                        Ok(u) => u,
+                        Err(_) => return,
+                    };
+
+                    let mut dids: Vec<SmolStr> =
+                        match fetcher.find_collaborators_for_resource(&uri).await {
+                            Ok(dids) => dids.into_iter().map(|did| did.as_ref().into()).collect(),
+                            Err(e) => {
+                                // Keep the last validated set rather than
+                                // revoking everyone on a transient
+                                // Constellation error.
+                                tracing::debug!("Collaborator re-validation failed: {e}");
+                                return;
+                            }
+                        };
+
+                    // The owner never has an invite record; authorize them
+                    // via the resource URI authority.
+                    if let jacquard::types::ident::AtIdentifier::Did(did) = uri.authority() {
+                        dids.push(did.as_ref().into());
+                    }
+
+                    if let Some(ref mut s) = *worker_sink.write() {
+                        if let Err(e) = s.send(WorkerInput::SetAuthorizedPeers { dids }).await {
+                            tracing::warn!("SetAuthorizedPeers send failed: {e}");
+                        }
+                    }
+                });
             },
         );
 
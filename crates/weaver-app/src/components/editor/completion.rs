@@ -0,0 +1,133 @@
+//! Wikilink and mention autocomplete popover.
+//!
+//! Trigger detection lives in [`weaver_editor_core::completion`]; this
+//! module turns an active trigger into concrete suggestions and markup.
+//! Wikilink candidates come from the notebook's [`EntryIndex`], mention
+//! candidates from resolving the typed handle through the agent. Like the
+//! slash menu, the popover itself is stateless — the editor component
+//! owns selection and dismissal and drives both from its keydown handler,
+//! and a pick replaces the whole `trigger..cursor` span in one
+//! [`EditorAction::Insert`] so undo restores the typed text in a step.
+
+use super::actions::{EditorAction, Range, execute_action};
+use super::document::{CursorState, SignalEditorDocument};
+use dioxus::prelude::*;
+use weaver_common::EntryIndex;
+
+/// Suggestions shown at once; the index can hold thousands of entries.
+pub const MAX_COMPLETION_ITEMS: usize = 8;
+
+/// One pickable suggestion.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletionItem {
+    /// Text shown in the popover row.
+    pub label: String,
+    /// Markdown inserted in place of the trigger and query.
+    pub insert: String,
+}
+
+/// Wikilink suggestions for `query`, already wrapped as `[[Title]]`.
+pub fn entry_completions(index: &EntryIndex, query: &str) -> Vec<CompletionItem> {
+    index
+        .search(query, MAX_COMPLETION_ITEMS)
+        .into_iter()
+        .map(|(title, _path)| CompletionItem {
+            insert: format!("[[{}]]", title),
+            label: title,
+        })
+        .collect()
+}
+
+/// Replaces `start..cursor` (the trigger plus typed query) with the
+/// item's markdown and parks the caret after it.
+pub fn apply_completion(doc: &mut SignalEditorDocument, start: usize, item: &CompletionItem) {
+    let cursor = doc.cursor.read().offset;
+    let range = Range::new(start, cursor.max(start));
+    execute_action(
+        doc,
+        &EditorAction::Insert {
+            text: item.insert.clone(),
+            range,
+        },
+    );
+    doc.cursor.set(CursorState {
+        offset: start + item.insert.chars().count(),
+        ..Default::default()
+    });
+}
+
+/// The popover itself; selection and dismissal are driven by the editor.
+///
+/// `position` is (left, top) in pixels relative to the editor wrapper,
+/// from `get_cursor_rect_relative`; `None` falls back to the stylesheet
+/// default while the DOM position is not yet measurable.
+#[component]
+pub fn CompletionPopover(
+    items: Vec<CompletionItem>,
+    selected: usize,
+    position: Option<(f64, f64)>,
+    on_pick: EventHandler<usize>,
+) -> Element {
+    if items.is_empty() {
+        return rsx! {};
+    }
+    let selected = selected.min(items.len() - 1);
+    let style = position
+        .map(|(x, y)| format!("left: {}px; top: {}px;", x, y))
+        .unwrap_or_default();
+    rsx! {
+        div {
+            class: "completion-popover",
+            role: "listbox",
+            aria_label: "Autocomplete",
+            style: "{style}",
+            for (i, item) in items.iter().enumerate() {
+                button {
+                    key: "{item.insert}",
+                    class: "completion-item",
+                    class: if i == selected { "selected" },
+                    role: "option",
+                    aria_selected: if i == selected { "true" } else { "false" },
+                    // Mousedown instead of click so the editor keeps focus
+                    // (a click would blur the contenteditable first).
+                    onmousedown: move |evt| {
+                        evt.prevent_default();
+                        on_pick.call(i);
+                    },
+                    "{item.label}"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_completions_wrap_titles() {
+        let mut index = EntryIndex::new();
+        index.add_entry("My Note", "my_note", "/a/nb/my_note");
+        index.add_entry("Other", "other", "/a/nb/other");
+
+        let items = entry_completions(&index, "my");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "My Note");
+        assert_eq!(items[0].insert, "[[My Note]]");
+    }
+
+    #[test]
+    fn test_entry_completions_honour_limit() {
+        let mut index = EntryIndex::new();
+        for i in 0..20 {
+            let title = format!("Note {}", i);
+            let path = format!("note_{}", i);
+            index.add_entry(&title, &path, format!("/a/nb/{}", path));
+        }
+        assert_eq!(
+            entry_completions(&index, "note").len(),
+            MAX_COMPLETION_ITEMS
+        );
+    }
+}
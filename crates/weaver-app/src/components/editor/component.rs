@@ -3,24 +3,37 @@
 use super::actions::{
     EditorAction, KeydownResult, Range, execute_action, handle_keydown_with_bindings,
 };
+use super::completion::{CompletionItem, CompletionPopover, apply_completion, entry_completions};
 use super::document::SignalEditorDocument;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use super::dom_sync::update_paragraph_dom;
+use super::image_upload::{
+    PastedUpload, PastedUploadStatus, dismiss_paste_upload, handle_image_paste, retry_paste_upload,
+};
+use super::outline::{
+    OutlineSidebar, current_section, extract_outline, jump_to_heading, move_section,
+};
 use super::publish::PublishButton;
 use super::remote_cursors::RemoteCursors;
+use super::slash_menu::{SlashMenu, SlashMenuState, filter_commands, insert_slash_command};
 use super::storage;
-use super::sync::{LoadEditorResult, SyncStatus, load_editor_state};
+use super::sync::{
+    ConflictResolution, DraftConflictDialog, LoadEditorResult, SyncStatus,
+    apply_conflict_resolution, load_editor_state,
+};
 use super::toolbar::EditorToolbar;
 use crate::auth::AuthState;
-use crate::components::collab::CollaboratorAvatars;
+use crate::components::collab::{CollaboratorAvatars, PresenceFacepile};
 use crate::components::editor::collab::CollabCoordinator;
 use crate::components::editor::{LoadedDocState, ReportButton};
+use crate::components::login::LoginModal;
 use crate::fetch::Fetcher;
 use dioxus::prelude::*;
 use jacquard::IntoStatic;
 use jacquard::smol_str::SmolStr;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use jacquard::types::blob::BlobRef;
+use jacquard::types::string::Handle;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use weaver_editor_browser::{BeforeInputContext, BeforeInputResult, update_syntax_visibility};
 use weaver_editor_browser::{
@@ -33,6 +46,7 @@ use weaver_editor_core::InputType;
 use weaver_editor_core::ParagraphRender;
 use weaver_editor_core::SnapDirection;
 use weaver_editor_core::apply_formatting;
+use weaver_editor_core::{CompletionTrigger, completion_context};
 
 /// Wrapper component that handles loading document state before rendering the editor.
 ///
@@ -46,16 +60,23 @@ use weaver_editor_core::apply_formatting;
 /// - `entry_uri`: Optional AT-URI of an existing entry to edit
 /// - `target_notebook`: Optional notebook title to add the entry to when publishing
 /// - `entry_index`: Optional index of entries for wikilink validation
+/// - `demo`: Run fully locally without PDS loading/sync (unauthenticated demo)
 #[component]
 pub fn MarkdownEditor(
     initial_content: Option<String>,
     entry_uri: Option<String>,
     target_notebook: Option<SmolStr>,
     entry_index: Option<weaver_common::EntryIndex>,
+    #[props(default = false)] demo: bool,
 ) -> Element {
     let fetcher = use_context::<Fetcher>();
 
     let draft_key = use_hook(|| {
+        if demo {
+            // Stable key so demo edits survive reloads instead of piling up
+            // a fresh localStorage entry per visit.
+            return "demo".to_string();
+        }
         entry_uri.clone().unwrap_or_else(|| {
             format!(
                 "new:{}",
@@ -72,6 +93,10 @@ pub fn MarkdownEditor(
     let draft_key_for_render = draft_key.clone();
     let target_notebook_for_render = target_notebook.clone();
 
+    // Holds the post-resolution state once the user has answered a draft
+    // conflict dialog; None while no conflict or not yet resolved.
+    let mut resolved_state = use_signal(|| None::<LoadedDocState>);
+
     let load_resource = use_resource(move || {
         let fetcher = fetcher.clone();
         let draft_key = draft_key.clone();
@@ -80,6 +105,14 @@ pub fn MarkdownEditor(
         let target_notebook = target_notebook.clone();
 
         async move {
+            if demo {
+                // Demo bootstrap is independent of AuthState and the
+                // fetcher: localStorage plus sample content only.
+                return LoadEditorResult::Loaded(super::sync::load_demo_state(
+                    &draft_key,
+                    initial_content.as_deref(),
+                ));
+            }
             load_editor_state(
                 &fetcher,
                 &draft_key,
@@ -93,13 +126,38 @@ pub fn MarkdownEditor(
 
     match &*load_resource.read() {
         Some(LoadEditorResult::Loaded(state)) => {
+            // A diverged draft pauses loading here: the user picks a side
+            // before the editor ever shows the merged text.
+            let resolved = resolved_state.read().clone();
+            if let (Some(conflict), None) = (state.conflict.clone(), &resolved) {
+                let state_for_resolve = state.clone();
+                let draft_key_for_resolve = draft_key_for_render.clone();
+                let conflict_for_resolve = conflict.clone();
+                return rsx! {
+                    DraftConflictDialog {
+                        conflict,
+                        on_resolve: move |resolution: ConflictResolution| {
+                            let mut state = state_for_resolve.clone();
+                            apply_conflict_resolution(&state.doc, &conflict_for_resolve, resolution);
+                            storage::record_conflict_resolution(
+                                &draft_key_for_resolve,
+                                resolution.as_str(),
+                            );
+                            state.conflict = None;
+                            resolved_state.set(Some(state));
+                        },
+                    }
+                };
+            }
+            let loaded_state = resolved.unwrap_or_else(|| state.clone());
             rsx! {
                 MarkdownEditorInner {
                     key: "{draft_key_for_render}",
                     draft_key: draft_key_for_render.clone(),
-                    loaded_state: state.clone(),
+                    loaded_state,
                     target_notebook: target_notebook_for_render.clone(),
                     entry_index: entry_index.clone(),
+                    demo,
                 }
             }
         }
@@ -136,11 +194,19 @@ fn MarkdownEditorInner(
     target_notebook: Option<SmolStr>,
     /// Optional entry index for wikilink validation in the editor
     entry_index: Option<weaver_common::EntryIndex>,
+    /// Demo mode: no PDS sync, sign-in CTA instead of publish controls
+    #[props(default = false)]
+    demo: bool,
 ) -> Element {
     // Context for authenticated API calls
     let fetcher = use_context::<Fetcher>();
     let auth_state = use_context::<Signal<AuthState>>();
 
+    // Demo mode shows a sign-in path where publish/sync controls would be;
+    // once the visitor authenticates, the normal controls take over.
+    let mut show_login_modal = use_signal(|| false);
+    let show_signin_cta = demo && !auth_state.read().is_authenticated();
+
     #[allow(unused_mut)]
     let mut document = use_hook(|| {
         let mut doc = SignalEditorDocument::from_loaded_state(loaded_state.clone());
@@ -180,6 +246,10 @@ fn MarkdownEditorInner(
     // Use pre-resolved content from loaded state (avoids embed pop-in)
     let resolved_content = use_signal(|| loaded_state.resolved_content.clone());
 
+    // Clipboard image uploads in flight or failed, shown in a status
+    // strip with retry.
+    let paste_uploads = use_signal(Vec::<PastedUpload>::new);
+
     // Presence snapshot for remote collaborators (updated by collab coordinator)
     let presence = use_signal(weaver_common::transport::PresenceSnapshot::default);
 
@@ -225,6 +295,15 @@ fn MarkdownEditorInner(
         paras
     });
 
+    // Embed worker handle and failure state (uri -> (reason, attempts)).
+    // Declared outside the block below so the editor's click handler can
+    // reach them for manual retries.
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    let mut embed_host: Signal<Option<weaver_embed_worker::EmbedWorkerHost>> = use_signal(|| None);
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    let embed_errors: Signal<std::collections::HashMap<String, (String, u32)>> =
+        use_signal(std::collections::HashMap::new);
+
     // Background fetch for AT embeds via worker
     #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
     {
@@ -232,7 +311,6 @@ fn MarkdownEditorInner(
         use weaver_embed_worker::{EmbedWorkerHost, EmbedWorkerOutput};
 
         let resolved_content_for_fetch = resolved_content;
-        let mut embed_host: Signal<Option<EmbedWorkerHost>> = use_signal(|| None);
 
         // Spawn embed worker on mount
         let doc_for_embeds = document.clone();
@@ -246,7 +324,9 @@ fn MarkdownEditorInner(
                 } => {
                     if !results.is_empty() {
                         let mut rc = resolved_content_for_fetch.write_unchecked();
+                        let mut errs = embed_errors.write_unchecked();
                         for (uri_str, html) in results {
+                            errs.remove(&uri_str);
                             if let Ok(at_uri) = jacquard::types::string::AtUri::new_owned(uri_str) {
                                 rc.add_embed(at_uri, html, None);
                             }
@@ -259,6 +339,39 @@ fn MarkdownEditorInner(
                     }
                     for (uri, err) in errors {
                         tracing::warn!("embed worker failed to fetch {}: {}", uri, err);
+
+                        let attempts = {
+                            let mut errs = embed_errors.write_unchecked();
+                            let entry = errs.entry(uri.clone()).or_insert((String::new(), 0));
+                            entry.0 = err.clone();
+                            entry.1 += 1;
+                            entry.1
+                        };
+                        let will_retry =
+                            is_transient_embed_error(&err) && attempts < EMBED_RETRY_MAX_ATTEMPTS;
+
+                        // Replace the silent "Loading embed..." fallback with
+                        // a visible card carrying the reason and a retry button.
+                        if let Ok(at_uri) = jacquard::types::string::AtUri::new_owned(uri.clone()) {
+                            resolved_content_for_fetch.write_unchecked().add_embed(
+                                at_uri,
+                                embed_error_card(&uri, &err, will_retry),
+                                None,
+                            );
+                        }
+
+                        // Transient failures retry automatically with
+                        // exponential backoff: 2s, 4s, 8s.
+                        if will_retry {
+                            let uri_for_retry = uri.clone();
+                            let delay_ms = EMBED_RETRY_BASE_MS << (attempts - 1);
+                            spawn(async move {
+                                gloo_timers::future::TimeoutFuture::new(delay_ms).await;
+                                if let Some(ref host) = *embed_host.peek() {
+                                    host.fetch_embeds(vec![uri_for_retry]);
+                                }
+                            });
+                        }
                     }
                 }
                 EmbedWorkerOutput::CacheCleared => {
@@ -306,6 +419,82 @@ fn MarkdownEditorInner(
 
     let mut new_tag = use_signal(String::new);
 
+    // Slash command insert menu; Some while a `/` menu is open.
+    let mut slash_menu = use_signal(|| None::<SlashMenuState>);
+
+    // Text typed after the opening `/`, read straight from the document so
+    // backspaces and IME edits stay in sync without tracking keystrokes.
+    // None means the menu should close: the cursor moved before the
+    // anchor, the slash was deleted, or the line ended.
+    let doc_for_slash = document.clone();
+    let slash_query = use_memo(move || {
+        let state = slash_menu()?;
+        let _ = doc_for_slash.content_changed.read();
+        let cursor = doc_for_slash.cursor.read().offset;
+        let content = doc_for_slash.content();
+        let mut chars = content.chars().skip(state.anchor);
+        if cursor == state.anchor {
+            // The `/` keydown opened the menu before beforeinput inserted
+            // the character; only close if the cursor genuinely sits
+            // before an already-inserted slash.
+            return if chars.next() == Some('/') {
+                None
+            } else {
+                Some(String::new())
+            };
+        }
+        if cursor < state.anchor || chars.next() != Some('/') {
+            return None;
+        }
+        let query: String = chars.take(cursor - state.anchor - 1).collect();
+        if query.contains('\n') {
+            return None;
+        }
+        Some(query)
+    });
+
+    // Close the menu as soon as the query becomes invalid.
+    use_effect(move || {
+        if slash_menu.read().is_some() && slash_query().is_none() {
+            slash_menu.set(None);
+        }
+    });
+
+    // Split view: live published-style preview beside the editing surface.
+    let mut split_preview = use_signal(|| false);
+
+    // Preview blocks for the split pane. Rendering is debounced so fast
+    // typing costs one preview pass per pause instead of one full re-parse
+    // per keystroke; the editing surface itself stays keystroke-fresh.
+    let doc_for_preview = document.clone();
+    let mut preview_blocks = use_signal(Vec::<(SmolStr, String)>::new);
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    let mut preview_debounce: Signal<Option<gloo_timers::callback::Timeout>> = use_signal(|| None);
+    use_effect(move || {
+        if !split_preview() {
+            return;
+        }
+        let content = doc_for_preview.content();
+        let paras = paragraphs();
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            // An empty pane renders immediately (toggle-on, first load);
+            // only subsequent edits wait out the debounce window.
+            if preview_blocks.peek().is_empty() {
+                preview_blocks.set(render_preview_blocks(&content, &paras));
+                return;
+            }
+            let timeout = gloo_timers::callback::Timeout::new(PREVIEW_DEBOUNCE_MS, move || {
+                preview_blocks.set(render_preview_blocks(&content, &paras));
+            });
+            // Replacing the handle drops (and thereby cancels) any render
+            // still pending from the previous edit.
+            preview_debounce.set(Some(timeout));
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        preview_blocks.set(render_preview_blocks(&content, &paras));
+    });
+
     #[allow(unused)]
     let offset_map = use_memo(move || {
         paragraphs()
@@ -319,6 +508,107 @@ fn MarkdownEditorInner(
             .flat_map(|p| p.syntax_spans.iter().cloned())
             .collect::<Vec<_>>()
     });
+
+    // Wikilink/mention autocomplete. Unlike the slash menu there is no
+    // explicit open state: the popover is open exactly while the text
+    // around the cursor contains an active trigger, so deleting the
+    // `[[`/`@` or moving away closes it for free.
+    let doc_for_completion = document.clone();
+    let completion_ctx = use_memo(move || {
+        let _ = doc_for_completion.content_changed.read();
+        let cursor = doc_for_completion.cursor.read().offset;
+        completion_context(&doc_for_completion.content(), cursor)
+    });
+
+    // Escape hides the popover for the current trigger site only; typing
+    // a fresh `[[` or `@` elsewhere brings it back.
+    let mut completion_dismissed = use_signal(|| None::<usize>);
+    let mut completion_selected = use_signal(|| 0usize);
+    use_effect(move || {
+        let start = completion_ctx().map(|ctx| ctx.start);
+        let dismissed = *completion_dismissed.peek();
+        if dismissed.is_some() && dismissed != start {
+            completion_dismissed.set(None);
+        }
+        // Any edit or cursor move refilters the list, so selection
+        // restarts at the top; arrow keys write `completion_selected`
+        // directly without touching the document.
+        if *completion_selected.peek() != 0 {
+            completion_selected.set(0);
+        }
+    });
+
+    // Mention lookups resolve the typed handle through the agent, after a
+    // pause so each keystroke does not hit the resolver. A handle needs a
+    // dot, so shorter queries skip the round-trip entirely.
+    let fetcher_for_completion = fetcher.clone();
+    let mention_item = use_resource(move || {
+        let fetcher = fetcher_for_completion.clone();
+        async move {
+            let ctx = completion_ctx()?;
+            if ctx.trigger != CompletionTrigger::Mention || !ctx.query.contains('.') {
+                return None;
+            }
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            gloo_timers::future::TimeoutFuture::new(300).await;
+            let handle = Handle::new(&ctx.query).ok()?;
+            let did = fetcher.resolve_handle(&handle).await.ok()?;
+            Some(CompletionItem {
+                label: format!("@{}", ctx.query),
+                insert: format!("[@{}](at://{})", ctx.query, did),
+            })
+        }
+    });
+
+    let entry_index_for_completion = entry_index.clone();
+    let completion_items = use_memo(move || {
+        let Some(ctx) = completion_ctx() else {
+            return Vec::new();
+        };
+        match ctx.trigger {
+            CompletionTrigger::Wikilink => match entry_index_for_completion.as_ref() {
+                Some(index) => entry_completions(index, &ctx.query),
+                None => Vec::new(),
+            },
+            CompletionTrigger::Mention => mention_item().flatten().into_iter().collect(),
+        }
+    });
+
+    // Anchor the popover under the trigger character; None (no DOM yet,
+    // server render) falls back to the stylesheet position.
+    let completion_pos: Memo<Option<(f64, f64)>> = use_memo(move || {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            let ctx = completion_ctx()?;
+            let map = offset_map();
+            let rect = weaver_editor_browser::get_cursor_rect_relative(
+                ctx.start,
+                &map,
+                "markdown-editor",
+            )?;
+            Some((rect.x, rect.y + rect.height))
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        None
+    });
+    // Outline sidebar: headings derived from the rendered paragraphs, and
+    // the section the viewport currently sits in for highlighting.
+    let mut outline_open = use_signal(|| false);
+    let doc_for_outline = document.clone();
+    let outline = use_memo(move || {
+        let _ = doc_for_outline.content_changed.read();
+        extract_outline(&doc_for_outline.content(), &paragraphs())
+    });
+    let doc_for_outline_scroll = document.clone();
+    let outline_current = use_memo(move || {
+        let start = doc_for_outline_scroll
+            .viewport
+            .read()
+            .map(|(start, _)| start)
+            .unwrap_or(0);
+        current_section(&outline(), start)
+    });
+
     #[allow(unused_mut)]
     let mut cached_paragraphs = use_signal(|| Vec::<ParagraphRender>::new());
 
@@ -356,6 +646,12 @@ fn MarkdownEditorInner(
 
         // Update syntax visibility after DOM changes
         update_syntax_visibility(cursor_offset, selection.as_ref(), &spans, &new_paras);
+
+        // Re-tag locked template paragraphs after DOM changes
+        weaver_editor_browser::update_locked_regions(
+            &new_paras,
+            &doc_for_dom.locked_regions.read(),
+        );
     });
 
     // Track last saved frontiers to detect changes (peek-only, no subscriptions)
@@ -634,6 +930,10 @@ fn MarkdownEditorInner(
                         }
 
                         div { class: "meta-actions",
+                            // Peers in the live editing session right now
+                            // (renders nothing while alone).
+                            PresenceFacepile { presence }
+
                             // Show collaborator avatars when editing an existing entry
                             if let Some(entry_ref) = document.entry_ref() {
                                 {
@@ -648,70 +948,108 @@ fn MarkdownEditorInner(
                                 }
                             }
 
-                            {
-                                // Enable collaborative sync for any published entry (both owners and collaborators)
-                                let is_published = document.entry_ref().is_some();
-
-                                // Refresh callback: fetch and merge collaborator changes (incremental)
-                                let on_refresh = if is_published {
-                                    let fetcher_for_refresh = fetcher.clone();
-                                    let doc_for_refresh = document.clone();
-                                    let entry_uri = document.entry_ref().map(|r| r.uri.clone().into_static());
-
-                                    Some(EventHandler::new(move |_| {
-                                        let fetcher = fetcher_for_refresh.clone();
-                                        let mut doc = doc_for_refresh.clone();
-                                        let uri = entry_uri.clone();
-
-                                        spawn(async move {
-                                            if let Some(uri) = uri {
-                                                // Get last seen diffs for incremental sync
-                                                let last_seen = doc.last_seen_diffs.read().clone();
-
-                                                match super::sync::load_all_edit_states_from_pds(&fetcher, &uri, &last_seen).await {
-                                                    Ok(Some(pds_state)) => {
-                                                        if let Err(e) = doc.import_updates(&pds_state.root_snapshot) {
-                                                            tracing::error!("Failed to import collaborator updates: {:?}", e);
-                                                        } else {
-                                                            tracing::info!("Successfully merged collaborator updates");
-                                                            // Update the last seen diffs for next incremental sync
-                                                            *doc.last_seen_diffs.write() = pds_state.last_seen_diffs;
+                            button {
+                                class: "split-toggle",
+                                class: if split_preview() { "active" },
+                                aria_pressed: "{split_preview}",
+                                title: "Side-by-side preview",
+                                onclick: move |_| {
+                                    let enabled = split_preview();
+                                    split_preview.set(!enabled);
+                                },
+                                "Split"
+                            }
+
+                            button {
+                                class: "outline-toggle",
+                                class: if outline_open() { "active" },
+                                aria_pressed: "{outline_open}",
+                                title: "Document outline",
+                                onclick: move |_| {
+                                    let enabled = outline_open();
+                                    outline_open.set(!enabled);
+                                },
+                                "Outline"
+                            }
+
+                            if show_signin_cta {
+                                button {
+                                    class: "publish-button",
+                                    onclick: move |_| show_login_modal.set(true),
+                                    "Sign in to publish"
+                                }
+                                LoginModal {
+                                    open: show_login_modal,
+                                    cached_route: String::from("/demo"),
+                                }
+                            } else {
+                                {
+                                    // Enable collaborative sync for any published entry (both owners and collaborators)
+                                    let is_published = document.entry_ref().is_some();
+
+                                    // Refresh callback: fetch and merge collaborator changes (incremental)
+                                    let on_refresh = if is_published {
+                                        let fetcher_for_refresh = fetcher.clone();
+                                        let doc_for_refresh = document.clone();
+                                        let entry_uri = document.entry_ref().map(|r| r.uri.clone().into_static());
+
+                                        Some(EventHandler::new(move |_| {
+                                            let fetcher = fetcher_for_refresh.clone();
+                                            let mut doc = doc_for_refresh.clone();
+                                            let uri = entry_uri.clone();
+
+                                            spawn(async move {
+                                                if let Some(uri) = uri {
+                                                    // Get last seen diffs for incremental sync
+                                                    let last_seen = doc.last_seen_diffs.read().clone();
+
+                                                    match super::sync::load_all_edit_states_from_pds(&fetcher, &uri, &last_seen).await {
+                                                        Ok(Some(pds_state)) => {
+                                                            if let Err(e) = doc.import_updates(&pds_state.root_snapshot) {
+                                                                tracing::error!("Failed to import collaborator updates: {:?}", e);
+                                                            } else {
+                                                                tracing::info!("Successfully merged collaborator updates");
+                                                                // Update the last seen diffs for next incremental sync
+                                                                *doc.last_seen_diffs.write() = pds_state.last_seen_diffs;
+                                                            }
+                                                        }
+                                                        Ok(None) => {
+                                                            tracing::debug!("No collaborator updates found");
+                                                        }
+                                                        Err(e) => {
+                                                            tracing::error!("Failed to fetch collaborator updates: {}", e);
                                                         }
-                                                    }
-                                                    Ok(None) => {
-                                                        tracing::debug!("No collaborator updates found");
-                                                    }
-                                                    Err(e) => {
-                                                        tracing::error!("Failed to fetch collaborator updates: {}", e);
                                                     }
                                                 }
-                                            }
-                                        });
-                                    }))
-                                } else {
-                                    None
-                                };
+                                            });
+                                        }))
+                                    } else {
+                                        None
+                                    };
 
-                                rsx! {
-                                    SyncStatus {
-                                        document: document.clone(),
-                                        draft_key: draft_key.to_string(),
-                                        on_refresh,
-                                        is_collaborative: is_published,
+                                    rsx! {
+                                        SyncStatus {
+                                            document: document.clone(),
+                                            draft_key: draft_key.to_string(),
+                                            on_refresh,
+                                            is_collaborative: is_published,
+                                        }
                                     }
                                 }
-                            }
 
-                            PublishButton {
-                                document: document.clone(),
-                                draft_key: draft_key.to_string(),
-                                target_notebook: target_notebook.as_ref().map(|s| s.to_string()),
+                                PublishButton {
+                                    document: document.clone(),
+                                    draft_key: draft_key.to_string(),
+                                    target_notebook: target_notebook.as_ref().map(|s| s.to_string()),
+                                }
                             }
                         }
                     }
 
                     // Editor content
-                    div { class: "editor-content-wrapper",
+                    div {
+                        class: "editor-content-wrapper",
+                        class: if split_preview() { "split" },
                         // Remote collaborator cursors overlay
                         RemoteCursors { presence, document: document.clone(), render_cache }
                         div {
@@ -722,6 +1060,27 @@ fn MarkdownEditorInner(
                             aria_multiline: "true",
                             aria_label: "Document content",
 
+                            onscroll: {
+                                let doc = document.clone();
+                                move |_| {
+                                    let paras = cached_paragraphs();
+                                    let range = weaver_editor_browser::visible_offset_range(&paras);
+                                    // Only write on change so awareness broadcasts
+                                    // don't fire for every scroll event.
+                                    let mut viewport = doc.viewport;
+                                    if *viewport.peek() != range {
+                                        viewport.set(range);
+                                    }
+                                    if split_preview() {
+                                        weaver_editor_browser::sync_preview_scroll(
+                                            editor_id,
+                                            "editor-preview-pane",
+                                            &paras,
+                                        );
+                                    }
+                                }
+                            },
+
                             onkeydown: {
                             let mut doc = document.clone();
                             let keybindings = super::actions::default_keybindings(platform::platform());
@@ -769,6 +1128,105 @@ fn MarkdownEditorInner(
                                     }
                                 }
 
+                                // Slash insert menu: while open, navigation keys drive the
+                                // menu instead of the document.
+                                if let Some(state) = *slash_menu.peek() {
+                                    let query = slash_query.peek().clone().unwrap_or_default();
+                                    let filtered = filter_commands(&query);
+                                    match evt.key() {
+                                        Key::ArrowDown if !filtered.is_empty() => {
+                                            evt.prevent_default();
+                                            slash_menu.set(Some(SlashMenuState {
+                                                selected: (state.selected + 1) % filtered.len(),
+                                                ..state
+                                            }));
+                                            return;
+                                        }
+                                        Key::ArrowUp if !filtered.is_empty() => {
+                                            evt.prevent_default();
+                                            slash_menu.set(Some(SlashMenuState {
+                                                selected: (state.selected + filtered.len() - 1)
+                                                    % filtered.len(),
+                                                ..state
+                                            }));
+                                            return;
+                                        }
+                                        Key::Enter | Key::Tab if !filtered.is_empty() => {
+                                            evt.prevent_default();
+                                            let cmd = filtered[state.selected.min(filtered.len() - 1)];
+                                            insert_slash_command(&mut doc, state.anchor, cmd);
+                                            slash_menu.set(None);
+                                            return;
+                                        }
+                                        Key::Escape => {
+                                            evt.prevent_default();
+                                            slash_menu.set(None);
+                                            return;
+                                        }
+                                        _ => {}
+                                    }
+                                }
+
+                                // Autocomplete popover: while suggestions are visible,
+                                // navigation keys drive the popover instead of the
+                                // document.
+                                if completion_dismissed.peek().is_none() {
+                                    let items = completion_items.peek().clone();
+                                    if let (Some(ctx), false) =
+                                        (completion_ctx.peek().clone(), items.is_empty())
+                                    {
+                                        match evt.key() {
+                                            Key::ArrowDown => {
+                                                evt.prevent_default();
+                                                completion_selected.set(
+                                                    (*completion_selected.peek() + 1) % items.len(),
+                                                );
+                                                return;
+                                            }
+                                            Key::ArrowUp => {
+                                                evt.prevent_default();
+                                                completion_selected.set(
+                                                    (*completion_selected.peek() + items.len() - 1)
+                                                        % items.len(),
+                                                );
+                                                return;
+                                            }
+                                            Key::Enter | Key::Tab => {
+                                                evt.prevent_default();
+                                                let item = &items[(*completion_selected.peek())
+                                                    .min(items.len() - 1)];
+                                                apply_completion(&mut doc, ctx.start, item);
+                                                return;
+                                            }
+                                            Key::Escape => {
+                                                evt.prevent_default();
+                                                completion_dismissed.set(Some(ctx.start));
+                                                return;
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                }
+
+                                // `/` at the start of a line opens the insert menu; the
+                                // character itself still inserts through beforeinput.
+                                if !has_modifier && slash_menu.peek().is_none() {
+                                    if let Key::Character(ref c) = evt.key() {
+                                        if c.as_str() == "/" && doc.selection.read().is_none() {
+                                            let cursor = doc.cursor.read().offset;
+                                            let at_line_start = cursor == 0
+                                                || doc.content().chars().nth(cursor - 1)
+                                                    == Some('\n');
+                                            if at_line_start {
+                                                slash_menu.set(Some(SlashMenuState {
+                                                    anchor: cursor,
+                                                    selected: 0,
+                                                }));
+                                            }
+                                        }
+                                    }
+                                }
+
                                 // Try keybindings first (for shortcuts like Ctrl+B, Ctrl+Z, etc.)
                                 let combo = super::actions::keycombo_from_dioxus_event(&evt.data());
                                 let cursor_offset = doc.cursor.read().offset;
@@ -878,6 +1336,18 @@ fn MarkdownEditorInner(
 
                                     let web_evt = evt.as_web_event();
                                     if let Some(target) = web_evt.target() {
+                                        // Retry button inside a failed-embed card.
+                                        if let Some(uri) =
+                                            weaver_editor_browser::get_embed_retry_uri(&target)
+                                        {
+                                            // Manual retry resets the backoff schedule.
+                                            embed_errors.write_unchecked().remove(&uri);
+                                            if let Some(ref host) = *embed_host.peek() {
+                                                host.fetch_embeds(vec![uri]);
+                                            }
+                                            return;
+                                        }
+
                                         if weaver_editor_browser::handle_math_click(
                                             &target, &mut doc, &spans, &paras, &map,
                                         ) {
@@ -918,7 +1388,20 @@ fn MarkdownEditorInner(
 
                         onpaste: {
                             let mut doc = document.clone();
+                            let fetcher = fetcher.clone();
                             move |evt| {
+                                // Clipboard images go through the upload flow;
+                                // text falls through to the normal paste path.
+                                if handle_image_paste(
+                                    &evt,
+                                    &mut doc,
+                                    &mut image_resolver,
+                                    &auth_state,
+                                    &fetcher,
+                                    paste_uploads,
+                                ) {
+                                    return;
+                                }
                                 handle_paste(evt, &mut doc);
                             }
                         },
@@ -970,6 +1453,133 @@ fn MarkdownEditorInner(
                             }
                         },
                         }
+
+                        // Slash command dropdown, floated over the editing surface.
+                        if let Some(state) = slash_menu() {
+                            if let Some(query) = slash_query() {
+                                SlashMenu {
+                                    query,
+                                    selected: state.selected,
+                                    on_pick: {
+                                        let mut doc = document.clone();
+                                        move |i: usize| {
+                                            let Some(state) = *slash_menu.peek() else {
+                                                return;
+                                            };
+                                            let query =
+                                                slash_query.peek().clone().unwrap_or_default();
+                                            if let Some(cmd) = filter_commands(&query).get(i) {
+                                                insert_slash_command(&mut doc, state.anchor, cmd);
+                                            }
+                                            slash_menu.set(None);
+                                        }
+                                    },
+                                }
+                            }
+                        }
+
+                        // Wikilink/mention autocomplete, anchored at the trigger.
+                        if completion_dismissed().is_none()
+                            && completion_ctx().is_some()
+                            && !completion_items().is_empty()
+                        {
+                            CompletionPopover {
+                                items: completion_items(),
+                                selected: completion_selected(),
+                                position: completion_pos(),
+                                on_pick: {
+                                    let mut doc = document.clone();
+                                    move |i: usize| {
+                                        let Some(ctx) = completion_ctx.peek().clone() else {
+                                            return;
+                                        };
+                                        if let Some(item) = completion_items.peek().get(i) {
+                                            apply_completion(&mut doc, ctx.start, item);
+                                        }
+                                    }
+                                },
+                            }
+                        }
+
+                        // Clipboard upload progress, with retry for failures.
+                        if !paste_uploads().is_empty() {
+                            div { class: "paste-uploads",
+                                for (i, up) in paste_uploads().iter().enumerate() {
+                                    div {
+                                        key: "{up.blob_tid.as_str()}",
+                                        class: "paste-upload",
+                                        if let PastedUploadStatus::Failed(ref err) = up.status {
+                                            span { class: "paste-upload-error",
+                                                "Upload of {up.name} failed: {err}"
+                                            }
+                                            button {
+                                                class: "paste-upload-retry",
+                                                onclick: {
+                                                    let doc = document.clone();
+                                                    let fetcher = fetcher.clone();
+                                                    move |_| {
+                                                        retry_paste_upload(
+                                                            i,
+                                                            &doc,
+                                                            &image_resolver,
+                                                            &fetcher,
+                                                            paste_uploads,
+                                                        );
+                                                    }
+                                                },
+                                                "Retry"
+                                            }
+                                            button {
+                                                class: "paste-upload-dismiss",
+                                                onclick: move |_| dismiss_paste_upload(i, paste_uploads),
+                                                "Dismiss"
+                                            }
+                                        } else {
+                                            span { "Uploading {up.name}…" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // Outline panel with click-to-jump and section
+                        // drag-to-reorder.
+                        if outline_open() {
+                            OutlineSidebar {
+                                outline: outline(),
+                                current: outline_current(),
+                                on_jump: {
+                                    let mut doc = document.clone();
+                                    move |i: usize| {
+                                        if let Some(heading) = outline.peek().get(i) {
+                                            jump_to_heading(&mut doc, heading);
+                                        }
+                                    }
+                                },
+                                on_move: {
+                                    let mut doc = document.clone();
+                                    move |(from, to): (usize, usize)| {
+                                        move_section(&mut doc, &outline.peek(), from, to);
+                                    }
+                                },
+                            }
+                        }
+
+                        // Published-style preview pane (read renderer output).
+                        if split_preview() {
+                            div {
+                                id: "editor-preview-pane",
+                                class: "editor-preview-pane",
+                                aria_label: "Preview",
+                                for (id, html) in preview_blocks() {
+                                    div {
+                                        key: "{id}",
+                                        id: "preview-{id}",
+                                        dangerous_inner_html: "{html}",
+                                    }
+                                }
+                            }
+                        }
                         div { class: "editor-debug",
                             div { "Cursor: {document.cursor.read().offset}, Chars: {document.len_chars()}" },
                             // Collab debug info
@@ -1026,3 +1636,72 @@ fn MarkdownEditorInner(
         }
     }
 }
+
+/// Delay between the last edit and a split-view preview re-render.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+const PREVIEW_DEBOUNCE_MS: u32 = 250;
+
+/// Renders one preview HTML block per editor paragraph.
+///
+/// Uses the plain read parser (same as entry cards), so the pane shows what
+/// readers will see rather than the syntax-visible writer output. Block IDs
+/// match editor paragraph IDs so scroll syncing can pair them up.
+fn render_preview_blocks(content: &str, paragraphs: &[ParagraphRender]) -> Vec<(SmolStr, String)> {
+    paragraphs
+        .iter()
+        .map(|para| {
+            let source = content.get(para.byte_range.clone()).unwrap_or("");
+            let parser = markdown_weaver::Parser::new(source);
+            let mut html = String::new();
+            markdown_weaver::html::push_html(&mut html, parser);
+            (para.id.clone(), html)
+        })
+        .collect()
+}
+
+/// Max automatic retries for a failed embed fetch.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+const EMBED_RETRY_MAX_ATTEMPTS: u32 = 3;
+
+/// First automatic retry delay; doubles per attempt.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+const EMBED_RETRY_BASE_MS: u32 = 2000;
+
+/// Whether a fetch failure is worth retrying automatically.
+///
+/// A malformed URI can never succeed; anything else (network errors, rate
+/// limits, slow PDSes) is treated as transient.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn is_transient_embed_error(err: &str) -> bool {
+    !err.starts_with("Invalid AT URI")
+}
+
+/// Visible placeholder card for an embed that failed to fetch.
+///
+/// Shows the URI and failure reason, plus a retry button that the editor's
+/// click handler picks up via the `data-embed-retry` attribute.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn embed_error_card(uri: &str, reason: &str, retrying: bool) -> String {
+    use markdown_weaver_escape::escape_html;
+
+    let mut escaped_uri = String::new();
+    let _ = escape_html(&mut escaped_uri, uri);
+    let mut escaped_reason = String::new();
+    let _ = escape_html(&mut escaped_reason, reason);
+
+    let status = if retrying {
+        "<span class=\"embed-error-status\">retrying…</span>"
+    } else {
+        ""
+    };
+
+    format!(
+        "<div class=\"atproto-embed atproto-embed-error\" contenteditable=\"false\">\
+         <span class=\"embed-error-uri\">{}</span>\
+         <span class=\"embed-error-reason\">{}</span>\
+         {}\
+         <button type=\"button\" class=\"embed-error-retry\" data-embed-retry=\"{}\">Retry</button>\
+         </div>",
+        escaped_uri, escaped_reason, status, escaped_uri
+    )
+}
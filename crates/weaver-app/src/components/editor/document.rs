@@ -130,6 +130,14 @@ pub struct SignalEditorDocument {
     /// Collected refs (wikilinks, AT embeds) from the most recent render.
     /// Updated by the render pipeline, read by publish for populating records.
     pub collected_refs: Signal<Vec<weaver_common::ExtractedRef>>,
+
+    /// Visible character range (start, end) of the editor viewport.
+    /// Updated on scroll, broadcast to collaborators via awareness.
+    pub viewport: Signal<Option<(usize, usize)>>,
+
+    /// Locked (non-editable) template regions. Edits touching these are
+    /// rejected in execute_action; paragraphs get distinct styling.
+    pub locked_regions: Signal<Vec<weaver_editor_core::LockedRegion>>,
 }
 
 /// Pre-loaded document state that can be created outside of reactive context.
@@ -162,6 +170,9 @@ pub struct LoadedDocState {
     pub resolved_content: weaver_common::ResolvedContent,
     /// Notebook URI for re-publishing to the same notebook.
     pub notebook_uri: Option<SmolStr>,
+    /// Detected divergence between localStorage and PDS copies.
+    /// When set, the editor asks the user to resolve before mounting.
+    pub conflict: Option<super::sync::DraftConflict>,
 }
 
 impl PartialEq for LoadedDocState {
@@ -225,6 +236,8 @@ impl SignalEditorDocument {
             content_changed: Signal::new(()),
             pending_snap: Signal::new(None),
             collected_refs: Signal::new(Vec::new()),
+            viewport: Signal::new(None),
+            locked_regions: Signal::new(Vec::new()),
         }
     }
 
@@ -977,6 +990,8 @@ impl SignalEditorDocument {
             content_changed: Signal::new(()),
             pending_snap: Signal::new(None),
             collected_refs: Signal::new(Vec::new()),
+            viewport: Signal::new(None),
+            locked_regions: Signal::new(Vec::new()),
         }
     }
 
@@ -1032,6 +1047,8 @@ impl SignalEditorDocument {
             content_changed: Signal::new(()),
             pending_snap: Signal::new(None),
             collected_refs: Signal::new(Vec::new()),
+            viewport: Signal::new(None),
+            locked_regions: Signal::new(Vec::new()),
         }
     }
 }
@@ -1137,7 +1154,8 @@ impl EditorDocument for SignalEditorDocument {
     }
 
     fn set_composition_ended_now(&mut self) {
-        self.composition_ended_at.set(Some(web_time::Instant::now()));
+        self.composition_ended_at
+            .set(Some(web_time::Instant::now()));
     }
 
     fn undo(&mut self) -> bool {
@@ -1176,4 +1194,12 @@ impl EditorDocument for SignalEditorDocument {
     fn set_pending_snap(&mut self, snap: Option<weaver_editor_core::SnapDirection>) {
         self.pending_snap.set(snap);
     }
+
+    fn locked_regions(&self) -> Vec<weaver_editor_core::LockedRegion> {
+        self.locked_regions.read().clone()
+    }
+
+    fn set_locked_regions(&mut self, regions: Vec<weaver_editor_core::LockedRegion>) {
+        self.locked_regions.set(regions);
+    }
 }
@@ -11,6 +11,7 @@ use jacquard::types::ident::AtIdentifier;
 use jacquard::{bytes::Bytes, types::tid::Tid};
 use mime_sniffer::MimeTypeSniffer;
 
+use super::actions::{EditorAction, Range, execute_action};
 use super::document::SignalEditorDocument;
 use crate::auth::AuthState;
 use crate::fetch::Fetcher;
@@ -257,7 +258,9 @@ pub fn handle_image_upload(
         let mut resolver_for_spawn = *image_resolver;
 
         spawn(async move {
-            upload_image_to_pds(
+            // Failures are logged inside; the toolbar flow has no retry
+            // surface, the image just stays as a data URL.
+            let _ = upload_image_to_pds(
                 &fetcher,
                 &mut doc_for_spawn,
                 &mut resolver_for_spawn,
@@ -273,7 +276,247 @@ pub fn handle_image_upload(
     }
 }
 
+/// A clipboard image upload tracked for progress display and retry.
+#[derive(Clone, Debug)]
+pub struct PastedUpload {
+    /// Filename used as the markdown reference name.
+    pub name: String,
+    pub status: PastedUploadStatus,
+    /// Kept so a failed upload can be retried without re-reading the
+    /// clipboard (which is gone by then).
+    pub(crate) data: Bytes,
+    pub(crate) alt: String,
+    pub(crate) blob_tid: Tid,
+}
+
+/// Where a pasted image is in its upload lifecycle.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PastedUploadStatus {
+    Uploading,
+    /// Upload failed; the image stays as a data URL until retried.
+    Failed(String),
+}
+
+/// Handle images pasted from the clipboard.
+///
+/// Returns `true` (and consumes the event) when the clipboard carried a
+/// file; text pastes return `false` so the caller falls through to the
+/// normal text paste path. A `![[uploading <name>...]]` placeholder goes
+/// in at the cursor immediately, then is replaced with real image
+/// markdown once the bytes are read; the PDS upload itself runs in the
+/// background with its progress tracked in `uploads` for the status
+/// strip and retry button.
+pub fn handle_image_paste(
+    evt: &Event<ClipboardData>,
+    doc: &mut SignalEditorDocument,
+    image_resolver: &mut Signal<EditorImageResolver>,
+    auth_state: &Signal<AuthState>,
+    fetcher: &Fetcher,
+    mut uploads: Signal<Vec<PastedUpload>>,
+) -> bool {
+    let files = evt.files();
+    let Some(file) = files.first().cloned() else {
+        return false;
+    };
+    evt.prevent_default();
+
+    // Clipboard screenshots usually arrive as "image.png"; suffix the
+    // pre-generated blob TID so repeated pastes stay distinguishable.
+    let blob_tid = jacquard::types::tid::Ticker::new().next(None);
+    let name = if file.name().is_empty() {
+        format!("pasted-{}.png", blob_tid.as_str())
+    } else {
+        file.name()
+    };
+
+    let placeholder = format!("![[uploading {}...]]", name);
+    let pos = doc.cursor_offset();
+    doc.insert(pos, &placeholder);
+
+    uploads.with_mut(|u| {
+        u.push(PastedUpload {
+            name: name.clone(),
+            status: PastedUploadStatus::Uploading,
+            data: Bytes::new(),
+            alt: String::new(),
+            blob_tid: blob_tid.clone(),
+        })
+    });
+
+    let auth = auth_state.read();
+    let did_for_path = auth.did.clone();
+    let is_authenticated = auth.is_authenticated();
+    drop(auth);
+
+    let fetcher = fetcher.clone();
+    let mut doc = doc.clone();
+    let mut image_resolver = *image_resolver;
+    spawn(async move {
+        let remove_entry = |uploads: &mut Signal<Vec<PastedUpload>>| {
+            uploads.with_mut(|u| u.retain(|up| up.blob_tid.as_str() != blob_tid.as_str()));
+        };
+
+        let Ok(data) = file.read_bytes().await else {
+            tracing::warn!(name = %name, "Failed to read pasted file");
+            replace_placeholder(&mut doc, &placeholder, "");
+            remove_entry(&mut uploads);
+            return;
+        };
+        let data = Bytes::from(data);
+        let mime_type = data
+            .sniff_mime_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        if !mime_type.starts_with("image/") {
+            // Not an image after all (e.g. a pasted document); drop the
+            // placeholder rather than embed something we can't render.
+            tracing::debug!(name = %name, mime = %mime_type, "Ignoring non-image paste");
+            replace_placeholder(&mut doc, &placeholder, "");
+            remove_entry(&mut uploads);
+            return;
+        }
+
+        // From here the flow matches the toolbar upload: pending data
+        // URL for immediate preview, then background PDS upload.
+        let data_url = format!("data:{};base64,{}", mime_type, STANDARD.encode(&data));
+        image_resolver.with_mut(|resolver| {
+            resolver.add_pending(name.clone(), data_url);
+        });
+
+        let markdown = if let Some(ref did) = did_for_path {
+            format!(
+                "![{}](/image/{}/draft/{}/{})",
+                name,
+                did,
+                blob_tid.as_str(),
+                name
+            )
+        } else {
+            format!("![{}](/image/{})", name, name)
+        };
+        replace_placeholder(&mut doc, &placeholder, &markdown);
+
+        if !is_authenticated {
+            tracing::debug!(name = %name, "Pasted image added with data URL (not authenticated)");
+            remove_entry(&mut uploads);
+            return;
+        }
+
+        uploads.with_mut(|u| {
+            if let Some(up) = u
+                .iter_mut()
+                .find(|up| up.blob_tid.as_str() == blob_tid.as_str())
+            {
+                up.data = data.clone();
+                up.alt = name.clone();
+            }
+        });
+
+        match upload_image_to_pds(
+            &fetcher,
+            &mut doc,
+            &mut image_resolver,
+            data,
+            name,
+            String::new(),
+            blob_tid.clone(),
+        )
+        .await
+        {
+            Ok(()) => remove_entry(&mut uploads),
+            Err(e) => uploads.with_mut(|u| {
+                if let Some(up) = u
+                    .iter_mut()
+                    .find(|up| up.blob_tid.as_str() == blob_tid.as_str())
+                {
+                    up.status = PastedUploadStatus::Failed(e);
+                }
+            }),
+        }
+    });
+    true
+}
+
+/// Retry a failed paste upload by index into the uploads list.
+pub fn retry_paste_upload(
+    index: usize,
+    doc: &SignalEditorDocument,
+    image_resolver: &Signal<EditorImageResolver>,
+    fetcher: &Fetcher,
+    mut uploads: Signal<Vec<PastedUpload>>,
+) {
+    let Some(up) = uploads.peek().get(index).cloned() else {
+        return;
+    };
+    uploads.with_mut(|u| {
+        if let Some(entry) = u.get_mut(index) {
+            entry.status = PastedUploadStatus::Uploading;
+        }
+    });
+
+    let fetcher = fetcher.clone();
+    let mut doc = doc.clone();
+    let mut image_resolver = *image_resolver;
+    spawn(async move {
+        match upload_image_to_pds(
+            &fetcher,
+            &mut doc,
+            &mut image_resolver,
+            up.data,
+            up.name,
+            up.alt,
+            up.blob_tid.clone(),
+        )
+        .await
+        {
+            Ok(()) => {
+                uploads.with_mut(|u| u.retain(|e| e.blob_tid.as_str() != up.blob_tid.as_str()))
+            }
+            Err(e) => uploads.with_mut(|u| {
+                if let Some(entry) = u
+                    .iter_mut()
+                    .find(|e| e.blob_tid.as_str() == up.blob_tid.as_str())
+                {
+                    entry.status = PastedUploadStatus::Failed(e);
+                }
+            }),
+        }
+    });
+}
+
+/// Drop a failed upload from the status strip; the image keeps working
+/// as a data URL locally, it just won't publish.
+pub fn dismiss_paste_upload(index: usize, mut uploads: Signal<Vec<PastedUpload>>) {
+    uploads.with_mut(|u| {
+        if index < u.len() {
+            u.remove(index);
+        }
+    });
+}
+
+/// Replace the first occurrence of `placeholder` with `replacement` via
+/// a single editor action, so undo treats it as one step.
+fn replace_placeholder(doc: &mut SignalEditorDocument, placeholder: &str, replacement: &str) {
+    let content = doc.content();
+    let Some(byte_idx) = content.find(placeholder) else {
+        // The user edited the placeholder away; nothing to do.
+        return;
+    };
+    let start = content[..byte_idx].chars().count();
+    let range = Range::new(start, start + placeholder.chars().count());
+    execute_action(
+        doc,
+        &EditorAction::Insert {
+            text: replacement.to_string(),
+            range,
+        },
+    );
+}
+
 /// Upload image to PDS and update resolver.
+///
+/// The error string is short and user-facing (it ends up in the paste
+/// upload status strip).
 async fn upload_image_to_pds(
     fetcher: &Fetcher,
     doc: &mut SignalEditorDocument,
@@ -282,7 +525,7 @@ async fn upload_image_to_pds(
     name: String,
     alt: String,
     blob_tid: Tid,
-) {
+) -> Result<(), String> {
     let client = fetcher.get_client();
     use weaver_common::WeaverExt;
 
@@ -302,7 +545,7 @@ async fn upload_image_to_pds(
                 Some(d) => d,
                 None => {
                     tracing::warn!("No DID available");
-                    return;
+                    return Err("no session DID available".to_string());
                 }
             };
 
@@ -311,7 +554,7 @@ async fn upload_image_to_pds(
                 Some(rkey) => rkey.0.clone().into_static(),
                 None => {
                     tracing::warn!("No rkey in PublishedBlob URI");
-                    return;
+                    return Err("blob record has no rkey".to_string());
                 }
             };
 
@@ -352,10 +595,13 @@ async fn upload_image_to_pds(
             // Suppress unused variable warning when fullstack-server is disabled.
             #[cfg(not(feature = "fullstack-server"))]
             let _ = cid;
+
+            Ok(())
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to upload image");
             // Image stays as data URL - will work for preview but not publish.
+            Err(format!("upload failed: {}", e))
         }
     }
 }
@@ -6,14 +6,18 @@
 
 mod actions;
 mod collab;
+mod completion;
 mod component;
 mod document;
 mod dom_sync;
 mod image_upload;
 mod log_buffer;
+mod outline;
 mod publish;
 mod remote_cursors;
 mod report;
+mod share_bsky;
+mod slash_menu;
 mod storage;
 mod sync;
 mod toolbar;
@@ -21,6 +25,15 @@ mod toolbar;
 #[cfg(test)]
 mod tests;
 
+/// Shared client-side collab quota ([`weaver_editor_crdt::CollabQuota`]).
+///
+/// Lives outside component state so a crash/remount loop cannot reset the
+/// rate windows by recreating signals; both the collab coordinator (session
+/// records) and the sync status component (diff records) consult it.
+pub(crate) static COLLAB_QUOTA: std::sync::LazyLock<
+    std::sync::Mutex<weaver_editor_crdt::CollabQuota>,
+> = std::sync::LazyLock::new(|| std::sync::Mutex::new(weaver_editor_crdt::CollabQuota::new()));
+
 // Main component
 pub use component::MarkdownEditor;
 
@@ -52,7 +65,8 @@ pub use storage::{
 // Sync
 #[allow(unused_imports)]
 pub use sync::{
-    PdsEditState, RemoteDraft, SyncState, SyncStatus, list_drafts_from_pds,
+    ConflictResolution, DraftConflict, DraftConflictDialog, PdsEditState, RemoteDraft, SyncState,
+    SyncStatus, apply_conflict_resolution, detect_draft_conflict, list_drafts_from_pds,
     load_and_merge_document, load_edit_state_from_pds, sync_to_pds,
 };
 
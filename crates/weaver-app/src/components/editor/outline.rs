@@ -0,0 +1,323 @@
+//! Document outline sidebar.
+//!
+//! Lists the document's headings with click-to-jump, highlights the
+//! section currently in the viewport, and supports dragging a heading to
+//! reorder whole sections. A section runs from its heading to the next
+//! heading of the same or higher level, so dragging a `##` carries its
+//! `###` children along. Reordering replaces the minimal covering range
+//! with one [`EditorAction::Insert`], keeping the move a single undo step
+//! and leaving the rest of the document untouched for CRDT merging.
+
+use super::actions::{EditorAction, Range, execute_action};
+use super::document::{CursorState, SignalEditorDocument};
+use dioxus::prelude::*;
+use jacquard::smol_str::SmolStr;
+use weaver_editor_core::ParagraphRender;
+
+/// One heading in the outline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineHeading {
+    /// Paragraph ID, which doubles as the DOM element id to scroll to.
+    pub id: SmolStr,
+    /// Heading level, 1-6.
+    pub level: u8,
+    /// Heading text with the `#` markers stripped.
+    pub text: String,
+    /// Char offset of the heading paragraph's start.
+    pub char_start: usize,
+}
+
+/// Extract the outline from rendered paragraphs.
+///
+/// A paragraph whose source starts with 1-6 `#` and a space is a heading;
+/// fenced code blocks are their own paragraphs starting with a fence, so
+/// `#` lines inside them never match.
+pub fn extract_outline(content: &str, paragraphs: &[ParagraphRender]) -> Vec<OutlineHeading> {
+    let mut outline = Vec::new();
+    for para in paragraphs {
+        let Some(source) = content.get(para.byte_range.clone()) else {
+            continue;
+        };
+        let line = source.trim_start().lines().next().unwrap_or("");
+        let level = line.bytes().take_while(|&b| b == b'#').count();
+        if !(1..=6).contains(&level) {
+            continue;
+        }
+        let Some(rest) = line[level..].strip_prefix(' ') else {
+            continue;
+        };
+        outline.push(OutlineHeading {
+            id: para.id.clone(),
+            level: level as u8,
+            text: rest.trim().to_string(),
+            char_start: para.char_range.start,
+        });
+    }
+    outline
+}
+
+/// Char ranges of each heading's section.
+///
+/// A section ends at the next heading with the same or a higher level
+/// (fewer `#`s), or at the end of the document.
+pub fn section_ranges(outline: &[OutlineHeading], doc_char_len: usize) -> Vec<Range> {
+    outline
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            let end = outline[i + 1..]
+                .iter()
+                .find(|next| next.level <= h.level)
+                .map(|next| next.char_start)
+                .unwrap_or(doc_char_len);
+            Range::new(h.char_start, end)
+        })
+        .collect()
+}
+
+/// Index of the section containing the top of the viewport, for
+/// current-section highlighting while scrolling.
+pub fn current_section(outline: &[OutlineHeading], viewport_start: usize) -> Option<usize> {
+    outline.iter().rposition(|h| h.char_start <= viewport_start)
+}
+
+/// Compute the text replacement that moves section `from` so it starts
+/// where section `to` currently starts (`to == outline.len()` moves it
+/// to the end of the document).
+///
+/// Returns the covering char range and its replacement text, or `None`
+/// when the move is a no-op (dropping a section onto itself or into its
+/// own children).
+fn reorder_sections(
+    content: &str,
+    outline: &[OutlineHeading],
+    from: usize,
+    to: usize,
+) -> Option<(Range, String)> {
+    if from >= outline.len() || to > outline.len() {
+        return None;
+    }
+    let chars: Vec<char> = content.chars().collect();
+    let ranges = section_ranges(outline, chars.len());
+    let sec = ranges[from];
+    let target = if to == outline.len() {
+        chars.len()
+    } else {
+        outline[to].char_start
+    };
+    if target >= sec.start && target <= sec.end {
+        return None;
+    }
+
+    let slice = |start: usize, end: usize| chars[start..end].iter().collect::<String>();
+    let mut moved = slice(sec.start, sec.end);
+    // A section at EOF may lack a trailing newline; it needs one once
+    // other text follows it.
+    if !moved.ends_with('\n') {
+        moved.push('\n');
+    }
+
+    if target < sec.start {
+        // Moving up: the moved section lands at the target, the text
+        // that was between them shifts down.
+        let between = slice(target, sec.start);
+        moved.push_str(&between);
+        Some((Range::new(target, sec.end), moved))
+    } else {
+        // Moving down: the in-between text shifts up, the section lands
+        // after it.
+        let mut replacement = slice(sec.end, target);
+        if !replacement.is_empty() && !replacement.ends_with('\n') {
+            replacement.push('\n');
+        }
+        replacement.push_str(&moved);
+        Some((Range::new(sec.start, target), replacement))
+    }
+}
+
+/// Apply a drag-to-reorder move as a single editor action.
+pub fn move_section(
+    doc: &mut SignalEditorDocument,
+    outline: &[OutlineHeading],
+    from: usize,
+    to: usize,
+) {
+    let content = doc.content();
+    let Some((range, text)) = reorder_sections(&content, outline, from, to) else {
+        return;
+    };
+    execute_action(doc, &EditorAction::Insert { text, range });
+    // Park the cursor at the start of the rewritten span so it cannot be
+    // left pointing past the end of a shrunken section.
+    doc.cursor.set(CursorState {
+        offset: range.start,
+        ..Default::default()
+    });
+}
+
+/// Jump the cursor to a heading and scroll its paragraph into view.
+pub fn jump_to_heading(doc: &mut SignalEditorDocument, heading: &OutlineHeading) {
+    doc.cursor.set(CursorState {
+        offset: heading.char_start,
+        ..Default::default()
+    });
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    if let Some(element) = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.get_element_by_id(heading.id.as_str()))
+    {
+        element.scroll_into_view();
+    }
+}
+
+/// The outline panel; jump and move mutations are driven by the editor.
+#[component]
+pub fn OutlineSidebar(
+    outline: Vec<OutlineHeading>,
+    current: Option<usize>,
+    on_jump: EventHandler<usize>,
+    on_move: EventHandler<(usize, usize)>,
+) -> Element {
+    let mut dragging = use_signal(|| None::<usize>);
+    let end = outline.len();
+
+    if outline.is_empty() {
+        return rsx! {
+            div { class: "outline-sidebar",
+                p { class: "outline-empty", "No headings yet" }
+            }
+        };
+    }
+
+    rsx! {
+        div { class: "outline-sidebar", role: "navigation", aria_label: "Document outline",
+            for (i, heading) in outline.iter().enumerate() {
+                button {
+                    key: "{heading.id}",
+                    class: "outline-item",
+                    class: if current == Some(i) { "current" },
+                    style: "padding-inline-start: {8 + (heading.level as usize - 1) * 12}px;",
+                    draggable: "true",
+                    ondragstart: move |_| dragging.set(Some(i)),
+                    ondragend: move |_| dragging.set(None),
+                    // Allowing drop requires cancelling dragover's default.
+                    ondragover: move |evt| evt.prevent_default(),
+                    ondrop: move |evt| {
+                        evt.prevent_default();
+                        if let Some(from) = dragging() {
+                            dragging.set(None);
+                            on_move.call((from, i));
+                        }
+                    },
+                    onclick: move |_| on_jump.call(i),
+                    "{heading.text}"
+                }
+            }
+            // Trailing drop zone so a section can be dragged below the
+            // last heading.
+            div {
+                class: "outline-drop-end",
+                ondragover: move |evt| evt.prevent_default(),
+                ondrop: move |evt| {
+                    evt.prevent_default();
+                    if let Some(from) = dragging() {
+                        dragging.set(None);
+                        on_move.call((from, end));
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outline_for(content: &str) -> Vec<OutlineHeading> {
+        // Build paragraph stubs the way the renderer would: one per
+        // blank-line-separated block.
+        let mut paragraphs = Vec::new();
+        let mut byte = 0;
+        let mut chars = 0;
+        for (i, block) in content.split_inclusive("\n\n").enumerate() {
+            let byte_range = byte..byte + block.len();
+            let char_range = chars..chars + block.chars().count();
+            byte = byte_range.end;
+            chars = char_range.end;
+            paragraphs.push(ParagraphRender {
+                id: SmolStr::new(format!("p-{}", i)),
+                byte_range,
+                char_range,
+                html: String::new(),
+                offset_map: Vec::new(),
+                syntax_spans: Vec::new(),
+                source_hash: 0,
+                code_language: None,
+            });
+        }
+        extract_outline(content, &paragraphs)
+    }
+
+    #[test]
+    fn test_extracts_heading_levels_and_text() {
+        let outline = outline_for("# Title\n\nbody\n\n## Sub section\n\nmore\n");
+        assert_eq!(outline.len(), 2);
+        assert_eq!((outline[0].level, outline[0].text.as_str()), (1, "Title"));
+        assert_eq!(
+            (outline[1].level, outline[1].text.as_str()),
+            (2, "Sub section")
+        );
+    }
+
+    #[test]
+    fn test_sections_nest_by_level() {
+        let content = "# A\n\n## A1\n\n## A2\n\n# B\n\nend\n";
+        let outline = outline_for(content);
+        let ranges = section_ranges(&outline, content.chars().count());
+        // "# A" runs to "# B"; "## A1" only to "## A2".
+        assert_eq!(ranges[0].end, outline[3].char_start);
+        assert_eq!(ranges[1].end, outline[2].char_start);
+        assert_eq!(ranges[3].end, content.chars().count());
+    }
+
+    #[test]
+    fn test_reorder_moves_section_up() {
+        let content = "# A\n\na body\n\n# B\n\nb body\n";
+        let outline = outline_for(content);
+        let (range, replacement) = reorder_sections(content, &outline, 1, 0).unwrap();
+        assert_eq!(range.start, 0);
+        assert!(replacement.starts_with("# B\n\nb body\n"));
+        assert!(replacement.contains("# A\n\na body\n"));
+    }
+
+    #[test]
+    fn test_reorder_moves_section_to_end() {
+        let content = "# A\n\na body\n\n# B\n\nb body\n";
+        let outline = outline_for(content);
+        let (range, replacement) = reorder_sections(content, &outline, 0, outline.len()).unwrap();
+        assert_eq!(range.end, content.chars().count());
+        assert!(replacement.starts_with("# B"));
+        assert!(replacement.ends_with("# A\n\na body\n"));
+    }
+
+    #[test]
+    fn test_reorder_onto_self_is_noop() {
+        let content = "# A\n\n## A1\n\n# B\n";
+        let outline = outline_for(content);
+        assert_eq!(reorder_sections(content, &outline, 0, 0), None);
+        // Dropping a section into its own children is also a no-op.
+        assert_eq!(reorder_sections(content, &outline, 0, 1), None);
+    }
+
+    #[test]
+    fn test_current_section_tracks_viewport() {
+        let content = "# A\n\nbody\n\n# B\n\nmore\n";
+        let outline = outline_for(content);
+        assert_eq!(current_section(&outline, 0), Some(0));
+        assert_eq!(
+            current_section(&outline, outline[1].char_start + 1),
+            Some(1)
+        );
+    }
+}
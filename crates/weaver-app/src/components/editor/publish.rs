@@ -57,9 +57,22 @@ fn rewrite_draft_paths_for_notebook(content: &str, notebook_key: &str) -> String
 use crate::auth::AuthState;
 use crate::components::editor::SignalEditorDocument;
 use crate::fetch::Fetcher;
+use crate::record_utils::{EntryVisibility, publish_extra_data};
 
+use super::share_bsky::ShareToBskyButton;
 use super::storage::{delete_draft, save_to_storage};
 
+/// Visibility and scheduling choices for a publish.
+///
+/// Defaults produce a plain public entry with a record identical to one
+/// from a client that predates these options.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PublishOptions {
+    pub visibility: EntryVisibility,
+    /// Scheduled publish time; listings hide the entry until it passes.
+    pub published_at: Option<Datetime>,
+}
+
 /// Result of a publish operation.
 #[derive(Clone, Debug)]
 pub enum PublishResult {
@@ -164,7 +177,14 @@ pub async fn publish_entry(
     doc: &mut SignalEditorDocument,
     notebook_title: Option<&str>,
     draft_key: &str,
+    options: &PublishOptions,
 ) -> Result<PublishResult, WeaverError> {
+    // Publish preflight: accessibility problems are worth flagging while
+    // the author can still edit, but they never block publishing.
+    for warning in weaver_renderer::a11y::check_markdown(&doc.content()) {
+        tracing::warn!("a11y: line {}: {}", warning.line, warning);
+    }
+
     // Get images from the document
     let editor_images = doc.images();
 
@@ -245,7 +265,7 @@ pub async fn publish_entry(
         // Rewrite draft image paths to notebook paths: /image/{notebook}/{name}
         let content = rewrite_draft_paths_for_notebook(&doc.content(), notebook);
 
-        let entry = Entry::new()
+        let mut entry = Entry::new()
             .content(content)
             .title(doc.title())
             .path(path)
@@ -254,6 +274,7 @@ pub async fn publish_entry(
             .maybe_tags(tags)
             .maybe_embeds(entry_embeds)
             .build();
+        entry.extra_data = publish_extra_data(options.visibility, options.published_at.as_ref());
 
         // Check if we have a stored notebook URI (for re-publishing to same notebook)
         // This avoids duplicate notebook creation when re-publishing
@@ -334,7 +355,7 @@ pub async fn publish_entry(
         // Rewrite draft image paths to published paths
         let content = rewrite_draft_paths(&doc.content(), rkey.0.as_str());
 
-        let entry = Entry::new()
+        let mut entry = Entry::new()
             .content(content)
             .title(doc.title())
             .path(path)
@@ -343,6 +364,7 @@ pub async fn publish_entry(
             .maybe_tags(tags)
             .maybe_embeds(entry_embeds)
             .build();
+        entry.extra_data = publish_extra_data(options.visibility, options.published_at.as_ref());
         let entry_data = to_data(&entry).unwrap();
 
         let collection = Nsid::new(ENTRY_NSID).map_err(|e| WeaverError::AtprotoString(e))?;
@@ -396,7 +418,7 @@ pub async fn publish_entry(
         // Rewrite draft image paths to published paths
         let content = rewrite_draft_paths(&doc.content(), entry_rkey_str);
 
-        let entry = Entry::new()
+        let mut entry = Entry::new()
             .content(content)
             .title(doc.title())
             .path(path)
@@ -405,6 +427,7 @@ pub async fn publish_entry(
             .maybe_tags(tags)
             .maybe_embeds(entry_embeds)
             .build();
+        entry.extra_data = publish_extra_data(options.visibility, options.published_at.as_ref());
         let entry_data = to_data(&entry).unwrap();
 
         let collection = Nsid::new(ENTRY_NSID).map_err(|e| WeaverError::AtprotoString(e))?;
@@ -456,6 +479,22 @@ pub async fn publish_entry(
     Ok(result)
 }
 
+/// Parse a `datetime-local` input value into a [`Datetime`].
+///
+/// The input carries no zone, so the value is interpreted as UTC; coarse
+/// but predictable without a timezone picker.
+fn parse_schedule_input(raw: &str) -> Result<Datetime, String> {
+    use std::str::FromStr;
+
+    // datetime-local omits seconds unless the user typed them.
+    let normalized = if raw.len() == 16 {
+        format!("{raw}:00Z")
+    } else {
+        format!("{raw}Z")
+    };
+    Datetime::from_str(&normalized).map_err(|_| format!("Invalid schedule time: {raw}"))
+}
+
 /// Simple slug generation from title.
 fn slugify(title: &str) -> String {
     title
@@ -506,6 +545,9 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
             .unwrap_or_else(|| String::from("Default"))
     });
     let mut use_notebook = use_signal(|| props.target_notebook.is_some());
+    let mut unlisted = use_signal(|| false);
+    // Raw datetime-local input value; empty means publish immediately.
+    let mut schedule_input = use_signal(String::new);
     let mut is_publishing = use_signal(|| false);
     let mut error_message: Signal<Option<String>> = use_signal(|| None);
     let mut success_uri: Signal<Option<AtUri<'static>>> = use_signal(|| None);
@@ -545,6 +587,21 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
         show_dialog.set(false);
     };
 
+    // Move focus into the dialog when it opens and hand it back to the
+    // publish button when it closes, so keyboard users are never
+    // stranded behind the overlay. The "was open" signal keeps the
+    // close branch from stealing focus on the initial render.
+    let mut dialog_was_open = use_signal(|| false);
+    use_effect(move || {
+        let open = show_dialog();
+        if open {
+            crate::components::focus::focus_first_in(".publish-dialog");
+        } else if *dialog_was_open.peek() {
+            crate::components::focus::focus_element(".publish-button");
+        }
+        *dialog_was_open.write() = open;
+    });
+
     let draft_key_clone = draft_key.clone();
     let doc_for_publish = doc.clone();
     let do_publish = move |_| {
@@ -557,12 +614,45 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
             None
         };
 
+        // Parse the schedule before spawning so a bad value surfaces
+        // immediately instead of mid-publish.
+        let published_at = {
+            let raw = schedule_input();
+            let raw = raw.trim();
+            if raw.is_empty() {
+                None
+            } else {
+                match parse_schedule_input(raw) {
+                    Ok(dt) => Some(dt),
+                    Err(e) => {
+                        error_message.set(Some(e));
+                        return;
+                    }
+                }
+            }
+        };
+        let options = PublishOptions {
+            visibility: if unlisted() {
+                EntryVisibility::Unlisted
+            } else {
+                EntryVisibility::Public
+            },
+            published_at,
+        };
+
         spawn(async move {
             is_publishing.set(true);
             error_message.set(None);
 
             let mut doc_snapshot = doc_snapshot;
-            match publish_entry(&fetcher, &mut doc_snapshot, notebook.as_deref(), &draft_key).await
+            match publish_entry(
+                &fetcher,
+                &mut doc_snapshot,
+                notebook.as_deref(),
+                &draft_key,
+                &options,
+            )
+            .await
             {
                 Ok(result) => {
                     success_uri.set(Some(result.uri().clone()));
@@ -598,6 +688,19 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
                 aria_modal: "true",
                 aria_labelledby: "publish-dialog-title",
                 onclick: close_dialog,
+                onkeydown: move |evt| {
+                    use dioxus::prelude::keyboard_types::Key;
+                    if evt.key() == Key::Escape {
+                        // Keep the dialog up mid-publish so the result
+                        // (or error) is not silently lost.
+                        if !is_publishing() {
+                            evt.prevent_default();
+                            show_dialog.set(false);
+                        }
+                    } else {
+                        crate::components::focus::trap_tab(".publish-dialog", &evt);
+                    }
+                },
 
                 div {
                     class: "publish-dialog",
@@ -617,6 +720,23 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
                                 let rkey = uri.rkey().map(|r| r.0.as_str()).unwrap_or("");
                                 format!("/{}/e/{}", did, rkey)
                             };
+                            let absolute_url =
+                                format!("{}{}", crate::env::WEAVER_APP_HOST, web_url);
+                            // Standalone entries have no entry OG route, so
+                            // their share card goes out without a thumbnail.
+                            let og_image_url = use_notebook().then(|| {
+                                format!(
+                                    "{}/og/{}/{}/{}.png",
+                                    crate::env::WEAVER_APP_HOST,
+                                    did,
+                                    notebook_title(),
+                                    doc.path()
+                                )
+                            });
+                            let description = crate::components::extract_preview(
+                                &doc.content(),
+                                160,
+                            );
 
                             rsx! {
                                 div { class: "publish-success",
@@ -626,6 +746,12 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
                                         target: "_blank",
                                         "View entry → "
                                     }
+                                    ShareToBskyButton {
+                                        title: doc.title(),
+                                        url: absolute_url,
+                                        description,
+                                        og_image_url,
+                                    }
                                     button {
                                         class: "publish-done",
                                         onclick: close_dialog,
@@ -674,6 +800,31 @@ pub fn PublishButton(props: PublishButtonProps) -> Element {
                                 }
                             }
 
+                            div { class: "publish-field publish-checkbox",
+                                label {
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: unlisted(),
+                                        onchange: move |e| unlisted.set(e.checked()),
+                                    }
+                                    " Unlisted (direct link only)"
+                                }
+                            }
+
+                            div { class: "publish-field",
+                                label { "Publish at" }
+                                input {
+                                    r#type: "datetime-local",
+                                    class: "publish-input",
+                                    aria_label: "Scheduled publish time (UTC)",
+                                    value: "{schedule_input}",
+                                    oninput: move |e| schedule_input.set(e.value()),
+                                }
+                                p { class: "publish-field-hint",
+                                    "Leave empty to publish now. Times are UTC; the entry stays out of listings until then."
+                                }
+                            }
+
                             div { class: "publish-preview",
                                 p { "Title: {doc.title()}" }
                                 p { "Path: {doc.path()}" }
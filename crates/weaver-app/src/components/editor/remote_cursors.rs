@@ -17,14 +17,27 @@ pub fn RemoteCursors(
     document: SignalEditorDocument,
     render_cache: Signal<weaver_editor_browser::RenderCache>,
 ) -> Element {
+    use weaver_common::transport::ConnectionState;
+
     let presence_read = presence.read();
     let cursor_count = presence_read.collaborators.len();
     let cursors: Vec<_> = presence_read
         .collaborators
         .iter()
+        // Peers past the disconnect threshold keep an entry in the
+        // snapshot until the tracker prunes them, but their caret is
+        // stale information - drop it rather than strand it on screen.
+        .filter(|c| c.connection_state != ConnectionState::Disconnected)
         .filter_map(|c| {
-            c.cursor_position
-                .map(|pos| (c.display_name.clone(), c.color, pos, c.selection))
+            c.cursor_position.map(|pos| {
+                (
+                    c.display_name.clone(),
+                    c.color,
+                    pos,
+                    c.selection,
+                    c.connection_state == ConnectionState::Stale,
+                )
+            })
         })
         .collect();
 
@@ -50,13 +63,14 @@ pub fn RemoteCursors(
 
     rsx! {
         div { class: "remote-cursors-overlay",
-            for (display_name, color, position, selection) in cursors {
+            for (display_name, color, position, selection, stale) in cursors {
                 RemoteCursorIndicator {
                     key: "{display_name}-{position}",
                     display_name,
                     position,
                     selection,
                     color,
+                    stale,
                     offset_map: offset_map.clone(),
                 }
             }
@@ -71,6 +85,7 @@ fn RemoteCursorIndicator(
     position: usize,
     selection: Option<(usize, usize)>,
     color: u32,
+    stale: bool,
     offset_map: Vec<weaver_editor_core::OffsetMapping>,
 ) -> Element {
     use weaver_editor_browser::{
@@ -131,12 +146,16 @@ fn RemoteCursorIndicator(
 
         div {
             class: "remote-cursor",
+            class: if stale { "stale" },
             style: "{style}",
 
             // Cursor caret line.
             div { class: "remote-cursor-caret" }
 
-            // Name label.
+            // Name label. This component remounts whenever the peer's
+            // position changes (the key includes it), which restarts the
+            // fade: the name shows at each movement, then gets out of
+            // the way of the text under it.
             div { class: "remote-cursor-label",
                 "{display_name}"
             }
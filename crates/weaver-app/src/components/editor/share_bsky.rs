@@ -0,0 +1,231 @@
+//! Cross-posting a published entry to Bluesky.
+//!
+//! An optional step after publishing: composes an `app.bsky.feed.post`
+//! announcing the entry, with a link facet over the title, an external
+//! link card pointing back to the entry, and the entry's OG image as the
+//! card thumbnail. Nothing is sent until the user confirms the preview.
+
+use crate::fetch::Fetcher;
+use dioxus::prelude::*;
+use jacquard::IntoStatic;
+use jacquard::types::string::{AtUri, Datetime, Uri};
+use weaver_api::app_bsky::embed::external::{External, ExternalRecord};
+use weaver_api::app_bsky::feed::post::{Post, PostEmbed};
+use weaver_api::app_bsky::richtext::facet::{ByteSlice, Facet, FacetFeaturesItem, Link};
+
+/// Compose the announcement text and the byte ranges to facet as links.
+///
+/// The title and the trailing URL each get a link facet pointing at the
+/// entry; facet indices are UTF-8 byte offsets per the Bluesky richtext
+/// spec, so multi-byte titles need byte lengths, not char counts.
+pub fn compose_post_text(title: &str, url: &str) -> (String, Vec<(usize, usize)>) {
+    let text = format!("{}\n\n{}", title, url);
+    let ranges = vec![
+        (0, title.len()),
+        (title.len() + 2, title.len() + 2 + url.len()),
+    ];
+    (text, ranges)
+}
+
+/// Web URL of a created Bluesky post, for the "view post" link.
+fn bsky_post_web_url(uri: &AtUri<'_>) -> String {
+    let rkey = uri.rkey().map(|r| r.0.to_string()).unwrap_or_default();
+    format!("https://bsky.app/profile/{}/post/{}", uri.authority(), rkey)
+}
+
+/// Create the announcement post through the authenticated agent.
+///
+/// The OG image fetch and blob upload are best-effort: a card without a
+/// thumbnail is still a valid card, and a missing image should not block
+/// the share.
+async fn share_entry_to_bsky(
+    fetcher: &Fetcher,
+    title: &str,
+    url: &str,
+    description: &str,
+    og_image_url: Option<&str>,
+) -> Result<AtUri<'static>, String> {
+    let mut thumb = None;
+    if let Some(og_url) = og_image_url {
+        if let Ok(response) = reqwest::get(og_url).await {
+            if response.status().is_success() {
+                if let Ok(bytes) = response.bytes().await {
+                    let mime = jacquard::types::blob::MimeType::new_owned("image/png");
+                    thumb = fetcher.get_client().upload_blob(bytes, mime).await.ok();
+                }
+            }
+        }
+    }
+
+    let link_uri =
+        Uri::new_owned(url.to_string()).map_err(|e| format!("invalid entry url: {}", e))?;
+
+    let external = External::new()
+        .uri(link_uri.clone())
+        .title(title)
+        .description(description)
+        .maybe_thumb(thumb)
+        .build();
+
+    let (text, ranges) = compose_post_text(title, url);
+    let facets: Vec<Facet> = ranges
+        .into_iter()
+        .map(|(start, end)| {
+            Facet::new()
+                .index(
+                    ByteSlice::new()
+                        .byte_start(start as i64)
+                        .byte_end(end as i64)
+                        .build(),
+                )
+                .features(vec![FacetFeaturesItem::Link(Box::new(
+                    Link::new().uri(link_uri.clone()).build(),
+                ))])
+                .build()
+        })
+        .collect();
+
+    let post = Post::new()
+        .text(text.as_str())
+        .created_at(Datetime::now())
+        .facets(facets)
+        .embed(PostEmbed::External(Box::new(
+            ExternalRecord::new().external(external).build(),
+        )))
+        .build();
+
+    let output = fetcher
+        .create_record(post, None)
+        .await
+        .map_err(|e| format!("failed to create post: {}", e))?;
+
+    Ok(output.uri.into_static())
+}
+
+/// "Share to Bluesky" step shown in the publish success panel.
+///
+/// Click-through: button, then an in-panel preview of the post text and
+/// link card, then a confirmation link to the created post.
+#[component]
+pub fn ShareToBskyButton(
+    title: String,
+    url: String,
+    description: String,
+    og_image_url: Option<String>,
+) -> Element {
+    let fetcher = use_context::<Fetcher>();
+    let mut show_preview = use_signal(|| false);
+    let mut posting = use_signal(|| false);
+    let mut posted = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    if let Some(post_url) = posted() {
+        return rsx! {
+            div { class: "share-bsky-done",
+                p { "Posted to Bluesky." }
+                a { href: "{post_url}", target: "_blank", "View post → " }
+            }
+        };
+    }
+
+    if !show_preview() {
+        return rsx! {
+            button {
+                class: "share-bsky-button",
+                onclick: move |_| show_preview.set(true),
+                "Share to Bluesky"
+            }
+        };
+    }
+
+    let (text, _) = compose_post_text(&title, &url);
+
+    rsx! {
+        div { class: "share-bsky-preview", aria_label: "Bluesky post preview",
+            p { class: "share-bsky-text", "{text}" }
+            div { class: "share-bsky-card",
+                if let Some(og) = og_image_url.as_ref() {
+                    img { class: "share-bsky-card-thumb", src: "{og}", alt: "" }
+                }
+                div { class: "share-bsky-card-body",
+                    span { class: "share-bsky-card-title", "{title}" }
+                    span { class: "share-bsky-card-desc", "{description}" }
+                    span { class: "share-bsky-card-url", "{url}" }
+                }
+            }
+            if let Some(err) = error() {
+                p { class: "share-bsky-error", "{err}" }
+            }
+            div { class: "share-bsky-actions",
+                button {
+                    class: "publish-cancel",
+                    disabled: posting(),
+                    onclick: move |_| show_preview.set(false),
+                    "Cancel"
+                }
+                button {
+                    class: "publish-submit",
+                    disabled: posting(),
+                    onclick: {
+                        let fetcher = fetcher.clone();
+                        let title = title.clone();
+                        let url = url.clone();
+                        let description = description.clone();
+                        let og_image_url = og_image_url.clone();
+                        move |_| {
+                            let fetcher = fetcher.clone();
+                            let title = title.clone();
+                            let url = url.clone();
+                            let description = description.clone();
+                            let og_image_url = og_image_url.clone();
+                            posting.set(true);
+                            error.set(None);
+                            spawn(async move {
+                                match share_entry_to_bsky(
+                                    &fetcher,
+                                    &title,
+                                    &url,
+                                    &description,
+                                    og_image_url.as_deref(),
+                                )
+                                .await
+                                {
+                                    Ok(uri) => posted.set(Some(bsky_post_web_url(&uri))),
+                                    Err(e) => error.set(Some(e)),
+                                }
+                                posting.set(false);
+                            });
+                        }
+                    },
+                    if posting() {
+                        "Posting..."
+                    } else {
+                        "Post"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose_facets_title_and_url() {
+        let (text, ranges) = compose_post_text("My Entry", "https://example.com/e/1");
+        assert_eq!(text, "My Entry\n\nhttps://example.com/e/1");
+        assert_eq!(&text[ranges[0].0..ranges[0].1], "My Entry");
+        assert_eq!(&text[ranges[1].0..ranges[1].1], "https://example.com/e/1");
+    }
+
+    #[test]
+    fn test_compose_uses_byte_offsets() {
+        // Multi-byte title: facet indices must be byte offsets, so the
+        // ranges still slice cleanly.
+        let (text, ranges) = compose_post_text("Caf\u{e9} notes", "https://x.y/e/2");
+        assert_eq!(&text[ranges[0].0..ranges[0].1], "Caf\u{e9} notes");
+        assert_eq!(&text[ranges[1].0..ranges[1].1], "https://x.y/e/2");
+    }
+}
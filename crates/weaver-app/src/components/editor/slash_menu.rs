@@ -0,0 +1,195 @@
+//! Slash command insert menu.
+//!
+//! Typing `/` at the start of a line opens a small dropdown of block
+//! snippets (headings, code block, table, and so on). The menu itself is
+//! deliberately dumb: the editor component owns the open/closed state and
+//! drives selection from its keydown handler, while this module supplies
+//! the command table, the fuzzy filter, and the dropdown markup. The typed
+//! `/query` lives in the document like ordinary text and is replaced in
+//! one [`EditorAction::Insert`] when a command is picked, so undo restores
+//! it in a single step.
+
+use super::actions::{EditorAction, Range, execute_action};
+use super::document::{CursorState, SignalEditorDocument};
+use dioxus::prelude::*;
+
+/// State while the menu is open.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlashMenuState {
+    /// Char offset of the `/` that opened the menu.
+    pub anchor: usize,
+    /// Index into the filtered command list.
+    pub selected: usize,
+}
+
+/// One insertable block snippet.
+#[derive(Debug, PartialEq)]
+pub struct SlashCommand {
+    pub label: &'static str,
+    /// Extra words the fuzzy filter matches besides the label.
+    pub keywords: &'static str,
+    /// Markdown inserted in place of the typed `/query`.
+    pub snippet: &'static str,
+    /// Caret position within the snippet after insertion, in chars.
+    pub caret: usize,
+}
+
+pub const SLASH_COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        label: "Heading 1",
+        keywords: "h1 title",
+        snippet: "# ",
+        caret: 2,
+    },
+    SlashCommand {
+        label: "Heading 2",
+        keywords: "h2 section",
+        snippet: "## ",
+        caret: 3,
+    },
+    SlashCommand {
+        label: "Heading 3",
+        keywords: "h3 subsection",
+        snippet: "### ",
+        caret: 4,
+    },
+    SlashCommand {
+        label: "Code block",
+        keywords: "fence pre source",
+        snippet: "```\n\n```",
+        caret: 4,
+    },
+    SlashCommand {
+        label: "Table",
+        keywords: "grid columns csv",
+        snippet: "| Column | Column |\n| ------ | ------ |\n|  |  |",
+        caret: 2,
+    },
+    SlashCommand {
+        label: "Image",
+        keywords: "picture photo upload",
+        snippet: "![alt](url)",
+        caret: 2,
+    },
+    SlashCommand {
+        label: "Embed record",
+        keywords: "at uri post bluesky",
+        snippet: "![[at://]]",
+        caret: 8,
+    },
+    SlashCommand {
+        label: "Callout",
+        keywords: "note admonition aside warning",
+        snippet: "> [!note] ",
+        caret: 10,
+    },
+    SlashCommand {
+        label: "Math block",
+        keywords: "latex katex equation",
+        snippet: "$$\n\n$$",
+        caret: 3,
+    },
+];
+
+/// Commands whose label or keywords fuzzy-match the query, in table order.
+pub fn filter_commands(query: &str) -> Vec<&'static SlashCommand> {
+    SLASH_COMMANDS
+        .iter()
+        .filter(|cmd| matches_query(query, cmd))
+        .collect()
+}
+
+/// Case-insensitive subsequence match against label plus keywords, so
+/// "h2" hits "Heading 2" and "eq" hits the math block via "equation".
+fn matches_query(query: &str, cmd: &SlashCommand) -> bool {
+    let haystack = format!("{} {}", cmd.label, cmd.keywords).to_lowercase();
+    let mut haystack = haystack.chars();
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .all(|q| haystack.any(|h| h == q))
+}
+
+/// Replaces the typed `/query` with the command snippet and places the
+/// caret at the command's editing position.
+pub fn insert_slash_command(doc: &mut SignalEditorDocument, anchor: usize, cmd: &SlashCommand) {
+    let cursor = doc.cursor.read().offset;
+    let range = Range::new(anchor, cursor.max(anchor));
+    execute_action(
+        doc,
+        &EditorAction::Insert {
+            text: cmd.snippet.to_string(),
+            range,
+        },
+    );
+    doc.cursor.set(CursorState {
+        offset: anchor + cmd.caret,
+        ..Default::default()
+    });
+}
+
+/// The dropdown itself; selection and dismissal are driven by the editor.
+#[component]
+pub fn SlashMenu(query: String, selected: usize, on_pick: EventHandler<usize>) -> Element {
+    let filtered = filter_commands(&query);
+    if filtered.is_empty() {
+        return rsx! {};
+    }
+    let selected = selected.min(filtered.len() - 1);
+    rsx! {
+        div {
+            class: "slash-menu",
+            role: "listbox",
+            aria_label: "Insert block",
+            for (i, cmd) in filtered.iter().enumerate() {
+                button {
+                    key: "{cmd.label}",
+                    class: "slash-menu-item",
+                    class: if i == selected { "selected" },
+                    role: "option",
+                    aria_selected: if i == selected { "true" } else { "false" },
+                    // Mousedown instead of click so the editor keeps focus
+                    // (a click would blur the contenteditable first).
+                    onmousedown: move |evt| {
+                        evt.prevent_default();
+                        on_pick.call(i);
+                    },
+                    "{cmd.label}"
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        assert_eq!(filter_commands("").len(), SLASH_COMMANDS.len());
+    }
+
+    #[test]
+    fn test_fuzzy_filter_matches_labels_and_keywords() {
+        let labels: Vec<&str> = filter_commands("h2").iter().map(|c| c.label).collect();
+        assert!(labels.contains(&"Heading 2"));
+
+        let labels: Vec<&str> = filter_commands("latex").iter().map(|c| c.label).collect();
+        assert_eq!(labels, vec!["Math block"]);
+
+        assert!(filter_commands("zzzz").is_empty());
+    }
+
+    #[test]
+    fn test_carets_stay_within_snippets() {
+        for cmd in SLASH_COMMANDS {
+            assert!(
+                cmd.caret <= cmd.snippet.chars().count(),
+                "caret out of range for {}",
+                cmd.label
+            );
+        }
+    }
+}
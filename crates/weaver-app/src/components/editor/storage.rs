@@ -228,10 +228,59 @@ pub fn load_snapshot_from_storage(_key: &str) -> Option<LocalSnapshotData> {
     None
 }
 
+/// Export the document as a portable backup blob.
+///
+/// Unlike [`save_to_storage`], this is not tied to LocalStorage or a
+/// draft key: the bytes are a self-contained
+/// [`DocumentSnapshot`](weaver_editor_crdt::DocumentSnapshot) suitable
+/// for downloading as a file, transferring to another account, or
+/// offline history inspection.
+pub fn export_backup(doc: &SignalEditorDocument) -> Result<Vec<u8>, String> {
+    use weaver_editor_crdt::CrdtDocument;
+    doc.export_document_snapshot()
+        .to_bytes()
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a document from a backup blob produced by [`export_backup`].
+///
+/// The returned document carries the backup's full edit history but no
+/// sync pointers — it syncs as this account's own document, regardless
+/// of which account exported it.
+pub fn import_backup(bytes: &[u8]) -> Result<SignalEditorDocument, String> {
+    let snapshot =
+        weaver_editor_crdt::DocumentSnapshot::from_bytes(bytes).map_err(|e| e.to_string())?;
+    Ok(SignalEditorDocument::from_snapshot(
+        &snapshot.snapshot,
+        None,
+        0,
+    ))
+}
+
+/// Prefix for recorded draft conflict resolutions.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+const CONFLICT_KEY_PREFIX: &str = "weaver_draft_conflict:";
+
+/// Record which way the user resolved a draft conflict (WASM only).
+///
+/// Purely diagnostic: the resolved content is persisted through the normal
+/// autosave path, but keeping the last choice around makes "my draft lost
+/// my edits" reports debuggable.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn record_conflict_resolution(key: &str, resolution: &str) {
+    let _ = LocalStorage::set(format!("{}{}", CONFLICT_KEY_PREFIX, key), resolution);
+}
+
+/// Record a draft conflict resolution (non-WASM stub).
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn record_conflict_resolution(_key: &str, _resolution: &str) {}
+
 /// Delete a draft from LocalStorage (WASM only).
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 pub fn delete_draft(key: &str) {
     LocalStorage::delete(storage_key(key));
+    // A recorded conflict choice is meaningless without its draft.
+    LocalStorage::delete(format!("{}{}", CONFLICT_KEY_PREFIX, key));
 }
 
 /// List all draft keys from LocalStorage (WASM only).
@@ -478,6 +478,7 @@ pub async fn load_and_merge_document(
                 last_seen_diffs: std::collections::HashMap::new(),
                 resolved_content,
                 notebook_uri: local.notebook_uri, // Restored from localStorage
+                conflict: None,
             }))
         }
 
@@ -519,6 +520,7 @@ pub async fn load_and_merge_document(
                 last_seen_diffs: pds.last_seen_diffs,
                 resolved_content,
                 notebook_uri: None, // PDS-only, notebook context comes from target_notebook
+                conflict: None,
             }))
         }
 
@@ -538,6 +540,20 @@ pub async fn load_and_merge_document(
             }
             let pds_version = pds_doc.oplog_vv();
 
+            // Reconstruct the local side on its own so divergence can be
+            // detected before the merge erases the distinction.
+            let local_doc = LoroDoc::new();
+            if let Err(e) = local_doc.import(&local.snapshot) {
+                tracing::warn!(
+                    "Failed to import local snapshot for conflict check: {:?}",
+                    e
+                );
+            }
+            let conflict = detect_draft_conflict(&local_doc, &pds_doc);
+            if conflict.is_some() {
+                tracing::info!("Draft diverged between localStorage and PDS; deferring to user");
+            }
+
             // Now create the merged doc
             let doc = LoroDoc::new();
 
@@ -572,11 +588,106 @@ pub async fn load_and_merge_document(
                 last_seen_diffs: pds.last_seen_diffs,
                 resolved_content,
                 notebook_uri: local.notebook_uri, // Restored from localStorage
+                conflict,
             }))
         }
     }
 }
 
+// ============================================================================
+// Draft conflict detection and resolution
+// ============================================================================
+
+/// A divergence between the local and PDS copies of a draft.
+///
+/// Carried through [`LoadedDocState`] so the editor can ask the user which
+/// side to keep instead of presenting the silent CRDT merge as their text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DraftConflict {
+    /// Markdown content as stored in localStorage.
+    pub local_content: String,
+    /// Markdown content as reconstructed from PDS edit state.
+    pub remote_content: String,
+}
+
+/// How the user chose to resolve a [`DraftConflict`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Keep the local text, discarding the remote side's changes.
+    KeepLocal,
+    /// Keep the remote text, discarding the local side's changes.
+    KeepRemote,
+    /// Accept the CRDT merge of both sides.
+    Merge,
+}
+
+impl ConflictResolution {
+    /// Stable string form used when recording the choice in localStorage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictResolution::KeepLocal => "keep-local",
+            ConflictResolution::KeepRemote => "keep-remote",
+            ConflictResolution::Merge => "merge",
+        }
+    }
+}
+
+/// Detect whether the local and PDS reconstructions of a draft diverged.
+///
+/// Returns `None` when one side's history already contains the other (the
+/// merge is a fast-forward) or when both sides render the same content
+/// (e.g. metadata-only edits); neither case needs user input.
+pub fn detect_draft_conflict(local_doc: &LoroDoc, pds_doc: &LoroDoc) -> Option<DraftConflict> {
+    let local_vv = local_doc.oplog_vv();
+    let pds_vv = pds_doc.oplog_vv();
+    if local_vv.includes_vv(&pds_vv) || pds_vv.includes_vv(&local_vv) {
+        return None;
+    }
+
+    let local_content = local_doc.get_text("content").to_string();
+    let remote_content = pds_doc.get_text("content").to_string();
+    if local_content == remote_content {
+        return None;
+    }
+
+    Some(DraftConflict {
+        local_content,
+        remote_content,
+    })
+}
+
+/// Apply the user's resolution on top of the merged document.
+///
+/// "Keep" choices are expressed as a fresh edit that rewrites the content
+/// container to the chosen side. Overwriting instead of rolling back keeps
+/// the operation log intact, so the decision survives future merges with a
+/// peer that still holds the discarded side.
+pub fn apply_conflict_resolution(
+    doc: &LoroDoc,
+    conflict: &DraftConflict,
+    resolution: ConflictResolution,
+) {
+    let chosen = match resolution {
+        // The merged doc already contains both sides.
+        ConflictResolution::Merge => return,
+        ConflictResolution::KeepLocal => &conflict.local_content,
+        ConflictResolution::KeepRemote => &conflict.remote_content,
+    };
+
+    let text = doc.get_text("content");
+    if text.to_string() == *chosen {
+        return;
+    }
+    if let Err(e) = text.delete(0, text.len_unicode()) {
+        tracing::warn!("Failed to clear merged content: {:?}", e);
+        return;
+    }
+    if let Err(e) = text.insert(0, chosen) {
+        tracing::warn!("Failed to write resolved content: {:?}", e);
+    }
+    doc.commit();
+}
+
 // ============================================================================
 // Sync UI Components
 // ============================================================================
@@ -755,6 +866,20 @@ pub fn SyncStatus(props: SyncStatusProps) -> Element {
             return;
         }
 
+        // Throttle edit record creation - a runaway trigger loop would
+        // otherwise write a diff record on every pass forever. A refused
+        // sync stays Unsynced and retries on the next trigger once the
+        // window refills; no edits are lost.
+        if let Ok(Err(e)) = super::COLLAB_QUOTA
+            .lock()
+            .map(|mut q| q.try_create_diff(weaver_editor_crdt::now_ms()))
+        {
+            last_error.set(Some(e.to_string()));
+            sync_state.set(SyncState::Unsynced);
+            tracing::warn!("Sync throttled: {e}");
+            return;
+        }
+
         sync_state.set(SyncState::Syncing);
 
         let mut doc = doc_for_sync.clone();
@@ -909,6 +1034,65 @@ pub fn SyncStatus(props: SyncStatusProps) -> Element {
     }
 }
 
+/// Side-by-side resolution dialog for a diverged draft.
+///
+/// Shown before the editor mounts when [`load_and_merge_document`] found
+/// independent edits in localStorage and on the PDS, so the merged text is
+/// never silently presented as the user's own. Dismissing the dialog keeps
+/// the CRDT merge, which was the previous default behaviour.
+#[component]
+pub fn DraftConflictDialog(
+    conflict: DraftConflict,
+    on_resolve: EventHandler<ConflictResolution>,
+) -> Element {
+    use crate::components::button::{Button, ButtonVariant};
+    use crate::components::dialog::{DialogContent, DialogDescription, DialogRoot, DialogTitle};
+
+    rsx! {
+        DialogRoot {
+            open: true,
+            on_open_change: move |open: bool| {
+                if !open {
+                    on_resolve.call(ConflictResolution::Merge);
+                }
+            },
+            DialogContent {
+                DialogTitle { "This draft changed in two places" }
+                DialogDescription {
+                    "The copy saved in this browser and the copy synced to your PDS have both been edited. Keep one version, or merge the two."
+                }
+                div { class: "conflict-compare",
+                    div { class: "conflict-pane",
+                        h3 { "In this browser" }
+                        pre { class: "conflict-content", "{conflict.local_content}" }
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            onclick: move |_| on_resolve.call(ConflictResolution::KeepLocal),
+                            "Keep this version"
+                        }
+                    }
+                    div { class: "conflict-pane",
+                        h3 { "Synced to your PDS" }
+                        pre { class: "conflict-content", "{conflict.remote_content}" }
+                        Button {
+                            variant: ButtonVariant::Primary,
+                            onclick: move |_| on_resolve.call(ConflictResolution::KeepRemote),
+                            "Keep this version"
+                        }
+                    }
+                }
+                div { class: "dialog-actions",
+                    Button {
+                        variant: ButtonVariant::Ghost,
+                        onclick: move |_| on_resolve.call(ConflictResolution::Merge),
+                        "Merge both"
+                    }
+                }
+            }
+        }
+    }
+}
+
 // === Editor state loading ===
 
 /// Result of loading editor state.
@@ -1023,6 +1207,7 @@ pub async fn load_editor_state(
                 last_seen_diffs: HashMap::new(),
                 resolved_content: weaver_common::ResolvedContent::default(),
                 notebook_uri,
+                conflict: None,
             })
         }
         Err(e) => {
@@ -1032,6 +1217,43 @@ pub async fn load_editor_state(
     }
 }
 
+/// Load editor state for the unauthenticated demo without touching the PDS.
+///
+/// Demo documents live entirely in localStorage: returning visitors get
+/// their previous edits back, first-time visitors get the sample content.
+/// No fetcher is involved, so the demo editor bootstraps before (and
+/// without) any session restore.
+pub fn load_demo_state(draft_key: &str, initial_content: Option<&str>) -> LoadedDocState {
+    use super::storage::load_snapshot_from_storage;
+
+    let doc = LoroDoc::new();
+    match load_snapshot_from_storage(draft_key) {
+        Some(local) => {
+            if let Err(e) = doc.import(&local.snapshot) {
+                tracing::warn!("Failed to import demo snapshot: {:?}", e);
+            }
+        }
+        None => {
+            if let Some(content) = initial_content {
+                doc.get_text("content").insert(0, content).ok();
+                doc.commit();
+            }
+        }
+    }
+
+    LoadedDocState {
+        doc,
+        entry_ref: None,
+        edit_root: None,
+        last_diff: None,
+        synced_version: None,
+        last_seen_diffs: HashMap::new(),
+        resolved_content: weaver_common::ResolvedContent::default(),
+        notebook_uri: None,
+        conflict: None,
+    }
+}
+
 /// Create LoadedDocState from a loaded entry.
 ///
 /// Handles:
@@ -1124,6 +1346,7 @@ async fn create_state_from_entry(
         last_seen_diffs: HashMap::new(),
         resolved_content,
         notebook_uri,
+        conflict: None,
     }
 }
 
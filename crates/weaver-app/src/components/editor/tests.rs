@@ -1186,3 +1186,106 @@ fn test_multiple_paragraphs_different_directions() {
     // Third paragraph should be LTR
     assert!(result[2].html.contains("dir=\"ltr\""));
 }
+
+#[test]
+fn test_detect_draft_conflict_fast_forward_is_not_a_conflict() {
+    use super::sync::detect_draft_conflict;
+    use loro::LoroDoc;
+
+    let base = LoroDoc::new();
+    base.get_text("content").insert(0, "shared base").unwrap();
+    base.commit();
+    let snapshot = base.export(loro::ExportMode::Snapshot).unwrap();
+
+    // Remote is strictly ahead of local: local saw nothing remote hasn't.
+    let local = LoroDoc::new();
+    local.import(&snapshot).unwrap();
+    let remote = LoroDoc::new();
+    remote.import(&snapshot).unwrap();
+    remote.get_text("content").insert(11, " plus more").unwrap();
+    remote.commit();
+
+    assert!(detect_draft_conflict(&local, &remote).is_none());
+    assert!(detect_draft_conflict(&remote, &local).is_none());
+}
+
+#[test]
+fn test_detect_draft_conflict_divergent_content() {
+    use super::sync::detect_draft_conflict;
+    use loro::LoroDoc;
+
+    let base = LoroDoc::new();
+    base.get_text("content").insert(0, "shared base").unwrap();
+    base.commit();
+    let snapshot = base.export(loro::ExportMode::Snapshot).unwrap();
+
+    let local = LoroDoc::new();
+    local.import(&snapshot).unwrap();
+    local.get_text("content").insert(0, "local edit: ").unwrap();
+    local.commit();
+
+    let remote = LoroDoc::new();
+    remote.import(&snapshot).unwrap();
+    remote
+        .get_text("content")
+        .insert(0, "remote edit: ")
+        .unwrap();
+    remote.commit();
+
+    let conflict = detect_draft_conflict(&local, &remote).expect("divergent edits should conflict");
+    assert_eq!(conflict.local_content, "local edit: shared base");
+    assert_eq!(conflict.remote_content, "remote edit: shared base");
+}
+
+#[test]
+fn test_detect_draft_conflict_ignores_metadata_only_divergence() {
+    use super::sync::detect_draft_conflict;
+    use loro::LoroDoc;
+
+    let base = LoroDoc::new();
+    base.get_text("content").insert(0, "shared base").unwrap();
+    base.commit();
+    let snapshot = base.export(loro::ExportMode::Snapshot).unwrap();
+
+    // Histories diverge, but only in the title container: the content the
+    // user would be asked about is identical on both sides.
+    let local = LoroDoc::new();
+    local.import(&snapshot).unwrap();
+    local.get_text("title").insert(0, "local title").unwrap();
+    local.commit();
+
+    let remote = LoroDoc::new();
+    remote.import(&snapshot).unwrap();
+    remote.get_text("title").insert(0, "remote title").unwrap();
+    remote.commit();
+
+    assert!(detect_draft_conflict(&local, &remote).is_none());
+}
+
+#[test]
+fn test_apply_conflict_resolution_rewrites_content() {
+    use super::sync::{ConflictResolution, DraftConflict, apply_conflict_resolution};
+    use loro::LoroDoc;
+
+    let conflict = DraftConflict {
+        local_content: "local version".to_string(),
+        remote_content: "remote version".to_string(),
+    };
+
+    let merged = LoroDoc::new();
+    merged
+        .get_text("content")
+        .insert(0, "locremoteal version")
+        .unwrap();
+    merged.commit();
+
+    apply_conflict_resolution(&merged, &conflict, ConflictResolution::KeepLocal);
+    assert_eq!(merged.get_text("content").to_string(), "local version");
+
+    apply_conflict_resolution(&merged, &conflict, ConflictResolution::KeepRemote);
+    assert_eq!(merged.get_text("content").to_string(), "remote version");
+
+    // Merge keeps whatever the merged doc already holds.
+    apply_conflict_resolution(&merged, &conflict, ConflictResolution::Merge);
+    assert_eq!(merged.get_text("content").to_string(), "remote version");
+}
@@ -1,8 +1,8 @@
 //! Editor toolbar component with formatting buttons.
 
-use weaver_editor_core::FormatAction;
 use super::image_upload::{ImageUploadButton, UploadedImage};
 use dioxus::prelude::*;
+use weaver_editor_core::FormatAction;
 
 /// Editor toolbar with formatting buttons.
 ///
@@ -18,6 +18,9 @@ pub fn EditorToolbar(
             role: "toolbar",
             aria_label: "Text formatting",
             aria_orientation: "vertical",
+            // Standard toolbar keyboard pattern: arrows move between
+            // buttons, Home/End jump to the ends.
+            onkeydown: move |evt| crate::components::focus::roving_focus(".editor-toolbar", &evt),
             button {
                 class: "toolbar-button",
                 title: "Bold (Ctrl+B)",
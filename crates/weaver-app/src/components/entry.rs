@@ -46,6 +46,9 @@ pub fn EntryPage(
     #[cfg(feature = "fullstack-server")]
     let mut entry_res = entry_res?;
 
+    #[cfg(not(feature = "fullstack-server"))]
+    let mut entry_res = entry_res;
+
     #[cfg(feature = "fullstack-server")]
     use_effect(use_reactive!(|title| {
         if title != last_title() {
@@ -75,7 +78,8 @@ pub fn EntryPage(
                 book_entry_view: book_entry_view.clone(),
                 entry_record: entry_record.clone(),
                 ident: ident(),
-                book_title: book_title()
+                book_title: book_title(),
+                on_refresh: move |_| entry_res.restart(),
             } }
         }
         _ => rsx! { p { "Loading..." } },
@@ -91,36 +95,13 @@ pub fn calculate_reading_stats(content: &str) -> (usize, usize) {
 
 /// Extract a plain-text preview from markdown content (first ~160 chars)
 pub fn extract_preview(content: &str, max_len: usize) -> String {
-    // Simple extraction: skip markdown syntax, get plain text
-    let plain: String = content
-        .lines()
-        .filter(|line| {
-            let trimmed = line.trim();
-            // Skip headings, images, links, code blocks
-            !trimmed.starts_with('#')
-                && !trimmed.starts_with('!')
-                && !trimmed.starts_with("```")
-                && !trimmed.is_empty()
-        })
-        .take(5)
-        .collect::<Vec<_>>()
-        .join(" ");
-
-    // Clean up markdown inline syntax
-    let cleaned = plain
-        .replace("**", "")
-        .replace("__", "")
-        .replace('*', "")
-        .replace('_', "")
-        .replace('`', "");
-
-    if cleaned.len() <= max_len {
-        cleaned
-    } else {
-        // Use char boundary-safe truncation to avoid panic on multibyte chars
-        let truncated: String = cleaned.chars().take(max_len - 3).collect();
-        format!("{}...", truncated)
-    }
+    // Shares the renderer's summary extraction so cards, OG tags and feeds
+    // describe an entry identically (frontmatter `summary:` wins, then the
+    // first paragraph cut at a sentence boundary).
+    let meta = weaver_renderer::metadata::parse_metadata(content);
+    meta.summary(max_len)
+        .map(|summary| weaver_renderer::metadata::truncate_at_sentence(&summary, max_len))
+        .unwrap_or_default()
 }
 
 /// OpenGraph and Twitter Card meta tags for entries
@@ -162,6 +143,7 @@ fn EntryPageView(
     entry_record: ReadSignal<entry::Entry<'static>>,
     ident: ReadSignal<AtIdentifier<'static>>,
     book_title: ReadSignal<SmolStr>,
+    #[props(default)] on_refresh: Option<EventHandler<()>>,
 ) -> Element {
     // Extract metadata
     let entry_view = &book_entry_view().entry;
@@ -213,6 +195,153 @@ fn EntryPageView(
 
     tracing::info!("Entry: {book_title} - {title}");
 
+    // Reading position persistence: restore the saved offset for this entry
+    // CID and keep it updated while the reader scrolls (browser only).
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    let mut scroll_closure: Signal<Option<wasm_bindgen::closure::Closure<dyn FnMut()>>> =
+        use_signal(|| None);
+
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    use_effect(move || {
+        use std::cell::Cell;
+        use std::rc::Rc;
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen::closure::Closure;
+
+        let view = book_entry_view();
+        let cid = view.entry.cid.to_string();
+        let title = view
+            .entry
+            .title
+            .as_ref()
+            .map(|t| t.as_ref().to_string())
+            .unwrap_or_else(|| "Untitled".to_string());
+        let entry_path = view
+            .entry
+            .path
+            .as_ref()
+            .map(|p| p.as_ref().to_string())
+            .unwrap_or_else(|| title.clone());
+        let ident_str = ident().to_string();
+        let book = book_title().to_string();
+
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        // Replace any listener from a previous entry (prev/next navigation).
+        if let Some(old) = scroll_closure.take() {
+            let _ =
+                window.remove_event_listener_with_callback("scroll", old.as_ref().unchecked_ref());
+        }
+
+        // Restore the saved offset once the async-rendered content is tall
+        // enough to hold it. One-shot retries instead of an interval so
+        // nothing keeps running afterwards; the done flag also stops later
+        // attempts from yanking the page once the reader scrolls themselves.
+        if let Some(pos) = crate::data::load_reading_position(&cid) {
+            if pos.scroll_y > 0.0 {
+                let target = pos.scroll_y;
+                let done = Rc::new(Cell::new(false));
+                for delay in [50u32, 300, 1000, 2500] {
+                    let done = done.clone();
+                    gloo_timers::callback::Timeout::new(delay, move || {
+                        if done.get() {
+                            return;
+                        }
+                        let Some(window) = web_sys::window() else {
+                            return;
+                        };
+                        if window.scroll_y().unwrap_or(0.0) > 100.0 {
+                            // The reader already moved; stop restoring.
+                            done.set(true);
+                            return;
+                        }
+                        let height = window
+                            .document()
+                            .and_then(|d| d.document_element())
+                            .map(|e| e.scroll_height() as f64)
+                            .unwrap_or(0.0);
+                        let viewport = window
+                            .inner_height()
+                            .ok()
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(0.0);
+                        if height >= target + viewport {
+                            window.scroll_to_with_x_and_y(0.0, target);
+                            done.set(true);
+                        }
+                    })
+                    .forget();
+                }
+            }
+        }
+
+        // Throttled scroll listener persisting the current offset.
+        let last_save = Rc::new(Cell::new(0.0f64));
+        let closure: Closure<dyn FnMut()> = Closure::wrap(Box::new(move || {
+            let now = js_sys::Date::now();
+            if now - last_save.get() < 500.0 {
+                return;
+            }
+            last_save.set(now);
+
+            let Some(window) = web_sys::window() else {
+                return;
+            };
+            let scroll_y = window.scroll_y().unwrap_or(0.0);
+            let height = window
+                .document()
+                .and_then(|d| d.document_element())
+                .map(|e| e.scroll_height() as f64)
+                .unwrap_or(0.0);
+            let viewport = window
+                .inner_height()
+                .ok()
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let progress = if height > 0.0 {
+                ((scroll_y + viewport) / height).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+
+            // Finished or barely-started entries don't belong in the
+            // continue-reading list.
+            if progress >= 0.98 || scroll_y < 100.0 {
+                crate::data::delete_reading_position(&cid);
+                return;
+            }
+
+            crate::data::save_reading_position(&crate::data::ReadingPosition {
+                cid: cid.clone(),
+                ident: ident_str.clone(),
+                book_title: book.clone(),
+                entry_path: entry_path.clone(),
+                title: title.clone(),
+                scroll_y,
+                progress,
+                updated_at: now,
+            });
+        }) as Box<dyn FnMut()>);
+
+        let _ = window.add_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref());
+        scroll_closure.set(Some(closure));
+    });
+
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    use_drop(move || {
+        if let Some(closure) = scroll_closure.take() {
+            if let Some(window) = web_sys::window() {
+                use wasm_bindgen::JsCast;
+                let _ = window.remove_event_listener_with_callback(
+                    "scroll",
+                    closure.as_ref().unchecked_ref(),
+                );
+            }
+        }
+    });
+
     rsx! {
         EntryOgMeta {
             title: title.to_string(),
@@ -268,6 +397,14 @@ fn EntryPageView(
             // Main content area
             div { class: "entry-content-wrapper",
                 div { class: "entry-content-main notebook-content",
+                    crate::components::ViewBeacon { uri: entry_view.uri.clone().into_static() }
+                    if let Some(on_refresh) = on_refresh {
+                        crate::components::StaleBanner {
+                            uri: entry_view.uri.clone().into_static(),
+                            cid: entry_view.cid.clone().into_static(),
+                            on_refresh: move |_| on_refresh.call(()),
+                        }
+                    }
                     EntryMarkdown {
                         content: entry_record,
                         ident
@@ -295,6 +432,15 @@ fn EntryPageView(
                     }
                 }
             }
+
+            crate::components::EntryHistorySection {
+                entry_uri: entry_view.uri.clone().into_static(),
+            }
+
+            crate::components::CommentsSection {
+                entry_uri: entry_view.uri.clone().into_static(),
+                entry_cid: entry_view.cid.clone().into_static(),
+            }
         }
     }
 }
@@ -615,6 +761,10 @@ pub fn EntryMetadata(
         header { class: "entry-metadata",
             div { class: "entry-header-row",
                 h1 { class: "entry-title", "{title}" }
+                crate::components::BookmarkButton {
+                    uri: entry_uri.clone(),
+                    cid: entry_view.cid.clone().into_static(),
+                }
                 EntryActions {
                     entry_uri: entry_uri.clone(),
                     entry_cid: entry_view.cid.clone().into_static(),
@@ -654,7 +804,14 @@ pub fn EntryMetadata(
                         div { class: "entry-tags",
                             span { class: "meta-label", "Tags:" }
                             for tag in tags.iter() {
-                                span { class: "entry-tag", "{tag}" }
+                                AppLink {
+                                    to: AppLinkTarget::Tag {
+                                        ident: ident.clone(),
+                                        tag: tag.as_ref().into(),
+                                    },
+                                    class: Some("entry-tag".to_string()),
+                                    "{tag}"
+                                }
                             }
                         }
                     }
@@ -0,0 +1,67 @@
+//! Route-level error fallback with classified messaging and retry.
+//!
+//! Error boundaries previously rendered one generic "An error occurred"
+//! for every failure. This fallback recovers the
+//! [`FetchErrorKind`] embedded in captured fetch errors and tailors the
+//! copy and recovery action: transient failures get a retry button
+//! (clearing the boundary remounts the route, which restarts its
+//! resources), missing records get a 404 page, and an expired session
+//! tells the user to sign in again.
+
+use dioxus::prelude::*;
+
+#[cfg(feature = "fullstack-server")]
+use {dioxus::fullstack::FullstackContext, http::StatusCode};
+
+use crate::fetch_error::FetchErrorKind;
+
+const ERROR_PAGE_CSS: Asset = asset!("/assets/styling/error_page.css");
+
+/// Classified fallback for a route-level `ErrorBoundary`.
+///
+/// Called from `handle_error` rather than mounted as a component: it
+/// needs the boundary's own [`ErrorContext`] to clear errors on retry.
+pub fn render_error(errors: ErrorContext) -> Element {
+    #[allow(unused_mut)]
+    let mut kind = errors
+        .error()
+        .map(|err| FetchErrorKind::classify(&err.to_string()))
+        .unwrap_or(FetchErrorKind::Other);
+
+    // On the server, also commit a real status code so crawlers and
+    // caches see an error response rather than a 200 with error copy.
+    #[cfg(feature = "fullstack-server")]
+    if let Some(err) = errors.error() {
+        let http_error = FullstackContext::commit_error_status(err);
+        if http_error.status == StatusCode::NOT_FOUND {
+            kind = FetchErrorKind::NotFound;
+        }
+    }
+
+    let heading = match kind {
+        FetchErrorKind::NotFound => "404 - page not found",
+        FetchErrorKind::Network => "Connection trouble",
+        FetchErrorKind::AuthExpired => "Session expired",
+        FetchErrorKind::RateLimited => "Slow down",
+        FetchErrorKind::Other => "Something went wrong",
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ERROR_PAGE_CSS }
+        div { class: "error-page", role: "alert",
+            h1 { class: "error-page-heading", "{heading}" }
+            p { class: "error-page-message", "{kind.user_message()}" }
+            div { class: "error-page-actions",
+                // Retrying a 404 cannot help; offer the way out instead.
+                if kind != FetchErrorKind::NotFound {
+                    button {
+                        class: "error-page-retry",
+                        onclick: move |_| errors.clear_errors(),
+                        "Try again"
+                    }
+                }
+                a { class: "error-page-home", href: "/", "Go home" }
+            }
+        }
+    }
+}
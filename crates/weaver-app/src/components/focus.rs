@@ -0,0 +1,156 @@
+//! Shared focus-management utilities for keyboard accessibility.
+//!
+//! Dialogs and toolbars implement the standard keyboard patterns with
+//! these helpers: trap Tab inside an open dialog, move focus into it on
+//! open, hand focus back to the trigger on close, and drive toolbars
+//! with arrow keys. All DOM access is gated to the browser so callers
+//! can invoke the helpers unconditionally; on the server they are
+//! no-ops and the rendered markup stays identical.
+
+use dioxus::prelude::keyboard_types::Key;
+use dioxus::prelude::*;
+
+/// Selector matching everything keyboard focus can land on.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+const FOCUSABLE: &str = "a[href], button:not([disabled]), input:not([disabled]), \
+     select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex='-1'])";
+
+/// Collect the focusable elements inside the first element matching
+/// `container`, in document (and therefore tab) order.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+fn focusables(container: &str) -> Vec<web_sys::HtmlElement> {
+    use wasm_bindgen::JsCast;
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return Vec::new();
+    };
+    let Ok(Some(container)) = document.query_selector(container) else {
+        return Vec::new();
+    };
+    let Ok(nodes) = container.query_selector_all(FOCUSABLE) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|i| nodes.get(i))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect()
+}
+
+/// Position of the currently focused element within `elements`, if the
+/// focus is inside the list at all.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+fn active_index(elements: &[web_sys::HtmlElement]) -> Option<usize> {
+    let active = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.active_element())?;
+    elements.iter().position(|elem| {
+        // JsValue equality is reference equality for objects, which is
+        // exactly the "same node" check we want here.
+        let elem: &wasm_bindgen::JsValue = elem.as_ref();
+        let active: &wasm_bindgen::JsValue = active.as_ref();
+        elem == active
+    })
+}
+
+/// Move focus to the first element matching `selector`.
+pub fn focus_element(selector: &str) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use wasm_bindgen::JsCast;
+        let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+            return;
+        };
+        if let Ok(Some(elem)) = document.query_selector(selector) {
+            if let Some(elem) = elem.dyn_ref::<web_sys::HtmlElement>() {
+                let _ = elem.focus();
+            }
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = selector;
+}
+
+/// Move focus to the first focusable element inside `container` (a CSS
+/// selector). Call from a `use_effect` when a dialog opens so keyboard
+/// users land inside it instead of on the page behind the overlay.
+pub fn focus_first_in(container: &str) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    if let Some(first) = focusables(container).first() {
+        let _ = first.focus();
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = container;
+}
+
+/// Keep Tab cycling inside `container` while a dialog is open.
+///
+/// Call from the dialog's `onkeydown`: Tab on the last focusable element
+/// wraps to the first, Shift+Tab on the first wraps to the last, and Tab
+/// from outside the container (or with nothing focusable inside) is
+/// swallowed so focus cannot escape to the page behind the overlay.
+pub fn trap_tab(container: &str, evt: &KeyboardEvent) {
+    if evt.key() != Key::Tab {
+        return;
+    }
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        let elements = focusables(container);
+        if elements.is_empty() {
+            evt.prevent_default();
+            return;
+        }
+        let shift = evt.modifiers().shift();
+        match active_index(&elements) {
+            Some(i) if !shift && i + 1 == elements.len() => {
+                evt.prevent_default();
+                let _ = elements[0].focus();
+            }
+            Some(0) if shift => {
+                evt.prevent_default();
+                let _ = elements[elements.len() - 1].focus();
+            }
+            Some(_) => {}
+            None => {
+                evt.prevent_default();
+                let _ = elements[0].focus();
+            }
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = container;
+}
+
+/// Arrow-key navigation for `role="toolbar"` widgets.
+///
+/// Call from the toolbar's `onkeydown`: arrows move focus between the
+/// toolbar's controls (wrapping at the ends, both axes handled so the
+/// same toolbar works in either orientation), Home/End jump to the
+/// first/last control. Other keys pass through untouched.
+pub fn roving_focus(container: &str, evt: &KeyboardEvent) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        let elements = focusables(container);
+        if elements.is_empty() {
+            return;
+        }
+        let current = active_index(&elements);
+        let target = match evt.key() {
+            Key::ArrowDown | Key::ArrowRight => Some(match current {
+                Some(i) => (i + 1) % elements.len(),
+                None => 0,
+            }),
+            Key::ArrowUp | Key::ArrowLeft => Some(match current {
+                Some(i) => (i + elements.len() - 1) % elements.len(),
+                None => elements.len() - 1,
+            }),
+            Key::Home => Some(0),
+            Key::End => Some(elements.len() - 1),
+            _ => None,
+        };
+        if let Some(i) = target {
+            evt.prevent_default();
+            let _ = elements[i].focus();
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = (container, evt);
+}
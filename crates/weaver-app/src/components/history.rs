@@ -0,0 +1,278 @@
+//! Version history for published entries.
+//!
+//! History is assembled from two sources. The CRDT edit chain (an
+//! `sh.weaver.edit.root` snapshot plus its diffs) is replayed one record
+//! at a time, giving the text after every edit. On top of that,
+//! collaborators can hold whole-record copies of the entry in their own
+//! repositories; those are surfaced as published versions. Any two
+//! versions on the timeline can be compared with [`TextDiff`].
+
+use dioxus::prelude::*;
+use jacquard::IntoStatic;
+use jacquard::smol_str::format_smolstr;
+use jacquard::types::collection::Collection;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::string::{AtUri, Datetime, Did};
+use weaver_api::sh_weaver::actor::ProfileDataViewInner;
+use weaver_api::sh_weaver::notebook::entry::Entry;
+use weaver_common::WeaverExt;
+use weaver_editor_crdt::{find_all_edit_roots, load_history};
+
+use crate::components::text_diff::TextDiff;
+use crate::fetch::Fetcher;
+
+const HISTORY_CSS: Asset = asset!("/assets/styling/history.css");
+
+/// One selectable version of the entry text.
+#[derive(Clone, PartialEq)]
+pub struct EntryVersion {
+    /// Stable key for selection: record URI, or root URI plus step index.
+    pub id: String,
+    /// Short label describing where the version came from.
+    pub label: String,
+    /// Repo the record producing this version lives in.
+    pub author: Option<Did<'static>>,
+    /// Timestamp where the source record carries one.
+    pub created_at: Option<Datetime>,
+    pub text: String,
+}
+
+/// Assemble the version timeline for one entry, oldest first.
+///
+/// Edit-chain steps come first in chain order, then whole-record
+/// collaborator versions oldest first — the chain is the editing history
+/// proper, while collaborator copies are concurrent published states
+/// rather than steps in a sequence. Sources that fail to load are
+/// skipped so a partial history still renders.
+async fn load_versions(fetcher: &Fetcher, entry_uri: &AtUri<'static>) -> Vec<EntryVersion> {
+    let client = fetcher.get_client();
+    let mut versions = Vec::new();
+
+    // Repos to search: the entry's own plus every collaborator's.
+    let owner = match entry_uri.authority() {
+        AtIdentifier::Did(did) => Some(did.clone().into_static()),
+        _ => None,
+    };
+    let collaborators = client
+        .find_collaborators_for_resource(entry_uri)
+        .await
+        .unwrap_or_default();
+    let mut all_dids: Vec<Did<'static>> = owner.into_iter().chain(collaborators).collect();
+    all_dids.sort();
+    all_dids.dedup();
+
+    // Edit chain: replay every root snapshot and its diffs.
+    let roots = find_all_edit_roots(client.as_ref(), entry_uri, all_dids.clone())
+        .await
+        .unwrap_or_default();
+    for root in roots {
+        let uri_str = format_smolstr!(
+            "at://{}/{}/{}",
+            root.did,
+            root.collection.as_str(),
+            root.rkey.0.as_str()
+        );
+        let Ok(root_uri) = AtUri::new(&uri_str) else {
+            continue;
+        };
+        let history = match load_history(client.as_ref(), &root_uri).await {
+            Ok(history) => history,
+            Err(e) => {
+                tracing::debug!("history: failed to load edit chain for {root_uri}: {e}");
+                continue;
+            }
+        };
+        for (index, step) in history.entries().iter().enumerate() {
+            let Ok(text) = history.text_at(index) else {
+                continue;
+            };
+            versions.push(EntryVersion {
+                id: match &step.uri {
+                    Some(uri) => uri.to_string(),
+                    None => format!("{root_uri}#{index}"),
+                },
+                label: if index == 0 {
+                    "Snapshot".to_string()
+                } else {
+                    format!("Edit {index}")
+                },
+                author: step.author.clone(),
+                created_at: None,
+                text,
+            });
+        }
+    }
+
+    // Collaborator versions: the full entry record in each repo,
+    // reversed so the latest-first search order becomes oldest first.
+    if let Some(rkey) = entry_uri.rkey() {
+        match client
+            .find_all_versions(Entry::NSID, rkey.0.as_str(), &all_dids)
+            .await
+        {
+            Ok(found) => {
+                for version in found.into_iter().rev() {
+                    let Ok(entry) = jacquard::from_data::<Entry>(&version.value) else {
+                        continue;
+                    };
+                    versions.push(EntryVersion {
+                        id: version.uri.to_string(),
+                        label: "Published version".to_string(),
+                        author: Some(version.did.clone().into_static()),
+                        created_at: version.updated_at.clone(),
+                        text: entry.content.as_ref().to_string(),
+                    });
+                }
+            }
+            Err(e) => {
+                tracing::debug!("history: collaborator version lookup failed: {e}");
+            }
+        }
+    }
+
+    versions
+}
+
+/// Collapsible history tab rendered under the entry content.
+///
+/// The timeline is only fetched once the tab is opened; walking the edit
+/// chain fetches every diff record, which is too expensive to do for
+/// readers who never look at it.
+#[component]
+pub fn EntryHistorySection(entry_uri: AtUri<'static>) -> Element {
+    let fetcher = use_context::<Fetcher>();
+    let mut open = use_signal(|| false);
+
+    let res_uri = entry_uri.clone();
+    let versions_res = use_resource(use_reactive!(|res_uri| {
+        let fetcher = fetcher.clone();
+        async move {
+            if !open() {
+                return None;
+            }
+            Some(load_versions(&fetcher, &res_uri).await)
+        }
+    }));
+
+    // Selected endpoints of the comparison; `None` means "use the
+    // default", which is the last two versions once they're loaded.
+    let mut from_sel = use_signal(|| None::<usize>);
+    let mut to_sel = use_signal(|| None::<usize>);
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: HISTORY_CSS }
+        section { class: "entry-history", aria_label: "History",
+            header { class: "entry-history-header",
+                h2 { class: "entry-history-heading", "History" }
+                button {
+                    class: "entry-history-toggle",
+                    onclick: move |_| {
+                        let next = !*open.peek();
+                        open.set(next);
+                    },
+                    if open() { "Hide history" } else { "Show history" }
+                }
+            }
+
+            if open() {
+                match &*versions_res.read() {
+                    Some(Some(versions)) if versions.is_empty() => rsx! {
+                        p { class: "entry-history-empty", "No edit history found for this entry." }
+                    },
+                    Some(Some(versions)) => {
+                        let count = versions.len();
+                        let from = from_sel().unwrap_or(count.saturating_sub(2)).min(count - 1);
+                        let to = to_sel().unwrap_or(count - 1).min(count - 1);
+                        rsx! {
+                            ol { class: "entry-history-timeline",
+                                for (index, version) in versions.iter().enumerate() {
+                                    li { key: "{version.id}", class: "entry-history-row",
+                                        // Radio pair picks the two endpoints of
+                                        // the comparison, wiki-history style.
+                                        input {
+                                            r#type: "radio",
+                                            name: "entry-history-from",
+                                            aria_label: "Compare from {version.label}",
+                                            checked: index == from,
+                                            onchange: move |_| from_sel.set(Some(index)),
+                                        }
+                                        input {
+                                            r#type: "radio",
+                                            name: "entry-history-to",
+                                            aria_label: "Compare to {version.label}",
+                                            checked: index == to,
+                                            onchange: move |_| to_sel.set(Some(index)),
+                                        }
+                                        VersionAuthor { did: version.author.clone() }
+                                        span { class: "entry-history-label", "{version.label}" }
+                                        if let Some(date) = version
+                                            .created_at
+                                            .as_ref()
+                                            .map(|d| d.as_ref().format("%B %d, %Y").to_string())
+                                        {
+                                            time { class: "entry-history-date", "{date}" }
+                                        }
+                                    }
+                                }
+                            }
+                            if count > 1 && from != to {
+                                h3 { class: "entry-history-diff-heading",
+                                    "{versions[from].label} → {versions[to].label}"
+                                }
+                                TextDiff {
+                                    old: versions[from].text.clone(),
+                                    new: versions[to].text.clone(),
+                                }
+                            }
+                        }
+                    },
+                    _ => rsx! {
+                        p { class: "entry-history-empty", "Loading history..." }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Avatar and handle for the author of one version.
+#[component]
+fn VersionAuthor(did: Option<Did<'static>>) -> Element {
+    let fetcher = use_context::<Fetcher>();
+    let profile_res = use_resource(use_reactive!(|did| {
+        let fetcher = fetcher.clone();
+        async move {
+            let did = did?;
+            fetcher.fetch_profile(&AtIdentifier::Did(did)).await.ok()
+        }
+    }));
+
+    let profile = profile_res().flatten();
+    let (avatar, handle) = match profile.as_ref().map(|p| &p.inner) {
+        Some(ProfileDataViewInner::ProfileView(p)) => {
+            (p.avatar.clone(), p.handle.as_ref().to_string())
+        }
+        Some(ProfileDataViewInner::ProfileViewDetailed(p)) => {
+            (p.avatar.clone(), p.handle.as_ref().to_string())
+        }
+        Some(ProfileDataViewInner::TangledProfileView(p)) => (None, p.handle.as_ref().to_string()),
+        _ => (None, "unknown".to_string()),
+    };
+
+    rsx! {
+        span { class: "entry-history-author", title: "@{handle}",
+            if let Some(avatar_url) = avatar {
+                img {
+                    class: "entry-history-avatar",
+                    src: avatar_url.as_ref(),
+                    alt: "@{handle}",
+                }
+            } else {
+                span { class: "entry-history-avatar entry-history-avatar-fallback",
+                    {handle.chars().next().unwrap_or('?').to_uppercase().to_string()}
+                }
+            }
+            span { class: "entry-history-handle", "@{handle}" }
+        }
+    }
+}
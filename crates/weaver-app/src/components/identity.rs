@@ -1,6 +1,8 @@
 use crate::auth::AuthState;
 use crate::components::css::DefaultNotebookCss;
-use crate::components::{AuthorList, FeedEntryCard, ProfileActions, ProfileActionsMenubar};
+use crate::components::{
+    AppLink, AppLinkTarget, AuthorList, FeedEntryCard, ProfileActions, ProfileActionsMenubar,
+};
 use crate::{Route, data, fetch};
 use dioxus::prelude::*;
 use jacquard::{smol_str::SmolStr, types::ident::AtIdentifier};
@@ -349,6 +351,20 @@ pub fn RepositoryIndex(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
     // Count standalone entries for stats
     let entry_count = use_memo(move || all_entries.read().as_ref().map(|e| e.len()).unwrap_or(0));
 
+    // Locally saved reading positions for this repository. Read in an
+    // effect so the server-rendered HTML (which has no localStorage)
+    // matches the client's first paint.
+    let mut reading_positions = use_signal(Vec::<data::ReadingPosition>::new);
+    use_effect(move || {
+        let ident_str = ident().to_string();
+        let positions: Vec<data::ReadingPosition> = data::list_reading_positions()
+            .into_iter()
+            .filter(|pos| pos.ident == ident_str)
+            .take(5)
+            .collect();
+        reading_positions.set(positions);
+    });
+
     // Build OG metadata when profile is available
     let og_meta = match &*profile.read() {
         Some(profile_view) => {
@@ -422,6 +438,41 @@ pub fn RepositoryIndex(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
                 ProfileActionsMenubar { ident }
 
                 div { class: "profile-timeline",
+                    // Continue-reading list from locally saved positions
+                    {
+                        let positions = reading_positions.read();
+                        if !positions.is_empty() {
+                            rsx! {
+                                div { class: "continue-reading-section",
+                                    h3 { class: "continue-reading-header", "Continue reading" }
+                                    for pos in positions.iter() {
+                                        {
+                                            let percent = (pos.progress * 100.0).round() as u32;
+                                            rsx! {
+                                                AppLink {
+                                                    key: "{pos.cid}",
+                                                    to: AppLinkTarget::Entry {
+                                                        ident: ident(),
+                                                        book_title: pos.book_title.clone().into(),
+                                                        entry_path: pos.entry_path.clone().into(),
+                                                    },
+                                                    class: Some("continue-reading-item".to_string()),
+                                                    span { class: "continue-reading-title", "{pos.title}" }
+                                                    span { class: "continue-reading-meta",
+                                                        span { class: "continue-reading-book", "{pos.book_title}" }
+                                                        span { class: "continue-reading-progress", "{percent}% read" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else {
+                            rsx! {}
+                        }
+                    }
+
                     // Pinned items section
                     {
                         let pinned = pinned_items.read();
@@ -0,0 +1,231 @@
+//! Full-screen lightbox for images embedded in rendered entry HTML.
+//!
+//! Entry content arrives as pre-rendered HTML, so the lightbox can't attach
+//! per-image handlers in rsx. Instead a delegated click listener on the
+//! document picks up clicks on any `img` inside `.notebook-content`, builds
+//! the gallery from the images in that container, and opens the clicked one.
+
+use dioxus::prelude::*;
+
+const LIGHTBOX_CSS: Asset = asset!("/assets/styling/lightbox.css");
+
+/// One image in the open gallery.
+#[derive(Clone, PartialEq)]
+pub struct LightboxImage {
+    pub src: String,
+    pub alt: String,
+}
+
+/// Full-screen image viewer with keyboard navigation and pinch zoom.
+///
+/// Mounted once in the navbar layout; the delegated listener covers every
+/// entry view that renders `.notebook-content` HTML.
+#[component]
+pub fn Lightbox() -> Element {
+    let images = use_signal(Vec::<LightboxImage>::new);
+    let mut index = use_signal(|| 0usize);
+    let mut open = use_signal(|| false);
+    // Zoom scale, 1.0 to 4.0. Reset on image change.
+    let mut scale = use_signal(|| 1.0f64);
+    // Distance between two fingers when a pinch started, with the scale at
+    // that moment; None while not pinching.
+    let mut pinch_start = use_signal(|| None::<(f64, f64)>);
+
+    // Delegated click listener for images in rendered entry HTML.
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use wasm_bindgen::JsCast;
+        use wasm_bindgen::closure::Closure;
+
+        let mut click_closure = use_signal(|| None::<Closure<dyn FnMut(web_sys::Event)>>);
+        use_effect(move || {
+            if click_closure.peek().is_some() {
+                return;
+            }
+            let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+                return;
+            };
+            let closure = Closure::wrap(Box::new(move |evt: web_sys::Event| {
+                let Some(target) = evt.target() else {
+                    return;
+                };
+                let Ok(img) = target.dyn_into::<web_sys::HtmlImageElement>() else {
+                    return;
+                };
+                // Only images inside rendered entry content participate.
+                if img.closest(".notebook-content").ok().flatten().is_none() {
+                    return;
+                }
+                evt.prevent_default();
+
+                // Rebuild the gallery from the current document: entry
+                // content is replaced wholesale on navigation, so caching
+                // the list would go stale.
+                let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+                    return;
+                };
+                let mut gallery = Vec::new();
+                let mut clicked = 0usize;
+                if let Ok(nodes) = document.query_selector_all(".notebook-content img") {
+                    for i in 0..nodes.length() {
+                        let Some(node) = nodes.get(i) else { continue };
+                        let Ok(item) = node.dyn_into::<web_sys::HtmlImageElement>() else {
+                            continue;
+                        };
+                        if item.src() == img.src() {
+                            clicked = gallery.len();
+                        }
+                        gallery.push(LightboxImage {
+                            src: item.src(),
+                            alt: item.alt(),
+                        });
+                    }
+                }
+                if gallery.is_empty() {
+                    return;
+                }
+                let mut images = images;
+                let mut index = index;
+                let mut open = open;
+                let mut scale = scale;
+                images.set(gallery);
+                index.set(clicked);
+                scale.set(1.0);
+                open.set(true);
+            }) as Box<dyn FnMut(web_sys::Event)>);
+            let _ = document
+                .add_event_listener_with_callback("click", closure.as_ref().unchecked_ref());
+            click_closure.set(Some(closure));
+        });
+        use_drop(move || {
+            if let Some(closure) = click_closure.take() {
+                if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+                    let _ = document.remove_event_listener_with_callback(
+                        "click",
+                        closure.as_ref().unchecked_ref(),
+                    );
+                }
+            }
+        });
+
+        // Preload the neighbours of the current image so keyboard paging
+        // doesn't wait on the network.
+        use_effect(move || {
+            if !open() {
+                return;
+            }
+            let imgs = images.read();
+            let count = imgs.len();
+            if count < 2 {
+                return;
+            }
+            let i = index();
+            for j in [(i + 1) % count, (i + count - 1) % count] {
+                if let Ok(preload) = web_sys::HtmlImageElement::new() {
+                    preload.set_src(&imgs[j].src);
+                }
+            }
+        });
+    }
+
+    let count = images.read().len();
+    let mut show = move |next: usize| {
+        index.set(next);
+        scale.set(1.0);
+    };
+
+    if !open() {
+        return rsx! {};
+    }
+    let Some(current) = images.read().get(index()).cloned() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: LIGHTBOX_CSS }
+        div {
+            class: "lightbox-overlay",
+            role: "dialog",
+            aria_label: "Image viewer",
+            tabindex: "0",
+            autofocus: true,
+            onclick: move |_| open.set(false),
+            onkeydown: move |evt| {
+                match evt.key() {
+                    Key::Escape => open.set(false),
+                    Key::ArrowRight if count > 0 => show((index() + 1) % count),
+                    Key::ArrowLeft if count > 0 => show((index() + count - 1) % count),
+                    _ => {}
+                }
+            },
+            ontouchstart: move |evt| {
+                let touches = evt.touches();
+                if touches.len() == 2 {
+                    let a = touches[0].client_coordinates();
+                    let b = touches[1].client_coordinates();
+                    let dist = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+                    pinch_start.set(Some((dist, scale())));
+                }
+            },
+            ontouchmove: move |evt| {
+                let touches = evt.touches();
+                if let (2, Some((start_dist, start_scale))) = (touches.len(), pinch_start()) {
+                    evt.prevent_default();
+                    let a = touches[0].client_coordinates();
+                    let b = touches[1].client_coordinates();
+                    let dist = ((a.x - b.x).powi(2) + (a.y - b.y).powi(2)).sqrt();
+                    if start_dist > 0.0 {
+                        scale.set((start_scale * dist / start_dist).clamp(1.0, 4.0));
+                    }
+                }
+            },
+            ontouchend: move |_| pinch_start.set(None),
+
+            if count > 1 {
+                button {
+                    class: "lightbox-nav lightbox-nav-prev",
+                    aria_label: "Previous image",
+                    onclick: move |evt| {
+                        evt.stop_propagation();
+                        show((index() + count - 1) % count);
+                    },
+                    "‹"
+                }
+            }
+            figure {
+                class: "lightbox-figure",
+                onclick: move |evt| evt.stop_propagation(),
+                img {
+                    class: "lightbox-image",
+                    style: "transform: scale({scale})",
+                    src: "{current.src}",
+                    alt: "{current.alt}",
+                    ondoubleclick: move |_| {
+                        scale.set(if scale() > 1.0 { 1.0 } else { 2.0 });
+                    },
+                }
+                if !current.alt.is_empty() {
+                    figcaption { class: "lightbox-caption", "{current.alt}" }
+                }
+            }
+            if count > 1 {
+                button {
+                    class: "lightbox-nav lightbox-nav-next",
+                    aria_label: "Next image",
+                    onclick: move |evt| {
+                        evt.stop_propagation();
+                        show((index() + 1) % count);
+                    },
+                    "›"
+                }
+                span { class: "lightbox-counter", "{index() + 1} / {count}" }
+            }
+            button {
+                class: "lightbox-close",
+                aria_label: "Close",
+                onclick: move |_| open.set(false),
+                "✕"
+            }
+        }
+    }
+}
@@ -29,11 +29,49 @@ pub use profile::ProfileDisplay;
 pub mod notebook_cover;
 pub use notebook_cover::NotebookCover;
 
+pub mod notebook_manage;
+pub use notebook_manage::NotebookManagePanel;
+
+pub mod offline;
+pub use offline::OfflineIndicator;
+
+pub mod command_palette;
+pub use command_palette::{Command, CommandPalette, use_command_registry_provider, use_commands};
+
+pub mod lightbox;
+pub use lightbox::Lightbox;
+
+pub mod comments;
+pub use comments::CommentsSection;
+
+pub mod bookmark;
+pub use bookmark::BookmarkButton;
+
+pub mod text_diff;
+pub use text_diff::TextDiff;
+
+pub mod history;
+pub use history::EntryHistorySection;
+
+pub mod error_page;
+
 pub mod login;
 
 pub mod record_editor;
 pub mod record_view;
 
+pub mod reader_settings;
+pub use reader_settings::ReaderSettingsButton;
+
+pub mod stale_banner;
+pub use stale_banner::StaleBanner;
+
+pub mod templates;
+pub use templates::{TEMPLATES_NOTEBOOK, TemplatePicker, TemplateVars, substitute_variables};
+
+pub mod view_beacon;
+pub use view_beacon::ViewBeacon;
+
 pub mod collab;
 pub use collab::{CollaboratorAvatars, CollaboratorsPanel, InviteDialog, InvitesList};
 
@@ -343,6 +381,7 @@ pub mod button;
 pub mod dialog;
 pub mod editor;
 pub mod entry_actions;
+pub mod focus;
 pub mod input;
 pub mod notebook_actions;
 pub mod profile_actions;
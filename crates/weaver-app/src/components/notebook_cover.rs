@@ -1,8 +1,8 @@
 #![allow(non_snake_case)]
 
-use crate::components::{AppLink, AppLinkTarget};
 use crate::components::AuthorList;
 use crate::components::button::{Button, ButtonVariant};
+use crate::components::{AppLink, AppLinkTarget};
 use dioxus::prelude::*;
 use jacquard::IntoStatic;
 use jacquard::smol_str::SmolStr;
@@ -35,10 +35,33 @@ pub fn NotebookCover(
         }
     };
 
+    // Cover image rides in extra_data since the book lexicon has no cover
+    // field; serve it through the same CDN route the record views use.
+    let cover_url = book
+        .extra_data
+        .as_ref()
+        .and_then(|extra| extra.get("cover"))
+        .and_then(|data| match data {
+            jacquard::types::value::Data::Blob(blob) => {
+                let format = blob.mime_type.strip_prefix("image/").unwrap_or("jpeg");
+                Some(format!(
+                    "https://cdn.bsky.app/img/feed_fullsize/plain/{}/{}@{}",
+                    notebook.uri.authority(),
+                    blob.cid(),
+                    format
+                ))
+            }
+            _ => None,
+        });
+
     rsx! {
         document::Stylesheet { href: NOTEBOOK_COVER_CSS }
 
         div { class: "notebook-cover",
+            if let Some(ref url) = cover_url {
+                img { class: "notebook-cover-image", src: "{url}", alt: "{title}" }
+            }
+
             h1 { class: "notebook-cover-title", "{title}" }
 
             // Authors section
@@ -0,0 +1,383 @@
+//! Owner management panel for notebooks: reorder entries, rename, cover image.
+//!
+//! All mutations go through the `WeaverExt` helpers so the `Book` record is
+//! rewritten wholesale with `updatedAt` bumped. The entry list is updated
+//! optimistically: the new order renders immediately and rolls back if the
+//! PDS write fails.
+
+use crate::components::button::{Button, ButtonVariant};
+use crate::components::{AppLinkTarget, use_app_navigate};
+use crate::fetch::Fetcher;
+use dioxus::prelude::*;
+use jacquard::IntoStatic;
+use jacquard::bytes::Bytes;
+use jacquard::from_data;
+use jacquard::smol_str::ToSmolStr;
+use jacquard::types::ident::AtIdentifier;
+use mime_sniffer::MimeTypeSniffer;
+use weaver_api::com_atproto::repo::strong_ref::StrongRef;
+use weaver_api::sh_weaver::notebook::{BookEntryView, NotebookView};
+use weaver_common::WeaverExt;
+
+const NOTEBOOK_MANAGE_CSS: Asset = asset!("/assets/styling/notebook-manage.css");
+
+/// Collapsible management panel rendered in the notebook sidebar for owners.
+#[component]
+pub fn NotebookManagePanel(
+    notebook: NotebookView<'static>,
+    entries: Vec<BookEntryView<'static>>,
+    title: String,
+    ident: AtIdentifier<'static>,
+    on_changed: EventHandler<()>,
+) -> Element {
+    use weaver_api::sh_weaver::notebook::book::Book;
+
+    let fetcher = use_context::<Fetcher>();
+    let navigate = use_app_navigate();
+
+    let mut expanded = use_signal(|| false);
+    let mut busy = use_signal(|| false);
+    let mut uploading = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut drag_index = use_signal(|| None::<usize>);
+    let mut rename_value = use_signal(|| title.clone());
+    let mut cover_preview = use_signal(|| None::<String>);
+
+    // Optimistic override for the entry list. `None` means "render what the
+    // record says". A successful write leaves the override in place, which is
+    // fine because it then matches the refetched record; a failed write
+    // reverts so the UI never shows an order the PDS rejected.
+    let mut override_list: Signal<Option<Vec<StrongRef<'static>>>> = use_signal(|| None);
+
+    let notebook_uri = notebook.uri.clone().into_static();
+
+    // The view embeds the raw Book record, which carries the authoritative
+    // entry list; reorder and remove operate on those StrongRefs directly
+    // rather than rebuilding refs from index views.
+    let book = from_data::<Book>(&notebook.record).ok();
+    let record_list: Vec<StrongRef<'static>> = book
+        .as_ref()
+        .map(|b| {
+            b.entry_list
+                .iter()
+                .cloned()
+                .map(IntoStatic::into_static)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // Existing cover lives in extra_data (the book lexicon has no cover
+    // field); preview it through the same CDN route the record views use.
+    let existing_cover_url = book
+        .as_ref()
+        .and_then(|b| b.extra_data.as_ref())
+        .and_then(|extra| extra.get("cover"))
+        .and_then(|data| match data {
+            jacquard::types::value::Data::Blob(blob) => {
+                let format = blob.mime_type.strip_prefix("image/").unwrap_or("jpeg");
+                Some(format!(
+                    "https://cdn.bsky.app/img/feed_fullsize/plain/{}/{}@{}",
+                    notebook_uri.authority(),
+                    blob.cid(),
+                    format
+                ))
+            }
+            _ => None,
+        });
+
+    let display_list = override_list().unwrap_or(record_list);
+
+    // Map entry URIs to display titles from the hydrated views.
+    let labels: std::collections::HashMap<String, String> = entries
+        .iter()
+        .map(|e| {
+            let label = e
+                .entry
+                .title
+                .as_ref()
+                .map(|t| t.as_ref().to_string())
+                .or_else(|| e.entry.path.as_ref().map(|p| p.as_ref().to_string()))
+                .unwrap_or_else(|| {
+                    e.entry
+                        .uri
+                        .rkey()
+                        .map(|r| r.0.as_str().to_string())
+                        .unwrap_or_else(|| e.entry.uri.to_string())
+                });
+            (e.entry.uri.to_string(), label)
+        })
+        .collect();
+
+    // Shared write path for reorder and remove: optimistic set, revert on
+    // failure, refetch on success.
+    let list_fetcher = fetcher.clone();
+    let uri_for_list = notebook_uri.clone();
+    let apply_entry_list = move |new_list: Vec<StrongRef<'static>>| {
+        let fetcher = list_fetcher.clone();
+        let uri = uri_for_list.clone();
+        let previous = override_list();
+        override_list.set(Some(new_list.clone()));
+
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+
+            let client = fetcher.get_client();
+            match client.set_notebook_entry_list(&uri, new_list).await {
+                Ok(()) => on_changed.call(()),
+                Err(e) => {
+                    override_list.set(previous);
+                    error.set(Some(format!("Failed to update entries: {:?}", e)));
+                }
+            }
+            busy.set(false);
+        });
+    };
+
+    let rename_fetcher = fetcher.clone();
+    let uri_for_rename = notebook_uri.clone();
+    let ident_for_rename = ident.clone();
+    let navigate_for_rename = navigate.clone();
+    let handle_rename = move |_| {
+        let fetcher = rename_fetcher.clone();
+        let uri = uri_for_rename.clone();
+        let ident = ident_for_rename.clone();
+        let navigate = navigate_for_rename.clone();
+        let new_title = rename_value.peek().trim().to_string();
+
+        if new_title.is_empty() {
+            error.set(Some("Notebook title cannot be empty".to_string()));
+            return;
+        }
+
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+
+            let client = fetcher.get_client();
+            match client.rename_notebook(&uri, &new_title).await {
+                Ok(()) => {
+                    // The index route is keyed by title, so the old URL stops
+                    // resolving once the rename lands.
+                    navigate(AppLinkTarget::Notebook {
+                        ident,
+                        book_title: new_title.to_smolstr(),
+                    });
+                }
+                Err(e) => {
+                    error.set(Some(format!("Rename failed: {:?}", e)));
+                }
+            }
+            busy.set(false);
+        });
+    };
+
+    let cover_fetcher = fetcher.clone();
+    let uri_for_cover = notebook_uri.clone();
+    let handle_cover_upload = move |evt: Event<FormData>| {
+        let fetcher = cover_fetcher.clone();
+        let uri = uri_for_cover.clone();
+
+        spawn(async move {
+            uploading.set(true);
+            error.set(None);
+
+            for file_data in evt.files() {
+                let bytes_data = match file_data.read_bytes().await {
+                    Ok(b) => b,
+                    Err(e) => {
+                        error.set(Some(format!("Failed to read file: {}", e)));
+                        continue;
+                    }
+                };
+                let bytes = Bytes::from(bytes_data.to_vec());
+                let mime_str = bytes
+                    .sniff_mime_type()
+                    .unwrap_or("application/octet-stream");
+                if !mime_str.starts_with("image/") {
+                    error.set(Some("Selected file is not an image".to_string()));
+                    continue;
+                }
+                let mime_type = jacquard::types::blob::MimeType::new_owned(mime_str);
+
+                // Data URL preview shows the new cover immediately, before
+                // the refetched record catches up.
+                let base64_data =
+                    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+                let data_url = format!("data:{};base64,{}", mime_str, base64_data);
+
+                let client = fetcher.get_client();
+                match client.upload_blob(bytes, mime_type).await {
+                    Ok(blob) => match client.set_notebook_cover(&uri, Some(blob)).await {
+                        Ok(()) => {
+                            cover_preview.set(Some(data_url));
+                            on_changed.call(());
+                        }
+                        Err(e) => {
+                            error.set(Some(format!("Failed to set cover: {:?}", e)));
+                        }
+                    },
+                    Err(e) => {
+                        error.set(Some(format!("Upload failed: {:?}", e)));
+                    }
+                }
+            }
+
+            uploading.set(false);
+        });
+    };
+
+    let remove_fetcher = fetcher.clone();
+    let uri_for_remove = notebook_uri.clone();
+    let handle_cover_remove = move |_| {
+        let fetcher = remove_fetcher.clone();
+        let uri = uri_for_remove.clone();
+
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+
+            let client = fetcher.get_client();
+            match client.set_notebook_cover(&uri, None).await {
+                Ok(()) => {
+                    cover_preview.set(None);
+                    on_changed.call(());
+                }
+                Err(e) => {
+                    error.set(Some(format!("Failed to remove cover: {:?}", e)));
+                }
+            }
+            busy.set(false);
+        });
+    };
+
+    let shown_cover = cover_preview().or(existing_cover_url);
+    let has_cover = shown_cover.is_some();
+
+    rsx! {
+        document::Stylesheet { href: NOTEBOOK_MANAGE_CSS }
+
+        div { class: "notebook-manage",
+            Button {
+                variant: ButtonVariant::Ghost,
+                onclick: move |_| expanded.toggle(),
+                if expanded() { "Done" } else { "Manage Notebook" }
+            }
+
+            if expanded() {
+                div { class: "notebook-manage-panel",
+                    if let Some(ref err) = error() {
+                        div { class: "notebook-manage-error", "{err}" }
+                    }
+
+                    // Rename.
+                    div { class: "notebook-manage-section",
+                        label { "Title" }
+                        input {
+                            r#type: "text",
+                            value: "{rename_value}",
+                            oninput: move |evt| rename_value.set(evt.value()),
+                        }
+                        Button {
+                            variant: ButtonVariant::Outline,
+                            disabled: busy(),
+                            onclick: handle_rename,
+                            "Rename"
+                        }
+                    }
+
+                    // Cover image.
+                    div { class: "notebook-manage-section",
+                        label { "Cover image" }
+                        if let Some(ref url) = shown_cover {
+                            img {
+                                class: "notebook-manage-cover-preview",
+                                src: "{url}",
+                                alt: "Notebook cover",
+                            }
+                        }
+                        input {
+                            r#type: "file",
+                            accept: "image/*",
+                            disabled: uploading(),
+                            onchange: handle_cover_upload,
+                        }
+                        if uploading() {
+                            span { class: "notebook-manage-status", "Uploading..." }
+                        }
+                        if has_cover {
+                            Button {
+                                variant: ButtonVariant::Ghost,
+                                disabled: busy(),
+                                onclick: handle_cover_remove,
+                                "Remove cover"
+                            }
+                        }
+                    }
+
+                    // Entry order and removal.
+                    div { class: "notebook-manage-section",
+                        label { "Entries" }
+                        if display_list.is_empty() {
+                            span { class: "notebook-manage-status", "No entries yet." }
+                        }
+                        ul { class: "notebook-manage-entries",
+                            for (idx, entry_ref) in display_list.iter().enumerate() {
+                                {
+                                    let uri_key: &str = entry_ref.uri.as_ref();
+                                    let label = labels
+                                        .get(uri_key)
+                                        .cloned()
+                                        .unwrap_or_else(|| entry_ref.uri.to_string());
+                                    let apply_drop = apply_entry_list.clone();
+                                    let apply_remove = apply_entry_list.clone();
+                                    let list_for_drop = display_list.clone();
+                                    let list_for_remove = display_list.clone();
+                                    rsx! {
+                                        li {
+                                            key: "{entry_ref.uri}",
+                                            class: "notebook-manage-entry",
+                                            draggable: "true",
+                                            ondragstart: move |_| drag_index.set(Some(idx)),
+                                            ondragover: move |evt| evt.prevent_default(),
+                                            ondrop: move |evt| {
+                                                evt.prevent_default();
+                                                if let Some(from) = drag_index() {
+                                                    drag_index.set(None);
+                                                    if from != idx {
+                                                        let mut new_list = list_for_drop.clone();
+                                                        let moved = new_list.remove(from);
+                                                        new_list.insert(idx, moved);
+                                                        apply_drop(new_list);
+                                                    }
+                                                }
+                                            },
+                                            span { class: "notebook-manage-handle", "⠿" }
+                                            span { class: "notebook-manage-label", "{label}" }
+                                            button {
+                                                class: "notebook-manage-remove",
+                                                title: "Remove from notebook",
+                                                disabled: busy(),
+                                                onclick: move |_| {
+                                                    // Removing from the book doesn't delete the
+                                                    // entry record, just drops it from the list.
+                                                    let mut new_list = list_for_remove.clone();
+                                                    new_list.remove(idx);
+                                                    apply_remove(new_list);
+                                                },
+                                                "✕"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        span { class: "notebook-manage-hint",
+                            "Drag entries to reorder. Removing an entry keeps its record."
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
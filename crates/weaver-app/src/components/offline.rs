@@ -0,0 +1,47 @@
+//! Offline status indicator backed by the browser's online state.
+
+use dioxus::prelude::*;
+
+const OFFLINE_CSS: Asset = asset!("/assets/styling/offline.css");
+
+/// Small banner shown while the browser reports no network connection.
+///
+/// Visited pages keep working offline via the service worker page cache;
+/// this makes it obvious that the user is looking at saved copies rather
+/// than live content.
+#[component]
+pub fn OfflineIndicator() -> Element {
+    #[allow(unused_mut)]
+    let mut offline = use_signal(|| false);
+
+    // Poll the browser's online flag. The `online`/`offline` events would
+    // need extra event listener plumbing; a short poll is cheap enough for
+    // a status badge.
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use_effect(move || {
+            if let Some(window) = web_sys::window() {
+                offline.set(!window.navigator().on_line());
+            }
+        });
+        dioxus_sdk::time::use_interval(std::time::Duration::from_secs(5), move |_| {
+            if let Some(window) = web_sys::window() {
+                let now_offline = !window.navigator().on_line();
+                if now_offline != *offline.peek() {
+                    offline.set(now_offline);
+                }
+            }
+        });
+    }
+
+    if !offline() {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: OFFLINE_CSS }
+        div { class: "offline-indicator", role: "status",
+            "Offline — showing saved copies of visited pages"
+        }
+    }
+}
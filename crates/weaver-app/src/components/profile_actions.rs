@@ -1,5 +1,6 @@
 //! Actions sidebar/menubar for profile page.
 
+use crate::Route;
 use crate::auth::AuthState;
 use crate::components::app_link::{AppLink, AppLinkTarget};
 use crate::components::button::{Button, ButtonVariant};
@@ -65,6 +66,26 @@ pub fn ProfileActions(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
                             "Invites"
                         }
                     }
+
+                    AppLink {
+                        to: AppLinkTarget::Analytics { ident: ident() },
+                        class: "profile-action-link".to_string(),
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            "Analytics"
+                        }
+                    }
+
+                    // Settings is session-scoped rather than per-ident,
+                    // so it bypasses AppLink.
+                    Link {
+                        to: Route::ProfileSettingsPage {},
+                        class: "profile-action-link",
+                        Button {
+                            variant: ButtonVariant::Ghost,
+                            "Edit Profile"
+                        }
+                    }
                 }
             }
         }
@@ -113,6 +134,22 @@ pub fn ProfileActionsMenubar(ident: ReadSignal<AtIdentifier<'static>>) -> Elemen
                     "Invites"
                 }
             }
+
+            AppLink {
+                to: AppLinkTarget::Analytics { ident: ident() },
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    "Analytics"
+                }
+            }
+
+            Link {
+                to: Route::ProfileSettingsPage {},
+                Button {
+                    variant: ButtonVariant::Ghost,
+                    "Edit Profile"
+                }
+            }
         }
     }
 }
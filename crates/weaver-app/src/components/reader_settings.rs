@@ -0,0 +1,413 @@
+//! Reader typography and display settings for entry pages.
+//!
+//! A small "Aa" popover letting readers pick font family, size, line width,
+//! line spacing, and justification. The choice is per device: persisted in
+//! localStorage like the theme preference, never synced. Settings are
+//! applied as `--reader-*` CSS variables on the root element; `entry.css`
+//! consumes them only under `.entry-page`, with fallbacks to the notebook
+//! theme's own values, so an unset variable changes nothing and the controls
+//! compose with whatever theme the notebook ships.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const READER_SETTINGS_CSS: Asset = asset!("/assets/styling/reader-settings.css");
+
+/// Storage key for the persisted settings.
+const READER_SETTINGS_KEY: &str = "weaver-reader-settings";
+
+/// Bounds and step for the font size control, in percent of the theme size.
+const SIZE_MIN: u16 = 80;
+const SIZE_MAX: u16 = 140;
+const SIZE_STEP: u16 = 10;
+
+/// Font family override. [`ReaderFont::Theme`] keeps the notebook's own
+/// `--font-body`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReaderFont {
+    #[default]
+    Theme,
+    Serif,
+    Sans,
+    Mono,
+}
+
+impl ReaderFont {
+    const ALL: [ReaderFont; 4] = [
+        ReaderFont::Theme,
+        ReaderFont::Serif,
+        ReaderFont::Sans,
+        ReaderFont::Mono,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ReaderFont::Theme => "Theme",
+            ReaderFont::Serif => "Serif",
+            ReaderFont::Sans => "Sans",
+            ReaderFont::Mono => "Mono",
+        }
+    }
+
+    /// The `--reader-font-family` value, `None` for the theme default.
+    ///
+    /// Mono defers to the notebook's `--font-mono` so code and prose agree.
+    fn css(self) -> Option<&'static str> {
+        match self {
+            ReaderFont::Theme => None,
+            ReaderFont::Serif => Some("Georgia, 'Times New Roman', serif"),
+            ReaderFont::Sans => Some("system-ui, -apple-system, sans-serif"),
+            ReaderFont::Mono => Some("var(--font-mono, monospace)"),
+        }
+    }
+}
+
+/// Line width override. [`ReaderWidth::Normal`] keeps the page's
+/// `--content-width`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReaderWidth {
+    Narrow,
+    #[default]
+    Normal,
+    Wide,
+}
+
+impl ReaderWidth {
+    const ALL: [ReaderWidth; 3] = [ReaderWidth::Narrow, ReaderWidth::Normal, ReaderWidth::Wide];
+
+    fn label(self) -> &'static str {
+        match self {
+            ReaderWidth::Narrow => "Narrow",
+            ReaderWidth::Normal => "Normal",
+            ReaderWidth::Wide => "Wide",
+        }
+    }
+
+    fn css(self) -> Option<&'static str> {
+        match self {
+            ReaderWidth::Narrow => Some("65ch"),
+            ReaderWidth::Normal => None,
+            ReaderWidth::Wide => Some("120ch"),
+        }
+    }
+}
+
+/// Line spacing override. [`ReaderSpacing::Normal`] keeps the theme's
+/// line height.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReaderSpacing {
+    Compact,
+    #[default]
+    Normal,
+    Relaxed,
+}
+
+impl ReaderSpacing {
+    const ALL: [ReaderSpacing; 3] = [
+        ReaderSpacing::Compact,
+        ReaderSpacing::Normal,
+        ReaderSpacing::Relaxed,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            ReaderSpacing::Compact => "Compact",
+            ReaderSpacing::Normal => "Normal",
+            ReaderSpacing::Relaxed => "Relaxed",
+        }
+    }
+
+    fn css(self) -> Option<&'static str> {
+        match self {
+            ReaderSpacing::Compact => Some("1.35"),
+            ReaderSpacing::Normal => None,
+            ReaderSpacing::Relaxed => Some("1.8"),
+        }
+    }
+}
+
+/// The reader's display preferences. The default is "change nothing": every
+/// field at its default produces no CSS variables at all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaderSettings {
+    pub font: ReaderFont,
+    pub size_percent: u16,
+    pub width: ReaderWidth,
+    pub spacing: ReaderSpacing,
+    pub justify: bool,
+}
+
+impl Default for ReaderSettings {
+    fn default() -> Self {
+        ReaderSettings {
+            font: ReaderFont::default(),
+            size_percent: 100,
+            width: ReaderWidth::default(),
+            spacing: ReaderSpacing::default(),
+            justify: false,
+        }
+    }
+}
+
+impl ReaderSettings {
+    /// Clamp persisted values back into range; stale or hand-edited storage
+    /// must not produce unreadable pages.
+    fn clamped(mut self) -> Self {
+        self.size_percent = self.size_percent.clamp(SIZE_MIN, SIZE_MAX);
+        self
+    }
+
+    /// The inline `style` value for the root element, `None` when every
+    /// setting is at its default so the attribute can be removed entirely.
+    pub(crate) fn style_value(&self) -> Option<String> {
+        let mut declarations = Vec::new();
+        if let Some(family) = self.font.css() {
+            declarations.push(format!("--reader-font-family: {family}"));
+        }
+        if self.size_percent != 100 {
+            declarations.push(format!("--reader-font-size: {}%", self.size_percent));
+        }
+        if let Some(width) = self.width.css() {
+            declarations.push(format!("--reader-line-width: {width}"));
+        }
+        if let Some(spacing) = self.spacing.css() {
+            declarations.push(format!("--reader-line-height: {spacing}"));
+        }
+        if self.justify {
+            declarations.push("--reader-text-align: justify".to_string());
+        }
+        if declarations.is_empty() {
+            None
+        } else {
+            Some(declarations.join("; "))
+        }
+    }
+}
+
+/// The persisted settings at first render, per platform.
+fn initial_settings() -> ReaderSettings {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use gloo_storage::{LocalStorage, Storage};
+        LocalStorage::get::<ReaderSettings>(READER_SETTINGS_KEY)
+            .map(ReaderSettings::clamped)
+            .unwrap_or_default()
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    ReaderSettings::default()
+}
+
+/// Reflect the settings onto the root element's inline style.
+///
+/// Setting the variables at the root (rather than on `.entry-page`) keeps
+/// this independent of when the page's DOM mounts; `entry.css` scopes the
+/// consumers, so nothing outside entry pages can observe them.
+fn apply_settings(settings: ReaderSettings) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        let Some(root) = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.document_element())
+        else {
+            return;
+        };
+        let result = match settings.style_value() {
+            Some(style) => root.set_attribute("style", &style),
+            None => root.remove_attribute("style"),
+        };
+        if let Err(err) = result {
+            tracing::warn!(?err, "failed to apply reader settings");
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = settings;
+}
+
+/// Persist the settings to localStorage.
+fn persist_settings(settings: ReaderSettings) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use gloo_storage::{LocalStorage, Storage};
+        if let Err(err) = LocalStorage::set(READER_SETTINGS_KEY, settings) {
+            tracing::warn!(?err, "failed to persist reader settings");
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = settings;
+}
+
+/// "Aa" toggle plus the reader settings popover.
+///
+/// Sits at the top of the entry content column. The popover edits a local
+/// signal; an effect mirrors every change onto the root element and into
+/// storage, so the page restyles live as options are clicked.
+#[component]
+pub fn ReaderSettingsButton() -> Element {
+    let mut settings = use_signal(initial_settings);
+    let mut open = use_signal(|| false);
+
+    // Effects only run in the browser, which is also the only place the
+    // style attribute and storage writes can happen.
+    use_effect(move || {
+        let current = settings();
+        apply_settings(current);
+        persist_settings(current);
+    });
+
+    let current = settings();
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: READER_SETTINGS_CSS }
+        div { class: "reader-settings",
+            button {
+                class: "reader-settings-toggle",
+                title: "Reader settings",
+                aria_label: "Reader settings",
+                aria_expanded: open(),
+                onclick: move |_| open.toggle(),
+                "Aa"
+            }
+            if open() {
+                div {
+                    class: "reader-settings-popover",
+                    role: "dialog",
+                    aria_label: "Reader settings",
+                    div { class: "reader-settings-row",
+                        span { class: "reader-settings-label", "Font" }
+                        div { class: "reader-settings-options",
+                            for option in ReaderFont::ALL {
+                                button {
+                                    class: "reader-settings-option",
+                                    class: if current.font == option { "active" },
+                                    onclick: move |_| settings.write().font = option,
+                                    {option.label()}
+                                }
+                            }
+                        }
+                    }
+                    div { class: "reader-settings-row",
+                        span { class: "reader-settings-label", "Size" }
+                        div { class: "reader-settings-options",
+                            button {
+                                class: "reader-settings-option",
+                                aria_label: "Smaller text",
+                                disabled: current.size_percent <= SIZE_MIN,
+                                onclick: move |_| {
+                                    let mut s = settings.write();
+                                    s.size_percent = (s.size_percent - SIZE_STEP).max(SIZE_MIN);
+                                },
+                                "A\u{2212}"
+                            }
+                            span { class: "reader-settings-value", "{current.size_percent}%" }
+                            button {
+                                class: "reader-settings-option",
+                                aria_label: "Larger text",
+                                disabled: current.size_percent >= SIZE_MAX,
+                                onclick: move |_| {
+                                    let mut s = settings.write();
+                                    s.size_percent = (s.size_percent + SIZE_STEP).min(SIZE_MAX);
+                                },
+                                "A+"
+                            }
+                        }
+                    }
+                    div { class: "reader-settings-row",
+                        span { class: "reader-settings-label", "Width" }
+                        div { class: "reader-settings-options",
+                            for option in ReaderWidth::ALL {
+                                button {
+                                    class: "reader-settings-option",
+                                    class: if current.width == option { "active" },
+                                    onclick: move |_| settings.write().width = option,
+                                    {option.label()}
+                                }
+                            }
+                        }
+                    }
+                    div { class: "reader-settings-row",
+                        span { class: "reader-settings-label", "Spacing" }
+                        div { class: "reader-settings-options",
+                            for option in ReaderSpacing::ALL {
+                                button {
+                                    class: "reader-settings-option",
+                                    class: if current.spacing == option { "active" },
+                                    onclick: move |_| settings.write().spacing = option,
+                                    {option.label()}
+                                }
+                            }
+                        }
+                    }
+                    div { class: "reader-settings-row",
+                        span { class: "reader-settings-label", "Align" }
+                        div { class: "reader-settings-options",
+                            button {
+                                class: "reader-settings-option",
+                                class: if !current.justify { "active" },
+                                onclick: move |_| settings.write().justify = false,
+                                "Default"
+                            }
+                            button {
+                                class: "reader-settings-option",
+                                class: if current.justify { "active" },
+                                onclick: move |_| settings.write().justify = true,
+                                "Justified"
+                            }
+                        }
+                    }
+                    if current != ReaderSettings::default() {
+                        button {
+                            class: "reader-settings-reset",
+                            onclick: move |_| settings.set(ReaderSettings::default()),
+                            "Reset to defaults"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_settings_emit_no_style() {
+        // The default must be indistinguishable from never having opened
+        // the popover, so the notebook theme stays fully in charge.
+        assert_eq!(ReaderSettings::default().style_value(), None);
+    }
+
+    #[test]
+    fn test_style_value_lists_only_changed_settings() {
+        let settings = ReaderSettings {
+            font: ReaderFont::Serif,
+            size_percent: 120,
+            justify: true,
+            ..Default::default()
+        };
+        let style = settings.style_value().unwrap();
+        assert!(style.contains("--reader-font-family: Georgia"));
+        assert!(style.contains("--reader-font-size: 120%"));
+        assert!(style.contains("--reader-text-align: justify"));
+        assert!(!style.contains("--reader-line-width"));
+        assert!(!style.contains("--reader-line-height"));
+    }
+
+    #[test]
+    fn test_clamped_bounds_persisted_size() {
+        let too_small = ReaderSettings {
+            size_percent: 10,
+            ..Default::default()
+        };
+        let too_large = ReaderSettings {
+            size_percent: 500,
+            ..Default::default()
+        };
+        assert_eq!(too_small.clamped().size_percent, SIZE_MIN);
+        assert_eq!(too_large.clamped().size_percent, SIZE_MAX);
+    }
+}
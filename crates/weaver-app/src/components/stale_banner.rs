@@ -0,0 +1,52 @@
+//! Stale-content indicator for views rendered from cache.
+//!
+//! Entry and notebook pages are served from several cache layers (the
+//! fetcher's in-memory caches, SSR payloads, the server blobcache). Rather
+//! than silently showing stale content until a TTL expires, this component
+//! probes the record's current CID in the background and offers a one-click
+//! refresh when a newer version exists upstream.
+
+use dioxus::prelude::*;
+use jacquard::types::{cid::Cid, string::AtUri};
+
+use crate::data::use_record_stale_check;
+
+const STALE_BANNER_CSS: Asset = asset!("/assets/styling/stale-banner.css");
+
+/// "Updated version available" banner with a one-click refresh.
+///
+/// Renders nothing while the cached copy is current (or while the probe is
+/// still in flight), so it can sit unconditionally at the top of a page.
+/// `on_refresh` should restart the page's data resource; once the refreshed
+/// view carries the new CID the probe re-runs and the banner disappears.
+#[component]
+pub fn StaleBanner(
+    uri: ReadSignal<AtUri<'static>>,
+    cid: ReadSignal<Cid<'static>>,
+    on_refresh: EventHandler<()>,
+) -> Element {
+    let stale = use_record_stale_check(uri, cid);
+    let mut dismissed = use_signal(|| false);
+
+    if dismissed() || stale().flatten() != Some(true) {
+        return rsx! {};
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: STALE_BANNER_CSS }
+        div { class: "stale-banner", role: "status",
+            span { class: "stale-banner-text", "Updated version available" }
+            button {
+                class: "stale-banner-refresh",
+                onclick: move |_| on_refresh.call(()),
+                "Refresh"
+            }
+            button {
+                class: "stale-banner-dismiss",
+                aria_label: "Dismiss",
+                onclick: move |_| dismissed.set(true),
+                "\u{00d7}"
+            }
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Entry templates for new drafts.
+//!
+//! Templates are ordinary entries in a notebook titled "Templates": no new
+//! record type, so they can be written, shared and published with the same
+//! tooling as any other entry. Picking one pre-fills a new draft with the
+//! template's content after `{{variable}}` substitution.
+
+use crate::fetch::Fetcher;
+use dioxus::prelude::*;
+use jacquard::smol_str::SmolStr;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::string::Datetime;
+
+/// Title of the notebook whose entries act as draft templates.
+pub const TEMPLATES_NOTEBOOK: &str = "Templates";
+
+/// Values substituted into `{{variable}}` placeholders.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateVars {
+    /// Draft title; usually empty at creation time.
+    pub title: String,
+    /// The drafting repository's handle or DID.
+    pub author: String,
+    /// Today's date as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+impl TemplateVars {
+    /// Variables for a draft created right now in `ident`'s repository.
+    pub fn for_new_draft(ident: &AtIdentifier<'_>) -> Self {
+        Self {
+            title: String::new(),
+            author: ident.to_string(),
+            date: Datetime::now().as_ref().format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+/// Replace `{{date}}`, `{{title}}` and `{{author}}` placeholders.
+///
+/// An empty value leaves its placeholder in the text so the user can fill
+/// it in by hand (a new draft has no title yet); unknown variables pass
+/// through untouched rather than silently disappearing.
+pub fn substitute_variables(template: &str, vars: &TemplateVars) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find("{{") {
+        let Some(close) = rest[open + 2..].find("}}") else {
+            break;
+        };
+        out.push_str(&rest[..open]);
+        let name = rest[open + 2..open + 2 + close].trim();
+        let placeholder = &rest[open..open + 2 + close + 2];
+        let value = match name {
+            "date" => Some(vars.date.as_str()),
+            "title" => Some(vars.title.as_str()),
+            "author" => Some(vars.author.as_str()),
+            _ => None,
+        };
+        match value {
+            Some(v) if !v.is_empty() => out.push_str(v),
+            _ => out.push_str(placeholder),
+        }
+        rest = &rest[open + 2 + close + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Template choice screen shown before the editor mounts.
+///
+/// `on_pick` receives the substituted content, or `None` for a blank
+/// draft. The choice happens before the editor exists because mounting
+/// the editor mints a draft key; re-mounting it with template content
+/// would leave an orphaned blank draft behind.
+#[component]
+pub fn TemplatePicker(
+    ident: AtIdentifier<'static>,
+    templates: Vec<SmolStr>,
+    on_pick: EventHandler<Option<String>>,
+) -> Element {
+    let fetcher = use_context::<Fetcher>();
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    rsx! {
+        div { class: "template-picker", aria_label: "Choose a template",
+            h2 { "New draft" }
+            p { class: "template-picker-hint",
+                "Start from a template in your Templates notebook, or from scratch."
+            }
+            if let Some(err) = error() {
+                p { class: "template-picker-error", "{err}" }
+            }
+            div { class: "template-picker-options",
+                button {
+                    class: "template-picker-option",
+                    disabled: busy(),
+                    onclick: move |_| on_pick.call(None),
+                    "Blank draft"
+                }
+                for title in templates.iter() {
+                    button {
+                        key: "{title}",
+                        class: "template-picker-option",
+                        disabled: busy(),
+                        onclick: {
+                            let fetcher = fetcher.clone();
+                            let ident = ident.clone();
+                            let title = title.clone();
+                            move |_| {
+                                let fetcher = fetcher.clone();
+                                let ident = ident.clone();
+                                let title = title.clone();
+                                busy.set(true);
+                                spawn(async move {
+                                    match fetcher
+                                        .get_entry(
+                                            ident.clone(),
+                                            SmolStr::new_static(TEMPLATES_NOTEBOOK),
+                                            title,
+                                        )
+                                        .await
+                                    {
+                                        Ok(Some(entry)) => {
+                                            let vars = TemplateVars::for_new_draft(&ident);
+                                            let content = substitute_variables(
+                                                entry.1.content.as_ref(),
+                                                &vars,
+                                            );
+                                            on_pick.call(Some(content));
+                                        }
+                                        Ok(None) | Err(_) => {
+                                            busy.set(false);
+                                            error.set(Some(
+                                                "Failed to load template".to_string(),
+                                            ));
+                                        }
+                                    }
+                                });
+                            }
+                        },
+                        "{title}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> TemplateVars {
+        TemplateVars {
+            title: String::new(),
+            author: "alice.example.com".to_string(),
+            date: "2025-01-15".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_substitutes_known_variables() {
+        let out = substitute_variables("By {{author}} on {{ date }}.", &vars());
+        assert_eq!(out, "By alice.example.com on 2025-01-15.");
+    }
+
+    #[test]
+    fn test_empty_value_keeps_placeholder() {
+        // A new draft has no title yet; the placeholder stays for the
+        // user to replace.
+        let out = substitute_variables("# {{title}}\n", &vars());
+        assert_eq!(out, "# {{title}}\n");
+    }
+
+    #[test]
+    fn test_unknown_and_unclosed_pass_through() {
+        let out = substitute_variables("{{nope}} and {{open", &vars());
+        assert_eq!(out, "{{nope}} and {{open");
+    }
+}
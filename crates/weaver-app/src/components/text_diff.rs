@@ -0,0 +1,262 @@
+//! Line-based text diff rendering.
+//!
+//! Computes a classic longest-common-subsequence diff over lines and
+//! renders it as a unified listing. The diff is computed client-side so
+//! any two version strings can be compared without a server round trip.
+
+use dioxus::prelude::*;
+
+const TEXT_DIFF_CSS: Asset = asset!("/assets/styling/text_diff.css");
+
+/// Above this many cell comparisons the quadratic LCS table is not worth
+/// building; fall back to a whole-block replacement diff.
+const MAX_LCS_CELLS: usize = 1_000_000;
+
+/// How one line of the diff relates to the two inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffLineKind {
+    /// Present in both versions.
+    Context,
+    /// Only in the new version.
+    Added,
+    /// Only in the old version.
+    Removed,
+}
+
+/// One rendered line of a diff.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub text: String,
+}
+
+impl DiffLine {
+    fn new(kind: DiffLineKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.to_string(),
+        }
+    }
+}
+
+/// Diff two texts line by line, old against new.
+///
+/// Common leading and trailing lines are emitted as context without
+/// entering the LCS table, which keeps the quadratic part proportional
+/// to the changed region rather than the whole document.
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    // Trim the common prefix.
+    let mut prefix = 0;
+    while prefix < old_lines.len()
+        && prefix < new_lines.len()
+        && old_lines[prefix] == new_lines[prefix]
+    {
+        prefix += 1;
+    }
+
+    // Trim the common suffix, without overlapping the prefix.
+    let mut suffix = 0;
+    while suffix < old_lines.len() - prefix
+        && suffix < new_lines.len() - prefix
+        && old_lines[old_lines.len() - 1 - suffix] == new_lines[new_lines.len() - 1 - suffix]
+    {
+        suffix += 1;
+    }
+
+    let old_mid = &old_lines[prefix..old_lines.len() - suffix];
+    let new_mid = &new_lines[prefix..new_lines.len() - suffix];
+
+    let mut out: Vec<DiffLine> = old_lines[..prefix]
+        .iter()
+        .map(|l| DiffLine::new(DiffLineKind::Context, l))
+        .collect();
+
+    if old_mid
+        .len()
+        .checked_mul(new_mid.len())
+        .is_none_or(|cells| cells > MAX_LCS_CELLS)
+    {
+        // Degenerate but correct: show the changed region as a removal
+        // followed by an addition.
+        out.extend(
+            old_mid
+                .iter()
+                .map(|l| DiffLine::new(DiffLineKind::Removed, l)),
+        );
+        out.extend(
+            new_mid
+                .iter()
+                .map(|l| DiffLine::new(DiffLineKind::Added, l)),
+        );
+    } else {
+        out.extend(lcs_diff(old_mid, new_mid));
+    }
+
+    out.extend(
+        old_lines[old_lines.len() - suffix..]
+            .iter()
+            .map(|l| DiffLine::new(DiffLineKind::Context, l)),
+    );
+
+    out
+}
+
+/// LCS diff over the changed region.
+fn lcs_diff(old: &[&str], new: &[&str]) -> Vec<DiffLine> {
+    let n = old.len();
+    let m = new.len();
+
+    // table[i][j] = length of the LCS of old[i..] and new[j..].
+    let mut table = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i][j] = if old[i] == new[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            out.push(DiffLine::new(DiffLineKind::Context, old[i]));
+            i += 1;
+            j += 1;
+        } else if table[i + 1][j] >= table[i][j + 1] {
+            out.push(DiffLine::new(DiffLineKind::Removed, old[i]));
+            i += 1;
+        } else {
+            out.push(DiffLine::new(DiffLineKind::Added, new[j]));
+            j += 1;
+        }
+    }
+    out.extend(
+        old[i..]
+            .iter()
+            .map(|l| DiffLine::new(DiffLineKind::Removed, l)),
+    );
+    out.extend(
+        new[j..]
+            .iter()
+            .map(|l| DiffLine::new(DiffLineKind::Added, l)),
+    );
+    out
+}
+
+/// Unified diff listing between two texts.
+#[component]
+pub fn TextDiff(old: String, new: String) -> Element {
+    let lines = use_memo(use_reactive!(|(old, new)| diff_lines(&old, &new)));
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: TEXT_DIFF_CSS }
+        div { class: "text-diff", role: "figure", aria_label: "Differences between versions",
+            for (index, line) in lines().iter().enumerate() {
+                {
+                    let (class, marker) = match line.kind {
+                        DiffLineKind::Context => ("text-diff-line", " "),
+                        DiffLineKind::Added => ("text-diff-line text-diff-added", "+"),
+                        DiffLineKind::Removed => ("text-diff-line text-diff-removed", "-"),
+                    };
+                    rsx! {
+                        div { key: "{index}", class: "{class}",
+                            span { class: "text-diff-marker", "{marker}" }
+                            span { class: "text-diff-text", "{line.text}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(diff: &[DiffLine]) -> Vec<DiffLineKind> {
+        diff.iter().map(|l| l.kind).collect()
+    }
+
+    #[test]
+    fn test_identical_texts_are_all_context() {
+        let diff = diff_lines("a\nb\nc", "a\nb\nc");
+        assert_eq!(kinds(&diff), vec![DiffLineKind::Context; 3]);
+    }
+
+    #[test]
+    fn test_pure_addition() {
+        let diff = diff_lines("a\nc", "a\nb\nc");
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                DiffLineKind::Context,
+                DiffLineKind::Added,
+                DiffLineKind::Context
+            ]
+        );
+        assert_eq!(diff[1].text, "b");
+    }
+
+    #[test]
+    fn test_pure_removal() {
+        let diff = diff_lines("a\nb\nc", "a\nc");
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                DiffLineKind::Context,
+                DiffLineKind::Removed,
+                DiffLineKind::Context
+            ]
+        );
+        assert_eq!(diff[1].text, "b");
+    }
+
+    #[test]
+    fn test_replacement_keeps_surrounding_context() {
+        let diff = diff_lines("a\nold\nz", "a\nnew\nz");
+        assert_eq!(
+            kinds(&diff),
+            vec![
+                DiffLineKind::Context,
+                DiffLineKind::Removed,
+                DiffLineKind::Added,
+                DiffLineKind::Context
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_inputs() {
+        assert!(diff_lines("", "").is_empty());
+        assert_eq!(kinds(&diff_lines("", "a")), vec![DiffLineKind::Added]);
+        assert_eq!(kinds(&diff_lines("a", "")), vec![DiffLineKind::Removed]);
+    }
+
+    #[test]
+    fn test_diff_roundtrips_both_sides() {
+        let old = "one\ntwo\nthree\nfour";
+        let new = "one\n2\nthree\nfive\nfour";
+        let diff = diff_lines(old, new);
+
+        let old_back: Vec<&str> = diff
+            .iter()
+            .filter(|l| l.kind != DiffLineKind::Added)
+            .map(|l| l.text.as_str())
+            .collect();
+        let new_back: Vec<&str> = diff
+            .iter()
+            .filter(|l| l.kind != DiffLineKind::Removed)
+            .map(|l| l.text.as_str())
+            .collect();
+
+        assert_eq!(old_back.join("\n"), old);
+        assert_eq!(new_back.join("\n"), new);
+    }
+}
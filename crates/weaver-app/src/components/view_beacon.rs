@@ -0,0 +1,94 @@
+//! Fire-and-forget view beacons for entry pages.
+//!
+//! Mounting [`ViewBeacon`] reports one page view to the index; unmounting
+//! reports time-on-page. Both go through `navigator.sendBeacon`, which the
+//! browser delivers even while the page is tearing down, and neither
+//! carries any reader identity: the index only ever sees the entry URI, a
+//! referrer reduced to its origin, and a duration. Rendered only when the
+//! index backend is in use; without it there is nowhere to report to.
+
+use dioxus::prelude::*;
+use jacquard::types::string::AtUri;
+
+/// Visits shorter than this report no reading time; a bounce tells the
+/// author nothing about how long their entry takes to read.
+#[cfg(all(feature = "use-index", target_family = "wasm", target_os = "unknown"))]
+const MIN_READING_MS: f64 = 2000.0;
+
+/// Serialize and hand a beacon to the browser.
+///
+/// `sendBeacon` with a plain string posts as `text/plain`, which the
+/// index's JSON extractor rejects, so the body goes out as a typed blob.
+/// Failures are swallowed: analytics must never affect the reading page.
+#[cfg(all(feature = "use-index", target_family = "wasm", target_os = "unknown"))]
+fn send_beacon(beacon: &weaver_common::analytics::ViewBeacon<'_>) {
+    let Ok(json) = serde_json::to_string(beacon) else {
+        return;
+    };
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let parts = js_sys::Array::new();
+    parts.push(&wasm_bindgen::JsValue::from_str(&json));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type("application/json");
+    let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&parts, &options) else {
+        return;
+    };
+    let url = format!("{}/analytics/beacon", crate::env::WEAVER_INDEXER_URL);
+    if window
+        .navigator()
+        .send_beacon_with_opt_blob(&url, Some(&blob))
+        .is_err()
+    {
+        tracing::debug!("view beacon rejected by browser");
+    }
+}
+
+/// Invisible component that reports a view of `uri` while mounted.
+///
+/// Mount it inside the loaded branch of an entry page so it fires once
+/// per viewed entry, not once per navigation. Tabs closed outright skip
+/// the reading-time beacon; view counts are unaffected.
+#[component]
+pub fn ViewBeacon(uri: AtUri<'static>) -> Element {
+    #[cfg(all(feature = "use-index", target_family = "wasm", target_os = "unknown"))]
+    {
+        use weaver_common::analytics::{ViewBeacon as Beacon, referrer_origin};
+
+        let mounted_at = use_hook(js_sys::Date::now);
+
+        use_effect({
+            let uri = uri.clone();
+            move || {
+                // Reduce the referrer before it leaves the page; the full
+                // URL never goes over the wire.
+                let referrer = web_sys::window()
+                    .and_then(|w| w.document())
+                    .map(|d| d.referrer())
+                    .as_deref()
+                    .and_then(referrer_origin);
+                send_beacon(&Beacon {
+                    uri: uri.clone(),
+                    referrer: referrer.map(Into::into),
+                    reading_ms: None,
+                });
+            }
+        });
+
+        use_drop(move || {
+            let elapsed = js_sys::Date::now() - mounted_at;
+            if elapsed >= MIN_READING_MS {
+                send_beacon(&Beacon {
+                    uri: uri.clone(),
+                    referrer: None,
+                    reading_ms: Some(elapsed as u64),
+                });
+            }
+        });
+    }
+    #[cfg(not(all(feature = "use-index", target_family = "wasm", target_os = "unknown")))]
+    let _ = uri;
+
+    rsx! {}
+}
@@ -139,6 +139,13 @@ pub fn use_entry_data(
                         }
                     }
                 }
+                // Report the record CID so the offline page cache can drop
+                // this page if the entry changes.
+                #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+                {
+                    let _ =
+                        crate::service_worker::cache_current_entry_page(entry.0.entry.cid.as_ref());
+                }
                 Some((entry.0.clone(), entry.1.clone()))
             } else {
                 None
@@ -400,7 +407,10 @@ pub fn use_rendered_markdown(
 
             let resolved_content = prefetch_embeds(&entry, &fetcher).await;
 
-            Some(render_markdown_impl(entry, did, resolved_content).await)
+            Some(
+                render_markdown_cached(entry, did, resolved_content, fetcher.paragraph_cache())
+                    .await,
+            )
         }
     }));
     let memo = use_memo(use_reactive!(|res| {
@@ -433,7 +443,10 @@ pub fn use_rendered_markdown(
 
             let resolved_content = prefetch_embeds(&entry, &fetcher).await;
 
-            Some(render_markdown_impl(entry, did, resolved_content).await)
+            Some(
+                render_markdown_cached(entry, did, resolved_content, fetcher.paragraph_cache())
+                    .await,
+            )
         }
     }));
     let memo = use_memo(use_reactive!(|res| {
@@ -504,11 +517,63 @@ async fn prefetch_embeds(
     resolved
 }
 
-/// Internal implementation of markdown rendering.
+/// Internal implementation of markdown rendering (whole document).
 async fn render_markdown_impl(
     content: Entry<'static>,
     did: Did<'static>,
     resolved_content: weaver_common::ResolvedContent,
+) -> String {
+    render_segment(&content.content, &content, did, &resolved_content).await
+}
+
+/// Render markdown with paragraph-level caching.
+///
+/// Splits the document into top-level block segments, renders each one
+/// independently, and caches the HTML by author DID and source hash, so
+/// re-visiting or live-updating an entry only re-renders the segments
+/// whose source changed. Documents that cannot be segmented safely, and
+/// segments whose output depends on content outside the entry, fall back
+/// to a fresh render.
+async fn render_markdown_cached(
+    content: Entry<'static>,
+    did: Did<'static>,
+    resolved_content: weaver_common::ResolvedContent,
+    cache: &crate::cache_impl::ParagraphCache,
+) -> String {
+    use weaver_editor_core::hash_source;
+
+    let Some(ranges) = segment_ranges(&content.content) else {
+        return render_markdown_impl(content, did, resolved_content).await;
+    };
+
+    let mut html = String::new();
+    for range in ranges {
+        let segment = &content.content[range];
+        // Embed and wikilink HTML depends on records outside this entry,
+        // which can change under the same source; render those fresh.
+        if segment.contains("at://") || segment.contains("[[") {
+            let rendered = render_segment(segment, &content, did.clone(), &resolved_content).await;
+            html.push_str(&rendered);
+            continue;
+        }
+        let hash = hash_source(segment);
+        if let Some(cached) = cache.get(&did, hash) {
+            html.push_str(&cached);
+            continue;
+        }
+        let rendered = render_segment(segment, &content, did.clone(), &resolved_content).await;
+        cache.insert(&did, hash, rendered.clone());
+        html.push_str(&rendered);
+    }
+    html
+}
+
+/// Render one markdown segment through the full client pipeline.
+async fn render_segment(
+    source: &str,
+    content: &Entry<'static>,
+    did: Did<'static>,
+    resolved_content: &weaver_common::ResolvedContent,
 ) -> String {
     use n0_future::stream::StreamExt;
     use weaver_renderer::{
@@ -517,21 +582,99 @@ async fn render_markdown_impl(
     };
 
     let ctx = ClientContext::<()>::new(content.clone(), did);
-    let parser =
-        markdown_weaver::Parser::new_ext(&content.content, weaver_renderer::default_md_options())
-            .into_offset_iter();
+    let parser = markdown_weaver::Parser::new_ext(source, weaver_renderer::default_md_options())
+        .into_offset_iter();
     let iter = ContextIterator::default(parser);
     let processor = NotebookProcessor::new(ctx, iter);
 
     let events: Vec<_> = StreamExt::collect(processor).await;
 
     let mut html_buf = String::new();
-    let writer = ClientWriter::<_, _, ()>::new(events.into_iter(), &mut html_buf, &content.content)
-        .with_embed_provider(resolved_content);
+    let writer = ClientWriter::<_, _, ()>::new(events.into_iter(), &mut html_buf, source)
+        .with_embed_provider(resolved_content.clone());
     writer.run().ok();
     html_buf
 }
 
+/// Top-level markdown segments that can be rendered independently.
+///
+/// Segments are discovered from parser events (mirroring how the editor
+/// finds paragraphs), so constructs that span blank lines — fenced code,
+/// loose lists, tables — stay in one segment. Returns `None` when the
+/// document cannot be split safely: footnote definitions and link
+/// reference definitions resolve across segments, so those documents get
+/// a whole-document render instead.
+fn segment_ranges(source: &str) -> Option<Vec<std::ops::Range<usize>>> {
+    use markdown_weaver::{Event, Tag};
+
+    let parser = markdown_weaver::Parser::new_ext(source, weaver_renderer::default_md_options())
+        .into_offset_iter();
+
+    let mut ranges: Vec<std::ops::Range<usize>> = Vec::new();
+    // Weaver blocks apply to the block after them, so they must share a
+    // segment with it.
+    let mut glue_to_next = false;
+    let mut depth = 0usize;
+
+    for (event, range) in parser {
+        match event {
+            Event::Start(ref tag) => {
+                // Footnote references can live in a different segment
+                // than their definition; only a full render links them.
+                if matches!(tag, Tag::FootnoteDefinition(_)) {
+                    return None;
+                }
+                if depth == 0 {
+                    push_segment(&mut ranges, range, &mut glue_to_next);
+                    glue_to_next = matches!(tag, Tag::WeaverBlock(..));
+                }
+                depth += 1;
+            }
+            Event::End(_) => depth = depth.saturating_sub(1),
+            // Standalone top-level events (rules, weaver block text).
+            _ if depth == 0 => {
+                let glue = matches!(event, Event::WeaverBlock(_));
+                push_segment(&mut ranges, range, &mut glue_to_next);
+                glue_to_next = glue;
+            }
+            _ => {}
+        }
+    }
+
+    // Link reference definitions emit no events, so their source sits in
+    // a gap between segments and would vanish from a stitched render.
+    // Any non-blank gap means the document cannot be segmented.
+    let mut cursor = 0;
+    for range in &ranges {
+        if !source[cursor..range.start].trim().is_empty() {
+            return None;
+        }
+        cursor = range.end;
+    }
+    if !source[cursor..].trim().is_empty() {
+        return None;
+    }
+
+    Some(ranges)
+}
+
+/// Append a top-level range, merging it into the previous segment when
+/// that segment must be rendered together with this one.
+fn push_segment(
+    ranges: &mut Vec<std::ops::Range<usize>>,
+    range: std::ops::Range<usize>,
+    glue_to_next: &mut bool,
+) {
+    if *glue_to_next {
+        if let Some(last) = ranges.last_mut() {
+            last.end = last.end.max(range.end);
+            *glue_to_next = false;
+            return;
+        }
+    }
+    ranges.push(range);
+}
+
 /// Fetches profile data for a given identifier
 #[cfg(feature = "fullstack-server")]
 pub fn use_profile_data(
@@ -811,6 +954,66 @@ pub fn use_entries_for_did_client(
     (res, memo)
 }
 
+/// Aggregated usage of one tag across a repository.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagSummary {
+    /// The tag, with the casing it was first seen with.
+    pub tag: String,
+    pub entry_count: usize,
+    pub notebook_count: usize,
+}
+
+/// Aggregates tags across all of an identity's entries and notebooks,
+/// client-side only. Tags are folded together case-insensitively and
+/// come back sorted by usage (most used first), ties alphabetically.
+pub fn use_tags_for_did(ident: ReadSignal<AtIdentifier<'static>>) -> Memo<Option<Vec<TagSummary>>> {
+    let (_entries_res, entries) = use_entries_for_did_client(ident);
+    let (_notebooks_res, notebooks) = use_notebooks_for_did_client(ident);
+    use_memo(move || {
+        use std::collections::BTreeMap;
+
+        let entries = entries.read();
+        let notebooks = notebooks.read();
+        let (Some(entries), Some(notebooks)) = (entries.as_ref(), notebooks.as_ref()) else {
+            return None;
+        };
+
+        // Keyed by the lowercased tag so "Rust" and "rust" fold together.
+        fn summary_for<'m>(
+            summaries: &'m mut BTreeMap<String, TagSummary>,
+            tag: &str,
+        ) -> &'m mut TagSummary {
+            summaries
+                .entry(tag.to_lowercase())
+                .or_insert_with(|| TagSummary {
+                    tag: tag.to_string(),
+                    entry_count: 0,
+                    notebook_count: 0,
+                })
+        }
+
+        let mut summaries: BTreeMap<String, TagSummary> = BTreeMap::new();
+        for (_, entry) in entries {
+            for tag in entry.tags.iter().flatten() {
+                summary_for(&mut summaries, tag.as_ref()).entry_count += 1;
+            }
+        }
+        for (notebook, _) in notebooks {
+            for tag in notebook.tags.iter().flatten() {
+                summary_for(&mut summaries, tag.as_ref()).notebook_count += 1;
+            }
+        }
+
+        let mut tags: Vec<TagSummary> = summaries.into_values().collect();
+        tags.sort_by(|a, b| {
+            (b.entry_count + b.notebook_count)
+                .cmp(&(a.entry_count + a.notebook_count))
+                .then_with(|| a.tag.cmp(&b.tag))
+        });
+        Some(tags)
+    })
+}
+
 /// Fetches notebooks from UFOS with SSR support in fullstack mode
 #[cfg(feature = "fullstack-server")]
 pub fn use_notebooks_from_ufos() -> (
@@ -1331,6 +1534,14 @@ pub fn use_standalone_entry_data(
                             }
                         }
                     }
+                    // Report the record CID so the offline page cache can drop
+                    // this page if the entry changes.
+                    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+                    {
+                        let _ = crate::service_worker::cache_current_entry_page(
+                            data.entry_view.cid.as_ref(),
+                        );
+                    }
                     let entry_json = serde_json::to_value(&data.entry).ok()?;
                     let entry_view_json = serde_json::to_value(&data.entry_view).ok()?;
                     let notebook_ctx_json = data
@@ -1491,6 +1702,37 @@ pub fn use_notebook_entry_by_rkey(
     (res, memo)
 }
 
+// ============================================================================
+// Stale-content revalidation
+// ============================================================================
+
+/// Revalidate a cached record view against its PDS.
+///
+/// Compares the CID a view was rendered from with the record's current CID,
+/// fetched directly from the PDS with every cache layer bypassed. Resolves to
+/// `Some(true)` when a newer version exists upstream, `Some(false)` when the
+/// cached copy is current, and `None` while the probe is in flight or failed.
+///
+/// The probe only runs in the browser: during SSR the server has just
+/// rendered the page, and probing there would double every request without
+/// anyone around to see the banner.
+pub fn use_record_stale_check(
+    uri: ReadSignal<AtUri<'static>>,
+    cid: ReadSignal<Cid<'static>>,
+) -> Resource<Option<bool>> {
+    let fetcher = use_context::<crate::fetch::Fetcher>();
+    use_resource(move || {
+        let fetcher = fetcher.clone();
+        async move {
+            if !cfg!(all(target_family = "wasm", target_os = "unknown")) {
+                return None;
+            }
+            let latest = fetcher.fetch_record_cid(&uri()).await.ok().flatten()?;
+            Some(latest != cid())
+        }
+    })
+}
+
 /// Fetches WhiteWind entry by rkey (SSR)
 #[cfg(feature = "fullstack-server")]
 pub fn use_whitewind_entry_data(
@@ -2034,6 +2276,113 @@ pub fn use_pckt_document_data(
     (res, memo)
 }
 
+// ============================================================================
+// Reading positions (client-side store)
+// ============================================================================
+
+/// localStorage key prefix for saved reading positions.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+const READING_KEY_PREFIX: &str = "weaver_reading:";
+
+/// A saved per-entry reading position.
+///
+/// Keyed by the entry CID so a republished entry (new CID) invalidates the
+/// stored offset instead of restoring a stale scroll position into changed
+/// content. The URL segments are stored as they appeared in the address bar
+/// so the continue-reading list can link back without re-resolving identity.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReadingPosition {
+    /// CID of the entry this position belongs to.
+    pub cid: String,
+    /// Identity segment of the entry URL.
+    pub ident: String,
+    /// Notebook title segment of the entry URL.
+    pub book_title: String,
+    /// Path segment of the entry URL.
+    pub entry_path: String,
+    /// Entry title for the continue-reading list.
+    pub title: String,
+    /// Absolute scroll offset in CSS pixels.
+    pub scroll_y: f64,
+    /// Fraction of the page scrolled past, 0.0 to 1.0.
+    pub progress: f64,
+    /// Milliseconds since the Unix epoch at last save, for recency sorting.
+    pub updated_at: f64,
+}
+
+/// Save a reading position (WASM only).
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn save_reading_position(pos: &ReadingPosition) {
+    use gloo_storage::{LocalStorage, Storage};
+    let _ = LocalStorage::set(format!("{}{}", READING_KEY_PREFIX, pos.cid), pos);
+}
+
+/// Save a reading position (non-WASM stub).
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn save_reading_position(_pos: &ReadingPosition) {}
+
+/// Load the saved reading position for an entry CID (WASM only).
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn load_reading_position(cid: &str) -> Option<ReadingPosition> {
+    use gloo_storage::{LocalStorage, Storage};
+    LocalStorage::get(format!("{}{}", READING_KEY_PREFIX, cid)).ok()
+}
+
+/// Load a saved reading position (non-WASM stub).
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn load_reading_position(_cid: &str) -> Option<ReadingPosition> {
+    None
+}
+
+/// Delete the saved reading position for an entry CID (WASM only).
+///
+/// Called when an entry is read to the end or abandoned near the top, so
+/// the continue-reading list only holds entries actually in progress.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn delete_reading_position(cid: &str) {
+    use gloo_storage::{LocalStorage, Storage};
+    LocalStorage::delete(format!("{}{}", READING_KEY_PREFIX, cid));
+}
+
+/// Delete a saved reading position (non-WASM stub).
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn delete_reading_position(_cid: &str) {}
+
+/// List all saved reading positions, most recently read first (WASM only).
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn list_reading_positions() -> Vec<ReadingPosition> {
+    use gloo_storage::{LocalStorage, Storage};
+
+    let mut positions = Vec::new();
+
+    // gloo_storage doesn't have a direct way to iterate keys,
+    // so we use web_sys directly.
+    if let Some(storage) = web_sys::window()
+        .and_then(|w| w.local_storage().ok())
+        .flatten()
+    {
+        let len = storage.length().unwrap_or(0);
+        for i in 0..len {
+            if let Ok(Some(key)) = storage.key(i) {
+                if key.starts_with(READING_KEY_PREFIX) {
+                    if let Ok(pos) = LocalStorage::get::<ReadingPosition>(&key) {
+                        positions.push(pos);
+                    }
+                }
+            }
+        }
+    }
+
+    positions.sort_by(|a, b| b.updated_at.total_cmp(&a.updated_at));
+    positions
+}
+
+/// List saved reading positions (non-WASM stub).
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn list_reading_positions() -> Vec<ReadingPosition> {
+    Vec::new()
+}
+
 #[cfg(feature = "fullstack-server")]
 #[put("/cache/{ident}/{cid}?name", cache: Extension<Arc<BlobCache>>)]
 pub async fn cache_blob(ident: SmolStr, cid: SmolStr, name: Option<SmolStr>) -> Result<()> {
@@ -2061,3 +2410,49 @@ pub async fn cache_blob_bytes(
     cache.insert_bytes(cid, body, cache_key);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_ranges_splits_top_level_blocks() {
+        let source = "# Title\n\nfirst paragraph\n\nsecond paragraph\n";
+        let ranges = segment_ranges(source).expect("should segment");
+        assert_eq!(ranges.len(), 3);
+        assert!(source[ranges[0].clone()].contains("# Title"));
+        assert!(source[ranges[1].clone()].contains("first paragraph"));
+        assert!(source[ranges[2].clone()].contains("second paragraph"));
+        // Segments are ordered and non-overlapping.
+        assert!(ranges[0].end <= ranges[1].start);
+        assert!(ranges[1].end <= ranges[2].start);
+    }
+
+    #[test]
+    fn test_segment_ranges_keeps_multiline_blocks_whole() {
+        // Blank lines inside fenced code and between loose list items must
+        // not split the block.
+        let source = "```rust\nlet a = 1;\n\nlet b = 2;\n```\n\n- one\n\n- two\n";
+        let ranges = segment_ranges(source).expect("should segment");
+        assert_eq!(ranges.len(), 2);
+        assert!(source[ranges[0].clone()].contains("let b = 2;"));
+        assert!(source[ranges[1].clone()].contains("- two"));
+    }
+
+    #[test]
+    fn test_segment_ranges_bails_on_cross_segment_definitions() {
+        // Footnote and link reference definitions resolve across segments.
+        assert!(segment_ranges("text[^1]\n\n[^1]: the footnote\n").is_none());
+        assert!(segment_ranges("[link][ref]\n\n[ref]: https://example.com\n").is_none());
+    }
+
+    #[test]
+    fn test_segment_ranges_glues_weaver_block_to_next_block() {
+        let source = "intro\n\n{.aside}\nstyled paragraph\n\noutro\n";
+        let ranges = segment_ranges(source).expect("should segment");
+        assert_eq!(ranges.len(), 3);
+        let glued = &source[ranges[1].clone()];
+        assert!(glued.contains("{.aside}"));
+        assert!(glued.contains("styled paragraph"));
+    }
+}
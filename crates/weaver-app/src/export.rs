@@ -0,0 +1,457 @@
+//! Notebook export: entries back to markdown, bundled as a ZIP archive.
+//!
+//! An escape hatch for user content. Each entry becomes a markdown file
+//! with YAML frontmatter carrying the record metadata (title, path, dates,
+//! tags), image embeds are fetched from the PDS and placed under `assets/`,
+//! and the whole set is downloaded as a single archive, built client-side.
+//!
+//! The ZIP writer is hand-rolled rather than a dependency: exports only
+//! need the stored (uncompressed) format, which is a page of spec — local
+//! headers, a central directory and the end record — and CRC-32 is the
+//! only non-trivial part.
+
+use dioxus::prelude::*;
+use jacquard::smol_str::SmolStr;
+use jacquard::types::ident::AtIdentifier;
+use weaver_api::sh_weaver::notebook::BookEntryView;
+use weaver_api::sh_weaver::notebook::entry::Entry;
+
+use crate::components::button::{Button, ButtonVariant};
+
+/// CRC-32 (IEEE) lookup table, built at compile time.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc = CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+/// Writer for stored (uncompressed) ZIP archives.
+///
+/// File names must be UTF-8 (the general-purpose flag advertises it) and
+/// are taken as given — sanitise them first with [`safe_file_name`].
+pub struct ZipBuilder {
+    data: Vec<u8>,
+    central: Vec<u8>,
+    entries: u16,
+}
+
+impl ZipBuilder {
+    pub fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            central: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    /// Append one file. Timestamps are left at the DOS epoch (1980-01-01);
+    /// the meaningful dates live in each entry's frontmatter instead.
+    pub fn add_file(&mut self, name: &str, contents: &[u8]) {
+        let crc = crc32(contents);
+        let size = contents.len() as u32;
+        let name_bytes = name.as_bytes();
+        let offset = self.data.len() as u32;
+        // DOS date 0x0021 is 1980-01-01; time 0 is midnight.
+        let (dos_time, dos_date) = (0u16, 0x0021u16);
+        // Bit 11: the file name is UTF-8.
+        let flags = 0x0800u16;
+
+        // Local file header.
+        self.data.extend_from_slice(&0x0403_4B50u32.to_le_bytes());
+        self.data.extend_from_slice(&20u16.to_le_bytes());
+        self.data.extend_from_slice(&flags.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+        self.data.extend_from_slice(&dos_time.to_le_bytes());
+        self.data.extend_from_slice(&dos_date.to_le_bytes());
+        self.data.extend_from_slice(&crc.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data
+            .extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+        self.data.extend_from_slice(name_bytes);
+        self.data.extend_from_slice(contents);
+
+        // Matching central directory record.
+        self.central
+            .extend_from_slice(&0x0201_4B50u32.to_le_bytes());
+        self.central.extend_from_slice(&20u16.to_le_bytes());
+        self.central.extend_from_slice(&20u16.to_le_bytes());
+        self.central.extend_from_slice(&flags.to_le_bytes());
+        self.central.extend_from_slice(&0u16.to_le_bytes());
+        self.central.extend_from_slice(&dos_time.to_le_bytes());
+        self.central.extend_from_slice(&dos_date.to_le_bytes());
+        self.central.extend_from_slice(&crc.to_le_bytes());
+        self.central.extend_from_slice(&size.to_le_bytes());
+        self.central.extend_from_slice(&size.to_le_bytes());
+        self.central
+            .extend_from_slice(&(name_bytes.len() as u16).to_le_bytes());
+        self.central.extend_from_slice(&0u16.to_le_bytes());
+        self.central.extend_from_slice(&0u16.to_le_bytes());
+        self.central.extend_from_slice(&0u16.to_le_bytes());
+        self.central.extend_from_slice(&0u16.to_le_bytes());
+        self.central.extend_from_slice(&0u32.to_le_bytes());
+        self.central.extend_from_slice(&offset.to_le_bytes());
+        self.central.extend_from_slice(name_bytes);
+
+        self.entries += 1;
+    }
+
+    /// Close the archive and return its bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        let central_offset = self.data.len() as u32;
+        let central_size = self.central.len() as u32;
+        self.data.extend_from_slice(&self.central);
+
+        // End of central directory record.
+        self.data.extend_from_slice(&0x0605_4B50u32.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data.extend_from_slice(&central_size.to_le_bytes());
+        self.data.extend_from_slice(&central_offset.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes());
+
+        self.data
+    }
+}
+
+impl Default for ZipBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quote a string for a YAML frontmatter scalar.
+fn yaml_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            other => quoted.push(other),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Serialise an entry back to markdown with YAML frontmatter.
+///
+/// The frontmatter carries the record metadata that would otherwise be
+/// lost in a plain-markdown export; the body is the content exactly as
+/// stored.
+pub fn entry_to_markdown(entry: &Entry<'_>) -> String {
+    let mut out = String::new();
+    out.push_str("---\n");
+    out.push_str(&format!("title: {}\n", yaml_quote(entry.title.as_ref())));
+    out.push_str(&format!("path: {}\n", yaml_quote(entry.path.as_ref())));
+    out.push_str(&format!("created_at: {}\n", entry.created_at));
+    if let Some(updated_at) = &entry.updated_at {
+        out.push_str(&format!("updated_at: {}\n", updated_at));
+    }
+    if let Some(tags) = &entry.tags {
+        if !tags.is_empty() {
+            out.push_str("tags:\n");
+            for tag in tags.iter() {
+                out.push_str(&format!("  - {}\n", yaml_quote(tag.as_ref())));
+            }
+        }
+    }
+    out.push_str("---\n\n");
+    out.push_str(entry.content.as_ref());
+    if !entry.content.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// Reduce an arbitrary title or path to a safe archive file name.
+///
+/// Keeps alphanumerics, `-`, `_` and `.`; everything else collapses to a
+/// single `-`. Path separators are deliberately flattened so one entry
+/// cannot escape the archive root.
+pub fn safe_file_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut last_dash = false;
+    for ch in name.trim().chars() {
+        if ch.is_alphanumeric() || ch == '-' || ch == '_' || ch == '.' {
+            out.push(ch);
+            last_dash = false;
+        } else if !last_dash && !out.is_empty() {
+            out.push('-');
+            last_dash = true;
+        }
+    }
+    let trimmed = out.trim_matches(|c| c == '-' || c == '.');
+    if trimmed.is_empty() {
+        "untitled".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Resolve a candidate name against the set already used, appending a
+/// counter on collision. Inserts the result into `used`.
+fn unique_name(used: &mut std::collections::HashSet<String>, stem: &str, ext: &str) -> String {
+    let mut candidate = format!("{stem}{ext}");
+    let mut counter = 2;
+    while used.contains(&candidate) {
+        candidate = format!("{stem}-{counter}{ext}");
+        counter += 1;
+    }
+    used.insert(candidate.clone());
+    candidate
+}
+
+/// Export button shown on a notebook's index page.
+///
+/// Converts every entry back to markdown, fetches referenced image blobs,
+/// and hands the archive to the browser as a download. The work happens
+/// entirely client-side from data the page has already substantially
+/// loaded; on the server this renders but clicks never happen.
+#[component]
+pub fn ExportNotebookButton(
+    ident: ReadSignal<AtIdentifier<'static>>,
+    book_title: ReadSignal<SmolStr>,
+    entries: Vec<BookEntryView<'static>>,
+) -> Element {
+    let mut exporting = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    #[allow(unused)]
+    let fetcher = use_context::<crate::fetch::Fetcher>();
+
+    let on_export = move |_| {
+        #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+        {
+            if exporting() {
+                return;
+            }
+            let entries = entries.clone();
+            let fetcher = fetcher.clone();
+            spawn(async move {
+                exporting.set(true);
+                error.set(None);
+                match build_notebook_archive(&fetcher, ident(), &entries).await {
+                    Ok(archive) => {
+                        let file = format!("{}.zip", safe_file_name(book_title().as_str()));
+                        if let Err(err) = download_bytes(&file, &archive) {
+                            tracing::error!(?err, "failed to trigger export download");
+                            error.set(Some("Download failed".to_string()));
+                        }
+                    }
+                    Err(message) => error.set(Some(message)),
+                }
+                exporting.set(false);
+            });
+        }
+        #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+        {
+            // Export is browser-only; keep the captures alive so the
+            // server build of this closure stays warning-free.
+            let _ = (&entries, &fetcher, ident, book_title, exporting, error);
+        }
+    };
+
+    rsx! {
+        Button {
+            variant: ButtonVariant::Ghost,
+            disabled: exporting(),
+            onclick: on_export,
+            if exporting() {
+                "Exporting..."
+            } else {
+                "Export"
+            }
+        }
+        if let Some(message) = error() {
+            span { class: "export-error", "{message}" }
+        }
+    }
+}
+
+/// Assemble the archive: one markdown file per entry plus fetched image
+/// blobs under `assets/`.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+async fn build_notebook_archive(
+    fetcher: &crate::fetch::Fetcher,
+    ident: AtIdentifier<'static>,
+    entries: &[BookEntryView<'static>],
+) -> Result<Vec<u8>, String> {
+    use jacquard::IntoStatic;
+    use jacquard::from_data_owned;
+    use jacquard::xrpc::XrpcExt;
+    use weaver_api::com_atproto::sync::get_blob::GetBlob;
+
+    let client = fetcher.get_client();
+    let (did, pds_url) = match &ident {
+        AtIdentifier::Did(did) => {
+            let pds = client
+                .pds_for_did(did)
+                .await
+                .map_err(|e| format!("Could not resolve PDS: {e}"))?;
+            (did.clone().into_static(), pds)
+        }
+        AtIdentifier::Handle(handle) => client
+            .pds_for_handle(handle)
+            .await
+            .map_err(|e| format!("Could not resolve PDS: {e}"))?,
+    };
+
+    let mut zip = ZipBuilder::new();
+    let mut used = std::collections::HashSet::new();
+    for view in entries {
+        let entry: Entry<'static> = match from_data_owned(view.entry.record.clone()) {
+            Ok(entry) => entry,
+            Err(err) => {
+                // A malformed record should not sink the rest of the
+                // notebook; skip it and keep going.
+                tracing::warn!(uri = %view.entry.uri, ?err, "skipping unparseable entry");
+                continue;
+            }
+        };
+
+        let stem = safe_file_name(if entry.path.as_ref().is_empty() {
+            entry.title.as_ref()
+        } else {
+            entry.path.as_ref()
+        });
+        let name = unique_name(&mut used, &stem, ".md");
+        zip.add_file(&name, entry_to_markdown(&entry).as_bytes());
+
+        let images = entry
+            .embeds
+            .as_ref()
+            .and_then(|e| e.images.as_ref())
+            .map(|imgs| imgs.images.clone())
+            .unwrap_or_default();
+        for image in &images {
+            let cid = image.image.blob().cid();
+            let response = client
+                .xrpc(pds_url.clone())
+                .send(&GetBlob::new().cid(cid.clone()).did(did.clone()).build())
+                .await;
+            match response {
+                Ok(blob) => {
+                    let stem = image
+                        .name
+                        .as_ref()
+                        .map(|n| safe_file_name(n.as_ref()))
+                        .unwrap_or_else(|| cid.to_string());
+                    let name = unique_name(&mut used, &format!("assets/{stem}"), "");
+                    zip.add_file(&name, blob.buffer());
+                }
+                Err(err) => {
+                    tracing::warn!(cid = %cid, ?err, "skipping unfetchable blob in export");
+                }
+            }
+        }
+    }
+
+    if used.is_empty() {
+        return Err("Nothing to export".to_string());
+    }
+    Ok(zip.finish())
+}
+
+/// Hand `bytes` to the browser as a file download.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+fn download_bytes(filename: &str, bytes: &[u8]) -> Result<(), wasm_bindgen::JsValue> {
+    use js_sys::{Array, Uint8Array};
+    use wasm_bindgen::JsCast;
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    let window = web_sys::window().ok_or("no window")?;
+    let document = window.document().ok_or("no document")?;
+
+    let parts = Array::new();
+    parts.push(&Uint8Array::from(bytes));
+    let opts = BlobPropertyBag::new();
+    opts.set_type("application/zip");
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &opts)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+    Url::revoke_object_url(&url)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32_matches_reference_vector() {
+        // The standard CRC-32 check value.
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn zip_has_expected_structure() {
+        let mut zip = ZipBuilder::new();
+        zip.add_file("hello.md", b"hello\n");
+        zip.add_file("assets/a.png", &[1, 2, 3]);
+        let bytes = zip.finish();
+
+        // Local header, central directory and end record signatures.
+        assert_eq!(&bytes[0..4], &0x0403_4B50u32.to_le_bytes());
+        let end = bytes.len() - 22;
+        assert_eq!(&bytes[end..end + 4], &0x0605_4B50u32.to_le_bytes());
+        // Two entries recorded in the end record.
+        assert_eq!(&bytes[end + 10..end + 12], &2u16.to_le_bytes());
+        // The central directory offset points at a central record.
+        let offset = u32::from_le_bytes(bytes[end + 16..end + 20].try_into().unwrap()) as usize;
+        assert_eq!(&bytes[offset..offset + 4], &0x0201_4B50u32.to_le_bytes());
+    }
+
+    #[test]
+    fn safe_file_name_flattens_separators() {
+        assert_eq!(safe_file_name("my entry/notes"), "my-entry-notes");
+        assert_eq!(safe_file_name("../../etc/passwd"), "etc-passwd");
+        assert_eq!(safe_file_name("  "), "untitled");
+    }
+
+    #[test]
+    fn unique_name_appends_counter_on_collision() {
+        let mut used = std::collections::HashSet::new();
+        assert_eq!(unique_name(&mut used, "notes", ".md"), "notes.md");
+        assert_eq!(unique_name(&mut used, "notes", ".md"), "notes-2.md");
+        assert_eq!(unique_name(&mut used, "notes", ".md"), "notes-3.md");
+    }
+
+    #[test]
+    fn yaml_quote_escapes_specials() {
+        assert_eq!(yaml_quote("plain"), "\"plain\"");
+        assert_eq!(yaml_quote("a \"b\" \\c"), "\"a \\\"b\\\" \\\\c\"");
+        assert_eq!(yaml_quote("two\nlines"), "\"two\\nlines\"");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::auth::AuthStore;
 use crate::cache_impl;
+use crate::fetch_error::FetchErrorKind;
 use dioxus::Result;
 use jacquard::AuthorizationToken;
 use jacquard::CowStr;
@@ -120,6 +121,82 @@ impl Client {
     }
 }
 
+/// Total attempts for one logical HTTP request, first try included.
+const MAX_HTTP_ATTEMPTS: u32 = 3;
+
+/// Rebuild a request for a retry; `http::Request` is not `Clone`.
+fn clone_http_request(request: &http::Request<Vec<u8>>) -> http::Request<Vec<u8>> {
+    let mut builder = http::Request::builder()
+        .method(request.method().clone())
+        .uri(request.uri().clone())
+        .version(request.version());
+    if let Some(headers) = builder.headers_mut() {
+        *headers = request.headers().clone();
+    }
+    builder
+        .body(request.body().clone())
+        .expect("rebuilding a valid request cannot fail")
+}
+
+/// Retry category for one HTTP attempt, if it should be retried at all.
+///
+/// 429 is always safe to retry — the server refused to execute the
+/// request. Transport errors and 5xx responses are only retried for
+/// safe (GET/HEAD) methods: a failed POST may still have executed on
+/// the server, and retrying it could create duplicate records.
+fn http_retry_kind(
+    request: &http::Request<Vec<u8>>,
+    result: &core::result::Result<http::Response<Vec<u8>>, IdentityError>,
+) -> Option<FetchErrorKind> {
+    let idempotent =
+        *request.method() == http::Method::GET || *request.method() == http::Method::HEAD;
+    match result {
+        Ok(response) => match response.status().as_u16() {
+            429 => Some(FetchErrorKind::RateLimited),
+            502..=504 if idempotent => Some(FetchErrorKind::Network),
+            _ => None,
+        },
+        Err(e) if idempotent => {
+            // IdentityError also covers permanent failures (bad DIDs,
+            // malformed documents); only retry what looks like transport.
+            (FetchErrorKind::classify(&e.to_string()) == FetchErrorKind::Network)
+                .then_some(FetchErrorKind::Network)
+        }
+        Err(_) => None,
+    }
+}
+
+impl Client {
+    /// Send one HTTP request, retrying transient failures with
+    /// exponential backoff.
+    async fn send_http_retrying(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> core::result::Result<http::Response<Vec<u8>>, IdentityError> {
+        let mut attempt: u32 = 0;
+        loop {
+            let result = self
+                .oauth_client
+                .send_http(clone_http_request(&request))
+                .await;
+            match http_retry_kind(&request, &result) {
+                Some(kind) if attempt + 1 < MAX_HTTP_ATTEMPTS => {
+                    let delay = crate::fetch_error::backoff_delay(kind, attempt);
+                    tracing::debug!(
+                        "retrying {} {} after transient failure (attempt {})",
+                        request.method(),
+                        request.uri(),
+                        attempt + 1
+                    );
+                    crate::fetch_error::backoff_sleep(delay).await;
+                    attempt += 1;
+                }
+                _ => return result,
+            }
+        }
+    }
+}
+
 impl HttpClient for Client {
     type Error = IdentityError;
 
@@ -129,7 +206,7 @@ impl HttpClient for Client {
         request: http::Request<Vec<u8>>,
     ) -> impl Future<Output = core::result::Result<http::Response<Vec<u8>>, Self::Error>> + Send
     {
-        self.oauth_client.send_http(request)
+        self.send_http_retrying(request)
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -137,7 +214,7 @@ impl HttpClient for Client {
         &self,
         request: http::Request<Vec<u8>>,
     ) -> impl Future<Output = core::result::Result<http::Response<Vec<u8>>, Self::Error>> {
-        self.oauth_client.send_http(request)
+        self.send_http_retrying(request)
     }
 }
 
@@ -411,6 +488,9 @@ pub struct Fetcher {
     #[cfg(feature = "server")]
     standalone_entry_cache:
         cache_impl::Cache<(AtIdentifier<'static>, SmolStr), Arc<StandaloneEntryData>>,
+    /// Per-paragraph rendered HTML for read-only entry views. Not server
+    /// gated: the client re-renders on live updates and benefits equally.
+    paragraph_cache: cache_impl::ParagraphCache,
 }
 
 impl Fetcher {
@@ -439,9 +519,14 @@ impl Fetcher {
             profile_cache: cache_impl::new_cache(100, std::time::Duration::from_secs(1800)),
             #[cfg(feature = "server")]
             standalone_entry_cache: cache_impl::new_cache(100, std::time::Duration::from_secs(30)),
+            paragraph_cache: cache_impl::ParagraphCache::new(),
         }
     }
 
+    pub fn paragraph_cache(&self) -> &cache_impl::ParagraphCache {
+        &self.paragraph_cache
+    }
+
     pub async fn upgrade_to_authenticated(
         &self,
         session: OAuthSession<JacquardResolver, crate::auth::AuthStore>,
@@ -482,6 +567,24 @@ impl Fetcher {
         self.client.clone()
     }
 
+    /// Drop cached views that embed viewer-scoped state (likes, bookmarks,
+    /// reading progress) so an account switch can't leak the previous
+    /// viewer's data. The paragraph cache survives: rendered HTML is a pure
+    /// function of author and source, and the title-to-ident map is
+    /// viewer-independent too.
+    pub fn clear_session_caches(&self) {
+        #[cfg(feature = "server")]
+        {
+            cache_impl::clear(&self.book_cache);
+            cache_impl::clear(&self.entry_cache);
+            cache_impl::clear(&self.profile_cache);
+            cache_impl::clear(&self.standalone_entry_cache);
+        }
+        // Permission state is viewer-independent but cheap to rebuild,
+        // and clearing it avoids surprising anyone mid-account-switch.
+        weaver_common::permission_cache::invalidate_all();
+    }
+
     pub async fn get_notebook(
         &self,
         ident: AtIdentifier<'static>,
@@ -496,7 +599,7 @@ impl Fetcher {
         if let Some((notebook, entries)) = client
             .notebook_by_title(&ident, &title)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?
+            .map_err(crate::fetch_error::capture)?
         {
             let stored = Arc::new((notebook, entries));
             #[cfg(feature = "server")]
@@ -513,7 +616,7 @@ impl Fetcher {
             }
             Ok(Some(stored))
         } else {
-            Err(dioxus::CapturedError::from_display("Notebook not found"))
+            Err(crate::fetch_error::capture("Notebook not found"))
         }
     }
 
@@ -577,13 +680,61 @@ impl Fetcher {
                 cache_impl::insert(&self.entry_cache, (ident, entry_title), stored.clone());
                 Ok(Some(stored))
             } else {
-                Err(dioxus::CapturedError::from_display("Entry not found"))
+                Err(crate::fetch_error::capture("Entry not found"))
             }
         } else {
-            Err(dioxus::CapturedError::from_display("Notebook not found"))
+            Err(crate::fetch_error::capture("Notebook not found"))
         }
     }
 
+    /// Fetch the current CID of a record directly from its PDS, bypassing
+    /// every cache layer.
+    ///
+    /// Used to revalidate cached views: when the CID has moved past the one
+    /// a view was rendered from, the cached copy is stale. Returns `None`
+    /// for URIs without a collection and rkey (profile-only URIs have no
+    /// record to check).
+    pub async fn fetch_record_cid(
+        &self,
+        uri: &AtUri<'_>,
+    ) -> Result<Option<jacquard::types::cid::Cid<'static>>> {
+        use weaver_api::com_atproto::repo::get_record::GetRecord;
+
+        let (Some(collection), Some(rkey)) = (uri.collection(), uri.rkey()) else {
+            return Ok(None);
+        };
+
+        let client = self.get_client();
+        let (repo_did, pds_url) = match uri.authority() {
+            AtIdentifier::Did(did) => {
+                let pds = client
+                    .pds_for_did(did)
+                    .await
+                    .map_err(crate::fetch_error::capture)?;
+                (did.clone(), pds)
+            }
+            AtIdentifier::Handle(handle) => client
+                .pds_for_handle(handle)
+                .await
+                .map_err(crate::fetch_error::capture)?,
+        };
+
+        let resp = client
+            .xrpc(pds_url)
+            .send(
+                &GetRecord::new()
+                    .repo(AtIdentifier::Did(repo_did))
+                    .collection(Nsid::raw(collection.as_ref()))
+                    .rkey(rkey.clone())
+                    .build(),
+            )
+            .await
+            .map_err(crate::fetch_error::capture)?;
+
+        let output = resp.into_output().map_err(crate::fetch_error::capture)?;
+        Ok(output.cid.map(|cid| cid.into_static()))
+    }
+
     #[cfg(feature = "use-index")]
     pub async fn fetch_notebooks_from_ufos(
         &self,
@@ -596,18 +747,16 @@ impl Fetcher {
         let resp = client
             .send(GetNotebookFeed::new().limit(100).build())
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
-        let output = resp
-            .into_output()
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let output = resp.into_output().map_err(crate::fetch_error::capture)?;
 
         let mut notebooks = Vec::new();
 
         for notebook in output.notebooks {
             // Extract entry_list from the record
-            let book: Book = jacquard::from_data(&notebook.record)
-                .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            let book: Book =
+                jacquard::from_data(&notebook.record).map_err(crate::fetch_error::capture)?;
             let book = book.into_static();
 
             let entries: Vec<StrongRef<'static>> = book
@@ -652,12 +801,10 @@ impl Fetcher {
         let url = "https://ufos-api.microcosm.blue/records?collection=sh.weaver.notebook.book";
         let response = reqwest::get(url)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
-        let records: Vec<UfosRecord> = response
-            .json()
-            .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let records: Vec<UfosRecord> =
+            response.json().await.map_err(crate::fetch_error::capture)?;
 
         let mut notebooks = Vec::new();
         let client = self.get_client();
@@ -671,7 +818,7 @@ impl Fetcher {
                 ufos_record.rkey
             );
             let uri = AtUri::new_owned(uri_str).map_err(|e| {
-                dioxus::CapturedError::from_display(format_smolstr!("Invalid URI: {}", e).as_str())
+                crate::fetch_error::capture(format_smolstr!("Invalid URI: {}", e).as_str())
             })?;
             match client.view_notebook(&uri).await {
                 Ok((notebook, entries)) => {
@@ -730,11 +877,9 @@ impl Fetcher {
         let resp = client
             .send(GetEntryFeed::new().limit(100).build())
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
-        let output = resp
-            .into_output()
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let output = resp.into_output().map_err(crate::fetch_error::capture)?;
 
         let mut entries = Vec::new();
 
@@ -745,8 +890,8 @@ impl Fetcher {
                 .map(|dt| dt.timestamp_millis() as u64)
                 .unwrap_or(0);
 
-            let entry: Entry = jacquard::from_data(&entry_view.record)
-                .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            let entry: Entry =
+                jacquard::from_data(&entry_view.record).map_err(crate::fetch_error::capture)?;
             let entry = entry.into_static();
 
             entries.push(Arc::new((entry_view.into_static(), entry, timestamp)));
@@ -766,12 +911,12 @@ impl Fetcher {
 
         let response = reqwest::get(url).await.map_err(|e| {
             tracing::error!("[fetch_entries_from_ufos] request failed: {:?}", e);
-            dioxus::CapturedError::from_display(e)
+            crate::fetch_error::capture(e)
         })?;
 
         let mut records: Vec<UfosRecord> = response.json().await.map_err(|e| {
             tracing::error!("[fetch_entries_from_ufos] json parse failed: {:?}", e);
-            dioxus::CapturedError::from_display(e)
+            crate::fetch_error::capture(e)
         })?;
         records.sort_by(|a, b| b.time_us.cmp(&a.time_us));
 
@@ -831,18 +976,16 @@ impl Fetcher {
                     .build(),
             )
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
-        let output = resp
-            .into_output()
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let output = resp.into_output().map_err(crate::fetch_error::capture)?;
 
         let mut notebooks = Vec::new();
 
         for notebook in output.notebooks {
             // Extract entry_list from the record
-            let book: Book = jacquard::from_data(&notebook.record)
-                .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            let book: Book =
+                jacquard::from_data(&notebook.record).map_err(crate::fetch_error::capture)?;
             let book = book.into_static();
 
             let entries: Vec<StrongRef<'static>> = book
@@ -903,13 +1046,13 @@ impl Fetcher {
                 let pds = client
                     .pds_for_did(did)
                     .await
-                    .map_err(|e| dioxus::CapturedError::from_display(e))?;
+                    .map_err(crate::fetch_error::capture)?;
                 (did.clone(), pds)
             }
             AtIdentifier::Handle(handle) => client
                 .pds_for_handle(handle)
                 .await
-                .map_err(|e| dioxus::CapturedError::from_display(e))?,
+                .map_err(crate::fetch_error::capture)?,
         };
 
         // Fetch all notebook records for this repo
@@ -935,7 +1078,7 @@ impl Fetcher {
                     e,
                     pds_url
                 );
-                dioxus::CapturedError::from_display(e)
+                crate::fetch_error::capture(e)
             })?;
 
         let mut notebooks = Vec::new();
@@ -1011,18 +1154,16 @@ impl Fetcher {
                     .build(),
             )
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
-        let output = resp
-            .into_output()
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let output = resp.into_output().map_err(crate::fetch_error::capture)?;
 
         let mut entries = Vec::new();
 
         for entry_view in output.entries {
             // Deserialize Entry from the record field
-            let entry: Entry = jacquard::from_data(&entry_view.record)
-                .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            let entry: Entry =
+                jacquard::from_data(&entry_view.record).map_err(crate::fetch_error::capture)?;
             let entry = entry.into_static();
 
             entries.push(Arc::new((entry_view.into_static(), entry)));
@@ -1052,13 +1193,13 @@ impl Fetcher {
                 let pds = client
                     .pds_for_did(did)
                     .await
-                    .map_err(|e| dioxus::CapturedError::from_display(e))?;
+                    .map_err(crate::fetch_error::capture)?;
                 (did.clone(), pds)
             }
             AtIdentifier::Handle(handle) => client
                 .pds_for_handle(handle)
                 .await
-                .map_err(|e| dioxus::CapturedError::from_display(e))?,
+                .map_err(crate::fetch_error::capture)?,
         };
 
         // Fetch all entry records for this repo
@@ -1072,7 +1213,7 @@ impl Fetcher {
                     .build(),
             )
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         let mut entries = Vec::new();
         let ident_static = ident.clone().into_static();
@@ -1110,7 +1251,7 @@ impl Fetcher {
         if let Some(result) = self.get_notebook(ident.clone(), book_title).await? {
             Ok(Some(result.as_ref().1.clone()))
         } else {
-            Err(dioxus::CapturedError::from_display("Notebook not found"))
+            Err(crate::fetch_error::capture("Notebook not found"))
         }
     }
 
@@ -1134,7 +1275,7 @@ impl Fetcher {
         let (_uri, profile_view) = client
             .hydrate_profile_view(&ident)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         let result = Arc::new(profile_view);
         #[cfg(feature = "server")]
@@ -1164,20 +1305,18 @@ impl Fetcher {
         let (entry_view, entry) = client
             .fetch_entry_by_rkey(&ident, &rkey)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         // Try to find notebook context via constellation
         let entry_uri = entry_view.uri.clone();
         let at_uri = AtUri::new(entry_uri.as_ref()).map_err(|e| {
-            dioxus::CapturedError::from_display(
-                format_smolstr!("Invalid entry URI: {}", e).as_str(),
-            )
+            crate::fetch_error::capture(format_smolstr!("Invalid entry URI: {}", e).as_str())
         })?;
 
         let (total, first_notebook) = client
             .find_notebooks_for_entry(&at_uri)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         // Only provide notebook context if entry is in exactly one notebook
         let notebook_context = if total == 1 {
@@ -1190,7 +1329,7 @@ impl Fetcher {
                     notebook_id.rkey.0.as_str()
                 );
                 let notebook_uri = AtUri::new_owned(notebook_uri_str).map_err(|e| {
-                    dioxus::CapturedError::from_display(
+                    crate::fetch_error::capture(
                         format_smolstr!("Invalid notebook URI: {}", e).as_str(),
                     )
                 })?;
@@ -1252,17 +1391,17 @@ impl Fetcher {
         let (entry_view, entry) = client
             .fetch_entry_by_rkey(&ident, &rkey)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         // Fetch notebook by title
         let notebook_result = client
             .notebook_by_title(&ident, &book_title)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         let (notebook, entries) = match notebook_result {
             Some((n, e)) => (n, e),
-            None => return Err(dioxus::CapturedError::from_display("Notebook not found")),
+            None => return Err(crate::fetch_error::capture("Notebook not found")),
         };
 
         // Find entry position in notebook
@@ -1282,15 +1421,13 @@ impl Fetcher {
         // Check if entry is in multiple notebooks - if so, clear prev/next
         let entry_uri = book_entry_view.entry.uri.clone();
         let at_uri = AtUri::new(entry_uri.as_ref()).map_err(|e| {
-            dioxus::CapturedError::from_display(
-                format_smolstr!("Invalid entry URI: {}", e).as_str(),
-            )
+            crate::fetch_error::capture(format_smolstr!("Invalid entry URI: {}", e).as_str())
         })?;
 
         let (total, _) = client
             .find_notebooks_for_entry(&at_uri)
             .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+            .map_err(crate::fetch_error::capture)?;
 
         if total >= 2 {
             // Entry is in multiple notebooks - clear prev/next to avoid ambiguity
@@ -1479,6 +1616,32 @@ impl LexiconSchemaResolver for Fetcher {
 // ============================================================================
 
 impl Fetcher {
+    /// Send an index query, refreshing the session once on an expired
+    /// token.
+    ///
+    /// The closure builds a fresh request per attempt, so the retry is
+    /// signed with the refreshed token rather than the stale one. A
+    /// second auth failure after a successful refresh is surfaced as-is:
+    /// at that point the session is genuinely unusable.
+    #[cfg(feature = "use-index")]
+    async fn send_refreshing<T, E, F, Fut>(&self, mut op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = core::result::Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        use crate::fetch_error::FetchError;
+
+        let first = match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) => FetchError::from_display(e),
+        };
+        if first.kind == FetchErrorKind::AuthExpired && self.client.refresh().await.is_ok() {
+            return op().await.map_err(crate::fetch_error::capture);
+        }
+        Err(first.into_captured())
+    }
+
     /// Get edit history for a resource from weaver-index.
     ///
     /// Returns edit roots and diffs for the given resource URI.
@@ -1490,14 +1653,17 @@ impl Fetcher {
         use weaver_api::sh_weaver::edit::get_edit_history::GetEditHistory;
 
         let client = self.get_client();
-        let resp = client
-            .send(GetEditHistory::new().resource(resource_uri.clone()).build())
-            .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let resp = self
+            .send_refreshing(|| {
+                let client = client.clone();
+                let request = GetEditHistory::new().resource(resource_uri.clone()).build();
+                async move { client.send(request).await }
+            })
+            .await?;
 
         resp.into_output()
             .map(|o| o.into_static())
-            .map_err(|e| dioxus::CapturedError::from_display(e))
+            .map_err(crate::fetch_error::capture)
     }
 
     /// List drafts for an actor from weaver-index.
@@ -1509,14 +1675,17 @@ impl Fetcher {
         use weaver_api::sh_weaver::edit::list_drafts::ListDrafts;
 
         let client = self.get_client();
-        let resp = client
-            .send(ListDrafts::new().actor(actor.clone()).build())
-            .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+        let resp = self
+            .send_refreshing(|| {
+                let client = client.clone();
+                let request = ListDrafts::new().actor(actor.clone()).build();
+                async move { client.send(request).await }
+            })
+            .await?;
 
         resp.into_output()
             .map(|o| o.into_static())
-            .map_err(|e| dioxus::CapturedError::from_display(e))
+            .map_err(crate::fetch_error::capture)
     }
 
     /// Get resource sessions from weaver-index.
@@ -1532,18 +1701,19 @@ impl Fetcher {
         use weaver_api::sh_weaver::collab::get_resource_sessions::GetResourceSessions;
 
         let client = self.get_client();
-        let resp = client
-            .send(
-                GetResourceSessions::new()
+        let resp = self
+            .send_refreshing(|| {
+                let client = client.clone();
+                let request = GetResourceSessions::new()
                     .resource(resource_uri.clone())
-                    .build(),
-            )
-            .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+                    .build();
+                async move { client.send(request).await }
+            })
+            .await?;
 
         resp.into_output()
             .map(|o| o.into_static())
-            .map_err(|e| dioxus::CapturedError::from_display(e))
+            .map_err(crate::fetch_error::capture)
     }
 
     /// Get resource participants from weaver-index.
@@ -1561,18 +1731,44 @@ impl Fetcher {
         use weaver_api::sh_weaver::collab::get_resource_participants::GetResourceParticipants;
 
         let client = self.get_client();
-        let resp = client
-            .send(
-                GetResourceParticipants::new()
+        let resp = self
+            .send_refreshing(|| {
+                let client = client.clone();
+                let request = GetResourceParticipants::new()
                     .resource(resource_uri.clone())
-                    .build(),
-            )
-            .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+                    .build();
+                async move { client.send(request).await }
+            })
+            .await?;
+
+        resp.into_output()
+            .map(|o| o.into_static())
+            .map_err(crate::fetch_error::capture)
+    }
+
+    /// Get bookmarks referencing a subject from weaver-index.
+    ///
+    /// Used for aggregate bookmark counts on entries and notebooks.
+    #[cfg(feature = "use-index")]
+    pub async fn get_bookmarked_by(
+        &self,
+        subject: &AtUri<'_>,
+    ) -> Result<weaver_api::sh_weaver::graph::get_bookmarked_by::GetBookmarkedByOutput<'static>>
+    {
+        use weaver_api::sh_weaver::graph::get_bookmarked_by::GetBookmarkedBy;
+
+        let client = self.get_client();
+        let resp = self
+            .send_refreshing(|| {
+                let client = client.clone();
+                let request = GetBookmarkedBy::new().subject(subject.clone()).build();
+                async move { client.send(request).await }
+            })
+            .await?;
 
         resp.into_output()
             .map(|o| o.into_static())
-            .map_err(|e| dioxus::CapturedError::from_display(e))
+            .map_err(crate::fetch_error::capture)
     }
 
     /// Get contributors for a resource from weaver-index.
@@ -1584,18 +1780,19 @@ impl Fetcher {
         use weaver_api::sh_weaver::edit::get_contributors::GetContributors;
 
         let client = self.get_client();
-        let resp = client
-            .send(
-                GetContributors::new()
+        let resp = self
+            .send_refreshing(|| {
+                let client = client.clone();
+                let request = GetContributors::new()
                     .resource(resource_uri.clone())
-                    .build(),
-            )
-            .await
-            .map_err(|e| dioxus::CapturedError::from_display(e))?;
+                    .build();
+                async move { client.send(request).await }
+            })
+            .await?;
 
         resp.into_output()
             .map(|o| o.into_static())
-            .map_err(|e| dioxus::CapturedError::from_display(e))
+            .map_err(crate::fetch_error::capture)
     }
 }
 
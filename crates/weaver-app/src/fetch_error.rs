@@ -0,0 +1,218 @@
+//! Classification of data-fetching failures.
+//!
+//! The fetch layer funnels many error types (XRPC, identity resolution,
+//! agent state) into [`dioxus::CapturedError`] for error boundaries,
+//! which erases all structure — every failure used to render the same
+//! "An error occurred". This module classifies failures into coarse
+//! kinds the UI can act on: a retry button for transient trouble, a
+//! session refresh for an expired token, and a plain 404 page for
+//! records that do not exist.
+
+use std::fmt;
+use std::time::Duration;
+
+/// Coarse category of a fetch failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FetchErrorKind {
+    /// Transport-level failure: DNS, connection refused, timeout.
+    Network,
+    /// The record or page does not exist.
+    NotFound,
+    /// The session token was rejected or has expired.
+    AuthExpired,
+    /// The upstream service asked us to slow down.
+    RateLimited,
+    /// Anything we cannot classify further.
+    Other,
+}
+
+impl FetchErrorKind {
+    /// Classify an error by its rendered message.
+    ///
+    /// The fetch layer deals in several unrelated error types, so
+    /// classification keys off the wire-level vocabulary they all share
+    /// (status codes and standard XRPC error names) rather than
+    /// concrete types. Unknown messages fall through to [`Self::Other`].
+    pub fn classify(message: &str) -> Self {
+        let msg = message.to_ascii_lowercase();
+        if msg.contains("429") || msg.contains("ratelimit") || msg.contains("rate limit") {
+            Self::RateLimited
+        } else if msg.contains("401")
+            || msg.contains("expiredtoken")
+            || msg.contains("invalidtoken")
+            || msg.contains("token expired")
+            || msg.contains("session expired")
+            || msg.contains("not authenticated")
+        {
+            Self::AuthExpired
+        } else if msg.contains("404") || msg.contains("notfound") || msg.contains("not found") {
+            Self::NotFound
+        } else if msg.contains("timed out")
+            || msg.contains("timeout")
+            || msg.contains("connection")
+            || msg.contains("dns")
+            || msg.contains("network")
+            || msg.contains("failed to fetch")
+        {
+            Self::Network
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Whether retrying without user action can plausibly succeed.
+    pub fn is_transient(self) -> bool {
+        matches!(self, Self::Network | Self::RateLimited)
+    }
+
+    /// Stable lowercase label; doubles as the classification anchor when
+    /// the message later round-trips through [`FetchErrorKind::classify`].
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Network => "network error",
+            Self::NotFound => "not found",
+            Self::AuthExpired => "session expired",
+            Self::RateLimited => "rate limited",
+            Self::Other => "error",
+        }
+    }
+
+    /// Message shown to the user on the error page.
+    pub fn user_message(self) -> &'static str {
+        match self {
+            Self::Network => "We couldn't reach the server. Check your connection and try again.",
+            Self::NotFound => "This page doesn't exist, or the record behind it was deleted.",
+            Self::AuthExpired => "Your session has expired. Sign in again to continue.",
+            Self::RateLimited => "The server asked us to slow down. Wait a moment and try again.",
+            Self::Other => "Something went wrong loading this page.",
+        }
+    }
+}
+
+/// A classified fetch failure.
+///
+/// Displays as `<label>: <original message>` so the kind survives the
+/// trip through [`dioxus::CapturedError`] (which only keeps the rendered
+/// message) and can be recovered at the error boundary.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+#[error("{}: {}", .kind.label(), .message)]
+pub struct FetchError {
+    pub kind: FetchErrorKind,
+    message: String,
+}
+
+impl FetchError {
+    /// Classify any displayable error.
+    pub fn from_display(err: impl fmt::Display) -> Self {
+        let message = err.to_string();
+        let kind = FetchErrorKind::classify(&message);
+        Self { kind, message }
+    }
+
+    /// The original, unprefixed message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn into_captured(self) -> dioxus::CapturedError {
+        dioxus::CapturedError::from_display(self)
+    }
+}
+
+/// Classify and capture an error for a dioxus error boundary.
+pub(crate) fn capture(err: impl fmt::Display) -> dioxus::CapturedError {
+    FetchError::from_display(err).into_captured()
+}
+
+/// Exponential backoff delay for retry attempt `attempt` (zero-based).
+///
+/// Rate limiting starts from a full second so we do not hammer a server
+/// that explicitly asked us to slow down.
+pub(crate) fn backoff_delay(kind: FetchErrorKind, attempt: u32) -> Duration {
+    let base_ms: u64 = match kind {
+        FetchErrorKind::RateLimited => 1_000,
+        _ => 250,
+    };
+    Duration::from_millis(base_ms.saturating_mul(1 << attempt.min(6)))
+}
+
+/// Cross-platform async sleep for backoff delays.
+pub(crate) async fn backoff_sleep(delay: Duration) {
+    n0_future::time::sleep(delay).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_status_codes() {
+        assert_eq!(
+            FetchErrorKind::classify("HTTP 429 Too Many Requests"),
+            FetchErrorKind::RateLimited
+        );
+        assert_eq!(
+            FetchErrorKind::classify("received 401 Unauthorized"),
+            FetchErrorKind::AuthExpired
+        );
+        assert_eq!(
+            FetchErrorKind::classify("record returned 404"),
+            FetchErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_classify_xrpc_vocabulary() {
+        assert_eq!(
+            FetchErrorKind::classify("ExpiredToken: token is expired"),
+            FetchErrorKind::AuthExpired
+        );
+        assert_eq!(
+            FetchErrorKind::classify("RecordNotFound"),
+            FetchErrorKind::NotFound
+        );
+        assert_eq!(
+            FetchErrorKind::classify("connection refused"),
+            FetchErrorKind::Network
+        );
+        assert_eq!(
+            FetchErrorKind::classify("something inexplicable"),
+            FetchErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn test_kind_survives_display_roundtrip() {
+        let err = FetchError::from_display("connection reset by peer");
+        assert_eq!(err.kind, FetchErrorKind::Network);
+        let reclassified = FetchErrorKind::classify(&err.to_string());
+        assert_eq!(reclassified, FetchErrorKind::Network);
+
+        // A kind classified only from context (not vocabulary) must also
+        // survive: the label itself is a classification anchor.
+        let other = FetchError {
+            kind: FetchErrorKind::AuthExpired,
+            message: "opaque".to_string(),
+        };
+        assert_eq!(
+            FetchErrorKind::classify(&other.to_string()),
+            FetchErrorKind::AuthExpired
+        );
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles() {
+        let kind = FetchErrorKind::Network;
+        assert_eq!(backoff_delay(kind, 0), Duration::from_millis(250));
+        assert_eq!(backoff_delay(kind, 1), Duration::from_millis(500));
+        assert_eq!(backoff_delay(kind, 2), Duration::from_millis(1_000));
+        assert_eq!(
+            backoff_delay(FetchErrorKind::RateLimited, 0),
+            Duration::from_millis(1_000)
+        );
+        // The exponent is capped so huge attempt counts cannot overflow.
+        assert!(backoff_delay(kind, 40) <= Duration::from_millis(250 * 64));
+    }
+}
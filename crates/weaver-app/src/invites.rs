@@ -0,0 +1,496 @@
+//! Invite-code registration and waitlist for gated deployments.
+//!
+//! Deployments that aren't open for general registration can require an
+//! invite code before a DID may use the write APIs. Codes are minted by the
+//! deployment admin, consumed by a registration endpoint, and everyone else
+//! can leave a waitlist entry. State lives in a server-local SQLite database
+//! via Diesel; it is deployment bookkeeping, not AT Protocol data, so it
+//! deliberately stays out of the PDS.
+
+use diesel::prelude::*;
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+use std::sync::Mutex;
+
+const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+mod schema {
+    diesel::table! {
+        invite_codes (code) {
+            code -> Text,
+            created_by -> Text,
+            created_at -> Text,
+            max_uses -> Integer,
+            uses -> Integer,
+            disabled -> Integer,
+        }
+    }
+
+    diesel::table! {
+        registrations (did) {
+            did -> Text,
+            invite_code -> Text,
+            registered_at -> Text,
+        }
+    }
+
+    diesel::table! {
+        waitlist (ident) {
+            ident -> Text,
+            note -> Nullable<Text>,
+            joined_at -> Text,
+        }
+    }
+}
+
+/// Error type for the invite subsystem.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum InviteError {
+    #[error("failed to open invite database: {0}")]
+    Connection(#[from] diesel::result::ConnectionError),
+    #[error("failed to run invite migrations: {0}")]
+    Migration(String),
+    #[error("invite database query failed: {0}")]
+    Database(#[from] diesel::result::Error),
+    #[error("failed to gather entropy for invite code")]
+    Entropy,
+    #[error("unknown invite code")]
+    UnknownCode,
+    #[error("invite code has been disabled")]
+    CodeDisabled,
+    #[error("invite code has no uses left")]
+    CodeExhausted,
+    #[error("identity is already registered")]
+    AlreadyRegistered,
+}
+
+/// A mintable, multi-use invite code.
+#[derive(Debug, Clone, Queryable, Insertable, serde::Serialize)]
+#[diesel(table_name = schema::invite_codes)]
+pub struct InviteCode {
+    pub code: String,
+    pub created_by: String,
+    pub created_at: String,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub disabled: i32,
+}
+
+/// A DID that consumed an invite code.
+#[derive(Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = schema::registrations)]
+pub struct Registration {
+    pub did: String,
+    pub invite_code: String,
+    pub registered_at: String,
+}
+
+/// A waitlist signup from someone without a code.
+#[derive(Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = schema::waitlist)]
+pub struct WaitlistEntry {
+    pub ident: String,
+    pub note: Option<String>,
+    pub joined_at: String,
+}
+
+/// Server-local store for invite codes, registrations, and the waitlist.
+///
+/// SQLite queries here are all single-row lookups and inserts; holding the
+/// connection behind a mutex and running them inline is cheaper than a
+/// blocking-pool round trip at this volume.
+pub struct InviteStore {
+    conn: Mutex<SqliteConnection>,
+}
+
+impl InviteStore {
+    /// Open (or create) the invite database and run pending migrations.
+    pub fn open(path: &str) -> Result<Self, InviteError> {
+        let mut conn = SqliteConnection::establish(path)?;
+        conn.run_pending_migrations(MIGRATIONS)
+            .map_err(|e| InviteError::Migration(e.to_string()))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Mint a fresh invite code with `max_uses` redemptions.
+    pub fn mint_code(&self, created_by: &str, max_uses: i32) -> Result<InviteCode, InviteError> {
+        use base64::Engine;
+        use ring::rand::{SecureRandom, SystemRandom};
+
+        let mut bytes = [0u8; 9];
+        SystemRandom::new()
+            .fill(&mut bytes)
+            .map_err(|_| InviteError::Entropy)?;
+        let row = InviteCode {
+            code: format!(
+                "weaver-{}",
+                base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+            ),
+            created_by: created_by.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            max_uses: max_uses.max(1),
+            uses: 0,
+            disabled: 0,
+        };
+
+        let mut conn = self.conn.lock().unwrap();
+        diesel::insert_into(schema::invite_codes::table)
+            .values(&row)
+            .execute(&mut *conn)?;
+        Ok(row)
+    }
+
+    /// Disable a code without deleting its redemption history.
+    pub fn disable_code(&self, code: &str) -> Result<(), InviteError> {
+        use schema::invite_codes::dsl as codes;
+
+        let mut conn = self.conn.lock().unwrap();
+        let updated = diesel::update(codes::invite_codes.find(code))
+            .set(codes::disabled.eq(1))
+            .execute(&mut *conn)?;
+        if updated == 0 {
+            return Err(InviteError::UnknownCode);
+        }
+        Ok(())
+    }
+
+    /// List every minted code, newest first.
+    pub fn list_codes(&self) -> Result<Vec<InviteCode>, InviteError> {
+        use schema::invite_codes::dsl as codes;
+
+        let mut conn = self.conn.lock().unwrap();
+        Ok(codes::invite_codes
+            .order(codes::created_at.desc())
+            .load(&mut *conn)?)
+    }
+
+    /// Consume an invite code for `did`.
+    ///
+    /// Validation and the use-count bump happen in one transaction so two
+    /// racing registrations cannot both take a code's last use.
+    pub fn register(&self, did: &str, code: &str) -> Result<(), InviteError> {
+        use schema::invite_codes::dsl as codes;
+        use schema::registrations::dsl as regs;
+
+        let mut conn = self.conn.lock().unwrap();
+        conn.transaction(|conn| {
+            let already: i64 = regs::registrations
+                .filter(regs::did.eq(did))
+                .count()
+                .get_result(conn)?;
+            if already > 0 {
+                return Err(InviteError::AlreadyRegistered);
+            }
+
+            let row: InviteCode = codes::invite_codes
+                .find(code)
+                .first(conn)
+                .optional()?
+                .ok_or(InviteError::UnknownCode)?;
+            if row.disabled != 0 {
+                return Err(InviteError::CodeDisabled);
+            }
+            if row.uses >= row.max_uses {
+                return Err(InviteError::CodeExhausted);
+            }
+
+            diesel::insert_into(schema::registrations::table)
+                .values(&Registration {
+                    did: did.to_string(),
+                    invite_code: code.to_string(),
+                    registered_at: chrono::Utc::now().to_rfc3339(),
+                })
+                .execute(conn)?;
+            diesel::update(codes::invite_codes.find(code))
+                .set(codes::uses.eq(row.uses + 1))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Whether `did` has consumed an invite code.
+    pub fn is_registered(&self, did: &str) -> Result<bool, InviteError> {
+        use schema::registrations::dsl as regs;
+
+        let mut conn = self.conn.lock().unwrap();
+        let count: i64 = regs::registrations
+            .filter(regs::did.eq(did))
+            .count()
+            .get_result(&mut *conn)?;
+        Ok(count > 0)
+    }
+
+    /// Record a waitlist signup; repeat signups are a no-op.
+    pub fn join_waitlist(&self, ident: &str, note: Option<&str>) -> Result<(), InviteError> {
+        let mut conn = self.conn.lock().unwrap();
+        diesel::insert_into(schema::waitlist::table)
+            .values(&WaitlistEntry {
+                ident: ident.to_string(),
+                note: note.map(str::to_string),
+                joined_at: chrono::Utc::now().to_rfc3339(),
+            })
+            .on_conflict_do_nothing()
+            .execute(&mut *conn)?;
+        Ok(())
+    }
+}
+
+/// Location of the invite database, `WEAVER_INVITE_DB` or a local default.
+pub fn db_path() -> String {
+    std::env::var("WEAVER_INVITE_DB").unwrap_or_else(|_| "weaver-invites.sqlite3".to_string())
+}
+
+/// Whether write APIs are gated behind registration at all.
+///
+/// Off by default so existing open deployments keep working; set
+/// `WEAVER_INVITES_REQUIRED=1` to turn the gate on.
+pub fn invites_required() -> bool {
+    std::env::var("WEAVER_INVITES_REQUIRED")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+mod http {
+    use super::{InviteError, InviteStore};
+    use axum::extract::{Extension, Json, Path};
+    use axum::http::{HeaderMap, Method, StatusCode};
+    use axum::response::{IntoResponse, Response};
+    use std::sync::Arc;
+
+    /// Admin requests must present this header matching `WEAVER_ADMIN_TOKEN`.
+    const ADMIN_TOKEN_HEADER: &str = "x-weaver-admin-token";
+
+    fn admin_authorized(headers: &HeaderMap) -> bool {
+        // No configured token means no admin surface at all, rather than an
+        // open one.
+        let Ok(expected) = std::env::var("WEAVER_ADMIN_TOKEN") else {
+            return false;
+        };
+        if expected.is_empty() {
+            return false;
+        }
+        let Some(presented) = headers
+            .get(ADMIN_TOKEN_HEADER)
+            .and_then(|v| v.to_str().ok())
+        else {
+            return false;
+        };
+        // Constant-time compare; the token is a bearer credential.
+        ring::constant_time::verify_slices_are_equal(presented.as_bytes(), expected.as_bytes())
+            .is_ok()
+    }
+
+    fn error_response(err: InviteError) -> Response {
+        let status = match &err {
+            InviteError::UnknownCode => StatusCode::NOT_FOUND,
+            InviteError::CodeDisabled | InviteError::CodeExhausted => StatusCode::FORBIDDEN,
+            InviteError::AlreadyRegistered => StatusCode::CONFLICT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        if status == StatusCode::INTERNAL_SERVER_ERROR {
+            tracing::error!(error = %err, "invite request failed");
+        }
+        (status, err.to_string()).into_response()
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct MintRequest {
+        #[serde(default)]
+        pub max_uses: Option<i32>,
+    }
+
+    pub async fn mint_invite(
+        Extension(store): Extension<Arc<InviteStore>>,
+        headers: HeaderMap,
+        Json(req): Json<MintRequest>,
+    ) -> Response {
+        if !admin_authorized(&headers) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        match store.mint_code("admin", req.max_uses.unwrap_or(1)) {
+            Ok(code) => (StatusCode::CREATED, axum::Json(code)).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    pub async fn disable_invite(
+        Extension(store): Extension<Arc<InviteStore>>,
+        headers: HeaderMap,
+        Path(code): Path<String>,
+    ) -> Response {
+        if !admin_authorized(&headers) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        match store.disable_code(&code) {
+            Ok(()) => StatusCode::NO_CONTENT.into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    pub async fn list_invites(
+        Extension(store): Extension<Arc<InviteStore>>,
+        headers: HeaderMap,
+    ) -> Response {
+        if !admin_authorized(&headers) {
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+        match store.list_codes() {
+            Ok(codes) => axum::Json(codes).into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct RegisterRequest {
+        pub did: String,
+        pub code: String,
+    }
+
+    pub async fn register(
+        Extension(store): Extension<Arc<InviteStore>>,
+        Json(req): Json<RegisterRequest>,
+    ) -> Response {
+        if jacquard::types::string::Did::new(&req.did).is_err() {
+            return (StatusCode::BAD_REQUEST, "invalid did").into_response();
+        }
+        match store.register(&req.did, &req.code) {
+            Ok(()) => StatusCode::CREATED.into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    pub struct WaitlistRequest {
+        pub ident: String,
+        #[serde(default)]
+        pub note: Option<String>,
+    }
+
+    pub async fn join_waitlist(
+        Extension(store): Extension<Arc<InviteStore>>,
+        Json(req): Json<WaitlistRequest>,
+    ) -> Response {
+        match store.join_waitlist(&req.ident, req.note.as_deref()) {
+            Ok(()) => StatusCode::CREATED.into_response(),
+            Err(e) => error_response(e),
+        }
+    }
+
+    fn is_gated_write(method: &Method, path: &str) -> bool {
+        if !matches!(*method, Method::POST | Method::PUT | Method::DELETE) {
+            return false;
+        }
+        // Registration and waitlist must stay reachable for the
+        // not-yet-registered, or nobody could ever get in.
+        if path.starts_with("/invites") {
+            return false;
+        }
+        path.starts_with("/cache") || path.starts_with("/api")
+    }
+
+    /// Middleware gating write APIs behind registration.
+    ///
+    /// Inactive unless `WEAVER_INVITES_REQUIRED` is set, so open
+    /// deployments are unaffected. The caller's DID comes from their
+    /// server-side OAuth session (see [`crate::auth::session_did`]);
+    /// trusting a header here would let anyone through by naming a DID
+    /// that happens to be registered.
+    pub async fn require_registration(
+        Extension(store): Extension<Arc<InviteStore>>,
+        request: axum::extract::Request,
+        next: axum::middleware::Next,
+    ) -> Response {
+        if !super::invites_required() || !is_gated_write(request.method(), request.uri().path()) {
+            return next.run(request).await;
+        }
+        let Some(did) = crate::auth::session_did(request.headers()).await else {
+            return (
+                StatusCode::UNAUTHORIZED,
+                "authentication required for write APIs",
+            )
+                .into_response();
+        };
+        if store.is_registered(did.as_str()).unwrap_or(false) {
+            next.run(request).await
+        } else {
+            (
+                StatusCode::FORBIDDEN,
+                "registration required; join the waitlist at /invites/waitlist",
+            )
+                .into_response()
+        }
+    }
+}
+
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+pub use http::{
+    disable_invite, join_waitlist, list_invites, mint_invite, register, require_registration,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> InviteStore {
+        InviteStore::open(":memory:").expect("in-memory invite store")
+    }
+
+    #[test]
+    fn mint_register_and_exhaust() {
+        let store = store();
+        let code = store.mint_code("did:plc:admin", 1).unwrap();
+
+        store.register("did:plc:alice", &code.code).unwrap();
+        assert!(store.is_registered("did:plc:alice").unwrap());
+
+        // The single use is spent; the next taker is turned away.
+        assert!(matches!(
+            store.register("did:plc:bob", &code.code),
+            Err(InviteError::CodeExhausted)
+        ));
+        assert!(!store.is_registered("did:plc:bob").unwrap());
+    }
+
+    #[test]
+    fn register_twice_is_rejected() {
+        let store = store();
+        let code = store.mint_code("did:plc:admin", 5).unwrap();
+        store.register("did:plc:alice", &code.code).unwrap();
+        assert!(matches!(
+            store.register("did:plc:alice", &code.code),
+            Err(InviteError::AlreadyRegistered)
+        ));
+    }
+
+    #[test]
+    fn disabled_code_is_rejected() {
+        let store = store();
+        let code = store.mint_code("did:plc:admin", 5).unwrap();
+        store.disable_code(&code.code).unwrap();
+        assert!(matches!(
+            store.register("did:plc:alice", &code.code),
+            Err(InviteError::CodeDisabled)
+        ));
+    }
+
+    #[test]
+    fn unknown_code_is_rejected() {
+        let store = store();
+        assert!(matches!(
+            store.register("did:plc:alice", "weaver-nope"),
+            Err(InviteError::UnknownCode)
+        ));
+    }
+
+    #[test]
+    fn waitlist_is_idempotent() {
+        let store = store();
+        store
+            .join_waitlist("alice.example.com", Some("pls"))
+            .unwrap();
+        store.join_waitlist("alice.example.com", None).unwrap();
+    }
+}
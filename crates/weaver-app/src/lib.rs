@@ -14,6 +14,8 @@ use std::sync::LazyLock;
 
 pub mod auth;
 #[cfg(feature = "server")]
+pub mod blob_store;
+#[cfg(feature = "server")]
 pub mod blobcache;
 pub mod cache_impl;
 pub mod collab_context;
@@ -21,15 +23,23 @@ pub mod components;
 pub mod config;
 pub mod data;
 pub mod env;
+pub mod export;
 pub mod fetch;
+pub mod fetch_error;
 pub mod host_mode;
 #[cfg(feature = "server")]
+pub mod invites;
+#[cfg(feature = "server")]
+pub mod middleware;
+#[cfg(feature = "server")]
 pub mod og;
 pub mod perf;
 pub mod record_utils;
+pub mod search;
 pub mod service_worker;
 
 pub mod subdomain_app;
+pub mod theme;
 pub mod views;
 
 pub use host_mode::{LinkMode, SubdomainContext};
@@ -41,11 +51,12 @@ use components::{EntryPage, Repository, RepositoryIndex};
 use config::{Config, OAuthConfig};
 #[allow(unused)]
 use views::{
-    AboutPage, Callback, DraftEdit, DraftsList, Editor, Home, InvitesPage, LeafletEntry,
-    LeafletEntryNsid, Navbar, NewDraft, Notebook, NotebookEntryByRkey, NotebookEntryEdit,
-    NotebookIndex, NotebookPage, PcktEntry, PcktEntryBlogNsid, PcktEntryNsid, PrivacyPage,
-    RecordIndex, RecordPage, StandaloneEntry, StandaloneEntryEdit, StandaloneEntryNsid, TermsPage,
-    WhiteWindEntry, WhiteWindEntryNsid,
+    AboutPage, AnalyticsPage, Callback, DemoEditor, DraftEdit, DraftsList, Editor, Home,
+    InvitesPage, LeafletEntry, LeafletEntryNsid, Navbar, NewDraft, Notebook, NotebookEntryByRkey,
+    NotebookEntryEdit, NotebookIndex, NotebookPage, NotificationsPage, PcktEntry,
+    PcktEntryBlogNsid, PcktEntryNsid, PrivacyPage, ProfileSettingsPage, RecordIndex, RecordPage,
+    SavedItemsPage, SearchPage, StandaloneEntry, StandaloneEntryEdit, StandaloneEntryNsid, TagPage,
+    TagsIndex, TermsPage, WhiteWindEntry, WhiteWindEntryNsid,
 };
 
 use crate::{
@@ -66,12 +77,20 @@ pub enum Route {
         Home {},
         #[route("/editor?:entry")]
         Editor { entry: Option<String> },
+        #[route("/demo")]
+        DemoEditor {},
         #[route("/about")]
         AboutPage {},
         #[route("/tos")]
         TermsPage {},
         #[route("/privacy")]
         PrivacyPage {},
+        #[route("/notifications")]
+        NotificationsPage {},
+        #[route("/settings/profile")]
+        ProfileSettingsPage {},
+        #[route("/saved")]
+        SavedItemsPage {},
         #[layout(ErrorLayout)]
         #[nest("/record")]
           #[layout(RecordIndex)]
@@ -95,6 +114,17 @@ pub enum Route {
             // Collaboration invites
             #[route("/invites")]
             InvitesPage { ident: AtIdentifier<'static> },
+            // Private view analytics (owner only)
+            #[route("/analytics")]
+            AnalyticsPage { ident: AtIdentifier<'static> },
+            // Full-text search within the repository
+            #[route("/search?:q")]
+            SearchPage { ident: AtIdentifier<'static>, q: Option<SmolStr> },
+            // Tag browsing
+            #[route("/tags")]
+            TagsIndex { ident: AtIdentifier<'static> },
+            #[route("/tags/:tag")]
+            TagPage { ident: AtIdentifier<'static>, tag: SmolStr },
             // Standalone entry routes
             #[route("/e/:rkey")]
             StandaloneEntry { ident: AtIdentifier<'static>, rkey: SmolStr },
@@ -150,6 +180,11 @@ pub fn App() -> Element {
         ))
     });
 
+    // Theme preference context: seeded from the request cookie on the
+    // server, from localStorage in the browser. Provided before the
+    // subdomain split so both shells share it.
+    theme::use_theme_provider();
+
     #[cfg(feature = "fullstack-server")]
     let ctx_resource = use_server_future({
         let fetcher = fetcher.clone();
@@ -272,6 +307,8 @@ pub fn MainDomainApp() -> Element {
         // Preconnect for external fonts (before loading them)
         document::Link { rel: "preconnect", href: "https://fonts.googleapis.com" }
         document::Link { rel: "preconnect", href: "https://fonts.gstatic.com" }
+        // Apply the stored theme preference before the stylesheets load.
+        document::Script { {theme::THEME_BOOT_SCRIPT} }
         // Theme defaults first: CSS variables, font-faces, reset
         document::Link { rel: "stylesheet", href: THEME_DEFAULTS_CSS }
         document::Link { rel: "stylesheet", href: "https://fonts.googleapis.com/css2?family=IBM+Plex+Mono:ital,wght@0,200;0,300;0,400;0,500;0,600;0,700;1,200;1,300;1,400;1,500;1,600;1,700&family=IBM+Plex+Sans:ital,wght@0,100..700;1,100..700&family=IBM+Plex+Serif:ital,wght@0,200;0,300;0,400;0,500;0,600;0,700;1,200;1,300;1,400;1,500;1,600;1,700&display=swap" }
@@ -288,20 +325,7 @@ pub fn MainDomainApp() -> Element {
 pub fn ErrorLayout() -> Element {
     rsx! {
         ErrorBoundary {
-            handle_error: move |_err: ErrorContext| {
-                #[cfg(feature = "fullstack-server")]
-                {
-                    let http_error = FullstackContext::commit_error_status(_err.error().unwrap());
-                    match http_error.status {
-                        StatusCode::NOT_FOUND => rsx! { div { "404 - Page not found" } },
-                        _ => rsx! { div { "An unknown error occurred" } },
-                    }
-                }
-                #[cfg(not(feature = "fullstack-server"))]
-                {
-                    rsx! { div { "An error occurred" } }
-                }
-            },
+            handle_error: move |err: ErrorContext| components::error_page::render_error(err),
             Outlet::<Route> {}
         }
     }
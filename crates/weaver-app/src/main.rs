@@ -77,12 +77,16 @@ fn main() {
         #[cfg(feature = "fullstack-server")]
         use axum::middleware;
         use axum::middleware::Next;
-        use axum::{Router, body::Body, extract::Request, response::Response, routing::get};
+        use axum::{
+            Router, body::Body, extract::Request, response::Response, routing::get, routing::post,
+        };
         use axum_extra::extract::Host;
         use jacquard::oauth::{client::OAuthClient, session::ClientData};
         use std::convert::Infallible;
         use weaver_app::auth::AuthStore;
         use weaver_app::blobcache::BlobCache;
+        #[cfg(feature = "fullstack-server")]
+        use weaver_app::invites;
 
         #[cfg(not(feature = "fullstack-server"))]
         let router = { Router::new().merge(dioxus::server::router(App)) };
@@ -95,7 +99,7 @@ fn main() {
             )));
 
             let blob_cache = Arc::new(BlobCache::new(fetcher.clone()));
-            axum::Router::new()
+            let router = axum::Router::new()
                 .route("/favicon.ico", get(weaver_app::favicon))
                 .serve_dioxus_application(ServeConfig::builder(), App)
                 .layer(middleware::from_fn({
@@ -110,7 +114,39 @@ fn main() {
                             Ok::<_, Infallible>(next.run(req).await)
                         }
                     }
-                }))
+                }));
+
+            // Invite/waitlist endpoints and the registration gate. If the
+            // invite database cannot be opened, the deployment keeps serving
+            // ungated rather than failing to start.
+            let router = match invites::InviteStore::open(&invites::db_path()) {
+                Ok(store) => {
+                    let store = Arc::new(store);
+                    router
+                        .route("/invites/mint", post(invites::mint_invite))
+                        .route("/invites/{code}/disable", post(invites::disable_invite))
+                        .route("/invites", get(invites::list_invites))
+                        .route("/invites/register", post(invites::register))
+                        .route("/invites/waitlist", post(invites::join_waitlist))
+                        // The Extension layer sits outside the gate so the
+                        // middleware can extract the store.
+                        .layer(middleware::from_fn(invites::require_registration))
+                        .layer(axum::Extension(store))
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "failed to open invite store; write APIs ungated");
+                    router
+                }
+            };
+
+            // Rate limiting wraps everything above, so over-limit callers are
+            // turned away before any handler work happens.
+            let limiter = Arc::new(weaver_app::middleware::RateLimiter::new(
+                weaver_app::middleware::RateLimitConfig::from_env(),
+            ));
+            router
+                .layer(middleware::from_fn(weaver_app::middleware::rate_limit))
+                .layer(axum::Extension(limiter))
         };
         Ok(router)
     });
@@ -0,0 +1,426 @@
+//! Server middleware: request rate limiting.
+//!
+//! Token-bucket limiter keyed by the caller's session-authenticated DID,
+//! falling back to client IP for anonymous traffic. Limits are
+//! configured per route group so the write-heavy invite endpoints can be
+//! throttled far harder than ordinary page loads. Over-limit requests get a
+//! standard `429` with a `Retry-After` hint.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Coarse route classification for per-group limits.
+///
+/// Groups are deliberately few: fine-grained per-route budgets invite
+/// configuration drift, and the interesting distinction is "cheap read"
+/// versus "state-changing write".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteGroup {
+    /// Invite minting, registration, and waitlist writes.
+    Invites,
+    /// Server functions and cache endpoints.
+    Api,
+    /// Everything else: pages, assets, OG images.
+    General,
+}
+
+impl RouteGroup {
+    pub fn classify(path: &str) -> Self {
+        if path.starts_with("/invites") {
+            Self::Invites
+        } else if path.starts_with("/api") || path.starts_with("/cache") {
+            Self::Api
+        } else {
+            Self::General
+        }
+    }
+}
+
+/// Budget for one route group: burst capacity and sustained rate.
+#[derive(Debug, Clone, Copy)]
+pub struct GroupLimit {
+    pub capacity: f64,
+    pub refill_per_sec: f64,
+}
+
+/// Rate limiter configuration, one budget per [`RouteGroup`].
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub enabled: bool,
+    /// Key anonymous callers by `x-forwarded-for` instead of the peer
+    /// address. Only safe behind a proxy that overwrites the header;
+    /// otherwise callers would pick their own bucket key.
+    pub trust_forwarded_for: bool,
+    pub invites: GroupLimit,
+    pub api: GroupLimit,
+    pub general: GroupLimit,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            // Off by default; existing deployments opt in explicitly.
+            enabled: false,
+            trust_forwarded_for: false,
+            invites: GroupLimit {
+                capacity: 5.0,
+                refill_per_sec: 0.1,
+            },
+            api: GroupLimit {
+                capacity: 60.0,
+                refill_per_sec: 10.0,
+            },
+            general: GroupLimit {
+                capacity: 300.0,
+                refill_per_sec: 50.0,
+            },
+        }
+    }
+}
+
+impl RateLimitConfig {
+    /// Build configuration from `WEAVER_RATE_LIMIT*` environment variables.
+    ///
+    /// `WEAVER_RATE_LIMIT=1` enables the limiter; per-group overrides take
+    /// the form `capacity/refill_per_sec`, e.g. `WEAVER_RATE_LIMIT_API=120/20`.
+    /// `WEAVER_RATE_LIMIT_TRUSTED_PROXY=1` declares that a proxy in front of
+    /// the app overwrites `x-forwarded-for`.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        config.enabled = std::env::var("WEAVER_RATE_LIMIT")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        config.trust_forwarded_for = std::env::var("WEAVER_RATE_LIMIT_TRUSTED_PROXY")
+            .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        for (var, slot) in [
+            ("WEAVER_RATE_LIMIT_INVITES", &mut config.invites),
+            ("WEAVER_RATE_LIMIT_API", &mut config.api),
+            ("WEAVER_RATE_LIMIT_GENERAL", &mut config.general),
+        ] {
+            if let Ok(raw) = std::env::var(var) {
+                match parse_limit(&raw) {
+                    Some(limit) => *slot = limit,
+                    None => {
+                        tracing::warn!(%var, %raw, "unparseable rate limit; using default")
+                    }
+                }
+            }
+        }
+        config
+    }
+
+    fn limit_for(&self, group: RouteGroup) -> GroupLimit {
+        match group {
+            RouteGroup::Invites => self.invites,
+            RouteGroup::Api => self.api,
+            RouteGroup::General => self.general,
+        }
+    }
+}
+
+fn parse_limit(raw: &str) -> Option<GroupLimit> {
+    let (capacity, refill) = raw.split_once('/')?;
+    let capacity: f64 = capacity.trim().parse().ok()?;
+    let refill_per_sec: f64 = refill.trim().parse().ok()?;
+    if capacity <= 0.0 || refill_per_sec <= 0.0 {
+        return None;
+    }
+    Some(GroupLimit {
+        capacity,
+        refill_per_sec,
+    })
+}
+
+/// Lock-free counters; snapshots go out through [`crate::perf`].
+#[derive(Debug, Default)]
+struct RateLimiterMetrics {
+    allowed: AtomicU64,
+    limited: AtomicU64,
+}
+
+/// Point-in-time rate limiter counters for telemetry.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitStats {
+    pub allowed: u64,
+    pub limited: u64,
+    pub tracked_buckets: usize,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Once the bucket map grows past this, full buckets are pruned. Bounds
+/// memory against key churn (rotating IPs) without a background task.
+const BUCKET_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Token-bucket rate limiter shared across requests.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Mutex<HashMap<(RouteGroup, String), Bucket>>,
+    metrics: RateLimiterMetrics,
+}
+
+/// Outcome of a rate limit check.
+pub enum RateDecision {
+    Allowed,
+    /// Denied; retry after this many whole seconds.
+    Limited {
+        retry_after_secs: u64,
+    },
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            metrics: RateLimiterMetrics::default(),
+        }
+    }
+
+    /// Take one token from the caller's bucket for `group`.
+    pub fn check(&self, group: RouteGroup, key: &str) -> RateDecision {
+        if !self.config.enabled {
+            return RateDecision::Allowed;
+        }
+        let limit = self.config.limit_for(group);
+        let now = Instant::now();
+
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= BUCKET_PRUNE_THRESHOLD {
+            prune_full(&mut buckets, &self.config, now);
+        }
+        let bucket = buckets
+            .entry((group, key.to_string()))
+            .or_insert_with(|| Bucket {
+                tokens: limit.capacity,
+                last_refill: now,
+            });
+
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * limit.refill_per_sec).min(limit.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            self.metrics.allowed.fetch_add(1, Ordering::Relaxed);
+            RateDecision::Allowed
+        } else {
+            let deficit = 1.0 - bucket.tokens;
+            let retry_after_secs = (deficit / limit.refill_per_sec).ceil().max(1.0) as u64;
+            self.metrics.limited.fetch_add(1, Ordering::Relaxed);
+            RateDecision::Limited { retry_after_secs }
+        }
+    }
+
+    pub fn stats(&self) -> RateLimitStats {
+        RateLimitStats {
+            allowed: self.metrics.allowed.load(Ordering::Relaxed),
+            limited: self.metrics.limited.load(Ordering::Relaxed),
+            tracked_buckets: self.buckets.lock().unwrap().len(),
+        }
+    }
+}
+
+fn prune_full(
+    buckets: &mut HashMap<(RouteGroup, String), Bucket>,
+    config: &RateLimitConfig,
+    now: Instant,
+) {
+    buckets.retain(|(group, _), bucket| {
+        let limit = config.limit_for(*group);
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        // A bucket that has refilled to capacity carries no state worth
+        // keeping; it is indistinguishable from a fresh one.
+        bucket.tokens + elapsed * limit.refill_per_sec < limit.capacity
+    });
+}
+
+#[cfg(feature = "fullstack-server")]
+mod http {
+    use super::{RateDecision, RateLimiter, RouteGroup};
+    use axum::extract::{ConnectInfo, Extension, Request};
+    use axum::http::StatusCode;
+    use axum::middleware::Next;
+    use axum::response::{IntoResponse, Response};
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    /// Identify the caller: session-verified DID first, then client IP.
+    ///
+    /// Only a DID backed by a server-side session gets its own bucket. A
+    /// header-supplied DID would let an abuser mint a fresh bucket per
+    /// request and drain a victim's bucket by spoofing theirs.
+    async fn caller_key(req: &Request, trust_forwarded_for: bool) -> String {
+        if let Some(did) = crate::auth::session_did(req.headers()).await {
+            return format!("did:{did}");
+        }
+        anonymous_key(req, trust_forwarded_for)
+    }
+
+    pub(super) fn anonymous_key(req: &Request, trust_forwarded_for: bool) -> String {
+        // `x-forwarded-for` is caller-controlled unless a proxy in front
+        // overwrites it, so it only counts when the operator says one
+        // exists.
+        if trust_forwarded_for {
+            let forwarded = req
+                .headers()
+                .get("x-forwarded-for")
+                .and_then(|v| v.to_str().ok())
+                // First hop in the chain is the client.
+                .and_then(|v| v.split(',').next())
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            if let Some(ip) = forwarded {
+                return format!("ip:{ip}");
+            }
+        }
+        match req.extensions().get::<ConnectInfo<SocketAddr>>() {
+            Some(ConnectInfo(addr)) => format!("ip:{}", addr.ip()),
+            // No peer address and no trusted proxy: all anonymous callers
+            // share one bucket. Over-throttling beats handing out a fresh
+            // bucket per forged header.
+            None => "ip:unknown".to_string(),
+        }
+    }
+
+    /// Axum middleware applying the shared [`RateLimiter`].
+    pub async fn rate_limit(
+        Extension(limiter): Extension<Arc<RateLimiter>>,
+        request: Request,
+        next: Next,
+    ) -> Response {
+        let group = RouteGroup::classify(request.uri().path());
+        let key = caller_key(&request, limiter.config.trust_forwarded_for).await;
+        match limiter.check(group, &key) {
+            RateDecision::Allowed => next.run(request).await,
+            RateDecision::Limited { retry_after_secs } => {
+                crate::perf::log_rate_limit_stats(&limiter.stats());
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [("retry-after", retry_after_secs.to_string())],
+                    "rate limit exceeded",
+                )
+                    .into_response()
+            }
+        }
+    }
+}
+
+#[cfg(feature = "fullstack-server")]
+pub use http::rate_limit;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled_config() -> RateLimitConfig {
+        RateLimitConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classify_route_groups() {
+        assert_eq!(RouteGroup::classify("/invites/mint"), RouteGroup::Invites);
+        assert_eq!(RouteGroup::classify("/api/foo"), RouteGroup::Api);
+        assert_eq!(RouteGroup::classify("/cache-bytes/x"), RouteGroup::Api);
+        assert_eq!(RouteGroup::classify("/notebook/x"), RouteGroup::General);
+    }
+
+    #[test]
+    fn burst_capacity_then_limited() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            invites: GroupLimit {
+                capacity: 2.0,
+                refill_per_sec: 0.001,
+            },
+            ..enabled_config()
+        });
+        assert!(matches!(
+            limiter.check(RouteGroup::Invites, "did:plc:alice"),
+            RateDecision::Allowed
+        ));
+        assert!(matches!(
+            limiter.check(RouteGroup::Invites, "did:plc:alice"),
+            RateDecision::Allowed
+        ));
+        let decision = limiter.check(RouteGroup::Invites, "did:plc:alice");
+        match decision {
+            RateDecision::Limited { retry_after_secs } => assert!(retry_after_secs >= 1),
+            RateDecision::Allowed => panic!("third request should be limited"),
+        }
+        // A different caller has their own bucket.
+        assert!(matches!(
+            limiter.check(RouteGroup::Invites, "did:plc:bob"),
+            RateDecision::Allowed
+        ));
+        let stats = limiter.stats();
+        assert_eq!(stats.allowed, 3);
+        assert_eq!(stats.limited, 1);
+    }
+
+    #[test]
+    fn disabled_limiter_always_allows() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            invites: GroupLimit {
+                capacity: 1.0,
+                refill_per_sec: 0.001,
+            },
+            enabled: false,
+            ..Default::default()
+        });
+        for _ in 0..10 {
+            assert!(matches!(
+                limiter.check(RouteGroup::Invites, "did:plc:alice"),
+                RateDecision::Allowed
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_limit_overrides() {
+        let limit = parse_limit("120/20").unwrap();
+        assert_eq!(limit.capacity, 120.0);
+        assert_eq!(limit.refill_per_sec, 20.0);
+        assert!(parse_limit("120").is_none());
+        assert!(parse_limit("0/5").is_none());
+        assert!(parse_limit("abc/5").is_none());
+    }
+
+    #[cfg(feature = "fullstack-server")]
+    #[test]
+    fn forwarded_for_only_counts_behind_trusted_proxy() {
+        let req = axum::extract::Request::builder()
+            .uri("/api/foo")
+            .header("x-forwarded-for", "203.0.113.9, 10.0.0.1")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        // Without a trusted proxy a forged header must not pick the bucket
+        // key; all such callers share one.
+        assert_eq!(super::http::anonymous_key(&req, false), "ip:unknown");
+        assert_eq!(super::http::anonymous_key(&req, true), "ip:203.0.113.9");
+    }
+
+    #[cfg(feature = "fullstack-server")]
+    #[test]
+    fn peer_address_keys_anonymous_callers() {
+        use axum::extract::ConnectInfo;
+        use std::net::SocketAddr;
+
+        let mut req = axum::extract::Request::builder()
+            .uri("/api/foo")
+            .header("x-forwarded-for", "203.0.113.9")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let addr: SocketAddr = "192.0.2.7:50000".parse().unwrap();
+        req.extensions_mut().insert(ConnectInfo(addr));
+        assert_eq!(super::http::anonymous_key(&req, false), "ip:192.0.2.7");
+    }
+}
@@ -68,12 +68,105 @@ mod colors {
     pub const SUBTLE: &str = "#908caa";
     pub const MUTED: &str = "#6e6a86";
     pub const OVERLAY: &str = "#524f67";
+    pub const PRIMARY: &str = "#c4a7e7";
+    pub const TERTIARY: &str = "#ebbcba";
+}
+
+/// Colour and branding tokens consumed by the OG SVG templates.
+///
+/// Built from the same `ResolvedTheme` the css module renders, so social
+/// cards follow a notebook's published theme instead of always using the
+/// Rose Pine defaults. The string fields localize the fixed labels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OgTheme {
+    /// Card background.
+    pub base: SmolStr,
+    /// Body text and entry listings.
+    pub text: SmolStr,
+    /// Footer branding colour.
+    pub subtle: SmolStr,
+    /// Headings.
+    pub primary: SmolStr,
+    /// Byline accent.
+    pub tertiary: SmolStr,
+    /// Footer branding string.
+    pub footer: SmolStr,
+    /// Separator between byline segments.
+    pub byline_separator: SmolStr,
+    /// Label after the entry count when it is exactly one.
+    pub entry_label_one: SmolStr,
+    /// Label after the entry count otherwise.
+    pub entry_label_many: SmolStr,
+}
+
+impl Default for OgTheme {
+    fn default() -> Self {
+        Self {
+            base: SmolStr::new_static(colors::BASE),
+            text: SmolStr::new_static(colors::TEXT),
+            subtle: SmolStr::new_static(colors::SUBTLE),
+            primary: SmolStr::new_static(colors::PRIMARY),
+            tertiary: SmolStr::new_static(colors::TERTIARY),
+            footer: SmolStr::new_static("weaver.sh"),
+            byline_separator: SmolStr::new_static("·"),
+            entry_label_one: SmolStr::new_static("entry"),
+            entry_label_many: SmolStr::new_static("entries"),
+        }
+    }
+}
+
+impl OgTheme {
+    /// Apply deployment label overrides on top of the defaults.
+    ///
+    /// `WEAVER_OG_FOOTER`, `WEAVER_OG_BYLINE_SEPARATOR`,
+    /// `WEAVER_OG_ENTRY_LABEL_ONE`, and `WEAVER_OG_ENTRY_LABEL_MANY` let a
+    /// deployment rebrand or localize the fixed card strings without
+    /// patching the templates.
+    pub fn from_env() -> Self {
+        let mut theme = Self::default();
+        override_label(&mut theme.footer, "WEAVER_OG_FOOTER");
+        override_label(&mut theme.byline_separator, "WEAVER_OG_BYLINE_SEPARATOR");
+        override_label(&mut theme.entry_label_one, "WEAVER_OG_ENTRY_LABEL_ONE");
+        override_label(&mut theme.entry_label_many, "WEAVER_OG_ENTRY_LABEL_MANY");
+        theme
+    }
+
+    /// Pick card colours from a notebook's resolved theme.
+    ///
+    /// Cards use the dark scheme unless the notebook explicitly defaults to
+    /// light, matching how the generated notebook css picks its initial
+    /// scheme.
+    pub fn from_resolved(resolved: &weaver_renderer::theme::ResolvedTheme<'_>) -> Self {
+        use weaver_renderer::theme::ThemeDefault;
+
+        let scheme = match resolved.default {
+            ThemeDefault::Light => &resolved.light_scheme,
+            ThemeDefault::Auto | ThemeDefault::Dark => &resolved.dark_scheme,
+        };
+        Self {
+            base: scheme.base.to_smolstr(),
+            text: scheme.text.to_smolstr(),
+            subtle: scheme.subtle.to_smolstr(),
+            primary: scheme.primary.to_smolstr(),
+            tertiary: scheme.tertiary.to_smolstr(),
+            ..Self::from_env()
+        }
+    }
+}
+
+fn override_label(slot: &mut SmolStr, key: &str) {
+    if let Ok(value) = std::env::var(key) {
+        if !value.is_empty() {
+            *slot = value.to_smolstr();
+        }
+    }
 }
 
 /// Text-only template (no hero image)
 #[derive(Template)]
 #[template(path = "og_text_only.svg", escape = "none")]
 pub struct TextOnlyTemplate {
+    pub theme: OgTheme,
     pub title_lines: Vec<String>,
     pub content_lines: Vec<String>,
     pub notebook_title: SmolStr,
@@ -84,6 +177,7 @@ pub struct TextOnlyTemplate {
 #[derive(Template)]
 #[template(path = "og_hero_image.svg", escape = "none")]
 pub struct HeroImageTemplate {
+    pub theme: OgTheme,
     pub hero_image_data: String,
     pub title_lines: Vec<String>,
     pub notebook_title: SmolStr,
@@ -94,6 +188,7 @@ pub struct HeroImageTemplate {
 #[derive(Template)]
 #[template(path = "og_notebook.svg", escape = "none")]
 pub struct NotebookTemplate {
+    pub theme: OgTheme,
     pub title_lines: Vec<String>,
     pub author_handle: SmolStr,
     pub entry_count: usize,
@@ -152,16 +247,52 @@ fn get_fontdb() -> &'static fontdb::Database {
         db.load_font_data(
             include_bytes!("../../assets/fonts/ioskeley-mono/IoskeleyMono-Regular.ttf").to_vec(),
         );
+        // The bundled fonts only cover Latin, so emoji and RTL scripts
+        // shape into tofu without a fallback. We don't ship a colour emoji
+        // font (they run to tens of megabytes); instead pick up the host's
+        // fonts and let an operator point at a dedicated fallback.
+        if let Ok(path) = std::env::var("WEAVER_OG_EMOJI_FONT") {
+            if let Err(e) = db.load_font_file(&path) {
+                tracing::warn!(%path, error = %e, "failed to load OG emoji fallback font");
+            }
+        }
+        db.load_system_fonts();
         db
     })
 }
 
+/// Which way a title's base paragraph runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextDirection {
+    Ltr,
+    Rtl,
+}
+
+/// Detect the bidi base direction of `text` from its first strong character.
+pub fn base_direction(text: &str) -> TextDirection {
+    let bidi = unicode_bidi::BidiInfo::new(text, None);
+    if bidi.paragraphs.first().is_some_and(|p| p.level.is_rtl()) {
+        TextDirection::Rtl
+    } else {
+        TextDirection::Ltr
+    }
+}
+
 /// Wrap title text into lines that fit the SVG width
+///
+/// resvg shapes each `<text>` element with rustybuzz and infers its
+/// direction from the first strong character. Wrapping can strand a line on
+/// a Latin word inside an otherwise RTL title, flipping that line's layout,
+/// so the paragraph's base direction is re-asserted on every wrapped line.
 pub fn wrap_title(title: &str, max_chars: usize, max_lines: usize) -> Vec<String> {
+    let mark = match base_direction(title) {
+        TextDirection::Rtl => "\u{200f}",
+        TextDirection::Ltr => "",
+    };
     textwrap::wrap(title, max_chars)
         .into_iter()
         .take(max_lines)
-        .map(|s| s.to_string())
+        .map(|s| format!("{mark}{s}"))
         .collect()
 }
 
@@ -169,6 +300,9 @@ pub fn wrap_title(title: &str, max_chars: usize, max_lines: usize) -> Vec<String
 pub fn render_svg_to_png(svg: &str) -> Result<Vec<u8>, OgError> {
     let fontdb = get_fontdb();
 
+    // usvg shapes text runs with rustybuzz against this fontdb, including
+    // per-glyph fallback across the loaded faces, so script and emoji
+    // coverage is entirely a question of which fonts the database holds.
     let options = usvg::Options {
         fontdb: std::sync::Arc::new(fontdb.clone()),
         ..Default::default()
@@ -189,6 +323,7 @@ pub fn render_svg_to_png(svg: &str) -> Result<Vec<u8>, OgError> {
 
 /// Generate a text-only OG image
 pub fn generate_text_only(
+    theme: &OgTheme,
     title: &str,
     content: &str,
     notebook_title: &str,
@@ -198,6 +333,7 @@ pub fn generate_text_only(
     let content_lines = wrap_title(content, 70, 5);
 
     let template = TextOnlyTemplate {
+        theme: theme.clone(),
         title_lines,
         content_lines,
         notebook_title: notebook_title.to_smolstr(),
@@ -213,6 +349,7 @@ pub fn generate_text_only(
 
 /// Generate a hero image OG image
 pub fn generate_hero_image(
+    theme: &OgTheme,
     hero_image_data: &str,
     title: &str,
     notebook_title: &str,
@@ -221,6 +358,7 @@ pub fn generate_hero_image(
     let title_lines = wrap_title(title, 50, 2);
 
     let template = HeroImageTemplate {
+        theme: theme.clone(),
         hero_image_data: hero_image_data.to_string(),
         title_lines,
         notebook_title: notebook_title.to_smolstr(),
@@ -246,6 +384,7 @@ pub fn profile_cache_key(ident: &str, cid: &str) -> SmolStr {
 
 /// Generate a notebook index OG image
 pub fn generate_notebook_og(
+    theme: &OgTheme,
     title: &str,
     author_handle: &str,
     entry_count: usize,
@@ -257,8 +396,11 @@ pub fn generate_notebook_og(
         .into_iter()
         .take(4)
         .map(|t| {
-            if t.len() > 60 {
-                format!("{}...", &t[..57])
+            if t.chars().count() > 60 {
+                // Truncate on a char boundary; a fixed byte slice panics
+                // on emoji and other multi-byte titles.
+                let cut = t.char_indices().nth(57).map(|(i, _)| i).unwrap_or(t.len());
+                format!("{}...", &t[..cut])
             } else {
                 t
             }
@@ -266,6 +408,7 @@ pub fn generate_notebook_og(
         .collect();
 
     let template = NotebookTemplate {
+        theme: theme.clone(),
         title_lines,
         author_handle: author_handle.to_smolstr(),
         entry_count,
@@ -354,6 +497,43 @@ mod tests {
         assert_eq!(lines, vec!["Hello World"]);
     }
 
+    #[test]
+    fn test_base_direction() {
+        assert_eq!(base_direction("Hello World"), TextDirection::Ltr);
+        assert_eq!(base_direction("مرحبا بالعالم"), TextDirection::Rtl);
+        // Leading neutrals don't decide direction; the first strong
+        // character does.
+        assert_eq!(base_direction("«שלום עולם»"), TextDirection::Rtl);
+    }
+
+    #[test]
+    fn test_wrap_title_rtl_marks_every_line() {
+        let lines = wrap_title("مرحبا بالعالم مرحبا بالعالم مرحبا بالعالم", 14, 3);
+        assert!(lines.len() > 1);
+        for line in &lines {
+            assert!(line.starts_with('\u{200f}'));
+        }
+    }
+
+    #[test]
+    fn test_og_theme_from_default_resolved_matches_defaults() {
+        // The stock resolved theme is Rose Pine, so the token mapping must
+        // land back on the hardcoded defaults.
+        let resolved = weaver_renderer::theme::default_resolved_theme();
+        let theme = OgTheme::from_resolved(&resolved);
+        assert_eq!(theme.base, OgTheme::default().base);
+        assert_eq!(theme.primary, OgTheme::default().primary);
+        assert_eq!(theme.subtle, OgTheme::default().subtle);
+    }
+
+    #[test]
+    fn test_og_theme_light_default_uses_light_scheme() {
+        let mut resolved = weaver_renderer::theme::default_resolved_theme();
+        resolved.default = weaver_renderer::theme::ThemeDefault::Light;
+        let theme = OgTheme::from_resolved(&resolved);
+        assert_eq!(theme.base.as_str(), resolved.light_scheme.base.as_ref());
+    }
+
     #[test]
     fn test_wrap_title_long() {
         let lines = wrap_title(
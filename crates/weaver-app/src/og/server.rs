@@ -16,6 +16,54 @@ use std::sync::Arc;
 #[cfg(all(feature = "fullstack-server", feature = "server"))]
 use jacquard::smol_str::ToSmolStr;
 
+/// Resolve a notebook record's published theme into OG tokens.
+///
+/// Any failure along the way (no theme ref, unfetchable colour schemes)
+/// falls back to the deployment defaults; a social card with stock colours
+/// beats no card at all.
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+async fn og_theme_from_book_record(
+    fetcher: &fetch::Fetcher,
+    record: &jacquard::types::value::Data<'_>,
+) -> og::OgTheme {
+    use jacquard::client::AgentSessionExt;
+    use jacquard::from_data;
+    use weaver_api::sh_weaver::notebook::book::Book;
+    use weaver_renderer::theme::{Theme, resolve_theme};
+
+    let Ok(book) = from_data::<Book>(record) else {
+        return og::OgTheme::from_env();
+    };
+    let Some(theme_ref) = book.theme else {
+        return og::OgTheme::from_env();
+    };
+    let Ok(theme_response) = fetcher.client.get_record::<Theme>(&theme_ref.uri).await else {
+        return og::OgTheme::from_env();
+    };
+    let Ok(theme_output) = theme_response.into_output() else {
+        return og::OgTheme::from_env();
+    };
+    let theme: Theme = theme_output.into();
+    let client = fetcher.get_client();
+    match resolve_theme(client.as_ref(), &theme).await {
+        Ok(resolved) => og::OgTheme::from_resolved(&resolved),
+        Err(_) => og::OgTheme::from_env(),
+    }
+}
+
+/// Look up a notebook and resolve its theme into OG tokens.
+#[cfg(all(feature = "fullstack-server", feature = "server"))]
+async fn og_theme_for_notebook(
+    fetcher: &fetch::Fetcher,
+    ident: AtIdentifier<'static>,
+    book_title: SmolStr,
+) -> og::OgTheme {
+    match fetcher.get_notebook(ident, book_title).await {
+        Ok(Some(notebook)) => og_theme_from_book_record(fetcher, &notebook.0.record).await,
+        _ => og::OgTheme::from_env(),
+    }
+}
+
 // Route: /og/{ident}/{book_title}/{entry_title} - OpenGraph image for entry
 #[cfg(all(feature = "fullstack-server", feature = "server"))]
 #[get("/og/{ident}/{book_title}/{entry_title}", fetcher: Extension<Arc<fetch::Fetcher>>)]
@@ -156,25 +204,46 @@ pub async fn og_image(
             .join(" ")
     };
 
+    // Pick card colours from the notebook's published theme, if any
+    let theme = og_theme_for_notebook(&fetcher, at_ident.clone(), book_title.clone()).await;
+
     // Generate image - hero or text-only based on available data
     let png_bytes = if let Some(ref hero_data) = hero_image_data {
-        match og::generate_hero_image(hero_data, title, &notebook_title_str, &author_handle) {
+        match og::generate_hero_image(
+            &theme,
+            hero_data,
+            title,
+            &notebook_title_str,
+            &author_handle,
+        ) {
             Ok(bytes) => bytes,
             Err(e) => {
                 tracing::error!(
                     "Failed to generate hero OG image: {:?}, falling back to text",
                     e
                 );
-                og::generate_text_only(title, &content_snippet, &notebook_title_str, &author_handle)
-                    .map_err(|e| {
-                        tracing::error!("Failed to generate text OG image: {:?}", e);
-                    })
-                    .ok()
-                    .unwrap_or_default()
+                og::generate_text_only(
+                    &theme,
+                    title,
+                    &content_snippet,
+                    &notebook_title_str,
+                    &author_handle,
+                )
+                .map_err(|e| {
+                    tracing::error!("Failed to generate text OG image: {:?}", e);
+                })
+                .ok()
+                .unwrap_or_default()
             }
         }
     } else {
-        match og::generate_text_only(title, &content_snippet, &notebook_title_str, &author_handle) {
+        match og::generate_text_only(
+            &theme,
+            title,
+            &content_snippet,
+            &notebook_title_str,
+            &author_handle,
+        ) {
             Ok(bytes) => bytes,
             Err(e) => {
                 tracing::error!("Failed to generate OG image: {:?}", e);
@@ -282,6 +351,12 @@ pub async fn og_notebook_image(
         .await;
     let (entry_count, entry_titles) = match entries_result {
         Ok(Some(entries)) => {
+            // Social cards are public surface: leave unlisted and
+            // not-yet-published entries out of the count and titles.
+            let entries: Vec<_> = entries
+                .iter()
+                .filter(|e| crate::record_utils::record_is_listed(&e.entry.record))
+                .collect();
             let count = entries.len();
             let titles: Vec<String> = entries
                 .iter()
@@ -299,19 +374,22 @@ pub async fn og_notebook_image(
         _ => (0, vec![]),
     };
 
+    // Pick card colours from the notebook's published theme, if any
+    let theme = og_theme_from_book_record(&fetcher, &notebook_view.record).await;
+
     // Generate image
-    let png_bytes = match og::generate_notebook_og(title, &author_handle, entry_count, entry_titles)
-    {
-        Ok(bytes) => bytes,
-        Err(e) => {
-            tracing::error!("Failed to generate notebook OG image: {:?}", e);
-            return Ok((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Failed to generate image",
-            )
-                .into_response());
-        }
-    };
+    let png_bytes =
+        match og::generate_notebook_og(&theme, title, &author_handle, entry_count, entry_titles) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::error!("Failed to generate notebook OG image: {:?}", e);
+                return Ok((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to generate image",
+                )
+                    .into_response());
+            }
+        };
 
     // Cache the generated image
     og::cache_image(cache_key, png_bytes.clone());
@@ -3,3 +3,41 @@
 //! This module exists for backwards compatibility during migration.
 
 pub use weaver_common::perf::*;
+
+#[cfg(feature = "server")]
+use crate::blob_store::BlobCacheStats;
+#[cfg(feature = "server")]
+use crate::middleware::RateLimitStats;
+
+/// Log a blob cache stats snapshot at debug level.
+///
+/// Counters are cumulative since startup; the resident gauges are current.
+/// The store logs this itself after each eviction pass, so memory-pressure
+/// behaviour shows up in traces without a polling task.
+#[cfg(feature = "server")]
+pub fn log_blob_cache_stats(stats: &BlobCacheStats) {
+    tracing::debug!(
+        memory_hits = stats.memory_hits,
+        disk_hits = stats.disk_hits,
+        misses = stats.misses,
+        evictions = stats.evictions,
+        expirations = stats.expirations,
+        resident_bytes = stats.resident_bytes,
+        resident_blobs = stats.resident_blobs,
+        "blob cache stats"
+    );
+}
+
+/// Log a rate limiter stats snapshot at debug level.
+///
+/// Counters are cumulative since startup. The limiter logs this on each
+/// denial, so sustained throttling is visible in traces as it happens.
+#[cfg(feature = "server")]
+pub fn log_rate_limit_stats(stats: &RateLimitStats) {
+    tracing::debug!(
+        allowed = stats.allowed,
+        limited = stats.limited,
+        tracked_buckets = stats.tracked_buckets,
+        "rate limit stats"
+    );
+}
@@ -366,3 +366,110 @@ pub fn get_hex_rep(byte_array: &mut [u8]) -> String {
         .collect();
     build_string_vec.join("")
 }
+
+// ============================================================================
+// Publish Visibility and Scheduling
+// ============================================================================
+
+/// Extra-data field carrying the visibility flag on an entry record.
+pub const VISIBILITY_FIELD: &str = "visibility";
+/// Extra-data field carrying the scheduled publish time on an entry record.
+pub const PUBLISHED_AT_FIELD: &str = "publishedAt";
+
+/// Visibility of a published entry.
+///
+/// Stored in the record's extra data rather than the lexicon so clients
+/// that do not know the field round-trip it untouched; absence means
+/// public.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntryVisibility {
+    #[default]
+    Public,
+    /// Renders when accessed directly but is excluded from listings.
+    Unlisted,
+}
+
+impl EntryVisibility {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EntryVisibility::Public => "public",
+            EntryVisibility::Unlisted => "unlisted",
+        }
+    }
+
+    /// Parse a stored flag; unknown values fall back to public.
+    pub fn parse(value: &str) -> Self {
+        if value == "unlisted" {
+            EntryVisibility::Unlisted
+        } else {
+            EntryVisibility::Public
+        }
+    }
+}
+
+/// Build the extra-data map carrying an entry's publish options.
+///
+/// Returns `None` when both options are defaults so plain public entries
+/// keep records identical to those from older clients.
+pub fn publish_extra_data(
+    visibility: EntryVisibility,
+    published_at: Option<&jacquard::types::string::Datetime>,
+) -> Option<std::collections::BTreeMap<SmolStr, Data<'static>>> {
+    use jacquard::types::string::AtprotoStr;
+    use std::collections::BTreeMap;
+
+    let mut map = BTreeMap::new();
+    if visibility == EntryVisibility::Unlisted {
+        map.insert(
+            SmolStr::new_static(VISIBILITY_FIELD),
+            Data::String(AtprotoStr::String(visibility.as_str().into())),
+        );
+    }
+    if let Some(dt) = published_at {
+        map.insert(
+            SmolStr::new_static(PUBLISHED_AT_FIELD),
+            Data::String(AtprotoStr::Datetime(dt.clone())),
+        );
+    }
+    if map.is_empty() { None } else { Some(map) }
+}
+
+/// Read the visibility flag from a hydrated entry record.
+pub fn record_visibility(record: &Data<'_>) -> EntryVisibility {
+    let Data::Object(obj) = record else {
+        return EntryVisibility::Public;
+    };
+    obj.0
+        .get(VISIBILITY_FIELD)
+        .and_then(|d| d.as_str())
+        .map(EntryVisibility::parse)
+        .unwrap_or_default()
+}
+
+/// Read the scheduled publish time from a hydrated entry record.
+pub fn record_published_at(record: &Data<'_>) -> Option<jacquard::types::string::Datetime> {
+    use jacquard::types::string::Datetime;
+    use std::str::FromStr;
+
+    let Data::Object(obj) = record else {
+        return None;
+    };
+    let text = obj.0.get(PUBLISHED_AT_FIELD)?.as_str()?;
+    Datetime::from_str(text).ok()
+}
+
+/// Whether an entry should appear in listings right now.
+///
+/// Unlisted entries and entries scheduled for the future still render
+/// when accessed directly; they only stay out of indexes and feeds.
+pub fn record_is_listed(record: &Data<'_>) -> bool {
+    use jacquard::types::string::Datetime;
+
+    if record_visibility(record) == EntryVisibility::Unlisted {
+        return false;
+    }
+    match record_published_at(record) {
+        Some(published_at) => published_at <= Datetime::now(),
+        None => true,
+    }
+}
@@ -0,0 +1,286 @@
+//! Client-side full-text search over a repository's entries.
+//!
+//! The app already fetches every entry for a repository to build the
+//! profile timeline, so search reuses that data instead of maintaining a
+//! separate index: the query is split into terms, every term must match
+//! somewhere in an entry (title, content, tags, or containing notebook),
+//! and results are ranked by where the terms hit. Matching is ASCII
+//! case-insensitive so match byte ranges index into the original strings
+//! unchanged, which is what the highlighting in the view relies on. If
+//! the `use-index` backend grows a search endpoint, this module's ranking
+//! can move behind it without changing the view.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use weaver_api::sh_weaver::notebook::EntryView;
+use weaver_api::sh_weaver::notebook::entry::Entry;
+
+/// How many bytes of context to keep on each side of the first content
+/// match when building a snippet.
+const SNIPPET_CONTEXT: usize = 80;
+
+/// A single entry matched by a search query, ready for rendering.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchHit {
+    pub view: EntryView<'static>,
+    pub entry: Entry<'static>,
+    /// Title of the notebook containing the entry, if it is in one.
+    pub notebook: Option<String>,
+    /// Byte ranges of query matches within the entry title.
+    pub title_ranges: Vec<Range<usize>>,
+    /// Content excerpt around the first content match, if any.
+    pub snippet: Option<Snippet>,
+    score: u32,
+}
+
+/// A content excerpt with highlight ranges relative to `text`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snippet {
+    pub text: String,
+    pub ranges: Vec<Range<usize>>,
+    pub truncated_start: bool,
+    pub truncated_end: bool,
+}
+
+/// Search `entries` for `query`, optionally restricted to a notebook
+/// title and/or a tag.
+///
+/// `notebook_of` maps entry URIs to the title of their containing
+/// notebook. Every whitespace-separated query term must match somewhere
+/// in an entry for it to count as a hit; results come back ranked, best
+/// match first, with title and tag matches weighted above content
+/// matches.
+pub fn search_entries(
+    entries: &[(EntryView<'static>, Entry<'static>)],
+    notebook_of: &HashMap<String, String>,
+    query: &str,
+    notebook_filter: Option<&str>,
+    tag_filter: Option<&str>,
+) -> Vec<SearchHit> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut hits: Vec<SearchHit> = entries
+        .iter()
+        .filter_map(|(view, entry)| {
+            let notebook = notebook_of.get(view.uri.as_ref()).cloned();
+            if let Some(filter) = notebook_filter {
+                if notebook.as_deref() != Some(filter) {
+                    return None;
+                }
+            }
+            if let Some(filter) = tag_filter {
+                let tagged = entry
+                    .tags
+                    .as_ref()
+                    .is_some_and(|tags| tags.iter().any(|t| t.eq_ignore_ascii_case(filter)));
+                if !tagged {
+                    return None;
+                }
+            }
+            score_entry(view, entry, notebook, &terms)
+        })
+        .collect();
+
+    // Ties broken by recency so equally relevant hits stay predictable.
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.entry.created_at.cmp(&a.entry.created_at))
+    });
+    hits
+}
+
+/// Score one entry against the query terms, or `None` if any term fails
+/// to match.
+fn score_entry(
+    view: &EntryView<'static>,
+    entry: &Entry<'static>,
+    notebook: Option<String>,
+    terms: &[String],
+) -> Option<SearchHit> {
+    let title = entry.title.as_ref();
+    let content = entry.content.as_ref();
+    let title_lower = title.to_ascii_lowercase();
+    let content_lower = content.to_ascii_lowercase();
+    let notebook_lower = notebook.as_deref().map(str::to_ascii_lowercase);
+
+    let mut score = 0u32;
+    let mut title_ranges = Vec::new();
+    let mut content_ranges = Vec::new();
+
+    for term in terms {
+        let in_title = match_ranges(&title_lower, term);
+        let in_content = match_ranges(&content_lower, term);
+        let in_tags = entry.tags.as_ref().is_some_and(|tags| {
+            tags.iter()
+                .any(|t| t.to_ascii_lowercase().contains(term.as_str()))
+        });
+        let in_notebook = notebook_lower
+            .as_deref()
+            .is_some_and(|nb| nb.contains(term.as_str()));
+
+        if in_title.is_empty() && in_content.is_empty() && !in_tags && !in_notebook {
+            return None;
+        }
+
+        // Content occurrences are capped so a term repeated hundreds of
+        // times cannot drown out a title match elsewhere.
+        score += in_title.len() as u32 * 8;
+        score += (in_content.len() as u32).min(20);
+        if in_tags {
+            score += 4;
+        }
+        if in_notebook {
+            score += 2;
+        }
+        title_ranges.extend(in_title);
+        content_ranges.extend(in_content);
+    }
+
+    let title_ranges = merge_ranges(title_ranges);
+    let content_ranges = merge_ranges(content_ranges);
+
+    Some(SearchHit {
+        view: view.clone(),
+        entry: entry.clone(),
+        notebook,
+        title_ranges,
+        snippet: build_snippet(content, &content_ranges),
+        score,
+    })
+}
+
+/// Byte ranges of every occurrence of `term` in `haystack_lower`. Both
+/// sides must already be ASCII-lowercased; because ASCII lowercasing
+/// preserves byte lengths, the ranges are valid for the original string.
+fn match_ranges(haystack_lower: &str, term: &str) -> Vec<Range<usize>> {
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(rel) = haystack_lower[from..].find(term) {
+        let start = from + rel;
+        out.push(start..start + term.len());
+        from = start + term.len().max(1);
+    }
+    out
+}
+
+/// Sort ranges and coalesce any that overlap or touch, so highlighting
+/// never emits nested or out-of-order marks.
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Cut an excerpt around the first content match, expanded to character
+/// boundaries, with the match ranges rebased onto the excerpt.
+fn build_snippet(content: &str, ranges: &[Range<usize>]) -> Option<Snippet> {
+    let first = ranges.first()?;
+    let start = floor_char_boundary(content, first.start.saturating_sub(SNIPPET_CONTEXT));
+    let end = ceil_char_boundary(content, (first.end + SNIPPET_CONTEXT).min(content.len()));
+    let ranges = ranges
+        .iter()
+        .filter(|r| r.start >= start && r.end <= end)
+        .map(|r| r.start - start..r.end - start)
+        .collect();
+    Some(Snippet {
+        text: content[start..end].to_string(),
+        ranges,
+        truncated_start: start > 0,
+        truncated_end: end < content.len(),
+    })
+}
+
+/// Split `text` into `(segment, highlighted)` pairs for rendering. The
+/// ranges must be sorted and non-overlapping, as produced by
+/// [`merge_ranges`].
+pub fn highlight_segments<'s>(text: &'s str, ranges: &[Range<usize>]) -> Vec<(&'s str, bool)> {
+    let mut segments = Vec::new();
+    let mut cursor = 0;
+    for range in ranges {
+        if range.start > cursor {
+            segments.push((&text[cursor..range.start], false));
+        }
+        segments.push((&text[range.start..range.end], true));
+        cursor = range.end;
+    }
+    if cursor < text.len() {
+        segments.push((&text[cursor..], false));
+    }
+    segments
+}
+
+fn floor_char_boundary(s: &str, mut i: usize) -> usize {
+    while i > 0 && !s.is_char_boundary(i) {
+        i -= 1;
+    }
+    i
+}
+
+fn ceil_char_boundary(s: &str, mut i: usize) -> usize {
+    while i < s.len() && !s.is_char_boundary(i) {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_ranges_is_case_insensitive_and_indexes_original() {
+        let original = "Weaver weaves WEAVER";
+        let lower = original.to_ascii_lowercase();
+        let ranges = match_ranges(&lower, "weaver");
+        assert_eq!(ranges, vec![0..6, 14..20]);
+        assert_eq!(&original[14..20], "WEAVER");
+    }
+
+    #[test]
+    fn merge_ranges_coalesces_overlaps() {
+        let merged = merge_ranges(vec![5..10, 0..3, 8..12, 12..14]);
+        assert_eq!(merged, vec![0..3, 5..14]);
+    }
+
+    #[test]
+    fn highlight_segments_covers_full_text() {
+        let segments = highlight_segments("abcdef", &[1..3, 4..5]);
+        assert_eq!(
+            segments,
+            vec![
+                ("a", false),
+                ("bc", true),
+                ("d", false),
+                ("e", true),
+                ("f", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn snippet_respects_char_boundaries() {
+        // A multi-byte character right at the context edge must not
+        // split the excerpt mid-character.
+        let content = format!("{}match", "é".repeat(100));
+        let lower = content.to_ascii_lowercase();
+        let ranges = match_ranges(&lower, "match");
+        let snippet = build_snippet(&content, &ranges).unwrap();
+        assert!(snippet.text.ends_with("match"));
+        assert!(snippet.truncated_start);
+        assert!(!snippet.truncated_end);
+    }
+}
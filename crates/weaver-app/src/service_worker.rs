@@ -2,12 +2,12 @@ use dioxus::prelude::*;
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 use wasm_bindgen::prelude::*;
 
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+use jacquard::smol_str::format_smolstr;
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 use wasm_bindgen_futures::JsFuture;
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 use web_sys::{RegistrationOptions, ServiceWorkerContainer, Window};
-#[cfg(all(target_family = "wasm", target_os = "unknown"))]
-use jacquard::smol_str::format_smolstr;
 
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 pub async fn register_service_worker() -> Result<(), JsValue> {
@@ -196,12 +196,43 @@ fn send_blob_rkey_mappings(
     Ok(())
 }
 
+/// Tell the service worker which record CID backs the current page, so its
+/// cached copy can be invalidated when the record changes.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub fn cache_current_entry_page(cid: &str) -> Result<(), JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("no window"))?;
+    let path = window.location().pathname()?;
+    let navigator = window.navigator();
+    let sw_container = navigator.service_worker();
+
+    let controller = sw_container
+        .controller()
+        .ok_or_else(|| JsValue::from_str("no service worker controller"))?;
+
+    // Build message object
+    let msg = js_sys::Object::new();
+    js_sys::Reflect::set(&msg, &"type".into(), &"cache_entry_page".into())?;
+    js_sys::Reflect::set(&msg, &"path".into(), &path.as_str().into())?;
+    js_sys::Reflect::set(&msg, &"cid".into(), &cid.into())?;
+
+    controller.post_message(&msg)?;
+    tracing::debug!("registered page cid for {}", path);
+
+    Ok(())
+}
+
 #[allow(unused)]
 #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
 pub async fn register_service_worker() -> Result<(), String> {
     Ok(())
 }
 
+#[allow(unused)]
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub fn cache_current_entry_page(_cid: &str) -> Result<(), String> {
+    Ok(())
+}
+
 #[allow(unused)]
 #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
 pub fn send_blob_mappings(
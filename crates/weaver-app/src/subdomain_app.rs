@@ -104,6 +104,8 @@ pub fn SubdomainApp() -> Element {
         document::Link { rel: "icon", href: crate::FAVICON }
         document::Link { rel: "preconnect", href: "https://fonts.googleapis.com" }
         document::Link { rel: "preconnect", href: "https://fonts.gstatic.com" }
+        // Apply the stored theme preference before the stylesheets load.
+        document::Script { {crate::theme::THEME_BOOT_SCRIPT} }
         document::Link { rel: "stylesheet", href: crate::THEME_DEFAULTS_CSS }
         document::Link { rel: "stylesheet", href: "https://fonts.googleapis.com/css2?family=IBM+Plex+Mono:ital,wght@0,200;0,300;0,400;0,500;0,600;0,700;1,200;1,300;1,400;1,500;1,600;1,700&family=IBM+Plex+Sans:ital,wght@0,100..700;1,100..700&family=IBM+Plex+Serif:ital,wght@0,200;0,300;0,400;0,500;0,600;0,700;1,200;1,300;1,400;1,500;1,600;1,700&display=swap" }
         document::Link { rel: "stylesheet", href: crate::MAIN_CSS }
@@ -0,0 +1,217 @@
+//! User theme preference: light, dark, or follow the system.
+//!
+//! The preference is carried as a `data-theme` attribute on the root
+//! element, which `theme-defaults.css` (and the generated notebook CSS) use
+//! to force one colour scheme over the `prefers-color-scheme` default. It is
+//! persisted in two places: localStorage, the source of truth in the
+//! browser, and a cookie, so server-side rendering can seed the context with
+//! the same value the client will hydrate with. A small inline script
+//! applies the attribute before first paint to avoid a theme flash.
+
+use dioxus::prelude::*;
+
+/// Storage key in localStorage and cookie name for the persisted preference.
+const THEME_KEY: &str = "weaver-theme";
+
+/// Applies the stored preference before first paint.
+///
+/// Rendered into `<head>` ahead of the stylesheets so server-rendered pages
+/// don't flash the default scheme while hydration catches up. Reads the same
+/// two stores the theme context persists to; gloo's localStorage values are
+/// JSON-encoded, hence the quote stripping.
+pub const THEME_BOOT_SCRIPT: &str = r#"(function(){try{var t=localStorage.getItem("weaver-theme");if(t){t=t.replace(/"/g,"")}else{var m=document.cookie.match(/(?:^|; )weaver-theme=([^;]*)/);t=m&&m[1]}if(t==="light"||t==="dark"){document.documentElement.dataset.theme=t}}catch(e){}})()"#;
+
+/// The user's theme preference. [`ThemePreference::System`] defers to the
+/// platform's `prefers-color-scheme`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemePreference {
+    Light,
+    Dark,
+    #[default]
+    System,
+}
+
+impl ThemePreference {
+    /// The value persisted to storage and set as `data-theme`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ThemePreference::Light => "light",
+            ThemePreference::Dark => "dark",
+            ThemePreference::System => "system",
+        }
+    }
+
+    /// Parse a persisted value; anything unrecognised is `None` so stale or
+    /// tampered storage falls back to the default.
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(ThemePreference::Light),
+            "dark" => Some(ThemePreference::Dark),
+            "system" => Some(ThemePreference::System),
+            _ => None,
+        }
+    }
+
+    /// The preference after this one in the toggle cycle.
+    pub fn next(self) -> Self {
+        match self {
+            ThemePreference::System => ThemePreference::Light,
+            ThemePreference::Light => ThemePreference::Dark,
+            ThemePreference::Dark => ThemePreference::System,
+        }
+    }
+}
+
+/// Provide the theme preference context and keep the document in sync.
+///
+/// Called once near the top of the app. Seeds the signal from the persisted
+/// preference (cookie on the server, localStorage in the browser — kept in
+/// step by [`persist_preference`], so hydration sees the same value), then
+/// mirrors every change onto the root element's `data-theme` attribute and
+/// back into both stores.
+pub fn use_theme_provider() -> Signal<ThemePreference> {
+    let theme = use_signal(initial_preference);
+    use_context_provider(|| theme);
+
+    // Effects only run in the browser, which is also the only place the
+    // attribute and storage writes can happen.
+    use_effect(move || {
+        let preference = theme();
+        apply_preference(preference);
+        persist_preference(preference);
+    });
+
+    theme
+}
+
+/// The theme preference context provided by [`use_theme_provider`].
+pub fn use_theme() -> Signal<ThemePreference> {
+    use_context()
+}
+
+/// The persisted preference at first render, per platform.
+fn initial_preference() -> ThemePreference {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use gloo_storage::{LocalStorage, Storage};
+        if let Ok(stored) = LocalStorage::get::<String>(THEME_KEY) {
+            if let Some(preference) = ThemePreference::parse(&stored) {
+                return preference;
+            }
+        }
+        ThemePreference::default()
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    {
+        #[cfg(feature = "fullstack-server")]
+        let preference = preference_from_request_cookie().unwrap_or_default();
+        #[cfg(not(feature = "fullstack-server"))]
+        let preference = ThemePreference::default();
+        preference
+    }
+}
+
+/// Read the preference cookie from the incoming request during SSR.
+///
+/// The fullstack feature is also on in browser builds, so gate on the
+/// target too: there is no request to read on the client.
+#[cfg(all(
+    feature = "fullstack-server",
+    not(all(target_family = "wasm", target_os = "unknown"))
+))]
+fn preference_from_request_cookie() -> Option<ThemePreference> {
+    use dioxus::fullstack::FullstackContext;
+    use http::header::COOKIE;
+
+    let ctx = FullstackContext::current()?;
+    let parts = ctx.parts_mut();
+    let cookies = parts.headers.get(COOKIE)?.to_str().ok()?;
+    cookies.split(';').find_map(|pair| {
+        let (name, value) = pair.trim().split_once('=')?;
+        if name == THEME_KEY {
+            ThemePreference::parse(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
+/// Reflect the preference onto the root element's `data-theme` attribute.
+///
+/// `System` removes the attribute entirely so the `prefers-color-scheme`
+/// media queries take over again.
+fn apply_preference(preference: ThemePreference) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        let Some(root) = web_sys::window()
+            .and_then(|w| w.document())
+            .and_then(|d| d.document_element())
+        else {
+            return;
+        };
+        let result = match preference {
+            ThemePreference::System => root.remove_attribute("data-theme"),
+            explicit => root.set_attribute("data-theme", explicit.as_str()),
+        };
+        if let Err(err) = result {
+            tracing::warn!(?err, "failed to set data-theme attribute");
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = preference;
+}
+
+/// Persist the preference to localStorage and the cookie the server reads.
+fn persist_preference(preference: ThemePreference) {
+    #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+    {
+        use gloo_storage::{LocalStorage, Storage};
+        use wasm_bindgen::JsCast;
+
+        if let Err(err) = LocalStorage::set(THEME_KEY, preference.as_str()) {
+            tracing::warn!(?err, "failed to persist theme preference");
+        }
+        // The cookie exists purely so SSR can seed the context; a year is
+        // effectively "until changed".
+        if let Some(document) = web_sys::window().and_then(|w| w.document()) {
+            if let Some(document) = document.dyn_ref::<web_sys::HtmlDocument>() {
+                let cookie = format!(
+                    "{THEME_KEY}={}; path=/; max-age=31536000; samesite=lax",
+                    preference.as_str()
+                );
+                if let Err(err) = document.set_cookie(&cookie) {
+                    tracing::warn!(?err, "failed to persist theme cookie");
+                }
+            }
+        }
+    }
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    let _ = preference;
+}
+
+/// Cycles the theme preference: system, then light, then dark.
+#[component]
+pub fn ThemeToggle() -> Element {
+    let mut theme = use_theme();
+    let preference = theme();
+    // The glyph shows the mode the click switches to, the title spells out
+    // the current one.
+    let (glyph, label) = match preference {
+        ThemePreference::System => ("☀", "Theme: system"),
+        ThemePreference::Light => ("☾", "Theme: light"),
+        ThemePreference::Dark => ("◑", "Theme: dark"),
+    };
+
+    rsx! {
+        button {
+            class: "theme-toggle",
+            title: "{label}",
+            aria_label: "{label}",
+            onclick: move |_| {
+                let next = theme.peek().next();
+                theme.set(next);
+            },
+            span { class: "theme-toggle-glyph", "{glyph}" }
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Private view analytics dashboard for a repository.
+//!
+//! Owner-only: the index enforces this server-side, and the page mirrors
+//! the check client-side so visitors see an explanation instead of an
+//! auth error. Backed by `sh.weaver.analytics.getSummary`, fed by the
+//! view beacons entry pages send; without the index backend there is no
+//! data source, so the page says so instead of rendering empty tables.
+
+#[cfg(feature = "use-index")]
+use crate::auth::AuthState;
+use dioxus::prelude::*;
+use jacquard::types::ident::AtIdentifier;
+
+const ANALYTICS_CSS: Asset = asset!("/assets/styling/analytics.css");
+
+/// Format a millisecond duration for display ("42s", "4m 12s").
+#[cfg(any(feature = "use-index", test))]
+fn format_duration(ms: u64) -> String {
+    let total_secs = ms / 1000;
+    let mins = total_secs / 60;
+    let secs = total_secs % 60;
+    if mins == 0 {
+        format!("{}s", secs)
+    } else if secs == 0 {
+        format!("{}m", mins)
+    } else {
+        format!("{}m {}s", mins, secs)
+    }
+}
+
+/// Label for a reading-time histogram bucket by its lower bound.
+///
+/// Bucket floors are fixed by the index query; anything unrecognised
+/// (a future server with more buckets) falls back to "over floor".
+#[cfg(any(feature = "use-index", test))]
+fn bucket_label(floor_ms: u64) -> String {
+    match floor_ms {
+        0 => "under 30s".to_string(),
+        30_000 => "30s \u{2013} 1m".to_string(),
+        60_000 => "1m \u{2013} 2m".to_string(),
+        120_000 => "2m \u{2013} 5m".to_string(),
+        300_000 => "5m \u{2013} 10m".to_string(),
+        600_000 => "10m \u{2013} 20m".to_string(),
+        1_200_000 => "over 20m".to_string(),
+        other => format!("over {}", format_duration(other)),
+    }
+}
+
+/// Whether the authenticated user owns this repository.
+#[cfg(feature = "use-index")]
+fn is_owner(auth_state: &Signal<AuthState>, ident: &AtIdentifier<'static>) -> bool {
+    let current_did = auth_state.read().did.clone();
+    match (&current_did, ident) {
+        (Some(did), AtIdentifier::Did(ident_did)) => *did == *ident_did,
+        _ => false,
+    }
+}
+
+/// Analytics dashboard page.
+#[cfg(not(feature = "use-index"))]
+#[component]
+pub fn AnalyticsPage(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
+    let _ = ident;
+    rsx! {
+        document::Link { rel: "stylesheet", href: ANALYTICS_CSS }
+        div { class: "analytics-page",
+            h1 { "Analytics" }
+            p { class: "analytics-note",
+                "Analytics needs the index backend, which this build runs without."
+            }
+        }
+    }
+}
+
+/// Analytics dashboard page.
+#[cfg(feature = "use-index")]
+#[component]
+pub fn AnalyticsPage(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
+    use crate::fetch::Fetcher;
+    use jacquard::IntoStatic;
+    use weaver_common::analytics::{AnalyticsSummary, GetAnalyticsSummary};
+
+    let auth_state = use_context::<Signal<AuthState>>();
+    let fetcher = use_context::<Fetcher>();
+    let mut days = use_signal(|| 30u32);
+
+    let summary = use_resource(move || {
+        let fetcher = fetcher.clone();
+        let actor = ident();
+        let days = days();
+        async move {
+            let response = fetcher
+                .send(GetAnalyticsSummary { actor, days })
+                .await
+                .map_err(|e| e.to_string())?;
+            response
+                .into_output()
+                .map(AnalyticsSummary::into_static)
+                .map_err(|e| e.to_string())
+        }
+    });
+
+    if !is_owner(&auth_state, &ident()) {
+        return rsx! {
+            document::Link { rel: "stylesheet", href: ANALYTICS_CSS }
+            div { class: "analytics-page",
+                h1 { "Analytics" }
+                p { class: "analytics-note", "Analytics are private to the repository owner." }
+            }
+        };
+    }
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: ANALYTICS_CSS }
+        div { class: "analytics-page",
+            div { class: "analytics-header",
+                h1 { "Analytics" }
+                div { class: "analytics-range",
+                    for window in [7u32, 30, 90] {
+                        button {
+                            class: "analytics-range-option",
+                            class: if days() == window { "active" },
+                            onclick: move |_| days.set(window),
+                            "{window}d"
+                        }
+                    }
+                }
+            }
+
+            match summary() {
+                None => rsx! {
+                    p { class: "analytics-note", "Loading..." }
+                },
+                Some(Err(err)) => rsx! {
+                    p { class: "analytics-error", "Failed to load analytics: {err}" }
+                },
+                Some(Ok(summary)) => rsx! {
+                    if summary.entries.is_empty() {
+                        p { class: "analytics-note",
+                            "No views recorded in the last {summary.days} days."
+                        }
+                    } else {
+                        section { class: "analytics-section",
+                            h2 { "Entries" }
+                            table { class: "analytics-table",
+                                thead {
+                                    tr {
+                                        th { "Entry" }
+                                        th { class: "analytics-num", "Views" }
+                                        th { class: "analytics-num", "Median read" }
+                                        th { class: "analytics-num", "P90 read" }
+                                    }
+                                }
+                                tbody {
+                                    for entry in summary.entries.iter() {
+                                        tr {
+                                            td {
+                                                if entry.title.is_empty() {
+                                                    span { class: "analytics-rkey", "{entry.rkey}" }
+                                                } else {
+                                                    "{entry.title}"
+                                                }
+                                            }
+                                            td { class: "analytics-num", "{entry.view_count}" }
+                                            td { class: "analytics-num",
+                                                if entry.p50_reading_ms > 0 {
+                                                    {format_duration(entry.p50_reading_ms)}
+                                                } else {
+                                                    "\u{2014}"
+                                                }
+                                            }
+                                            td { class: "analytics-num",
+                                                if entry.p90_reading_ms > 0 {
+                                                    {format_duration(entry.p90_reading_ms)}
+                                                } else {
+                                                    "\u{2014}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        section { class: "analytics-section",
+                            h2 { "Referrers" }
+                            if summary.referrers.is_empty() {
+                                p { class: "analytics-note", "All views were direct visits." }
+                            } else {
+                                table { class: "analytics-table",
+                                    tbody {
+                                        for referrer in summary.referrers.iter() {
+                                            tr {
+                                                td { "{referrer.referrer}" }
+                                                td { class: "analytics-num", "{referrer.view_count}" }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        section { class: "analytics-section",
+                            h2 { "Reading time" }
+                            if summary.reading_time.is_empty() {
+                                p { class: "analytics-note", "No reading-time data yet." }
+                            } else {
+                                {
+                                    let max = summary
+                                        .reading_time
+                                        .iter()
+                                        .map(|b| b.view_count)
+                                        .max()
+                                        .unwrap_or(1)
+                                        .max(1);
+                                    rsx! {
+                                        div { class: "analytics-histogram",
+                                            for bucket in summary.reading_time.iter() {
+                                                div { class: "analytics-bucket",
+                                                    span { class: "analytics-bucket-label",
+                                                        {bucket_label(bucket.floor_ms)}
+                                                    }
+                                                    div { class: "analytics-bucket-track",
+                                                        div {
+                                                            class: "analytics-bucket-bar",
+                                                            style: "width: {bucket.view_count * 100 / max}%;",
+                                                        }
+                                                    }
+                                                    span { class: "analytics-bucket-count",
+                                                        "{bucket.view_count}"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(0), "0s");
+        assert_eq!(format_duration(42_000), "42s");
+        assert_eq!(format_duration(60_000), "1m");
+        assert_eq!(format_duration(252_000), "4m 12s");
+    }
+
+    #[test]
+    fn test_bucket_labels_cover_index_buckets() {
+        // The fixed floors the index query emits must all have labels.
+        for floor in [0, 30_000, 60_000, 120_000, 300_000, 600_000, 1_200_000] {
+            assert!(!bucket_label(floor).starts_with("over 0"));
+        }
+        // Unknown floors fall back to a generic label instead of panicking.
+        assert_eq!(bucket_label(2_400_000), "over 40m");
+    }
+}
@@ -39,6 +39,16 @@ pub fn Callback(
                 let did_owned = did.into_static();
                 auth.write()
                     .set_authenticated(did_owned.clone(), session_id);
+                // The OAuth redirect reloaded the app, so re-seed the account
+                // list from storage and mark the fresh login as active.
+                #[cfg(target_arch = "wasm32")]
+                {
+                    crate::auth::remember_active_did(&did_owned);
+                    for (stored_did, stored_session_id) in crate::auth::stored_accounts() {
+                        auth.write()
+                            .upsert_account(stored_did, stored_session_id.into());
+                    }
+                }
                 fetcher.upgrade_to_authenticated(session).await;
 
                 // Create weaver profile if it doesn't exist
@@ -4,7 +4,7 @@ use crate::Route;
 use crate::auth::AuthState;
 use crate::components::button::{Button, ButtonVariant};
 use crate::components::dialog::{DialogContent, DialogDescription, DialogRoot, DialogTitle};
-use crate::components::editor::{list_drafts_from_pds, RemoteDraft};
+use crate::components::editor::{RemoteDraft, list_drafts_from_pds};
 use crate::components::editor::{delete_draft, delete_draft_from_pds, list_drafts};
 use crate::fetch::Fetcher;
 use dioxus::prelude::*;
@@ -47,7 +47,12 @@ pub fn DraftsList(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
     let remote_drafts_resource = use_resource(move || {
         let fetcher = fetcher_for_resource.clone();
         let _did = auth_state.read().did.clone(); // Track auth state for reactivity
-        async move { list_drafts_from_pds(&fetcher).await.ok().unwrap_or_default() }
+        async move {
+            list_drafts_from_pds(&fetcher)
+                .await
+                .ok()
+                .unwrap_or_default()
+        }
     });
 
     // Check ownership - redirect if not viewing own drafts
@@ -76,7 +81,11 @@ pub fn DraftsList(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
         let local = local_drafts();
         let remote: Vec<RemoteDraft> = remote_drafts_resource().unwrap_or_default();
 
-        tracing::debug!("Merging drafts: {} local, {} remote", local.len(), remote.len());
+        tracing::debug!(
+            "Merging drafts: {} local, {} remote",
+            local.len(),
+            remote.len()
+        );
         for (key, _, _) in &local {
             tracing::debug!("  Local draft key: {}", key);
         }
@@ -90,9 +99,7 @@ pub fn DraftsList(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
         // Build set of local rkeys
         let local_rkeys: HashSet<String> = local
             .iter()
-            .map(|(key, _, _)| {
-                key.strip_prefix("new:").unwrap_or(key).to_string()
-            })
+            .map(|(key, _, _)| key.strip_prefix("new:").unwrap_or(key).to_string())
             .collect();
 
         let mut merged = Vec::new();
@@ -128,7 +135,12 @@ pub fn DraftsList(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
 
         tracing::info!("Merged {} drafts total", merged.len());
         for m in &merged {
-            tracing::info!("  Merged: rkey={} is_local={} is_remote={}", m.rkey, m.is_local, m.is_remote);
+            tracing::info!(
+                "  Merged: rkey={} is_local={} is_remote={}",
+                m.rkey,
+                m.is_local,
+                m.is_remote
+            );
         }
 
         merged
@@ -287,14 +299,67 @@ pub fn NewDraft(
     notebook: ReadSignal<Option<SmolStr>>,
 ) -> Element {
     use crate::components::editor::MarkdownEditor;
+    use crate::components::templates::{TEMPLATES_NOTEBOOK, TemplatePicker};
     use crate::views::editor::EditorCss;
 
+    let fetcher = use_context::<Fetcher>();
+
+    // The chosen starting content; outer None while the user has not
+    // picked yet. The choice must land before the editor mounts, because
+    // mounting mints a fresh draft key.
+    let mut template_choice = use_signal(|| None::<Option<String>>);
+
+    // Titles of entries in the designated Templates notebook; an absent
+    // notebook just means no templates.
+    let templates_resource = use_resource(move || {
+        let fetcher = fetcher.clone();
+        async move {
+            fetcher
+                .list_notebook_entries(ident(), SmolStr::new_static(TEMPLATES_NOTEBOOK))
+                .await
+                .ok()
+                .flatten()
+                .map(|entries| {
+                    entries
+                        .iter()
+                        .filter_map(|e| e.entry.title.as_ref().map(|t| SmolStr::new(t.as_str())))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        }
+    });
+
     rsx! {
         EditorCss {}
         div { class: "editor-page",
-            MarkdownEditor {
-                entry_uri: None,
-                target_notebook: notebook()
+            if let Some(initial) = template_choice() {
+                MarkdownEditor {
+                    initial_content: initial,
+                    entry_uri: None,
+                    target_notebook: notebook()
+                }
+            } else {
+                {
+                    match templates_resource() {
+                        None => rsx! {
+                            div { class: "editor-loading", "Loading..." }
+                        },
+                        // No templates: skip the picker entirely.
+                        Some(titles) if titles.is_empty() => rsx! {
+                            MarkdownEditor {
+                                entry_uri: None,
+                                target_notebook: notebook()
+                            }
+                        },
+                        Some(titles) => rsx! {
+                            TemplatePicker {
+                                ident: ident(),
+                                templates: titles,
+                                on_pick: move |content| template_choice.set(Some(content)),
+                            }
+                        },
+                    }
+                }
             }
         }
     }
@@ -310,8 +375,9 @@ pub fn StandaloneEntryEdit(
     use crate::views::editor::EditorCss;
 
     // Construct AT-URI for the entry
-    let entry_uri =
-        use_memo(move || format_smolstr!("at://{}/sh.weaver.notebook.entry/{}", ident(), rkey()).to_string());
+    let entry_uri = use_memo(move || {
+        format_smolstr!("at://{}/sh.weaver.notebook.entry/{}", ident(), rkey()).to_string()
+    });
 
     rsx! {
         EditorCss {}
@@ -334,8 +400,9 @@ pub fn NotebookEntryEdit(
     use weaver_common::EntryIndex;
 
     // Construct AT-URI for the entry
-    let entry_uri =
-        use_memo(move || format_smolstr!("at://{}/sh.weaver.notebook.entry/{}", ident(), rkey()).to_string());
+    let entry_uri = use_memo(move || {
+        format_smolstr!("at://{}/sh.weaver.notebook.entry/{}", ident(), rkey()).to_string()
+    });
 
     // Fetch notebook entries for wikilink validation
     let (_entries_resource, entries_memo) = use_notebook_entries(ident, book_title);
@@ -348,11 +415,22 @@ pub fn NotebookEntryEdit(
             let book = book_title();
             for book_entry in &entries {
                 // EntryView has optional title/path
-                let title = book_entry.entry.title.as_ref().map(|t| t.as_str()).unwrap_or("");
-                let path = book_entry.entry.path.as_ref().map(|p| p.as_str()).unwrap_or("");
+                let title = book_entry
+                    .entry
+                    .title
+                    .as_ref()
+                    .map(|t| t.as_str())
+                    .unwrap_or("");
+                let path = book_entry
+                    .entry
+                    .path
+                    .as_ref()
+                    .map(|p| p.as_str())
+                    .unwrap_or("");
                 if !title.is_empty() || !path.is_empty() {
                     // Build canonical URL: /{ident}/{book}/{path}
-                    let canonical_url = format_smolstr!("/{}/{}/{}", ident_str, book, path).to_string();
+                    let canonical_url =
+                        format_smolstr!("/{}/{}/{}", ident_str, book, path).to_string();
                     index.add_entry(title, path, canonical_url);
                 }
             }
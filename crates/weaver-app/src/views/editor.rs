@@ -17,6 +17,52 @@ pub fn Editor(entry: Option<String>) -> Element {
     }
 }
 
+/// Sample document seeded into the demo editor on first visit.
+const DEMO_CONTENT: &str = r#"# Welcome to weaver
+
+This is a live demo of the weaver editor. Everything you type here stays
+in this browser — nothing is uploaded until you sign in and publish.
+
+## Things to try
+
+- **Bold**, *italic*, and `inline code` via the toolbar or keyboard shortcuts
+- Lists, headings, and > blockquotes
+- Wiki links like [[another entry]]
+- Math: $e^{i\pi} + 1 = 0$
+
+```rust
+fn main() {
+    println!("code blocks with syntax highlighting");
+}
+```
+
+When you're ready to publish to your own PDS, use the sign-in button in
+the toolbar.
+"#;
+
+/// Demo editor page view at the /demo route.
+///
+/// Runs the full editor against localStorage only: no session restore, no
+/// PDS sync, and a sign-in CTA in place of the publish controls. Lets
+/// prospective users try the editing experience before OAuth.
+#[component]
+pub fn DemoEditor() -> Element {
+    rsx! {
+        EditorCss {}
+        div { class: "editor-page",
+            div { class: "demo-banner",
+                "You're trying the weaver editor. Your edits stay in this browser until you sign in."
+            }
+            MarkdownEditor {
+                initial_content: Some(DEMO_CONTENT.to_string()),
+                entry_uri: None,
+                target_notebook: None,
+                demo: true,
+            }
+        }
+    }
+}
+
 #[component]
 pub fn EditorCss() -> Element {
     use weaver_renderer::css::{generate_base_css, generate_syntax_css};
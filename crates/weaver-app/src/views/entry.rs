@@ -14,14 +14,16 @@ pub fn StandaloneEntry(
     rkey: ReadSignal<SmolStr>,
 ) -> Element {
     use crate::components::{
-        ENTRY_CSS, EntryMarkdown, EntryMetadata, EntryOgMeta, NavButton, calculate_reading_stats, extract_preview,
+        ENTRY_CSS, EntryMarkdown, EntryMetadata, EntryOgMeta, NavButton, ReaderSettingsButton,
+        StaleBanner, ViewBeacon, calculate_reading_stats, extract_preview,
     };
     use weaver_api::sh_weaver::actor::ProfileDataViewInner;
 
     let (entry_res, entry_data) = crate::data::use_standalone_entry_data(ident, rkey);
 
     #[cfg(feature = "fullstack-server")]
-    let _entry_res = entry_res?;
+    let entry_res = entry_res?;
+    let mut entry_res = entry_res;
 
     match &*entry_data.read() {
         Some(data) => {
@@ -90,6 +92,13 @@ pub fn StandaloneEntry(
                         }
 
                         div { class: "entry-content-main notebook-content",
+                            ViewBeacon { uri: entry_view.uri.clone() }
+                            ReaderSettingsButton {}
+                            StaleBanner {
+                                uri: entry_view.uri.clone(),
+                                cid: entry_view.cid.clone(),
+                                on_refresh: move |_| entry_res.restart(),
+                            }
                             {
                                 let (word_count, reading_time_mins) = calculate_reading_stats(&entry_record.content);
                                 rsx! {
@@ -135,6 +144,13 @@ pub fn StandaloneEntry(
 
                     div { class: "entry-page",
                         div { class: "entry-content-main notebook-content",
+                            ViewBeacon { uri: entry_view.uri.clone() }
+                            ReaderSettingsButton {}
+                            StaleBanner {
+                                uri: entry_view.uri.clone(),
+                                cid: entry_view.cid.clone(),
+                                on_refresh: move |_| entry_res.restart(),
+                            }
                             {
                                 let (word_count, reading_time_mins) = calculate_reading_stats(&entry_record.content);
                                 rsx! {
@@ -167,14 +183,16 @@ pub fn NotebookEntryByRkey(
     rkey: ReadSignal<SmolStr>,
 ) -> Element {
     use crate::components::{
-        ENTRY_CSS, EntryMarkdown, EntryMetadata, EntryOgMeta, NavButton, calculate_reading_stats, extract_preview,
+        ENTRY_CSS, EntryMarkdown, EntryMetadata, EntryOgMeta, NavButton, ReaderSettingsButton,
+        StaleBanner, ViewBeacon, calculate_reading_stats, extract_preview,
     };
     use weaver_api::sh_weaver::actor::ProfileDataViewInner;
 
     let (entry_res, entry_data) = crate::data::use_notebook_entry_by_rkey(ident, book_title, rkey);
 
     #[cfg(feature = "fullstack-server")]
-    let _entry_res = entry_res?;
+    let entry_res = entry_res?;
+    let mut entry_res = entry_res;
 
     match &*entry_data.read() {
         Some((book_entry_view, entry_record)) => {
@@ -210,7 +228,8 @@ pub fn NotebookEntryByRkey(
             } else {
                 SmolStr::new_static(crate::env::WEAVER_APP_HOST)
             };
-            let canonical_url = format_smolstr!("{}/{}/{}/e/{}", base, ident(), book_title(), rkey());
+            let canonical_url =
+                format_smolstr!("{}/{}/{}/e/{}", base, ident(), book_title(), rkey());
             let og_image_url = format_smolstr!(
                 "{}/og/{}/{}/{}.png",
                 base,
@@ -247,6 +266,13 @@ pub fn NotebookEntryByRkey(
                     }
 
                     div { class: "entry-content-main notebook-content",
+                        ViewBeacon { uri: entry_view.uri.clone() }
+                        ReaderSettingsButton {}
+                        StaleBanner {
+                            uri: entry_view.uri.clone(),
+                            cid: entry_view.cid.clone(),
+                            on_refresh: move |_| entry_res.restart(),
+                        }
                         {
                             let (word_count, reading_time_mins) = calculate_reading_stats(&entry_record.content);
                             rsx! {
@@ -8,6 +8,9 @@
 //! The [`Navbar`] component will be rendered on all pages of our app since every page is under the layout. The layout defines
 //! a common wrapper around all child routes.
 
+mod analytics;
+pub use analytics::AnalyticsPage;
+
 mod home;
 pub use home::Home;
 
@@ -27,7 +30,7 @@ mod callback;
 pub use callback::Callback;
 
 mod editor;
-pub use editor::Editor;
+pub use editor::{DemoEditor, Editor};
 
 mod drafts;
 pub use drafts::{DraftEdit, DraftsList, NewDraft, NotebookEntryEdit, StandaloneEntryEdit};
@@ -38,6 +41,21 @@ pub use entry::{NotebookEntryByRkey, StandaloneEntry, StandaloneEntryNsid};
 mod invites;
 pub use invites::InvitesPage;
 
+mod notifications;
+pub use notifications::NotificationsPage;
+
+mod profile_settings;
+pub use profile_settings::ProfileSettingsPage;
+
+mod saved;
+pub use saved::SavedItemsPage;
+
+mod search;
+pub use search::SearchPage;
+
+mod tags;
+pub use tags::{TagPage, TagsIndex};
+
 mod footer;
 pub use footer::{Footer, should_show_full_footer};
 
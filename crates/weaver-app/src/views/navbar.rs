@@ -1,9 +1,11 @@
 use crate::Route;
 use crate::auth::{AuthState, RestoreResult};
 use crate::components::button::{Button, ButtonVariant};
+use crate::components::collab::NotificationBell;
 use crate::components::login::LoginModal;
 use crate::data::{use_get_handle, use_load_handle};
 use crate::fetch::Fetcher;
+use crate::theme::ThemeToggle;
 use crate::views::{Footer, should_show_full_footer};
 use dioxus::prelude::*;
 use dioxus_primitives::toast::{ToastOptions, use_toast};
@@ -24,6 +26,8 @@ const NOTEBOOK_CARD_CSS: Asset = asset!("/assets/styling/notebook-card.css");
 pub fn Navbar() -> Element {
     // Provide navigator for programmatic navigation in shared components
     crate::components::use_main_navigator_provider();
+    // Shared registry for the Cmd/Ctrl-K command palette.
+    crate::components::use_command_registry_provider();
 
     let route = use_route::<Route>();
     tracing::trace!("Route: {:?}", route);
@@ -74,6 +78,9 @@ pub fn Navbar() -> Element {
         document::Link { rel: "stylesheet", href: NOTEBOOK_CARD_CSS }
 
         div { class: "app-shell",
+            crate::components::OfflineIndicator {}
+            crate::components::CommandPalette {}
+            crate::components::Lightbox {}
             div {
                 id: "navbar",
                 nav { class: "breadcrumbs",
@@ -270,14 +277,26 @@ pub fn Navbar() -> Element {
                             class: "nav-tool-link",
                             "Record Viewer"
                         }
-                        Link {
-                            to: Route::Editor { entry: None },
-                            class: "nav-tool-link",
-                            "Editor"
+                        // Logged-out visitors get the local-only demo editor.
+                        if auth_state.read().is_authenticated() {
+                            Link {
+                                to: Route::Editor { entry: None },
+                                class: "nav-tool-link",
+                                "Editor"
+                            }
+                        } else {
+                            Link {
+                                to: Route::DemoEditor {},
+                                class: "nav-tool-link",
+                                "Try the editor"
+                            }
                         }
                     }
                 }
 
+                NotificationBell {}
+                ThemeToggle {}
+
                 if auth_state.read().is_authenticated() {
                     if let Some(did) = &auth_state.read().did {
                         AuthButton { did: did.clone() }
@@ -321,22 +340,125 @@ fn ProfileBreadcrumb(did: Did<'static>) -> Element {
 
 #[component]
 fn AuthButton(did: Did<'static>) -> Element {
-    let auth_handle = use_get_handle(did);
+    let auth_handle = use_get_handle(did.clone());
+    let route = use_route::<Route>();
 
     let fetcher = use_context::<Fetcher>();
     let mut auth_state = use_context::<Signal<AuthState>>();
+    let mut show_menu = use_signal(|| false);
+    let mut show_login_modal = use_signal(|| false);
 
-    rsx! {
-        Button {
-            variant: ButtonVariant::Ghost,
-            onclick: move |_| {
-                let fetcher = fetcher.clone();
-                auth_state.write().clear();
-                async move {
+    // Every stored session other than the active one is a switch target.
+    let other_accounts: Vec<crate::auth::StoredAccount> = auth_state
+        .read()
+        .accounts
+        .iter()
+        .filter(|a| a.did.as_ref() != did.as_ref())
+        .cloned()
+        .collect();
+
+    let signout_fetcher = fetcher.clone();
+    let did_for_signout = did.clone();
+    let handle_signout = move |_| {
+        show_menu.set(false);
+        let fetcher = signout_fetcher.clone();
+        let did = did_for_signout.clone();
+
+        spawn(async move {
+            // Forget this account's stored session, then fall back to another
+            // account if one remains instead of logging out entirely.
+            #[cfg(target_arch = "wasm32")]
+            {
+                use jacquard::oauth::authstore::ClientAuthStore;
+
+                let session_id = auth_state
+                    .peek()
+                    .account(&did)
+                    .map(|a| a.session_id.to_string());
+                if let Some(session_id) = session_id {
+                    let _ = crate::auth::AuthStore::new()
+                        .delete_session(&did, &session_id)
+                        .await;
+                }
+            }
+            auth_state.write().remove_account(&did);
+
+            let next = auth_state.peek().accounts.first().map(|a| a.did.clone());
+            match next {
+                Some(next_did) => {
+                    crate::auth::switch_account(fetcher, auth_state, next_did).await;
+                }
+                None => {
+                    auth_state.write().clear();
                     fetcher.downgrade_to_unauthenticated().await;
+                    fetcher.clear_session_caches();
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div { class: "auth-button account-switcher",
+            Button {
+                variant: ButtonVariant::Ghost,
+                onclick: move |_| show_menu.toggle(),
+                span { class: "auth-handle", "@{auth_handle()}" }
+            }
+
+            if show_menu() {
+                div { class: "account-menu",
+                    for account in other_accounts.iter() {
+                        {
+                            let switch_fetcher = fetcher.clone();
+                            rsx! {
+                                AccountMenuItem {
+                                    key: "{account.did}",
+                                    did: account.did.clone(),
+                                    on_select: move |target: Did<'static>| {
+                                        show_menu.set(false);
+                                        let fetcher = switch_fetcher.clone();
+                                        spawn(async move {
+                                            crate::auth::switch_account(fetcher, auth_state, target).await;
+                                        });
+                                    },
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "account-menu-item",
+                        onclick: move |_| {
+                            show_menu.set(false);
+                            show_login_modal.set(true);
+                        },
+                        "Add Account"
+                    }
+                    button {
+                        class: "account-menu-item account-menu-item-danger",
+                        onclick: handle_signout,
+                        "Sign Out"
+                    }
                 }
-            },
-            span { class: "auth-handle", "@{auth_handle()}" }
+            }
+
+            LoginModal {
+                open: show_login_modal,
+                cached_route: format!("{}", route),
+            }
+        }
+    }
+}
+
+/// One switch target in the account menu; resolves the handle for display.
+#[component]
+fn AccountMenuItem(did: Did<'static>, on_select: EventHandler<Did<'static>>) -> Element {
+    let handle = use_get_handle(did.clone());
+
+    rsx! {
+        button {
+            class: "account-menu-item",
+            onclick: move |_| on_select.call(did.clone()),
+            "@{handle()}"
         }
     }
 }
@@ -1,9 +1,10 @@
 use crate::{
     Route,
     auth::AuthState,
-    components::{EntryCard, NotebookCover, NotebookCss},
     components::button::{Button, ButtonVariant},
+    components::{EntryCard, NotebookCover, NotebookCss, NotebookManagePanel, StaleBanner},
     data,
+    export::ExportNotebookButton,
 };
 use dioxus::prelude::*;
 use jacquard::{
@@ -80,11 +81,58 @@ pub fn NotebookIndex(
     let (entries_result, entries_resource) = data::use_notebook_entries(ident, book_title);
     tracing::debug!("NotebookIndex got notebook data and entries");
 
+    // Contribute this notebook's entries to the command palette while the
+    // index is mounted.
+    {
+        let navigate = crate::components::use_app_navigate();
+        crate::components::use_commands(move || {
+            let Some(entries) = entries_resource() else {
+                return Vec::new();
+            };
+            let ident = ident();
+            let book_title = book_title();
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let path = entry.entry.path.as_ref()?;
+                    let label = entry
+                        .entry
+                        .title
+                        .as_ref()
+                        .map(|t| t.as_ref().to_string())
+                        .unwrap_or_else(|| path.as_ref().to_string());
+                    let entry_path = SmolStr::new(path.as_ref());
+                    Some(crate::components::Command {
+                        id: SmolStr::new(format!("notebook-entry-{}", entry.entry.uri)),
+                        label: format!("Open entry: {label}"),
+                        section: "Entries",
+                        keywords: vec![book_title.to_string()],
+                        action: {
+                            let navigate = navigate.clone();
+                            let ident = ident.clone();
+                            let book_title = book_title.clone();
+                            std::rc::Rc::new(move || {
+                                navigate(crate::components::AppLinkTarget::Entry {
+                                    ident: ident.clone(),
+                                    book_title: book_title.clone(),
+                                    entry_path: entry_path.clone(),
+                                })
+                            })
+                        },
+                    })
+                })
+                .collect()
+        });
+    }
+
     #[cfg(feature = "fullstack-server")]
-    notebook_result?;
+    let notebook_result = notebook_result?;
 
     #[cfg(feature = "fullstack-server")]
-    entries_result?;
+    let entries_result = entries_result?;
+
+    let mut notebook_result = notebook_result;
+    let mut entries_result = entries_result;
 
     // Check ownership for "Add Entry" button
     let auth_state = use_context::<Signal<AuthState>>();
@@ -104,6 +152,14 @@ pub fn NotebookIndex(
                 let (notebook_view, _) = data;
                 let author_count = notebook_view.authors.len();
 
+                // Owners still see unlisted and scheduled entries so they can
+                // manage them; other viewers only get listed ones.
+                let listed: Vec<_> = entries
+                    .iter()
+                    .filter(|e| is_owner || crate::record_utils::record_is_listed(&e.entry.record))
+                    .cloned()
+                    .collect();
+
                 // Build OG metadata
                 let og_title = notebook_view.title
                     .as_ref()
@@ -140,7 +196,7 @@ pub fn NotebookIndex(
                         image_url: og_image_url.to_string(),
                         canonical_url: canonical_url.to_string(),
                         author_handle: og_author.to_string(),
-                        entry_count: entries.len(),
+                        entry_count: listed.len(),
                     }
                     div { class: "notebook-layout",
                         aside { class: "notebook-sidebar",
@@ -150,11 +206,38 @@ pub fn NotebookIndex(
                                 is_owner,
                                 ident: Some(ident())
                             }
+                            if is_owner {
+                                NotebookManagePanel {
+                                    notebook: notebook_view.clone(),
+                                    entries: entries.clone(),
+                                    title: book_title().to_string(),
+                                    ident: ident(),
+                                    on_changed: move |_| {
+                                        notebook_result.restart();
+                                        entries_result.restart();
+                                    },
+                                }
+                            }
+                            div { class: "notebook-export",
+                                ExportNotebookButton {
+                                    ident: ident(),
+                                    book_title: book_title(),
+                                    entries: entries.clone(),
+                                }
+                            }
                         }
 
                         main { class: "notebook-main",
+                            StaleBanner {
+                                uri: notebook_view.uri.clone(),
+                                cid: notebook_view.cid.clone(),
+                                on_refresh: move |_| {
+                                    notebook_result.restart();
+                                    entries_result.restart();
+                                },
+                            }
                             div { class: "entries-list",
-                                for entry in entries {
+                                for entry in listed {
                                     EntryCard {
                                         entry: entry.clone(),
                                         book_title: book_title(),
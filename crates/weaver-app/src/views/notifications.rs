@@ -0,0 +1,126 @@
+//! Collaboration activity notifications page.
+
+use crate::Route;
+use crate::auth::AuthState;
+use crate::components::collab::{
+    Notification, NotificationKind, fetch_notifications, mark_all_seen,
+};
+use crate::data::use_get_handle;
+use crate::fetch::Fetcher;
+use dioxus::prelude::*;
+
+const NOTIFICATIONS_CSS: Asset = asset!("/assets/styling/notifications.css");
+
+/// Page listing collaboration activity for the signed-in user.
+#[component]
+pub fn NotificationsPage() -> Element {
+    let auth_state = use_context::<Signal<AuthState>>();
+    let fetcher = use_context::<Fetcher>();
+    let navigator = use_navigator();
+
+    let is_authenticated = auth_state.read().is_authenticated();
+
+    // Notifications are per-session; there is nothing to show visitors.
+    use_effect(move || {
+        if !is_authenticated {
+            navigator.replace(Route::Home {});
+        }
+    });
+
+    let notifications = use_resource(move || {
+        let fetcher = fetcher.clone();
+        let _auth = auth_state.read().did.clone();
+        async move { fetch_notifications(&fetcher).await.ok().unwrap_or_default() }
+    });
+
+    // Visiting the page acknowledges everything currently listed, so the
+    // navbar badge clears on its next poll.
+    use_effect(move || {
+        if let Some(items) = notifications() {
+            if !items.is_empty() {
+                mark_all_seen(&items);
+            }
+        }
+    });
+
+    // Early return kept below every hook so the hook order is stable
+    // across the redirect transition.
+    if !is_authenticated {
+        return rsx! { div { "Redirecting..." } };
+    }
+
+    rsx! {
+        document::Stylesheet { href: NOTIFICATIONS_CSS }
+
+        div { class: "notifications-page",
+            header { class: "notifications-header",
+                h1 { "Notifications" }
+                p { class: "notifications-description",
+                    "Invites, acceptances, and new edits on documents you collaborate on."
+                }
+            }
+
+            match notifications() {
+                None => rsx! { p { class: "empty-state", "Loading..." } },
+                Some(items) if items.is_empty() => rsx! {
+                    p { class: "empty-state", "No collaboration activity yet" }
+                },
+                Some(items) => rsx! {
+                    div { class: "notifications-list",
+                        for notification in items {
+                            NotificationCard {
+                                key: "{notification.id}",
+                                notification: notification.clone(),
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Card showing a single notification with a link to the record viewer.
+#[component]
+fn NotificationCard(notification: Notification) -> Element {
+    let actor_handle = use_get_handle(notification.actor.clone());
+
+    let action = match notification.kind {
+        NotificationKind::InviteReceived => "invited you to collaborate on",
+        NotificationKind::InviteAccepted => "accepted your invite to",
+        NotificationKind::NewDiff => "published new edits to",
+    };
+
+    // The record viewer route splats path segments and rejoins them, so
+    // splitting the AT-URI on '/' round-trips it exactly.
+    let record_route = Route::RecordPage {
+        uri: notification
+            .resource_uri
+            .to_string()
+            .split('/')
+            .map(String::from)
+            .collect(),
+    };
+
+    rsx! {
+        div { class: "notification-card",
+            div { class: "notification-info",
+                span { class: "notification-kind", "{notification.kind.label()}" }
+                span { class: "notification-summary",
+                    "@{actor_handle()} {action} "
+                    Link {
+                        to: record_route,
+                        class: "notification-resource",
+                        "{notification.resource_uri}"
+                    }
+                }
+                if let Some(msg) = &notification.message {
+                    p { class: "notification-message", "{msg}" }
+                }
+            }
+            if let Some(created_at) = &notification.created_at {
+                span { class: "notification-date", "{created_at}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! Profile settings page for editing the `sh.weaver.actor.profile/self` record.
+
+use crate::Route;
+use crate::auth::AuthState;
+use crate::components::button::{Button, ButtonVariant};
+use crate::fetch::Fetcher;
+use dioxus::prelude::{FormData, *};
+use jacquard::bytes::Bytes;
+use jacquard::types::blob::BlobRef;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::string::{Nsid, Rkey};
+use jacquard::{CowStr, to_data};
+use mime_sniffer::MimeTypeSniffer;
+use weaver_api::com_atproto::repo::put_record::PutRecord;
+use weaver_api::sh_weaver::actor::profile::Profile as WeaverProfile;
+
+const PROFILE_SETTINGS_CSS: Asset = asset!("/assets/styling/profile-settings.css");
+
+/// Which of the two profile images an upload targets.
+#[derive(Clone, Copy, PartialEq)]
+enum ImageSlot {
+    Avatar,
+    Banner,
+}
+
+/// Page for creating or editing the signed-in user's weaver profile.
+#[component]
+pub fn ProfileSettingsPage() -> Element {
+    let auth_state = use_context::<Signal<AuthState>>();
+    let fetcher = use_context::<Fetcher>();
+    let navigator = use_navigator();
+
+    let is_authenticated = auth_state.read().is_authenticated();
+
+    // The profile record lives in the signed-in user's repo; visitors
+    // have nothing to edit here.
+    use_effect(move || {
+        if !is_authenticated {
+            navigator.replace(Route::Home {});
+        }
+    });
+
+    // Form state. Text fields are plain strings; pronouns are entered
+    // comma-separated and links one-per-line, then parsed on save.
+    let mut display_name = use_signal(String::new);
+    let mut description = use_signal(String::new);
+    let mut location = use_signal(String::new);
+    let mut pronouns_input = use_signal(String::new);
+    let mut links_input = use_signal(String::new);
+    let mut avatar = use_signal(|| None::<BlobRef<'static>>);
+    let mut banner = use_signal(|| None::<BlobRef<'static>>);
+    let mut avatar_preview = use_signal(|| None::<String>);
+    let mut banner_preview = use_signal(|| None::<String>);
+
+    let mut saving = use_signal(|| false);
+    let mut uploading = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut saved = use_signal(|| false);
+    // Guards against the loader clobbering in-progress edits when the
+    // resource re-resolves.
+    let mut form_seeded = use_signal(|| false);
+
+    let existing_profile = {
+        let fetcher = fetcher.clone();
+        use_resource(move || {
+            let fetcher = fetcher.clone();
+            let did = auth_state.read().did.clone();
+            async move {
+                let did = did?;
+                let client = fetcher.get_client();
+                let uri_str = format!("at://{}/sh.weaver.actor.profile/self", did);
+                let uri = WeaverProfile::uri(&uri_str).ok()?;
+                match client.fetch_record(&uri).await {
+                    Ok(output) => Some(output.value),
+                    Err(_) => None,
+                }
+            }
+        })
+    };
+
+    // Seed the form once the existing record (or its absence) is known.
+    use_effect(move || {
+        let Some(profile) = existing_profile() else {
+            return;
+        };
+        if *form_seeded.peek() {
+            return;
+        }
+        form_seeded.set(true);
+
+        let Some(profile) = profile else {
+            return;
+        };
+        display_name.set(
+            profile
+                .display_name
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        );
+        description.set(
+            profile
+                .description
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        );
+        location.set(
+            profile
+                .location
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        );
+        pronouns_input.set(
+            profile
+                .pronouns
+                .as_ref()
+                .map(|p| p.iter().map(|s| s.as_ref()).collect::<Vec<_>>().join(", "))
+                .unwrap_or_default(),
+        );
+        links_input.set(
+            profile
+                .links
+                .as_ref()
+                .map(|l| l.iter().map(|u| u.as_ref()).collect::<Vec<_>>().join("\n"))
+                .unwrap_or_default(),
+        );
+        avatar.set(profile.avatar.clone());
+        banner.set(profile.banner.clone());
+
+        // Preview existing images through the Bluesky CDN, the same
+        // route hydrate_profile_view uses for display.
+        if let Some(did) = &auth_state.peek().did {
+            if let Some(blob) = &profile.avatar {
+                avatar_preview.set(Some(format!(
+                    "https://cdn.bsky.app/img/avatar/plain/{}/{}@jpeg",
+                    did,
+                    blob.blob().cid()
+                )));
+            }
+            if let Some(blob) = &profile.banner {
+                banner_preview.set(Some(format!(
+                    "https://cdn.bsky.app/img/banner/plain/{}/{}@jpeg",
+                    did,
+                    blob.blob().cid()
+                )));
+            }
+        }
+    });
+
+    let upload_fetcher = fetcher.clone();
+    let handle_upload = move |evt: Event<FormData>, slot: ImageSlot| {
+        let fetcher = upload_fetcher.clone();
+        spawn(async move {
+            uploading.set(true);
+            error.set(None);
+
+            for file_data in evt.files() {
+                let bytes_data = match file_data.read_bytes().await {
+                    Ok(b) => b,
+                    Err(e) => {
+                        error.set(Some(format!("Failed to read file: {}", e)));
+                        continue;
+                    }
+                };
+                let bytes = Bytes::from(bytes_data.to_vec());
+                let mime_str = bytes
+                    .sniff_mime_type()
+                    .unwrap_or("application/octet-stream");
+                if !mime_str.starts_with("image/") {
+                    error.set(Some("Selected file is not an image".to_string()));
+                    continue;
+                }
+                let mime_type = jacquard::types::blob::MimeType::new_owned(mime_str);
+
+                // Data URL preview shows the new image immediately,
+                // before the record is saved.
+                let base64_data =
+                    base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &bytes);
+                let data_url = format!("data:{};base64,{}", mime_str, base64_data);
+
+                let client = fetcher.get_client();
+                match client.upload_blob(bytes, mime_type).await {
+                    // The profile lexicon wants a blob ref, upload_blob hands
+                    // back the bare blob.
+                    Ok(new_blob) => match slot {
+                        ImageSlot::Avatar => {
+                            avatar.set(Some(BlobRef::Blob(new_blob)));
+                            avatar_preview.set(Some(data_url));
+                        }
+                        ImageSlot::Banner => {
+                            banner.set(Some(BlobRef::Blob(new_blob)));
+                            banner_preview.set(Some(data_url));
+                        }
+                    },
+                    Err(e) => {
+                        error.set(Some(format!("Upload failed: {:?}", e)));
+                    }
+                }
+            }
+
+            uploading.set(false);
+        });
+    };
+    // The closure only captures clonable state, so each file input gets
+    // its own copy.
+    let upload_avatar = handle_upload.clone();
+    let upload_banner = handle_upload;
+
+    let save_fetcher = fetcher.clone();
+    let handle_save = move |_| {
+        let fetcher = save_fetcher.clone();
+        let existing = existing_profile().flatten();
+        spawn(async move {
+            saving.set(true);
+            error.set(None);
+            saved.set(false);
+
+            let did = match fetcher.current_did().await {
+                Some(d) => d,
+                None => {
+                    error.set(Some("Not authenticated".to_string()));
+                    saving.set(false);
+                    return;
+                }
+            };
+
+            let none_if_empty = |s: String| {
+                let trimmed = s.trim().to_string();
+                (!trimmed.is_empty()).then(|| CowStr::from(trimmed))
+            };
+
+            let pronouns: Vec<CowStr<'static>> = pronouns_input
+                .peek()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| CowStr::from(s.to_string()))
+                .collect();
+
+            let mut links = Vec::new();
+            for line in links_input.peek().lines() {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                match jacquard::types::string::Uri::new_owned(line.to_string()) {
+                    Ok(uri) => links.push(uri),
+                    Err(_) => {
+                        error.set(Some(format!("Invalid link: {}", line)));
+                        saving.set(false);
+                        return;
+                    }
+                }
+            }
+
+            // Struct update keeps fields this form doesn't edit (pinned
+            // list, external account toggles, labels) intact.
+            let profile_to_save = match existing {
+                Some(existing) => WeaverProfile {
+                    display_name: none_if_empty(display_name.peek().clone()),
+                    description: none_if_empty(description.peek().clone()),
+                    location: none_if_empty(location.peek().clone()),
+                    pronouns: (!pronouns.is_empty()).then_some(pronouns),
+                    links: (!links.is_empty()).then_some(links),
+                    avatar: avatar.peek().clone(),
+                    banner: banner.peek().clone(),
+                    ..existing
+                },
+                None => WeaverProfile::new()
+                    .maybe_display_name(none_if_empty(display_name.peek().clone()))
+                    .maybe_description(none_if_empty(description.peek().clone()))
+                    .maybe_location(none_if_empty(location.peek().clone()))
+                    .maybe_pronouns((!pronouns.is_empty()).then_some(pronouns))
+                    .maybe_links((!links.is_empty()).then_some(links))
+                    .maybe_avatar(avatar.peek().clone())
+                    .maybe_banner(banner.peek().clone())
+                    .created_at(jacquard::types::string::Datetime::now())
+                    .build(),
+            };
+
+            let profile_data = match to_data(&profile_to_save) {
+                Ok(d) => d,
+                Err(e) => {
+                    error.set(Some(format!("Failed to serialize profile: {:?}", e)));
+                    saving.set(false);
+                    return;
+                }
+            };
+
+            let request = PutRecord::new()
+                .repo(AtIdentifier::Did(did))
+                .collection(Nsid::new_static("sh.weaver.actor.profile").unwrap())
+                .rkey(Rkey::new("self").unwrap())
+                .record(profile_data)
+                .build();
+
+            let client = fetcher.get_client();
+            match client.send(request).await {
+                Ok(_) => {
+                    saved.set(true);
+                }
+                Err(e) => {
+                    error.set(Some(format!("Failed to save profile: {:?}", e)));
+                }
+            }
+            saving.set(false);
+        });
+    };
+
+    if !is_authenticated {
+        return rsx! { div { "Redirecting..." } };
+    }
+
+    rsx! {
+        document::Stylesheet { href: PROFILE_SETTINGS_CSS }
+
+        div { class: "profile-settings-page",
+            header { class: "profile-settings-header",
+                h1 { "Edit profile" }
+                p { class: "profile-settings-description",
+                    "These details are stored in your repository as your weaver profile."
+                }
+            }
+
+            div { class: "profile-settings-form",
+                div { class: "profile-field",
+                    label { r#for: "profile-display-name", "Display name" }
+                    input {
+                        id: "profile-display-name",
+                        r#type: "text",
+                        value: "{display_name}",
+                        maxlength: 64,
+                        oninput: move |evt| display_name.set(evt.value()),
+                    }
+                }
+
+                div { class: "profile-field",
+                    label { r#for: "profile-description", "Bio" }
+                    textarea {
+                        id: "profile-description",
+                        rows: 4,
+                        value: "{description}",
+                        oninput: move |evt| description.set(evt.value()),
+                    }
+                }
+
+                div { class: "profile-field",
+                    label { r#for: "profile-location", "Location" }
+                    input {
+                        id: "profile-location",
+                        r#type: "text",
+                        value: "{location}",
+                        oninput: move |evt| location.set(evt.value()),
+                    }
+                }
+
+                div { class: "profile-field",
+                    label { r#for: "profile-pronouns", "Pronouns" }
+                    input {
+                        id: "profile-pronouns",
+                        r#type: "text",
+                        placeholder: "they/them, she/her",
+                        value: "{pronouns_input}",
+                        oninput: move |evt| pronouns_input.set(evt.value()),
+                    }
+                    span { class: "profile-field-hint", "Separate multiple sets with commas." }
+                }
+
+                div { class: "profile-field",
+                    label { r#for: "profile-links", "Links" }
+                    textarea {
+                        id: "profile-links",
+                        rows: 3,
+                        placeholder: "https://example.com",
+                        value: "{links_input}",
+                        oninput: move |evt| links_input.set(evt.value()),
+                    }
+                    span { class: "profile-field-hint", "One link per line." }
+                }
+
+                div { class: "profile-field",
+                    label { "Avatar" }
+                    if let Some(url) = avatar_preview() {
+                        img { class: "profile-avatar-preview", src: "{url}", alt: "Avatar preview" }
+                    }
+                    input {
+                        r#type: "file",
+                        accept: "image/*",
+                        disabled: uploading(),
+                        onchange: move |evt| upload_avatar(evt, ImageSlot::Avatar),
+                    }
+                }
+
+                div { class: "profile-field",
+                    label { "Banner" }
+                    if let Some(url) = banner_preview() {
+                        img { class: "profile-banner-preview", src: "{url}", alt: "Banner preview" }
+                    }
+                    input {
+                        r#type: "file",
+                        accept: "image/*",
+                        disabled: uploading(),
+                        onchange: move |evt| upload_banner(evt, ImageSlot::Banner),
+                    }
+                }
+
+                if uploading() {
+                    span { class: "profile-status", "Uploading..." }
+                }
+                if let Some(err) = error() {
+                    div { class: "profile-error", "{err}" }
+                }
+                if saved() {
+                    div { class: "profile-saved", "Profile saved" }
+                }
+
+                div { class: "profile-settings-actions",
+                    Button {
+                        variant: ButtonVariant::Primary,
+                        disabled: saving() || uploading(),
+                        onclick: handle_save,
+                        if saving() { "Saving..." } else { "Save profile" }
+                    }
+                }
+            }
+        }
+    }
+}
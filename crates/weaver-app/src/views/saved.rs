@@ -0,0 +1,146 @@
+//! Saved items page listing the viewer's bookmarks.
+
+use crate::components::bookmark::{SavedBookmark, list_own_bookmarks};
+use crate::components::{AppLink, AppLinkTarget};
+use crate::fetch::Fetcher;
+use dioxus::prelude::*;
+use jacquard::IntoStatic;
+use jacquard::client::AgentSessionExt;
+use weaver_api::sh_weaver::notebook::book::Book;
+use weaver_api::sh_weaver::notebook::entry::Entry;
+
+const SAVED_CSS: Asset = asset!("/assets/styling/saved.css");
+
+/// A bookmark hydrated with enough of its subject to render a preview.
+#[derive(Clone, PartialEq)]
+struct SavedItem {
+    bookmark: SavedBookmark,
+    /// Subject title, or a fallback when the record could not be fetched.
+    title: String,
+    kind: &'static str,
+    target: Option<AppLinkTarget>,
+}
+
+/// Hydrate each bookmark's subject into a preview.
+///
+/// Subjects that fail to fetch (deleted, private PDS) still render, as a
+/// plain item without a link, so the list never silently shrinks.
+async fn load_saved_items(fetcher: &Fetcher) -> Vec<SavedItem> {
+    let bookmarks = list_own_bookmarks(fetcher).await;
+
+    let mut items = Vec::new();
+    for bookmark in bookmarks {
+        let subject_uri = &bookmark.subject.uri;
+        let ident = subject_uri.authority().clone().into_static();
+        let rkey = subject_uri.rkey().map(|r| r.0.as_str().to_string());
+
+        let (title, kind, target) = match subject_uri.collection().map(|c| c.as_str()) {
+            Some("sh.weaver.notebook.entry") => {
+                let title = match Entry::uri(subject_uri.as_str()) {
+                    Ok(uri) => fetcher
+                        .fetch_record(&uri)
+                        .await
+                        .ok()
+                        .map(|r| r.value.title.as_ref().to_string()),
+                    Err(_) => None,
+                };
+                let target = rkey.map(|rkey| AppLinkTarget::StandaloneEntry {
+                    ident: ident.clone(),
+                    rkey: rkey.into(),
+                });
+                (title, "Entry", target)
+            }
+            Some("sh.weaver.notebook.book") => {
+                let title = match Book::uri(subject_uri.as_str()) {
+                    Ok(uri) => fetcher
+                        .fetch_record(&uri)
+                        .await
+                        .ok()
+                        .and_then(|r| r.value.title.map(|t| t.as_ref().to_string())),
+                    Err(_) => None,
+                };
+                let target = title.as_ref().map(|title| AppLinkTarget::Notebook {
+                    ident: ident.clone(),
+                    book_title: title.as_str().into(),
+                });
+                (title, "Notebook", target)
+            }
+            _ => (None, "Record", None),
+        };
+
+        items.push(SavedItem {
+            title: title.unwrap_or_else(|| subject_uri.to_string()),
+            kind,
+            target,
+            bookmark,
+        });
+    }
+    items
+}
+
+/// Page listing everything the viewer has bookmarked.
+#[component]
+pub fn SavedItemsPage() -> Element {
+    let fetcher = use_context::<Fetcher>();
+
+    let items_res = use_resource(move || {
+        let fetcher = fetcher.clone();
+        async move { load_saved_items(&fetcher).await }
+    });
+
+    rsx! {
+        document::Stylesheet { href: SAVED_CSS }
+
+        div { class: "saved-page",
+            header { class: "saved-header",
+                h1 { "Saved items" }
+                p { class: "saved-description",
+                    "Entries and notebooks you bookmarked for later reading."
+                }
+            }
+
+            match &*items_res.read() {
+                Some(items) if items.is_empty() => rsx! {
+                    p { class: "saved-empty", "Nothing saved yet. Bookmark an entry to see it here." }
+                },
+                Some(items) => rsx! {
+                    ul { class: "saved-list",
+                        for item in items.iter().cloned() {
+                            li { key: "{item.bookmark.uri}", class: "saved-item",
+                                div { class: "saved-item-main",
+                                    if let Some(target) = item.target {
+                                        AppLink {
+                                            to: target,
+                                            class: Some("saved-item-title".to_string()),
+                                            "{item.title}"
+                                        }
+                                    } else {
+                                        span { class: "saved-item-title", "{item.title}" }
+                                    }
+                                    span { class: "saved-item-kind", "{item.kind}" }
+                                }
+                                if let Some(note) = item.bookmark.note {
+                                    p { class: "saved-item-note", "{note}" }
+                                }
+                                {
+                                    let saved_date = item
+                                        .bookmark
+                                        .created_at
+                                        .as_ref()
+                                        .format("%B %d, %Y")
+                                        .to_string();
+                                    rsx! {
+                                        span { class: "saved-item-date", "Saved {saved_date}" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                },
+                None => rsx! {
+                    p { class: "saved-empty", "Loading saved items..." }
+                },
+            }
+        }
+    }
+}
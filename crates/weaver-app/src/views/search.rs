@@ -0,0 +1,273 @@
+//! Full-text search across a repository's notebooks and entries.
+
+use crate::Route;
+use crate::data;
+use crate::search::{self, SearchHit};
+use dioxus::prelude::*;
+use jacquard::smol_str::SmolStr;
+use jacquard::types::ident::AtIdentifier;
+use std::collections::HashMap;
+
+const SEARCH_CSS: Asset = asset!("/assets/styling/search.css");
+
+/// Search page for a repository: a query input plus notebook and tag
+/// filters over every entry the identity has published.
+#[component]
+pub fn SearchPage(
+    ident: ReadSignal<AtIdentifier<'static>>,
+    q: ReadSignal<Option<SmolStr>>,
+) -> Element {
+    let navigator = use_navigator();
+
+    // Client-only data hooks: search is interactive, so there is nothing
+    // useful to server-render beyond the empty shell.
+    let (_entries_res, entries) = data::use_entries_for_did_client(ident);
+    let (_notebooks_res, notebooks) = data::use_notebooks_for_did_client(ident);
+
+    // The input is seeded from the route query so searches are linkable;
+    // edits filter live, and Enter writes the query back into the URL.
+    let mut query = use_signal(|| q().map(|q| q.to_string()).unwrap_or_default());
+    let mut notebook_filter = use_signal(|| None::<String>);
+    let mut tag_filter = use_signal(|| None::<String>);
+
+    // Map entry URIs to their containing notebook: the display title for
+    // ranking/filtering, and the URL path for building entry links.
+    let notebook_lookup = use_memo(move || {
+        let mut titles: HashMap<String, String> = HashMap::new();
+        let mut paths: HashMap<String, SmolStr> = HashMap::new();
+        if let Some(nbs) = notebooks.read().as_ref() {
+            for (notebook, entry_refs) in nbs {
+                let title = notebook
+                    .title
+                    .as_ref()
+                    .map(|t| t.as_ref())
+                    .unwrap_or("Untitled Notebook")
+                    .to_string();
+                let path: SmolStr = notebook
+                    .path
+                    .as_ref()
+                    .map(|p| p.as_ref().to_string())
+                    .unwrap_or_else(|| title.clone())
+                    .into();
+                for r in entry_refs {
+                    titles.insert(r.uri.as_ref().to_string(), title.clone());
+                    paths.insert(r.uri.as_ref().to_string(), path.clone());
+                }
+            }
+        }
+        (titles, paths)
+    });
+
+    // Filter options, gathered from the loaded data rather than hardcoded.
+    let notebook_options = use_memo(move || {
+        let (titles, _) = &*notebook_lookup.read();
+        let mut options: Vec<String> = titles.values().cloned().collect();
+        options.sort();
+        options.dedup();
+        options
+    });
+    let tag_options = use_memo(move || {
+        let mut options: Vec<String> = Vec::new();
+        if let Some(ents) = entries.read().as_ref() {
+            for (_, entry) in ents {
+                if let Some(tags) = entry.tags.as_ref() {
+                    options.extend(tags.iter().map(|t| t.as_ref().to_string()));
+                }
+            }
+        }
+        options.sort();
+        options.dedup();
+        options
+    });
+
+    // Each hit is paired with its notebook's URL path so the result list
+    // can link notebook entries through their canonical route.
+    let results = use_memo(move || {
+        let entries = entries.read();
+        let entries = entries.as_ref()?;
+        let (titles, paths) = &*notebook_lookup.read();
+        let hits = search::search_entries(
+            entries,
+            titles,
+            &query.read(),
+            notebook_filter.read().as_deref(),
+            tag_filter.read().as_deref(),
+        );
+        Some(
+            hits.into_iter()
+                .map(|hit| {
+                    let book_path = paths.get(hit.view.uri.as_ref()).cloned();
+                    (hit, book_path)
+                })
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    let loading = entries.read().is_none();
+    let query_empty = query.read().trim().is_empty();
+    let result_count = results.read().as_ref().map(Vec::len);
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: SEARCH_CSS }
+        div { class: "search-page",
+            h1 { class: "search-heading", "Search" }
+
+            div { class: "search-controls",
+                input {
+                    r#type: "search",
+                    class: "search-input",
+                    aria_label: "Search entries",
+                    placeholder: "Search titles, content, and tags...",
+                    value: "{query}",
+                    oninput: move |e| query.set(e.value()),
+                    onkeydown: move |evt| {
+                        use dioxus::prelude::keyboard_types::Key;
+                        if evt.key() == Key::Enter {
+                            let trimmed = query.read().trim().to_string();
+                            let q = (!trimmed.is_empty()).then(|| SmolStr::from(trimmed));
+                            navigator.replace(Route::SearchPage { ident: ident(), q });
+                        }
+                    },
+                }
+                select {
+                    class: "search-filter",
+                    aria_label: "Filter by notebook",
+                    onchange: move |e| {
+                        let value = e.value();
+                        notebook_filter.set((!value.is_empty()).then_some(value));
+                    },
+                    option { value: "", "All notebooks" }
+                    for name in notebook_options() {
+                        option {
+                            value: "{name}",
+                            selected: notebook_filter.read().as_deref() == Some(name.as_str()),
+                            "{name}"
+                        }
+                    }
+                }
+                select {
+                    class: "search-filter",
+                    aria_label: "Filter by tag",
+                    onchange: move |e| {
+                        let value = e.value();
+                        tag_filter.set((!value.is_empty()).then_some(value));
+                    },
+                    option { value: "", "All tags" }
+                    for tag in tag_options() {
+                        option {
+                            value: "{tag}",
+                            selected: tag_filter.read().as_deref() == Some(tag.as_str()),
+                            "{tag}"
+                        }
+                    }
+                }
+            }
+
+            if loading {
+                p { class: "search-status", "Loading entries..." }
+            } else if query_empty {
+                p { class: "search-status", "Type to search this repository's entries." }
+            } else if result_count == Some(0) {
+                p { class: "search-status", "No entries match this search." }
+            } else if let Some(count) = result_count {
+                p { class: "search-count",
+                    if count == 1 {
+                        "1 result"
+                    } else {
+                        "{count} results"
+                    }
+                }
+                div { class: "search-results",
+                    for (hit, book_path) in results().unwrap_or_default() {
+                        SearchResult { hit, ident: ident(), book_path }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A single search result: linked, highlighted title, notebook and tag
+/// context, and a content snippet around the first match.
+#[component]
+fn SearchResult(
+    hit: SearchHit,
+    ident: AtIdentifier<'static>,
+    #[props(default)] book_path: Option<SmolStr>,
+) -> Element {
+    let title = hit.entry.title.as_ref().to_string();
+    let rkey: Option<SmolStr> = hit.view.uri.rkey().map(|r| r.0.as_str().into());
+
+    // Notebook entries link through their notebook path, standalone
+    // entries through /e/:rkey. Entries without an rkey (which should
+    // not happen for published records) render unlinked.
+    let route = rkey.map(|rkey| match &book_path {
+        Some(path) => Route::NotebookEntryByRkey {
+            ident: ident.clone(),
+            book_title: path.clone(),
+            rkey,
+        },
+        None => Route::StandaloneEntry {
+            ident: ident.clone(),
+            rkey,
+        },
+    });
+
+    let title_segments: Vec<(String, bool)> = search::highlight_segments(&title, &hit.title_ranges)
+        .into_iter()
+        .map(|(seg, hl)| (seg.to_string(), hl))
+        .collect();
+
+    rsx! {
+        div { class: "search-hit",
+            if let Some(route) = route {
+                Link { to: route, class: "search-hit-link",
+                    h3 { class: "search-hit-title",
+                        for (seg, highlighted) in title_segments.iter() {
+                            if *highlighted {
+                                mark { "{seg}" }
+                            } else {
+                                "{seg}"
+                            }
+                        }
+                    }
+                }
+            } else {
+                h3 { class: "search-hit-title",
+                    for (seg, highlighted) in title_segments.iter() {
+                        if *highlighted {
+                            mark { "{seg}" }
+                        } else {
+                            "{seg}"
+                        }
+                    }
+                }
+            }
+
+            div { class: "search-hit-meta",
+                if let Some(ref notebook) = hit.notebook {
+                    span { class: "search-hit-notebook", "{notebook}" }
+                }
+                if let Some(tags) = hit.entry.tags.as_ref() {
+                    for tag in tags.iter() {
+                        span { class: "search-hit-tag", "{tag}" }
+                    }
+                }
+            }
+
+            if let Some(ref snippet) = hit.snippet {
+                p { class: "search-hit-snippet",
+                    if snippet.truncated_start { "…" }
+                    for (seg, highlighted) in search::highlight_segments(&snippet.text, &snippet.ranges) {
+                        if highlighted {
+                            mark { "{seg}" }
+                        } else {
+                            "{seg}"
+                        }
+                    }
+                    if snippet.truncated_end { "…" }
+                }
+            }
+        }
+    }
+}
@@ -13,11 +13,9 @@ use crate::data::{use_get_handle, use_handle};
 use crate::env::WEAVER_APP_HOST;
 use crate::fetch::Fetcher;
 use crate::host_mode::SubdomainContext;
+use crate::theme::ThemeToggle;
 use crate::views::Footer;
 
-#[cfg(feature = "fullstack-server")]
-use {dioxus::fullstack::FullstackContext, http::StatusCode};
-
 const NAVBAR_CSS: Asset = asset!("/assets/styling/navbar.css");
 const BUTTON_CSS: Asset = asset!("/assets/styling/button.css");
 const CARDS_BASE_CSS: Asset = asset!("/assets/styling/cards-base.css");
@@ -101,6 +99,8 @@ pub fn SubdomainNavbar() -> Element {
                     AuthorProfileLink { ident: ctx.owner.clone() }
                 }
 
+                ThemeToggle {}
+
                 // Auth button
                 if auth_state.read().is_authenticated() {
                     if let Some(did) = &auth_state.read().did {
@@ -138,19 +138,8 @@ pub fn SubdomainNavbar() -> Element {
 pub fn SubdomainErrorLayout() -> Element {
     rsx! {
         ErrorBoundary {
-            handle_error: move |_err: ErrorContext| {
-                #[cfg(feature = "fullstack-server")]
-                {
-                    let http_error = FullstackContext::commit_error_status(_err.error().unwrap());
-                    match http_error.status {
-                        StatusCode::NOT_FOUND => rsx! { div { "404 - Page not found" } },
-                        _ => rsx! { div { "An unknown error occurred" } },
-                    }
-                }
-                #[cfg(not(feature = "fullstack-server"))]
-                {
-                    rsx! { div { "An error occurred" } }
-                }
+            handle_error: move |err: ErrorContext| {
+                crate::components::error_page::render_error(err)
             },
             Outlet::<SubdomainRoute> {}
         }
@@ -0,0 +1,181 @@
+//! Tag browsing: a repository-wide tag index and per-tag entry listings.
+
+use crate::Route;
+use crate::components::FeedEntryCard;
+use crate::data;
+use dioxus::prelude::*;
+use jacquard::smol_str::SmolStr;
+use jacquard::types::ident::AtIdentifier;
+
+const TAGS_CSS: Asset = asset!("/assets/styling/tags.css");
+
+/// Tag index for a repository: every tag used by the identity's entries
+/// and notebooks, with usage counts, linking into per-tag pages.
+#[component]
+pub fn TagsIndex(ident: ReadSignal<AtIdentifier<'static>>) -> Element {
+    let tags = data::use_tags_for_did(ident);
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: TAGS_CSS }
+        div { class: "tags-page",
+            h1 { class: "tags-heading", "Tags" }
+
+            match tags.read().as_ref() {
+                None => rsx! {
+                    p { class: "tags-status", "Loading tags..." }
+                },
+                Some(tags) if tags.is_empty() => rsx! {
+                    p { class: "tags-status", "Nothing here is tagged yet." }
+                },
+                Some(tags) => rsx! {
+                    div { class: "tags-list",
+                        for summary in tags.iter() {
+                            Link {
+                                to: Route::TagPage {
+                                    ident: ident(),
+                                    tag: summary.tag.clone().into(),
+                                },
+                                class: "tag-chip",
+                                span { class: "tag-chip-name", "{summary.tag}" }
+                                span { class: "tag-chip-count",
+                                    "{summary.entry_count + summary.notebook_count}"
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Everything in a repository carrying one tag: matching notebooks
+/// first, then matching entries as feed cards.
+#[component]
+pub fn TagPage(ident: ReadSignal<AtIdentifier<'static>>, tag: ReadSignal<SmolStr>) -> Element {
+    let (_entries_res, entries) = data::use_entries_for_did_client(ident);
+    let (_notebooks_res, notebooks) = data::use_notebooks_for_did_client(ident);
+
+    // Tags match case-insensitively, consistent with the tag index.
+    let tagged_entries = use_memo(move || {
+        let entries = entries.read();
+        let entries = entries.as_ref()?;
+        let tag = tag.read();
+        Some(
+            entries
+                .iter()
+                .filter(|(_, entry)| {
+                    entry
+                        .tags
+                        .iter()
+                        .flatten()
+                        .any(|t| t.eq_ignore_ascii_case(tag.as_str()))
+                })
+                .cloned()
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    let tagged_notebooks = use_memo(move || {
+        let notebooks = notebooks.read();
+        let notebooks = notebooks.as_ref()?;
+        let tag = tag.read();
+        Some(
+            notebooks
+                .iter()
+                .filter(|(notebook, _)| {
+                    notebook
+                        .tags
+                        .iter()
+                        .flatten()
+                        .any(|t| t.eq_ignore_ascii_case(tag.as_str()))
+                })
+                .map(|(notebook, _)| notebook.clone())
+                .collect::<Vec<_>>(),
+        )
+    });
+
+    let loading = entries.read().is_none() || notebooks.read().is_none();
+    let empty = tagged_entries.read().as_ref().is_some_and(Vec::is_empty)
+        && tagged_notebooks.read().as_ref().is_some_and(Vec::is_empty);
+
+    rsx! {
+        document::Link { rel: "stylesheet", href: TAGS_CSS }
+        div { class: "tags-page",
+            h1 { class: "tags-heading",
+                "Tagged "
+                span { class: "tags-heading-tag", "{tag}" }
+            }
+            Link {
+                to: Route::TagsIndex { ident: ident() },
+                class: "tags-back-link",
+                "All tags"
+            }
+
+            if loading {
+                p { class: "tags-status", "Loading..." }
+            } else if empty {
+                p { class: "tags-status", "Nothing carries this tag." }
+            } else {
+                if let Some(nbs) = tagged_notebooks() {
+                    if !nbs.is_empty() {
+                        h2 { class: "tags-section-heading", "Notebooks" }
+                        div { class: "tags-notebooks",
+                            for notebook in nbs {
+                                TaggedNotebookRow { notebook, ident: ident() }
+                            }
+                        }
+                    }
+                }
+                if let Some(ents) = tagged_entries() {
+                    if !ents.is_empty() {
+                        h2 { class: "tags-section-heading", "Entries" }
+                        div { class: "tags-entries",
+                            for (entry_view, entry) in ents {
+                                FeedEntryCard { entry_view, entry, show_author: false }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compact notebook row on a tag page, linking to the notebook index.
+#[component]
+fn TaggedNotebookRow(
+    notebook: weaver_api::sh_weaver::notebook::NotebookView<'static>,
+    ident: AtIdentifier<'static>,
+) -> Element {
+    let title = notebook
+        .title
+        .as_ref()
+        .map(|t| t.as_ref())
+        .unwrap_or("Untitled Notebook");
+    // Route through the notebook's URL path, falling back to its title,
+    // the same way the profile's notebook cards do.
+    let book_title: SmolStr = notebook
+        .path
+        .as_ref()
+        .map(|p| p.as_ref().to_string())
+        .unwrap_or_else(|| title.to_string())
+        .into();
+
+    rsx! {
+        Link {
+            to: Route::NotebookIndex { ident, book_title },
+            class: "tagged-notebook-row",
+            span { class: "tagged-notebook-title", "{title}" }
+            if let Some(count) = notebook.entry_count {
+                span { class: "tagged-notebook-count",
+                    if count == 1 {
+                        "1 entry"
+                    } else {
+                        "{count} entries"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Cross-posting entry excerpts to Bluesky on publish.
+//!
+//! An optional publish step that announces a freshly published entry on
+//! Bluesky: the post text is built from the entry's first paragraphs, with an
+//! external link embed pointing back to the entry on weaver.sh. The post is
+//! previewed (and can be edited or skipped) before anything is sent.
+
+use std::io::{BufRead, Write};
+
+use jacquard::client::{Agent, AgentSessionExt};
+use jacquard::identity::IdentityResolver;
+use jacquard::prelude::*;
+use jacquard::types::string::{AtUri, Datetime};
+use miette::{IntoDiagnostic, Result};
+use weaver_api::app_bsky::embed::external::{External, ExternalRecord};
+use weaver_api::app_bsky::feed::post::{Post, PostEmbed};
+
+/// Bluesky's post length limit in graphemes; we count chars, which is
+/// conservative enough for excerpt purposes.
+const MAX_POST_CHARS: usize = 300;
+
+/// Build a plain-text excerpt from entry markdown.
+///
+/// Takes the first paragraphs (skipping headings, code fences, and embeds),
+/// strips inline markdown syntax, and truncates to `max_chars` on a word
+/// boundary with an ellipsis.
+pub fn excerpt_from_markdown(markdown: &str, max_chars: usize) -> String {
+    let mut excerpt = String::new();
+    let mut in_code_fence = false;
+
+    for block in markdown.split("\n\n") {
+        let block = block.trim();
+        if block.is_empty() {
+            continue;
+        }
+        // Track fences that happen to contain blank lines.
+        if block.starts_with("```") || block.ends_with("```") {
+            in_code_fence = !block.starts_with("```") || !block.ends_with("```");
+            continue;
+        }
+        if in_code_fence {
+            if block.contains("```") {
+                in_code_fence = false;
+            }
+            continue;
+        }
+        // Skip non-prose blocks.
+        if block.starts_with('#')
+            || block.starts_with("![")
+            || block.starts_with("---")
+            || block.starts_with('>')
+            || block.starts_with("- ")
+            || block.starts_with("* ")
+        {
+            continue;
+        }
+
+        if !excerpt.is_empty() {
+            excerpt.push_str("\n\n");
+        }
+        excerpt.push_str(&strip_inline_markdown(block));
+
+        if excerpt.chars().count() >= max_chars {
+            break;
+        }
+    }
+
+    truncate_on_word(&excerpt, max_chars)
+}
+
+/// Strip inline markdown syntax, keeping link/emphasis text.
+fn strip_inline_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '*' | '_' | '`' | '~' => {}
+            // `[label](target)` - keep the label, drop the target.
+            // `[[wikilink]]` - keep the page name.
+            '[' => {}
+            ']' => {
+                // Drop a following `(target)`.
+                if chars.peek() == Some(&'(') {
+                    for c in chars.by_ref() {
+                        if c == ')' {
+                            break;
+                        }
+                    }
+                }
+            }
+            '\n' => out.push(' '),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Truncate to `max_chars` characters on a word boundary, appending `…`.
+fn truncate_on_word(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let budget = max_chars.saturating_sub(1);
+    let cut: String = text.chars().take(budget).collect();
+    // If the cut happens to land on a word boundary, keep the whole cut.
+    let at_boundary = text.chars().nth(budget).is_some_and(char::is_whitespace);
+    let trimmed = match cut.rfind(char::is_whitespace) {
+        // Back up to the last full word unless it costs too much text.
+        Some(pos) if !at_boundary && pos > budget / 2 => &cut[..pos],
+        _ => cut.as_str(),
+    };
+    format!("{}…", trimmed.trim_end())
+}
+
+/// Entry web URL for the link embed (mirrors the renderer's URL mapping).
+fn entry_web_url(entry_uri: &AtUri<'_>) -> String {
+    let authority = entry_uri.authority().as_ref();
+    match entry_uri.rkey() {
+        Some(rkey) => format!("https://alpha.weaver.sh/{}/e/{}", authority, rkey.0.as_ref()),
+        None => format!("https://alpha.weaver.sh/{}", authority),
+    }
+}
+
+/// Preview the post in the terminal and let the user confirm, edit, or skip.
+///
+/// Returns the final post text, or None if the user skipped.
+fn confirm_or_edit(text: &str, url: &str) -> Result<Option<String>> {
+    let stdin = std::io::stdin();
+    let mut current = text.to_string();
+
+    loop {
+        println!("\n─── Bluesky post preview ───");
+        println!("{}", current);
+        println!("🔗 {}", url);
+        println!("────────────────────────────");
+        print!("Post to Bluesky? [y]es / [e]dit / [n]o: ");
+        std::io::stdout().flush().into_diagnostic()?;
+
+        let mut line = String::new();
+        stdin.lock().read_line(&mut line).into_diagnostic()?;
+        match line.trim().to_lowercase().as_str() {
+            "y" | "yes" => return Ok(Some(current)),
+            "n" | "no" | "" => return Ok(None),
+            "e" | "edit" => {
+                println!("Enter replacement text (end with an empty line):");
+                let mut replacement = String::new();
+                for input in stdin.lock().lines() {
+                    let input = input.into_diagnostic()?;
+                    if input.is_empty() {
+                        break;
+                    }
+                    if !replacement.is_empty() {
+                        replacement.push('\n');
+                    }
+                    replacement.push_str(&input);
+                }
+                if !replacement.is_empty() {
+                    current = truncate_on_word(&replacement, MAX_POST_CHARS);
+                }
+            }
+            _ => println!("Please answer y, e, or n."),
+        }
+    }
+}
+
+/// Announce a published entry on Bluesky.
+///
+/// Builds an excerpt post with a link embed back to the entry, previews it,
+/// and creates the post record after confirmation. Skipping is not an error.
+pub async fn crosspost_entry<A>(
+    agent: &Agent<A>,
+    entry_title: &str,
+    entry_uri: &AtUri<'_>,
+    markdown: &str,
+) -> Result<()>
+where
+    A: jacquard::client::AgentSession + IdentityResolver,
+{
+    let excerpt = excerpt_from_markdown(markdown, MAX_POST_CHARS);
+    let excerpt = if excerpt.is_empty() {
+        format!("New entry: {}", entry_title)
+    } else {
+        excerpt
+    };
+    let url = entry_web_url(entry_uri);
+
+    let Some(text) = confirm_or_edit(&excerpt, &url)? else {
+        println!("  Skipped cross-post");
+        return Ok(());
+    };
+
+    let external = External::new()
+        .uri(jacquard::types::string::Uri::new_owned(url.clone())?)
+        .title(entry_title)
+        .description(text.as_str())
+        .build();
+    let post = Post::new()
+        .text(text.as_str())
+        .created_at(Datetime::now())
+        .embed(PostEmbed::External(Box::new(
+            ExternalRecord::new().external(external).build(),
+        )))
+        .build();
+
+    let record = agent
+        .create_record(post, None)
+        .await
+        .map_err(|e| miette::miette!("Failed to create Bluesky post: {}", e))?;
+
+    println!("  ✓ Cross-posted: {}", record.uri.as_ref());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_excerpt_skips_headings_and_code() {
+        let md = "# Title\n\n```rust\nlet x = 1;\n```\n\nFirst paragraph here.\n\nSecond one.";
+        let excerpt = excerpt_from_markdown(md, 300);
+        assert_eq!(excerpt, "First paragraph here.\n\nSecond one.");
+    }
+
+    #[test]
+    fn test_excerpt_strips_inline_markdown() {
+        let md = "Some **bold** and a [link](https://example.com) plus `code`.";
+        assert_eq!(
+            excerpt_from_markdown(md, 300),
+            "Some bold and a link plus code."
+        );
+    }
+
+    #[test]
+    fn test_excerpt_truncates_on_word_boundary() {
+        let md = "one two three four five";
+        let excerpt = excerpt_from_markdown(md, 14);
+        assert_eq!(excerpt, "one two three…");
+    }
+
+    #[test]
+    fn test_short_text_not_truncated() {
+        assert_eq!(truncate_on_word("short", 300), "short");
+    }
+}
@@ -0,0 +1,488 @@
+//! Incremental deployment of rendered static sites.
+//!
+//! Deploys the output of the static renderer to a hosting target, uploading
+//! only files whose content hash changed since the last deploy. Change
+//! detection works by diffing a blake3 manifest of the local output against
+//! the manifest stored with the previous deploy:
+//!
+//! - **s3**: manifest lives in the bucket; per-file uploads via the `aws` CLI
+//!   with cache-control headers.
+//! - **github-pages**: manifest lives in the publishing branch; changed files
+//!   are committed on top of a shallow clone and pushed.
+//! - **netlify**: deploys are atomic and deduplicated server-side, so the
+//!   manifest is cached locally and only used to skip no-op deploys.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use clap::ValueEnum;
+use miette::{IntoDiagnostic, Result, miette};
+use serde::{Deserialize, Serialize};
+use weaver_common::blake3;
+
+/// Name of the manifest file stored alongside the deployed site.
+const MANIFEST_KEY: &str = ".weaver-deploy-manifest.json";
+
+/// Supported deployment targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DeployTargetKind {
+    /// Amazon S3 (or S3-compatible) bucket, via the `aws` CLI.
+    S3,
+    /// GitHub Pages publishing branch, via `git`.
+    GithubPages,
+    /// Netlify site, via the `netlify` CLI.
+    Netlify,
+}
+
+/// Options collected from the `deploy` subcommand.
+#[derive(Debug, Clone)]
+pub struct DeployOptions {
+    pub bucket: Option<String>,
+    pub repo: Option<String>,
+    pub branch: String,
+    pub site: Option<String>,
+    pub dry_run: bool,
+}
+
+/// Content manifest of a deployed site: relative path -> blake3 hex digest.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeployManifest {
+    pub files: BTreeMap<String, String>,
+}
+
+/// Files that need to change on the remote to match the local output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeployPlan {
+    /// New or changed files, relative to the site root.
+    pub upload: Vec<String>,
+    /// Files present remotely but no longer in the local output.
+    pub delete: Vec<String>,
+}
+
+impl DeployPlan {
+    pub fn is_empty(&self) -> bool {
+        self.upload.is_empty() && self.delete.is_empty()
+    }
+}
+
+/// Deploy a rendered site directory to the given target.
+pub async fn deploy_site(dir: PathBuf, target: DeployTargetKind, opts: DeployOptions) -> Result<()> {
+    if !dir.is_dir() {
+        return Err(miette!(
+            "site directory ({}) does not exist - render the notebook first",
+            dir.display()
+        ));
+    }
+
+    let local = build_local_manifest(&dir)?;
+    println!("Hashed {} files in {}", local.files.len(), dir.display());
+
+    match target {
+        DeployTargetKind::S3 => {
+            let bucket = opts
+                .bucket
+                .as_deref()
+                .ok_or_else(|| miette!("--bucket is required for the s3 target"))?;
+            deploy_s3(&dir, bucket, &local, opts.dry_run).await
+        }
+        DeployTargetKind::GithubPages => {
+            let repo = opts
+                .repo
+                .as_deref()
+                .ok_or_else(|| miette!("--repo is required for the github-pages target"))?;
+            deploy_github_pages(&dir, repo, &opts.branch, &local, opts.dry_run).await
+        }
+        DeployTargetKind::Netlify => deploy_netlify(&dir, opts.site.as_deref(), &local, opts.dry_run).await,
+    }
+}
+
+/// Hash every file under `dir` into a manifest, skipping the manifest itself.
+fn build_local_manifest(dir: &Path) -> Result<DeployManifest> {
+    let mut manifest = DeployManifest::default();
+    let mut stack = vec![dir.to_path_buf()];
+
+    while let Some(current) = stack.pop() {
+        for entry in std::fs::read_dir(&current).into_diagnostic()? {
+            let entry = entry.into_diagnostic()?;
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let relative = path
+                .strip_prefix(dir)
+                .expect("walked file should be nested under the site root");
+            let key = relative.to_string_lossy().replace('\\', "/");
+            if key == MANIFEST_KEY {
+                continue;
+            }
+            let contents = std::fs::read(&path).into_diagnostic()?;
+            manifest
+                .files
+                .insert(key, blake3::hash(&contents).to_hex().to_string());
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Diff two manifests into the uploads and deletions needed on the remote.
+fn diff_manifests(remote: &DeployManifest, local: &DeployManifest) -> DeployPlan {
+    let mut plan = DeployPlan::default();
+
+    for (key, hash) in &local.files {
+        if remote.files.get(key) != Some(hash) {
+            plan.upload.push(key.clone());
+        }
+    }
+    for key in remote.files.keys() {
+        if !local.files.contains_key(key) {
+            plan.delete.push(key.clone());
+        }
+    }
+
+    plan
+}
+
+/// Cache-control header for a deployed file.
+///
+/// HTML (and the manifest) must revalidate so navigation picks up new
+/// deploys; everything else is fingerprinted by the manifest diff and can be
+/// cached for a day.
+fn cache_control_for(key: &str) -> &'static str {
+    let ext = Path::new(key)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("");
+    match ext {
+        "html" | "json" | "xml" | "txt" => "no-cache",
+        _ => "public, max-age=86400",
+    }
+}
+
+fn print_plan(plan: &DeployPlan) {
+    for key in &plan.upload {
+        println!("  upload {}", key);
+    }
+    for key in &plan.delete {
+        println!("  delete {}", key);
+    }
+}
+
+/// Run an external command, failing with its stderr on non-zero exit.
+async fn run_command(mut cmd: tokio::process::Command, what: &str) -> Result<Vec<u8>> {
+    let output = cmd
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| miette!("failed to run {}: {}", what, e))?;
+    if !output.status.success() {
+        return Err(miette!(
+            "{} failed ({}): {}",
+            what,
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+// === s3 ===
+
+async fn deploy_s3(dir: &Path, bucket: &str, local: &DeployManifest, dry_run: bool) -> Result<()> {
+    let bucket = bucket.trim_start_matches("s3://").trim_end_matches('/');
+
+    // Fetch the remote manifest; a missing key means a first deploy.
+    let remote = {
+        let mut cmd = tokio::process::Command::new("aws");
+        cmd.args(["s3", "cp"])
+            .arg(format!("s3://{}/{}", bucket, MANIFEST_KEY))
+            .arg("-");
+        match run_command(cmd, "aws s3 cp (manifest)").await {
+            Ok(bytes) => serde_json::from_slice(&bytes).into_diagnostic()?,
+            Err(_) => DeployManifest::default(),
+        }
+    };
+
+    let plan = diff_manifests(&remote, local);
+    if plan.is_empty() {
+        println!("Already up to date");
+        return Ok(());
+    }
+    print_plan(&plan);
+    if dry_run {
+        return Ok(());
+    }
+
+    for key in &plan.upload {
+        let mut cmd = tokio::process::Command::new("aws");
+        cmd.args(["s3", "cp"])
+            .arg(dir.join(key))
+            .arg(format!("s3://{}/{}", bucket, key))
+            .args(["--cache-control", cache_control_for(key), "--only-show-errors"]);
+        run_command(cmd, "aws s3 cp").await?;
+    }
+    for key in &plan.delete {
+        let mut cmd = tokio::process::Command::new("aws");
+        cmd.args(["s3", "rm"])
+            .arg(format!("s3://{}/{}", bucket, key))
+            .arg("--only-show-errors");
+        run_command(cmd, "aws s3 rm").await?;
+    }
+
+    // Upload the new manifest last so an interrupted deploy re-uploads.
+    let manifest_json = serde_json::to_vec_pretty(local).into_diagnostic()?;
+    let manifest_path = dir.join(MANIFEST_KEY);
+    tokio::fs::write(&manifest_path, &manifest_json)
+        .await
+        .into_diagnostic()?;
+    let mut cmd = tokio::process::Command::new("aws");
+    cmd.args(["s3", "cp"])
+        .arg(&manifest_path)
+        .arg(format!("s3://{}/{}", bucket, MANIFEST_KEY))
+        .args(["--cache-control", "no-cache", "--only-show-errors"]);
+    run_command(cmd, "aws s3 cp (manifest)").await?;
+
+    println!(
+        "✓ Deployed {} files to s3://{} ({} removed)",
+        plan.upload.len(),
+        bucket,
+        plan.delete.len()
+    );
+    Ok(())
+}
+
+// === github-pages ===
+
+async fn deploy_github_pages(
+    dir: &Path,
+    repo: &str,
+    branch: &str,
+    local: &DeployManifest,
+    dry_run: bool,
+) -> Result<()> {
+    let remote_url = github_remote_url(repo);
+    let checkout = std::env::temp_dir().join(format!("weaver-deploy-{}", std::process::id()));
+
+    // Shallow-clone the publishing branch; if it doesn't exist yet, start an
+    // empty history for it.
+    let mut clone = tokio::process::Command::new("git");
+    clone
+        .args(["clone", "--depth", "1", "--branch", branch, &remote_url])
+        .arg(&checkout);
+    let fresh_branch = run_command(clone, "git clone").await.is_err();
+    if fresh_branch {
+        tokio::fs::create_dir_all(&checkout).await.into_diagnostic()?;
+        let mut init = tokio::process::Command::new("git");
+        init.current_dir(&checkout)
+            .args(["init", "--initial-branch", branch]);
+        run_command(init, "git init").await?;
+        let mut add_remote = tokio::process::Command::new("git");
+        add_remote
+            .current_dir(&checkout)
+            .args(["remote", "add", "origin", &remote_url]);
+        run_command(add_remote, "git remote add").await?;
+    }
+
+    let remote = match std::fs::read(checkout.join(MANIFEST_KEY)) {
+        Ok(bytes) => serde_json::from_slice(&bytes).into_diagnostic()?,
+        Err(_) => DeployManifest::default(),
+    };
+
+    let plan = diff_manifests(&remote, local);
+    let result = if plan.is_empty() {
+        println!("Already up to date");
+        Ok(())
+    } else {
+        print_plan(&plan);
+        if dry_run {
+            Ok(())
+        } else {
+            push_github_pages(dir, &checkout, branch, local, &plan).await
+        }
+    };
+
+    // Best-effort cleanup of the temporary checkout.
+    let _ = tokio::fs::remove_dir_all(&checkout).await;
+    result
+}
+
+async fn push_github_pages(
+    dir: &Path,
+    checkout: &Path,
+    branch: &str,
+    local: &DeployManifest,
+    plan: &DeployPlan,
+) -> Result<()> {
+    for key in &plan.upload {
+        let dest = checkout.join(key);
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent).await.into_diagnostic()?;
+        }
+        tokio::fs::copy(dir.join(key), dest).await.into_diagnostic()?;
+    }
+    for key in &plan.delete {
+        let _ = tokio::fs::remove_file(checkout.join(key)).await;
+    }
+    let manifest_json = serde_json::to_vec_pretty(local).into_diagnostic()?;
+    tokio::fs::write(checkout.join(MANIFEST_KEY), manifest_json)
+        .await
+        .into_diagnostic()?;
+
+    let mut add = tokio::process::Command::new("git");
+    add.current_dir(checkout).args(["add", "-A"]);
+    run_command(add, "git add").await?;
+
+    let message = format!(
+        "deploy: {} changed, {} removed",
+        plan.upload.len(),
+        plan.delete.len()
+    );
+    let mut commit = tokio::process::Command::new("git");
+    commit.current_dir(checkout).args(["commit", "-m", &message]);
+    run_command(commit, "git commit").await?;
+
+    let mut push = tokio::process::Command::new("git");
+    push.current_dir(checkout)
+        .args(["push", "origin"])
+        .arg(format!("HEAD:{}", branch));
+    run_command(push, "git push").await?;
+
+    println!(
+        "✓ Deployed {} files to {} ({} removed)",
+        plan.upload.len(),
+        branch,
+        plan.delete.len()
+    );
+    Ok(())
+}
+
+/// Accept either a full git URL or an `owner/repo` shorthand.
+fn github_remote_url(repo: &str) -> String {
+    if repo.contains("://") || repo.starts_with("git@") {
+        repo.to_string()
+    } else {
+        format!("https://github.com/{}.git", repo.trim_end_matches(".git"))
+    }
+}
+
+// === netlify ===
+
+async fn deploy_netlify(
+    dir: &Path,
+    site: Option<&str>,
+    local: &DeployManifest,
+    dry_run: bool,
+) -> Result<()> {
+    // Netlify deploys are atomic and deduplicated by digest server-side, so
+    // the manifest only short-circuits no-op deploys via a local cache.
+    let cache_path = netlify_cache_path(site);
+    let remote = cache_path
+        .as_ref()
+        .and_then(|p| std::fs::read(p).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+        .unwrap_or_default();
+
+    let plan = diff_manifests(&remote, local);
+    if plan.is_empty() {
+        println!("Already up to date");
+        return Ok(());
+    }
+    print_plan(&plan);
+    if dry_run {
+        return Ok(());
+    }
+
+    let mut cmd = tokio::process::Command::new("netlify");
+    cmd.args(["deploy", "--prod", "--dir"]).arg(dir);
+    if let Some(site) = site {
+        cmd.args(["--site", site]);
+    }
+    run_command(cmd, "netlify deploy").await?;
+
+    if let Some(cache_path) = cache_path {
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent).into_diagnostic()?;
+        }
+        let manifest_json = serde_json::to_vec_pretty(local).into_diagnostic()?;
+        let _ = std::fs::write(cache_path, manifest_json);
+    }
+
+    println!("✓ Deployed {} changed files to Netlify", plan.upload.len());
+    Ok(())
+}
+
+/// Local cache location for the last-deployed Netlify manifest.
+fn netlify_cache_path(site: Option<&str>) -> Option<PathBuf> {
+    let site = site.unwrap_or("default");
+    dirs::config_dir().map(|d| d.join("weaver").join("deploy").join(format!("netlify-{}.json", site)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(entries: &[(&str, &str)]) -> DeployManifest {
+        DeployManifest {
+            files: entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff_detects_changes() {
+        let remote = manifest(&[("index.html", "aaa"), ("css/base.css", "bbb"), ("old.html", "ccc")]);
+        let local = manifest(&[("index.html", "aaa"), ("css/base.css", "ddd"), ("new.html", "eee")]);
+
+        let plan = diff_manifests(&remote, &local);
+        assert_eq!(plan.upload, vec!["css/base.css", "new.html"]);
+        assert_eq!(plan.delete, vec!["old.html"]);
+    }
+
+    #[test]
+    fn test_diff_empty_when_identical() {
+        let m = manifest(&[("index.html", "aaa")]);
+        assert!(diff_manifests(&m, &m).is_empty());
+    }
+
+    #[test]
+    fn test_first_deploy_uploads_everything() {
+        let local = manifest(&[("a.html", "1"), ("b.css", "2")]);
+        let plan = diff_manifests(&DeployManifest::default(), &local);
+        assert_eq!(plan.upload, vec!["a.html", "b.css"]);
+        assert!(plan.delete.is_empty());
+    }
+
+    #[test]
+    fn test_cache_control() {
+        assert_eq!(cache_control_for("entry/post.html"), "no-cache");
+        assert_eq!(cache_control_for("feed.xml"), "no-cache");
+        assert_eq!(
+            cache_control_for("css/base.css"),
+            "public, max-age=86400"
+        );
+        assert_eq!(
+            cache_control_for("images/photo.png"),
+            "public, max-age=86400"
+        );
+    }
+
+    #[test]
+    fn test_github_remote_url() {
+        assert_eq!(
+            github_remote_url("alice/notebook"),
+            "https://github.com/alice/notebook.git"
+        );
+        assert_eq!(
+            github_remote_url("git@github.com:alice/notebook.git"),
+            "git@github.com:alice/notebook.git"
+        );
+        assert_eq!(
+            github_remote_url("https://github.com/alice/notebook.git"),
+            "https://github.com/alice/notebook.git"
+        );
+    }
+}
@@ -0,0 +1,368 @@
+//! `weaver doctor`: diagnose auth, PDS, and vault health in one pass.
+//!
+//! Runs a fixed battery of checks and prints one line per check with a
+//! fix suggestion where there is an obvious one. Account checks (session,
+//! PDS reachability, record collections) are skipped when no auth store
+//! exists; vault checks run whenever a vault path is given.
+
+use jacquard::client::{Agent, FileAuthStore};
+use jacquard::identity::JacquardResolver;
+use jacquard::oauth::client::OAuthSession;
+use jacquard::prelude::*;
+use miette::{IntoDiagnostic, Result};
+use std::path::{Path, PathBuf};
+use weaver_api::com_atproto::repo::describe_repo::DescribeRepo;
+use weaver_renderer::metadata::parse_metadata;
+use weaver_renderer::utils::lookup_filename_in_vault;
+use weaver_renderer::walker::{WalkOptions, vault_contents};
+use yaml_rust2::Yaml;
+
+/// The agent type the CLI works with (OAuth session from the auth store).
+type CliAgent = Agent<OAuthSession<JacquardResolver, FileAuthStore>>;
+
+/// Images above this size get flagged; PDS blob limits and page weight
+/// both start to hurt well before the protocol maximum.
+const IMAGE_WARN_BYTES: u64 = 1_000_000;
+
+/// Collections a publishing account is expected to hold records in.
+const CORE_COLLECTIONS: &[&str] = &["sh.weaver.notebook.book", "sh.weaver.notebook.entry"];
+
+/// Outcome of a single check.
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// One line of the doctor report.
+struct Check {
+    name: &'static str,
+    status: CheckStatus,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run all checks and print the report.
+///
+/// Returns an error (non-zero exit) only when at least one check failed;
+/// warnings alone exit cleanly so doctor can run in scripts.
+pub async fn doctor(vault: Option<PathBuf>, store_path: PathBuf) -> Result<()> {
+    let mut checks = Vec::new();
+
+    check_account(&store_path, &mut checks).await;
+
+    if let Some(vault) = &vault {
+        check_vault(vault, &mut checks)?;
+    }
+
+    let mut failed = 0usize;
+    for check in &checks {
+        let symbol = match check.status {
+            CheckStatus::Pass => "✓",
+            CheckStatus::Warn => "⚠",
+            CheckStatus::Fail => {
+                failed += 1;
+                "✗"
+            }
+        };
+        println!("{} {}: {}", symbol, check.name, check.detail);
+        if let Some(fix) = &check.fix {
+            println!("    → {}", fix);
+        }
+    }
+
+    if failed > 0 {
+        return Err(miette::miette!(
+            "{} of {} checks failed",
+            failed,
+            checks.len()
+        ));
+    }
+    println!("✓ All {} checks passed", checks.len());
+    Ok(())
+}
+
+/// Session validity, PDS reachability, and record collection presence.
+async fn check_account(store_path: &PathBuf, checks: &mut Vec<Check>) {
+    if !store_path.exists() {
+        checks.push(Check::fail(
+            "auth store",
+            format!("not found at {}", store_path.display()),
+            "run 'weaver auth <handle>' to authenticate",
+        ));
+        return;
+    }
+
+    let Some(session) = crate::try_load_session(store_path).await else {
+        checks.push(Check::fail(
+            "oauth session",
+            "stored session could not be restored (expired or revoked)",
+            "run 'weaver auth <handle>' to re-authenticate",
+        ));
+        return;
+    };
+    let agent = Agent::new(session);
+
+    let Some((did, _session_id)) = agent.info().await else {
+        checks.push(Check::fail(
+            "oauth session",
+            "session restored but carries no account info",
+            "run 'weaver auth <handle>' to re-authenticate",
+        ));
+        return;
+    };
+    checks.push(Check::pass(
+        "oauth session",
+        format!("valid for {}", did.as_str()),
+    ));
+
+    // One describeRepo round trip answers both reachability and which
+    // collections the repo actually holds.
+    match describe_repo(&agent).await {
+        Ok(collections) => {
+            checks.push(Check::pass("pds", "reachable"));
+            for collection in CORE_COLLECTIONS {
+                if collections.iter().any(|c| c == collection) {
+                    checks.push(Check::pass(collection, "records present"));
+                } else {
+                    checks.push(Check::warn(
+                        collection,
+                        "no records in your repo",
+                        "publish a notebook with 'weaver publish <dir> <title>'",
+                    ));
+                }
+            }
+        }
+        Err(e) => {
+            checks.push(Check::fail(
+                "pds",
+                format!("unreachable: {}", e),
+                "check your network; if the PDS moved, re-run 'weaver auth'",
+            ));
+        }
+    }
+}
+
+/// Fetch the NSIDs of every collection the account's repo contains.
+async fn describe_repo(agent: &CliAgent) -> Result<Vec<String>> {
+    let (did, _session_id) = agent
+        .info()
+        .await
+        .ok_or_else(|| miette::miette!("No session info available"))?;
+    let resp = agent
+        .send(DescribeRepo::new().repo(did.clone()).build())
+        .await
+        .map_err(|e| miette::miette!("{}", e))?;
+    let output = resp.parse().map_err(|e| miette::miette!("{}", e))?;
+    Ok(output
+        .collections
+        .iter()
+        .map(|nsid| nsid.to_string())
+        .collect())
+}
+
+/// Broken links, oversized images, and frontmatter problems in the vault.
+fn check_vault(root: &Path, checks: &mut Vec<Check>) -> Result<()> {
+    if !root.exists() {
+        checks.push(Check::fail(
+            "vault",
+            format!("not found at {}", root.display()),
+            "pass the notebook directory: weaver doctor <dir>",
+        ));
+        return Ok(());
+    }
+
+    let contents =
+        vault_contents(root, WalkOptions::new()).map_err(|e| miette::miette!("{}", e))?;
+    let md_files: Vec<&PathBuf> = contents
+        .iter()
+        .filter(|p| p.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    checks.push(Check::pass(
+        "vault",
+        format!("{} markdown files", md_files.len()),
+    ));
+
+    let mut broken_links = Vec::new();
+    let mut frontmatter_problems = Vec::new();
+
+    for file in &md_files {
+        let source = std::fs::read_to_string(file).into_diagnostic()?;
+        let meta = parse_metadata(&source);
+        let display = file
+            .strip_prefix(root)
+            .unwrap_or(file)
+            .to_string_lossy()
+            .into_owned();
+
+        for link in &meta.links {
+            if let Some(target) = internal_link_target(link) {
+                if lookup_filename_in_vault(&target, &contents).is_none() {
+                    broken_links.push(format!("{}: [[{}]]", display, target));
+                }
+            }
+        }
+
+        if let Some(problem) = frontmatter_problem(&meta.frontmatter) {
+            frontmatter_problems.push(format!("{}: {}", display, problem));
+        }
+    }
+
+    if broken_links.is_empty() {
+        checks.push(Check::pass("wikilinks", "all internal links resolve"));
+    } else {
+        checks.push(Check::fail(
+            "wikilinks",
+            format!("{} broken: {}", broken_links.len(), broken_links.join(", ")),
+            "fix the link targets or create the missing entries",
+        ));
+    }
+
+    if frontmatter_problems.is_empty() {
+        checks.push(Check::pass("frontmatter", "no schema problems"));
+    } else {
+        checks.push(Check::fail(
+            "frontmatter",
+            frontmatter_problems.join(", "),
+            "title must be a string; tags must be a list or comma-separated string",
+        ));
+    }
+
+    let mut oversized = Vec::new();
+    for path in &contents {
+        let is_image = path.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+            matches!(
+                e.to_ascii_lowercase().as_str(),
+                "png" | "jpg" | "jpeg" | "gif" | "webp" | "avif"
+            )
+        });
+        if !is_image {
+            continue;
+        }
+        if let Ok(meta) = std::fs::metadata(path) {
+            if meta.len() > IMAGE_WARN_BYTES {
+                oversized.push(format!(
+                    "{} ({} KiB)",
+                    path.strip_prefix(root).unwrap_or(path).display(),
+                    meta.len() / 1024
+                ));
+            }
+        }
+    }
+    if oversized.is_empty() {
+        checks.push(Check::pass("images", "none oversized"));
+    } else {
+        checks.push(Check::warn(
+            "images",
+            format!("{} over 1 MB: {}", oversized.len(), oversized.join(", ")),
+            "compress or resize before publishing; large blobs upload slowly",
+        ));
+    }
+
+    Ok(())
+}
+
+/// The vault-relative target of an internal link, or `None` for external
+/// destinations (schemes, anchors, protocol-relative URLs).
+fn internal_link_target(link: &str) -> Option<String> {
+    if link.starts_with('#') || link.contains("://") || link.starts_with("mailto:") {
+        return None;
+    }
+    // Drop a section anchor and undo the percent-encoding renderers apply
+    // to spaces, so the lookup sees the filename as written on disk.
+    let target = link.split('#').next().unwrap_or(link);
+    let target = target.replace("%20", " ");
+    if target.is_empty() {
+        return None;
+    }
+    Some(target)
+}
+
+/// Human-readable description of the first schema problem in parsed
+/// frontmatter, if any.
+fn frontmatter_problem(frontmatter: &weaver_renderer::Frontmatter) -> Option<String> {
+    let contents = frontmatter.contents();
+    let yaml = contents.read().ok()?;
+    let doc = yaml.first()?;
+
+    if matches!(doc, Yaml::BadValue) {
+        return Some("frontmatter is not valid YAML".to_string());
+    }
+
+    let title = &doc["title"];
+    if !matches!(title, Yaml::BadValue) && title.as_str().is_none() {
+        return Some("title is not a string".to_string());
+    }
+
+    let tags = &doc["tags"];
+    if !matches!(tags, Yaml::BadValue) {
+        match tags {
+            Yaml::String(_) => {}
+            Yaml::Array(items) => {
+                if items.iter().any(|t| t.as_str().is_none()) {
+                    return Some("tags list contains non-string entries".to_string());
+                }
+            }
+            _ => return Some("tags is neither a list nor a string".to_string()),
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_internal_link_target() {
+        assert_eq!(
+            internal_link_target("Some%20Note.md#section"),
+            Some("Some Note.md".to_string())
+        );
+        assert_eq!(internal_link_target("https://example.com"), None);
+        assert_eq!(internal_link_target("#anchor"), None);
+        assert_eq!(internal_link_target("mailto:a@b.c"), None);
+    }
+
+    #[test]
+    fn test_frontmatter_problem() {
+        let good = weaver_renderer::Frontmatter::new("title: hello\ntags:\n  - a\n  - b\n");
+        assert_eq!(frontmatter_problem(&good), None);
+
+        let bad_title = weaver_renderer::Frontmatter::new("title:\n  - not\n  - a string\n");
+        assert!(frontmatter_problem(&bad_title).is_some());
+
+        let bad_tags = weaver_renderer::Frontmatter::new("tags: 42\n");
+        assert!(frontmatter_problem(&bad_tags).is_some());
+    }
+}
@@ -0,0 +1,367 @@
+//! Account data export and deletion requests (GDPR-style).
+//!
+//! `weaver export-data` gathers everything weaver holds about the
+//! authenticated account into a local directory: every sh.weaver.* record
+//! from the user's PDS, local CLI state (session config, deploy caches),
+//! and the appview's server-held data fetched from its export endpoint.
+//! `weaver request-deletion` asks the appview to purge its server-held
+//! copy; records on the PDS stay under the user's own control.
+
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use jacquard::client::{Agent, FileAuthStore};
+use jacquard::http_client::HttpClient;
+use jacquard::identity::JacquardResolver;
+use jacquard::oauth::client::OAuthSession;
+use jacquard::prelude::*;
+use jacquard::types::nsid::Nsid;
+use miette::{IntoDiagnostic, Result};
+use weaver_api::com_atproto::repo::list_records::ListRecords;
+use weaver_api::com_atproto::server::get_service_auth::GetServiceAuth;
+
+/// The agent type the CLI works with (OAuth session from the auth store).
+type CliAgent = Agent<OAuthSession<JacquardResolver, FileAuthStore>>;
+
+/// Every sh.weaver record collection a user can hold in their repo.
+///
+/// Kept in sync with the record lexicons in weaver-api; a collection the
+/// user has never written to simply exports as an empty list.
+const RECORD_COLLECTIONS: &[&str] = &[
+    "sh.weaver.actor.profile",
+    "sh.weaver.collab.accept",
+    "sh.weaver.collab.invite",
+    "sh.weaver.collab.session",
+    "sh.weaver.edit.comment",
+    "sh.weaver.edit.cursor",
+    "sh.weaver.edit.diff",
+    "sh.weaver.edit.draft",
+    "sh.weaver.edit.root",
+    "sh.weaver.graph.bookmark",
+    "sh.weaver.graph.follow",
+    "sh.weaver.graph.followAccept",
+    "sh.weaver.graph.followGate",
+    "sh.weaver.graph.like",
+    "sh.weaver.graph.list",
+    "sh.weaver.graph.listitem",
+    "sh.weaver.graph.subscribe",
+    "sh.weaver.graph.subscribeAccept",
+    "sh.weaver.graph.tag",
+    "sh.weaver.notebook.authors",
+    "sh.weaver.notebook.book",
+    "sh.weaver.notebook.chapter",
+    "sh.weaver.notebook.colourScheme",
+    "sh.weaver.notebook.entry",
+    "sh.weaver.notebook.page",
+    "sh.weaver.notebook.theme",
+    "sh.weaver.publish.blob",
+];
+
+/// Export the account's data to a local directory.
+pub async fn export_data(
+    agent: &CliAgent,
+    out: Option<PathBuf>,
+    store_path: &Path,
+    appview: &str,
+) -> Result<()> {
+    let (did, _session_id) = agent
+        .info()
+        .await
+        .ok_or_else(|| miette::miette!("No session info available"))?;
+
+    let out = out.unwrap_or_else(|| PathBuf::from(format!("weaver-export-{}", date_stamp())));
+    std::fs::create_dir_all(&out).into_diagnostic()?;
+
+    println!("Exporting data for {}", did.as_str());
+    println!("→ Output: {}", out.display());
+
+    // 1. Records from the PDS, one file per collection.
+    let records_dir = out.join("records");
+    std::fs::create_dir_all(&records_dir).into_diagnostic()?;
+
+    let mut total_records = 0usize;
+    for collection in RECORD_COLLECTIONS {
+        let records = list_collection(agent, &did, collection).await?;
+        if records.is_empty() {
+            continue;
+        }
+        total_records += records.len();
+        let path = records_dir.join(format!("{}.json", collection));
+        let json = serde_json::to_vec_pretty(&records).into_diagnostic()?;
+        std::fs::write(&path, json).into_diagnostic()?;
+        println!("  ✓ {} ({} records)", collection, records.len());
+    }
+    println!("✓ Exported {} records from your PDS", total_records);
+
+    // 2. Local CLI state.
+    let copied = copy_local_state(&out, store_path)?;
+    if copied.is_empty() {
+        println!("✓ No local state to export");
+    } else {
+        println!("✓ Exported local state: {}", copied.join(", "));
+    }
+
+    // 3. Server-held data from the appview (public firehose-derived index).
+    match fetch_appview_export(agent, appview, did.as_str()).await {
+        Ok(body) => {
+            std::fs::write(out.join("appview.json"), body).into_diagnostic()?;
+            println!("✓ Exported appview data from {}", appview);
+        }
+        Err(e) => {
+            // The local and PDS portions are still useful without it.
+            println!("⚠ Could not fetch appview data: {}", e);
+        }
+    }
+
+    println!("✓ Export complete: {}", out.display());
+    Ok(())
+}
+
+/// Ask the appview to purge all server-held data for the account.
+pub async fn request_deletion(agent: &CliAgent, appview: &str, yes: bool) -> Result<()> {
+    let (did, _session_id) = agent
+        .info()
+        .await
+        .ok_or_else(|| miette::miette!("No session info available"))?;
+
+    println!(
+        "This asks {} to purge all indexed data for {}.",
+        appview,
+        did.as_str()
+    );
+    println!("Records on your PDS are not affected. While your repo stays");
+    println!("live on the firehose, the appview will re-index it over time.");
+
+    if !yes {
+        println!("Continue? [y/N]");
+        let mut answer = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut answer)
+            .into_diagnostic()?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            println!("Aborted");
+            return Ok(());
+        }
+    }
+
+    // Mint a service auth token addressed to the appview so it can verify
+    // the deletion request really comes from the account being purged.
+    let aud = appview_service_did(appview)?;
+    let aud = jacquard::types::string::Did::new_owned(aud)
+        .map_err(|e| miette::miette!("Invalid appview service DID: {}", e))?;
+
+    let resp = agent
+        .send(
+            GetServiceAuth::new()
+                .aud(aud)
+                .lxm(Some(Nsid::raw("sh.weaver.account.deleteData")))
+                .build(),
+        )
+        .await
+        .map_err(|e| miette::miette!("Failed to mint service auth token: {}", e))?;
+    let token = resp
+        .into_output()
+        .map_err(|e| miette::miette!("Failed to parse service auth response: {}", e))?
+        .token;
+
+    let url = format!("{}/account/delete", appview.trim_end_matches('/'));
+    let body = serde_json::json!({ "actor": did.as_str() })
+        .to_string()
+        .into_bytes();
+    let request = http::Request::builder()
+        .method(http::Method::POST)
+        .uri(&url)
+        .header(http::header::AUTHORIZATION, format!("Bearer {}", token))
+        .header(http::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .into_diagnostic()?;
+
+    let response = agent
+        .send_http(request)
+        .await
+        .map_err(|e| miette::miette!("Deletion request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(miette::miette!(
+            "Appview rejected deletion request: {} {}",
+            response.status(),
+            String::from_utf8_lossy(response.body())
+        ));
+    }
+
+    // Show the per-table accounting the appview returns.
+    if let Ok(summary) = serde_json::from_slice::<serde_json::Value>(response.body()) {
+        if let Some(purged) = summary.get("purged").and_then(|p| p.as_array()) {
+            let rows: u64 = purged
+                .iter()
+                .filter_map(|t| t.get("matchedRows").and_then(|r| r.as_u64()))
+                .sum();
+            println!(
+                "✓ Purge scheduled: {} rows across {} tables",
+                rows,
+                purged.len()
+            );
+        }
+    }
+    println!("✓ Deletion request accepted");
+
+    Ok(())
+}
+
+/// List every record in one collection of the account's repo.
+async fn list_collection(
+    agent: &CliAgent,
+    did: &jacquard::types::string::Did<'_>,
+    collection: &'static str,
+) -> Result<Vec<serde_json::Value>> {
+    let mut records = Vec::new();
+    let mut cursor: Option<jacquard::CowStr<'static>> = None;
+
+    loop {
+        let resp = agent
+            .send(
+                ListRecords::new()
+                    .repo(did.clone())
+                    .collection(Nsid::raw(collection))
+                    .limit(100)
+                    .maybe_cursor(cursor.clone())
+                    .build(),
+            )
+            .await
+            .map_err(|e| miette::miette!("Failed to list {}: {}", collection, e))?;
+
+        let list = resp
+            .parse()
+            .map_err(|e| miette::miette!("Failed to parse {} listing: {}", collection, e))?;
+
+        for record in &list.records {
+            // Owned JSON so nothing borrows from the response across pages.
+            records.push(serde_json::to_value(record).into_diagnostic()?);
+        }
+
+        match list.cursor {
+            Some(c) => cursor = Some(c.into_static()),
+            None => break,
+        }
+    }
+
+    Ok(records)
+}
+
+/// Fetch the appview's export bundle for the account.
+async fn fetch_appview_export(agent: &CliAgent, appview: &str, did: &str) -> Result<Vec<u8>> {
+    let url = format!(
+        "{}/account/export?actor={}",
+        appview.trim_end_matches('/'),
+        did
+    );
+    let request = http::Request::builder()
+        .method(http::Method::GET)
+        .uri(&url)
+        .body(Vec::new())
+        .into_diagnostic()?;
+
+    let response = agent
+        .send_http(request)
+        .await
+        .map_err(|e| miette::miette!("{}", e))?;
+
+    if !response.status().is_success() {
+        return Err(miette::miette!("{} returned {}", url, response.status()));
+    }
+
+    Ok(response.into_body())
+}
+
+/// Copy the CLI's local state into the export directory.
+///
+/// Auth tokens (auth.json) are deliberately excluded: they are live
+/// credentials, not personal usage data.
+fn copy_local_state(out: &Path, store_path: &Path) -> Result<Vec<String>> {
+    let mut copied = Vec::new();
+    let local_dir = out.join("local");
+
+    // Session config: DID and session id, no tokens.
+    let config_path = store_path.with_extension("kdl");
+    if config_path.exists() {
+        std::fs::create_dir_all(&local_dir).into_diagnostic()?;
+        std::fs::copy(&config_path, local_dir.join("config.kdl")).into_diagnostic()?;
+        copied.push("config.kdl".to_string());
+    }
+
+    // Deploy caches: last-deployed manifests per hosting target.
+    let Some(config_dir) = dirs::config_dir() else {
+        return Ok(copied);
+    };
+    let deploy_dir = config_dir.join("weaver").join("deploy");
+    if let Ok(entries) = std::fs::read_dir(&deploy_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                let dest_dir = local_dir.join("deploy");
+                std::fs::create_dir_all(&dest_dir).into_diagnostic()?;
+                std::fs::copy(&path, dest_dir.join(name)).into_diagnostic()?;
+                copied.push(format!("deploy/{}", name));
+            }
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Derive the appview's did:web service DID from its base URL.
+///
+/// Only bare http(s) origins are supported; did:web encodes ports with
+/// percent-escapes, which no weaver deployment uses.
+fn appview_service_did(appview: &str) -> Result<String> {
+    let trimmed = appview.trim_end_matches('/');
+    let host = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"))
+        .ok_or_else(|| miette::miette!("Appview URL must be http(s): {}", appview))?;
+
+    if host.is_empty() || host.contains('/') || host.contains(':') {
+        return Err(miette::miette!(
+            "Appview URL must be a bare origin: {}",
+            appview
+        ));
+    }
+
+    Ok(format!("did:web:{}", host))
+}
+
+/// Today's UTC date (YYYY-MM-DD) for naming the export directory.
+fn date_stamp() -> String {
+    let now = jacquard::types::string::Datetime::now();
+    // The date part of RFC 3339 is filesystem-friendly; the time part
+    // contains colons, which are not.
+    now.as_str().chars().take_while(|c| *c != 'T').collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_did_from_origin() {
+        assert_eq!(
+            appview_service_did("https://index.weaver.sh").unwrap(),
+            "did:web:index.weaver.sh"
+        );
+        assert_eq!(
+            appview_service_did("https://index.weaver.sh/").unwrap(),
+            "did:web:index.weaver.sh"
+        );
+    }
+
+    #[test]
+    fn test_service_did_rejects_paths_and_ports() {
+        assert!(appview_service_did("index.weaver.sh").is_err());
+        assert!(appview_service_did("https://index.weaver.sh/xrpc").is_err());
+        assert!(appview_service_did("https://localhost:3000").is_err());
+    }
+}
@@ -0,0 +1,363 @@
+//! Import foreign note exports into a clean weaver vault.
+//!
+//! `weaver import <format> <path>` converts an export from another notes app
+//! into a directory layout ready for `weaver publish`:
+//!
+//! - **notion**: a "Markdown & CSV" export. Notion suffixes every file and
+//!   directory with a 32-hex object id; those are stripped and internal
+//!   links rewritten to match. HTML exports are not supported - re-export
+//!   as Markdown & CSV.
+//! - **bear**: a Bear markdown export. Inline `#tags` are collected into
+//!   frontmatter, and lines consisting only of tags are dropped.
+//! - **obsidian**: an Obsidian vault. Plugin state (`.obsidian/`) and
+//!   trash are dropped; notes and frontmatter pass through unchanged.
+//!
+//! All formats move non-markdown files into an `attachments/` directory and
+//! rewrite links to them.
+
+use clap::ValueEnum;
+use miette::{IntoDiagnostic, Result};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Source formats recognized by `weaver import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Notion "Markdown & CSV" export.
+    Notion,
+    /// Bear markdown export.
+    Bear,
+    /// Obsidian vault.
+    Obsidian,
+}
+
+/// Convert an export at `source` into a weaver vault at `out`.
+pub async fn import_vault(format: ImportFormat, source: PathBuf, out: PathBuf) -> Result<()> {
+    if !source.exists() {
+        return Err(miette::miette!(
+            "Source path not found: {}",
+            source.display()
+        ));
+    }
+    if out.exists() && std::fs::read_dir(&out).into_diagnostic()?.next().is_some() {
+        return Err(miette::miette!(
+            "Output directory is not empty: {}",
+            out.display()
+        ));
+    }
+    std::fs::create_dir_all(&out).into_diagnostic()?;
+
+    // Collect source files, skipping app-internal state.
+    let mut markdown = Vec::new();
+    let mut attachments = Vec::new();
+    collect_files(&source, &source, &mut markdown, &mut attachments)?;
+
+    if markdown.is_empty() {
+        let hint = match format {
+            ImportFormat::Notion => {
+                " (Notion HTML exports are not supported; re-export as Markdown & CSV)"
+            }
+            _ => "",
+        };
+        return Err(miette::miette!(
+            "No markdown files found in {}{}",
+            source.display(),
+            hint
+        ));
+    }
+
+    // Map every source-relative path to its place in the new vault, so link
+    // rewriting can run over the full set at once.
+    let mut renames: BTreeMap<String, String> = BTreeMap::new();
+    let mut used_attachment_names: BTreeMap<String, usize> = BTreeMap::new();
+
+    for relative in &markdown {
+        let new_relative = match format {
+            ImportFormat::Notion => strip_notion_ids(relative),
+            ImportFormat::Bear | ImportFormat::Obsidian => relative.clone(),
+        };
+        renames.insert(
+            path_to_link_string(relative),
+            path_to_link_string(&new_relative),
+        );
+    }
+
+    for relative in &attachments {
+        // Attachments are normalized into a single directory; collisions get
+        // a numeric suffix rather than silently overwriting.
+        let filename = match format {
+            ImportFormat::Notion => strip_notion_ids(relative),
+            ImportFormat::Bear | ImportFormat::Obsidian => relative.clone(),
+        };
+        let filename = filename
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "attachment".to_string());
+        let unique = uniquify_name(&filename, &mut used_attachment_names);
+        renames.insert(
+            path_to_link_string(relative),
+            format!("attachments/{}", unique),
+        );
+    }
+
+    // Copy attachments.
+    if !attachments.is_empty() {
+        std::fs::create_dir_all(out.join("attachments")).into_diagnostic()?;
+    }
+    for relative in &attachments {
+        let new_relative = &renames[&path_to_link_string(relative)];
+        std::fs::copy(source.join(relative), out.join(new_relative)).into_diagnostic()?;
+    }
+
+    // Rewrite and write markdown.
+    for relative in &markdown {
+        let contents = std::fs::read_to_string(source.join(relative)).into_diagnostic()?;
+        let contents = rewrite_links(&contents, &renames);
+        let contents = match format {
+            ImportFormat::Bear => hoist_bear_tags(&contents),
+            ImportFormat::Notion | ImportFormat::Obsidian => contents,
+        };
+
+        let new_relative = &renames[&path_to_link_string(relative)];
+        let dest = out.join(new_relative);
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent).into_diagnostic()?;
+        }
+        std::fs::write(&dest, contents).into_diagnostic()?;
+    }
+
+    println!(
+        "✓ Imported {} notes and {} attachments into {}",
+        markdown.len(),
+        attachments.len(),
+        out.display()
+    );
+    println!("  Publish with: weaver publish {} <title>", out.display());
+
+    Ok(())
+}
+
+/// Recursively collect files, splitting markdown from attachments and
+/// skipping app-internal directories.
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    markdown: &mut Vec<PathBuf>,
+    attachments: &mut Vec<PathBuf>,
+) -> Result<()> {
+    for entry in std::fs::read_dir(dir).into_diagnostic()? {
+        let path = entry.into_diagnostic()?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        // App state, not content: Obsidian config/trash, hidden files.
+        if name.starts_with('.') || name == ".obsidian" || name == ".trash" {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_files(root, &path, markdown, attachments)?;
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(root)
+            .expect("walked paths are under root")
+            .to_path_buf();
+        let is_markdown = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext == "md" || ext == "markdown")
+            .unwrap_or(false);
+        // Notion exports ship per-database CSV indexes; content lives in
+        // the markdown files, so the indexes are dropped.
+        let is_csv = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext == "csv")
+            .unwrap_or(false);
+
+        if is_markdown {
+            markdown.push(relative);
+        } else if !is_csv {
+            attachments.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Strip Notion's 32-hex object id suffix from every component of a path.
+///
+/// `Page Name 0123456789abcdef0123456789abcdef.md` -> `Page Name.md`.
+fn strip_notion_ids(path: &Path) -> PathBuf {
+    path.iter()
+        .map(|component| {
+            let component = component.to_string_lossy();
+            let (stem, ext) = match component.rsplit_once('.') {
+                Some((stem, ext)) => (stem, Some(ext)),
+                None => (component.as_ref(), None),
+            };
+            let stem = match stem.rsplit_once(' ') {
+                Some((name, id)) if id.len() == 32 && id.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                    name
+                }
+                _ => stem,
+            };
+            match ext {
+                Some(ext) => format!("{}.{}", stem, ext),
+                None => stem.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Rewrite markdown link targets according to the rename map.
+///
+/// Targets appear both raw and percent-encoded (Notion encodes spaces), so
+/// both spellings of every old path are replaced.
+fn rewrite_links(contents: &str, renames: &BTreeMap<String, String>) -> String {
+    let mut result = contents.to_string();
+    for (old, new) in renames {
+        if old == new {
+            continue;
+        }
+        let encoded_old = old.replace(' ', "%20");
+        let encoded_new = new.replace(' ', "%20");
+        result = result.replace(&format!("({})", encoded_old), &format!("({})", encoded_new));
+        result = result.replace(&format!("({})", old), &format!("({})", new));
+    }
+    result
+}
+
+/// Hoist Bear's inline `#tags` into a frontmatter block.
+///
+/// Lines consisting only of tags are removed; tags used mid-sentence are
+/// recorded but left in place.
+fn hoist_bear_tags(contents: &str) -> String {
+    let mut tags: Vec<String> = Vec::new();
+    let mut body_lines: Vec<&str> = Vec::new();
+
+    for line in contents.lines() {
+        let mut line_tags = Vec::new();
+        for word in line.split_whitespace() {
+            // Headings start with '#' too, but always have a space after the
+            // hashes, so a heading marker never parses as a tag here.
+            if let Some(tag) = word.strip_prefix('#') {
+                if !tag.is_empty() && !tag.starts_with('#') {
+                    line_tags.push(tag.trim_end_matches(['.', ',', ';']).to_string());
+                    continue;
+                }
+            }
+            line_tags.clear();
+            break;
+        }
+
+        let is_tag_only_line = !line.trim().is_empty() && !line_tags.is_empty();
+        if is_tag_only_line {
+            tags.extend(line_tags);
+        } else {
+            for word in line.split_whitespace() {
+                if let Some(tag) = word.strip_prefix('#') {
+                    if !tag.is_empty() && !tag.starts_with('#') {
+                        tags.push(tag.trim_end_matches(['.', ',', ';']).to_string());
+                    }
+                }
+            }
+            body_lines.push(line);
+        }
+    }
+
+    tags.sort();
+    tags.dedup();
+
+    if tags.is_empty() {
+        return contents.to_string();
+    }
+
+    let mut result = String::from("---\ntags:\n");
+    for tag in &tags {
+        result.push_str("  - ");
+        result.push_str(tag);
+        result.push('\n');
+    }
+    result.push_str("---\n");
+    result.push_str(&body_lines.join("\n"));
+    result.push('\n');
+    result
+}
+
+/// Forward-slash string form of a relative path, as it appears in links.
+fn path_to_link_string(path: &Path) -> String {
+    path.iter()
+        .map(|c| c.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Deduplicate a filename by appending a counter before the extension.
+fn uniquify_name(name: &str, used: &mut BTreeMap<String, usize>) -> String {
+    let count = used.entry(name.to_string()).or_insert(0);
+    *count += 1;
+    if *count == 1 {
+        return name.to_string();
+    }
+    match name.rsplit_once('.') {
+        Some((stem, ext)) => format!("{}-{}.{}", stem, count, ext),
+        None => format!("{}-{}", name, count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_notion_ids() {
+        let path = Path::new(
+            "Workspace 0123456789abcdef0123456789abcdef/Page 89abcdef0123456789abcdef01234567.md",
+        );
+        assert_eq!(strip_notion_ids(path), PathBuf::from("Workspace/Page.md"));
+        // Names without an id suffix pass through.
+        assert_eq!(
+            strip_notion_ids(Path::new("notes/Plain.md")),
+            PathBuf::from("notes/Plain.md")
+        );
+    }
+
+    #[test]
+    fn test_rewrite_links_handles_percent_encoding() {
+        let mut renames = BTreeMap::new();
+        renames.insert(
+            "Page 0123456789abcdef0123456789abcdef.md".to_string(),
+            "Page.md".to_string(),
+        );
+        let input = "[Page](Page%200123456789abcdef0123456789abcdef.md)";
+        assert_eq!(rewrite_links(input, &renames), "[Page](Page.md)");
+    }
+
+    #[test]
+    fn test_hoist_bear_tags() {
+        let input = "# Title\n\nSome text about #rust stuff.\n\n#notes #rust\n";
+        let output = hoist_bear_tags(input);
+        assert!(output.starts_with("---\ntags:\n  - notes\n  - rust\n---\n"));
+        // The tag-only line is dropped; the inline mention stays.
+        assert!(output.contains("Some text about #rust stuff."));
+        assert!(!output.contains("#notes #rust"));
+    }
+
+    #[test]
+    fn test_hoist_bear_tags_ignores_headings() {
+        let input = "# Heading\n\n## Another\n\nbody\n";
+        assert_eq!(hoist_bear_tags(input), input);
+    }
+
+    #[test]
+    fn test_uniquify_name() {
+        let mut used = BTreeMap::new();
+        assert_eq!(uniquify_name("img.png", &mut used), "img.png");
+        assert_eq!(uniquify_name("img.png", &mut used), "img-2.png");
+        assert_eq!(uniquify_name("img.png", &mut used), "img-3.png");
+    }
+}
@@ -1,3 +1,4 @@
+use indicatif::{ProgressBar, ProgressStyle};
 use jacquard::IntoStatic;
 use jacquard::client::{Agent, FileAuthStore};
 use jacquard::identity::JacquardResolver;
@@ -7,16 +8,26 @@ use jacquard::prelude::*;
 use jacquard::types::string::Handle;
 use miette::{IntoDiagnostic, Result};
 use std::io::BufRead;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use weaver_common::normalize_title_path;
 use weaver_renderer::atproto::AtProtoPreprocessContext;
-use weaver_renderer::static_site::StaticSiteWriter;
+use weaver_renderer::static_site::templates::SiteTemplates;
+use weaver_renderer::static_site::{RenderProgress, StaticSiteWriter};
 use weaver_renderer::utils::VaultBrokenLinkCallback;
 use weaver_renderer::walker::{WalkOptions, vault_contents};
 
 use clap::{Parser, Subcommand};
 
+mod crosspost;
+mod deploy;
+mod doctor;
+mod export;
+mod import;
+mod theme;
+mod thread_import;
+mod workspace;
+
 #[derive(Parser)]
 #[command(version, about = "Weaver - Static site generator for AT Protocol notebooks", long_about = None)]
 #[command(propagate_version = true)]
@@ -31,6 +42,14 @@ struct Cli {
     #[arg(long)]
     store: Option<PathBuf>,
 
+    /// Theme directory with template overrides (header/footer/page, extra CSS/JS)
+    #[arg(long)]
+    theme: Option<PathBuf>,
+
+    /// Number of files to render in parallel (defaults to the CPU count)
+    #[arg(long)]
+    jobs: Option<usize>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -51,14 +70,147 @@ enum Commands {
         /// Path to notebook directory
         source: PathBuf,
 
-        /// Notebook title
+        /// Notebook title (optional when the source has a weaver.kdl workspace)
         //#[arg(long)]
-        title: String,
+        title: Option<String>,
+
+        /// Path to auth store file
+        #[arg(long)]
+        store: Option<PathBuf>,
+
+        /// Offer to announce published entries on Bluesky (with preview)
+        #[arg(long)]
+        crosspost: bool,
+
+        /// Warn about accessibility problems (skipped heading levels,
+        /// missing alt text, empty links) while publishing
+        #[arg(long)]
+        a11y_check: bool,
+    },
+    /// Publish a single file (or stdin with '-') as a notebook entry
+    PublishEntry {
+        /// Markdown file to publish, or '-' to read from stdin
+        file: PathBuf,
+
+        /// Notebook title the entry belongs to
+        #[arg(long)]
+        notebook: String,
+
+        /// Path to auth store file
+        #[arg(long)]
+        store: Option<PathBuf>,
+
+        /// Offer to announce the published entry on Bluesky (with preview)
+        #[arg(long)]
+        crosspost: bool,
+
+        /// Warn about accessibility problems (skipped heading levels,
+        /// missing alt text, empty links) before publishing
+        #[arg(long)]
+        a11y_check: bool,
+    },
+    /// Deploy a rendered static site, uploading only changed files
+    Deploy {
+        /// Rendered site directory (the render destination)
+        dir: PathBuf,
+
+        /// Hosting target (defaults to the deploy node in weaver.kdl)
+        #[arg(long, value_enum)]
+        target: Option<deploy::DeployTargetKind>,
+
+        /// S3 bucket name (s3 target)
+        #[arg(long)]
+        bucket: Option<String>,
+
+        /// Git remote URL or owner/repo shorthand (github-pages target)
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// Publishing branch (github-pages target, defaults to gh-pages)
+        #[arg(long)]
+        branch: Option<String>,
+
+        /// Netlify site ID or name (netlify target)
+        #[arg(long)]
+        site: Option<String>,
+
+        /// Show what would be uploaded without deploying
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export your weaver records, local state, and appview data to a directory
+    ExportData {
+        /// Output directory (defaults to ./weaver-export-<date>)
+        #[arg(long)]
+        out: Option<PathBuf>,
+
+        /// Path to auth store file
+        #[arg(long)]
+        store: Option<PathBuf>,
+
+        /// Appview base URL to export server-held data from
+        #[arg(long, default_value = "https://index.weaver.sh")]
+        appview: String,
+    },
+    /// Ask the appview to purge its server-held data about your account
+    RequestDeletion {
+        /// Path to auth store file
+        #[arg(long)]
+        store: Option<PathBuf>,
+
+        /// Appview base URL to send the deletion request to
+        #[arg(long, default_value = "https://index.weaver.sh")]
+        appview: String,
+
+        /// Skip the confirmation prompt
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Manage static site themes
+    Theme {
+        #[command(subcommand)]
+        command: ThemeCommands,
+    },
+    /// Check auth, PDS connectivity, and vault health
+    Doctor {
+        /// Notebook directory to check (skips vault checks when omitted)
+        vault: Option<PathBuf>,
 
         /// Path to auth store file
         #[arg(long)]
         store: Option<PathBuf>,
     },
+    /// Convert a Notion, Bear, or Obsidian export into a weaver vault
+    Import {
+        /// Export format of the source
+        #[arg(value_enum)]
+        format: import::ImportFormat,
+
+        /// Path to the export directory
+        path: PathBuf,
+
+        /// Output vault directory (defaults to ./<source>-weaver)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Save a Bluesky thread as a markdown entry draft
+    SaveThread {
+        /// URL of any post in the thread (bsky.app link or at:// URI)
+        url: String,
+
+        /// Output file path (defaults to <rkey>.md in the current directory)
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ThemeCommands {
+    /// Scaffold a theme directory with commented template stubs
+    Init {
+        /// Directory to create (defaults to ./theme)
+        dir: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -76,9 +228,127 @@ async fn main() -> Result<()> {
             source,
             title,
             store,
+            crosspost,
+            a11y_check,
+        }) => {
+            let store_path = store.unwrap_or_else(default_auth_store_path);
+            match (title, workspace::WorkspaceConfig::load(&source)?) {
+                // An explicit title always publishes the source as one notebook
+                (Some(title), _) => {
+                    publish_notebook(source, title, store_path, crosspost, vec![], a11y_check)
+                        .await?;
+                }
+                (None, Some(ws)) => {
+                    println!("Publishing workspace ({} notebooks)", ws.notebooks.len());
+                    for notebook in &ws.notebooks {
+                        println!("=== {} ===", notebook.title);
+                        publish_notebook(
+                            source.join(&notebook.dir),
+                            notebook.title.clone(),
+                            store_path.clone(),
+                            crosspost,
+                            ws.ignore.clone(),
+                            a11y_check,
+                        )
+                        .await?;
+                    }
+                }
+                (None, None) => {
+                    return Err(miette::miette!(
+                        "Notebook title required (or declare notebooks in weaver.kdl)"
+                    ));
+                }
+            }
+        }
+        Some(Commands::PublishEntry {
+            file,
+            notebook,
+            store,
+            crosspost,
+            a11y_check,
+        }) => {
+            let store_path = store.unwrap_or_else(default_auth_store_path);
+            publish_entry(file, notebook, store_path, crosspost, a11y_check).await?;
+        }
+        Some(Commands::Deploy {
+            dir,
+            target,
+            bucket,
+            repo,
+            branch,
+            site,
+            dry_run,
+        }) => {
+            // Workspace deploy defaults: flags win, then the deploy node of a
+            // weaver.kdl in the site directory or the current directory.
+            let workspace_deploy = match workspace::WorkspaceConfig::load(&dir)? {
+                Some(ws) => ws.deploy,
+                None => match std::env::current_dir() {
+                    Ok(cwd) => workspace::WorkspaceConfig::load(&cwd)?.and_then(|ws| ws.deploy),
+                    Err(_) => None,
+                },
+            };
+            let target = target
+                .or(workspace_deploy.as_ref().map(|d| d.target))
+                .ok_or_else(|| {
+                    miette::miette!(
+                        "Deploy target required: pass --target or declare a deploy node in weaver.kdl"
+                    )
+                })?;
+            let (ws_bucket, ws_repo, ws_branch, ws_site) = match workspace_deploy {
+                Some(d) => (d.bucket, d.repo, d.branch, d.site),
+                None => (None, None, None, None),
+            };
+            let opts = deploy::DeployOptions {
+                bucket: bucket.or(ws_bucket),
+                repo: repo.or(ws_repo),
+                branch: branch
+                    .or(ws_branch)
+                    .unwrap_or_else(|| "gh-pages".to_string()),
+                site: site.or(ws_site),
+                dry_run,
+            };
+            deploy::deploy_site(dir, target, opts).await?;
+        }
+        Some(Commands::ExportData {
+            out,
+            store,
+            appview,
+        }) => {
+            let store_path = store.unwrap_or_else(default_auth_store_path);
+            let agent = require_agent(&store_path).await?;
+            export::export_data(&agent, out, &store_path, &appview).await?;
+        }
+        Some(Commands::RequestDeletion {
+            store,
+            appview,
+            yes,
         }) => {
             let store_path = store.unwrap_or_else(default_auth_store_path);
-            publish_notebook(source, title, store_path).await?;
+            let agent = require_agent(&store_path).await?;
+            export::request_deletion(&agent, &appview, yes).await?;
+        }
+        Some(Commands::Theme { command }) => match command {
+            ThemeCommands::Init { dir } => {
+                theme::init_theme(dir.unwrap_or_else(|| PathBuf::from("theme")))?;
+            }
+        },
+        Some(Commands::Doctor { vault, store }) => {
+            let store_path = store.unwrap_or_else(default_auth_store_path);
+            doctor::doctor(vault, store_path).await?;
+        }
+        Some(Commands::Import { format, path, out }) => {
+            let out = out.unwrap_or_else(|| {
+                let stem = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "vault".to_string());
+                PathBuf::from(format!("{}-weaver", stem))
+            });
+            import::import_vault(format, path, out).await?;
+        }
+        Some(Commands::SaveThread { url, out }) => {
+            thread_import::save_thread(&url, out).await?;
         }
         None => {
             // Render command (default)
@@ -90,7 +360,27 @@ async fn main() -> Result<()> {
             })?;
             let store_path = cli.store.unwrap_or_else(default_auth_store_path);
 
-            render_notebook(source, dest, store_path).await?;
+            if let Some(ws) = workspace::WorkspaceConfig::load(&source)? {
+                println!("✓ Workspace with {} notebooks", ws.notebooks.len());
+                for notebook in &ws.notebooks {
+                    let notebook_dest = dest.join(&notebook.dir);
+                    std::fs::create_dir_all(&notebook_dest).into_diagnostic()?;
+                    render_notebook(
+                        source.join(&notebook.dir),
+                        notebook_dest,
+                        store_path.clone(),
+                        cli.theme.clone(),
+                        ws.ignore.clone(),
+                        cli.jobs,
+                    )
+                    .await?;
+                }
+                if let Some(base_url) = &ws.base_url {
+                    println!("✓ Site will be served from {}", base_url);
+                }
+            } else {
+                render_notebook(source, dest, store_path, cli.theme, vec![], cli.jobs).await?;
+            }
         }
     }
 
@@ -152,7 +442,24 @@ async fn try_load_session(
     oauth.restore(&did, session_id).await.ok()
 }
 
-async fn render_notebook(source: PathBuf, dest: PathBuf, store_path: PathBuf) -> Result<()> {
+/// Load the stored session as an agent, or explain how to authenticate.
+async fn require_agent(
+    store_path: &PathBuf,
+) -> Result<Agent<OAuthSession<JacquardResolver, FileAuthStore>>> {
+    let session = try_load_session(store_path).await.ok_or_else(|| {
+        miette::miette!("No authentication found. Run 'weaver auth <handle>' first")
+    })?;
+    Ok(Agent::new(session))
+}
+
+async fn render_notebook(
+    source: PathBuf,
+    dest: PathBuf,
+    store_path: PathBuf,
+    theme_dir: Option<PathBuf>,
+    ignore_globs: Vec<String>,
+    jobs: Option<usize>,
+) -> Result<()> {
     // Validate source exists
     if !source.exists() {
         return Err(miette::miette!(
@@ -180,12 +487,49 @@ async fn render_notebook(source: PathBuf, dest: PathBuf, store_path: PathBuf) ->
     }
 
     // Create renderer
-    let writer = StaticSiteWriter::new(source, dest.clone(), session);
+    let mut writer = StaticSiteWriter::new(source, dest.clone(), session);
+
+    // Merge theme template overrides with the built-in layout
+    if let Some(theme_dir) = theme_dir {
+        let templates = SiteTemplates::load(&theme_dir)?;
+        println!("✓ Loaded theme: {}", theme_dir.display());
+        writer = writer.with_templates(templates);
+    }
+
+    // Workspace-level ignore globs, if any
+    if !ignore_globs.is_empty() {
+        writer = writer.with_ignore_globs(ignore_globs);
+    }
+
+    if let Some(jobs) = jobs {
+        writer = writer.with_jobs(jobs);
+    }
+
+    // Per-file progress, driven by events from the renderer's worker pool
+    let bar = ProgressBar::new(0);
+    bar.set_style(
+        ProgressStyle::with_template("{spinner} [{bar:40}] {pos}/{len} {msg}")
+            .expect("static template is valid")
+            .progress_chars("=> "),
+    );
+    let progress_bar = bar.clone();
+    writer = writer.with_progress(Arc::new(move |event| match event {
+        RenderProgress::Started { total } => progress_bar.set_length(total as u64),
+        RenderProgress::FileDone { path, elapsed } => {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            progress_bar.set_message(format!("{} ({}ms)", name, elapsed.as_millis()));
+            progress_bar.inc(1);
+        }
+    }));
 
     // Render
     println!("→ Rendering notebook...");
     let start = std::time::Instant::now();
     writer.run().await?;
+    bar.finish_and_clear();
     let elapsed = start.elapsed();
 
     // Report success
@@ -202,14 +546,22 @@ fn default_auth_store_path() -> PathBuf {
         .join("auth.json")
 }
 
-async fn publish_notebook(source: PathBuf, title: String, store_path: PathBuf) -> Result<()> {
-    // Initialize tracing for debugging
-    tracing_subscriber::fmt()
+async fn publish_notebook(
+    source: PathBuf,
+    title: String,
+    store_path: PathBuf,
+    crosspost: bool,
+    ignore_globs: Vec<String>,
+    a11y_check: bool,
+) -> Result<()> {
+    // Initialize tracing for debugging; a workspace publish calls this once
+    // per notebook, so tolerate an already-set subscriber.
+    let _ = tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug")),
         )
-        .init();
+        .try_init();
 
     println!("Publishing notebook from: {}", source.display());
     println!("Title: {}", title);
@@ -254,18 +606,7 @@ async fn publish_notebook(source: PathBuf, title: String, store_path: PathBuf) -
         .info()
         .await
         .ok_or_else(|| miette::miette!("No session info available"))?;
-    let did_doc_response = agent.resolve_did_doc(&did).await?;
-    let did_doc = did_doc_response.parse()?;
-
-    // Extract handle from alsoKnownAs
-    let aka_vec = did_doc
-        .also_known_as
-        .ok_or_else(|| miette::miette!("No alsoKnownAs in DID document"))?;
-    let handle_str = aka_vec
-        .get(0)
-        .and_then(|aka| aka.as_ref().strip_prefix("at://"))
-        .ok_or_else(|| miette::miette!("No handle found in DID document"))?;
-    let handle = Handle::new(handle_str)?;
+    let handle = resolve_publishing_handle(&agent, &did).await?;
 
     println!("Publishing as @{}", handle.as_ref());
 
@@ -279,7 +620,7 @@ async fn publish_notebook(source: PathBuf, title: String, store_path: PathBuf) -
     let vault_arc: Arc<[PathBuf]> = contents.into();
 
     // Filter markdown files after converting to Arc
-    let md_files: Vec<PathBuf> = vault_arc
+    let mut md_files: Vec<PathBuf> = vault_arc
         .iter()
         .filter(|path| {
             path.extension()
@@ -290,6 +631,16 @@ async fn publish_notebook(source: PathBuf, title: String, store_path: PathBuf) -
         .cloned()
         .collect();
 
+    // Drop anything excluded by workspace-level ignore globs
+    if !ignore_globs.is_empty() {
+        let mut builder = ignore::gitignore::GitignoreBuilder::new(&source);
+        for glob in &ignore_globs {
+            builder.add_line(None, glob).into_diagnostic()?;
+        }
+        let matcher = builder.build().into_diagnostic()?;
+        md_files.retain(|path| !matcher.matched_path_or_any_parents(path, false).is_ignore());
+    }
+
     println!("Found {} markdown files", md_files.len());
 
     // Create preprocessing context
@@ -306,101 +657,227 @@ async fn publish_notebook(source: PathBuf, title: String, store_path: PathBuf) -
             .await
             .into_diagnostic()?;
 
-        // Clone context for this file
-        let mut file_context = context.clone();
-        file_context.set_current_path(file_path.clone());
-        let callback = Some(VaultBrokenLinkCallback {
-            vault_contents: vault_arc.clone(),
-        });
-
-        // Parse markdown
-        use markdown_weaver::Parser;
-        use weaver_renderer::default_md_options;
-        let parser =
-            Parser::new_with_broken_link_callback(&contents, default_md_options(), callback)
-                .into_offset_iter();
-        let iterator = weaver_renderer::ContextIterator::default(parser);
-
-        // Process through NotebookProcessor
-        use n0_future::StreamExt;
-        use weaver_renderer::{NotebookContext, NotebookProcessor};
-        let mut processor = NotebookProcessor::new(file_context.clone(), iterator);
-
-        // Write canonical markdown with MarkdownWriter
-        use markdown_weaver_escape::FmtWriter;
-        use weaver_renderer::atproto::MarkdownWriter;
-        let mut output = String::new();
-        let mut md_writer = MarkdownWriter::new(FmtWriter(&mut output));
-
-        // Process all events
-        while let Some((event, _)) = processor.next().await {
-            md_writer
-                .write_event(event)
-                .map_err(|e| miette::miette!("Failed to write markdown: {:?}", e))?;
+        if a11y_check {
+            report_a11y_warnings(file_path, &contents);
         }
 
-        // Extract blobs and entry metadata
-        let blobs = file_context.blobs();
-        let entry_title = file_context.entry_title();
+        publish_entry_source(
+            &agent, &context, &vault_arc, file_path, &contents, &title, crosspost,
+        )
+        .await?;
+    }
+
+    println!("✓ Published {} entries", md_files.len());
+
+    Ok(())
+}
+
+/// Resolve the account handle from the DID document's `alsoKnownAs`.
+async fn resolve_publishing_handle(
+    agent: &Agent<OAuthSession<JacquardResolver, FileAuthStore>>,
+    did: &jacquard::types::string::Did<'_>,
+) -> Result<Handle<'static>> {
+    let did_doc_response = agent.resolve_did_doc(did).await?;
+    let did_doc = did_doc_response.parse()?;
 
-        if !blobs.is_empty() {
-            tracing::debug!("Uploaded {} image(s)", blobs.len());
+    // Extract handle from alsoKnownAs
+    let aka_vec = did_doc
+        .also_known_as
+        .ok_or_else(|| miette::miette!("No alsoKnownAs in DID document"))?;
+    let handle_str = aka_vec
+        .get(0)
+        .and_then(|aka| aka.as_ref().strip_prefix("at://"))
+        .ok_or_else(|| miette::miette!("No handle found in DID document"))?;
+    Ok(Handle::new(handle_str)?.into_static())
+}
+
+/// Print accessibility warnings for one markdown source.
+///
+/// Warnings never block a publish; the author decides whether to fix
+/// them first.
+fn report_a11y_warnings(path: &Path, contents: &str) {
+    for warning in weaver_renderer::a11y::check_markdown(contents) {
+        println!("⚠ {}:{}: {}", path.display(), warning.line, warning);
+    }
+}
+
+/// Publish a single file as a notebook entry, reading stdin when `file`
+/// is `-`.
+///
+/// Meant for CI pipelines that generate content, so authentication is
+/// non-interactive: a missing session errors instead of prompting.
+async fn publish_entry(
+    file: PathBuf,
+    notebook: String,
+    store_path: PathBuf,
+    crosspost: bool,
+    a11y_check: bool,
+) -> Result<()> {
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("debug")),
+        )
+        .try_init();
+
+    // Read the source before touching the network so pipe errors surface
+    // immediately.
+    let (contents, path) = if file.as_os_str() == "-" {
+        let mut buf = String::new();
+        use std::io::Read;
+        std::io::stdin()
+            .lock()
+            .read_to_string(&mut buf)
+            .into_diagnostic()?;
+        // A stable pseudo-path gives the title fallback something to chew
+        // on when the piped content has no heading.
+        (buf, PathBuf::from("stdin.md"))
+    } else {
+        if !file.exists() {
+            return Err(miette::miette!("File not found: {}", file.display()));
         }
+        let contents = std::fs::read_to_string(&file).into_diagnostic()?;
+        (contents, file)
+    };
+
+    if a11y_check {
+        report_a11y_warnings(&path, &contents);
+    }
+
+    let agent = Arc::new(require_agent(&store_path).await?);
+    let (did, _session_id) = agent
+        .info()
+        .await
+        .ok_or_else(|| miette::miette!("No session info available"))?;
+    let handle = resolve_publishing_handle(&agent, &did).await?;
+
+    println!("Publishing as @{}", handle.as_ref());
 
-        // Build Entry record with blobs
-        use jacquard::types::blob::BlobRef;
-        use jacquard::types::string::Datetime;
-        use weaver_api::sh_weaver::embed::images::{Image, Images};
-        use weaver_api::sh_weaver::notebook::entry::{Entry, EntryEmbeds};
-
-        let embeds = if !blobs.is_empty() {
-            // Build images from blobs
-            let images: Vec<Image> = blobs
-                .iter()
-                .map(|blob_info| {
-                    Image::new()
-                        .image(BlobRef::Blob(blob_info.blob.clone()))
-                        .alt(blob_info.alt.as_ref().map(|a| a.as_ref()).unwrap_or(""))
-                        .maybe_name(Some(blob_info.name.as_str().into()))
-                        .build()
-                })
-                .collect();
-
-            Some(EntryEmbeds {
-                images: Some(Images::new().images(images).build()),
-                externals: None,
-                records: None,
-                records_with_media: None,
-                videos: None,
-                extra_data: None,
+    // No vault to walk: the file itself is the whole vault, so wikilinks
+    // to other entries stay unresolved rather than erroring.
+    let vault_arc: Arc<[PathBuf]> = Arc::from([path.clone()]);
+    let context = AtProtoPreprocessContext::new(vault_arc.clone(), notebook.clone(), agent.clone())
+        .with_creator(did.clone().into_static(), handle.clone().into_static());
+
+    publish_entry_source(
+        &agent, &context, &vault_arc, &path, &contents, &notebook, crosspost,
+    )
+    .await?;
+
+    println!("✓ Published 1 entry");
+
+    Ok(())
+}
+
+/// Preprocess one markdown source and upsert it as a notebook entry.
+///
+/// Shared by the vault publish loop and `publish-entry`, which feeds a
+/// single file or stdin through the same pipeline.
+async fn publish_entry_source(
+    agent: &Arc<Agent<OAuthSession<JacquardResolver, FileAuthStore>>>,
+    context: &AtProtoPreprocessContext<OAuthSession<JacquardResolver, FileAuthStore>>,
+    vault_arc: &Arc<[PathBuf]>,
+    file_path: &Path,
+    contents: &str,
+    notebook_title: &str,
+    crosspost: bool,
+) -> Result<()> {
+    // Clone context for this file
+    let mut file_context = context.clone();
+    file_context.set_current_path(file_path.to_path_buf());
+    let callback = Some(VaultBrokenLinkCallback {
+        vault_contents: vault_arc.clone(),
+    });
+
+    // Parse markdown
+    use markdown_weaver::Parser;
+    use weaver_renderer::default_md_options;
+    let parser = Parser::new_with_broken_link_callback(&contents, default_md_options(), callback)
+        .into_offset_iter();
+    let iterator = weaver_renderer::ContextIterator::default(parser);
+
+    // Process through NotebookProcessor
+    use n0_future::StreamExt;
+    use weaver_renderer::{NotebookContext, NotebookProcessor};
+    let mut processor = NotebookProcessor::new(file_context.clone(), iterator);
+
+    // Write canonical markdown with MarkdownWriter
+    use markdown_weaver_escape::FmtWriter;
+    use weaver_renderer::atproto::MarkdownWriter;
+    let mut output = String::new();
+    let mut md_writer = MarkdownWriter::new(FmtWriter(&mut output));
+
+    // Process all events
+    while let Some((event, _)) = processor.next().await {
+        md_writer
+            .write_event(event)
+            .map_err(|e| miette::miette!("Failed to write markdown: {:?}", e))?;
+    }
+
+    // Extract blobs and entry metadata
+    let blobs = file_context.blobs();
+    let entry_title = file_context.entry_title();
+
+    if !blobs.is_empty() {
+        tracing::debug!("Uploaded {} image(s)", blobs.len());
+    }
+
+    // Build Entry record with blobs
+    use jacquard::types::blob::BlobRef;
+    use jacquard::types::string::Datetime;
+    use weaver_api::sh_weaver::embed::images::{Image, Images};
+    use weaver_api::sh_weaver::notebook::entry::{Entry, EntryEmbeds};
+
+    let embeds = if !blobs.is_empty() {
+        // Build images from blobs
+        let images: Vec<Image> = blobs
+            .iter()
+            .map(|blob_info| {
+                Image::new()
+                    .image(BlobRef::Blob(blob_info.blob.clone()))
+                    .alt(blob_info.alt.as_ref().map(|a| a.as_ref()).unwrap_or(""))
+                    .maybe_name(Some(blob_info.name.as_str().into()))
+                    .build()
             })
-        } else {
-            None
-        };
-
-        let entry = Entry::new()
-            .content(output.as_str())
-            .title(entry_title.as_ref())
-            .path(normalize_title_path(entry_title.as_ref()))
-            .created_at(Datetime::now())
-            .maybe_embeds(embeds)
-            .build();
-
-        // Use WeaverExt to upsert entry (handles notebook + entry creation/updates)
-        use jacquard::http_client::HttpClient;
-        use weaver_common::WeaverExt;
-        let (entry_ref, _, was_created) = agent
-            .upsert_entry(&title, entry_title.as_ref(), entry, None)
-            .await?;
-
-        if was_created {
-            println!("  ✓ Created new entry: {}", entry_ref.uri.as_ref());
-        } else {
-            println!("  ✓ Updated existing entry: {}", entry_ref.uri.as_ref());
-        }
+            .collect();
+
+        Some(EntryEmbeds {
+            images: Some(Images::new().images(images).build()),
+            externals: None,
+            records: None,
+            records_with_media: None,
+            videos: None,
+            extra_data: None,
+        })
+    } else {
+        None
+    };
+
+    let entry = Entry::new()
+        .content(output.as_str())
+        .title(entry_title.as_ref())
+        .path(normalize_title_path(entry_title.as_ref()))
+        .created_at(Datetime::now())
+        .maybe_embeds(embeds)
+        .build();
+
+    // Use WeaverExt to upsert entry (handles notebook + entry creation/updates)
+    use jacquard::http_client::HttpClient;
+    use weaver_common::WeaverExt;
+    let (entry_ref, _, was_created) = agent
+        .upsert_entry(notebook_title, entry_title.as_ref(), entry, None)
+        .await?;
+
+    if was_created {
+        println!("  ✓ Created new entry: {}", entry_ref.uri.as_ref());
+    } else {
+        println!("  ✓ Updated existing entry: {}", entry_ref.uri.as_ref());
     }
 
-    println!("✓ Published {} entries", md_files.len());
+    // Optionally announce the entry on Bluesky (previewed before sending).
+    if crosspost && was_created {
+        crosspost::crosspost_entry(&agent, entry_title.as_ref(), &entry_ref.uri, &output).await?;
+    }
 
     Ok(())
 }
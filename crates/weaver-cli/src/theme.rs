@@ -0,0 +1,67 @@
+//! Scaffolding for static site theme directories.
+//!
+//! `weaver theme init` writes commented template stubs that mirror what
+//! [`weaver_renderer::static_site::templates::SiteTemplates`] recognizes, so
+//! a fresh theme renders identically to the built-in layout until edited.
+
+use miette::{IntoDiagnostic, Result};
+use std::path::PathBuf;
+
+const HEADER_STUB: &str = "\
+<!-- header.html: written right after <body> opens on every page. -->
+<!-- Typical use: a site-wide masthead or navigation bar. -->
+<!--
+<header class=\"site-header\">
+  <nav><a href=\"/\">Home</a></nav>
+</header>
+-->
+";
+
+const FOOTER_STUB: &str = "\
+<!-- footer.html: written right before </body> on every page. -->
+<!--
+<footer class=\"site-footer\">
+  <p>Rendered with weaver.</p>
+</footer>
+-->
+";
+
+const PAGE_STUB: &str = "\
+<!-- page.html: wraps each rendered entry. -->
+<!-- The {{ content }} placeholder is replaced with the entry's HTML. -->
+{{ content }}
+";
+
+const CSS_STUB: &str = "\
+/* custom.css: copied into theme/ and linked from every page head. */
+/* Loaded after base.css and syntax.css, so rules here win ties. */
+";
+
+/// Scaffold a theme directory with commented template stubs.
+pub fn init_theme(dir: PathBuf) -> Result<()> {
+    if dir.exists() {
+        return Err(miette::miette!(
+            "Directory already exists: {}",
+            dir.display()
+        ));
+    }
+    std::fs::create_dir_all(&dir).into_diagnostic()?;
+
+    let stubs = [
+        ("header.html", HEADER_STUB),
+        ("footer.html", FOOTER_STUB),
+        ("page.html", PAGE_STUB),
+        ("custom.css", CSS_STUB),
+    ];
+    for (name, contents) in stubs {
+        std::fs::write(dir.join(name), contents).into_diagnostic()?;
+    }
+
+    println!("✓ Created theme scaffold: {}", dir.display());
+    println!(
+        "  Render with: weaver <source> <dest> --theme {}",
+        dir.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! Save a Bluesky thread as a markdown entry draft.
+//!
+//! Given any post URL in a thread, fetches the full thread from the public
+//! appview, keeps the posts written by the same author, and converts them
+//! (text, facets, images, embeds) into a single markdown draft so a thread
+//! can be turned into a long-form entry.
+
+use std::path::PathBuf;
+
+use jacquard::client::UnauthenticatedSession;
+use jacquard::identity::JacquardResolver;
+use jacquard::prelude::*;
+use jacquard::types::string::{AtUri, Did, Handle};
+use miette::{IntoDiagnostic, Result};
+use weaver_api::app_bsky::feed::get_post_thread::{GetPostThread, GetPostThreadOutputThread};
+use weaver_api::app_bsky::feed::post::Post;
+use weaver_api::app_bsky::feed::{
+    PostView, PostViewEmbed, ThreadViewPost, ThreadViewPostParent, ThreadViewPostRepliesItem,
+};
+use weaver_renderer::facet::{NormalizedFacet, render_faceted_markdown};
+
+/// Fetch a thread and write the same-author posts as a markdown draft.
+///
+/// `url` may be a bsky.app post link or an at:// URI of the post record.
+/// The draft is written to `out`, defaulting to `<rkey>.md` in the current
+/// directory.
+pub async fn save_thread(url: &str, out: Option<PathBuf>) -> Result<()> {
+    let client: UnauthenticatedSession<JacquardResolver> = UnauthenticatedSession::new_public();
+
+    let uri = resolve_post_url(&client, url).await?;
+    println!("→ Fetching thread for {}", uri.as_str());
+
+    let request = GetPostThread::new()
+        .uri(uri.clone())
+        .depth(1000)
+        .parent_height(1000)
+        .build();
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|e| miette::miette!("Failed to fetch thread: {}", e))?;
+    let output = response
+        .into_output()
+        .map_err(|e| miette::miette!("Failed to parse thread response: {}", e))?;
+
+    let target = match output.thread {
+        GetPostThreadOutputThread::ThreadViewPost(post) => *post,
+        GetPostThreadOutputThread::NotFoundPost(_) => {
+            return Err(miette::miette!("Post not found: {}", uri.as_str()));
+        }
+        GetPostThreadOutputThread::BlockedPost(_) => {
+            return Err(miette::miette!("Post is blocked: {}", uri.as_str()));
+        }
+        GetPostThreadOutputThread::Unknown(_) => {
+            return Err(miette::miette!("Unrecognized thread response"));
+        }
+    };
+
+    let author_did = target.post.author.did.clone();
+
+    // Climb to the topmost consecutive post by the same author, then collect
+    // the author's posts in the subtree below it.
+    let root = climb_to_author_root(&target, &author_did);
+    let mut posts: Vec<&PostView<'_>> = Vec::new();
+    collect_author_posts(root, &author_did, &mut posts);
+    posts.sort_by_key(|p| p.indexed_at.to_string());
+
+    if posts.is_empty() {
+        return Err(miette::miette!("No posts by the thread author were found"));
+    }
+    println!("✓ Found {} post(s) by @{}", posts.len(), root.post.author.handle.as_str());
+
+    let draft = render_thread_draft(&posts, url)?;
+
+    let out_path = out.unwrap_or_else(|| {
+        let rkey = uri.as_str().rsplit('/').next().unwrap_or("thread");
+        PathBuf::from(format!("{}.md", rkey))
+    });
+    std::fs::write(&out_path, draft).into_diagnostic()?;
+    println!("✓ Draft saved to {}", out_path.display());
+
+    Ok(())
+}
+
+/// Resolve a bsky.app post link or at:// URI to the post's at:// URI.
+async fn resolve_post_url(
+    client: &UnauthenticatedSession<JacquardResolver>,
+    url: &str,
+) -> Result<AtUri<'static>> {
+    if url.starts_with("at://") {
+        return AtUri::new_owned(url.to_string())
+            .map_err(|e| miette::miette!("Invalid at:// URI: {}", e));
+    }
+
+    // https://bsky.app/profile/{actor}/post/{rkey}
+    let rest = url
+        .strip_prefix("https://bsky.app/profile/")
+        .or_else(|| url.strip_prefix("https://deer.social/profile/"))
+        .ok_or_else(|| miette::miette!("Expected a bsky.app post URL or at:// URI: {}", url))?;
+    let (actor, rkey) = rest
+        .split_once("/post/")
+        .ok_or_else(|| miette::miette!("Expected a post URL, got: {}", url))?;
+    let rkey = rkey.trim_end_matches('/');
+
+    let did = if actor.starts_with("did:") {
+        Did::new_owned(actor.to_string()).map_err(|e| miette::miette!("Invalid DID: {}", e))?
+    } else {
+        let handle =
+            Handle::new(actor).map_err(|e| miette::miette!("Invalid handle '{}': {}", actor, e))?;
+        client
+            .resolve_handle(&handle)
+            .await
+            .map_err(|e| miette::miette!("Failed to resolve @{}: {}", actor, e))?
+            .into_static()
+    };
+
+    AtUri::new_owned(format!("at://{}/app.bsky.feed.post/{}", did.as_str(), rkey))
+        .map_err(|e| miette::miette!("Invalid post URI: {}", e))
+}
+
+/// Walk up the parent chain while the parent is by the same author.
+fn climb_to_author_root<'t, 'a>(
+    mut node: &'t ThreadViewPost<'a>,
+    author: &Did<'_>,
+) -> &'t ThreadViewPost<'a> {
+    while let Some(ThreadViewPostParent::ThreadViewPost(parent)) = node.parent.as_ref() {
+        if parent.post.author.did.as_str() != author.as_str() {
+            break;
+        }
+        node = parent;
+    }
+    node
+}
+
+/// Collect posts by `author` from this node downward.
+///
+/// Replies by other accounts terminate their branch: a thread interrupted by
+/// someone else's reply doesn't pull in the author's answers to them.
+fn collect_author_posts<'t, 'a>(
+    node: &'t ThreadViewPost<'a>,
+    author: &Did<'_>,
+    out: &mut Vec<&'t PostView<'a>>,
+) {
+    if node.post.author.did.as_str() != author.as_str() {
+        return;
+    }
+    out.push(&node.post);
+    if let Some(replies) = node.replies.as_ref() {
+        for reply in replies {
+            if let ThreadViewPostRepliesItem::ThreadViewPost(child) = reply {
+                collect_author_posts(child, author, out);
+            }
+        }
+    }
+}
+
+/// Render the collected posts as a markdown draft.
+fn render_thread_draft(posts: &[&PostView<'_>], source_url: &str) -> Result<String> {
+    let mut draft = String::new();
+
+    for (i, view) in posts.iter().enumerate() {
+        if i > 0 {
+            draft.push_str("\n\n");
+        }
+        draft.push_str(&render_post(view)?);
+    }
+
+    // Keep a reference back to the source thread at the end of the draft.
+    draft.push_str(&format!("\n\n---\n\n*Imported from [a Bluesky thread]({})*\n", source_url));
+
+    Ok(draft)
+}
+
+/// Render one post: faceted text plus any embeds.
+fn render_post(view: &PostView<'_>) -> Result<String> {
+    let mut out = String::new();
+
+    // The record carries the canonical text and facets.
+    let post: Post<'_> = jacquard::from_data(&view.record)
+        .map_err(|e| miette::miette!("Failed to parse post record: {}", e))?;
+
+    let facets: Vec<NormalizedFacet<'_>> = post
+        .facets
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(NormalizedFacet::from)
+        .collect();
+    let text = render_faceted_markdown(post.text.as_ref(), &facets)
+        .map_err(|e| miette::miette!("Failed to render post text: {}", e))?;
+
+    if !text.trim().is_empty() {
+        out.push_str(text.trim_end());
+    }
+
+    // Embeds come from the view, which carries resolved CDN URLs.
+    if let Some(embed) = view.embed.as_ref() {
+        let rendered = render_embed(embed);
+        if !rendered.is_empty() {
+            if !out.is_empty() {
+                out.push_str("\n\n");
+            }
+            out.push_str(&rendered);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Render a post embed as markdown.
+fn render_embed(embed: &PostViewEmbed<'_>) -> String {
+    let mut out = String::new();
+    match embed {
+        PostViewEmbed::ImagesView(images) => {
+            for (i, image) in images.images.iter().enumerate() {
+                if i > 0 {
+                    out.push_str("\n\n");
+                }
+                out.push_str(&format!(
+                    "![{}]({})",
+                    image.alt.as_ref(),
+                    image.fullsize.as_ref()
+                ));
+            }
+        }
+        PostViewEmbed::ExternalView(external) => {
+            let ext = &external.external;
+            out.push_str(&format!("[{}]({})", ext.title.as_ref(), ext.uri.as_ref()));
+        }
+        PostViewEmbed::RecordView(record) => {
+            // Reference the quoted record as an embed; the renderer resolves
+            // at:// URIs when the entry is published.
+            use weaver_api::app_bsky::embed::record::ViewUnionRecord;
+            if let ViewUnionRecord::ViewRecord(quoted) = &record.record {
+                out.push_str(&format!("![]({})", quoted.uri.as_str()));
+            }
+        }
+        PostViewEmbed::RecordWithMediaView(rwm) => {
+            use weaver_api::app_bsky::embed::record_with_media::ViewMedia;
+            match &rwm.media {
+                ViewMedia::ImagesView(images) => {
+                    for (i, image) in images.images.iter().enumerate() {
+                        if i > 0 {
+                            out.push_str("\n\n");
+                        }
+                        out.push_str(&format!(
+                            "![{}]({})",
+                            image.alt.as_ref(),
+                            image.fullsize.as_ref()
+                        ));
+                    }
+                }
+                ViewMedia::ExternalView(ext) => {
+                    out.push_str(&format!(
+                        "[{}]({})",
+                        ext.external.title.as_ref(),
+                        ext.external.uri.as_ref()
+                    ));
+                }
+                ViewMedia::VideoView(_) | ViewMedia::Unknown(_) => {}
+            }
+            use weaver_api::app_bsky::embed::record::ViewUnionRecord;
+            if let ViewUnionRecord::ViewRecord(quoted) = &rwm.record.record {
+                if !out.is_empty() {
+                    out.push_str("\n\n");
+                }
+                out.push_str(&format!("![]({})", quoted.uri.as_str()));
+            }
+        }
+        // Video has no markdown representation yet.
+        PostViewEmbed::VideoView(_) | PostViewEmbed::Unknown(_) => {}
+    }
+    out
+}
@@ -0,0 +1,198 @@
+//! Workspace configuration (`weaver.kdl`) at the vault root.
+//!
+//! A workspace maps subdirectories to notebooks so one invocation renders or
+//! publishes the whole vault instead of one notebook at a time:
+//!
+//! ```kdl
+//! base-url "https://notes.example.com"
+//! ignore "drafts/**" "*.tmp"
+//! notebook "garden" title="Digital garden"
+//! notebook "recipes" title="Recipes"
+//! deploy target="github-pages" repo="user/site" branch="gh-pages"
+//! ```
+//!
+//! The `deploy` node supplies defaults for `weaver deploy`; flags given on
+//! the command line still win.
+
+use clap::ValueEnum;
+use kdl::{KdlDocument, KdlNode, KdlValue};
+use miette::{IntoDiagnostic, Result};
+use std::path::{Path, PathBuf};
+
+use crate::deploy::DeployTargetKind;
+
+/// Filename looked up at the vault root.
+pub const WORKSPACE_FILE: &str = "weaver.kdl";
+
+/// Parsed workspace configuration.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceConfig {
+    /// Subdirectory -> notebook mappings, in declaration order.
+    pub notebooks: Vec<WorkspaceNotebook>,
+    /// Gitignore-style globs excluded from rendering and publishing.
+    pub ignore: Vec<String>,
+    /// Public base URL of the deployed site.
+    pub base_url: Option<String>,
+    /// Default deploy target and options.
+    pub deploy: Option<WorkspaceDeploy>,
+}
+
+/// One notebook within a workspace.
+#[derive(Debug, Clone)]
+pub struct WorkspaceNotebook {
+    /// Subdirectory relative to the vault root.
+    pub dir: PathBuf,
+    /// Notebook title used when publishing; defaults to the directory name.
+    pub title: String,
+}
+
+/// Deploy defaults declared in the workspace file.
+#[derive(Debug, Clone)]
+pub struct WorkspaceDeploy {
+    pub target: DeployTargetKind,
+    pub bucket: Option<String>,
+    pub repo: Option<String>,
+    pub branch: Option<String>,
+    pub site: Option<String>,
+}
+
+impl WorkspaceConfig {
+    /// Load `weaver.kdl` from the vault root, if present.
+    pub fn load(root: &Path) -> Result<Option<Self>> {
+        let path = root.join(WORKSPACE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path).into_diagnostic()?;
+        Ok(Some(Self::parse(&contents)?))
+    }
+
+    /// Parse workspace configuration from KDL source.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let doc: KdlDocument = contents.parse()?;
+
+        let mut config = Self::default();
+        for node in doc.nodes() {
+            match node.name().value() {
+                "base-url" => {
+                    config.base_url = Some(required_arg(node, "base-url")?.to_string());
+                }
+                "ignore" => {
+                    for value in positional_args(node) {
+                        let glob = value
+                            .as_string()
+                            .ok_or_else(|| miette::miette!("ignore globs must be strings"))?;
+                        config.ignore.push(glob.to_string());
+                    }
+                }
+                "notebook" => {
+                    let dir = required_arg(node, "notebook")?;
+                    let title = prop(node, "title").unwrap_or(dir).to_string();
+                    config.notebooks.push(WorkspaceNotebook {
+                        dir: PathBuf::from(dir),
+                        title,
+                    });
+                }
+                "deploy" => {
+                    let target = prop(node, "target")
+                        .ok_or_else(|| miette::miette!("deploy node requires a target property"))?;
+                    let target = DeployTargetKind::from_str(target, true)
+                        .map_err(|e| miette::miette!("invalid deploy target: {}", e))?;
+                    config.deploy = Some(WorkspaceDeploy {
+                        target,
+                        bucket: prop(node, "bucket").map(str::to_string),
+                        repo: prop(node, "repo").map(str::to_string),
+                        branch: prop(node, "branch").map(str::to_string),
+                        site: prop(node, "site").map(str::to_string),
+                    });
+                }
+                // Reject unknown nodes so typos surface instead of being
+                // silently dropped.
+                other => {
+                    return Err(miette::miette!(
+                        "unknown node '{}' in {}",
+                        other,
+                        WORKSPACE_FILE
+                    ));
+                }
+            }
+        }
+
+        if config.notebooks.is_empty() {
+            return Err(miette::miette!(
+                "{} must declare at least one notebook",
+                WORKSPACE_FILE
+            ));
+        }
+
+        Ok(config)
+    }
+}
+
+/// First positional argument of a node, as a string.
+fn required_arg<'a>(node: &'a KdlNode, name: &str) -> Result<&'a str> {
+    positional_args(node)
+        .next()
+        .and_then(KdlValue::as_string)
+        .ok_or_else(|| miette::miette!("{} requires a string argument", name))
+}
+
+/// Positional (unnamed) arguments of a node.
+fn positional_args(node: &KdlNode) -> impl Iterator<Item = &KdlValue> {
+    node.entries()
+        .iter()
+        .filter(|e| e.name().is_none())
+        .map(|e| e.value())
+}
+
+/// Named property of a node, as a string.
+fn prop<'a>(node: &'a KdlNode, key: &str) -> Option<&'a str> {
+    node.entries()
+        .iter()
+        .find(|e| e.name().map(|n| n.value()) == Some(key))
+        .and_then(|e| e.value().as_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_workspace() {
+        let config = WorkspaceConfig::parse(
+            r#"
+base-url "https://notes.example.com"
+ignore "drafts/**" "*.tmp"
+notebook "garden" title="Digital garden"
+notebook "recipes"
+deploy target="github-pages" repo="user/site" branch="pages"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.base_url.as_deref(),
+            Some("https://notes.example.com")
+        );
+        assert_eq!(config.ignore, vec!["drafts/**", "*.tmp"]);
+        assert_eq!(config.notebooks.len(), 2);
+        assert_eq!(config.notebooks[0].title, "Digital garden");
+        // Title falls back to the directory name.
+        assert_eq!(config.notebooks[1].title, "recipes");
+
+        let deploy = config.deploy.unwrap();
+        assert_eq!(deploy.target, DeployTargetKind::GithubPages);
+        assert_eq!(deploy.repo.as_deref(), Some("user/site"));
+        assert_eq!(deploy.branch.as_deref(), Some("pages"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_nodes() {
+        assert!(WorkspaceConfig::parse("notebok \"typo\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_requires_a_notebook() {
+        assert!(WorkspaceConfig::parse("base-url \"https://x\"").is_err());
+    }
+}
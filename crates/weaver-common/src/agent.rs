@@ -7,7 +7,8 @@ pub use weaver_api::sh_weaver::notebook::{
 
 // Re-export jacquard for convenience
 use crate::constellation::{GetBacklinksQuery, RecordId};
-use crate::error::WeaverError;
+use crate::error::{OpError, WeaverError, WeaverErrorKind};
+use crate::retry::{Idempotency, RetryPolicy};
 #[allow(unused_imports)]
 use crate::{PublishResult, W_TICKER, normalize_title_path};
 pub use jacquard;
@@ -58,6 +59,24 @@ pub fn title_matches(value: &str, search: &str) -> bool {
     false
 }
 
+/// Build the sidecar index URI for a notebook.
+///
+/// The index record shares its rkey with the book record, so it is
+/// addressable from the notebook URI alone - no lookup required.
+fn entry_index_uri(notebook_uri: &AtUri<'_>) -> Option<AtUri<'static>> {
+    use jacquard::types::collection::Collection;
+    use weaver_api::sh_weaver::notebook::index::NotebookIndex;
+
+    let rkey = notebook_uri.rkey()?;
+    AtUri::new_owned(format!(
+        "at://{}/{}/{}",
+        notebook_uri.authority(),
+        NotebookIndex::NSID,
+        rkey.0.as_ref()
+    ))
+    .ok()
+}
+
 /// Extension trait providing weaver-specific multi-step operations on Agent
 ///
 /// This trait extends jacquard's Agent with notebook-specific workflows that
@@ -250,6 +269,163 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         }
     }
 
+    /// Fetch a notebook's full `Book` record for editing
+    ///
+    /// Unlike [`get_notebook_by_uri`](WeaverExt::get_notebook_by_uri), this
+    /// returns the whole record (including `extra_data`) so callers can write
+    /// back a modified copy without dropping fields they don't touch.
+    fn fetch_notebook_book(
+        &self,
+        uri: &AtUri<'_>,
+    ) -> impl Future<Output = Result<weaver_api::sh_weaver::notebook::book::Book<'static>, WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            use weaver_api::sh_weaver::notebook::book::Book;
+
+            let notebook = self
+                .get_record::<Book>(uri)
+                .await?
+                .into_output()
+                .map_err(|_| {
+                    AgentError::from(ClientError::invalid_request("Failed to parse Book record"))
+                })?;
+
+            Ok(notebook.value.into_static())
+        }
+    }
+
+    /// Write a modified `Book` record back to its repo
+    ///
+    /// Bumps `updatedAt` on every write since it's used for canonicality
+    /// tiebreaking; all structural notebook mutations route through here to
+    /// keep it monotonic.
+    fn put_notebook_book(
+        &self,
+        uri: &AtUri<'_>,
+        mut book: weaver_api::sh_weaver::notebook::book::Book<'static>,
+    ) -> impl Future<Output = Result<(), WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            use jacquard::types::collection::Collection;
+            use jacquard::types::nsid::Nsid;
+            use weaver_api::com_atproto::repo::put_record::PutRecord;
+            use weaver_api::sh_weaver::notebook::book::Book;
+
+            let rkey = uri
+                .rkey()
+                .ok_or_else(|| {
+                    WeaverError::InvalidNotebook(format!("Notebook URI missing rkey: {}", uri))
+                })?
+                .clone()
+                .into_static();
+
+            book.updated_at = Some(Datetime::now());
+
+            let record = jacquard::to_data(&book).map_err(|_| {
+                AgentError::from(ClientError::invalid_request(
+                    "Failed to serialize Book record",
+                ))
+            })?;
+
+            let request = PutRecord::new()
+                .repo(uri.authority().clone().into_static())
+                .collection(Nsid::raw(Book::NSID))
+                .rkey(rkey)
+                .record(record)
+                .build();
+
+            let response = self.send(request).await.map_err(AgentError::from)?;
+            response.into_output().map_err(|_| {
+                AgentError::from(ClientError::invalid_request("Failed to update notebook"))
+            })?;
+
+            Ok(())
+        }
+    }
+
+    /// Replace a notebook's entry list wholesale
+    ///
+    /// Covers both reordering and removal: callers pass the full list in its
+    /// new order. The record is overwritten rather than patched since
+    /// `entry_list` is the single source of truth for book structure.
+    fn set_notebook_entry_list(
+        &self,
+        uri: &AtUri<'_>,
+        entry_list: Vec<StrongRef<'static>>,
+    ) -> impl Future<Output = Result<(), WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            let mut book = self.fetch_notebook_book(uri).await?;
+            book.entry_list = entry_list;
+            self.put_notebook_book(uri, book).await
+        }
+    }
+
+    /// Rename a notebook, regenerating its path from the new title
+    ///
+    /// The path follows the same normalization rule as notebook creation so
+    /// renamed books stay addressable by title-derived URLs.
+    fn rename_notebook(
+        &self,
+        uri: &AtUri<'_>,
+        new_title: &str,
+    ) -> impl Future<Output = Result<(), WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            if new_title.trim().is_empty() {
+                return Err(WeaverError::InvalidNotebook(
+                    "Notebook title cannot be empty".to_string(),
+                ));
+            }
+
+            let mut book = self.fetch_notebook_book(uri).await?;
+            book.title = Some(new_title.to_string().into());
+            book.path = Some(normalize_title_path(new_title).into());
+            self.put_notebook_book(uri, book).await
+        }
+    }
+
+    /// Set or clear a notebook's cover image
+    ///
+    /// The book lexicon has no cover field, so the blob rides in `extra_data`
+    /// under a `cover` key (the same approach as `publishedBlobUri` tracking
+    /// on draft images). Passing `None` clears the cover.
+    fn set_notebook_cover(
+        &self,
+        uri: &AtUri<'_>,
+        cover: Option<jacquard::types::blob::Blob<'static>>,
+    ) -> impl Future<Output = Result<(), WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            use jacquard::types::value::Data;
+
+            let mut book = self.fetch_notebook_book(uri).await?;
+            match cover {
+                Some(blob) => {
+                    book.extra_data
+                        .get_or_insert_with(Default::default)
+                        .insert("cover".into(), Data::Blob(blob));
+                }
+                None => {
+                    if let Some(extra) = book.extra_data.as_mut() {
+                        extra.remove("cover");
+                    }
+                }
+            }
+            self.put_notebook_book(uri, book).await
+        }
+    }
+
     /// Find or create an entry within a notebook (with pre-fetched notebook data)
     ///
     /// This variant accepts notebook URI and entry_refs directly to avoid redundant
@@ -268,6 +444,11 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         Self: Sized,
     {
         async move {
+            // The entry is moved into create_record below, so capture what
+            // the sidecar index needs up front.
+            let index_title = entry.title.to_string();
+            let index_path = entry.path.to_string();
+
             // If we have an existing rkey, try to find and update that specific entry
             if let Some(rkey) = existing_rkey {
                 // Check if this entry exists in the notebook by comparing rkeys
@@ -288,6 +469,13 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                             .uri(output.uri.into_static())
                             .cid(output.cid.into_static())
                             .build();
+                        self.record_entry_in_index(
+                            &notebook_uri,
+                            &updated_ref.uri,
+                            &index_title,
+                            &index_path,
+                        )
+                        .await;
                         return Ok((updated_ref, notebook_uri, false));
                     }
                 }
@@ -312,6 +500,9 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 })
                 .await?;
 
+                self.record_entry_in_index(&notebook_uri, &new_ref.uri, &index_title, &index_path)
+                    .await;
+
                 return Ok((new_ref, notebook_uri, true));
             }
 
@@ -336,36 +527,75 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 })
                 .await?;
 
+                self.record_entry_in_index(&notebook_uri, &new_ref.uri, &index_title, &index_path)
+                    .await;
+
                 return Ok((new_ref, notebook_uri, true));
             }
 
-            // Check if entry with this title exists in the notebook
-            // O(n) network calls - unavoidable without title indexing
-            for entry_ref in &entry_refs {
-                let existing = self
-                    .get_record::<entry::Entry>(&entry_ref.uri)
-                    .await
-                    .map_err(|e| AgentError::from(ClientError::from(e)))?;
-                if let Ok(existing_entry) = existing.parse() {
-                    if existing_entry.value.title == entry_title {
-                        // Update existing entry
-                        let output = self
-                            .update_record::<entry::Entry>(&entry_ref.uri, |e| {
-                                e.content = entry.content.clone();
-                                e.embeds = entry.embeds.clone();
-                                e.tags = entry.tags.clone();
-                                e.updated_at = Some(Datetime::now());
-                            })
-                            .await?;
-                        let updated_ref = StrongRef::new()
-                            .uri(output.uri.into_static())
-                            .cid(output.cid.into_static())
-                            .build();
-                        return Ok((updated_ref, notebook_uri, false));
+            // Check if entry with this title exists in the notebook.
+            // Consult the sidecar index first (one fetch plus one verify);
+            // the fetched record must confirm the title since the index may
+            // lag a rename.
+            let mut matched: Option<(StrongRef<'static>, String)> = None;
+            if let Some(index) = self.fetch_entry_index(&notebook_uri).await
+                && let Some(mapped) = index
+                    .entries
+                    .iter()
+                    .find(|e| e.title.as_ref() == entry_title)
+                && let Some(entry_ref) = entry_refs
+                    .iter()
+                    .find(|r| r.uri.rkey().map(|k| k.0.as_str()) == Some(mapped.rkey.as_ref()))
+                && let Ok(existing) = self.get_record::<entry::Entry>(&entry_ref.uri).await
+                && let Ok(existing_entry) = existing.parse()
+                && existing_entry.value.title == entry_title
+            {
+                matched = Some((entry_ref.clone(), existing_entry.value.path.to_string()));
+            }
+
+            // Fall back to the O(n) scan when the index is missing or stale
+            if matched.is_none() {
+                for entry_ref in &entry_refs {
+                    let existing = self
+                        .get_record::<entry::Entry>(&entry_ref.uri)
+                        .await
+                        .map_err(|e| AgentError::from(ClientError::from(e)))?;
+                    if let Ok(existing_entry) = existing.parse() {
+                        if existing_entry.value.title == entry_title {
+                            matched =
+                                Some((entry_ref.clone(), existing_entry.value.path.to_string()));
+                            break;
+                        }
                     }
                 }
             }
 
+            if let Some((entry_ref, existing_path)) = matched {
+                // Update existing entry
+                let output = self
+                    .update_record::<entry::Entry>(&entry_ref.uri, |e| {
+                        e.content = entry.content.clone();
+                        e.embeds = entry.embeds.clone();
+                        e.tags = entry.tags.clone();
+                        e.updated_at = Some(Datetime::now());
+                    })
+                    .await?;
+                let updated_ref = StrongRef::new()
+                    .uri(output.uri.into_static())
+                    .cid(output.cid.into_static())
+                    .build();
+                // This branch leaves the record's title and path untouched,
+                // so the index keeps the entry's existing path.
+                self.record_entry_in_index(
+                    &notebook_uri,
+                    &updated_ref.uri,
+                    entry_title,
+                    &existing_path,
+                )
+                .await;
+                return Ok((updated_ref, notebook_uri, false));
+            }
+
             // Entry doesn't exist, create it
             let response = self.create_record(entry, None).await?;
             let new_ref = StrongRef::new()
@@ -385,6 +615,9 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
             })
             .await?;
 
+            self.record_entry_in_index(&notebook_uri, &new_ref.uri, &index_title, &index_path)
+                .await;
+
             Ok((new_ref, notebook_uri, true))
         }
     }
@@ -414,7 +647,7 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         async move {
             // Get our own DID
             let (did, _) = self.session_info().await.ok_or_else(|| {
-                AgentError::from(ClientError::invalid_request("No session info available"))
+                OpError::new(WeaverErrorKind::AuthExpired, "no session info available")
             })?;
 
             // Find or create notebook
@@ -690,9 +923,140 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         }
     }
 
+    /// Fetch a notebook's sidecar title index, if one exists
+    ///
+    /// The index is an optimization hint: any failure (missing record,
+    /// network error, parse error) degrades to `None` and callers fall
+    /// back to scanning the entry list.
+    fn fetch_entry_index(
+        &self,
+        notebook_uri: &AtUri<'_>,
+    ) -> impl Future<Output = Option<weaver_api::sh_weaver::notebook::index::NotebookIndex<'static>>>
+    where
+        Self: Sized,
+    {
+        async move {
+            use weaver_api::sh_weaver::notebook::index::NotebookIndex;
+
+            let index_uri = entry_index_uri(notebook_uri)?;
+            let resp = self.get_record::<NotebookIndex>(&index_uri).await.ok()?;
+            let output = resp.into_output().ok()?;
+            Some(output.value.into_static())
+        }
+    }
+
+    /// Record a title/path -> rkey mapping in a notebook's sidecar index
+    ///
+    /// Best-effort wrapper around [`upsert_entry_index`](WeaverExt::upsert_entry_index):
+    /// the index is a derived structure, so a failed write is logged rather
+    /// than failing the publish - readers fall back to scanning when stale.
+    fn record_entry_in_index(
+        &self,
+        notebook_uri: &AtUri<'_>,
+        entry_uri: &AtUri<'_>,
+        title: &str,
+        path: &str,
+    ) -> impl Future<Output = ()>
+    where
+        Self: Sized,
+    {
+        async move {
+            let Some(entry_rkey) = entry_uri.rkey() else {
+                return;
+            };
+            if let Err(e) = self
+                .upsert_entry_index(notebook_uri, title, path, entry_rkey.0.as_ref())
+                .await
+            {
+                tracing::warn!("Failed to update notebook entry index: {}", e);
+            }
+        }
+    }
+
+    /// Write a title/path -> rkey mapping into a notebook's sidecar index
+    ///
+    /// Creates the index record (anchored to the notebook's current CID) if
+    /// it doesn't exist yet; otherwise replaces any stale mapping for the
+    /// same entry rkey.
+    fn upsert_entry_index(
+        &self,
+        notebook_uri: &AtUri<'_>,
+        title: &str,
+        path: &str,
+        entry_rkey: &str,
+    ) -> impl Future<Output = Result<(), WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            use jacquard::types::collection::Collection;
+            use jacquard::types::nsid::Nsid;
+            use weaver_api::com_atproto::repo::put_record::PutRecord;
+            use weaver_api::sh_weaver::notebook::index::{IndexedEntry, NotebookIndex};
+
+            let rkey = notebook_uri
+                .rkey()
+                .ok_or_else(|| {
+                    WeaverError::InvalidNotebook(format!(
+                        "Notebook URI missing rkey: {}",
+                        notebook_uri
+                    ))
+                })?
+                .clone()
+                .into_static();
+
+            // Reuse the existing index when present; otherwise start a new
+            // one anchored to the notebook's current CID.
+            let mut index = match self.fetch_entry_index(notebook_uri).await {
+                Some(index) => index,
+                None => {
+                    let notebook_ref = self.confirm_record_ref(notebook_uri).await?;
+                    NotebookIndex::new()
+                        .entries(vec![])
+                        .notebook(notebook_ref)
+                        .build()
+                }
+            };
+
+            // Replace any stale mapping for the same entry.
+            index.entries.retain(|e| e.rkey.as_ref() != entry_rkey);
+            index.entries.push(
+                IndexedEntry::new()
+                    .path(path.to_string())
+                    .rkey(entry_rkey.to_string())
+                    .title(title.to_string())
+                    .build(),
+            );
+            index.updated_at = Some(Datetime::now());
+
+            let record = jacquard::to_data(&index).map_err(|_| {
+                AgentError::from(ClientError::invalid_request(
+                    "Failed to serialize notebook index",
+                ))
+            })?;
+
+            let request = PutRecord::new()
+                .repo(notebook_uri.authority().clone().into_static())
+                .collection(Nsid::raw(NotebookIndex::NSID))
+                .rkey(rkey)
+                .record(record)
+                .build();
+
+            let response = self.send(request).await.map_err(AgentError::from)?;
+            response.into_output().map_err(|_| {
+                AgentError::from(ClientError::invalid_request(
+                    "Failed to update notebook index",
+                ))
+            })?;
+
+            Ok(())
+        }
+    }
+
     /// Search for an entry by title within a notebook's entry list
     ///
-    /// O(n) network calls - unavoidable without title indexing.
+    /// Consults the sidecar title index first (constant fetches); falls
+    /// back to O(n) network calls when the index is missing or stale.
     /// Breaks early on match to minimize unnecessary fetches.
     fn entry_by_title<'a>(
         &self,
@@ -707,46 +1071,80 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
             use weaver_api::sh_weaver::notebook::BookEntryRef;
             use weaver_api::sh_weaver::notebook::entry::Entry;
 
-            for (index, entry_ref) in entries.iter().enumerate() {
-                let resp = self
-                    .get_record::<Entry>(&entry_ref.uri)
-                    .await
-                    .map_err(|e| AgentError::from(e))?;
-                if let Ok(entry) = resp.parse() {
-                    let path_matches = title_matches(entry.value.path.as_ref(), title);
-                    let title_field_matches = title_matches(entry.value.title.as_ref(), title);
-                    if path_matches || title_field_matches {
-                        // Build BookEntryView with prev/next
-                        let entry_view = self.fetch_entry_view(notebook, entry_ref).await?;
-
-                        let prev_entry = if index > 0 {
-                            let prev_entry_ref = &entries[index - 1];
-                            self.fetch_entry_view(notebook, prev_entry_ref).await.ok()
-                        } else {
-                            None
-                        }
-                        .map(|e| BookEntryRef::new().entry(e).build());
+            // Consult the sidecar index first: one fetch for the index plus
+            // one for the mapped entry, instead of one fetch per entry. The
+            // index may lag a rename, so the fetched record must confirm the
+            // match before we trust it.
+            let mut found: Option<(usize, Entry<'static>)> = None;
+            if let Some(index_record) = self.fetch_entry_index(&notebook.uri).await {
+                let mapped = index_record.entries.iter().find(|e| {
+                    title_matches(e.title.as_ref(), title) || title_matches(e.path.as_ref(), title)
+                });
+                if let Some(mapped) = mapped {
+                    let position = entries.iter().position(|entry_ref| {
+                        entry_ref.uri.rkey().map(|r| r.0.as_str()) == Some(mapped.rkey.as_ref())
+                    });
+                    if let Some(position) = position
+                        && let Ok(resp) = self.get_record::<Entry>(&entries[position].uri).await
+                        && let Ok(entry) = resp.parse()
+                        && (title_matches(entry.value.path.as_ref(), title)
+                            || title_matches(entry.value.title.as_ref(), title))
+                    {
+                        found = Some((position, entry.value.into_static()));
+                    }
+                }
+            }
 
-                        let next_entry = if index < entries.len() - 1 {
-                            let next_entry_ref = &entries[index + 1];
-                            self.fetch_entry_view(notebook, next_entry_ref).await.ok()
-                        } else {
-                            None
+            // Fall back to scanning when the index is missing or stale.
+            if found.is_none() {
+                for (index, entry_ref) in entries.iter().enumerate() {
+                    let resp = self
+                        .get_record::<Entry>(&entry_ref.uri)
+                        .await
+                        .map_err(|e| AgentError::from(e))?;
+                    if let Ok(entry) = resp.parse() {
+                        let path_matches = title_matches(entry.value.path.as_ref(), title);
+                        let title_field_matches = title_matches(entry.value.title.as_ref(), title);
+                        if path_matches || title_field_matches {
+                            found = Some((index, entry.value.into_static()));
+                            break;
                         }
-                        .map(|e| BookEntryRef::new().entry(e).build());
-
-                        let book_entry_view = BookEntryView::new()
-                            .entry(entry_view)
-                            .maybe_next(next_entry)
-                            .maybe_prev(prev_entry)
-                            .index(index as i64)
-                            .build();
-
-                        return Ok(Some((book_entry_view, entry.value.into_static())));
                     }
                 }
             }
-            Ok(None)
+
+            let Some((index, entry_value)) = found else {
+                return Ok(None);
+            };
+            let entry_ref = &entries[index];
+
+            // Build BookEntryView with prev/next
+            let entry_view = self.fetch_entry_view(notebook, entry_ref).await?;
+
+            let prev_entry = if index > 0 {
+                let prev_entry_ref = &entries[index - 1];
+                self.fetch_entry_view(notebook, prev_entry_ref).await.ok()
+            } else {
+                None
+            }
+            .map(|e| BookEntryRef::new().entry(e).build());
+
+            let next_entry = if index < entries.len() - 1 {
+                let next_entry_ref = &entries[index + 1];
+                self.fetch_entry_view(notebook, next_entry_ref).await.ok()
+            } else {
+                None
+            }
+            .map(|e| BookEntryRef::new().entry(e).build());
+
+            let book_entry_view = BookEntryView::new()
+                .entry(entry_view)
+                .maybe_next(next_entry)
+                .maybe_prev(prev_entry)
+                .index(index as i64)
+                .build();
+
+            Ok(Some((book_entry_view, entry_value)))
         }
     }
 
@@ -1155,9 +1553,9 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         async move {
             use weaver_api::sh_weaver::notebook::BookEntryRef;
 
-            let entry_ref = entries.get(index).ok_or_else(|| {
-                AgentError::from(ClientError::invalid_request("entry out of bounds"))
-            })?;
+            let entry_ref = entries
+                .get(index)
+                .ok_or_else(|| OpError::not_found("entry out of bounds"))?;
             let entry = self.fetch_entry_view(notebook, entry_ref).await?;
 
             let prev_entry = if index > 0 {
@@ -1195,9 +1593,9 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         async move {
             use weaver_api::sh_weaver::notebook::BookEntryRef;
 
-            let entry_ref = pages.get(index).ok_or_else(|| {
-                AgentError::from(ClientError::invalid_request("entry out of bounds"))
-            })?;
+            let entry_ref = pages
+                .get(index)
+                .ok_or_else(|| OpError::not_found("entry out of bounds"))?;
             let entry = self.fetch_page_view(notebook, entry_ref).await?;
 
             let prev_entry = if index > 0 {
@@ -1385,10 +1783,7 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 .send(&query)
                 .await
                 .map_err(|e| {
-                    AgentError::from(ClientError::invalid_request(format!(
-                        "Constellation query failed: {}",
-                        e
-                    )))
+                    OpError::constellation_unavailable(format!("constellation query failed: {}", e))
                 })?;
 
             let output = response.into_output().map_err(|e| {
@@ -1405,6 +1800,73 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
         }
     }
 
+    /// Find comment-like records referencing a published entry.
+    ///
+    /// Queries constellation backlinks for two sources: weaver comment
+    /// records pointing at the entry through `doc.value.entry.uri`, and
+    /// Bluesky posts that embed the entry record. Results keep discovery
+    /// order with weaver comments first.
+    fn find_entry_comments(
+        &self,
+        entry_uri: &AtUri<'_>,
+    ) -> impl Future<Output = Result<Vec<RecordId<'static>>, WeaverError>>
+    where
+        Self: Sized,
+    {
+        async move {
+            let constellation_url = Url::parse(CONSTELLATION_URL).map_err(|e| {
+                AgentError::from(ClientError::invalid_request(format!(
+                    "Invalid constellation URL: {}",
+                    e
+                )))
+            })?;
+
+            let mut found = Vec::new();
+            for source in [
+                "sh.weaver.edit.comment:doc.value.entry.uri",
+                "app.bsky.feed.post:embed.record.uri",
+            ] {
+                let mut cursor: Option<String> = None;
+                loop {
+                    let query = GetBacklinksQuery {
+                        subject: Uri::At(entry_uri.clone().into_static()),
+                        source: source.into(),
+                        cursor: cursor.map(Into::into),
+                        did: vec![],
+                        limit: 100,
+                    };
+
+                    let response = self
+                        .xrpc(constellation_url.clone())
+                        .send(&query)
+                        .await
+                        .map_err(|e| {
+                            OpError::constellation_unavailable(format!(
+                                "constellation query failed: {}",
+                                e
+                            ))
+                        })?;
+
+                    let output = response.into_output().map_err(|e| {
+                        AgentError::from(ClientError::invalid_request(format!(
+                            "Failed to parse constellation response: {}",
+                            e
+                        )))
+                    })?;
+
+                    found.extend(output.records.into_iter().map(|r| r.into_static()));
+
+                    match output.cursor {
+                        Some(c) => cursor = Some(c.to_string()),
+                        None => break,
+                    }
+                }
+            }
+
+            Ok(found)
+        }
+    }
+
     /// Fetch an entry directly by its rkey, returning the EntryView and raw Entry.
     ///
     /// This bypasses notebook context entirely - useful for standalone entries
@@ -1672,15 +2134,17 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 limit: 100,
             };
 
-            let response = self
-                .xrpc(constellation_url.clone())
-                .send(&invite_query)
+            // Constellation is a read-only index; a backlink query is
+            // safe to replay on a transient failure.
+            let response = RetryPolicy::default()
+                .run(
+                    Idempotency::Idempotent,
+                    "constellation.getBacklinks",
+                    || self.xrpc(constellation_url.clone()).send(&invite_query),
+                )
                 .await
                 .map_err(|e| {
-                    AgentError::from(ClientError::invalid_request(format!(
-                        "Constellation query failed: {}",
-                        e
-                    )))
+                    OpError::constellation_unavailable(format!("constellation query failed: {}", e))
                 })?;
 
             let invite_output = response.into_output().map_err(|e| {
@@ -1895,6 +2359,14 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
             const INVITE_NSID: &str = "sh.weaver.collab.invite";
             const ACCEPT_NSID: &str = "sh.weaver.collab.accept";
 
+            // Resolving permissions costs several constellation queries
+            // plus a record fetch per invite; serve cached state when
+            // fresh (writers invalidate on local invite/accept changes).
+            #[cfg(feature = "cache")]
+            if let Some(cached) = crate::permission_cache::get(resource_uri) {
+                return Ok(cached);
+            }
+
             let constellation_url = Url::parse(CONSTELLATION_URL).map_err(|e| {
                 AgentError::from(ClientError::invalid_request(format!(
                     "Invalid constellation URL: {}",
@@ -1953,15 +2425,19 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 limit: 100,
             };
 
-            let invite_response = self
-                .xrpc(constellation_url.clone())
-                .send(&invite_query)
+            // Read-only index query; safe to replay on transient failures.
+            let invite_response = RetryPolicy::default()
+                .run(
+                    Idempotency::Idempotent,
+                    "constellation.getBacklinks",
+                    || self.xrpc(constellation_url.clone()).send(&invite_query),
+                )
                 .await
                 .map_err(|e| {
-                    AgentError::from(ClientError::invalid_request(format!(
-                        "Constellation invite query failed: {}",
+                    OpError::constellation_unavailable(format!(
+                        "constellation invite query failed: {}",
                         e
-                    )))
+                    ))
                 })?;
             let invite_output = invite_response.into_output().map_err(|e| {
                 AgentError::from(ClientError::invalid_request(format!(
@@ -2011,10 +2487,10 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                     .send(&accept_query)
                     .await
                     .map_err(|e| {
-                        AgentError::from(ClientError::invalid_request(format!(
-                            "Constellation accept query failed: {}",
+                        OpError::constellation_unavailable(format!(
+                            "constellation accept query failed: {}",
                             e
-                        )))
+                        ))
                     })?;
                 let accept_output = accept_response.into_output().map_err(|e| {
                     AgentError::from(ClientError::invalid_request(format!(
@@ -2094,10 +2570,10 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                         .send(&notebook_invite_query)
                         .await
                         .map_err(|e| {
-                            AgentError::from(ClientError::invalid_request(format!(
-                                "Constellation notebook invite query failed: {}",
+                            OpError::constellation_unavailable(format!(
+                                "constellation notebook invite query failed: {}",
                                 e
-                            )))
+                            ))
                         })?;
                     let notebook_invite_output =
                         notebook_invite_response.into_output().map_err(|e| {
@@ -2151,10 +2627,10 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                             .send(&accept_query)
                             .await
                             .map_err(|e| {
-                                AgentError::from(ClientError::invalid_request(format!(
-                                    "Constellation accept query failed: {}",
+                                OpError::constellation_unavailable(format!(
+                                    "constellation accept query failed: {}",
                                     e
-                                )))
+                                ))
                             })?;
                         let accept_output = accept_response.into_output().map_err(|e| {
                             AgentError::from(ClientError::invalid_request(format!(
@@ -2203,10 +2679,15 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 }
             }
 
-            Ok(PermissionsState::new()
+            let permissions = PermissionsState::new()
                 .editors(editors)
                 .build()
-                .into_static())
+                .into_static();
+
+            #[cfg(feature = "cache")]
+            crate::permission_cache::insert(resource_uri, permissions.clone());
+
+            Ok(permissions)
         }
     }
 
@@ -2338,9 +2819,10 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
             use weaver_api::com_atproto::repo::list_records::ListRecords;
             use weaver_api::sh_weaver::collab::session::Session;
 
-            let (did, _) = self.session_info().await.ok_or_else(|| {
-                AgentError::from(ClientError::invalid_request("No active session"))
-            })?;
+            let (did, _) = self
+                .session_info()
+                .await
+                .ok_or_else(|| OpError::new(WeaverErrorKind::AuthExpired, "no active session"))?;
             let now = chrono::Utc::now();
             let mut deleted = 0u32;
 
@@ -2440,10 +2922,7 @@ pub trait WeaverExt: AgentSessionExt + XrpcExt + Send + Sync + Sized {
                 .send(&query)
                 .await
                 .map_err(|e| {
-                    AgentError::from(ClientError::invalid_request(format!(
-                        "Constellation query failed: {}",
-                        e
-                    )))
+                    OpError::constellation_unavailable(format!("constellation query failed: {}", e))
                 })?;
 
             let output = response.into_output().map_err(|e| {
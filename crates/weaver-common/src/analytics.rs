@@ -0,0 +1,131 @@
+//! View analytics types shared between the app and the index.
+//!
+//! Entry pages report views through a lightweight beacon route on the
+//! index ([`ViewBeacon`] is the POST body); owners read aggregates back
+//! through the `sh.weaver.analytics.getSummary` query, which the app sends
+//! like any other index endpoint via service proxying. Referrers are
+//! reduced to an origin before they leave the page, so neither side ever
+//! handles full referring URLs.
+
+use jacquard::{
+    CowStr, IntoStatic, XrpcRequest,
+    types::{did::Did, ident::AtIdentifier, string::AtUri},
+};
+use serde::{Deserialize, Serialize};
+
+/// Body of a `POST /analytics/beacon` request.
+///
+/// A beacon without `reading_ms` counts as a page view; one with it
+/// reports time spent reading and does not add to view counts, so the
+/// two can be sent independently (on mount and on leave).
+#[derive(Clone, Debug, Serialize, Deserialize, IntoStatic)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewBeacon<'a> {
+    /// The viewed entry record.
+    ///
+    /// No `#[serde(borrow)]` here: the index deserializes beacons from an
+    /// owned axum `Json` body, which requires owned deserialization.
+    pub uri: AtUri<'a>,
+    /// Referrer origin (scheme and host only), absent for direct visits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub referrer: Option<CowStr<'a>>,
+    /// Milliseconds spent on the page, for reading-time beacons.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading_ms: Option<u64>,
+}
+
+/// Reduce a referrer URL to its origin.
+///
+/// Full referring URLs can carry search terms and other per-reader data;
+/// the dashboard only needs "where from", so everything past the host is
+/// dropped. Unparseable values reduce to `None` rather than being stored
+/// verbatim.
+pub fn referrer_origin(referrer: &str) -> Option<String> {
+    let url = jacquard::url::Url::parse(referrer).ok()?;
+    let host = url.host_str()?;
+    Some(format!("{}://{}", url.scheme(), host))
+}
+
+fn get_default_days() -> u32 {
+    30
+}
+
+/// Private per-account analytics summary query.
+#[derive(Clone, Deserialize, Serialize, XrpcRequest, IntoStatic)]
+#[xrpc(
+    nsid = "sh.weaver.analytics.getSummary",
+    method = Query,
+    output = AnalyticsSummary,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAnalyticsSummary<'a> {
+    /// Handle or DID whose analytics to fetch (must match the caller).
+    pub actor: AtIdentifier<'a>,
+    /// How far back to aggregate, in days.
+    #[serde(default = "get_default_days")]
+    pub days: u32,
+}
+
+/// Aggregated view statistics for one account.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, IntoStatic)]
+#[serde(rename_all = "camelCase")]
+pub struct AnalyticsSummary<'a> {
+    #[serde(borrow)]
+    pub did: Did<'a>,
+    /// The aggregation window actually applied.
+    pub days: u32,
+    pub entries: Vec<EntryViewStats<'a>>,
+    pub referrers: Vec<ReferrerStats<'a>>,
+    pub reading_time: Vec<ReadingTimeBucket>,
+}
+
+/// View counts and reading-time quantiles for one entry.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, IntoStatic)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryViewStats<'a> {
+    #[serde(borrow)]
+    pub rkey: CowStr<'a>,
+    /// Entry title from the index, empty when unknown.
+    pub title: CowStr<'a>,
+    pub view_count: u64,
+    /// Median reported reading time, 0 when no reading beacons exist.
+    pub p50_reading_ms: u64,
+    pub p90_reading_ms: u64,
+}
+
+/// View count for one referrer origin.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, IntoStatic)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferrerStats<'a> {
+    #[serde(borrow)]
+    pub referrer: CowStr<'a>,
+    pub view_count: u64,
+}
+
+/// One bucket of the reading-time histogram.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, IntoStatic)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadingTimeBucket {
+    /// Inclusive lower bound of the bucket, in milliseconds.
+    pub floor_ms: u64,
+    pub view_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referrer_origin_drops_path_and_query() {
+        assert_eq!(
+            referrer_origin("https://bsky.app/profile/someone?tab=posts").as_deref(),
+            Some("https://bsky.app")
+        );
+    }
+
+    #[test]
+    fn test_referrer_origin_rejects_garbage() {
+        assert_eq!(referrer_origin("not a url"), None);
+        assert_eq!(referrer_origin(""), None);
+    }
+}
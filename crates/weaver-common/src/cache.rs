@@ -45,6 +45,22 @@ mod native {
     {
         cache.iter().map(|entry| entry.value().clone()).collect()
     }
+
+    pub fn invalidate<K, V>(cache: &Cache<K, V>, key: &K)
+    where
+        K: std::hash::Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        cache.invalidate(key);
+    }
+
+    pub fn clear<K, V>(cache: &Cache<K, V>)
+    where
+        K: std::hash::Hash + Eq + Send + Sync + 'static,
+        V: Clone + Send + Sync + 'static,
+    {
+        cache.invalidate_all();
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -96,6 +112,22 @@ mod wasm {
             .map(|(_, v)| v.clone())
             .collect()
     }
+
+    pub fn invalidate<K, V>(cache: &Cache<K, V>, key: &K)
+    where
+        K: std::hash::Hash + Eq + 'static,
+        V: Clone + 'static,
+    {
+        cache.lock().unwrap().invalidate(key);
+    }
+
+    pub fn clear<K, V>(cache: &Cache<K, V>)
+    where
+        K: std::hash::Hash + Eq + 'static,
+        V: Clone + 'static,
+    {
+        cache.lock().unwrap().invalidate_all();
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -56,4 +56,3 @@ pub struct RecordId<'a> {
     pub collection: Nsid<'a>,
     pub rkey: RecordKey<Rkey<'a>>,
 }
-
@@ -4,9 +4,134 @@ use jacquard::{types::string::AtStrError, xrpc::GenericXrpcError};
 use miette::{Diagnostic, NamedSource, SourceOffset, SourceSpan};
 use std::borrow::Cow;
 
+/// Coarse category of a weaver operation failure.
+///
+/// Carried by [`OpError`] and recoverable from every [`WeaverError`]
+/// via [`WeaverError::kind`], so the app and CLI can choose between a
+/// retry button, a sign-in prompt, and a plain failure page instead of
+/// rendering every error the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WeaverErrorKind {
+    /// The record, notebook, or profile does not exist.
+    NotFound,
+    /// The caller is not allowed to do this.
+    PermissionDenied,
+    /// The session token was rejected or has expired.
+    AuthExpired,
+    /// The upstream service asked us to slow down.
+    RateLimited,
+    /// The Constellation backlink index is unreachable or failing.
+    ConstellationUnavailable,
+    /// A compare-and-swap write lost against a concurrent update.
+    ConflictingVersion,
+    /// Transport-level failure: DNS, connection refused, timeout, 5xx.
+    Network,
+    /// The request itself is malformed; retrying cannot help.
+    Invalid,
+    /// Anything we cannot classify further.
+    Other,
+}
+
+impl WeaverErrorKind {
+    /// Whether retrying without user action can plausibly succeed.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited | Self::ConstellationUnavailable | Self::Network
+        )
+    }
+
+    /// Message suitable for direct display to the user.
+    pub fn user_message(self) -> &'static str {
+        match self {
+            Self::NotFound => "That doesn't exist, or the record behind it was deleted.",
+            Self::PermissionDenied => "You don't have permission to do that.",
+            Self::AuthExpired => "Your session has expired. Sign in again to continue.",
+            Self::RateLimited => "The server asked us to slow down. Wait a moment and try again.",
+            Self::ConstellationUnavailable => {
+                "The backlink index is unavailable right now. Collaboration info may be incomplete."
+            }
+            Self::ConflictingVersion => {
+                "Someone else changed this at the same time. Reload and try again."
+            }
+            Self::Network => "We couldn't reach the server. Check your connection and try again.",
+            Self::Invalid => "The request was invalid.",
+            Self::Other => "Something went wrong.",
+        }
+    }
+}
+
+/// A classified operation failure.
+///
+/// The structured counterpart to wrapping everything in
+/// `ClientError::invalid_request(format!(..))`: the kind survives for
+/// programmatic handling while the message keeps the detail.
+#[derive(thiserror::Error, Debug, Diagnostic)]
+#[error("{message}")]
+#[diagnostic(code(weaver::op))]
+#[non_exhaustive]
+pub struct OpError {
+    kind: WeaverErrorKind,
+    message: String,
+    #[source]
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl OpError {
+    /// Create an error with an explicit kind.
+    pub fn new(kind: WeaverErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Attach the underlying cause.
+    pub fn with_source(mut self, source: impl std::error::Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    /// The failure category.
+    pub fn kind(&self) -> WeaverErrorKind {
+        self.kind
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(WeaverErrorKind::NotFound, message)
+    }
+
+    pub fn permission_denied(message: impl Into<String>) -> Self {
+        Self::new(WeaverErrorKind::PermissionDenied, message)
+    }
+
+    pub fn rate_limited(message: impl Into<String>) -> Self {
+        Self::new(WeaverErrorKind::RateLimited, message)
+    }
+
+    pub fn constellation_unavailable(message: impl Into<String>) -> Self {
+        Self::new(WeaverErrorKind::ConstellationUnavailable, message)
+    }
+
+    pub fn conflicting_version(message: impl Into<String>) -> Self {
+        Self::new(WeaverErrorKind::ConflictingVersion, message)
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::new(WeaverErrorKind::Invalid, message)
+    }
+}
+
 /// Main error type for weaver operations
 #[derive(thiserror::Error, Debug, Diagnostic)]
 pub enum WeaverError {
+    /// Classified operation failure
+    #[error(transparent)]
+    #[diagnostic_source]
+    Op(#[from] OpError),
+
     /// Jacquard Agent error
     #[error(transparent)]
     #[diagnostic_source]
@@ -52,6 +177,68 @@ pub enum WeaverError {
     Xrpc(#[from] jacquard::xrpc::XrpcError<GenericXrpcError>),
 }
 
+impl WeaverError {
+    /// Coarse category of this failure.
+    ///
+    /// [`OpError`]s carry their kind directly. Wrapped jacquard errors
+    /// are opaque, so - like [`crate::retry::is_transient`] - they are
+    /// classified by the wire-level vocabulary in their rendered
+    /// message rather than by structure.
+    pub fn kind(&self) -> WeaverErrorKind {
+        match self {
+            Self::Op(op) => op.kind(),
+            Self::InvalidNotebook(_)
+            | Self::Markdown(_)
+            | Self::Parse(_)
+            | Self::Serde(_)
+            | Self::AtprotoString(_) => WeaverErrorKind::Invalid,
+            Self::Task(_) => WeaverErrorKind::Other,
+            Self::Io(_) | Self::Agent(_) | Self::Identity(_) | Self::Xrpc(_) => {
+                classify_message(&self.to_string())
+            }
+        }
+    }
+
+    /// Whether retrying without user action can plausibly succeed.
+    pub fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+
+    /// Message suitable for direct display to the user.
+    pub fn user_message(&self) -> &'static str {
+        self.kind().user_message()
+    }
+}
+
+/// Best-effort kind for an opaque error, judged by its message.
+///
+/// Ordering matters: the specific categories are checked before the
+/// broad transient bucket so a rate-limited 429 is not reported as a
+/// generic network failure.
+fn classify_message(message: &str) -> WeaverErrorKind {
+    let msg = message.to_ascii_lowercase();
+    if msg.contains("401") || msg.contains("expiredtoken") || msg.contains("invalidtoken") {
+        WeaverErrorKind::AuthExpired
+    } else if msg.contains("403") || msg.contains("forbidden") || msg.contains("permission") {
+        WeaverErrorKind::PermissionDenied
+    } else if msg.contains("404")
+        || msg.contains("notfound")
+        || msg.contains("not found")
+        || msg.contains("could not locate record")
+    {
+        WeaverErrorKind::NotFound
+    } else if msg.contains("429") || msg.contains("ratelimit") || msg.contains("rate limit") {
+        WeaverErrorKind::RateLimited
+    } else if msg.contains("invalidswap") || msg.contains("conflict") {
+        // InvalidSwap is the atproto name for a lost compare-and-swap.
+        WeaverErrorKind::ConflictingVersion
+    } else if crate::retry::is_transient(&msg) {
+        WeaverErrorKind::Network
+    } else {
+        WeaverErrorKind::Other
+    }
+}
+
 /// Parse error with source code location information
 #[derive(thiserror::Error, Debug, Diagnostic)]
 #[error("parse error: {}",self.kind)]
@@ -131,6 +318,52 @@ impl From<serde_json::Error> for ParseError {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn op_error_carries_its_kind_through_weaver_error() {
+        let err = WeaverError::from(OpError::not_found("no notebook at that uri"));
+        assert_eq!(err.kind(), WeaverErrorKind::NotFound);
+        assert!(!err.is_retryable());
+        assert_eq!(err.to_string(), "no notebook at that uri");
+    }
+
+    #[test]
+    fn opaque_messages_classify_by_wire_vocabulary() {
+        assert_eq!(
+            classify_message("XRPC request failed: 429 RateLimitExceeded"),
+            WeaverErrorKind::RateLimited
+        );
+        assert_eq!(
+            classify_message("InvalidSwap: record was updated"),
+            WeaverErrorKind::ConflictingVersion
+        );
+        assert_eq!(
+            classify_message("connection refused"),
+            WeaverErrorKind::Network
+        );
+        assert_eq!(
+            classify_message("RecordNotFound: could not locate record"),
+            WeaverErrorKind::NotFound
+        );
+        assert_eq!(
+            classify_message("InvalidRequest: bad rkey"),
+            WeaverErrorKind::Other
+        );
+    }
+
+    #[test]
+    fn retryable_kinds_match_transient_categories() {
+        assert!(WeaverErrorKind::RateLimited.is_retryable());
+        assert!(WeaverErrorKind::ConstellationUnavailable.is_retryable());
+        assert!(WeaverErrorKind::Network.is_retryable());
+        assert!(!WeaverErrorKind::ConflictingVersion.is_retryable());
+        assert!(!WeaverErrorKind::PermissionDenied.is_retryable());
+    }
+}
+
 fn offset_to_line_col(offset: usize, src: &NamedSource<Cow<'static, str>>) -> (usize, usize) {
     let mut acc_chars = 0usize;
 
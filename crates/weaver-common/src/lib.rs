@@ -1,13 +1,17 @@
 //! Weaver common library - thin wrapper around jacquard with notebook-specific conveniences
 
 pub mod agent;
+pub mod analytics;
 #[cfg(feature = "cache")]
 pub mod cache;
 pub mod constellation;
 pub mod error;
 #[cfg(feature = "perf")]
 pub mod perf;
+#[cfg(feature = "cache")]
+pub mod permission_cache;
 pub mod resolve;
+pub mod retry;
 #[cfg(feature = "telemetry")]
 pub mod telemetry;
 pub mod transport;
@@ -15,11 +19,12 @@ pub mod worker_rt;
 
 // Re-export jacquard for convenience
 pub use agent::{SessionPeer, WeaverExt};
-pub use error::WeaverError;
+pub use error::{OpError, WeaverError, WeaverErrorKind};
 
 // Re-export blake3 for topic hashing
 pub use blake3;
 pub use resolve::{EntryIndex, ExtractedRef, RefCollector, ResolvedContent, ResolvedEntry};
+pub use retry::{Idempotency, RetryPolicy};
 
 pub use jacquard;
 use jacquard::CowStr;
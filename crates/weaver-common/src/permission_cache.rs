@@ -0,0 +1,96 @@
+//! Process-wide TTL cache for resource permission state.
+//!
+//! `get_permissions_for_resource` fans out into several constellation
+//! backlink queries plus a record fetch per invite, and it runs on
+//! every entry view. Permissions change rarely - only when an invite
+//! or accept record is written - so the results are cached here, keyed
+//! by resource URI, on top of the platform cache abstraction in
+//! [`crate::cache`]. The cache is a process-wide static (same pattern
+//! as `W_TICKER`) because `WeaverExt` is a stateless extension trait:
+//! the app's wasm client and the server each get one cache per
+//! process, shared across every agent in it.
+//!
+//! Writers that create or delete invite/accept records locally must
+//! call [`invalidate`] for the affected resource so the next view sees
+//! the change immediately; remote changes surface when the TTL lapses.
+
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use jacquard::smol_str::SmolStr;
+use jacquard::types::string::AtUri;
+use weaver_api::sh_weaver::notebook::PermissionsState;
+
+use crate::cache;
+
+/// Permissions are small; capacity only bounds pathological fan-out.
+const CAPACITY: u64 = 512;
+
+/// Upper bound on how stale a *remotely* revoked or granted permission
+/// can look. Local changes bypass this via [`invalidate`].
+const TTL: Duration = Duration::from_secs(60);
+
+static PERMISSIONS: LazyLock<cache::Cache<SmolStr, PermissionsState<'static>>> =
+    LazyLock::new(|| cache::new_cache(CAPACITY, TTL));
+
+fn key(resource_uri: &AtUri<'_>) -> SmolStr {
+    SmolStr::new(resource_uri.as_str())
+}
+
+/// Cached permissions for a resource, if still fresh.
+pub fn get(resource_uri: &AtUri<'_>) -> Option<PermissionsState<'static>> {
+    cache::get(&PERMISSIONS, &key(resource_uri))
+}
+
+/// Store freshly computed permissions for a resource.
+pub fn insert(resource_uri: &AtUri<'_>, permissions: PermissionsState<'static>) {
+    cache::insert(&PERMISSIONS, key(resource_uri), permissions);
+}
+
+/// Drop the cached permissions for one resource.
+///
+/// Call this after locally creating or deleting an invite or accept
+/// record that targets the resource, so the change is visible without
+/// waiting out the TTL.
+pub fn invalidate(resource_uri: &AtUri<'_>) {
+    cache::invalidate(&PERMISSIONS, &key(resource_uri));
+}
+
+/// Drop all cached permissions (e.g. on logout or account switch).
+pub fn invalidate_all() {
+    cache::clear(&PERMISSIONS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> PermissionsState<'static> {
+        PermissionsState::new().editors(vec![]).build()
+    }
+
+    #[test]
+    fn insert_get_invalidate_roundtrip() {
+        let uri = AtUri::new("at://did:plc:permcache/sh.weaver.notebook.book/3test").unwrap();
+
+        assert!(get(&uri).is_none());
+        insert(&uri, state());
+        assert!(get(&uri).is_some());
+
+        invalidate(&uri);
+        assert!(get(&uri).is_none());
+    }
+
+    #[test]
+    fn invalidation_is_per_resource() {
+        let a = AtUri::new("at://did:plc:permcache/sh.weaver.notebook.book/3aaa").unwrap();
+        let b = AtUri::new("at://did:plc:permcache/sh.weaver.notebook.book/3bbb").unwrap();
+
+        insert(&a, state());
+        insert(&b, state());
+        invalidate(&a);
+
+        assert!(get(&a).is_none());
+        assert!(get(&b).is_some());
+    }
+}
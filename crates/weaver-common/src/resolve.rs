@@ -145,6 +145,35 @@ impl EntryIndex {
         None
     }
 
+    /// Find entries whose title contains `query`, case-insensitively.
+    ///
+    /// Returns up to `limit` `(display_title, canonical_path)` pairs.
+    /// Prefix matches sort before other substring matches, ties broken
+    /// alphabetically so results are stable between renders. An empty
+    /// query matches everything, which is what an autocomplete popover
+    /// wants right after its trigger is typed.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(String, String)> {
+        let needle = query.to_lowercase();
+        let mut matches: Vec<(bool, &str, &str)> = self
+            .by_title
+            .iter()
+            .filter(|(key, _)| key.contains(needle.as_str()))
+            .map(|(key, (path, title))| {
+                (
+                    key.starts_with(needle.as_str()),
+                    title.as_ref(),
+                    path.as_ref(),
+                )
+            })
+            .collect();
+        matches.sort_by(|a, b| (!a.0, a.1).cmp(&(!b.0, b.1)));
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, title, path)| (title.to_string(), path.to_string()))
+            .collect()
+    }
+
     /// Parse a wikilink into (target, fragment)
     pub fn parse_wikilink(wikilink: &str) -> (&str, Option<&str>) {
         match wikilink.split_once('#') {
@@ -380,6 +409,29 @@ mod tests {
         assert_eq!(fragment, Some("section"));
     }
 
+    #[test]
+    fn test_entry_index_search_prefers_prefix_matches() {
+        let mut index = EntryIndex::new();
+        index.add_entry("Notes on rust", "notes_on_rust", "/a/nb/notes_on_rust");
+        index.add_entry("Rust tips", "rust_tips", "/a/nb/rust_tips");
+        index.add_entry("Gardening", "gardening", "/a/nb/gardening");
+
+        let results = index.search("rust", 10);
+        assert_eq!(
+            results,
+            vec![
+                ("Rust tips".to_string(), "/a/nb/rust_tips".to_string()),
+                (
+                    "Notes on rust".to_string(),
+                    "/a/nb/notes_on_rust".to_string()
+                ),
+            ]
+        );
+
+        // Empty query matches everything, honouring the limit.
+        assert_eq!(index.search("", 2).len(), 2);
+    }
+
     #[test]
     fn test_collect_refs_wikilink() {
         let markdown = "Check out [[My Note]] for more info.";
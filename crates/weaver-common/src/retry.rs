@@ -0,0 +1,227 @@
+//! Retry policy for transient XRPC failures.
+//!
+//! Agent calls fail hard on the first 5xx or dropped connection, which
+//! turns every infrastructure blip into a user-visible error. This
+//! module centralizes the policy instead of scattering ad-hoc loops:
+//! a cap on attempts, jittered exponential backoff, and idempotency
+//! awareness so a `create_record` is never blindly replayed when the
+//! first attempt may already have landed.
+
+use std::fmt;
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Whether an operation can be safely replayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idempotency {
+    /// Reads, and writes keyed by a caller-chosen rkey; replaying at
+    /// worst repeats the same outcome.
+    Idempotent,
+    /// A retry after an ambiguous failure could apply twice (e.g.
+    /// `create_record` with a server-assigned rkey). Never retried: by
+    /// the time an error surfaces we cannot know whether the first
+    /// attempt landed.
+    NonIdempotent,
+}
+
+/// Retry tuning for XRPC sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts including the first (minimum 1).
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles per retry.
+    pub base_delay: Duration,
+    /// Upper bound on any single delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy with a custom attempt cap.
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            ..Self::default()
+        }
+    }
+
+    /// Jittered exponential delay before the given retry (1-based).
+    ///
+    /// Jitter in [0.5, 1.5) keeps a fleet of clients from hammering a
+    /// recovering server in lockstep.
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        // Shift capped well below overflow; max_delay clamps anyway.
+        let exponent = retry.saturating_sub(1).min(16);
+        let exponential = self.base_delay.saturating_mul(1u32 << exponent);
+        exponential
+            .min(self.max_delay)
+            .mul_f64(rand::rng().random_range(0.5..1.5))
+    }
+
+    /// Run an operation under this policy.
+    ///
+    /// The operation is re-invoked only for transient failures of
+    /// idempotent operations; everything else surfaces immediately.
+    /// Each retry is logged through `tracing` at warn level with the
+    /// operation name - that is the logging hook; subscribers turn it
+    /// into metrics or structured logs without a callback API here.
+    pub async fn run<T, E, F, Fut>(
+        &self,
+        idempotency: Idempotency,
+        op_name: &str,
+        mut op: F,
+    ) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: fmt::Display,
+    {
+        let mut attempt = 1u32;
+        loop {
+            match op().await {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    if idempotency == Idempotency::NonIdempotent
+                        || attempt >= self.max_attempts.max(1)
+                        || !is_transient(&e.to_string())
+                    {
+                        return Err(e);
+                    }
+                    let delay = self.backoff_delay(attempt);
+                    tracing::warn!(
+                        op = op_name,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        error = %e,
+                        "transient failure, retrying"
+                    );
+                    n0_future::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Whether a failure is worth retrying, judged by its rendered message.
+///
+/// The agent funnels transport, HTTP, and XRPC failures through several
+/// opaque wrappers, so classification keys off the wire-level
+/// vocabulary they all share (status codes and standard error names)
+/// rather than concrete types. Unknown messages are treated as
+/// permanent: retrying a malformed request helps nobody.
+pub fn is_transient(message: &str) -> bool {
+    let msg = message.to_ascii_lowercase();
+    msg.contains("500")
+        || msg.contains("502")
+        || msg.contains("503")
+        || msg.contains("504")
+        || msg.contains("internalservererror")
+        || msg.contains("upstreamfailure")
+        || msg.contains("upstreamtimeout")
+        || msg.contains("429")
+        || msg.contains("ratelimit")
+        || msg.contains("rate limit")
+        || msg.contains("timed out")
+        || msg.contains("timeout")
+        || msg.contains("connection")
+        || msg.contains("dns")
+        || msg.contains("network")
+        || msg.contains("failed to fetch")
+        || msg.contains("temporarily")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(2),
+        }
+    }
+
+    #[test]
+    fn transient_classification() {
+        assert!(is_transient(
+            "Constellation query failed: 503 Service Unavailable"
+        ));
+        assert!(is_transient("request timed out"));
+        assert!(is_transient("RateLimitExceeded"));
+        assert!(!is_transient("InvalidRequest: bad rkey"));
+        assert!(!is_transient("record not found"));
+    }
+
+    #[test]
+    fn backoff_stays_within_jittered_bounds() {
+        let policy = RetryPolicy::default();
+        for retry in 1..6 {
+            let delay = policy.backoff_delay(retry);
+            // Cap times maximum jitter bounds every delay.
+            assert!(delay <= policy.max_delay.mul_f64(1.5));
+            assert!(delay >= policy.base_delay.mul_f64(0.5));
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures_until_success() {
+        let attempts = Cell::new(0u32);
+        let result: Result<u32, String> = fast_policy()
+            .run(Idempotency::Idempotent, "test.op", || {
+                attempts.set(attempts.get() + 1);
+                let n = attempts.get();
+                async move {
+                    if n < 3 {
+                        Err("503 service unavailable".to_owned())
+                    } else {
+                        Ok(n)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn permanent_failures_surface_immediately() {
+        let attempts = Cell::new(0u32);
+        let result: Result<(), String> = fast_policy()
+            .run(Idempotency::Idempotent, "test.op", || {
+                attempts.set(attempts.get() + 1);
+                async { Err("InvalidRequest: bad input".to_owned()) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn non_idempotent_operations_are_never_retried() {
+        let attempts = Cell::new(0u32);
+        let result: Result<(), String> = fast_policy()
+            .run(Idempotency::NonIdempotent, "test.op", || {
+                attempts.set(attempts.get() + 1);
+                async { Err("503 service unavailable".to_owned()) }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.get(), 1);
+    }
+}
@@ -91,10 +91,7 @@ pub fn init_metrics() -> &'static PrometheusHandle {
 /// Initialize tracing with console + optional Loki layers.
 async fn init_tracing(config: TelemetryConfig) {
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-        EnvFilter::new(format!(
-            "{}",
-            config.console_level.as_str().to_lowercase()
-        ))
+        EnvFilter::new(format!("{}", config.console_level.as_str().to_lowercase()))
     });
 
     // Pretty console layer for human-readable stdout
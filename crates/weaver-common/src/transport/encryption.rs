@@ -0,0 +1,160 @@
+#![cfg(feature = "iroh")]
+
+//! End-to-end encryption for collaboration messages.
+//!
+//! Signing proves who sent a message; it does nothing to hide the
+//! content from the relay or anyone else on the wire. This module adds
+//! per-session symmetric encryption: collaborators derive a
+//! [`SessionKey`] from an invite-scoped secret (minted by the inviter
+//! and carried in the `sh.weaver.collab.invite` record), and
+//! [`super::SignedMessage`] seals its payload with it before signing.
+//! Relays and observers then see only ciphertext and the sender's
+//! public key.
+
+use miette::Diagnostic;
+use rand::RngCore;
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+
+use super::TopicId;
+
+/// Domain separation for session key derivation. Bump the suffix if
+/// the derivation scheme ever changes.
+const KEY_CONTEXT: &str = "sh.weaver.collab session key v1";
+
+/// XChaCha20 nonce length, prefixed to every sealed payload.
+const NONCE_LEN: usize = 24;
+
+/// Error type for encryption operations.
+#[derive(Debug, thiserror::Error, Diagnostic)]
+#[diagnostic(code(weaver::transport::encryption))]
+pub enum EncryptionError {
+    #[error("encryption failed")]
+    Encrypt,
+
+    #[error("decryption failed - wrong session key or tampered ciphertext")]
+    Decrypt,
+}
+
+/// Symmetric key shared by all collaborators in one session.
+///
+/// Derived, not exchanged: both sides feed the invite secret and the
+/// topic into a KDF, so one invite covering several resources still
+/// yields a distinct key per resource.
+#[derive(Clone)]
+pub struct SessionKey([u8; 32]);
+
+impl std::fmt::Debug for SessionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Never print key material, even at trace level.
+        f.write_str("SessionKey(..)")
+    }
+}
+
+impl SessionKey {
+    /// Derive the session key for a resource from an invite secret.
+    pub fn from_invite_secret(invite_secret: &[u8], topic: &TopicId) -> Self {
+        let mut material = Vec::with_capacity(invite_secret.len() + 32);
+        material.extend_from_slice(invite_secret);
+        material.extend_from_slice(topic.as_bytes());
+        Self(blake3::derive_key(KEY_CONTEXT, &material))
+    }
+
+    /// Encrypt a payload. Output layout: 24-byte nonce, then AEAD
+    /// ciphertext (which includes the auth tag).
+    pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let cipher = XChaCha20Poly1305::new((&self.0).into());
+
+        // XChaCha nonces are wide enough that random generation is
+        // safe; no counter state to persist across reconnects.
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::rng().fill_bytes(&mut nonce);
+
+        let ciphertext = cipher
+            .encrypt(XNonce::from_slice(&nonce), plaintext)
+            .map_err(|_| EncryptionError::Encrypt)?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt a payload produced by [`SessionKey::seal`].
+    pub fn open(&self, sealed: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        if sealed.len() < NONCE_LEN {
+            return Err(EncryptionError::Decrypt);
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+
+        let cipher = XChaCha20Poly1305::new((&self.0).into());
+        cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::Decrypt)
+    }
+}
+
+/// Mint a fresh invite-scoped secret.
+///
+/// The inviter stores this in the invite record (encrypted to the
+/// invitee); everyone holding it can derive the session key for any
+/// resource the invite covers.
+pub fn generate_invite_secret() -> [u8; 32] {
+    let mut secret = [0u8; 32];
+    rand::rng().fill_bytes(&mut secret);
+    secret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn topic(uri: &str) -> TopicId {
+        TopicId::from_bytes(*blake3::hash(uri.as_bytes()).as_bytes())
+    }
+
+    #[test]
+    fn seal_open_roundtrip() {
+        let secret = generate_invite_secret();
+        let key = SessionKey::from_invite_secret(&secret, &topic("at://did:plc:a/x/y"));
+
+        let sealed = key.seal(b"hello collaborators").unwrap();
+        assert_ne!(&sealed[NONCE_LEN..], b"hello collaborators".as_slice());
+
+        let opened = key.open(&sealed).unwrap();
+        assert_eq!(opened, b"hello collaborators");
+    }
+
+    #[test]
+    fn open_rejects_tampered_ciphertext() {
+        let secret = generate_invite_secret();
+        let key = SessionKey::from_invite_secret(&secret, &topic("at://did:plc:a/x/y"));
+
+        let mut sealed = key.seal(b"payload").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 0x01;
+
+        assert!(matches!(key.open(&sealed), Err(EncryptionError::Decrypt)));
+    }
+
+    #[test]
+    fn open_rejects_wrong_key() {
+        let t = topic("at://did:plc:a/x/y");
+        let key_a = SessionKey::from_invite_secret(&generate_invite_secret(), &t);
+        let key_b = SessionKey::from_invite_secret(&generate_invite_secret(), &t);
+
+        let sealed = key_a.seal(b"payload").unwrap();
+        assert!(matches!(key_b.open(&sealed), Err(EncryptionError::Decrypt)));
+    }
+
+    #[test]
+    fn topic_separates_keys_from_one_invite_secret() {
+        let secret = generate_invite_secret();
+        let key_x = SessionKey::from_invite_secret(&secret, &topic("at://did:plc:a/x/1"));
+        let key_y = SessionKey::from_invite_secret(&secret, &topic("at://did:plc:a/x/2"));
+
+        let sealed = key_x.seal(b"payload").unwrap();
+        assert!(matches!(key_y.open(&sealed), Err(EncryptionError::Decrypt)));
+    }
+}
@@ -51,6 +51,38 @@ pub enum CollabMessage {
         /// True if this is a full snapshot, false if incremental
         is_snapshot: bool,
     },
+
+    /// Ephemeral awareness update (presence).
+    ///
+    /// Richer successor to `Cursor`: also carries the sender's visible
+    /// viewport. Never persisted to the CRDT — receivers only feed it
+    /// into presence tracking, and stale state is pruned by timeout.
+    Awareness {
+        /// Cursor position in document
+        position: usize,
+        /// Optional selection range (anchor, head)
+        selection: Option<(usize, usize)>,
+        /// Visible character range (start, end), if known
+        viewport: Option<(usize, usize)>,
+        /// Sender's self-assigned colour (RGBA)
+        color: u32,
+    },
+
+    /// Periodic liveness probe.
+    ///
+    /// Broadcast on a fixed interval so peers can distinguish "idle"
+    /// from "silently disconnected". Receivers answer with
+    /// `HeartbeatAck` echoing the same seq.
+    Heartbeat {
+        /// Monotonic per sender; matched against acks for RTT/loss.
+        seq: u64,
+    },
+
+    /// Echo of a received `Heartbeat`, sent back to the probing peer.
+    HeartbeatAck {
+        /// The seq of the heartbeat being acknowledged.
+        seq: u64,
+    },
 }
 
 impl CollabMessage {
@@ -74,6 +106,8 @@ mod signed {
     use super::*;
     use iroh::{PublicKey, SecretKey, Signature};
 
+    use crate::transport::encryption::{EncryptionError, SessionKey};
+
     /// A signed message wrapper for authenticated transport.
     #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct SignedMessage {
@@ -88,7 +122,14 @@ mod signed {
     /// Versioned wire format with timestamp.
     #[derive(Debug, Clone, Serialize, Deserialize)]
     enum WireMessage {
-        V0 { timestamp: u64, message: CollabMessage },
+        V0 {
+            timestamp: u64,
+            message: CollabMessage,
+        },
+
+        /// End-to-end encrypted: the ciphertext of a postcard-encoded
+        /// `WireMessage::V0`, sealed with the session key.
+        V1Encrypted { sealed: Vec<u8> },
     }
 
     /// A verified message with sender and timestamp info.
@@ -109,25 +150,35 @@ mod signed {
         Serialization(#[from] postcard::Error),
         #[error("signature verification failed")]
         InvalidSignature,
+        #[error("message is encrypted but no session key was provided")]
+        Encrypted,
+        #[error("expected an encrypted message but got plaintext")]
+        UnexpectedPlaintext,
+        #[error(transparent)]
+        Encryption(#[from] EncryptionError),
     }
 
     impl SignedMessage {
-        /// Sign a message and encode to bytes for wire transmission.
-        pub fn sign_and_encode(
-            secret_key: &SecretKey,
-            message: &CollabMessage,
-        ) -> Result<Vec<u8>, SignedMessageError> {
+        /// Build the timestamped inner wire message.
+        fn inner_wire(message: &CollabMessage) -> WireMessage {
             use web_time::SystemTime;
 
             let timestamp = SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
                 .as_micros() as u64;
-            let wire = WireMessage::V0 {
+            WireMessage::V0 {
                 timestamp,
                 message: message.clone(),
-            };
-            let data = postcard::to_stdvec(&wire)?;
+            }
+        }
+
+        /// Sign the wire payload and encode for transmission.
+        fn sign_wire(
+            secret_key: &SecretKey,
+            wire: &WireMessage,
+        ) -> Result<Vec<u8>, SignedMessageError> {
+            let data = postcard::to_stdvec(wire)?;
             let signature = secret_key.sign(&data);
             let from = secret_key.public();
             let signed = Self {
@@ -138,15 +189,71 @@ mod signed {
             Ok(postcard::to_stdvec(&signed)?)
         }
 
+        /// Sign a message and encode to bytes for wire transmission.
+        pub fn sign_and_encode(
+            secret_key: &SecretKey,
+            message: &CollabMessage,
+        ) -> Result<Vec<u8>, SignedMessageError> {
+            Self::sign_wire(secret_key, &Self::inner_wire(message))
+        }
+
+        /// Seal a message with the session key, then sign and encode.
+        ///
+        /// The signature covers the ciphertext, so relays can still
+        /// attribute traffic to a public key but read nothing else.
+        pub fn sign_encrypt_and_encode(
+            secret_key: &SecretKey,
+            session_key: &SessionKey,
+            message: &CollabMessage,
+        ) -> Result<Vec<u8>, SignedMessageError> {
+            let inner = postcard::to_stdvec(&Self::inner_wire(message))?;
+            let sealed = session_key.seal(&inner)?;
+            Self::sign_wire(secret_key, &WireMessage::V1Encrypted { sealed })
+        }
+
         /// Decode from bytes and verify signature.
+        ///
+        /// Plaintext-only path; encrypted messages fail with
+        /// [`SignedMessageError::Encrypted`].
         pub fn decode_and_verify(bytes: &[u8]) -> Result<ReceivedMessage, SignedMessageError> {
+            Self::decode_verify_open(bytes, None)
+        }
+
+        /// Decode from bytes, verify the signature, and decrypt.
+        ///
+        /// When a session key is present, plaintext messages are
+        /// rejected: accepting them would let a peer silently downgrade
+        /// an encrypted session.
+        pub fn decode_verify_open(
+            bytes: &[u8],
+            session_key: Option<&SessionKey>,
+        ) -> Result<ReceivedMessage, SignedMessageError> {
             let signed: Self = postcard::from_bytes(bytes)?;
             signed
                 .from
                 .verify(&signed.data, &signed.signature)
                 .map_err(|_| SignedMessageError::InvalidSignature)?;
             let wire: WireMessage = postcard::from_bytes(&signed.data)?;
-            let WireMessage::V0 { timestamp, message } = wire;
+            let (timestamp, message) = match wire {
+                WireMessage::V0 { timestamp, message } => {
+                    if session_key.is_some() {
+                        return Err(SignedMessageError::UnexpectedPlaintext);
+                    }
+                    (timestamp, message)
+                }
+                WireMessage::V1Encrypted { sealed } => {
+                    let Some(key) = session_key else {
+                        return Err(SignedMessageError::Encrypted);
+                    };
+                    let inner = key.open(&sealed)?;
+                    let WireMessage::V0 { timestamp, message } = postcard::from_bytes(&inner)?
+                    else {
+                        // Nested encryption is not a valid construction.
+                        return Err(SignedMessageError::Encrypted);
+                    };
+                    (timestamp, message)
+                }
+            };
             Ok(ReceivedMessage {
                 from: signed.from,
                 timestamp,
@@ -154,6 +261,64 @@ mod signed {
             })
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::transport::encryption::generate_invite_secret;
+
+        fn session_key() -> SessionKey {
+            let topic = iroh_gossip::TopicId::from_bytes([7u8; 32]);
+            SessionKey::from_invite_secret(&generate_invite_secret(), &topic)
+        }
+
+        #[test]
+        fn encrypted_roundtrip() {
+            let secret_key = SecretKey::generate(&mut rand::rng());
+            let key = session_key();
+            let msg = CollabMessage::Leave {
+                did: "did:plc:abc123".into(),
+            };
+
+            let bytes = SignedMessage::sign_encrypt_and_encode(&secret_key, &key, &msg).unwrap();
+            let received = SignedMessage::decode_verify_open(&bytes, Some(&key)).unwrap();
+
+            assert_eq!(received.from, secret_key.public());
+            assert!(matches!(received.message, CollabMessage::Leave { .. }));
+        }
+
+        #[test]
+        fn encrypted_message_needs_the_key() {
+            let secret_key = SecretKey::generate(&mut rand::rng());
+            let key = session_key();
+            let msg = CollabMessage::Leave {
+                did: "did:plc:abc123".into(),
+            };
+
+            let bytes = SignedMessage::sign_encrypt_and_encode(&secret_key, &key, &msg).unwrap();
+            assert!(matches!(
+                SignedMessage::decode_verify_open(&bytes, None),
+                Err(SignedMessageError::Encrypted)
+            ));
+        }
+
+        #[test]
+        fn plaintext_rejected_in_encrypted_session() {
+            let secret_key = SecretKey::generate(&mut rand::rng());
+            let key = session_key();
+            let msg = CollabMessage::Leave {
+                did: "did:plc:abc123".into(),
+            };
+
+            // A peer that skips encryption must not be able to downgrade
+            // the session.
+            let bytes = SignedMessage::sign_and_encode(&secret_key, &msg).unwrap();
+            assert!(matches!(
+                SignedMessage::decode_verify_open(&bytes, Some(&key)),
+                Err(SignedMessageError::UnexpectedPlaintext)
+            ));
+        }
+    }
 }
 
 #[cfg(feature = "iroh")]
@@ -181,6 +346,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_roundtrip_awareness() {
+        let msg = CollabMessage::Awareness {
+            position: 42,
+            selection: Some((40, 50)),
+            viewport: Some((0, 500)),
+            color: 0x4ECDC4FF,
+        };
+        let bytes = msg.to_bytes().unwrap();
+        let decoded = CollabMessage::from_bytes(&bytes).unwrap();
+
+        match decoded {
+            CollabMessage::Awareness {
+                position,
+                selection,
+                viewport,
+                color,
+            } => {
+                assert_eq!(position, 42);
+                assert_eq!(selection, Some((40, 50)));
+                assert_eq!(viewport, Some((0, 500)));
+                assert_eq!(color, 0x4ECDC4FF);
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
     #[test]
     fn test_roundtrip_join() {
         let msg = CollabMessage::Join {
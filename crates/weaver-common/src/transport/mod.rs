@@ -9,19 +9,25 @@ mod presence_types;
 #[cfg(feature = "iroh")]
 mod discovery;
 #[cfg(feature = "iroh")]
+mod encryption;
+#[cfg(feature = "iroh")]
 mod node;
 #[cfg(feature = "iroh")]
 mod presence;
 #[cfg(feature = "iroh")]
 mod session;
+#[cfg(feature = "webrtc")]
+mod webrtc;
 
 // Always available - wire protocol
 pub use messages::CollabMessage;
-pub use presence_types::{CollaboratorInfo, PresenceSnapshot, RemoteCursorInfo};
+pub use presence_types::{CollaboratorInfo, ConnectionState, PresenceSnapshot, RemoteCursorInfo};
 
 // iroh feature - networking
 #[cfg(feature = "iroh")]
-pub use discovery::{node_id_to_string, parse_node_id, DiscoveredPeer, DiscoveryError};
+pub use discovery::{DiscoveredPeer, DiscoveryError, node_id_to_string, parse_node_id};
+#[cfg(feature = "iroh")]
+pub use encryption::{EncryptionError, SessionKey, generate_invite_secret};
 #[cfg(feature = "iroh")]
 pub use iroh::EndpointId;
 #[cfg(feature = "iroh")]
@@ -29,6 +35,16 @@ pub use messages::{ReceivedMessage, SignedMessage, SignedMessageError};
 #[cfg(feature = "iroh")]
 pub use node::{CollabNode, TransportError};
 #[cfg(feature = "iroh")]
-pub use presence::{Collaborator, PresenceTracker, RemoteCursor};
+pub use presence::{
+    AWARENESS_MIN_INTERVAL, AWARENESS_TIMEOUT, AwarenessThrottle, Collaborator, ConnectionQuality,
+    HeartbeatConfig, HeartbeatMonitor, Liveness, PresenceTracker, RemoteCursor,
+};
 #[cfg(feature = "iroh")]
 pub use session::{CollabSession, SessionError, SessionEvent, TopicId};
+
+// webrtc feature - fallback transport for blocked-relay networks
+#[cfg(feature = "webrtc")]
+pub use webrtc::{
+    FallbackSession, IncomingHandle, PeerChannel, SignalPayload, WebRtcSession, WebRtcSignal,
+    is_initiator, join_with_fallback,
+};
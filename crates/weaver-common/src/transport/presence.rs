@@ -4,12 +4,75 @@
 //!
 //! Tracks active collaborators, their cursor positions, and display info.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
 
 use iroh::EndpointId;
 use jacquard::smol_str::SmolStr;
 use web_time::Instant;
 
+/// How long a cursor stays rendered without a fresh awareness update.
+///
+/// Peers broadcast on every cursor/selection change, so a cursor older
+/// than this belongs to someone who stopped editing (or silently
+/// disconnected); pass it to [`PresenceTracker::prune_stale_cursors`].
+pub const AWARENESS_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Minimum interval between outgoing awareness broadcasts.
+///
+/// Cursor movement generates an event per keystroke; gossip does not
+/// need more than ~10 updates a second to feel live.
+pub const AWARENESS_MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Heartbeat tuning for liveness detection.
+///
+/// Awareness only flows while someone is editing, so an idle but
+/// connected peer is indistinguishable from one whose connection died.
+/// Heartbeats flow unconditionally: silence past `stale_after` marks a
+/// collaborator stale, and past `disconnect_after` they are presumed
+/// silently disconnected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeartbeatConfig {
+    /// How often to broadcast a heartbeat.
+    pub interval: Duration,
+    /// Silence after which a collaborator is considered stale.
+    pub stale_after: Duration,
+    /// Silence after which a collaborator is presumed disconnected.
+    pub disconnect_after: Duration,
+}
+
+impl Default for HeartbeatConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            // Three missed beats before "stale", six before
+            // "disconnected" - tolerant of one-off gossip hiccups.
+            stale_after: Duration::from_secs(15),
+            disconnect_after: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Heartbeat-derived liveness of a collaborator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liveness {
+    /// Heard from recently.
+    Active,
+    /// Missed a few heartbeats; connection may be degraded.
+    Stale,
+    /// Silent past the disconnect threshold without a Leave.
+    Disconnected,
+}
+
+/// Measured connection quality for one peer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ConnectionQuality {
+    /// Smoothed round-trip time from heartbeat acks, if measured.
+    pub rtt: Option<Duration>,
+    /// Fraction of recent heartbeats that went unacknowledged (0..=1).
+    pub packet_loss: f32,
+}
+
 /// A remote collaborator's cursor state.
 #[derive(Debug, Clone, PartialEq)]
 pub struct RemoteCursor {
@@ -17,6 +80,8 @@ pub struct RemoteCursor {
     pub position: usize,
     /// Selection range (anchor, head) if any.
     pub selection: Option<(usize, usize)>,
+    /// Visible character range (start, end), if the peer shared one.
+    pub viewport: Option<(usize, usize)>,
     /// Assigned colour (RGBA).
     pub color: u32,
     /// When this cursor was last updated.
@@ -36,6 +101,12 @@ pub struct Collaborator {
     pub cursor: Option<RemoteCursor>,
     /// iroh endpoint ID for this peer.
     pub node_id: EndpointId,
+    /// Last time any message arrived from this peer.
+    pub last_heard_at: Instant,
+    /// Heartbeat-measured connection quality.
+    pub connection: ConnectionQuality,
+    /// Set once the disconnect threshold passes; cleared on activity.
+    marked_disconnected: bool,
 }
 
 /// Tracks all collaborators in a session.
@@ -45,6 +116,8 @@ pub struct PresenceTracker {
     collaborators: HashMap<EndpointId, Collaborator>,
     /// Colour assignment counter.
     next_color_index: usize,
+    /// Liveness thresholds.
+    heartbeat: HeartbeatConfig,
 }
 
 /// Predefined collaborator colours (pastel-ish for readability).
@@ -65,6 +138,14 @@ impl PresenceTracker {
         Self::default()
     }
 
+    /// Create a tracker with custom liveness thresholds.
+    pub fn with_heartbeat_config(config: HeartbeatConfig) -> Self {
+        Self {
+            heartbeat: config,
+            ..Self::default()
+        }
+    }
+
     /// Add a collaborator when they join.
     pub fn add_collaborator(
         &mut self,
@@ -81,6 +162,9 @@ impl PresenceTracker {
                 color,
                 cursor: None,
                 node_id,
+                last_heard_at: Instant::now(),
+                connection: ConnectionQuality::default(),
+                marked_disconnected: false,
             },
         );
     }
@@ -96,15 +180,93 @@ impl PresenceTracker {
         node_id: &EndpointId,
         position: usize,
         selection: Option<(usize, usize)>,
+    ) {
+        self.update_awareness(node_id, position, selection, None, None);
+    }
+
+    /// Apply a full awareness update from a collaborator.
+    ///
+    /// Like [`update_cursor`](Self::update_cursor) but also records the
+    /// peer's visible viewport and, when `color` is given, adopts their
+    /// self-assigned colour so every participant renders them the same
+    /// way.
+    pub fn update_awareness(
+        &mut self,
+        node_id: &EndpointId,
+        position: usize,
+        selection: Option<(usize, usize)>,
+        viewport: Option<(usize, usize)>,
+        color: Option<u32>,
     ) {
         if let Some(collab) = self.collaborators.get_mut(node_id) {
+            if let Some(color) = color {
+                collab.color = color;
+            }
             collab.cursor = Some(RemoteCursor {
                 position,
                 selection,
+                viewport,
                 color: collab.color,
                 updated_at: Instant::now(),
             });
+            collab.last_heard_at = Instant::now();
+            collab.marked_disconnected = false;
+        }
+    }
+
+    /// Record that any message arrived from a peer.
+    ///
+    /// Every inbound message counts as liveness, not just awareness -
+    /// a peer streaming CRDT updates without moving their cursor is
+    /// very much connected.
+    pub fn record_activity(&mut self, node_id: &EndpointId) {
+        if let Some(collab) = self.collaborators.get_mut(node_id) {
+            collab.last_heard_at = Instant::now();
+            collab.marked_disconnected = false;
+        }
+    }
+
+    /// Store the latest heartbeat-measured quality for a peer.
+    pub fn record_quality(&mut self, node_id: &EndpointId, quality: ConnectionQuality) {
+        if let Some(collab) = self.collaborators.get_mut(node_id) {
+            collab.connection = quality;
+        }
+    }
+
+    /// Heartbeat-derived liveness of a collaborator.
+    pub fn liveness(&self, node_id: &EndpointId) -> Option<Liveness> {
+        self.collaborators.get(node_id).map(|collab| {
+            if collab.marked_disconnected {
+                Liveness::Disconnected
+            } else if collab.last_heard_at.elapsed() > self.heartbeat.stale_after {
+                Liveness::Stale
+            } else {
+                Liveness::Active
+            }
+        })
+    }
+
+    /// Mark collaborators silent past the disconnect threshold.
+    ///
+    /// Returns the newly disconnected collaborators so the caller can
+    /// surface the transition (each one is reported exactly once).
+    /// Entries stay in the tracker - the UI renders them greyed out
+    /// rather than vanishing - and are removed only by an explicit
+    /// Leave or the peer actually dropping.
+    pub fn sweep_silent(&mut self) -> Vec<Collaborator> {
+        let mut newly_disconnected = Vec::new();
+        for collab in self.collaborators.values_mut() {
+            if !collab.marked_disconnected
+                && collab.last_heard_at.elapsed() > self.heartbeat.disconnect_after
+            {
+                collab.marked_disconnected = true;
+                // A disconnected peer's cursor is certainly not where
+                // it was.
+                collab.cursor = None;
+                newly_disconnected.push(collab.clone());
+            }
         }
+        newly_disconnected
     }
 
     /// Get all active collaborators.
@@ -159,6 +321,189 @@ impl PresenceTracker {
     }
 }
 
+/// Rate limiter for outgoing awareness broadcasts.
+///
+/// Awareness is ephemeral, so dropped intermediate states are harmless —
+/// only the latest matters. This suppresses broadcasts that repeat the
+/// last sent state or arrive within [`AWARENESS_MIN_INTERVAL`] of it.
+/// Because cursor movement produces a steady stream of events, the final
+/// state after a burst still goes out on the next event past the
+/// interval.
+#[derive(Debug, Default)]
+pub struct AwarenessThrottle {
+    last_sent_at: Option<Instant>,
+    last_state: Option<AwarenessState>,
+}
+
+type AwarenessState = (usize, Option<(usize, usize)>, Option<(usize, usize)>);
+
+impl AwarenessThrottle {
+    /// Create a new throttle with no history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether an update with this state should be broadcast now.
+    ///
+    /// Returns true (and records the send) when the state differs from
+    /// the last sent one and the minimum interval has elapsed.
+    pub fn should_send(
+        &mut self,
+        position: usize,
+        selection: Option<(usize, usize)>,
+        viewport: Option<(usize, usize)>,
+    ) -> bool {
+        let state = (position, selection, viewport);
+        if self.last_state == Some(state) {
+            return false;
+        }
+        if let Some(at) = self.last_sent_at {
+            if at.elapsed() < AWARENESS_MIN_INTERVAL {
+                return false;
+            }
+        }
+        self.last_sent_at = Some(Instant::now());
+        self.last_state = Some(state);
+        true
+    }
+}
+
+/// Outstanding probes kept per peer before the oldest counts as lost.
+///
+/// With the default 5s interval this is ~40s of history, comfortably
+/// past the disconnect threshold.
+const PROBE_WINDOW: usize = 8;
+
+/// Ack/loss counters are halved once their sum reaches this, so the
+/// loss estimate reflects recent behaviour instead of the whole
+/// session.
+const LOSS_DECAY: u32 = 64;
+
+/// Per-peer probe bookkeeping for [`HeartbeatMonitor`].
+#[derive(Debug, Default, Clone)]
+struct PeerProbes {
+    /// Unacknowledged (seq, sent-at) pairs, oldest first.
+    outstanding: VecDeque<(u64, Instant)>,
+    /// Exponentially weighted RTT in milliseconds.
+    rtt_ewma_ms: Option<f64>,
+    acked: u32,
+    lost: u32,
+}
+
+impl PeerProbes {
+    fn quality(&self) -> ConnectionQuality {
+        let total = self.acked + self.lost;
+        ConnectionQuality {
+            rtt: self
+                .rtt_ewma_ms
+                .map(|ms| Duration::from_secs_f64(ms / 1000.0)),
+            packet_loss: if total == 0 {
+                0.0
+            } else {
+                self.lost as f32 / total as f32
+            },
+        }
+    }
+}
+
+/// Drives outgoing heartbeats and turns acks into per-peer RTT and
+/// loss estimates.
+///
+/// The session owner calls [`tick`](Self::tick) on its event loop and
+/// broadcasts a `CollabMessage::Heartbeat` whenever it returns a seq;
+/// each inbound `HeartbeatAck` goes to [`record_ack`](Self::record_ack).
+/// Estimates are read back via [`quality`](Self::quality) and fed into
+/// [`PresenceTracker::record_quality`].
+#[derive(Debug, Default, Clone)]
+pub struct HeartbeatMonitor {
+    config: HeartbeatConfig,
+    next_seq: u64,
+    last_sent_at: Option<Instant>,
+    peers: HashMap<EndpointId, PeerProbes>,
+}
+
+impl HeartbeatMonitor {
+    /// Create a monitor with default thresholds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a monitor with custom thresholds.
+    pub fn with_config(config: HeartbeatConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the seq to broadcast if a heartbeat is due.
+    ///
+    /// Records an outstanding probe for every current peer; probes that
+    /// fall out of the window unacknowledged count as lost.
+    pub fn tick(&mut self, peers: impl IntoIterator<Item = EndpointId>) -> Option<u64> {
+        if let Some(at) = self.last_sent_at {
+            if at.elapsed() < self.config.interval {
+                return None;
+            }
+        }
+        self.last_sent_at = Some(Instant::now());
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        let now = Instant::now();
+        for peer in peers {
+            let probes = self.peers.entry(peer).or_default();
+            probes.outstanding.push_back((seq, now));
+            while probes.outstanding.len() > PROBE_WINDOW {
+                probes.outstanding.pop_front();
+                probes.lost += 1;
+            }
+        }
+        Some(seq)
+    }
+
+    /// Record a heartbeat ack from a peer.
+    ///
+    /// Older probes the ack skipped over count as lost: their acks were
+    /// either dropped or are so late they describe a connection state
+    /// that no longer matters.
+    pub fn record_ack(&mut self, peer: &EndpointId, seq: u64) {
+        let Some(probes) = self.peers.get_mut(peer) else {
+            return;
+        };
+        while let Some(&(s, sent_at)) = probes.outstanding.front() {
+            if s > seq {
+                break;
+            }
+            probes.outstanding.pop_front();
+            if s == seq {
+                probes.acked += 1;
+                let sample = sent_at.elapsed().as_secs_f64() * 1000.0;
+                probes.rtt_ewma_ms = Some(match probes.rtt_ewma_ms {
+                    Some(prev) => prev * 0.875 + sample * 0.125,
+                    None => sample,
+                });
+            } else {
+                probes.lost += 1;
+            }
+        }
+        if probes.acked + probes.lost >= LOSS_DECAY {
+            probes.acked /= 2;
+            probes.lost /= 2;
+        }
+    }
+
+    /// Latest quality estimate for a peer, if any probes were sent.
+    pub fn quality(&self, peer: &EndpointId) -> Option<ConnectionQuality> {
+        self.peers.get(peer).map(PeerProbes::quality)
+    }
+
+    /// Drop bookkeeping for a departed peer.
+    pub fn forget_peer(&mut self, peer: &EndpointId) {
+        self.peers.remove(peer);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -197,6 +542,141 @@ mod tests {
         assert_eq!(cursor.selection, Some((40, 50)));
     }
 
+    #[test]
+    fn test_awareness_update_records_viewport_and_adopts_color() {
+        let mut tracker = PresenceTracker::new();
+        let node_id = test_node_id();
+
+        tracker.add_collaborator(node_id, "did:plc:test".into(), "Carol".into());
+        tracker.update_awareness(&node_id, 7, None, Some((0, 120)), Some(0xAA96DAFF));
+
+        let collab = tracker.get(&node_id).unwrap();
+        assert_eq!(collab.color, 0xAA96DAFF);
+        let cursor = collab.cursor.as_ref().unwrap();
+        assert_eq!(cursor.position, 7);
+        assert_eq!(cursor.viewport, Some((0, 120)));
+        assert_eq!(cursor.color, 0xAA96DAFF);
+    }
+
+    #[test]
+    fn test_throttle_suppresses_duplicates_and_bursts() {
+        let mut throttle = AwarenessThrottle::new();
+
+        assert!(throttle.should_send(5, None, None));
+        // Same state: never resent, regardless of elapsed time.
+        assert!(!throttle.should_send(5, None, None));
+        // Changed state inside the minimum interval: suppressed.
+        assert!(!throttle.should_send(6, None, None));
+
+        std::thread::sleep(AWARENESS_MIN_INTERVAL + Duration::from_millis(10));
+        // Changed state after the interval: goes out.
+        assert!(throttle.should_send(6, None, None));
+    }
+
+    #[test]
+    fn test_prune_stale_cursors_clears_timed_out_entries() {
+        let mut tracker = PresenceTracker::new();
+        let node_id = test_node_id();
+
+        tracker.add_collaborator(node_id, "did:plc:test".into(), "Dave".into());
+        tracker.update_cursor(&node_id, 3, None);
+        assert!(tracker.get(&node_id).unwrap().cursor.is_some());
+
+        // Zero max age makes everything stale without a long wait; the
+        // tiny sleep guarantees a nonzero cursor age.
+        std::thread::sleep(Duration::from_millis(1));
+        tracker.prune_stale_cursors(Duration::from_secs(0));
+        assert!(tracker.get(&node_id).unwrap().cursor.is_none());
+    }
+
+    #[test]
+    fn test_sweep_silent_marks_and_reports_once() {
+        let mut tracker = PresenceTracker::with_heartbeat_config(HeartbeatConfig {
+            interval: Duration::from_millis(1),
+            stale_after: Duration::from_millis(1),
+            disconnect_after: Duration::from_millis(1),
+        });
+        let node_id = test_node_id();
+        tracker.add_collaborator(node_id, "did:plc:test".into(), "Eve".into());
+        tracker.update_cursor(&node_id, 5, None);
+
+        std::thread::sleep(Duration::from_millis(5));
+        let newly = tracker.sweep_silent();
+        assert_eq!(newly.len(), 1);
+        assert_eq!(tracker.liveness(&node_id), Some(Liveness::Disconnected));
+        // Still listed (UI greys them out), cursor cleared.
+        assert!(tracker.contains(&node_id));
+        assert!(tracker.get(&node_id).unwrap().cursor.is_none());
+
+        // A second sweep must not report the same disconnect again.
+        assert!(tracker.sweep_silent().is_empty());
+    }
+
+    #[test]
+    fn test_activity_revives_disconnected_collaborator() {
+        let mut tracker = PresenceTracker::with_heartbeat_config(HeartbeatConfig {
+            interval: Duration::from_millis(1),
+            stale_after: Duration::from_secs(60),
+            disconnect_after: Duration::from_millis(1),
+        });
+        let node_id = test_node_id();
+        tracker.add_collaborator(node_id, "did:plc:test".into(), "Frank".into());
+
+        std::thread::sleep(Duration::from_millis(5));
+        tracker.sweep_silent();
+        assert_eq!(tracker.liveness(&node_id), Some(Liveness::Disconnected));
+
+        tracker.record_activity(&node_id);
+        assert_eq!(tracker.liveness(&node_id), Some(Liveness::Active));
+    }
+
+    #[test]
+    fn test_heartbeat_monitor_measures_rtt_on_ack() {
+        let mut monitor = HeartbeatMonitor::new();
+        let peer = test_node_id();
+
+        let seq = monitor.tick([peer]).expect("first tick always beats");
+        monitor.record_ack(&peer, seq);
+
+        let quality = monitor.quality(&peer).unwrap();
+        assert!(quality.rtt.is_some());
+        assert_eq!(quality.packet_loss, 0.0);
+    }
+
+    #[test]
+    fn test_heartbeat_monitor_counts_skipped_probes_as_lost() {
+        let mut monitor = HeartbeatMonitor::with_config(HeartbeatConfig {
+            interval: Duration::from_secs(0),
+            ..HeartbeatConfig::default()
+        });
+        let peer = test_node_id();
+
+        let first = monitor.tick([peer]).unwrap();
+        let second = monitor.tick([peer]).unwrap();
+        assert_ne!(first, second);
+
+        // Ack only the newer probe; the skipped one counts as lost.
+        monitor.record_ack(&peer, second);
+        let quality = monitor.quality(&peer).unwrap();
+        assert_eq!(quality.packet_loss, 0.5);
+
+        monitor.forget_peer(&peer);
+        assert!(monitor.quality(&peer).is_none());
+    }
+
+    #[test]
+    fn test_heartbeat_monitor_respects_interval() {
+        let mut monitor = HeartbeatMonitor::with_config(HeartbeatConfig {
+            interval: Duration::from_secs(60),
+            ..HeartbeatConfig::default()
+        });
+        let peer = test_node_id();
+
+        assert!(monitor.tick([peer]).is_some());
+        // Second beat inside the interval is suppressed.
+        assert!(monitor.tick([peer]).is_none());
+    }
+
     #[test]
     fn test_color_assignment() {
         let mut tracker = PresenceTracker::new();
@@ -19,6 +19,22 @@ pub struct RemoteCursorInfo {
     pub color: u32,
 }
 
+/// Heartbeat-derived liveness for rendering.
+///
+/// String mirror of the tracker's `Liveness` so it serializes without
+/// the iroh feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionState {
+    /// Heard from recently.
+    #[default]
+    Active,
+    /// Missed a few heartbeats; connection may be degraded.
+    Stale,
+    /// Silent past the disconnect threshold without a Leave.
+    Disconnected,
+}
+
 /// Collaborator info for presence display.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CollaboratorInfo {
@@ -34,6 +50,18 @@ pub struct CollaboratorInfo {
     pub cursor_position: Option<usize>,
     /// Current selection (if any)
     pub selection: Option<(usize, usize)>,
+    /// Visible character range (start, end), if the peer shared one
+    #[serde(default)]
+    pub viewport: Option<(usize, usize)>,
+    /// Heartbeat-derived liveness
+    #[serde(default)]
+    pub connection_state: ConnectionState,
+    /// Smoothed round-trip time in milliseconds, if measured
+    #[serde(default)]
+    pub rtt_ms: Option<u32>,
+    /// Estimated heartbeat loss fraction (0..=1), if measured
+    #[serde(default)]
+    pub packet_loss: Option<f32>,
 }
 
 /// Presence update sent from worker to main thread.
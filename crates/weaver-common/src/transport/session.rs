@@ -11,6 +11,7 @@ use n0_future::StreamExt;
 use n0_future::boxed::BoxStream;
 use n0_future::stream;
 
+use super::encryption::SessionKey;
 use super::{CollabMessage, CollabNode, SignedMessage};
 
 /// Topic ID for a gossip session - derived from resource URI.
@@ -60,6 +61,9 @@ pub struct CollabSession {
     topic: TopicId,
     sender: GossipSender,
     node: Arc<CollabNode>,
+    /// End-to-end encryption key; without it payloads are readable by
+    /// the relay.
+    session_key: Option<SessionKey>,
 }
 
 impl CollabSession {
@@ -80,6 +84,20 @@ impl CollabSession {
         node: Arc<CollabNode>,
         topic: TopicId,
         bootstrap_peers: Vec<EndpointId>,
+    ) -> Result<(Self, BoxStream<Result<SessionEvent, SessionError>>), SessionError> {
+        Self::join_with_key(node, topic, bootstrap_peers, None).await
+    }
+
+    /// Join a collaboration session with end-to-end encryption.
+    ///
+    /// All outgoing messages are sealed with the session key and
+    /// plaintext messages from peers are rejected, so a session either
+    /// is encrypted or is not - peers cannot downgrade it.
+    pub async fn join_with_key(
+        node: Arc<CollabNode>,
+        topic: TopicId,
+        bootstrap_peers: Vec<EndpointId>,
+        session_key: Option<SessionKey>,
     ) -> Result<(Self, BoxStream<Result<SessionEvent, SessionError>>), SessionError> {
         tracing::info!(
             topic = ?topic,
@@ -113,71 +131,81 @@ impl CollabSession {
             topic,
             sender,
             node: node.clone(),
+            session_key: session_key.clone(),
         };
 
         // Create event stream from the gossip receiver
-        let event_stream = Self::event_stream(receiver);
+        let event_stream = Self::event_stream(receiver, session_key);
 
         Ok((session, event_stream))
     }
 
     /// Convert gossip receiver into a stream of session events.
-    fn event_stream(receiver: GossipReceiver) -> BoxStream<Result<SessionEvent, SessionError>> {
-        let stream = stream::try_unfold(receiver, |mut receiver| async move {
-            loop {
-                let Some(event) = receiver.try_next().await.map_err(|e| {
-                    tracing::error!(?e, "CollabSession: gossip receiver error");
-                    SessionError::Decode(Box::new(e))
-                })?
-                else {
-                    tracing::debug!("CollabSession: gossip stream ended");
-                    return Ok(None);
-                };
-
-                tracing::debug!(?event, "CollabSession: raw gossip event");
-                let session_event = match event {
-                    Event::NeighborUp(peer) => {
-                        tracing::info!(peer = %peer, "CollabSession: neighbor up");
-                        SessionEvent::PeerJoined(peer)
-                    }
-                    Event::NeighborDown(peer) => {
-                        tracing::info!(peer = %peer, "CollabSession: neighbor down");
-                        SessionEvent::PeerLeft(peer)
-                    }
-                    Event::Received(msg) => {
-                        tracing::debug!(
-                            from = %msg.delivered_from,
-                            bytes = msg.content.len(),
-                            "CollabSession: received message"
-                        );
-                        match SignedMessage::decode_and_verify(&msg.content) {
-                            Ok(received) => {
-                                // Verify claimed sender matches transport sender
-                                if received.from != msg.delivered_from {
-                                    tracing::warn!(
-                                        claimed = %received.from,
-                                        transport = %msg.delivered_from,
-                                        "sender mismatch - possible spoofing attempt"
-                                    );
-                                    continue;
+    fn event_stream(
+        receiver: GossipReceiver,
+        session_key: Option<SessionKey>,
+    ) -> BoxStream<Result<SessionEvent, SessionError>> {
+        let stream = stream::try_unfold(receiver, move |mut receiver| {
+            let session_key = session_key.clone();
+            async move {
+                loop {
+                    let Some(event) = receiver.try_next().await.map_err(|e| {
+                        tracing::error!(?e, "CollabSession: gossip receiver error");
+                        SessionError::Decode(Box::new(e))
+                    })?
+                    else {
+                        tracing::debug!("CollabSession: gossip stream ended");
+                        return Ok(None);
+                    };
+
+                    tracing::debug!(?event, "CollabSession: raw gossip event");
+                    let session_event = match event {
+                        Event::NeighborUp(peer) => {
+                            tracing::info!(peer = %peer, "CollabSession: neighbor up");
+                            SessionEvent::PeerJoined(peer)
+                        }
+                        Event::NeighborDown(peer) => {
+                            tracing::info!(peer = %peer, "CollabSession: neighbor down");
+                            SessionEvent::PeerLeft(peer)
+                        }
+                        Event::Received(msg) => {
+                            tracing::debug!(
+                                from = %msg.delivered_from,
+                                bytes = msg.content.len(),
+                                "CollabSession: received message"
+                            );
+                            match SignedMessage::decode_verify_open(
+                                &msg.content,
+                                session_key.as_ref(),
+                            ) {
+                                Ok(received) => {
+                                    // Verify claimed sender matches transport sender
+                                    if received.from != msg.delivered_from {
+                                        tracing::warn!(
+                                            claimed = %received.from,
+                                            transport = %msg.delivered_from,
+                                            "sender mismatch - possible spoofing attempt"
+                                        );
+                                        continue;
+                                    }
+                                    SessionEvent::Message {
+                                        from: received.from,
+                                        message: received.message,
+                                    }
                                 }
-                                SessionEvent::Message {
-                                    from: received.from,
-                                    message: received.message,
+                                Err(e) => {
+                                    tracing::warn!(?e, "failed to verify/decode signed message");
+                                    continue;
                                 }
                             }
-                            Err(e) => {
-                                tracing::warn!(?e, "failed to verify/decode signed message");
-                                continue;
-                            }
                         }
-                    }
-                    Event::Lagged => {
-                        tracing::warn!("gossip receiver lagged, some messages may be lost");
-                        continue;
-                    }
-                };
-                break Ok(Some((session_event, receiver)));
+                        Event::Lagged => {
+                            tracing::warn!("gossip receiver lagged, some messages may be lost");
+                            continue;
+                        }
+                    };
+                    break Ok(Some((session_event, receiver)));
+                }
             }
         });
 
@@ -185,9 +213,16 @@ impl CollabSession {
     }
 
     /// Broadcast a signed message to all peers in the session.
+    ///
+    /// Sealed with the session key first when the session is encrypted.
     pub async fn broadcast(&self, message: &CollabMessage) -> Result<(), SessionError> {
-        let bytes = SignedMessage::sign_and_encode(&self.node.secret_key(), message)
-            .map_err(|e| SessionError::Broadcast(Box::new(e)))?;
+        let bytes = match &self.session_key {
+            Some(key) => {
+                SignedMessage::sign_encrypt_and_encode(&self.node.secret_key(), key, message)
+            }
+            None => SignedMessage::sign_and_encode(&self.node.secret_key(), message),
+        }
+        .map_err(|e| SessionError::Broadcast(Box::new(e)))?;
 
         tracing::debug!(
             bytes = bytes.len(),
@@ -0,0 +1,504 @@
+#![cfg(feature = "webrtc")]
+
+//! WebRTC fallback transport for networks that block the iroh relay.
+//!
+//! Mirrors the iroh [`CollabSession`] surface: the same [`SessionEvent`]s,
+//! the same signed wire format, and the same peer identities (iroh
+//! endpoint keys), so the editor above does not care which transport
+//! carried a message. What differs is the path bytes take: a full mesh
+//! of WebRTC data channels, signaled by exchanging offers, answers, and
+//! ICE candidates through the collaborators' session records.
+//!
+//! This module is platform-independent. The browser layer owns the
+//! actual `RtcPeerConnection` objects and bridges each open data channel
+//! to a [`PeerChannel`]; tests drive the same handles with in-memory
+//! channels.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use iroh::{EndpointId, SecretKey};
+use n0_future::boxed::BoxStream;
+use n0_future::{FutureExt, StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+
+use super::encryption::SessionKey;
+use super::{
+    CollabMessage, CollabNode, CollabSession, SessionError, SessionEvent, SignedMessage, TopicId,
+};
+
+/// Buffered events between the transport glue and the session stream.
+const EVENT_BUFFER: usize = 256;
+
+/// Buffered outgoing messages per peer data channel.
+const PEER_BUFFER: usize = 64;
+
+// ============================================================================
+// Signaling
+// ============================================================================
+
+/// A single signaling step, exchanged through session records.
+///
+/// Field names follow the W3C `RTCIceCandidateInit` / session
+/// description shapes so the browser layer can pass them through
+/// without translation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SignalPayload {
+    /// SDP offer from the initiating side.
+    Offer { sdp: String },
+    /// SDP answer from the accepting side.
+    Answer { sdp: String },
+    /// Trickled ICE candidate.
+    #[serde(rename_all = "camelCase")]
+    IceCandidate {
+        candidate: String,
+        sdp_mid: Option<String>,
+        sdp_m_line_index: Option<u16>,
+    },
+}
+
+/// An addressed signal, as published in the sender's session record.
+///
+/// Session records are the only rendezvous both peers can already
+/// reach (the same records used for iroh peer discovery), so signals
+/// ride along in the record's open extra data and peers poll for
+/// entries addressed to them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebRtcSignal {
+    /// Sender's node ID, z-base32 (same encoding as session records).
+    pub from: String,
+    /// Recipient's node ID, z-base32.
+    pub to: String,
+    /// Monotonic per (from, to) pair; receivers apply signals in order
+    /// and ignore anything at or below the last seq they consumed.
+    pub seq: u64,
+    /// The signaling step itself.
+    pub payload: SignalPayload,
+}
+
+impl WebRtcSignal {
+    /// Serialize for storage in a session record.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Parse a signal read back from a session record.
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+}
+
+/// Whether the local peer creates the offer for this pairing.
+///
+/// Both sides derive the role from the key ordering, so they agree
+/// without an extra round trip and never produce crossing offers
+/// (glare). The lower endpoint ID offers.
+pub fn is_initiator(local: &EndpointId, remote: &EndpointId) -> bool {
+    local.as_bytes() < remote.as_bytes()
+}
+
+// ============================================================================
+// Session
+// ============================================================================
+
+/// Raw events pushed by the platform glue and control paths.
+enum RawEvent {
+    PeerJoined(EndpointId),
+    PeerLeft(EndpointId),
+    Data { from: EndpointId, bytes: Vec<u8> },
+}
+
+/// Handle for pushing one peer's received bytes into the session.
+///
+/// The browser layer calls [`IncomingHandle::push`] from the data
+/// channel's message callback and [`IncomingHandle::closed`] when the
+/// channel closes.
+pub struct IncomingHandle {
+    peer: EndpointId,
+    events: mpsc::Sender<RawEvent>,
+}
+
+impl IncomingHandle {
+    /// Deliver bytes received on the peer's data channel.
+    pub async fn push(&self, bytes: Vec<u8>) -> Result<(), SessionError> {
+        self.events
+            .send(RawEvent::Data {
+                from: self.peer,
+                bytes,
+            })
+            .await
+            .map_err(|_| SessionError::Closed)
+    }
+
+    /// Signal that the peer's data channel closed.
+    pub async fn closed(self) {
+        let _ = self.events.send(RawEvent::PeerLeft(self.peer)).await;
+    }
+}
+
+/// Byte-level handles for one peer's data channel.
+///
+/// The platform layer bridges these to an `RtcDataChannel`: everything
+/// arriving on `outgoing` is written to the channel, and every message
+/// event is pushed through `incoming`.
+pub struct PeerChannel {
+    /// The peer this channel carries traffic for.
+    pub peer: EndpointId,
+    /// Bytes the session wants sent to the peer.
+    pub outgoing: mpsc::Receiver<Vec<u8>>,
+    /// Where bytes received from the peer go.
+    pub incoming: IncomingHandle,
+}
+
+/// A collaboration session carried over WebRTC data channels.
+///
+/// API mirror of [`CollabSession`]: same events, same signed wire
+/// format. Connectivity is the caller's job — drive the signaling
+/// exchange, then register each opened data channel with
+/// [`WebRtcSession::connect_peer`].
+pub struct WebRtcSession {
+    topic: TopicId,
+    secret_key: SecretKey,
+    peers: Arc<Mutex<HashMap<EndpointId, mpsc::Sender<Vec<u8>>>>>,
+    events: mpsc::Sender<RawEvent>,
+    /// End-to-end encryption key; without it payloads are readable by
+    /// anyone relaying the data channel.
+    session_key: Option<SessionKey>,
+}
+
+impl WebRtcSession {
+    /// Create a session and its event stream.
+    ///
+    /// The secret key is the node's iroh identity; peers verify message
+    /// signatures against it exactly as they do over gossip.
+    pub fn new(
+        secret_key: SecretKey,
+        topic: TopicId,
+    ) -> (Self, BoxStream<Result<SessionEvent, SessionError>>) {
+        Self::new_with_key(secret_key, topic, None)
+    }
+
+    /// Create an end-to-end encrypted session and its event stream.
+    ///
+    /// Same encryption contract as [`CollabSession::join_with_key`]:
+    /// outgoing messages are sealed and plaintext from peers is
+    /// rejected.
+    pub fn new_with_key(
+        secret_key: SecretKey,
+        topic: TopicId,
+        session_key: Option<SessionKey>,
+    ) -> (Self, BoxStream<Result<SessionEvent, SessionError>>) {
+        let (events_tx, events_rx) = mpsc::channel(EVENT_BUFFER);
+
+        let session = Self {
+            topic,
+            secret_key,
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            events: events_tx,
+            session_key: session_key.clone(),
+        };
+
+        (session, Self::event_stream(events_rx, session_key))
+    }
+
+    /// Convert raw transport events into verified session events.
+    fn event_stream(
+        receiver: mpsc::Receiver<RawEvent>,
+        session_key: Option<SessionKey>,
+    ) -> BoxStream<Result<SessionEvent, SessionError>> {
+        let stream = stream::try_unfold(receiver, move |mut receiver| {
+            let session_key = session_key.clone();
+            async move {
+                loop {
+                    let Some(event) = receiver.recv().await else {
+                        return Ok(None);
+                    };
+
+                    let session_event = match event {
+                        RawEvent::PeerJoined(peer) => SessionEvent::PeerJoined(peer),
+                        RawEvent::PeerLeft(peer) => SessionEvent::PeerLeft(peer),
+                        RawEvent::Data { from, bytes } => {
+                            match SignedMessage::decode_verify_open(&bytes, session_key.as_ref()) {
+                                Ok(received) => {
+                                    // Verify claimed sender matches the channel the
+                                    // bytes arrived on, same as the gossip path.
+                                    if received.from != from {
+                                        tracing::warn!(
+                                            claimed = %received.from,
+                                            transport = %from,
+                                            "sender mismatch - possible spoofing attempt"
+                                        );
+                                        continue;
+                                    }
+                                    SessionEvent::Message {
+                                        from: received.from,
+                                        message: received.message,
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::warn!(?e, "failed to verify/decode signed message");
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+                    break Ok(Some((session_event, receiver)));
+                }
+            }
+        });
+
+        Box::pin(stream)
+    }
+
+    /// Register an opened data channel for a peer.
+    ///
+    /// Emits [`SessionEvent::PeerJoined`] and returns the byte-level
+    /// handles the platform glue wires to the channel. Connecting a
+    /// peer twice replaces the previous channel.
+    pub async fn connect_peer(&self, peer: EndpointId) -> Result<PeerChannel, SessionError> {
+        let (outgoing_tx, outgoing_rx) = mpsc::channel(PEER_BUFFER);
+
+        if let Ok(mut peers) = self.peers.lock() {
+            peers.insert(peer, outgoing_tx);
+        }
+
+        self.events
+            .send(RawEvent::PeerJoined(peer))
+            .await
+            .map_err(|_| SessionError::Closed)?;
+
+        Ok(PeerChannel {
+            peer,
+            outgoing: outgoing_rx,
+            incoming: IncomingHandle {
+                peer,
+                events: self.events.clone(),
+            },
+        })
+    }
+
+    /// Drop a peer's channel and emit [`SessionEvent::PeerLeft`].
+    pub async fn disconnect_peer(&self, peer: EndpointId) {
+        let removed = match self.peers.lock() {
+            Ok(mut peers) => peers.remove(&peer).is_some(),
+            Err(_) => false,
+        };
+        if removed {
+            let _ = self.events.send(RawEvent::PeerLeft(peer)).await;
+        }
+    }
+
+    /// Broadcast a signed message to all connected peers.
+    ///
+    /// Peers whose channel has gone away are dropped from the mesh and
+    /// reported as [`SessionEvent::PeerLeft`].
+    pub async fn broadcast(&self, message: &CollabMessage) -> Result<(), SessionError> {
+        let bytes = match &self.session_key {
+            Some(key) => SignedMessage::sign_encrypt_and_encode(&self.secret_key, key, message),
+            None => SignedMessage::sign_and_encode(&self.secret_key, message),
+        }
+        .map_err(|e| SessionError::Broadcast(Box::new(e)))?;
+
+        // Clone senders out so the lock is not held across awaits.
+        let senders: Vec<(EndpointId, mpsc::Sender<Vec<u8>>)> = match self.peers.lock() {
+            Ok(peers) => peers.iter().map(|(id, tx)| (*id, tx.clone())).collect(),
+            Err(_) => Vec::new(),
+        };
+
+        let mut dead = Vec::new();
+        for (peer, sender) in senders {
+            if sender.send(bytes.clone()).await.is_err() {
+                dead.push(peer);
+            }
+        }
+
+        for peer in dead {
+            self.disconnect_peer(peer).await;
+        }
+
+        Ok(())
+    }
+
+    /// Get the topic ID for this session.
+    pub fn topic(&self) -> TopicId {
+        self.topic
+    }
+
+    /// Number of peers with a registered data channel.
+    pub fn peer_count(&self) -> usize {
+        self.peers.lock().map(|peers| peers.len()).unwrap_or(0)
+    }
+}
+
+// ============================================================================
+// Automatic fallback
+// ============================================================================
+
+/// A session joined by [`join_with_fallback`].
+pub enum FallbackSession {
+    /// The gossip swarm was reachable; collaboration runs over iroh.
+    Iroh {
+        session: CollabSession,
+        events: BoxStream<Result<SessionEvent, SessionError>>,
+    },
+    /// The swarm could not be reached in time; the caller should start
+    /// the WebRTC signaling exchange with the known peers.
+    WebRtc {
+        session: WebRtcSession,
+        events: BoxStream<Result<SessionEvent, SessionError>>,
+    },
+}
+
+/// Join over iroh, falling back to WebRTC when the swarm cannot be
+/// reached within `connect_timeout`.
+///
+/// "Reached" means the gossip session produced its first event (a
+/// neighbor coming up); on networks that block the relay this never
+/// happens and the attempt times out. With no bootstrap peers there is
+/// nobody to open data channels to either, so the iroh session is
+/// returned as-is and peers found later can still be joined normally.
+pub async fn join_with_fallback(
+    node: Arc<CollabNode>,
+    topic: TopicId,
+    bootstrap_peers: Vec<EndpointId>,
+    session_key: Option<SessionKey>,
+    connect_timeout: Duration,
+) -> Result<FallbackSession, SessionError> {
+    let has_peers = !bootstrap_peers.is_empty();
+    let (session, mut events) =
+        CollabSession::join_with_key(node.clone(), topic, bootstrap_peers, session_key.clone())
+            .await?;
+
+    if !has_peers {
+        return Ok(FallbackSession::Iroh { session, events });
+    }
+
+    enum First {
+        Event(Option<Result<SessionEvent, SessionError>>),
+        TimedOut,
+    }
+
+    let first = async { First::Event(events.next().await) }
+        .race(async {
+            n0_future::time::sleep(connect_timeout).await;
+            First::TimedOut
+        })
+        .await;
+
+    match first {
+        First::Event(Some(Ok(event))) => {
+            // Re-attach the consumed event so the caller sees the full
+            // stream.
+            let events = Box::pin(stream::iter([Ok(event)]).chain(events));
+            Ok(FallbackSession::Iroh { session, events })
+        }
+        First::Event(_) | First::TimedOut => {
+            tracing::info!(
+                topic = ?topic,
+                timeout = ?connect_timeout,
+                "iroh swarm unreachable, falling back to WebRTC transport"
+            );
+            let (session, events) =
+                WebRtcSession::new_with_key(node.secret_key(), topic, session_key);
+            Ok(FallbackSession::WebRtc { session, events })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair() -> (SecretKey, EndpointId) {
+        let secret = SecretKey::generate(&mut rand::rng());
+        let public = secret.public();
+        (secret, public)
+    }
+
+    #[test]
+    fn initiator_role_is_deterministic_and_antisymmetric() {
+        let (_, a) = keypair();
+        let (_, b) = keypair();
+
+        assert_eq!(is_initiator(&a, &b), !is_initiator(&b, &a));
+        assert_eq!(is_initiator(&a, &b), is_initiator(&a, &b));
+    }
+
+    #[test]
+    fn signal_roundtrips_through_record_json() {
+        let signal = WebRtcSignal {
+            from: "node-a".into(),
+            to: "node-b".into(),
+            seq: 3,
+            payload: SignalPayload::IceCandidate {
+                candidate: "candidate:1 1 udp 2122260223 192.0.2.1 54400 typ host".into(),
+                sdp_mid: Some("0".into()),
+                sdp_m_line_index: Some(0),
+            },
+        };
+
+        let json = signal.to_json().unwrap();
+        let decoded = WebRtcSignal::from_json(&json).unwrap();
+
+        assert_eq!(decoded.from, "node-a");
+        assert_eq!(decoded.to, "node-b");
+        assert_eq!(decoded.seq, 3);
+        match decoded.payload {
+            SignalPayload::IceCandidate { sdp_mid, .. } => {
+                assert_eq!(sdp_mid.as_deref(), Some("0"));
+            }
+            _ => panic!("wrong variant"),
+        }
+    }
+
+    #[tokio::test]
+    async fn broadcast_reaches_verified_peer() {
+        let (key_a, id_a) = keypair();
+        let (key_b, id_b) = keypair();
+        let topic = CollabSession::topic_from_uri("at://did:plc:a/sh.weaver.notebook.entry/x");
+
+        let (session_a, _events_a) = WebRtcSession::new(key_a, topic);
+        let (session_b, mut events_b) = WebRtcSession::new(key_b, topic);
+
+        let channel_ab = session_a.connect_peer(id_b).await.unwrap();
+        let channel_ba = session_b.connect_peer(id_a).await.unwrap();
+
+        // Bridge a's outgoing bytes into b, standing in for the data
+        // channel the browser layer would provide.
+        tokio::spawn(async move {
+            let mut outgoing = channel_ab.outgoing;
+            while let Some(bytes) = outgoing.recv().await {
+                if channel_ba.incoming.push(bytes).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        session_a
+            .broadcast(&CollabMessage::Leave {
+                did: "did:plc:a".into(),
+            })
+            .await
+            .unwrap();
+
+        // b sees its own PeerJoined for a first, then the message.
+        match events_b.next().await {
+            Some(Ok(SessionEvent::PeerJoined(peer))) => assert_eq!(peer, id_a),
+            other => panic!(
+                "expected PeerJoined, got {:?}",
+                other.map(|r| r.map(|_| ()))
+            ),
+        }
+        match events_b.next().await {
+            Some(Ok(SessionEvent::Message { from, message })) => {
+                assert_eq!(from, id_a);
+                assert!(matches!(message, CollabMessage::Leave { .. }));
+            }
+            other => panic!("expected Message, got {:?}", other.map(|r| r.map(|_| ()))),
+        }
+    }
+}
@@ -89,7 +89,7 @@ impl CursorPlatform for BrowserCursor {
 ///
 /// Returns the offset map of the paragraph containing the given offset,
 /// or an empty slice if no paragraph contains it.
-fn find_offset_map_for_char(
+pub(crate) fn find_offset_map_for_char(
     paragraphs: &[ParagraphRender],
     char_offset: usize,
 ) -> &[OffsetMapping] {
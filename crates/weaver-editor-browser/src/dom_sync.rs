@@ -528,6 +528,79 @@ pub fn sync_cursor_and_visibility<D: EditorDocument>(
     update_syntax_visibility(cursor_offset, selection.as_ref(), syntax_spans, paragraphs);
 }
 
+/// Whether paragraph DOM replacement should be deferred right now.
+///
+/// While an IME composition is active the browser owns the composing
+/// paragraph's content; replacing innerHTML mid-composition aborts the
+/// composition and drops the preview text. Callers of [`update_paragraph_dom`]
+/// should check this and skip the update until compositionend has committed
+/// the text (see the composition state machine in [`crate::events`]).
+pub fn should_defer_dom_update<D: EditorDocument>(doc: &D) -> bool {
+    doc.composition().is_some()
+}
+
+/// Class applied to the paragraph element under an active drag.
+///
+/// Styling is left to the consumer's stylesheet (e.g. a top border as an
+/// insertion indicator); this module only toggles the class.
+pub const DROP_TARGET_CLASS: &str = "drop-target";
+
+/// Mark the paragraph containing `offset` as the current drop target.
+///
+/// Call from dragover with the offset from
+/// [`crate::events::drop_target_offset`]; the class is moved between
+/// paragraphs as the pointer travels so only one indicator is ever visible.
+/// Returns true if a paragraph was marked.
+pub fn set_drop_indicator(
+    editor_id: &str,
+    paragraphs: &[ParagraphRender],
+    offset: usize,
+) -> bool {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return false;
+    };
+    if document.get_element_by_id(editor_id).is_none() {
+        return false;
+    }
+
+    let mut marked = false;
+    for para in paragraphs {
+        let Some(elem) = document.get_element_by_id(&para.id) else {
+            continue;
+        };
+        if para.char_range.start <= offset && offset <= para.char_range.end && !marked {
+            let _ = elem.class_list().add_1(DROP_TARGET_CLASS);
+            marked = true;
+        } else {
+            let _ = elem.class_list().remove_1(DROP_TARGET_CLASS);
+        }
+    }
+    marked
+}
+
+/// Remove any drop-target indicator from the editor.
+///
+/// Call from dragleave and after the drop has been applied.
+pub fn clear_drop_indicator(editor_id: &str) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    let Some(editor) = document.get_element_by_id(editor_id) else {
+        return;
+    };
+
+    let selector = format!(".{}", DROP_TARGET_CLASS);
+    if let Ok(marked) = editor.query_selector_all(&selector) {
+        for i in 0..marked.length() {
+            if let Some(node) = marked.get(i) {
+                if let Some(elem) = node.dyn_ref::<web_sys::Element>() {
+                    let _ = elem.class_list().remove_1(DROP_TARGET_CLASS);
+                }
+            }
+        }
+    }
+}
+
 /// Update paragraph DOM elements incrementally.
 ///
 /// Uses stable content-based paragraph IDs for efficient DOM reconciliation:
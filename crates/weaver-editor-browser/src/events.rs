@@ -536,6 +536,18 @@ pub fn get_math_click_offset(target: &web_sys::EventTarget) -> Option<usize> {
     char_target.parse().ok()
 }
 
+/// Check if a click target is a failed-embed retry button.
+///
+/// Returns the embed's AT URI if the click was on (or inside) an element
+/// carrying a `data-embed-retry` attribute, None otherwise.
+pub fn get_embed_retry_uri(target: &web_sys::EventTarget) -> Option<String> {
+    use wasm_bindgen::JsCast;
+
+    let element = target.dyn_ref::<web_sys::Element>()?;
+    let retry_el = element.closest("[data-embed-retry]").ok()??;
+    retry_el.get_attribute("data-embed-retry")
+}
+
 /// Handle a click that might be on a math element.
 ///
 /// If the click target is a math-clickable element, this updates the cursor,
@@ -562,18 +574,21 @@ pub fn handle_math_click<D: EditorDocument>(
     }
 }
 
-// === Composition (IME) event handlers ===
-
-/// Handle composition start event.
+// === Composition (IME) state machine ===
+//
+// IME input arrives as a compositionstart / compositionupdate* / compositionend
+// sequence. The browser owns the in-progress preview text, so the state machine
+// defers all model mutations (and, via [`crate::dom_sync::should_defer_dom_update`],
+// paragraph innerHTML replacement) until compositionend: start/update only
+// track the composing range, and end commits the final text in one edit and
+// restores the cursor after it.
+
+/// Begin an IME composition at the current cursor.
 ///
-/// Clears any existing selection (composition replaces it) and sets up
-/// composition state tracking.
-#[cfg(feature = "dioxus")]
-pub fn handle_compositionstart<D: EditorDocument>(
-    evt: dioxus_core::Event<dioxus_html::CompositionData>,
-    doc: &mut D,
-) {
-    let data = evt.data().data();
+/// Clears any existing selection (composition replaces it) and records the
+/// composition state. No text is inserted into the model yet - the browser
+/// renders the in-progress composition preview.
+pub fn composition_start<D: EditorDocument>(doc: &mut D, data: String) {
     tracing::trace!(data = %data, "compositionstart");
 
     // Delete selection if present (composition replaces it).
@@ -593,15 +608,11 @@ pub fn handle_compositionstart<D: EditorDocument>(
     }));
 }
 
-/// Handle composition update event.
+/// Update the in-progress composition text.
 ///
-/// Updates the composition text as the user types or selects IME suggestions.
-#[cfg(feature = "dioxus")]
-pub fn handle_compositionupdate<D: EditorDocument>(
-    evt: dioxus_core::Event<dioxus_html::CompositionData>,
-    doc: &mut D,
-) {
-    let data = evt.data().data();
+/// Tracks the text as the user types or cycles IME candidates; the model is
+/// not mutated until [`composition_end`].
+pub fn composition_update<D: EditorDocument>(doc: &mut D, data: String) {
     tracing::trace!(data = %data, "compositionupdate");
 
     if let Some(mut comp) = doc.composition() {
@@ -612,16 +623,13 @@ pub fn handle_compositionupdate<D: EditorDocument>(
     }
 }
 
-/// Handle composition end event.
+/// Commit an IME composition.
 ///
-/// Finalizes the composition by inserting the final text into the document.
-/// Also handles zero-width character cleanup that some IMEs leave behind.
-#[cfg(feature = "dioxus")]
-pub fn handle_compositionend<D: EditorDocument>(
-    evt: dioxus_core::Event<dioxus_html::CompositionData>,
-    doc: &mut D,
-) {
-    let final_text = evt.data().data();
+/// Finalizes the composition by inserting the final text into the document as
+/// a single edit, cleans up zero-width characters that some IMEs leave
+/// behind, and places the cursor after the committed text. An empty final
+/// text means the composition was cancelled and the model stays untouched.
+pub fn composition_end<D: EditorDocument>(doc: &mut D, final_text: String) {
     tracing::trace!(data = %final_text, "compositionend");
 
     // Record when composition ended for Safari timing workaround.
@@ -669,3 +677,487 @@ pub fn handle_compositionend<D: EditorDocument>(
         tracing::debug!("compositionend without active composition state");
     }
 }
+
+/// Range the active composition would occupy once committed.
+///
+/// Returns None when no composition is in progress. Useful for hit-testing
+/// and for keeping decorations away from the browser-managed preview.
+pub fn composing_range<D: EditorDocument>(doc: &D) -> Option<Range> {
+    let comp = doc.composition()?;
+    Some(Range::new(comp.start_offset, comp.end_offset()))
+}
+
+// === Drag and drop ===
+//
+// Drops arrive with a DataTransfer that can carry several kinds of content:
+// files (images, markdown/text documents), plain text dragged from other
+// apps, or our own selection dragged within the document. Text-ish content
+// is applied synchronously; files have to be read through async browser
+// APIs, so they are extracted as [`DroppedFile`]s and classified for the
+// caller to route (images go into the app's upload flow, which lives above
+// this crate).
+
+/// Custom MIME type marking a drag that originated from our own selection.
+///
+/// Written by [`handle_dragstart`], matched on drop to detect intra-document
+/// block moves. Mirrors the clipboard's `text/x-weaver-md` internal-paste
+/// detection.
+pub const INTERNAL_DRAG_MIME: &str = "text/x-weaver-md";
+
+/// A file extracted from a drop's DataTransfer.
+#[derive(Debug, Clone)]
+pub struct DroppedFile {
+    /// Original filename.
+    pub name: String,
+    /// MIME type as reported by the browser (may be empty).
+    pub mime_type: String,
+    /// Raw file bytes.
+    pub data: Vec<u8>,
+}
+
+/// A classified drop payload ready for routing.
+#[derive(Debug, Clone)]
+pub enum DropPayload {
+    /// An image file; route into the image upload flow.
+    Image(DroppedFile),
+    /// A markdown or plain text file; insert its contents.
+    TextFile {
+        /// Original filename.
+        name: String,
+        /// Decoded file contents.
+        contents: String,
+    },
+}
+
+/// Classify a dropped file by MIME type and extension.
+///
+/// Returns None for files the editor has no use for (binaries, PDFs, etc.);
+/// callers should ignore those rather than inserting garbage.
+pub fn classify_dropped_file(file: DroppedFile) -> Option<DropPayload> {
+    if file.mime_type.starts_with("image/") {
+        return Some(DropPayload::Image(file));
+    }
+
+    // Browsers often report an empty MIME type for .md files, so fall back
+    // to the extension.
+    let extension = file
+        .name
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase());
+    let is_text = file.mime_type.starts_with("text/")
+        || file.mime_type == "application/markdown"
+        || matches!(extension.as_deref(), Some("md" | "markdown" | "txt"));
+
+    if is_text {
+        match String::from_utf8(file.data) {
+            Ok(contents) => Some(DropPayload::TextFile {
+                name: file.name,
+                contents,
+            }),
+            Err(_) => {
+                tracing::warn!(name = %file.name, "dropped text file was not valid UTF-8");
+                None
+            }
+        }
+    } else {
+        tracing::debug!(name = %file.name, mime = %file.mime_type, "ignoring dropped file");
+        None
+    }
+}
+
+/// Insert dropped text at the target offset, placing the cursor after it.
+pub fn apply_text_drop<D: EditorDocument>(doc: &mut D, text: &str, target: usize) {
+    let target = target.min(doc.len_chars());
+    doc.insert(target, text);
+    doc.set_selection(None);
+    doc.set_cursor_offset(target + text.chars().count());
+}
+
+/// Move the current selection (an intra-document block drag) to the target.
+///
+/// The dragged text is the document's selection at drop time: the selection
+/// is deleted, the target adjusted for the removed span, and the text
+/// reinserted with the cursor after it. Dropping inside the dragged span is
+/// a no-op move.
+pub fn apply_block_drop<D: EditorDocument>(doc: &mut D, text: &str, target: usize) {
+    let Some(sel) = doc.selection() else {
+        // No source selection (e.g. dragged from another editor instance);
+        // treat as a plain text drop.
+        apply_text_drop(doc, text, target);
+        return;
+    };
+
+    let (start, end) = (sel.start(), sel.end());
+    if target >= start && target <= end {
+        doc.set_selection(None);
+        doc.set_cursor_offset(end);
+        return;
+    }
+
+    doc.delete(start..end);
+    doc.set_selection(None);
+    let target = if target > end {
+        target - (end - start)
+    } else {
+        target
+    };
+    doc.insert(target, text);
+    doc.set_cursor_offset(target + text.chars().count());
+}
+
+/// Populate a dragstart DataTransfer from the current selection.
+///
+/// Writes both our internal MIME type (for intra-document move detection)
+/// and text/plain (so the selection can be dragged into other apps).
+/// Returns false when there is no selection to drag.
+pub fn handle_dragstart<D: EditorDocument>(
+    doc: &D,
+    data_transfer: &web_sys::DataTransfer,
+) -> bool {
+    let Some(sel) = doc.selection() else {
+        return false;
+    };
+    let Some(text) = doc.slice(sel.start()..sel.end()) else {
+        return false;
+    };
+
+    if let Err(e) = data_transfer.set_data(INTERNAL_DRAG_MIME, &text) {
+        tracing::warn!("dragstart: internal MIME write failed: {:?}", e);
+    }
+    if let Err(e) = data_transfer.set_data("text/plain", &text) {
+        tracing::warn!("dragstart: text/plain write failed: {:?}", e);
+    }
+    true
+}
+
+/// Handle the synchronously-available text content of a drop.
+///
+/// Intra-document drags (detected via [`INTERNAL_DRAG_MIME`]) are applied as
+/// block moves; external plain text is inserted at the target. Returns true
+/// if the drop was handled, false if only files (or nothing) were dropped and
+/// the caller should fall through to [`read_dropped_files`].
+pub fn handle_drop_text<D: EditorDocument>(
+    doc: &mut D,
+    data_transfer: &web_sys::DataTransfer,
+    target: usize,
+) -> bool {
+    if let Ok(text) = data_transfer.get_data(INTERNAL_DRAG_MIME) {
+        if !text.is_empty() {
+            apply_block_drop(doc, &text, target);
+            return true;
+        }
+    }
+    if let Ok(text) = data_transfer.get_data("text/plain") {
+        if !text.is_empty() {
+            apply_text_drop(doc, &text, target);
+            return true;
+        }
+    }
+    false
+}
+
+/// Read all files out of a drop's DataTransfer.
+///
+/// File contents are only reachable through async browser APIs, so this must
+/// run after the drop event itself (clone what you need from the event
+/// first). Classify the results with [`classify_dropped_file`].
+pub async fn read_dropped_files(data_transfer: &web_sys::DataTransfer) -> Vec<DroppedFile> {
+    let mut out = Vec::new();
+    let Some(files) = data_transfer.files() else {
+        return out;
+    };
+
+    for i in 0..files.length() {
+        let Some(file) = files.get(i) else {
+            continue;
+        };
+        let name = file.name();
+        let mime_type = file.type_();
+
+        match wasm_bindgen_futures::JsFuture::from(file.array_buffer()).await {
+            Ok(buffer) => {
+                let data = js_sys::Uint8Array::new(&buffer).to_vec();
+                out.push(DroppedFile {
+                    name,
+                    mime_type,
+                    data,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(name = %name, "failed to read dropped file: {:?}", e);
+            }
+        }
+    }
+    out
+}
+
+// Caret-from-point bindings: web-sys exposes neither caretRangeFromPoint
+// (WebKit/Blink) nor caretPositionFromPoint (Firefox), so bind both
+// structurally like the StaticRange binding above.
+#[wasm_bindgen]
+extern "C" {
+    type CaretDocument;
+
+    #[wasm_bindgen(method, structural, js_name = caretRangeFromPoint)]
+    fn caret_range_from_point(this: &CaretDocument, x: f32, y: f32) -> Option<web_sys::Range>;
+
+    #[wasm_bindgen(method, structural, js_name = caretPositionFromPoint)]
+    fn caret_position_from_point(this: &CaretDocument, x: f32, y: f32) -> Option<CaretPosition>;
+
+    type CaretPosition;
+
+    #[wasm_bindgen(method, getter, structural, js_name = offsetNode)]
+    fn offset_node(this: &CaretPosition) -> web_sys::Node;
+
+    #[wasm_bindgen(method, getter, structural)]
+    fn offset(this: &CaretPosition) -> u32;
+}
+
+/// Map a drag/drop event's pointer position to a document char offset.
+///
+/// Uses caretRangeFromPoint where available (WebKit/Blink) and falls back to
+/// caretPositionFromPoint (Firefox), then converts the DOM position through
+/// the paragraph offset maps. Returns None when the pointer is outside any
+/// mapped content.
+pub fn drop_target_offset(
+    event: &web_sys::DragEvent,
+    editor_id: &str,
+    paragraphs: &[ParagraphRender],
+) -> Option<usize> {
+    offset_at_point(
+        event.client_x() as f32,
+        event.client_y() as f32,
+        editor_id,
+        paragraphs,
+    )
+}
+
+/// Map viewport client coordinates to a document char offset.
+///
+/// Shared by drop targeting and touch caret placement; same caret-from-point
+/// strategy as [`drop_target_offset`].
+pub fn offset_at_point(
+    x: f32,
+    y: f32,
+    editor_id: &str,
+    paragraphs: &[ParagraphRender],
+) -> Option<usize> {
+    use wasm_bindgen::JsCast;
+
+    let window = web_sys::window()?;
+    let dom_document = window.document()?;
+    let editor_element = dom_document.get_element_by_id(editor_id)?;
+
+    let caret_doc: &CaretDocument = dom_document.unchecked_ref();
+    let (node, offset) = if let Some(range) = caret_doc.caret_range_from_point(x, y) {
+        (range.start_container().ok()?, range.start_offset().ok()? as usize)
+    } else if let Some(pos) = caret_doc.caret_position_from_point(x, y) {
+        (pos.offset_node(), pos.offset() as usize)
+    } else {
+        return None;
+    };
+
+    dom_position_to_text_offset(
+        &dom_document,
+        &editor_element,
+        &node,
+        offset,
+        paragraphs,
+        None,
+    )
+}
+
+// === Composition (IME) event handlers ===
+
+/// Handle composition start event.
+#[cfg(feature = "dioxus")]
+pub fn handle_compositionstart<D: EditorDocument>(
+    evt: dioxus_core::Event<dioxus_html::CompositionData>,
+    doc: &mut D,
+) {
+    composition_start(doc, evt.data().data());
+}
+
+/// Handle composition update event.
+#[cfg(feature = "dioxus")]
+pub fn handle_compositionupdate<D: EditorDocument>(
+    evt: dioxus_core::Event<dioxus_html::CompositionData>,
+    doc: &mut D,
+) {
+    composition_update(doc, evt.data().data());
+}
+
+/// Handle composition end event.
+#[cfg(feature = "dioxus")]
+pub fn handle_compositionend<D: EditorDocument>(
+    evt: dioxus_core::Event<dioxus_html::CompositionData>,
+    doc: &mut D,
+) {
+    composition_end(doc, evt.data().data());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weaver_editor_core::{EditorRope, PlainEditor, UndoableBuffer};
+
+    type TestEditor = PlainEditor<UndoableBuffer<EditorRope>>;
+
+    fn make_editor(content: &str) -> TestEditor {
+        let rope = EditorRope::from_str(content);
+        let buf = UndoableBuffer::new(rope, 100);
+        PlainEditor::new(buf)
+    }
+
+    #[test]
+    fn test_japanese_composition_sequence() {
+        // "watashi" -> わ -> わた -> わたし -> commit 私.
+        let mut editor = make_editor("before ");
+        editor.set_cursor_offset(7);
+
+        composition_start(&mut editor, "わ".to_string());
+        composition_update(&mut editor, "わた".to_string());
+        composition_update(&mut editor, "わたし".to_string());
+
+        // Model is untouched while composing; the range tracks the preview.
+        assert_eq!(editor.content_string(), "before ");
+        assert_eq!(composing_range(&editor), Some(Range::new(7, 10)));
+
+        composition_end(&mut editor, "私".to_string());
+        assert_eq!(editor.content_string(), "before 私");
+        assert_eq!(editor.cursor_offset(), 8);
+        assert!(editor.composition().is_none());
+        assert_eq!(composing_range(&editor), None);
+    }
+
+    #[test]
+    fn test_pinyin_composition_mid_document() {
+        // Pinyin "nihao" committed as 你好 in the middle of existing text.
+        let mut editor = make_editor("ab cd");
+        editor.set_cursor_offset(2);
+
+        composition_start(&mut editor, "n".to_string());
+        composition_update(&mut editor, "ni".to_string());
+        composition_update(&mut editor, "nihao".to_string());
+        assert_eq!(composing_range(&editor), Some(Range::new(2, 7)));
+
+        composition_end(&mut editor, "你好".to_string());
+        assert_eq!(editor.content_string(), "ab你好 cd");
+        assert_eq!(editor.cursor_offset(), 4);
+    }
+
+    #[test]
+    fn test_composition_replaces_selection() {
+        let mut editor = make_editor("hello world");
+        editor.set_selection(Some(Selection::new(6, 11)));
+        editor.set_cursor_offset(11);
+
+        composition_start(&mut editor, "せ".to_string());
+        assert_eq!(editor.content_string(), "hello ");
+        assert_eq!(composing_range(&editor), Some(Range::new(6, 7)));
+
+        composition_end(&mut editor, "世界".to_string());
+        assert_eq!(editor.content_string(), "hello 世界");
+        assert_eq!(editor.cursor_offset(), 8);
+    }
+
+    #[test]
+    fn test_cancelled_composition_leaves_model_untouched() {
+        let mut editor = make_editor("text");
+        editor.set_cursor_offset(4);
+
+        composition_start(&mut editor, "か".to_string());
+        composition_update(&mut editor, "かん".to_string());
+        // Escape: compositionend fires with empty data.
+        composition_end(&mut editor, String::new());
+
+        assert_eq!(editor.content_string(), "text");
+        assert_eq!(editor.cursor_offset(), 4);
+        assert!(editor.composition().is_none());
+    }
+
+    #[test]
+    fn test_classify_dropped_file() {
+        let image = DroppedFile {
+            name: "photo.png".into(),
+            mime_type: "image/png".into(),
+            data: vec![0x89, 0x50],
+        };
+        assert!(matches!(
+            classify_dropped_file(image),
+            Some(DropPayload::Image(_))
+        ));
+
+        // Browsers report empty MIME for .md; the extension decides.
+        let md = DroppedFile {
+            name: "notes.md".into(),
+            mime_type: String::new(),
+            data: b"# hi".to_vec(),
+        };
+        assert!(matches!(
+            classify_dropped_file(md),
+            Some(DropPayload::TextFile { ref contents, .. }) if contents == "# hi"
+        ));
+
+        let binary = DroppedFile {
+            name: "doc.pdf".into(),
+            mime_type: "application/pdf".into(),
+            data: vec![0x25, 0x50],
+        };
+        assert!(classify_dropped_file(binary).is_none());
+    }
+
+    #[test]
+    fn test_apply_text_drop() {
+        let mut editor = make_editor("ab cd");
+        apply_text_drop(&mut editor, "XY", 3);
+        assert_eq!(editor.content_string(), "ab XYcd");
+        assert_eq!(editor.cursor_offset(), 5);
+
+        // Target past the end clamps to the end.
+        apply_text_drop(&mut editor, "!", 100);
+        assert_eq!(editor.content_string(), "ab XYcd!");
+    }
+
+    #[test]
+    fn test_apply_block_drop_moves_selection() {
+        // Drag "bb " (3..6) and drop after "cc" (offset 8).
+        let mut editor = make_editor("aa bb cc");
+        editor.set_selection(Some(Selection::new(3, 6)));
+        apply_block_drop(&mut editor, "bb ", 8);
+        assert_eq!(editor.content_string(), "aa ccbb ");
+        assert_eq!(editor.cursor_offset(), 8);
+        assert!(editor.selection().is_none());
+
+        // Drag "cc " backward to the start.
+        let mut editor = make_editor("aa bb cc ");
+        editor.set_selection(Some(Selection::new(6, 9)));
+        apply_block_drop(&mut editor, "cc ", 0);
+        assert_eq!(editor.content_string(), "cc aa bb ");
+        assert_eq!(editor.cursor_offset(), 3);
+    }
+
+    #[test]
+    fn test_apply_block_drop_inside_selection_is_noop() {
+        let mut editor = make_editor("aa bb cc");
+        editor.set_selection(Some(Selection::new(3, 6)));
+        apply_block_drop(&mut editor, "bb ", 4);
+        assert_eq!(editor.content_string(), "aa bb cc");
+        assert!(editor.selection().is_none());
+        assert_eq!(editor.cursor_offset(), 6);
+    }
+
+    #[test]
+    fn test_composition_cleans_zero_width_chars() {
+        // A soft break leaves "\n\u{200C}"; composing right after it should
+        // splice out the zero-width char.
+        let mut editor = make_editor("line\n\u{200C}");
+        editor.set_cursor_offset(6);
+
+        composition_start(&mut editor, "あ".to_string());
+        composition_end(&mut editor, "あ".to_string());
+
+        assert_eq!(editor.content_string(), "line\nあ");
+        assert_eq!(editor.cursor_offset(), 6);
+    }
+}
@@ -53,6 +53,8 @@ pub mod cursor;
 pub mod dom_sync;
 pub mod events;
 pub mod platform;
+pub mod touch;
+pub mod virtual_scroll;
 pub mod visibility;
 
 // Browser cursor implementation
@@ -63,23 +65,33 @@ pub use cursor::{
 
 // DOM sync types
 pub use dom_sync::{
-    BrowserCursorSync, CursorSyncResult, dom_position_to_text_offset, sync_cursor_and_visibility,
-    sync_cursor_from_dom, sync_cursor_from_dom_impl, update_paragraph_dom,
+    BrowserCursorSync, CursorSyncResult, DROP_TARGET_CLASS, clear_drop_indicator,
+    dom_position_to_text_offset, set_drop_indicator, should_defer_dom_update,
+    sync_cursor_and_visibility, sync_cursor_from_dom, sync_cursor_from_dom_impl,
+    update_paragraph_dom,
 };
 
 // Event handling
 pub use events::{
     BeforeInputContext, BeforeInputResult, StaticRange, get_current_range, get_data_from_event,
-    get_input_type_from_event, get_math_click_offset, get_target_range_from_event,
-    handle_beforeinput, handle_math_click, is_composing, parse_browser_input_type,
-    read_clipboard_text, write_clipboard_with_custom_type,
+    get_embed_retry_uri, get_input_type_from_event, get_math_click_offset,
+    get_target_range_from_event, handle_beforeinput, handle_math_click, is_composing,
+    parse_browser_input_type, read_clipboard_text, write_clipboard_with_custom_type,
 };
 
 // Platform detection
 pub use platform::{Platform, platform};
 
 // Visibility updates
-pub use visibility::update_syntax_visibility;
+pub use visibility::{
+    sync_preview_scroll, update_locked_regions, update_syntax_visibility, visible_offset_range,
+};
+
+// Viewport-windowed rendering for long documents
+pub use virtual_scroll::{
+    PLACEHOLDER_ATTR, VirtualWindow, WindowObserver, ensure_offset_mounted,
+    paragraph_index_at_offset, scroll_offset_into_view, update_paragraph_dom_windowed,
+};
 
 // Color utilities
 pub use color::{rgba_u32_to_css, rgba_u32_to_css_alpha};
@@ -89,6 +101,21 @@ pub use clipboard::{BrowserClipboard, write_html_to_clipboard};
 #[cfg(feature = "dioxus")]
 pub use clipboard::{handle_copy, handle_cut, handle_paste};
 
-// Composition (IME) handlers
+// Drag and drop
+pub use events::{
+    DropPayload, DroppedFile, INTERNAL_DRAG_MIME, apply_block_drop, apply_text_drop,
+    classify_dropped_file, drop_target_offset, handle_dragstart, handle_drop_text, offset_at_point,
+    read_dropped_files,
+};
+
+// Touch and mobile selection
+pub use touch::{
+    SELECTION_HANDLE_CLASS, TouchGesture, TouchTracker, clear_selection_handles,
+    drag_handle_to, first_touch_position, scroll_caret_above_keyboard, tap_caret_offset,
+    update_selection_handles, word_range_at,
+};
+
+// Composition (IME) state machine and handlers
+pub use events::{composing_range, composition_end, composition_start, composition_update};
 #[cfg(feature = "dioxus")]
 pub use events::{handle_compositionend, handle_compositionstart, handle_compositionupdate};
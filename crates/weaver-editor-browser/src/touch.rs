@@ -0,0 +1,376 @@
+//! Touch and mobile selection support.
+//!
+//! Desktop interaction (mouse selection, keyboard navigation) mostly works
+//! through the browser's native contenteditable behaviour, but mobile needs
+//! an explicit layer: taps place the caret through caret-from-point mapping,
+//! long-presses select the word under the finger and show draggable
+//! selection handles, and the caret is kept visible above the virtual
+//! keyboard. Gate the whole layer on [`crate::platform::platform`]'s
+//! `mobile` flag; none of it should run for mouse input.
+//!
+//! The gesture recognizer ([`TouchTracker`]) and selection math are pure so
+//! they can be tested off-browser; only the handle rendering and scrolling
+//! touch the DOM.
+
+use std::ops::Range;
+
+use weaver_editor_core::{EditorDocument, ParagraphRender, Selection};
+
+use crate::cursor::{find_offset_map_for_char, get_cursor_rect, get_cursor_rect_relative};
+use crate::events::offset_at_point;
+
+/// Press duration (ms) after which a touch becomes a long-press.
+pub const LONG_PRESS_MS: f64 = 500.0;
+
+/// Movement tolerance (px) before a press stops being a tap/long-press.
+///
+/// Fingers are imprecise; small drift during a press should not cancel the
+/// gesture or the long-press timer.
+pub const TAP_SLOP_PX: f64 = 8.0;
+
+/// Class on both selection handle elements.
+pub const SELECTION_HANDLE_CLASS: &str = "selection-handle";
+
+/// Minimum gap (px) kept between the caret and the virtual keyboard edge.
+const KEYBOARD_CARET_MARGIN_PX: f64 = 24.0;
+
+/// Recognized single-finger gesture.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchGesture {
+    /// Quick press and release: place the caret at the point.
+    Tap { x: f64, y: f64 },
+    /// Held press: select the word at the point and show handles.
+    LongPress { x: f64, y: f64 },
+}
+
+/// State of an in-progress press.
+#[derive(Debug, Clone)]
+struct PressState {
+    x: f64,
+    y: f64,
+    started_at: f64,
+    /// Set once the finger drifts past the slop; the press can no longer
+    /// produce a gesture (the browser handles it as a scroll).
+    moved: bool,
+}
+
+/// Single-finger gesture recognizer.
+///
+/// Feed it touchstart/touchmove/touchend positions and timestamps (e.g.
+/// `performance.now()`); it reports taps and long-presses. Long-presses are
+/// polled via [`long_press_ready`](Self::long_press_ready) so they can fire
+/// while the finger is still down, matching native selection behaviour.
+#[derive(Debug, Clone, Default)]
+pub struct TouchTracker {
+    press: Option<PressState>,
+}
+
+impl TouchTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Begin tracking a press. A second finger cancels tracking entirely.
+    pub fn touch_start(&mut self, x: f64, y: f64, now_ms: f64) {
+        if self.press.is_some() {
+            // Multi-touch (pinch zoom etc.) is never a caret gesture.
+            self.press = None;
+            return;
+        }
+        self.press = Some(PressState {
+            x,
+            y,
+            started_at: now_ms,
+            moved: false,
+        });
+    }
+
+    /// Track finger movement; drifting past the slop invalidates the press.
+    pub fn touch_move(&mut self, x: f64, y: f64) {
+        if let Some(press) = self.press.as_mut() {
+            if (x - press.x).hypot(y - press.y) > TAP_SLOP_PX {
+                press.moved = true;
+            }
+        }
+    }
+
+    /// Position of the press if it has been held long enough to long-press.
+    ///
+    /// Call from a timer while the finger is down; returns the press origin
+    /// once, then clears tracking so touch_end does not also report a tap.
+    pub fn long_press_ready(&mut self, now_ms: f64) -> Option<(f64, f64)> {
+        let press = self.press.as_ref()?;
+        if press.moved || now_ms - press.started_at < LONG_PRESS_MS {
+            return None;
+        }
+        let (x, y) = (press.x, press.y);
+        self.press = None;
+        Some((x, y))
+    }
+
+    /// End the press, reporting the recognized gesture if any.
+    pub fn touch_end(&mut self, now_ms: f64) -> Option<TouchGesture> {
+        let press = self.press.take()?;
+        if press.moved {
+            return None;
+        }
+        if now_ms - press.started_at >= LONG_PRESS_MS {
+            Some(TouchGesture::LongPress {
+                x: press.x,
+                y: press.y,
+            })
+        } else {
+            Some(TouchGesture::Tap {
+                x: press.x,
+                y: press.y,
+            })
+        }
+    }
+
+    /// Abandon the current press (touchcancel, focus loss).
+    pub fn cancel(&mut self) {
+        self.press = None;
+    }
+}
+
+/// Client coordinates of the first touch point of a touch event.
+pub fn first_touch_position(event: &web_sys::TouchEvent) -> Option<(f64, f64)> {
+    // touchend events have an empty `touches` list; the released finger is
+    // in `changedTouches`.
+    let touch = event
+        .touches()
+        .item(0)
+        .or_else(|| event.changed_touches().item(0))?;
+    Some((touch.client_x() as f64, touch.client_y() as f64))
+}
+
+/// Map a tap position to a caret offset through the paragraph offset maps.
+///
+/// Returns None when the tap lands outside any mapped content (margins,
+/// embeds without offset maps).
+pub fn tap_caret_offset(
+    x: f64,
+    y: f64,
+    editor_id: &str,
+    paragraphs: &[ParagraphRender],
+) -> Option<usize> {
+    offset_at_point(x as f32, y as f32, editor_id, paragraphs)
+}
+
+/// The word range containing `offset`, for long-press selection.
+///
+/// Word characters match the editing boundaries in weaver-editor-core
+/// (alphanumeric or underscore). Returns None when neither side of the
+/// offset is a word character, in which case the caller should just place
+/// the caret.
+pub fn word_range_at<D: EditorDocument>(doc: &D, offset: usize) -> Option<Range<usize>> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+
+    // Pivot on the char at the offset, or the one before it when pressing
+    // just past the end of a word.
+    let pivot = if doc.char_at(offset).is_some_and(is_word) {
+        offset
+    } else if offset > 0 && doc.char_at(offset - 1).is_some_and(is_word) {
+        offset - 1
+    } else {
+        return None;
+    };
+
+    let mut start = pivot;
+    while start > 0 && doc.char_at(start - 1).is_some_and(is_word) {
+        start -= 1;
+    }
+    let mut end = pivot + 1;
+    while doc.char_at(end).is_some_and(is_word) {
+        end += 1;
+    }
+    Some(start..end)
+}
+
+/// Extend a handle drag to a new offset, keeping the other end anchored.
+///
+/// `dragging_start` says which handle the finger is on. Dragging a handle
+/// past the other end swaps the roles instead of collapsing the selection,
+/// matching native mobile behaviour.
+pub fn drag_handle_to(selection: &Selection, dragging_start: bool, offset: usize) -> Selection {
+    let anchor = if dragging_start {
+        selection.end()
+    } else {
+        selection.start()
+    };
+    Selection::new(anchor, offset)
+}
+
+/// Position the two selection handles under the selection endpoints.
+///
+/// Handles are divs with [`SELECTION_HANDLE_CLASS`] (plus `-start`/`-end`
+/// modifiers), absolutely positioned inside the editor element, which must
+/// be a positioning context (`position: relative`). Returns false if the
+/// endpoints could not be located in the DOM.
+pub fn update_selection_handles(
+    editor_id: &str,
+    paragraphs: &[ParagraphRender],
+    selection: &Selection,
+) -> bool {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return false;
+    };
+    let Some(editor) = document.get_element_by_id(editor_id) else {
+        return false;
+    };
+
+    let mut placed = true;
+    for (suffix, offset) in [("start", selection.start()), ("end", selection.end())] {
+        let handle_id = format!("{editor_id}-handle-{suffix}");
+        let handle = match document.get_element_by_id(&handle_id) {
+            Some(h) => h,
+            None => {
+                let Ok(div) = document.create_element("div") else {
+                    placed = false;
+                    continue;
+                };
+                div.set_id(&handle_id);
+                div.set_class_name(&format!(
+                    "{SELECTION_HANDLE_CLASS} {SELECTION_HANDLE_CLASS}-{suffix}"
+                ));
+                let _ = editor.append_child(div.as_ref());
+                div
+            }
+        };
+
+        let offset_map = find_offset_map_for_char(paragraphs, offset);
+        match get_cursor_rect_relative(offset, offset_map, editor_id) {
+            Some(rect) => {
+                // Anchor the handle to the bottom of the caret line; CSS
+                // draws the grab circle below it.
+                let _ = handle.set_attribute(
+                    "style",
+                    &format!("left: {}px; top: {}px", rect.x, rect.y + rect.height),
+                );
+            }
+            None => placed = false,
+        }
+    }
+    placed
+}
+
+/// Remove both selection handles (selection cleared or editor blurred).
+pub fn clear_selection_handles(editor_id: &str) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    for suffix in ["start", "end"] {
+        if let Some(handle) = document.get_element_by_id(&format!("{editor_id}-handle-{suffix}")) {
+            let _ = handle.remove();
+        }
+    }
+}
+
+/// Scroll so the caret sits above the virtual keyboard.
+///
+/// The visual viewport shrinks when the keyboard opens; if the caret rect
+/// falls below it (plus margin), scroll the window down by the difference.
+/// Call after focus, caret moves, and visualViewport resize events.
+pub fn scroll_caret_above_keyboard(offset: usize, paragraphs: &[ParagraphRender]) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let offset_map = find_offset_map_for_char(paragraphs, offset);
+    let Some(rect) = get_cursor_rect(offset, offset_map) else {
+        return;
+    };
+
+    // visualViewport reflects the keyboard; fall back to the layout
+    // viewport where the API is missing (keyboard handling is then the
+    // browser's problem).
+    let visible_height = window
+        .visual_viewport()
+        .map(|vv| vv.height())
+        .or_else(|| window.inner_height().ok().and_then(|h| h.as_f64()))
+        .unwrap_or_default();
+    if visible_height <= 0.0 {
+        return;
+    }
+
+    let caret_bottom = rect.y + rect.height;
+    let overflow = caret_bottom + KEYBOARD_CARET_MARGIN_PX - visible_height;
+    if overflow > 0.0 {
+        window.scroll_by_with_x_and_y(0.0, overflow);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weaver_editor_core::{EditorRope, PlainEditor, UndoableBuffer};
+
+    type TestEditor = PlainEditor<UndoableBuffer<EditorRope>>;
+
+    fn make_editor(content: &str) -> TestEditor {
+        let rope = EditorRope::from_str(content);
+        let buf = UndoableBuffer::new(rope, 100);
+        PlainEditor::new(buf)
+    }
+
+    #[test]
+    fn test_tap_and_long_press_recognition() {
+        let mut tracker = TouchTracker::new();
+
+        tracker.touch_start(10.0, 20.0, 0.0);
+        assert_eq!(
+            tracker.touch_end(100.0),
+            Some(TouchGesture::Tap { x: 10.0, y: 20.0 })
+        );
+
+        tracker.touch_start(10.0, 20.0, 0.0);
+        assert_eq!(tracker.long_press_ready(100.0), None);
+        assert_eq!(tracker.long_press_ready(600.0), Some((10.0, 20.0)));
+        // The long-press consumed the press; release reports nothing.
+        assert_eq!(tracker.touch_end(650.0), None);
+    }
+
+    #[test]
+    fn test_movement_past_slop_cancels_gesture() {
+        let mut tracker = TouchTracker::new();
+        tracker.touch_start(10.0, 20.0, 0.0);
+        // Within slop: still a tap.
+        tracker.touch_move(13.0, 22.0);
+        // Past slop: the browser owns this as a scroll.
+        tracker.touch_move(40.0, 20.0);
+        assert_eq!(tracker.long_press_ready(600.0), None);
+        assert_eq!(tracker.touch_end(700.0), None);
+    }
+
+    #[test]
+    fn test_second_finger_cancels_tracking() {
+        let mut tracker = TouchTracker::new();
+        tracker.touch_start(10.0, 20.0, 0.0);
+        tracker.touch_start(100.0, 20.0, 10.0);
+        assert_eq!(tracker.touch_end(50.0), None);
+    }
+
+    #[test]
+    fn test_word_range_at() {
+        let editor = make_editor("hello there_world !");
+        assert_eq!(word_range_at(&editor, 2), Some(0..5));
+        // Pressing just past the end of a word selects it.
+        assert_eq!(word_range_at(&editor, 5), Some(0..5));
+        // Underscores join words, matching the editing boundaries.
+        assert_eq!(word_range_at(&editor, 8), Some(6..17));
+        // Punctuation surrounded by spaces selects nothing.
+        assert_eq!(word_range_at(&editor, 18), None);
+    }
+
+    #[test]
+    fn test_drag_handle_swaps_past_anchor() {
+        let selection = Selection::new(5, 10);
+
+        // Dragging the end handle forward extends.
+        let extended = drag_handle_to(&selection, false, 15);
+        assert_eq!((extended.start(), extended.end()), (5, 15));
+
+        // Dragging the start handle past the end swaps roles.
+        let swapped = drag_handle_to(&selection, true, 14);
+        assert_eq!((swapped.start(), swapped.end()), (10, 14));
+    }
+}
@@ -0,0 +1,475 @@
+//! Viewport-windowed paragraph mounting for long documents.
+//!
+//! [`crate::update_paragraph_dom`] reconciles every paragraph into the DOM,
+//! which degrades on 1000+ paragraph documents: initial render, layout and
+//! style recalculation all scale with document length. This module adds a
+//! windowed mode where only paragraphs near the viewport are mounted with
+//! real content; everything else is a fixed-height placeholder div that
+//! preserves scroll geometry.
+//!
+//! # How it works
+//!
+//! 1. [`VirtualWindow`] tracks which paragraph indices are mounted, plus
+//!    measured heights for placeholder sizing.
+//! 2. [`update_paragraph_dom_windowed`] reconciles the DOM like
+//!    `update_paragraph_dom`, but unmounted paragraphs become placeholders
+//!    (`data-virtual-placeholder`) sized from their last measured height.
+//! 3. A [`WindowObserver`] (IntersectionObserver) watches placeholders and
+//!    reports the ones scrolling toward the viewport so the caller can grow
+//!    the window and re-render.
+//! 4. [`ensure_offset_mounted`] recenters the window when the cursor jumps
+//!    outside it (goto-line, search, undo far away), and
+//!    [`scroll_offset_into_view`] brings the remounted paragraph on screen.
+//!
+//! Scroll anchoring: when a paragraph above the viewport swaps between
+//! placeholder and real content its height can change; the resulting delta
+//! is applied to the window scroll position so the visible content does not
+//! jump.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+use weaver_editor_core::ParagraphRender;
+
+use crate::cursor::restore_cursor_position;
+
+/// Attribute marking an unmounted paragraph's placeholder div.
+pub const PLACEHOLDER_ATTR: &str = "data-virtual-placeholder";
+
+/// Default placeholder height (px) for paragraphs never measured.
+///
+/// One text line at typical notebook font size; placeholders correct
+/// themselves to the real height once the paragraph has been mounted.
+const DEFAULT_ESTIMATED_HEIGHT: f64 = 28.0;
+
+/// Mounted-range state for windowed rendering.
+///
+/// Pure bookkeeping: which contiguous run of paragraph indices is mounted,
+/// how far beyond the viewport to overscan, and the last measured pixel
+/// height of each paragraph (by id) for placeholder sizing.
+#[derive(Debug, Clone)]
+pub struct VirtualWindow {
+    first: usize,
+    last: usize,
+    overscan: usize,
+    heights: HashMap<String, f64>,
+}
+
+impl VirtualWindow {
+    /// Create a window mounting the first `overscan * 2` paragraphs.
+    ///
+    /// `overscan` is the number of paragraphs kept mounted beyond the
+    /// viewport in each direction.
+    pub fn new(overscan: usize) -> Self {
+        Self {
+            first: 0,
+            last: overscan * 2,
+            overscan,
+            heights: HashMap::new(),
+        }
+    }
+
+    /// The currently mounted index range.
+    pub fn mounted_range(&self) -> Range<usize> {
+        self.first..self.last
+    }
+
+    /// Whether the paragraph at `idx` should be mounted with real content.
+    pub fn is_mounted(&self, idx: usize) -> bool {
+        (self.first..self.last).contains(&idx)
+    }
+
+    /// Grow the window toward `idx` (from an observer notification).
+    ///
+    /// Extends the nearest edge to cover `idx` plus overscan, keeping
+    /// already-mounted paragraphs mounted so scrolling only ever reveals
+    /// content. Returns true if the window changed.
+    pub fn extend_toward(&mut self, idx: usize, len: usize) -> bool {
+        let mut changed = false;
+        if idx < self.first {
+            self.first = idx.saturating_sub(self.overscan);
+            changed = true;
+        }
+        if idx >= self.last {
+            self.last = (idx + self.overscan + 1).min(len);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Recenter the window on `idx` (cursor jump).
+    ///
+    /// Unlike [`extend_toward`](Self::extend_toward) this drops paragraphs
+    /// far from the new position, so a jump to the end of a long document
+    /// does not mount everything in between. Returns true if the window
+    /// changed.
+    pub fn recenter_on(&mut self, idx: usize, len: usize) -> bool {
+        if self.is_mounted(idx) {
+            return false;
+        }
+        self.first = idx.saturating_sub(self.overscan);
+        self.last = (idx + self.overscan + 1).min(len);
+        true
+    }
+
+    /// Record a measured height for placeholder sizing.
+    pub fn record_height(&mut self, para_id: &str, height: f64) {
+        if height > 0.0 {
+            self.heights.insert(para_id.to_owned(), height);
+        }
+    }
+
+    /// Height to give a placeholder for this paragraph.
+    pub fn placeholder_height(&self, para_id: &str) -> f64 {
+        self.heights
+            .get(para_id)
+            .copied()
+            .unwrap_or(DEFAULT_ESTIMATED_HEIGHT)
+    }
+}
+
+/// Find the index of the paragraph containing a char offset.
+///
+/// Paragraph ranges are half-open, so an offset at the very end of the
+/// document resolves to the last paragraph.
+pub fn paragraph_index_at_offset(paragraphs: &[ParagraphRender], offset: usize) -> Option<usize> {
+    let last = paragraphs.len().checked_sub(1)?;
+    Some(
+        paragraphs
+            .iter()
+            .position(|p| p.contains_char(offset))
+            .unwrap_or(last),
+    )
+}
+
+/// Recenter the window so the paragraph containing `offset` is mounted.
+///
+/// Call before [`update_paragraph_dom_windowed`] whenever the cursor may
+/// have jumped (goto, search, undo). Returns true if the window moved and a
+/// re-render is needed.
+pub fn ensure_offset_mounted(
+    window: &mut VirtualWindow,
+    paragraphs: &[ParagraphRender],
+    offset: usize,
+) -> bool {
+    match paragraph_index_at_offset(paragraphs, offset) {
+        Some(idx) => window.recenter_on(idx, paragraphs.len()),
+        None => false,
+    }
+}
+
+/// Scroll the paragraph containing `offset` into view.
+///
+/// Use after a cursor jump once the paragraph has been mounted.
+pub fn scroll_offset_into_view(paragraphs: &[ParagraphRender], offset: usize) {
+    let Some(idx) = paragraph_index_at_offset(paragraphs, offset) else {
+        return;
+    };
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+    if let Some(elem) = document.get_element_by_id(&paragraphs[idx].id) {
+        elem.scroll_into_view();
+    }
+}
+
+/// Windowed variant of [`crate::update_paragraph_dom`].
+///
+/// Reconciles paragraph divs by stable ID like the full version, but only
+/// paragraphs inside the window get their HTML mounted; the rest become
+/// fixed-height placeholder divs. Heights are measured as paragraphs
+/// unmount, and height deltas above the viewport are compensated by
+/// adjusting the scroll position so visible content stays anchored.
+///
+/// Returns true if the paragraph containing the cursor was updated.
+pub fn update_paragraph_dom_windowed(
+    editor_id: &str,
+    new_paragraphs: &[ParagraphRender],
+    cursor_offset: usize,
+    window: &mut VirtualWindow,
+    force: bool,
+) -> bool {
+    let Some(dom_window) = web_sys::window() else {
+        return false;
+    };
+    let Some(document) = dom_window.document() else {
+        return false;
+    };
+    let Some(editor) = document.get_element_by_id(editor_id) else {
+        return false;
+    };
+
+    let mut cursor_para_updated = false;
+    // Net height change of elements above the viewport, for scroll anchoring.
+    let mut scroll_delta = 0.0;
+
+    // Pool of existing DOM elements by ID.
+    let mut old_elements: HashMap<String, web_sys::Element> = HashMap::new();
+    let mut child_opt = editor.first_element_child();
+    while let Some(child) = child_opt {
+        let next = child.next_element_sibling();
+        if let Some(id) = child.get_attribute("id") {
+            old_elements.insert(id, child);
+        }
+        child_opt = next;
+    }
+
+    let mut insert_before: Option<web_sys::Node> = editor.first_element_child().map(|e| e.into());
+
+    for (idx, new_para) in new_paragraphs.iter().enumerate() {
+        let para_id = &new_para.id;
+        let new_hash = format!("{:x}", new_para.source_hash);
+        let mounted = window.is_mounted(idx);
+        let is_cursor_para =
+            new_para.char_range.start <= cursor_offset && cursor_offset <= new_para.char_range.end;
+
+        let existing = old_elements.remove(para_id.as_str());
+        let existed = existing.is_some();
+        let elem = if let Some(existing) = existing {
+            let existing_as_node: &web_sys::Node = existing.as_ref();
+            let at_correct_position = insert_before
+                .as_ref()
+                .map(|c| c == existing_as_node)
+                .unwrap_or(false);
+            if !at_correct_position {
+                let _ = editor.insert_before(existing_as_node, insert_before.as_ref());
+            } else {
+                insert_before = existing.next_element_sibling().map(|e| e.into());
+            }
+            existing
+        } else {
+            let Ok(div) = document.create_element("div") else {
+                continue;
+            };
+            div.set_id(para_id);
+            let div_node: &web_sys::Node = div.as_ref();
+            let _ = editor.insert_before(div_node, insert_before.as_ref());
+            div
+        };
+
+        let was_placeholder = elem.has_attribute(PLACEHOLDER_ATTR);
+        let old_hash = elem.get_attribute("data-hash").unwrap_or_default();
+        let rect = elem.get_bounding_client_rect();
+        let old_height = rect.height();
+        // Brand-new elements had no prior height, so there is nothing to
+        // anchor against.
+        let above_viewport = existed && rect.bottom() <= 0.0;
+
+        if mounted {
+            if was_placeholder || force || old_hash != new_hash {
+                elem.set_inner_html(&new_para.html);
+                let _ = elem.remove_attribute(PLACEHOLDER_ATTR);
+                let _ = elem.remove_attribute("style");
+                let _ = elem.set_attribute("data-hash", &new_hash);
+
+                if above_viewport {
+                    scroll_delta += elem.get_bounding_client_rect().height() - old_height;
+                }
+
+                if is_cursor_para {
+                    if let Err(e) =
+                        restore_cursor_position(cursor_offset, &new_para.offset_map, None)
+                    {
+                        tracing::warn!("windowed cursor restore failed: {:?}", e);
+                    }
+                    cursor_para_updated = true;
+                }
+            }
+        } else {
+            if !was_placeholder {
+                // Remember the real height before collapsing to a placeholder.
+                window.record_height(para_id, old_height);
+                elem.set_inner_html("");
+                let _ = elem.set_attribute(PLACEHOLDER_ATTR, "true");
+                if above_viewport {
+                    scroll_delta += window.placeholder_height(para_id) - old_height;
+                }
+            }
+            let _ = elem.set_attribute(
+                "style",
+                &format!("min-height: {}px", window.placeholder_height(para_id)),
+            );
+            let _ = elem.set_attribute("data-hash", &new_hash);
+        }
+    }
+
+    // Remove stale elements.
+    for (_, elem) in old_elements {
+        let _ = elem.remove();
+        cursor_para_updated = true;
+    }
+
+    if scroll_delta.abs() > 0.5 {
+        dom_window.scroll_by_with_x_and_y(0.0, scroll_delta);
+    }
+
+    cursor_para_updated
+}
+
+/// IntersectionObserver watching placeholder divs.
+///
+/// Reports placeholder paragraph IDs entering the observation margin so the
+/// caller can [`VirtualWindow::extend_toward`] them and re-render. Re-call
+/// [`observe_placeholders`](Self::observe_placeholders) after each windowed
+/// DOM update to track the current placeholder set; dropping the observer
+/// disconnects it.
+pub struct WindowObserver {
+    observer: web_sys::IntersectionObserver,
+    // Kept alive for the observer's lifetime; dropping the closure would
+    // invalidate the JS callback.
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl WindowObserver {
+    /// Create an observer firing `margin` ahead of the viewport.
+    ///
+    /// `margin` is a CSS rootMargin string, e.g. `"600px 0px"` to start
+    /// mounting paragraphs 600px before they scroll on screen.
+    pub fn new(
+        margin: &str,
+        mut on_placeholder_visible: impl FnMut(Vec<String>) + 'static,
+    ) -> Option<Self> {
+        let callback: Closure<dyn FnMut(js_sys::Array)> = Closure::new(move |entries| {
+            let mut ids = Vec::new();
+            for entry in entries.iter() {
+                let entry: web_sys::IntersectionObserverEntry = entry.unchecked_into();
+                if entry.is_intersecting() {
+                    ids.push(entry.target().id());
+                }
+            }
+            if !ids.is_empty() {
+                on_placeholder_visible(ids);
+            }
+        });
+
+        let init = web_sys::IntersectionObserverInit::new();
+        init.set_root_margin(margin);
+        let observer = web_sys::IntersectionObserver::new_with_options(
+            callback.as_ref().unchecked_ref(),
+            &init,
+        )
+        .ok()?;
+
+        Some(Self {
+            observer,
+            _callback: callback,
+        })
+    }
+
+    /// Observe the current placeholder set inside the editor.
+    pub fn observe_placeholders(&self, editor_id: &str) {
+        self.observer.disconnect();
+        let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+            return;
+        };
+        let selector = format!("#{} [{}]", editor_id, PLACEHOLDER_ATTR);
+        let Ok(placeholders) = document.query_selector_all(&selector) else {
+            return;
+        };
+        for i in 0..placeholders.length() {
+            if let Some(node) = placeholders.item(i) {
+                if let Some(elem) = node.dyn_ref::<web_sys::Element>() {
+                    self.observer.observe(elem);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for WindowObserver {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use smol_str::SmolStr;
+
+    fn make_para(idx: usize, char_range: Range<usize>) -> ParagraphRender {
+        ParagraphRender {
+            id: SmolStr::new(format!("p-{}", idx)),
+            byte_range: char_range.clone(),
+            char_range,
+            html: String::new(),
+            offset_map: vec![],
+            syntax_spans: vec![],
+            source_hash: 0,
+            code_language: None,
+        }
+    }
+
+    #[test]
+    fn test_extend_toward_grows_nearest_edge() {
+        let mut window = VirtualWindow::new(5);
+        assert_eq!(window.mounted_range(), 0..10);
+
+        // Scrolling down: extend past the bottom edge.
+        assert!(window.extend_toward(10, 100));
+        assert_eq!(window.mounted_range(), 0..16);
+
+        // Already mounted: no change.
+        assert!(!window.extend_toward(12, 100));
+
+        // Clamped at document end.
+        assert!(window.extend_toward(98, 100));
+        assert_eq!(window.mounted_range().end, 100);
+    }
+
+    #[test]
+    fn test_recenter_drops_distant_paragraphs() {
+        let mut window = VirtualWindow::new(5);
+        assert!(window.recenter_on(500, 1000));
+        assert_eq!(window.mounted_range(), 495..506);
+        assert!(!window.is_mounted(0));
+
+        // Recentering inside the window is a no-op.
+        assert!(!window.recenter_on(500, 1000));
+
+        // Near the start the range clamps at zero.
+        assert!(window.recenter_on(2, 1000));
+        assert_eq!(window.mounted_range(), 0..8);
+    }
+
+    #[test]
+    fn test_paragraph_index_at_offset() {
+        let paragraphs = vec![
+            make_para(0, 0..10),
+            make_para(1, 10..25),
+            make_para(2, 25..30),
+        ];
+        assert_eq!(paragraph_index_at_offset(&paragraphs, 0), Some(0));
+        assert_eq!(paragraph_index_at_offset(&paragraphs, 10), Some(1));
+        // End of document falls into the last paragraph.
+        assert_eq!(paragraph_index_at_offset(&paragraphs, 30), Some(2));
+        assert_eq!(paragraph_index_at_offset(&[], 0), None);
+    }
+
+    #[test]
+    fn test_ensure_offset_mounted_recenters() {
+        let paragraphs: Vec<_> = (0..100).map(|i| make_para(i, i * 10..(i + 1) * 10)).collect();
+        let mut window = VirtualWindow::new(5);
+
+        // Cursor jump to paragraph 80.
+        assert!(ensure_offset_mounted(&mut window, &paragraphs, 805));
+        assert!(window.is_mounted(80));
+        // Second call is a no-op.
+        assert!(!ensure_offset_mounted(&mut window, &paragraphs, 805));
+    }
+
+    #[test]
+    fn test_placeholder_heights() {
+        let mut window = VirtualWindow::new(5);
+        assert_eq!(window.placeholder_height("p-0"), DEFAULT_ESTIMATED_HEIGHT);
+
+        window.record_height("p-0", 120.0);
+        assert_eq!(window.placeholder_height("p-0"), 120.0);
+
+        // Zero heights (display: none) are not recorded.
+        window.record_height("p-1", 0.0);
+        assert_eq!(window.placeholder_height("p-1"), DEFAULT_ESTIMATED_HEIGHT);
+    }
+}
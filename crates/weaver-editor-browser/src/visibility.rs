@@ -19,7 +19,7 @@
 //! }
 //! ```
 
-use weaver_editor_core::{ParagraphRender, Selection, SyntaxSpanInfo};
+use weaver_editor_core::{LockedRegion, ParagraphRender, Selection, SyntaxSpanInfo};
 
 /// Update syntax span visibility in the DOM based on cursor position.
 ///
@@ -93,3 +93,131 @@ pub fn update_syntax_visibility(
     _paragraphs: &[ParagraphRender],
 ) {
 }
+
+/// Toggle the "locked-region" class on paragraphs inside locked regions.
+///
+/// Paragraph elements (looked up by their `p-{n}` id) whose char range
+/// intersects a template's locked region get the class so CSS can render
+/// them distinctly; all others have it removed. Call after paragraph
+/// renders, alongside syntax visibility updates.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn update_locked_regions(paragraphs: &[ParagraphRender], locked: &[LockedRegion]) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(document) = window.document() else {
+        return;
+    };
+
+    for para in paragraphs {
+        let Some(element) = document.get_element_by_id(&para.id) else {
+            continue;
+        };
+        let is_locked = locked
+            .iter()
+            .any(|r| r.range.start < para.char_range.end && r.range.end > para.char_range.start);
+
+        let class_list = element.class_list();
+        if is_locked {
+            let _ = class_list.add_1("locked-region");
+        } else {
+            let _ = class_list.remove_1("locked-region");
+        }
+    }
+}
+
+/// No-op on non-WASM targets.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn update_locked_regions(_paragraphs: &[ParagraphRender], _locked: &[LockedRegion]) {}
+
+/// Compute the character range currently visible in the browser viewport.
+///
+/// Checks each rendered paragraph's DOM element (looked up by its `p-{n}`
+/// id) against the window height and returns the union of char ranges of
+/// paragraphs that intersect the viewport. Paragraphs are vertically
+/// ordered, so the union is contiguous.
+///
+/// Returns `None` when no paragraph is visible or the DOM is unavailable.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn visible_offset_range(paragraphs: &[ParagraphRender]) -> Option<(usize, usize)> {
+    let window = web_sys::window()?;
+    let document = window.document()?;
+    let window_height = window.inner_height().ok()?.as_f64()?;
+
+    let mut range: Option<(usize, usize)> = None;
+    for para in paragraphs {
+        let Some(element) = document.get_element_by_id(&para.id) else {
+            continue;
+        };
+        let rect = element.get_bounding_client_rect();
+        // Intersects the viewport: bottom below the top edge, top above
+        // the bottom edge.
+        if rect.bottom() < 0.0 || rect.top() > window_height {
+            continue;
+        }
+        range = Some(match range {
+            Some((start, end)) => (
+                start.min(para.char_range.start),
+                end.max(para.char_range.end),
+            ),
+            None => (para.char_range.start, para.char_range.end),
+        });
+    }
+    range
+}
+
+/// Always `None` on non-WASM targets.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn visible_offset_range(_paragraphs: &[ParagraphRender]) -> Option<(usize, usize)> {
+    None
+}
+
+/// Scroll the preview pane to track the editor's topmost visible paragraph.
+///
+/// Editor paragraphs and preview blocks share IDs (`p-{n}` in the editor,
+/// `preview-p-{n}` in the pane), so the first paragraph intersecting the
+/// editor's viewport picks the preview block to align with the pane's top
+/// edge. Call from the editor's scroll handler when split view is active.
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn sync_preview_scroll(editor_id: &str, preview_pane_id: &str, paragraphs: &[ParagraphRender]) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Some(document) = window.document() else {
+        return;
+    };
+    let Some(editor) = document.get_element_by_id(editor_id) else {
+        return;
+    };
+    let Some(pane) = document.get_element_by_id(preview_pane_id) else {
+        return;
+    };
+
+    // The editor scrolls in its own container, so visibility is measured
+    // against the container's top edge, not the window's.
+    let editor_top = editor.get_bounding_client_rect().top();
+
+    let Some(top_para) = paragraphs.iter().find(|para| {
+        document
+            .get_element_by_id(&para.id)
+            .is_some_and(|el| el.get_bounding_client_rect().bottom() > editor_top)
+    }) else {
+        return;
+    };
+
+    let Some(target) = document.get_element_by_id(&format!("preview-{}", top_para.id)) else {
+        return;
+    };
+
+    let delta = target.get_bounding_client_rect().top() - pane.get_bounding_client_rect().top();
+    pane.set_scroll_top(pane.scroll_top() + delta as i32);
+}
+
+/// No-op on non-WASM targets.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn sync_preview_scroll(
+    _editor_id: &str,
+    _preview_pane_id: &str,
+    _paragraphs: &[ParagraphRender],
+) {
+}
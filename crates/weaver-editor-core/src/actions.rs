@@ -217,6 +217,9 @@ pub enum EditorAction {
     /// Insert a paragraph break (Enter).
     InsertParagraph { range: Range },
 
+    /// Insert one indent unit (Tab inside a fenced code block).
+    InsertIndent { range: Range },
+
     // === Deletion ===
     /// Delete content backward (Backspace).
     DeleteBackward { range: Range },
@@ -265,6 +268,9 @@ pub enum EditorAction {
     /// Insert/wrap with link.
     InsertLink,
 
+    /// Set or clear the language tag of the fenced code block at the cursor.
+    ToggleCodeBlockLanguage { language: Option<String> },
+
     // === Clipboard ===
     /// Cut selection to clipboard.
     Cut,
@@ -297,6 +303,7 @@ impl EditorAction {
             Self::Insert { text, .. } => Self::Insert { text, range },
             Self::InsertLineBreak { .. } => Self::InsertLineBreak { range },
             Self::InsertParagraph { .. } => Self::InsertParagraph { range },
+            Self::InsertIndent { .. } => Self::InsertIndent { range },
             Self::DeleteBackward { .. } => Self::DeleteBackward { range },
             Self::DeleteForward { .. } => Self::DeleteForward { range },
             Self::DeleteWordBackward { .. } => Self::DeleteWordBackward { range },
@@ -0,0 +1,216 @@
+//! Fenced code block detection and editing affordances.
+//!
+//! Detects when the cursor sits inside a fenced code block so editing
+//! commands can behave code-aware: Enter preserves the current line's
+//! indentation, Tab inserts a per-language indent unit, and the fence's
+//! language tag can be toggled for the UI's language picker.
+
+use smol_str::SmolStr;
+
+use crate::document::EditorDocument;
+use crate::text_helpers::{find_line_end, find_line_start};
+
+/// Context for a cursor positioned inside a fenced code block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlockContext {
+    /// Char offset of the start of the opening fence line.
+    pub fence_start: usize,
+    /// Char range of the language tag on the opening fence line (empty range
+    /// right after the fence markers when no tag is present).
+    pub language_range: std::ops::Range<usize>,
+    /// Language tag on the opening fence, if any.
+    pub language: Option<SmolStr>,
+    /// Leading whitespace of the line containing the cursor.
+    pub line_indent: String,
+}
+
+impl CodeBlockContext {
+    /// The indent unit for this block's language.
+    pub fn indent_unit(&self) -> &'static str {
+        indent_for_language(self.language.as_deref())
+    }
+}
+
+/// Per-language indent unit for Tab inside code blocks.
+///
+/// Defaults to four spaces; languages with strong two-space or tab
+/// conventions get theirs.
+pub fn indent_for_language(language: Option<&str>) -> &'static str {
+    match language {
+        Some(
+            "js" | "javascript" | "ts" | "typescript" | "jsx" | "tsx" | "json" | "yaml" | "yml"
+            | "html" | "css" | "scss" | "nix" | "ruby" | "rb" | "elixir" | "ex",
+        ) => "  ",
+        Some("go" | "make" | "makefile") => "\t",
+        _ => "    ",
+    }
+}
+
+/// Fence language tag of a paragraph source, if it is a fenced code block.
+///
+/// Used to surface language metadata in `ParagraphRender` for the UI's
+/// language picker. `None` means the paragraph is not a fenced code block; an
+/// empty tag means a bare fence with no language.
+pub fn code_block_language(source: &str) -> Option<SmolStr> {
+    let first_line = source.trim_start().lines().next()?;
+    if !(first_line.starts_with("```") || first_line.starts_with("~~~")) {
+        return None;
+    }
+    let fence_char = first_line.chars().next()?;
+    let tag = first_line.trim_start_matches(fence_char).trim();
+    Some(SmolStr::new(tag))
+}
+
+/// Detect whether `offset` is inside a fenced code block.
+///
+/// Scans backward line by line counting fence lines (``` or ~~~); an odd
+/// number of fences above the cursor means we're inside an open block. The
+/// opening fence line itself is not considered inside.
+pub fn detect_code_block_context<D: EditorDocument>(
+    doc: &D,
+    offset: usize,
+) -> Option<CodeBlockContext> {
+    let cursor_line_start = find_line_start(doc, offset);
+
+    // Count fence lines strictly above the cursor's line, remembering the
+    // nearest one. An odd count means an unclosed fence, and the nearest
+    // fence line is its opener.
+    let mut line_start = cursor_line_start;
+    let mut nearest_fence: Option<(usize, String)> = None;
+    let mut fence_count = 0usize;
+
+    while line_start > 0 {
+        let prev_line_end = line_start - 1;
+        line_start = find_line_start(doc, prev_line_end);
+        let line = doc.slice(line_start..prev_line_end + 1)?;
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence_count += 1;
+            if nearest_fence.is_none() {
+                nearest_fence = Some((line_start, line.to_string()));
+            }
+        }
+    }
+
+    if fence_count % 2 == 0 {
+        return None;
+    }
+    let (fence_line_start, fence_line) = nearest_fence?;
+
+    // Parse the language tag from the opening fence line.
+    let trimmed = fence_line.trim_start();
+    let fence_char = trimmed.chars().next().unwrap_or('`');
+    let indent_len = fence_line.len() - trimmed.len();
+    let marker_len = trimmed.chars().take_while(|&c| c == fence_char).count();
+    let tag_rel_start = indent_len + marker_len;
+    let tag = trimmed[marker_len..].trim();
+    let language = if tag.is_empty() {
+        None
+    } else {
+        Some(SmolStr::new(tag))
+    };
+    let tag_chars_start =
+        fence_line_start + fence_line[..tag_rel_start].chars().count();
+    let tag_char_len = fence_line[tag_rel_start..].trim_end().chars().count();
+
+    // Leading whitespace of the cursor's line (up to the cursor), for Enter
+    // indentation.
+    let cursor_line_end = find_line_end(doc, offset);
+    let line_indent = doc
+        .slice(cursor_line_start..cursor_line_end.min(offset))
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(CodeBlockContext {
+        fence_start: fence_line_start,
+        language_range: tag_chars_start..tag_chars_start + tag_char_len,
+        language,
+        line_indent,
+    })
+}
+
+/// Replace (or clear) the language tag on the opening fence.
+///
+/// Returns the signed char delta applied to the document so callers can fix
+/// up the cursor.
+pub fn set_fence_language<D: EditorDocument>(
+    doc: &mut D,
+    ctx: &CodeBlockContext,
+    language: Option<&str>,
+) -> isize {
+    let new_tag = language.unwrap_or("");
+    let old_len = ctx.language_range.len() as isize;
+    doc.replace(ctx.language_range.clone(), new_tag);
+    new_tag.chars().count() as isize - old_len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EditorRope, PlainEditor, UndoableBuffer};
+
+    type TestEditor = PlainEditor<UndoableBuffer<EditorRope>>;
+
+    fn make_editor(content: &str) -> TestEditor {
+        let rope = EditorRope::from_str(content);
+        let buf = UndoableBuffer::new(rope, 100);
+        PlainEditor::new(buf)
+    }
+
+    #[test]
+    fn test_detects_inside_code_block() {
+        let editor = make_editor("text\n\n```rust\nlet x = 1;\n```\n\nmore");
+        // Offset inside "let x = 1;".
+        let ctx = detect_code_block_context(&editor, 16).expect("inside block");
+        assert_eq!(ctx.language.as_deref(), Some("rust"));
+        assert_eq!(ctx.fence_start, 6);
+    }
+
+    #[test]
+    fn test_outside_code_block() {
+        let editor = make_editor("text\n\n```rust\nlet x = 1;\n```\n\nmore");
+        assert!(detect_code_block_context(&editor, 2).is_none());
+        // After the closing fence.
+        assert!(detect_code_block_context(&editor, 31).is_none());
+    }
+
+    #[test]
+    fn test_no_language_tag() {
+        let editor = make_editor("```\ncode\n```");
+        let ctx = detect_code_block_context(&editor, 5).expect("inside block");
+        assert_eq!(ctx.language, None);
+        assert!(ctx.language_range.is_empty());
+    }
+
+    #[test]
+    fn test_line_indent_captured() {
+        let editor = make_editor("```rust\n    indented\n```");
+        let ctx = detect_code_block_context(&editor, 14).expect("inside block");
+        assert_eq!(ctx.line_indent, "    ");
+    }
+
+    #[test]
+    fn test_indent_for_language() {
+        assert_eq!(indent_for_language(Some("js")), "  ");
+        assert_eq!(indent_for_language(Some("go")), "\t");
+        assert_eq!(indent_for_language(Some("rust")), "    ");
+        assert_eq!(indent_for_language(None), "    ");
+    }
+
+    #[test]
+    fn test_set_fence_language() {
+        let mut editor = make_editor("```rust\ncode\n```");
+        let ctx = detect_code_block_context(&editor, 9).expect("inside block");
+        set_fence_language(&mut editor, &ctx, Some("python"));
+        assert_eq!(editor.content_string(), "```python\ncode\n```");
+
+        let ctx = detect_code_block_context(&editor, 11).expect("inside block");
+        set_fence_language(&mut editor, &ctx, None);
+        assert_eq!(editor.content_string(), "```\ncode\n```");
+    }
+}
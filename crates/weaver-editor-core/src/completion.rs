@@ -0,0 +1,161 @@
+//! Completion trigger detection.
+//!
+//! Scans backwards from the cursor to decide whether an autocomplete
+//! popover should be open and what the user has typed so far. Two
+//! triggers exist: `[[` for wikilinks and `@` (at a word boundary) for
+//! mentions. Detection is pure text analysis — the UI layer decides how
+//! to source candidates and what to insert.
+
+/// What kind of completion the cursor position calls for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionTrigger {
+    /// Inside an unclosed `[[`.
+    Wikilink,
+    /// After an `@` at the start of a word.
+    Mention,
+}
+
+/// An active completion site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionContext {
+    pub trigger: CompletionTrigger,
+    /// Char offset of the first trigger character (`[` or `@`); replacing
+    /// `start..cursor` with the completed text removes the trigger too.
+    pub start: usize,
+    /// Text typed between the trigger and the cursor.
+    pub query: String,
+}
+
+/// Mention queries longer than this stop triggering lookups; no handle
+/// is that long and it avoids re-resolving while prose is typed after
+/// a literal `@`.
+const MAX_MENTION_QUERY: usize = 64;
+
+/// Wikilink queries are bounded too, mostly to keep the backward scan
+/// cheap on pathological lines.
+const MAX_WIKILINK_QUERY: usize = 128;
+
+/// Detects a completion site ending at `cursor` (a char offset).
+///
+/// Returns `None` when the cursor is not inside a trigger: the scan stops
+/// at the line start, at a closing `]]`, or once the query exceeds the
+/// trigger's length bound.
+pub fn completion_context(text: &str, cursor: usize) -> Option<CompletionContext> {
+    // Chars of the current line up to the cursor, most recent first.
+    let before: Vec<char> = text.chars().take(cursor).collect();
+    let line_start = before
+        .iter()
+        .rposition(|&c| c == '\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let line = &before[line_start..];
+
+    let mut closed = false;
+    for (i, &c) in line.iter().enumerate().rev() {
+        let query_len = line.len() - i - 1;
+        match c {
+            ']' if line.get(i + 1) == Some(&']') || line.get(i.wrapping_sub(1)) == Some(&']') => {
+                // A completed `]]` between here and the cursor means any
+                // earlier `[[` is already closed.
+                closed = true;
+            }
+            '[' if line.get(i.wrapping_sub(1)) == Some(&'[') && i > 0 => {
+                if closed || query_len > MAX_WIKILINK_QUERY {
+                    return None;
+                }
+                let query: String = line[i + 1..].iter().collect();
+                // A lone `]` in the query means the user is past the link.
+                if query.contains(']') {
+                    return None;
+                }
+                return Some(CompletionContext {
+                    trigger: CompletionTrigger::Wikilink,
+                    start: line_start + i - 1,
+                    query,
+                });
+            }
+            '@' => {
+                // Only a word-initial `@` triggers mentions, so emails and
+                // mid-word ats stay inert.
+                let word_initial = i == 0
+                    || line
+                        .get(i - 1)
+                        .is_some_and(|p| p.is_whitespace() || *p == '(');
+                if !word_initial {
+                    return None;
+                }
+                if closed || query_len > MAX_MENTION_QUERY {
+                    return None;
+                }
+                let query: String = line[i + 1..].iter().collect();
+                // Handles have no spaces; a space means ordinary prose.
+                if query.contains(char::is_whitespace) {
+                    return None;
+                }
+                return Some(CompletionContext {
+                    trigger: CompletionTrigger::Mention,
+                    start: line_start + i,
+                    query,
+                });
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wikilink_trigger() {
+        let text = "see [[My En";
+        let ctx = completion_context(text, text.chars().count()).unwrap();
+        assert_eq!(ctx.trigger, CompletionTrigger::Wikilink);
+        assert_eq!(ctx.start, 4);
+        assert_eq!(ctx.query, "My En");
+    }
+
+    #[test]
+    fn test_closed_wikilink_does_not_trigger() {
+        let text = "see [[done]] and";
+        assert_eq!(completion_context(text, text.chars().count()), None);
+    }
+
+    #[test]
+    fn test_mention_trigger() {
+        let text = "ping @alice.bsky";
+        let ctx = completion_context(text, text.chars().count()).unwrap();
+        assert_eq!(ctx.trigger, CompletionTrigger::Mention);
+        assert_eq!(ctx.start, 5);
+        assert_eq!(ctx.query, "alice.bsky");
+    }
+
+    #[test]
+    fn test_mid_word_at_is_inert() {
+        let text = "mail me a b@c";
+        assert_eq!(completion_context(text, text.chars().count()), None);
+    }
+
+    #[test]
+    fn test_cursor_mid_text() {
+        let text = "[[abc]] tail";
+        // Cursor right after "ab" — inside the link, before its close.
+        let ctx = completion_context(text, 4);
+        assert_eq!(
+            ctx,
+            Some(CompletionContext {
+                trigger: CompletionTrigger::Wikilink,
+                start: 0,
+                query: "ab".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_newline_breaks_trigger() {
+        let text = "[[open\nnext line";
+        assert_eq!(completion_context(text, text.chars().count()), None);
+    }
+}
@@ -9,6 +9,7 @@ use std::ops::Range;
 use smol_str::SmolStr;
 use web_time::Instant;
 
+use crate::template::LockedRegion;
 use crate::text::TextBuffer;
 use crate::types::{BLOCK_SYNTAX_ZONE, CompositionState, CursorState, EditInfo, Selection};
 use crate::undo::UndoManager;
@@ -87,6 +88,80 @@ pub trait EditorDocument {
     /// Set the pending snap direction hint.
     fn set_pending_snap(&mut self, snap: Option<crate::SnapDirection>);
 
+    // === Provided: Locked regions (templates) ===
+
+    /// Locked (non-editable) regions of the document, in document order.
+    ///
+    /// User actions touching these ranges are rejected by
+    /// [`execute_action`](crate::execute_action). The defaults have no
+    /// storage, so editors without template support need no changes;
+    /// template-aware editors override both accessors.
+    ///
+    /// Regions track edits made through [`insert`](Self::insert),
+    /// [`delete`](Self::delete), and [`replace`](Self::replace), but not
+    /// undo/redo - callers re-apply template regions after history jumps.
+    fn locked_regions(&self) -> Vec<LockedRegion> {
+        Vec::new()
+    }
+
+    /// Replace the set of locked regions.
+    fn set_locked_regions(&mut self, _regions: Vec<LockedRegion>) {}
+
+    /// Whether inserting at `offset` would edit a locked region.
+    fn is_insert_locked(&self, offset: usize) -> bool {
+        self.locked_regions()
+            .iter()
+            .any(|r| r.blocks_insert_at(offset))
+    }
+
+    /// Whether editing `range` would touch a locked region.
+    ///
+    /// An empty range is treated as a caret insertion point.
+    fn is_range_locked(&self, range: &Range<usize>) -> bool {
+        if range.start == range.end {
+            return self.is_insert_locked(range.start);
+        }
+        self.locked_regions()
+            .iter()
+            .any(|r| r.blocks_edit_of(range))
+    }
+
+    /// Shift locked regions to track an edit replacing
+    /// `pos..pos + deleted` with `inserted` chars.
+    ///
+    /// Allowed edits never overlap a region, but programmatic edits
+    /// (CRDT merges, paste pipelines) can - overlapped portions collapse
+    /// onto the edit point so regions stay well-formed. Regions that
+    /// collapse to nothing are dropped.
+    fn shift_locked_regions(&mut self, pos: usize, inserted: usize, deleted: usize) {
+        let mut regions = self.locked_regions();
+        if regions.is_empty() {
+            return;
+        }
+
+        let del_end = pos + deleted;
+        for r in &mut regions {
+            // Start: shifts when the edit lands before it. A pure
+            // insertion exactly at the start also pushes the region
+            // right, so boundary insertions stay editable.
+            if r.range.start >= del_end && (r.range.start > pos || deleted == 0) {
+                r.range.start = r.range.start - deleted + inserted;
+            } else if r.range.start > pos {
+                r.range.start = pos + inserted;
+            }
+            // End: shifts when the edit starts before it; insertion at
+            // the end boundary leaves the region alone.
+            if r.range.end >= del_end && r.range.end > pos {
+                r.range.end = r.range.end - deleted + inserted;
+            } else if r.range.end > pos {
+                r.range.end = pos + inserted;
+            }
+        }
+        regions.retain(|r| r.range.start < r.range.end);
+
+        self.set_locked_regions(regions);
+    }
+
     // === Provided: Convenience accessors ===
 
     /// Get the cursor offset.
@@ -157,6 +232,7 @@ pub trait EditorDocument {
         self.buffer_mut().insert(offset, text);
 
         let inserted_len = text.chars().count();
+        self.shift_locked_regions(offset, inserted_len, 0);
         self.set_cursor_offset(offset + inserted_len);
 
         let edit = EditInfo {
@@ -184,6 +260,7 @@ pub trait EditorDocument {
         let deleted_len = range.end - range.start;
 
         self.buffer_mut().delete(range.clone());
+        self.shift_locked_regions(range.start, 0, deleted_len);
         self.set_cursor_offset(range.start);
 
         let edit = EditInfo {
@@ -215,6 +292,7 @@ pub trait EditorDocument {
         self.buffer_mut().insert(range.start, text);
 
         let inserted_len = text.chars().count();
+        self.shift_locked_regions(range.start, inserted_len, deleted_len);
         self.set_cursor_offset(range.start + inserted_len);
 
         let edit = EditInfo {
@@ -319,6 +397,7 @@ pub struct PlainEditor<T: TextBuffer + UndoManager> {
     composition: Option<CompositionState>,
     composition_ended_at: Option<web_time::Instant>,
     pending_snap: Option<crate::SnapDirection>,
+    locked_regions: Vec<LockedRegion>,
 }
 
 impl<T: TextBuffer + UndoManager + Default> Default for PlainEditor<T> {
@@ -338,6 +417,7 @@ impl<T: TextBuffer + UndoManager> PlainEditor<T> {
             composition: None,
             composition_ended_at: None,
             pending_snap: None,
+            locked_regions: Vec::new(),
         }
     }
 
@@ -410,6 +490,14 @@ impl<T: TextBuffer + UndoManager> EditorDocument for PlainEditor<T> {
     fn set_pending_snap(&mut self, snap: Option<crate::SnapDirection>) {
         self.pending_snap = snap;
     }
+
+    fn locked_regions(&self) -> Vec<LockedRegion> {
+        self.locked_regions.clone()
+    }
+
+    fn set_locked_regions(&mut self, regions: Vec<LockedRegion>) {
+        self.locked_regions = regions;
+    }
 }
 
 #[cfg(test)]
@@ -498,6 +586,24 @@ mod tests {
         assert!(edit.in_block_syntax_zone);
     }
 
+    #[test]
+    fn test_locked_regions_follow_edits() {
+        let mut editor = make_editor("# title\nbody");
+        editor.set_locked_regions(vec![LockedRegion::new(0..8)]);
+
+        // Insertion at the region's start boundary pushes it right.
+        editor.insert(0, "x");
+        assert_eq!(editor.locked_regions(), vec![LockedRegion::new(1..9)]);
+
+        // Edits after the region leave it alone.
+        editor.insert(10, "y");
+        assert_eq!(editor.locked_regions(), vec![LockedRegion::new(1..9)]);
+
+        // A programmatic delete spanning the region drops it.
+        editor.delete(0..10);
+        assert!(editor.locked_regions().is_empty());
+    }
+
     #[test]
     fn test_composition_state() {
         let mut editor = make_editor("hello");
@@ -6,6 +6,7 @@
 
 use crate::SnapDirection;
 use crate::actions::{EditorAction, FormatAction, Range};
+use crate::code_block::{detect_code_block_context, set_fence_language};
 use crate::document::EditorDocument;
 use crate::platform::{ClipboardPlatform, clipboard_copy, clipboard_cut, clipboard_paste};
 use crate::text_helpers::{
@@ -47,6 +48,11 @@ pub fn snap_direction_for_action(action: &EditorAction) -> Option<SnapDirection>
 /// Note: Clipboard operations (Cut, Copy, CopyAsHtml, Paste) return false here.
 /// Use [`execute_action_with_clipboard`] if you have a clipboard platform available.
 pub fn execute_action<D: EditorDocument>(doc: &mut D, action: &EditorAction) -> bool {
+    // Reject edits that would touch a locked template region.
+    if action_touches_locked_region(doc, action) {
+        return false;
+    }
+
     // Set pending snap direction before executing action.
     if let Some(snap) = snap_direction_for_action(action) {
         doc.set_pending_snap(Some(snap));
@@ -56,6 +62,7 @@ pub fn execute_action<D: EditorDocument>(doc: &mut D, action: &EditorAction) ->
         EditorAction::Insert { text, range } => execute_insert(doc, text, *range),
         EditorAction::InsertLineBreak { range } => execute_insert_line_break(doc, *range),
         EditorAction::InsertParagraph { range } => execute_insert_paragraph(doc, *range),
+        EditorAction::InsertIndent { range } => execute_insert_indent(doc, *range),
         EditorAction::DeleteBackward { range } => execute_delete_backward(doc, *range),
         EditorAction::DeleteForward { range } => execute_delete_forward(doc, *range),
         EditorAction::DeleteWordBackward { range } => execute_delete_word_backward(doc, *range),
@@ -75,6 +82,9 @@ pub fn execute_action<D: EditorDocument>(doc: &mut D, action: &EditorAction) ->
         EditorAction::ToggleCode => execute_toggle_format(doc, "`"),
         EditorAction::ToggleStrikethrough => execute_toggle_format(doc, "~~"),
         EditorAction::InsertLink => execute_insert_link(doc),
+        EditorAction::ToggleCodeBlockLanguage { language } => {
+            execute_toggle_code_block_language(doc, language.as_deref())
+        }
         EditorAction::Cut | EditorAction::Copy | EditorAction::CopyAsHtml => {
             // Clipboard operations need platform - use execute_action_with_clipboard.
             false
@@ -93,11 +103,21 @@ pub fn execute_action<D: EditorDocument>(doc: &mut D, action: &EditorAction) ->
 ///
 /// Like [`execute_action`], but also handles clipboard operations (Cut, Copy, Paste, CopyAsHtml)
 /// using the provided platform implementation.
-pub fn execute_action_with_clipboard<D, P>(doc: &mut D, action: &EditorAction, clipboard: &P) -> bool
+pub fn execute_action_with_clipboard<D, P>(
+    doc: &mut D,
+    action: &EditorAction,
+    clipboard: &P,
+) -> bool
 where
     D: EditorDocument,
     P: ClipboardPlatform,
 {
+    // Mirror execute_action's locked-region guard so Cut/Paste can't
+    // bypass it (they don't delegate).
+    if action_touches_locked_region(doc, action) {
+        return false;
+    }
+
     match action {
         EditorAction::Copy => clipboard_copy(doc, clipboard),
         EditorAction::Cut => clipboard_cut(doc, clipboard),
@@ -108,6 +128,107 @@ where
     }
 }
 
+/// Whether an action's edit range touches a locked template region.
+fn action_touches_locked_region<D: EditorDocument>(doc: &D, action: &EditorAction) -> bool {
+    match action_edit_range(doc, action) {
+        Some(range) => doc.is_range_locked(&range),
+        None => false,
+    }
+}
+
+/// Compute the char range an action would edit, if any.
+///
+/// Read-only actions (navigation, selection, copy) and history jumps
+/// return None. Ranges are conservative approximations of the real edit:
+/// word and line deletions use the same boundary helpers as execution,
+/// formatting uses the selection or word the markers would wrap.
+fn action_edit_range<D: EditorDocument>(
+    doc: &D,
+    action: &EditorAction,
+) -> Option<std::ops::Range<usize>> {
+    let selection_or_word = || {
+        if let Some(sel) = doc.selection() {
+            (sel.start(), sel.end())
+        } else {
+            find_word_boundaries(doc, doc.cursor_offset())
+        }
+    };
+
+    match action {
+        EditorAction::Insert { range, .. }
+        | EditorAction::InsertLineBreak { range }
+        | EditorAction::InsertParagraph { range }
+        | EditorAction::InsertIndent { range }
+        | EditorAction::Paste { range } => {
+            let r = range.normalize();
+            Some(r.start..r.end)
+        }
+        EditorAction::DeleteBackward { range } => {
+            let r = range.normalize();
+            if r.is_caret() {
+                Some(r.start.saturating_sub(1)..r.start)
+            } else {
+                Some(r.start..r.end)
+            }
+        }
+        EditorAction::DeleteForward { range } => {
+            let r = range.normalize();
+            if r.is_caret() {
+                Some(r.start..(r.start + 1).min(doc.len_chars()))
+            } else {
+                Some(r.start..r.end)
+            }
+        }
+        EditorAction::DeleteWordBackward { range } => {
+            let r = range.normalize();
+            if r.is_caret() {
+                Some(find_word_boundary_backward(doc, r.start)..r.start)
+            } else {
+                Some(r.start..r.end)
+            }
+        }
+        EditorAction::DeleteWordForward { range } => {
+            let r = range.normalize();
+            if r.is_caret() {
+                Some(r.start..find_word_boundary_forward(doc, r.start))
+            } else {
+                Some(r.start..r.end)
+            }
+        }
+        EditorAction::DeleteToLineStart { range }
+        | EditorAction::DeleteSoftLineBackward { range } => {
+            let r = range.normalize();
+            Some(find_line_start(doc, r.start)..r.start)
+        }
+        EditorAction::DeleteToLineEnd { range } | EditorAction::DeleteSoftLineForward { range } => {
+            let r = range.normalize();
+            let cursor = if r.is_caret() { r.start } else { r.end };
+            Some(cursor..find_line_end(doc, cursor))
+        }
+        EditorAction::ToggleBold
+        | EditorAction::ToggleItalic
+        | EditorAction::ToggleCode
+        | EditorAction::ToggleStrikethrough
+        | EditorAction::InsertLink => {
+            let (start, end) = selection_or_word();
+            Some(start..end)
+        }
+        EditorAction::ToggleCodeBlockLanguage { .. } => {
+            detect_code_block_context(doc, doc.cursor_offset()).map(|ctx| ctx.language_range)
+        }
+        EditorAction::Cut => doc.selection().map(|sel| sel.to_range()),
+        // Undo/redo restore history that predates the regions, and the
+        // rest never modify content.
+        EditorAction::Undo
+        | EditorAction::Redo
+        | EditorAction::Copy
+        | EditorAction::CopyAsHtml
+        | EditorAction::SelectAll
+        | EditorAction::MoveCursor { .. }
+        | EditorAction::ExtendSelection { .. } => None,
+    }
+}
+
 fn execute_insert<D: EditorDocument>(doc: &mut D, text: &str, range: Range) -> bool {
     let range = range.normalize();
 
@@ -163,6 +284,17 @@ fn execute_insert_line_break<D: EditorDocument>(doc: &mut D, range: Range) -> bo
     };
 
     if !is_double_enter {
+        // Inside a fenced code block, insert a plain newline preserving the
+        // current line's indentation - no zero-width chars or list handling.
+        if let Some(ctx) = detect_code_block_context(doc, offset) {
+            let insertion = format!("\n{}", ctx.line_indent);
+            let len = insertion.chars().count();
+            doc.insert(offset, &insertion);
+            doc.set_cursor_offset(offset + len);
+            doc.set_selection(None);
+            return true;
+        }
+
         // Check for list context.
         if let Some(ctx) = detect_list_context(doc, offset) {
             if is_list_item_empty(doc, offset, &ctx) {
@@ -363,6 +495,50 @@ fn execute_delete_to_line_end<D: EditorDocument>(doc: &mut D, range: Range) -> b
     true
 }
 
+fn execute_insert_indent<D: EditorDocument>(doc: &mut D, range: Range) -> bool {
+    let range = range.normalize();
+    let offset = range.start;
+
+    let Some(ctx) = detect_code_block_context(doc, offset) else {
+        return false;
+    };
+
+    if !range.is_caret() {
+        doc.delete(offset..range.end);
+    }
+
+    let indent = ctx.indent_unit();
+    doc.insert(offset, indent);
+    doc.set_cursor_offset(offset + indent.chars().count());
+    doc.set_selection(None);
+    true
+}
+
+fn execute_toggle_code_block_language<D: EditorDocument>(
+    doc: &mut D,
+    language: Option<&str>,
+) -> bool {
+    let cursor = doc.cursor_offset();
+    let Some(ctx) = detect_code_block_context(doc, cursor) else {
+        return false;
+    };
+
+    // Toggling to the current tag clears it.
+    let language = if ctx.language.as_deref() == language {
+        None
+    } else {
+        language
+    };
+    let delta = set_fence_language(doc, &ctx, language);
+
+    // Keep the cursor on the same content if it sits after the fence line.
+    if cursor >= ctx.language_range.end {
+        doc.set_cursor_offset(cursor.saturating_add_signed(delta));
+    }
+    doc.set_selection(None);
+    true
+}
+
 fn execute_undo<D: EditorDocument>(doc: &mut D) -> bool {
     if doc.undo() {
         let max = doc.len_chars();
@@ -609,7 +785,7 @@ fn list_continuation(ctx: &ListContext) -> String {
 
 // === Keydown handling ===
 
-use crate::actions::{KeyCombo, KeybindingConfig, KeydownResult};
+use crate::actions::{Key, KeyCombo, KeybindingConfig, KeydownResult, Modifiers};
 
 /// Handle a keydown event using the keybinding configuration.
 ///
@@ -630,6 +806,10 @@ pub fn handle_keydown<D: EditorDocument>(
         return KeydownResult::Handled;
     }
 
+    if handle_code_block_tab(doc, &combo, range) {
+        return KeydownResult::Handled;
+    }
+
     check_passthrough(&combo)
 }
 
@@ -654,9 +834,23 @@ where
         return KeydownResult::Handled;
     }
 
+    if handle_code_block_tab(doc, &combo, range) {
+        return KeydownResult::Handled;
+    }
+
     check_passthrough(&combo)
 }
 
+/// Tab inside a fenced code block inserts a per-language indent unit.
+///
+/// Outside code blocks Tab stays unhandled so platform focus behavior is
+/// preserved.
+fn handle_code_block_tab<D: EditorDocument>(doc: &mut D, combo: &KeyCombo, range: Range) -> bool {
+    combo.key == Key::Tab
+        && combo.modifiers == Modifiers::NONE
+        && execute_action(doc, &EditorAction::InsertIndent { range })
+}
+
 /// Check if a key combo should pass through to the platform.
 fn check_passthrough(combo: &KeyCombo) -> KeydownResult {
     // Navigation keys should pass through.
@@ -746,6 +940,108 @@ mod tests {
         assert_eq!(sel.end(), 11);
     }
 
+    #[test]
+    fn test_line_break_preserves_code_block_indent() {
+        let mut editor = make_editor("```rust\n    let x = 1;\n```");
+        // Cursor at end of the indented line.
+        let action = EditorAction::InsertLineBreak {
+            range: Range::caret(22),
+        };
+        assert!(execute_action(&mut editor, &action));
+        assert_eq!(
+            editor.content_string(),
+            "```rust\n    let x = 1;\n    \n```"
+        );
+        assert_eq!(editor.cursor_offset(), 27);
+    }
+
+    #[test]
+    fn test_tab_indents_in_code_block_only() {
+        let config = KeybindingConfig::default();
+        let combo = KeyCombo::new(Key::Tab);
+
+        let mut editor = make_editor("```go\ncode\n```");
+        let result = handle_keydown(&mut editor, &config, combo.clone(), Range::caret(6));
+        assert_eq!(result, KeydownResult::Handled);
+        assert_eq!(editor.content_string(), "```go\n\tcode\n```");
+
+        // Outside a code block Tab stays unhandled.
+        let mut editor = make_editor("hello");
+        let result = handle_keydown(&mut editor, &config, combo, Range::caret(3));
+        assert_eq!(result, KeydownResult::NotHandled);
+        assert_eq!(editor.content_string(), "hello");
+    }
+
+    #[test]
+    fn test_toggle_code_block_language() {
+        let mut editor = make_editor("```rust\ncode\n```");
+        editor.set_cursor_offset(10);
+        let action = EditorAction::ToggleCodeBlockLanguage {
+            language: Some("python".to_string()),
+        };
+        assert!(execute_action(&mut editor, &action));
+        assert_eq!(editor.content_string(), "```python\ncode\n```");
+        assert_eq!(editor.cursor_offset(), 12);
+
+        // Toggling to the current tag clears it.
+        assert!(execute_action(&mut editor, &action));
+        assert_eq!(editor.content_string(), "```\ncode\n```");
+    }
+
+    #[test]
+    fn test_locked_region_rejects_edits() {
+        use crate::template::LockedRegion;
+
+        let mut editor = make_editor("# Agenda\nnotes");
+        editor.set_locked_regions(vec![LockedRegion::new(0..9)]);
+
+        // Typing inside the locked heading is rejected.
+        let action = EditorAction::Insert {
+            text: "x".to_string(),
+            range: Range::caret(3),
+        };
+        assert!(!execute_action(&mut editor, &action));
+        assert_eq!(editor.content_string(), "# Agenda\nnotes");
+
+        // Backspacing into the region from just after is rejected.
+        let action = EditorAction::DeleteBackward {
+            range: Range::caret(9),
+        };
+        assert!(!execute_action(&mut editor, &action));
+
+        // A selection spanning the region is rejected.
+        let action = EditorAction::DeleteBackward {
+            range: Range::new(5, 12),
+        };
+        assert!(!execute_action(&mut editor, &action));
+        assert_eq!(editor.content_string(), "# Agenda\nnotes");
+
+        // Edits past the region still work.
+        let action = EditorAction::Insert {
+            text: "!".to_string(),
+            range: Range::caret(14),
+        };
+        assert!(execute_action(&mut editor, &action));
+        assert_eq!(editor.content_string(), "# Agenda\nnotes!");
+    }
+
+    #[test]
+    fn test_locked_region_allows_boundary_insertions() {
+        use crate::template::LockedRegion;
+
+        let mut editor = make_editor("# Agenda\nnotes");
+        editor.set_locked_regions(vec![LockedRegion::new(0..9)]);
+
+        // Inserting at the region's end boundary only shifts what follows.
+        let action = EditorAction::Insert {
+            text: "> ".to_string(),
+            range: Range::caret(9),
+        };
+        assert!(execute_action(&mut editor, &action));
+        assert_eq!(editor.content_string(), "# Agenda\n> notes");
+        assert_eq!(editor.locked_regions(), vec![LockedRegion::new(0..9)]);
+    }
+
     #[test]
     fn test_toggle_bold() {
         let mut editor = make_editor("hello");
@@ -34,6 +34,10 @@ pub struct ParagraphRender {
 
     /// Hash of source text for quick change detection
     pub source_hash: u64,
+
+    /// Fence language tag when this paragraph is a fenced code block, so the
+    /// UI can show a language picker (empty tag means a bare fence)
+    pub code_language: Option<SmolStr>,
 }
 
 impl ParagraphRender {
@@ -106,6 +110,7 @@ mod tests {
             offset_map: vec![],
             syntax_spans: vec![],
             source_hash: 0,
+            code_language: None,
         };
 
         assert!(!para.contains_byte(9));
@@ -0,0 +1,658 @@
+//! Paste processing pipeline.
+//!
+//! Converts raw paste payloads into a single [`EditorAction`] so the browser
+//! layer and the JS bindings share identical paste semantics. The pipeline is
+//! configurable via [`PasteConfig`]:
+//!
+//! - HTML clipboard flavors can be converted to markdown instead of pasting
+//!   the plain-text fallback.
+//! - Bare URLs in plain text can be auto-linked with markdown autolink syntax.
+//! - Pasting a URL over a selection wraps the selection as a markdown link.
+
+use crate::actions::{EditorAction, Range};
+
+/// The content of a paste operation, as read from the platform clipboard.
+///
+/// `text` is the plain-text flavor (always present, possibly empty), `html` is
+/// the `text/html` flavor when the source application provided one.
+/// `selected_text` carries the currently selected document text, which the
+/// pipeline needs to implement link-wrapping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PasteContent {
+    /// Plain-text clipboard flavor.
+    pub text: String,
+    /// HTML clipboard flavor, if the source provided one.
+    pub html: Option<String>,
+    /// Target range in the document (selection or caret).
+    pub range: Range,
+    /// Text currently selected in the document, if any.
+    pub selected_text: Option<String>,
+}
+
+impl PasteContent {
+    /// Plain-text paste at a caret or over a selection.
+    pub fn plain(text: impl Into<String>, range: Range) -> Self {
+        Self {
+            text: text.into(),
+            html: None,
+            range,
+            selected_text: None,
+        }
+    }
+
+    /// Attach the HTML clipboard flavor.
+    pub fn with_html(mut self, html: impl Into<String>) -> Self {
+        self.html = Some(html.into());
+        self
+    }
+
+    /// Attach the currently selected document text.
+    pub fn with_selected_text(mut self, selected: impl Into<String>) -> Self {
+        self.selected_text = Some(selected.into());
+        self
+    }
+}
+
+/// Configuration for the paste pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasteConfig {
+    /// Convert the HTML clipboard flavor to markdown when present.
+    pub convert_html: bool,
+    /// Wrap bare URLs in pasted plain text with markdown autolink syntax.
+    pub auto_link_urls: bool,
+    /// When a lone URL is pasted over a selection, turn the selection into a
+    /// markdown link targeting that URL instead of replacing it.
+    pub link_selection_on_url_paste: bool,
+}
+
+impl Default for PasteConfig {
+    fn default() -> Self {
+        Self {
+            convert_html: true,
+            auto_link_urls: true,
+            link_selection_on_url_paste: true,
+        }
+    }
+}
+
+/// Process a paste payload into an editor action using the default config.
+///
+/// See [`process_paste_with_config`] for the pipeline stages.
+pub fn process_paste(input: PasteContent) -> EditorAction {
+    process_paste_with_config(input, &PasteConfig::default())
+}
+
+/// Process a paste payload into an editor action.
+///
+/// Pipeline order:
+/// 1. If the paste is a lone URL over a non-empty selection, produce a
+///    markdown link wrapping the selection.
+/// 2. Otherwise, if an HTML flavor is present, convert it to markdown.
+/// 3. Otherwise, auto-link bare URLs in the plain text.
+///
+/// The result is always an [`EditorAction::Insert`] targeting `input.range`,
+/// so callers dispatch it through `execute_action` like any other edit.
+pub fn process_paste_with_config(input: PasteContent, config: &PasteConfig) -> EditorAction {
+    let range = input.range.normalize();
+
+    // Stage 1: URL pasted over a selection becomes a link.
+    if config.link_selection_on_url_paste
+        && !range.is_caret()
+        && let Some(selected) = input.selected_text.as_deref()
+        && !selected.trim().is_empty()
+        && let Some(url) = lone_url(&input.text)
+    {
+        return EditorAction::Insert {
+            text: format!("[{}]({})", selected, url),
+            range,
+        };
+    }
+
+    // Stage 2: HTML flavor converted to markdown.
+    if config.convert_html
+        && let Some(html) = input.html.as_deref()
+        && !html.trim().is_empty()
+    {
+        let markdown = html_to_markdown(html);
+        if !markdown.trim().is_empty() {
+            return EditorAction::Insert {
+                text: markdown,
+                range,
+            };
+        }
+    }
+
+    // Stage 3: plain text, optionally auto-linking bare URLs.
+    let text = if config.auto_link_urls {
+        auto_link_bare_urls(&input.text)
+    } else {
+        input.text
+    };
+
+    EditorAction::Insert { text, range }
+}
+
+/// If `text` is exactly one URL (modulo surrounding whitespace), return it.
+fn lone_url(text: &str) -> Option<&str> {
+    let trimmed = text.trim();
+    if is_bare_url(trimmed) && !trimmed.contains(char::is_whitespace) {
+        Some(trimmed)
+    } else {
+        None
+    }
+}
+
+/// Whether a token looks like an absolute http(s) URL.
+fn is_bare_url(token: &str) -> bool {
+    (token.starts_with("https://") || token.starts_with("http://"))
+        && token.len() > "https://".len()
+}
+
+/// Wrap bare URLs in markdown autolink syntax (`<https://...>`).
+///
+/// URLs already inside markdown link syntax (preceded by `(` or `<`) are left
+/// alone so re-pasting markdown doesn't double-wrap.
+pub fn auto_link_bare_urls(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        let mut prev_end: Option<char> = None;
+        for (j, token) in line.split(' ').enumerate() {
+            if j > 0 {
+                out.push(' ');
+            }
+            // Trailing punctuation commonly follows URLs in prose; keep it
+            // outside the autolink.
+            let stripped = token.trim_end_matches(['.', ',', ';', ':', '!', '?', ')']);
+            let trailer = &token[stripped.len()..];
+            let already_wrapped = matches!(prev_end, Some('(') | Some('<'));
+            if is_bare_url(stripped) && !already_wrapped {
+                out.push('<');
+                out.push_str(stripped);
+                out.push('>');
+                out.push_str(trailer);
+            } else {
+                out.push_str(token);
+            }
+            prev_end = token.chars().next_back();
+        }
+    }
+    out
+}
+
+/// Convert an HTML fragment to markdown.
+///
+/// This is a deliberately small, tolerant converter for clipboard payloads,
+/// not a general HTML-to-markdown engine. It handles the inline and block
+/// elements that word processors and browsers emit for rich text (headings,
+/// paragraphs, emphasis, code, links, lists, blockquotes) and strips
+/// everything else, keeping the text content.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut conv = HtmlConverter::default();
+    conv.run(html);
+    conv.finish()
+}
+
+#[derive(Default)]
+struct HtmlConverter {
+    out: String,
+    /// Stack of open list contexts: `Some(n)` for ordered (next number n),
+    /// `None` for unordered.
+    lists: Vec<Option<u32>>,
+    /// Href of the innermost open anchor, with the output length at open time.
+    link: Option<(String, usize)>,
+    /// Depth of open `<pre>` elements; text inside is emitted verbatim.
+    pre_depth: usize,
+    /// Depth of elements whose content is dropped entirely.
+    skip_depth: usize,
+    blockquote_depth: usize,
+}
+
+impl HtmlConverter {
+    fn run(&mut self, html: &str) {
+        let mut rest = html;
+        while let Some(lt) = rest.find('<') {
+            let (text, after) = rest.split_at(lt);
+            self.text(text);
+            let Some(gt) = after.find('>') else {
+                // Unterminated tag: treat the remainder as text.
+                self.text(after);
+                return;
+            };
+            self.tag(&after[1..gt]);
+            rest = &after[gt + 1..];
+        }
+        self.text(rest);
+    }
+
+    fn finish(mut self) -> String {
+        while self.out.ends_with('\n') {
+            self.out.pop();
+        }
+        self.out
+    }
+
+    fn text(&mut self, raw: &str) {
+        if raw.is_empty() || self.skip_depth > 0 {
+            return;
+        }
+        let decoded = decode_entities(raw);
+        if self.pre_depth > 0 {
+            self.out.push_str(&decoded);
+            return;
+        }
+        // Collapse whitespace runs outside preformatted content.
+        let mut last_space = self.out.ends_with([' ', '\n']) || self.out.is_empty();
+        for ch in decoded.chars() {
+            if ch.is_whitespace() {
+                if !last_space {
+                    self.out.push(' ');
+                    last_space = true;
+                }
+            } else {
+                self.out.push(ch);
+                last_space = false;
+            }
+        }
+    }
+
+    fn tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        if tag.starts_with('!') || tag.starts_with('?') {
+            // Comments, doctypes.
+            return;
+        }
+        let closing = tag.starts_with('/');
+        let body = tag.trim_start_matches('/');
+        let name_end = body
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(body.len());
+        let name = body[..name_end].to_ascii_lowercase();
+
+        if self.skip_depth > 0 {
+            match name.as_str() {
+                "script" | "style" | "head" => {
+                    if closing {
+                        self.skip_depth -= 1;
+                    } else {
+                        self.skip_depth += 1;
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        match name.as_str() {
+            "script" | "style" | "head" => {
+                if !closing {
+                    self.skip_depth += 1;
+                }
+            }
+            "br" => self.out.push('\n'),
+            "hr" => {
+                self.block_break();
+                self.out.push_str("---");
+                self.block_break();
+            }
+            "p" | "div" => self.block_break(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                if closing {
+                    self.block_break();
+                } else {
+                    self.block_break();
+                    let level = name.as_bytes()[1] - b'0';
+                    for _ in 0..level {
+                        self.out.push('#');
+                    }
+                    self.out.push(' ');
+                }
+            }
+            "strong" | "b" => self.out.push_str("**"),
+            "em" | "i" => self.out.push('*'),
+            "del" | "s" | "strike" => self.out.push_str("~~"),
+            "code" => {
+                // Inside <pre>, the fence is emitted by the pre handler.
+                if self.pre_depth == 0 {
+                    self.out.push('`');
+                }
+            }
+            "pre" => {
+                if closing {
+                    self.pre_depth = self.pre_depth.saturating_sub(1);
+                    if !self.out.ends_with('\n') {
+                        self.out.push('\n');
+                    }
+                    self.out.push_str("```");
+                    self.block_break();
+                } else {
+                    self.block_break();
+                    self.out.push_str("```\n");
+                    self.pre_depth += 1;
+                }
+            }
+            "a" => {
+                if closing {
+                    if let Some((href, start)) = self.link.take() {
+                        let label = self.out.split_off(start);
+                        if label.is_empty() || label == href {
+                            self.out.push('<');
+                            self.out.push_str(&href);
+                            self.out.push('>');
+                        } else {
+                            self.out.push('[');
+                            self.out.push_str(&label);
+                            self.out.push_str("](");
+                            self.out.push_str(&href);
+                            self.out.push(')');
+                        }
+                    }
+                } else if let Some(href) = attr_value(&body[name_end..], "href") {
+                    self.link = Some((href, self.out.len()));
+                }
+            }
+            "img" => {
+                if let Some(src) = attr_value(&body[name_end..], "src") {
+                    let alt = attr_value(&body[name_end..], "alt").unwrap_or_default();
+                    self.out.push_str("![");
+                    self.out.push_str(&alt);
+                    self.out.push_str("](");
+                    self.out.push_str(&src);
+                    self.out.push(')');
+                }
+            }
+            "ul" => {
+                if closing {
+                    self.lists.pop();
+                    self.block_break();
+                } else {
+                    self.lists.push(None);
+                }
+            }
+            "ol" => {
+                if closing {
+                    self.lists.pop();
+                    self.block_break();
+                } else {
+                    self.lists.push(Some(1));
+                }
+            }
+            "li" => {
+                if !closing {
+                    if !self.out.is_empty() && !self.out.ends_with('\n') {
+                        self.out.push('\n');
+                    }
+                    let depth = self.lists.len().saturating_sub(1);
+                    for _ in 0..depth {
+                        self.out.push_str("  ");
+                    }
+                    match self.lists.last_mut() {
+                        Some(Some(n)) => {
+                            self.out.push_str(&format!("{}. ", n));
+                            *n += 1;
+                        }
+                        _ => self.out.push_str("- "),
+                    }
+                }
+            }
+            "blockquote" => {
+                if closing {
+                    self.blockquote_depth = self.blockquote_depth.saturating_sub(1);
+                    self.block_break();
+                } else {
+                    self.block_break();
+                    self.blockquote_depth += 1;
+                    self.out.push_str("> ");
+                }
+            }
+            // Unknown elements contribute only their text content.
+            _ => {}
+        }
+    }
+
+    /// Ensure the output ends with a paragraph break (outside lists).
+    fn block_break(&mut self) {
+        while self.out.ends_with(' ') {
+            self.out.pop();
+        }
+        if self.out.is_empty() {
+            return;
+        }
+        while !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+}
+
+/// Extract a quoted attribute value from a tag body.
+fn attr_value(attrs: &str, name: &str) -> Option<String> {
+    let lower = attrs.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(pos) = lower[search..].find(name) {
+        let at = search + pos;
+        // Must be a standalone attribute name followed by '='.
+        let before_ok = at == 0
+            || lower[..at]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_whitespace());
+        let after = lower[at + name.len()..].trim_start();
+        if before_ok && after.starts_with('=') {
+            let rest = &attrs[at + name.len()..];
+            let rest = rest.trim_start().trim_start_matches('=').trim_start();
+            let quote = rest.chars().next()?;
+            if quote == '"' || quote == '\'' {
+                let inner = &rest[1..];
+                let end = inner.find(quote)?;
+                return Some(decode_entities(&inner[..end]));
+            }
+            // Unquoted value.
+            let end = rest
+                .find(|c: char| c.is_whitespace() || c == '>')
+                .unwrap_or(rest.len());
+            return Some(decode_entities(&rest[..end]));
+        }
+        search = at + name.len();
+    }
+    None
+}
+
+/// Decode the HTML entities that appear in clipboard payloads.
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp..];
+        let Some(semi) = after[..after.len().min(12)].find(';') else {
+            out.push('&');
+            rest = &rest[amp + 1..];
+            continue;
+        };
+        let entity = &after[1..semi];
+        let replacement = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match replacement {
+            Some(ch) => {
+                out.push(ch);
+                rest = &rest[amp + semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[amp + 1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_passthrough() {
+        let action = process_paste(PasteContent::plain("hello world", Range::caret(0)));
+        assert_eq!(
+            action,
+            EditorAction::Insert {
+                text: "hello world".to_string(),
+                range: Range::caret(0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_auto_link_bare_url() {
+        let action = process_paste(PasteContent::plain(
+            "see https://example.com for details",
+            Range::caret(0),
+        ));
+        let EditorAction::Insert { text, .. } = action else {
+            panic!("expected insert");
+        };
+        assert_eq!(text, "see <https://example.com> for details");
+    }
+
+    #[test]
+    fn test_auto_link_keeps_trailing_punctuation() {
+        assert_eq!(
+            auto_link_bare_urls("go to https://example.com."),
+            "go to <https://example.com>."
+        );
+    }
+
+    #[test]
+    fn test_auto_link_skips_existing_markdown_links() {
+        let text = "[label](https://example.com)";
+        assert_eq!(auto_link_bare_urls(text), text);
+    }
+
+    #[test]
+    fn test_url_over_selection_becomes_link() {
+        let input = PasteContent::plain("https://example.com", Range::new(0, 5))
+            .with_selected_text("hello");
+        let EditorAction::Insert { text, range } = process_paste(input) else {
+            panic!("expected insert");
+        };
+        assert_eq!(text, "[hello](https://example.com)");
+        assert_eq!(range, Range::new(0, 5));
+    }
+
+    #[test]
+    fn test_url_over_selection_disabled() {
+        let config = PasteConfig {
+            link_selection_on_url_paste: false,
+            ..Default::default()
+        };
+        let input = PasteContent::plain("https://example.com", Range::new(0, 5))
+            .with_selected_text("hello");
+        let EditorAction::Insert { text, .. } = process_paste_with_config(input, &config) else {
+            panic!("expected insert");
+        };
+        assert_eq!(text, "<https://example.com>");
+    }
+
+    #[test]
+    fn test_html_basic_formatting() {
+        assert_eq!(
+            html_to_markdown("<p>some <strong>bold</strong> and <em>italic</em></p>"),
+            "some **bold** and *italic*"
+        );
+    }
+
+    #[test]
+    fn test_html_headings_and_paragraphs() {
+        assert_eq!(
+            html_to_markdown("<h2>Title</h2><p>first</p><p>second</p>"),
+            "## Title\n\nfirst\n\nsecond"
+        );
+    }
+
+    #[test]
+    fn test_html_links() {
+        assert_eq!(
+            html_to_markdown(r#"<a href="https://example.com">label</a>"#),
+            "[label](https://example.com)"
+        );
+        assert_eq!(
+            html_to_markdown(r#"<a href="https://example.com">https://example.com</a>"#),
+            "<https://example.com>"
+        );
+    }
+
+    #[test]
+    fn test_html_lists() {
+        assert_eq!(
+            html_to_markdown("<ul><li>one</li><li>two</li></ul>"),
+            "- one\n- two"
+        );
+        assert_eq!(
+            html_to_markdown("<ol><li>one</li><li>two</li></ol>"),
+            "1. one\n2. two"
+        );
+    }
+
+    #[test]
+    fn test_html_code_block() {
+        assert_eq!(
+            html_to_markdown("<pre><code>let x = 1;</code></pre>"),
+            "```\nlet x = 1;\n```"
+        );
+    }
+
+    #[test]
+    fn test_html_entities() {
+        assert_eq!(html_to_markdown("<p>a &amp; b &lt;c&gt;</p>"), "a & b <c>");
+    }
+
+    #[test]
+    fn test_html_strips_scripts() {
+        assert_eq!(
+            html_to_markdown("<p>safe</p><script>alert(1)</script>"),
+            "safe"
+        );
+    }
+
+    #[test]
+    fn test_html_flavor_preferred_over_plain() {
+        let input = PasteContent::plain("bold", Range::caret(0))
+            .with_html("<strong>bold</strong>");
+        let EditorAction::Insert { text, .. } = process_paste(input) else {
+            panic!("expected insert");
+        };
+        assert_eq!(text, "**bold**");
+    }
+
+    #[test]
+    fn test_html_conversion_disabled() {
+        let config = PasteConfig {
+            convert_html: false,
+            ..Default::default()
+        };
+        let input = PasteContent::plain("bold", Range::caret(0))
+            .with_html("<strong>bold</strong>");
+        let EditorAction::Insert { text, .. } = process_paste_with_config(input, &config) else {
+            panic!("expected insert");
+        };
+        assert_eq!(text, "bold");
+    }
+}
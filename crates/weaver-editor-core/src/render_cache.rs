@@ -7,6 +7,7 @@ use std::ops::Range;
 
 use smol_str::SmolStr;
 
+use crate::code_block::code_block_language;
 use crate::offset_map::OffsetMapping;
 use crate::paragraph::{ParagraphRender, hash_source, make_paragraph_id};
 use crate::syntax::SyntaxSpanInfo;
@@ -148,6 +149,7 @@ where
             offset_map: vec![],
             syntax_spans: vec![],
             source_hash: 0,
+            code_language: None,
         };
 
         let new_cache = RenderCache {
@@ -197,6 +199,9 @@ where
                     offset_map: p.offset_map.clone(),
                     syntax_spans: p.syntax_spans.clone(),
                     source_hash: p.source_hash,
+                    code_language: text
+                        .slice(p.char_range.clone())
+                        .and_then(|s| code_block_language(&s)),
                 })
                 .collect();
             return IncrementalRenderResult {
@@ -409,6 +414,7 @@ where
                     offset_map,
                     syntax_spans,
                     source_hash,
+                    code_language: code_block_language(&para_source),
                 });
             } else {
                 // Reuse cached with adjusted offsets.
@@ -448,6 +454,7 @@ where
                     offset_map,
                     syntax_spans,
                     source_hash,
+                    code_language: code_block_language(&para_source),
                 });
             }
         }
@@ -739,6 +746,7 @@ where
             offset_map,
             syntax_spans,
             source_hash,
+            code_language: code_block_language(&para_source),
         });
     }
 
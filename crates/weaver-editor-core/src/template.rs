@@ -0,0 +1,307 @@
+//! Document templates with locked (non-editable) regions.
+//!
+//! A [`DocumentTemplate`] pairs initial markdown content with a set of
+//! [`LockedRegion`]s that user edits must not touch - useful for
+//! structured forms (meeting notes, review documents) where headings and
+//! boilerplate stay fixed while collaborators fill in the blanks.
+//!
+//! Enforcement happens in [`execute_action`](crate::execute_action):
+//! actions whose edit range touches a locked region are rejected.
+//! Programmatic edits (CRDT sync, undo) bypass the check, so locked
+//! regions follow allowed edits via offset shifting in
+//! [`EditorDocument`](crate::EditorDocument) rather than staying pinned.
+//!
+//! Templates can declare regions inline in markdown using HTML comment
+//! markers, which are stripped from the instantiated content:
+//!
+//! ```markdown
+//! <!-- lock -->
+//! # Weekly sync
+//! <!-- /lock -->
+//! Notes go here...
+//! ```
+
+use std::ops::Range;
+
+use smol_str::SmolStr;
+
+use crate::document::EditorDocument;
+
+/// Marker opening a locked region in template markdown.
+const LOCK_OPEN: &str = "<!-- lock -->";
+/// Marker closing a locked region in template markdown.
+const LOCK_CLOSE: &str = "<!-- /lock -->";
+
+/// A non-editable character range in a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LockedRegion {
+    /// Character range that must not be edited.
+    pub range: Range<usize>,
+    /// Optional label describing the region (for UI affordances).
+    pub label: Option<SmolStr>,
+}
+
+impl LockedRegion {
+    /// Create an unlabeled locked region.
+    pub fn new(range: Range<usize>) -> Self {
+        Self { range, label: None }
+    }
+
+    /// Create a locked region with a label.
+    pub fn with_label(range: Range<usize>, label: impl Into<SmolStr>) -> Self {
+        Self {
+            range,
+            label: Some(label.into()),
+        }
+    }
+
+    /// Whether inserting at `offset` would edit this region.
+    ///
+    /// Boundary insertions are allowed: typing immediately before or
+    /// after locked text only shifts it.
+    pub fn blocks_insert_at(&self, offset: usize) -> bool {
+        self.range.start < offset && offset < self.range.end
+    }
+
+    /// Whether deleting or replacing `range` would edit this region.
+    pub fn blocks_edit_of(&self, range: &Range<usize>) -> bool {
+        range.start < self.range.end && range.end > self.range.start
+    }
+}
+
+/// Error from parsing template markdown.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum TemplateError {
+    /// A lock marker was opened but never closed.
+    #[error("lock marker opened at char {offset} is never closed")]
+    UnclosedMarker {
+        /// Char offset of the open marker in the template source.
+        offset: usize,
+    },
+    /// A close marker appeared without a matching open marker.
+    #[error("close marker at char {offset} has no matching open marker")]
+    UnmatchedClose {
+        /// Char offset of the close marker in the template source.
+        offset: usize,
+    },
+    /// Lock markers cannot nest.
+    #[error("lock markers cannot nest (second open at char {offset})")]
+    NestedMarker {
+        /// Char offset of the nested open marker in the template source.
+        offset: usize,
+    },
+}
+
+/// A document template: initial content plus locked regions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentTemplate {
+    name: SmolStr,
+    content: String,
+    locked: Vec<LockedRegion>,
+}
+
+impl DocumentTemplate {
+    /// Create a template with no locked regions.
+    pub fn new(name: impl Into<SmolStr>, content: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            content: content.into(),
+            locked: Vec::new(),
+        }
+    }
+
+    /// Add a locked region (char offsets into the template content).
+    pub fn lock(mut self, region: LockedRegion) -> Self {
+        self.locked.push(region);
+        self
+    }
+
+    /// Parse template markdown with inline `<!-- lock -->` markers.
+    ///
+    /// Markers are removed from the content; the text between each
+    /// open/close pair becomes a locked region. A marker that sits on
+    /// its own line is removed together with its trailing newline so
+    /// templates read naturally in source form.
+    pub fn from_markdown(name: impl Into<SmolStr>, source: &str) -> Result<Self, TemplateError> {
+        let mut content = String::new();
+        let mut out_chars = 0usize;
+        // Char offset in the source, for error reporting.
+        let mut src_chars = 0usize;
+        let mut rest = source;
+        // (content offset, source offset) of the pending open marker.
+        let mut open_at: Option<(usize, usize)> = None;
+        let mut locked = Vec::new();
+
+        loop {
+            let next_open = rest.find(LOCK_OPEN);
+            let next_close = rest.find(LOCK_CLOSE);
+
+            // The markers differ at their fifth char, so they can never
+            // match at the same index - the earliest one wins cleanly.
+            let (idx, is_open) = match (next_open, next_close) {
+                (Some(o), Some(c)) if o < c => (o, true),
+                (Some(_), Some(c)) => (c, false),
+                (Some(o), None) => (o, true),
+                (None, Some(c)) => (c, false),
+                (None, None) => break,
+            };
+
+            let before = &rest[..idx];
+            content.push_str(before);
+            out_chars += before.chars().count();
+            src_chars += before.chars().count();
+
+            let marker = if is_open { LOCK_OPEN } else { LOCK_CLOSE };
+            if is_open {
+                if open_at.is_some() {
+                    return Err(TemplateError::NestedMarker { offset: src_chars });
+                }
+                open_at = Some((out_chars, src_chars));
+            } else {
+                let Some((start, _)) = open_at.take() else {
+                    return Err(TemplateError::UnmatchedClose { offset: src_chars });
+                };
+                locked.push(LockedRegion::new(start..out_chars));
+            }
+
+            src_chars += marker.chars().count();
+            rest = &rest[idx + marker.len()..];
+
+            // Swallow the newline after a marker that occupies its own
+            // line, so markers don't leave blank lines behind.
+            let at_line_start = content.is_empty() || content.ends_with('\n');
+            if at_line_start && rest.starts_with('\n') {
+                rest = &rest[1..];
+                src_chars += 1;
+            }
+        }
+
+        if let Some((_, src_offset)) = open_at {
+            return Err(TemplateError::UnclosedMarker { offset: src_offset });
+        }
+
+        content.push_str(rest);
+        Ok(Self {
+            name: name.into(),
+            content,
+            locked,
+        })
+    }
+
+    /// Template name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Instantiated content with lock markers stripped.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Locked regions, as char ranges into [`content`](Self::content).
+    pub fn locked_regions(&self) -> &[LockedRegion] {
+        &self.locked
+    }
+
+    /// First char offset where edits are allowed.
+    ///
+    /// Useful for placing the initial cursor past leading boilerplate.
+    pub fn first_editable_offset(&self) -> usize {
+        let mut offset = 0;
+        // Regions are recorded in document order by construction.
+        for region in &self.locked {
+            if region.blocks_insert_at(offset) || region.range.start == offset {
+                offset = region.range.end;
+            }
+        }
+        offset
+    }
+
+    /// Initialize a document from this template.
+    ///
+    /// Replaces the entire content, installs the locked regions, and
+    /// clears edit history so undo cannot strip template structure.
+    /// The cursor lands on the first editable offset.
+    pub fn apply_to<D: EditorDocument>(&self, doc: &mut D) {
+        let len = doc.len_chars();
+        doc.replace(0..len, &self.content);
+        doc.set_locked_regions(self.locked.clone());
+        doc.clear_history();
+        doc.set_cursor_offset(self.first_editable_offset());
+        doc.set_selection(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EditorRope, PlainEditor, UndoableBuffer};
+
+    type TestEditor = PlainEditor<UndoableBuffer<EditorRope>>;
+
+    #[test]
+    fn test_from_markdown_strips_markers_and_records_regions() {
+        let source = "<!-- lock -->\n# Weekly sync\n<!-- /lock -->\nNotes here\n";
+        let template = DocumentTemplate::from_markdown("meeting", source).unwrap();
+
+        assert_eq!(template.content(), "# Weekly sync\nNotes here\n");
+        assert_eq!(
+            template.locked_regions(),
+            &[LockedRegion::new(0.."# Weekly sync\n".chars().count())]
+        );
+        assert_eq!(template.first_editable_offset(), 14);
+    }
+
+    #[test]
+    fn test_from_markdown_inline_markers_keep_surrounding_text() {
+        let source = "Status: <!-- lock -->do not edit<!-- /lock --> (fill in)";
+        let template = DocumentTemplate::from_markdown("status", source).unwrap();
+
+        assert_eq!(template.content(), "Status: do not edit (fill in)");
+        assert_eq!(template.locked_regions(), &[LockedRegion::new(8..19)]);
+    }
+
+    #[test]
+    fn test_from_markdown_rejects_bad_markers() {
+        assert!(matches!(
+            DocumentTemplate::from_markdown("t", "<!-- lock -->abc"),
+            Err(TemplateError::UnclosedMarker { .. })
+        ));
+        assert!(matches!(
+            DocumentTemplate::from_markdown("t", "abc<!-- /lock -->"),
+            Err(TemplateError::UnmatchedClose { offset: 3 })
+        ));
+        assert!(matches!(
+            DocumentTemplate::from_markdown("t", "<!-- lock --><!-- lock -->"),
+            Err(TemplateError::NestedMarker { .. })
+        ));
+    }
+
+    #[test]
+    fn test_blocks_insert_allows_boundaries() {
+        let region = LockedRegion::new(5..10);
+        assert!(!region.blocks_insert_at(5));
+        assert!(region.blocks_insert_at(7));
+        assert!(!region.blocks_insert_at(10));
+    }
+
+    #[test]
+    fn test_apply_to_installs_content_and_regions() {
+        let template = DocumentTemplate::from_markdown(
+            "meeting",
+            "<!-- lock -->\n# Agenda\n<!-- /lock -->\n- \n",
+        )
+        .unwrap();
+
+        let mut editor = TestEditor::default();
+        editor.insert(0, "old content");
+        template.apply_to(&mut editor);
+
+        assert_eq!(editor.content_string(), "# Agenda\n- \n");
+        assert_eq!(editor.locked_regions(), template.locked_regions());
+        assert_eq!(editor.cursor_offset(), 9);
+        // Template instantiation is not undoable.
+        assert!(!editor.can_undo());
+    }
+}
@@ -0,0 +1,677 @@
+//! Modal (vim) keybinding mode.
+//!
+//! Extends the flat [`KeybindingConfig`] lookup into a modal input system.
+//! [`VimState`] tracks the current mode plus pending count/operator state, and
+//! [`handle_vim_key`] translates normal/visual-mode key sequences into the
+//! existing [`EditorAction`] vocabulary so the document layer needs no
+//! vim-specific logic.
+//!
+//! Supported subset:
+//! - Modes: normal, insert, visual (character-wise).
+//! - Motions: `h`, `l`, `j`, `k`, `w`, `b`, `e`, `0`, `$`, `gg`, `G`.
+//! - Operators: `d`, `c`, `y`, including linewise doubles (`dd`, `cc`, `yy`).
+//! - Counts before motions and operators (`3w`, `2dd`, `d2w`).
+//! - Insert entry: `i`, `I`, `a`, `A`, `o`, `O`; `x`, `p`, `u`, `Ctrl+r`.
+
+use crate::actions::{EditorAction, Key, KeyCombo, KeydownResult, Modifiers, Range};
+use crate::document::EditorDocument;
+use crate::execute::execute_action;
+use crate::text_helpers::{
+    find_line_end, find_line_start, find_word_boundary_backward, find_word_boundary_forward,
+};
+use crate::types::Selection;
+
+/// The current input mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VimMode {
+    /// Keys are commands; nothing is inserted.
+    #[default]
+    Normal,
+    /// Keys insert text through the regular input pipeline.
+    Insert,
+    /// Motions extend the selection; operators act on it.
+    Visual,
+}
+
+/// A pending operator waiting for a motion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VimOperator {
+    /// `d` - delete the motion range.
+    Delete,
+    /// `c` - delete the motion range and enter insert mode.
+    Change,
+    /// `y` - yank the motion range into the register.
+    Yank,
+}
+
+/// Modal editing state.
+///
+/// One instance lives alongside the document for the lifetime of the editor.
+/// The browser layer consults [`VimState::mode`] to decide whether beforeinput
+/// events should insert text (insert mode) or be suppressed (normal/visual).
+#[derive(Debug, Clone, Default)]
+pub struct VimState {
+    /// Current mode.
+    pub mode: VimMode,
+    /// Count prefix being accumulated (`None` means no count typed yet).
+    count: Option<usize>,
+    /// Operator awaiting a motion.
+    operator: Option<VimOperator>,
+    /// Count typed before the operator (for forms like `2d3w`).
+    operator_count: Option<usize>,
+    /// A `g` was pressed and we're waiting for the second key of the sequence.
+    pending_g: bool,
+    /// Anchor of the visual selection.
+    visual_anchor: Option<usize>,
+    /// Yank register.
+    register: String,
+}
+
+impl VimState {
+    /// Create a fresh state in normal mode.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether text input should currently reach the document.
+    pub fn accepts_input(&self) -> bool {
+        self.mode == VimMode::Insert
+    }
+
+    /// Contents of the yank register.
+    pub fn register(&self) -> &str {
+        &self.register
+    }
+
+    /// Effective count for the next motion: operator count times motion count,
+    /// defaulting each to 1.
+    fn take_count(&mut self) -> usize {
+        let count = self.count.take().unwrap_or(1) * self.operator_count.take().unwrap_or(1);
+        count.max(1)
+    }
+
+    /// Reset pending count/operator state (e.g. on Escape or invalid key).
+    fn clear_pending(&mut self) {
+        self.count = None;
+        self.operator = None;
+        self.operator_count = None;
+        self.pending_g = false;
+    }
+}
+
+/// Handle a keydown in vim mode.
+///
+/// Returns [`KeydownResult::Handled`] when the key was consumed as a vim
+/// command, [`KeydownResult::NotHandled`] when it should flow through the
+/// regular input pipeline (insert mode typing), and
+/// [`KeydownResult::PassThrough`] for keys the platform should keep
+/// (modifiers, navigation in insert mode).
+pub fn handle_vim_key<D: EditorDocument>(
+    doc: &mut D,
+    state: &mut VimState,
+    combo: &KeyCombo,
+) -> KeydownResult {
+    if combo.key.is_modifier() {
+        return KeydownResult::PassThrough;
+    }
+
+    match state.mode {
+        VimMode::Insert => handle_insert_mode(state, combo),
+        VimMode::Normal => handle_normal_mode(doc, state, combo, false),
+        VimMode::Visual => handle_normal_mode(doc, state, combo, true),
+    }
+}
+
+fn handle_insert_mode(state: &mut VimState, combo: &KeyCombo) -> KeydownResult {
+    if combo.key == Key::Escape {
+        state.mode = VimMode::Normal;
+        state.clear_pending();
+        return KeydownResult::Handled;
+    }
+    // Everything else flows through the regular input pipeline.
+    KeydownResult::NotHandled
+}
+
+fn handle_normal_mode<D: EditorDocument>(
+    doc: &mut D,
+    state: &mut VimState,
+    combo: &KeyCombo,
+    visual: bool,
+) -> KeydownResult {
+    let ctrl = combo.modifiers == Modifiers::CTRL;
+
+    // Ctrl+r - redo (normal mode only).
+    if ctrl {
+        if let Key::Character(ref s) = combo.key
+            && s.as_str() == "r"
+            && !visual
+        {
+            execute_action(doc, &EditorAction::Redo);
+            return KeydownResult::Handled;
+        }
+        // Leave other control chords to the platform.
+        return KeydownResult::PassThrough;
+    }
+
+    match &combo.key {
+        Key::Escape => {
+            state.clear_pending();
+            if visual {
+                state.mode = VimMode::Normal;
+                state.visual_anchor = None;
+                doc.set_selection(None);
+            }
+            KeydownResult::Handled
+        }
+        Key::Character(s) => handle_normal_char(doc, state, s.as_str(), visual),
+        // Arrow keys etc. behave as in insert mode.
+        key if key.is_navigation() => KeydownResult::PassThrough,
+        _ => KeydownResult::Handled,
+    }
+}
+
+fn handle_normal_char<D: EditorDocument>(
+    doc: &mut D,
+    state: &mut VimState,
+    ch: &str,
+    visual: bool,
+) -> KeydownResult {
+    // Count accumulation: `0` is a motion unless a count is already pending.
+    if let Ok(digit) = ch.parse::<usize>()
+        && (digit != 0 || state.count.is_some())
+    {
+        let so_far = state.count.unwrap_or(0);
+        state.count = Some(so_far.saturating_mul(10).saturating_add(digit));
+        return KeydownResult::Handled;
+    }
+
+    // `g` prefix.
+    if state.pending_g {
+        state.pending_g = false;
+        if ch == "g" {
+            apply_motion(doc, state, Motion::DocumentStart, visual);
+        } else {
+            state.clear_pending();
+        }
+        return KeydownResult::Handled;
+    }
+
+    match ch {
+        // === Motions ===
+        "h" => apply_motion(doc, state, Motion::Left, visual),
+        "l" => apply_motion(doc, state, Motion::Right, visual),
+        "j" => apply_motion(doc, state, Motion::Down, visual),
+        "k" => apply_motion(doc, state, Motion::Up, visual),
+        "w" => apply_motion(doc, state, Motion::WordForward, visual),
+        "b" => apply_motion(doc, state, Motion::WordBackward, visual),
+        "e" => apply_motion(doc, state, Motion::WordEnd, visual),
+        "0" => apply_motion(doc, state, Motion::LineStart, visual),
+        "$" => apply_motion(doc, state, Motion::LineEnd, visual),
+        "G" => apply_motion(doc, state, Motion::DocumentEnd, visual),
+        "g" => {
+            state.pending_g = true;
+        }
+
+        // === Operators ===
+        "d" => press_operator(doc, state, VimOperator::Delete, visual),
+        "c" => press_operator(doc, state, VimOperator::Change, visual),
+        "y" => press_operator(doc, state, VimOperator::Yank, visual),
+
+        // === Mode changes ===
+        "i" => enter_insert(doc, state, InsertAt::Cursor),
+        "I" => enter_insert(doc, state, InsertAt::LineStart),
+        "a" => enter_insert(doc, state, InsertAt::AfterCursor),
+        "A" => enter_insert(doc, state, InsertAt::LineEnd),
+        "o" => enter_insert(doc, state, InsertAt::LineBelow),
+        "O" => enter_insert(doc, state, InsertAt::LineAbove),
+        "v" => {
+            if visual {
+                state.mode = VimMode::Normal;
+                state.visual_anchor = None;
+                doc.set_selection(None);
+            } else {
+                state.mode = VimMode::Visual;
+                state.visual_anchor = Some(doc.cursor_offset());
+            }
+            state.clear_pending();
+        }
+
+        // === Simple commands ===
+        "x" => {
+            let count = state.take_count();
+            if visual {
+                operate_on_visual(doc, state, VimOperator::Delete);
+            } else {
+                let cursor = doc.cursor_offset();
+                let end = (cursor + count).min(doc.len_chars());
+                if cursor < end {
+                    yank_range(doc, state, cursor, end);
+                    doc.delete(cursor..end);
+                }
+            }
+        }
+        "p" => {
+            let count = state.take_count();
+            if !state.register.is_empty() {
+                let text = state.register.repeat(count);
+                let cursor = doc.cursor_offset();
+                // Paste after the cursor, like vim's `p`.
+                let at = if cursor < doc.len_chars() && !state.register.contains('\n') {
+                    cursor + 1
+                } else {
+                    cursor
+                };
+                let len = text.chars().count();
+                doc.insert(at.min(doc.len_chars()), &text);
+                doc.set_cursor_offset(at.min(doc.len_chars()) + len);
+            }
+        }
+        "u" => {
+            state.clear_pending();
+            execute_action(doc, &EditorAction::Undo);
+        }
+
+        _ => {
+            state.clear_pending();
+        }
+    }
+    KeydownResult::Handled
+}
+
+/// Where to place the cursor when entering insert mode.
+enum InsertAt {
+    Cursor,
+    AfterCursor,
+    LineStart,
+    LineEnd,
+    LineBelow,
+    LineAbove,
+}
+
+fn enter_insert<D: EditorDocument>(doc: &mut D, state: &mut VimState, at: InsertAt) {
+    let cursor = doc.cursor_offset();
+    match at {
+        InsertAt::Cursor => {}
+        InsertAt::AfterCursor => {
+            doc.set_cursor_offset((cursor + 1).min(doc.len_chars()));
+        }
+        InsertAt::LineStart => doc.set_cursor_offset(find_line_start(doc, cursor)),
+        InsertAt::LineEnd => doc.set_cursor_offset(find_line_end(doc, cursor)),
+        InsertAt::LineBelow => {
+            let line_end = find_line_end(doc, cursor);
+            doc.insert(line_end, "\n");
+            doc.set_cursor_offset(line_end + 1);
+        }
+        InsertAt::LineAbove => {
+            let line_start = find_line_start(doc, cursor);
+            doc.insert(line_start, "\n");
+            doc.set_cursor_offset(line_start);
+        }
+    }
+    doc.set_selection(None);
+    state.visual_anchor = None;
+    state.mode = VimMode::Insert;
+    state.clear_pending();
+}
+
+/// A motion target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordBackward,
+    WordEnd,
+    LineStart,
+    LineEnd,
+    DocumentStart,
+    DocumentEnd,
+}
+
+impl Motion {
+    /// Whether an operator over this motion includes the end position.
+    fn is_inclusive(&self) -> bool {
+        matches!(self, Motion::WordEnd | Motion::Right)
+    }
+}
+
+/// Compute the offset a motion lands on, applied `count` times.
+fn motion_target<D: EditorDocument>(doc: &D, from: usize, motion: Motion, count: usize) -> usize {
+    let len = doc.len_chars();
+    let mut pos = from;
+    for _ in 0..count {
+        let next = match motion {
+            Motion::Left => pos.saturating_sub(1),
+            Motion::Right => (pos + 1).min(len),
+            Motion::Up => vertical_move(doc, pos, false),
+            Motion::Down => vertical_move(doc, pos, true),
+            Motion::WordForward => find_word_boundary_forward(doc, pos),
+            Motion::WordBackward => find_word_boundary_backward(doc, pos),
+            Motion::WordEnd => word_end(doc, pos),
+            Motion::LineStart => find_line_start(doc, pos),
+            Motion::LineEnd => find_line_end(doc, pos),
+            Motion::DocumentStart => 0,
+            Motion::DocumentEnd => len,
+        };
+        if next == pos {
+            break;
+        }
+        pos = next;
+    }
+    pos
+}
+
+/// Move to the same column on the previous/next line.
+fn vertical_move<D: EditorDocument>(doc: &D, pos: usize, down: bool) -> usize {
+    let line_start = find_line_start(doc, pos);
+    let col = pos - line_start;
+    if down {
+        let line_end = find_line_end(doc, pos);
+        if line_end >= doc.len_chars() {
+            return pos;
+        }
+        let next_start = line_end + 1;
+        let next_end = find_line_end(doc, next_start);
+        (next_start + col).min(next_end)
+    } else {
+        if line_start == 0 {
+            return pos;
+        }
+        let prev_end = line_start - 1;
+        let prev_start = find_line_start(doc, prev_end);
+        (prev_start + col).min(prev_end)
+    }
+}
+
+/// Find the end of the current/next word (vim's `e`).
+fn word_end<D: EditorDocument>(doc: &D, pos: usize) -> usize {
+    let len = doc.len_chars();
+    let mut i = pos + 1;
+    // Skip any whitespace to reach the next word.
+    while i < len && doc.char_at(i).is_some_and(char::is_whitespace) {
+        i += 1;
+    }
+    // Advance to the last character of the word.
+    while i + 1 < len && !doc.char_at(i + 1).is_some_and(char::is_whitespace) {
+        i += 1;
+    }
+    i.min(len.saturating_sub(1)).max(pos)
+}
+
+fn apply_motion<D: EditorDocument>(doc: &mut D, state: &mut VimState, motion: Motion, visual: bool) {
+    let count = state.take_count();
+    let cursor = doc.cursor_offset();
+
+    if let Some(op) = state.operator.take() {
+        // Operator + motion: act on the range from cursor to target.
+        let target = motion_target(doc, cursor, motion, count);
+        let (mut start, mut end) = if target >= cursor {
+            (cursor, target)
+        } else {
+            (target, cursor)
+        };
+        if motion.is_inclusive() {
+            end = (end + 1).min(doc.len_chars());
+        }
+        // Linewise motions to document edges operate on whole lines.
+        if matches!(motion, Motion::DocumentStart | Motion::DocumentEnd) {
+            start = find_line_start(doc, start);
+            end = line_end_with_newline(doc, end);
+        }
+        operate(doc, state, op, start, end);
+        return;
+    }
+
+    let target = motion_target(doc, cursor, motion, count);
+    if visual {
+        let anchor = state.visual_anchor.unwrap_or(cursor);
+        execute_action(doc, &EditorAction::ExtendSelection { offset: target });
+        // ExtendSelection resets the anchor from the current selection; keep ours.
+        doc.set_selection(Some(Selection::new(anchor, target)));
+    } else {
+        execute_action(doc, &EditorAction::MoveCursor { offset: target });
+    }
+}
+
+fn press_operator<D: EditorDocument>(
+    doc: &mut D,
+    state: &mut VimState,
+    op: VimOperator,
+    visual: bool,
+) {
+    if visual {
+        operate_on_visual(doc, state, op);
+        return;
+    }
+    match state.operator {
+        // Doubled operator (`dd`, `cc`, `yy`): linewise on the current line.
+        Some(pending) if pending == op => {
+            state.operator = None;
+            let count = state.take_count();
+            let cursor = doc.cursor_offset();
+            let start = find_line_start(doc, cursor);
+            let mut end = cursor;
+            for _ in 0..count {
+                end = line_end_with_newline(doc, end);
+                if end >= doc.len_chars() {
+                    break;
+                }
+            }
+            operate(doc, state, op, start, end);
+        }
+        _ => {
+            // Count typed so far belongs to the operator.
+            state.operator_count = state.count.take();
+            state.operator = Some(op);
+        }
+    }
+}
+
+fn operate_on_visual<D: EditorDocument>(doc: &mut D, state: &mut VimState, op: VimOperator) {
+    let Some(sel) = doc.selection() else {
+        state.mode = VimMode::Normal;
+        state.clear_pending();
+        return;
+    };
+    let (start, end) = (sel.start().min(sel.end()), sel.start().max(sel.end()));
+    // Visual selections are inclusive of the head character.
+    let end = (end + 1).min(doc.len_chars());
+    state.mode = VimMode::Normal;
+    state.visual_anchor = None;
+    doc.set_selection(None);
+    operate(doc, state, op, start, end);
+}
+
+fn operate<D: EditorDocument>(
+    doc: &mut D,
+    state: &mut VimState,
+    op: VimOperator,
+    start: usize,
+    end: usize,
+) {
+    if start >= end {
+        state.clear_pending();
+        return;
+    }
+    yank_range(doc, state, start, end);
+    match op {
+        VimOperator::Delete => {
+            doc.delete(start..end);
+            doc.set_cursor_offset(start.min(doc.len_chars()));
+        }
+        VimOperator::Change => {
+            doc.delete(start..end);
+            doc.set_cursor_offset(start.min(doc.len_chars()));
+            state.mode = VimMode::Insert;
+        }
+        VimOperator::Yank => {
+            doc.set_cursor_offset(start);
+        }
+    }
+    state.clear_pending();
+}
+
+fn yank_range<D: EditorDocument>(doc: &D, state: &mut VimState, start: usize, end: usize) {
+    if let Some(text) = doc.slice(start..end) {
+        state.register = text;
+    }
+}
+
+/// End of the line containing `pos`, including its trailing newline if present.
+fn line_end_with_newline<D: EditorDocument>(doc: &D, pos: usize) -> usize {
+    let end = find_line_end(doc, pos);
+    if end < doc.len_chars() { end + 1 } else { end }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EditorRope, PlainEditor, UndoableBuffer};
+
+    type TestEditor = PlainEditor<UndoableBuffer<EditorRope>>;
+
+    fn make_editor(content: &str) -> TestEditor {
+        let rope = EditorRope::from_str(content);
+        let buf = UndoableBuffer::new(rope, 100);
+        PlainEditor::new(buf)
+    }
+
+    fn key(state: &mut VimState, editor: &mut TestEditor, ch: &str) -> KeydownResult {
+        handle_vim_key(editor, state, &KeyCombo::new(Key::character(ch)))
+    }
+
+    #[test]
+    fn test_starts_in_normal_mode() {
+        let state = VimState::new();
+        assert_eq!(state.mode, VimMode::Normal);
+        assert!(!state.accepts_input());
+    }
+
+    #[test]
+    fn test_insert_mode_entry_and_escape() {
+        let mut editor = make_editor("hello");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "i");
+        assert_eq!(state.mode, VimMode::Insert);
+        assert!(state.accepts_input());
+
+        let result = handle_vim_key(&mut editor, &mut state, &KeyCombo::new(Key::Escape));
+        assert_eq!(result, KeydownResult::Handled);
+        assert_eq!(state.mode, VimMode::Normal);
+    }
+
+    #[test]
+    fn test_word_motion_with_count() {
+        let mut editor = make_editor("one two three four");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "2");
+        key(&mut state, &mut editor, "w");
+        assert_eq!(editor.cursor_offset(), 8); // Start of "three".
+    }
+
+    #[test]
+    fn test_line_motions() {
+        let mut editor = make_editor("hello world");
+        let mut state = VimState::new();
+        editor.set_cursor_offset(5);
+        key(&mut state, &mut editor, "$");
+        assert_eq!(editor.cursor_offset(), 11);
+        key(&mut state, &mut editor, "0");
+        assert_eq!(editor.cursor_offset(), 0);
+    }
+
+    #[test]
+    fn test_gg_and_shift_g() {
+        let mut editor = make_editor("one\ntwo\nthree");
+        let mut state = VimState::new();
+        editor.set_cursor_offset(5);
+        key(&mut state, &mut editor, "G");
+        assert_eq!(editor.cursor_offset(), 13);
+        key(&mut state, &mut editor, "g");
+        key(&mut state, &mut editor, "g");
+        assert_eq!(editor.cursor_offset(), 0);
+    }
+
+    #[test]
+    fn test_dw_deletes_word() {
+        let mut editor = make_editor("one two three");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "d");
+        key(&mut state, &mut editor, "w");
+        assert_eq!(editor.content_string(), "two three");
+        assert_eq!(state.register(), "one ");
+    }
+
+    #[test]
+    fn test_dd_deletes_line() {
+        let mut editor = make_editor("one\ntwo\nthree");
+        let mut state = VimState::new();
+        editor.set_cursor_offset(5);
+        key(&mut state, &mut editor, "d");
+        key(&mut state, &mut editor, "d");
+        assert_eq!(editor.content_string(), "one\nthree");
+    }
+
+    #[test]
+    fn test_cw_enters_insert_mode() {
+        let mut editor = make_editor("one two");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "c");
+        key(&mut state, &mut editor, "w");
+        assert_eq!(editor.content_string(), "two");
+        assert_eq!(state.mode, VimMode::Insert);
+    }
+
+    #[test]
+    fn test_yank_and_paste() {
+        let mut editor = make_editor("one two");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "y");
+        key(&mut state, &mut editor, "w");
+        assert_eq!(state.register(), "one ");
+        assert_eq!(editor.content_string(), "one two");
+        key(&mut state, &mut editor, "p");
+        assert_eq!(editor.content_string(), "oone ne two");
+    }
+
+    #[test]
+    fn test_x_deletes_char() {
+        let mut editor = make_editor("hello");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "x");
+        assert_eq!(editor.content_string(), "ello");
+    }
+
+    #[test]
+    fn test_visual_mode_delete() {
+        let mut editor = make_editor("hello world");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "v");
+        assert_eq!(state.mode, VimMode::Visual);
+        key(&mut state, &mut editor, "w");
+        key(&mut state, &mut editor, "d");
+        assert_eq!(state.mode, VimMode::Normal);
+        assert_eq!(editor.content_string(), "orld");
+    }
+
+    #[test]
+    fn test_count_applies_to_operator_motion() {
+        let mut editor = make_editor("a b c d e");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "d");
+        key(&mut state, &mut editor, "2");
+        key(&mut state, &mut editor, "w");
+        assert_eq!(editor.content_string(), "c d e");
+    }
+
+    #[test]
+    fn test_insert_mode_passes_keys_through() {
+        let mut editor = make_editor("hello");
+        let mut state = VimState::new();
+        key(&mut state, &mut editor, "i");
+        let result = key(&mut state, &mut editor, "z");
+        assert_eq!(result, KeydownResult::NotHandled);
+    }
+}
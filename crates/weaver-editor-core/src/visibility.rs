@@ -213,6 +213,7 @@ mod tests {
             offset_map: vec![],
             syntax_spans,
             source_hash: 0,
+            code_language: None,
         }
     }
 
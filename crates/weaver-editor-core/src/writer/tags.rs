@@ -1500,10 +1500,18 @@ where
 
                     let syn_id = self.gen_syn_id();
 
+                    // Directives get their own class so the editor can
+                    // style them distinctly from attr blocks.
+                    let extra_class = if Self::is_directive_text(&self.weaver_block.buffer) {
+                        " md-directive"
+                    } else {
+                        ""
+                    };
+
                     write!(
                         &mut self.writer,
-                        "<span class=\"md-syntax-block\" data-syn-id=\"{}\" data-char-start=\"{}\" data-char-end=\"{}\" spellcheck=\"false\">",
-                        syn_id, char_start, char_end
+                        "<span class=\"md-syntax-block{}\" data-syn-id=\"{}\" data-char-start=\"{}\" data-char-end=\"{}\" spellcheck=\"false\">",
+                        extra_class, syn_id, char_start, char_end
                     )?;
                     escape_html(&mut self.writer, &syntax_text)?;
                     self.write("</span>")?;
@@ -1524,10 +1532,14 @@ where
                     self.last_byte_offset = range.end;
                 }
 
-                // Parse the buffered text for attrs and store for next block
-                if !self.weaver_block.buffer.is_empty() {
+                // Parse the buffered text for attrs and store for next block.
+                // `weaver:` directives are rendering instructions handled by
+                // the publish pipelines; in the editor they stay visible as
+                // syntax and must not restyle (or hide) the next block.
+                if !self.weaver_block.buffer.is_empty()
+                    && !Self::is_directive_text(&self.weaver_block.buffer)
+                {
                     let parsed = Self::parse_weaver_attrs(&self.weaver_block.buffer);
-                    self.weaver_block.buffer.clear();
                     // Merge with any existing pending attrs or set new
                     if let Some(ref mut existing) = self.weaver_block.pending_attrs {
                         existing.classes.extend(parsed.classes);
@@ -1536,6 +1548,7 @@ where
                         self.weaver_block.pending_attrs = Some(parsed);
                     }
                 }
+                self.weaver_block.buffer.clear();
 
                 Ok(())
             }
@@ -1634,6 +1647,12 @@ where
         Ok(())
     }
 
+    /// Whether weaver block text is a `weaver:` directive (handled by the
+    /// publish pipelines) rather than attributes for the next block.
+    fn is_directive_text(text: &str) -> bool {
+        text.trim_start().starts_with("weaver:")
+    }
+
     /// Parse weaver block text into attributes.
     fn parse_weaver_attrs(text: &str) -> markdown_weaver::WeaverAttributes<'static> {
         let mut classes = Vec::new();
@@ -3,9 +3,11 @@
 use std::cell::RefCell;
 use std::ops::Range;
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
 use loro::{
     cursor::{Cursor, PosType, Side},
+    undo::UndoItemMeta,
     LoroDoc, LoroText, UndoManager as LoroUndoManager, VersionVector,
 };
 use smol_str::{SmolStr, ToSmolStr};
@@ -18,7 +20,43 @@ use crate::CrdtError;
 struct LoroTextBufferInner {
     undo_mgr: LoroUndoManager,
     last_edit: Option<EditInfo>,
-    loro_cursor: Option<Cursor>,
+}
+
+/// Cursor state shared with the undo manager's callbacks.
+///
+/// Lives outside [`LoroTextBufferInner`] because the on-pop callback fires
+/// while `inner` is mutably borrowed during `undo()`/`redo()`; Loro's
+/// callback types also require `Send + Sync`, hence `Arc<Mutex<_>>` rather
+/// than the crate's usual `Rc<RefCell<_>>`.
+type SharedCursor = Arc<Mutex<Option<Cursor>>>;
+
+/// Build the Loro undo manager for a document.
+///
+/// Loro's `UndoManager` is selective: it only ever reverts operations
+/// made by this document's own peer, so undoing after importing remote
+/// updates preserves collaborators' text. The push/pop callbacks record
+/// the cursor with each undo step and restore it (transformed across any
+/// remote edits) when the step is undone or redone.
+fn make_undo_manager(doc: &LoroDoc, cursor: &SharedCursor) -> LoroUndoManager {
+    let undo_mgr = LoroUndoManager::new(doc);
+
+    let push_cursor = cursor.clone();
+    undo_mgr.set_on_push(Some(Box::new(move |_source, _span, _event| {
+        let mut meta = UndoItemMeta::new();
+        if let Some(c) = push_cursor.lock().unwrap().as_ref() {
+            meta.add_cursor(c);
+        }
+        meta
+    })));
+
+    let pop_cursor = cursor.clone();
+    undo_mgr.set_on_pop(Some(Box::new(move |_source, _span, meta| {
+        if let Some(with_pos) = meta.cursors.first() {
+            *pop_cursor.lock().unwrap() = Some(with_pos.cursor.clone());
+        }
+    })));
+
+    undo_mgr
 }
 
 /// Loro-backed text buffer with undo/redo support.
@@ -36,6 +74,7 @@ pub struct LoroTextBuffer {
     doc: LoroDoc,
     content: LoroText,
     inner: Rc<RefCell<LoroTextBufferInner>>,
+    loro_cursor: SharedCursor,
 }
 
 impl LoroTextBuffer {
@@ -43,16 +82,17 @@ impl LoroTextBuffer {
     pub fn new() -> Self {
         let doc = LoroDoc::new();
         let content = doc.get_text("content");
-        let loro_cursor = content.get_cursor(0, Side::default());
+        let loro_cursor: SharedCursor =
+            Arc::new(Mutex::new(content.get_cursor(0, Side::default())));
 
         Self {
             inner: Rc::new(RefCell::new(LoroTextBufferInner {
-                undo_mgr: LoroUndoManager::new(&doc),
+                undo_mgr: make_undo_manager(&doc, &loro_cursor),
                 last_edit: None,
-                loro_cursor,
             })),
             doc,
             content,
+            loro_cursor,
         }
     }
 
@@ -61,16 +101,17 @@ impl LoroTextBuffer {
         let doc = LoroDoc::new();
         doc.import(snapshot)?;
         let content = doc.get_text("content");
-        let loro_cursor = content.get_cursor(0, Side::default());
+        let loro_cursor: SharedCursor =
+            Arc::new(Mutex::new(content.get_cursor(0, Side::default())));
 
         Ok(Self {
             inner: Rc::new(RefCell::new(LoroTextBufferInner {
-                undo_mgr: LoroUndoManager::new(&doc),
+                undo_mgr: make_undo_manager(&doc, &loro_cursor),
                 last_edit: None,
-                loro_cursor,
             })),
             doc,
             content,
+            loro_cursor,
         })
     }
 
@@ -80,16 +121,17 @@ impl LoroTextBuffer {
     /// The doc is cloned (cheap - Arc-backed) so the buffer shares state with the original.
     pub fn from_doc(doc: LoroDoc, key: &str) -> Self {
         let content = doc.get_text(key);
-        let loro_cursor = content.get_cursor(0, Side::default());
+        let loro_cursor: SharedCursor =
+            Arc::new(Mutex::new(content.get_cursor(0, Side::default())));
 
         Self {
             inner: Rc::new(RefCell::new(LoroTextBufferInner {
-                undo_mgr: LoroUndoManager::new(&doc),
+                undo_mgr: make_undo_manager(&doc, &loro_cursor),
                 last_edit: None,
-                loro_cursor,
             })),
             doc,
             content,
+            loro_cursor,
         }
     }
 
@@ -150,27 +192,27 @@ impl LoroTextBuffer {
     /// Sync the Loro cursor to track a specific char offset.
     /// Call this after local edits where you know the new cursor position.
     pub fn sync_cursor(&self, offset: usize) {
-        self.inner.borrow_mut().loro_cursor = self.content.get_cursor(offset, Side::default());
+        *self.loro_cursor.lock().unwrap() = self.content.get_cursor(offset, Side::default());
     }
 
     /// Resolve the Loro cursor to its current char offset.
     /// Call this after undo/redo or remote edits where the position may have shifted.
     /// Returns None if no cursor is set or resolution fails.
     pub fn resolve_cursor(&self) -> Option<usize> {
-        let inner = self.inner.borrow();
-        let cursor = inner.loro_cursor.as_ref()?;
+        let guard = self.loro_cursor.lock().unwrap();
+        let cursor = guard.as_ref()?;
         let result = self.doc.get_cursor_pos(cursor).ok()?;
         Some(result.current.pos.min(self.content.len_unicode()))
     }
 
     /// Get a clone of the Loro cursor for serialization.
     pub fn loro_cursor(&self) -> Option<Cursor> {
-        self.inner.borrow().loro_cursor.clone()
+        self.loro_cursor.lock().unwrap().clone()
     }
 
     /// Set the Loro cursor (used when restoring from storage).
     pub fn set_loro_cursor(&self, cursor: Option<Cursor>) {
-        self.inner.borrow_mut().loro_cursor = cursor;
+        *self.loro_cursor.lock().unwrap() = cursor;
     }
 }
 
@@ -305,6 +347,10 @@ impl TextBuffer for LoroTextBuffer {
     }
 }
 
+/// Undo/redo backed by Loro's selective undo.
+///
+/// Only this peer's operations are ever reverted: undoing after a remote
+/// import removes local edits while collaborators' text stays in place.
 impl UndoManager for LoroTextBuffer {
     fn can_undo(&self) -> bool {
         self.inner.borrow().undo_mgr.can_undo()
@@ -315,15 +361,17 @@ impl UndoManager for LoroTextBuffer {
     }
 
     fn undo(&mut self) -> bool {
-        self.inner.borrow_mut().undo_mgr.undo().is_ok()
+        // `undo()` returns Ok(false) when there was nothing local to
+        // revert; report that as failure rather than a successful no-op.
+        self.inner.borrow_mut().undo_mgr.undo().unwrap_or(false)
     }
 
     fn redo(&mut self) -> bool {
-        self.inner.borrow_mut().undo_mgr.redo().is_ok()
+        self.inner.borrow_mut().undo_mgr.redo().unwrap_or(false)
     }
 
     fn clear_history(&mut self) {
-        self.inner.borrow_mut().undo_mgr = LoroUndoManager::new(&self.doc);
+        self.inner.borrow_mut().undo_mgr = make_undo_manager(&self.doc, &self.loro_cursor);
     }
 }
 
@@ -396,6 +444,44 @@ mod tests {
         assert_eq!(buffer2.last_edit().unwrap().inserted_len, 6);
     }
 
+    #[test]
+    fn test_undo_skips_remote_operations() {
+        let mut local = LoroTextBuffer::new();
+        local.insert(0, "local");
+
+        // A second peer edits concurrently and sends its update back.
+        let mut remote = LoroTextBuffer::from_snapshot(&local.export_snapshot()).unwrap();
+        remote.insert(5, " remote");
+        let update = remote.export_updates_since(&local.version()).unwrap();
+        local.import(&update).unwrap();
+        assert_eq!(local.to_string(), "local remote");
+
+        // Undo reverts only the local insert; the remote text survives.
+        assert!(local.undo());
+        assert_eq!(local.to_string(), " remote");
+
+        // Nothing local remains to revert.
+        assert!(!local.undo());
+        assert_eq!(local.to_string(), " remote");
+    }
+
+    #[test]
+    fn test_undo_restores_cursor_position() {
+        let mut buffer = LoroTextBuffer::new();
+        buffer.insert(0, "Hello");
+        // Commit so each edit is its own undo step.
+        buffer.doc().commit();
+
+        buffer.sync_cursor(5);
+        buffer.insert(5, "!");
+        buffer.sync_cursor(6);
+
+        assert!(buffer.undo());
+        assert_eq!(buffer.to_string(), "Hello");
+        // The caret lands back at the end of the surviving text.
+        assert_eq!(buffer.resolve_cursor(), Some(5));
+    }
+
     #[test]
     fn test_cursor_management() {
         let mut buffer = LoroTextBuffer::new();
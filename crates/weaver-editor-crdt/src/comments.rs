@@ -0,0 +1,379 @@
+//! Inline comment threads anchored to CRDT positions.
+//!
+//! Comments are `sh.weaver.edit.comment` records in each commenter's
+//! repo, discovered through Constellation backlinks on the document
+//! they reference — the same pattern the edit chain uses for diffs.
+//! The annotated range is stored as encoded Loro cursors rather than
+//! char offsets, so a comment keeps pointing at the same text while
+//! collaborators edit around (or inside) it; [`CommentAnchor::resolve`]
+//! maps it back to current offsets against the live buffer.
+//!
+//! Threading is flat: a reply carries a StrongRef to its thread root,
+//! and [`list_threads`] groups replies under roots in TID order. The
+//! `resolved` flag lives on the root record only.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use jacquard::IntoStatic;
+use jacquard::bytes::Bytes;
+use jacquard::cowstr::ToCowStr;
+use jacquard::prelude::*;
+use jacquard::smol_str::format_smolstr;
+use jacquard::types::string::{AtUri, Cid, Did};
+use loro::cursor::{Cursor, Side};
+use weaver_api::com_atproto::repo::strong_ref::StrongRef;
+use weaver_api::sh_weaver::edit::comment::Comment;
+use weaver_common::agent::WeaverExt;
+use weaver_editor_core::TextBuffer;
+
+use crate::CrdtError;
+use crate::buffer::LoroTextBuffer;
+use crate::sync::{
+    COMMENT_NSID, build_doc_ref, create_comment_record, find_comments_for_doc, get_current_did,
+    update_comment_record,
+};
+
+/// An annotated range in a document, stable across concurrent edits.
+///
+/// Wraps encoded Loro cursors, which survive serialization and resolve
+/// against any replica that has imported the relevant ops. An anchor
+/// without an end cursor is a point annotation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommentAnchor {
+    start: Bytes,
+    end: Option<Bytes>,
+}
+
+impl CommentAnchor {
+    /// Anchor a char range in the buffer.
+    ///
+    /// Returns `None` when the range is out of bounds. An empty range
+    /// produces a point annotation.
+    pub fn from_range(buffer: &LoroTextBuffer, range: Range<usize>) -> Option<Self> {
+        let start = buffer.content().get_cursor(range.start, Side::default())?;
+        let end = if range.end > range.start {
+            let cursor = buffer.content().get_cursor(range.end, Side::default())?;
+            Some(Bytes::from(cursor.encode()))
+        } else {
+            None
+        };
+        Some(Self {
+            start: Bytes::from(start.encode()),
+            end,
+        })
+    }
+
+    /// Anchor a single position (point annotation).
+    pub fn at(buffer: &LoroTextBuffer, offset: usize) -> Option<Self> {
+        Self::from_range(buffer, offset..offset)
+    }
+
+    /// Rebuild an anchor from stored cursor bytes (e.g. a fetched record).
+    pub fn from_bytes(start: Bytes, end: Option<Bytes>) -> Self {
+        Self { start, end }
+    }
+
+    /// Resolve the anchor to current char offsets in the buffer.
+    ///
+    /// Deleted anchor text collapses to the nearest surviving position
+    /// (Loro's cursor semantics), so this still returns a (possibly
+    /// empty) range; `None` means the bytes don't decode or reference
+    /// ops this replica has never seen.
+    pub fn resolve(&self, buffer: &LoroTextBuffer) -> Option<Range<usize>> {
+        let start = resolve_cursor_bytes(buffer, &self.start)?;
+        let end = match &self.end {
+            Some(bytes) => resolve_cursor_bytes(buffer, bytes)?,
+            None => start,
+        };
+        // Concurrent deletes can fold the end cursor past the start.
+        Some(start..end.max(start))
+    }
+}
+
+/// Decode and resolve a single encoded cursor.
+fn resolve_cursor_bytes(buffer: &LoroTextBuffer, encoded: &[u8]) -> Option<usize> {
+    let cursor = Cursor::decode(encoded).ok()?;
+    let result = buffer.doc().get_cursor_pos(&cursor).ok()?;
+    Some(result.current.pos.min(buffer.len_chars()))
+}
+
+/// A fetched comment record.
+#[derive(Clone, Debug)]
+pub struct CommentView {
+    /// URI of the comment record.
+    pub uri: AtUri<'static>,
+    /// DID of the author — the repo the record lives in.
+    pub author: Did<'static>,
+    /// Comment text.
+    pub body: String,
+    /// The annotated range, resolvable against the live buffer.
+    pub anchor: CommentAnchor,
+    /// Whether this comment is marked resolved (thread roots only).
+    pub resolved: bool,
+    /// When the comment was created, if recorded.
+    pub created_at: Option<String>,
+    /// URI of the thread root this comment replies to.
+    pub thread: Option<AtUri<'static>>,
+}
+
+/// A comment thread: root comment plus replies, oldest first.
+#[derive(Clone, Debug)]
+pub struct CommentThread {
+    /// The comment that anchors the discussion.
+    pub root: CommentView,
+    /// Replies in chronological order (rkeys are TIDs).
+    pub replies: Vec<CommentView>,
+}
+
+impl CommentThread {
+    /// Whether the thread has been marked resolved.
+    pub fn is_resolved(&self) -> bool {
+        self.root.resolved
+    }
+}
+
+/// Create a comment record anchored to a document.
+///
+/// `thread` makes this a reply to an existing thread root; pass the
+/// root's anchor along with it, since the lexicon requires one on every
+/// comment. The record lands in the current session's repo, and the doc
+/// reference follows the same entry/draft split as the edit chain.
+pub async fn add_comment<C>(
+    client: &C,
+    body: &str,
+    anchor: &CommentAnchor,
+    thread: Option<&StrongRef<'_>>,
+    draft_key: &str,
+    entry_uri: Option<&AtUri<'_>>,
+    entry_cid: Option<&Cid<'_>>,
+) -> Result<(AtUri<'static>, Cid<'static>), CrdtError>
+where
+    C: XrpcClient + IdentityResolver + AgentSession,
+{
+    let did = get_current_did(client).await?;
+    let doc_ref = build_doc_ref(&did, draft_key, entry_uri, entry_cid);
+
+    let comment = Comment::new()
+        .doc(doc_ref)
+        .body(body.to_cowstr().into_static())
+        .anchor_start(anchor.start.clone())
+        .maybe_anchor_end(anchor.end.clone())
+        .maybe_thread(thread.map(|t| t.clone().into_static()))
+        .created_at(jacquard::types::datetime::Datetime::now())
+        .build();
+
+    create_comment_record(client, &comment).await
+}
+
+/// Mark a thread root resolved, or reopen it.
+///
+/// Rewrites the root record in place. Only the thread author can do
+/// this — the PDS rejects writes to other repos — which matches who
+/// owns the question being answered.
+pub async fn resolve_thread<C>(
+    client: &C,
+    root_uri: &AtUri<'_>,
+    resolved: bool,
+) -> Result<(), CrdtError>
+where
+    C: WeaverExt,
+{
+    let response = client
+        .get_record::<Comment>(root_uri)
+        .await
+        .map_err(|e| CrdtError::Xrpc(format!("fetch comment: {}", e)))?;
+
+    let output = response
+        .into_output()
+        .map_err(|e| CrdtError::Xrpc(format!("parse comment: {}", e)))?;
+
+    let mut comment = output.value.into_static();
+    comment.resolved = Some(resolved);
+
+    update_comment_record(client, root_uri, &comment).await
+}
+
+/// List comment threads for a document.
+///
+/// Fetches every comment referencing the document via Constellation
+/// backlinks, then groups replies under their roots. Replies whose root
+/// record has been deleted are dropped. Threads and replies come back
+/// oldest first.
+pub async fn list_threads<C>(
+    client: &C,
+    doc_uri: &AtUri<'_>,
+    draft: bool,
+) -> Result<Vec<CommentThread>, CrdtError>
+where
+    C: WeaverExt,
+{
+    let comment_ids = find_comments_for_doc(client, doc_uri, draft).await?;
+
+    let mut views: Vec<(String, CommentView)> = Vec::new();
+    for comment_id in &comment_ids {
+        let rkey_str: &str = comment_id.rkey.as_ref();
+
+        let comment_uri = AtUri::new(&format_smolstr!(
+            "at://{}/{}/{}",
+            comment_id.did,
+            COMMENT_NSID,
+            rkey_str
+        ))
+        .map_err(|e| CrdtError::InvalidUri(format!("comment URI: {}", e)))?
+        .into_static();
+
+        let response = client
+            .get_record::<Comment>(&comment_uri)
+            .await
+            .map_err(|e| CrdtError::Xrpc(format!("fetch comment: {}", e)))?;
+
+        let output = match response.into_output() {
+            Ok(o) => o,
+            Err(e) => {
+                // Constellation backlinks can outlive a deleted record
+                // for a while; skip rather than fail the whole listing.
+                tracing::warn!("Failed to fetch comment {}: {}", comment_uri, e);
+                continue;
+            }
+        };
+
+        let value = output.value.into_static();
+        views.push((
+            rkey_str.to_string(),
+            CommentView {
+                uri: comment_uri,
+                author: comment_id.did.clone(),
+                body: value.body.to_string(),
+                anchor: CommentAnchor::from_bytes(value.anchor_start, value.anchor_end),
+                resolved: value.resolved.unwrap_or(false),
+                created_at: value.created_at.map(|d| d.to_string()),
+                thread: value.thread.map(|t| t.uri.into_static()),
+            },
+        ));
+    }
+
+    // Rkeys are TIDs, so sorting by rkey yields chronological order even
+    // across repos (modulo clock skew, which TIDs already accept).
+    views.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(group_threads(views.into_iter().map(|(_, v)| v).collect()))
+}
+
+/// Group a chronologically ordered flat list of comments into threads.
+fn group_threads(views: Vec<CommentView>) -> Vec<CommentThread> {
+    let mut threads: Vec<CommentThread> = Vec::new();
+    let mut root_index: HashMap<AtUri<'static>, usize> = HashMap::new();
+
+    for view in views {
+        match &view.thread {
+            None => {
+                root_index.insert(view.uri.clone(), threads.len());
+                threads.push(CommentThread {
+                    root: view,
+                    replies: Vec::new(),
+                });
+            }
+            Some(root_uri) => match root_index.get(root_uri) {
+                Some(&idx) => threads[idx].replies.push(view),
+                None => {
+                    // Root deleted (or not yet indexed); an orphan reply
+                    // has no anchor context worth showing.
+                    tracing::warn!("Dropping reply {} with missing root {}", view.uri, root_uri);
+                }
+            },
+        }
+    }
+
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view(uri: &str, thread: Option<&str>) -> CommentView {
+        CommentView {
+            uri: AtUri::new(uri).unwrap().into_static(),
+            author: Did::new_static("did:plc:alice").unwrap(),
+            body: "note".to_string(),
+            anchor: CommentAnchor::from_bytes(Bytes::new(), None),
+            resolved: false,
+            created_at: None,
+            thread: thread.map(|t| AtUri::new(t).unwrap().into_static()),
+        }
+    }
+
+    #[test]
+    fn test_anchor_survives_edits_before_it() {
+        let mut buffer = LoroTextBuffer::new();
+        buffer.insert(0, "Hello World");
+
+        let anchor = CommentAnchor::from_range(&buffer, 6..11).unwrap();
+        assert_eq!(anchor.resolve(&buffer), Some(6..11));
+
+        // An insertion before the anchored text shifts it intact.
+        buffer.insert(0, ">> ");
+        assert_eq!(anchor.resolve(&buffer), Some(9..14));
+    }
+
+    #[test]
+    fn test_anchor_survives_concurrent_edits() {
+        let mut local = LoroTextBuffer::new();
+        local.insert(0, "Hello World");
+        let anchor = CommentAnchor::from_range(&local, 6..11).unwrap();
+
+        // A second peer inserts before the anchor and syncs back.
+        let mut remote = LoroTextBuffer::from_snapshot(&local.export_snapshot()).unwrap();
+        remote.insert(5, " there");
+        let update = remote.export_updates_since(&local.version()).unwrap();
+        local.import(&update).unwrap();
+
+        assert_eq!(local.to_string(), "Hello there World");
+        assert_eq!(anchor.resolve(&local), Some(12..17));
+    }
+
+    #[test]
+    fn test_point_anchor_and_bounds() {
+        let mut buffer = LoroTextBuffer::new();
+        buffer.insert(0, "abc");
+
+        let point = CommentAnchor::at(&buffer, 2).unwrap();
+        assert_eq!(point.resolve(&buffer), Some(2..2));
+
+        assert!(CommentAnchor::from_range(&buffer, 0..100).is_none());
+    }
+
+    #[test]
+    fn test_anchor_roundtrips_through_bytes() {
+        let mut buffer = LoroTextBuffer::new();
+        buffer.insert(0, "Hello World");
+
+        let anchor = CommentAnchor::from_range(&buffer, 0..5).unwrap();
+        let restored = CommentAnchor::from_bytes(anchor.start.clone(), anchor.end.clone());
+        assert_eq!(restored.resolve(&buffer), Some(0..5));
+    }
+
+    #[test]
+    fn test_group_threads_attaches_replies_and_drops_orphans() {
+        let root = "at://did:plc:alice/sh.weaver.edit.comment/3laaaaaaaaa2a";
+        let threads = group_threads(vec![
+            view(root, None),
+            view(
+                "at://did:plc:bob/sh.weaver.edit.comment/3laaaaaaaab2a",
+                Some(root),
+            ),
+            view(
+                "at://did:plc:carol/sh.weaver.edit.comment/3laaaaaaaac2a",
+                Some("at://did:plc:gone/sh.weaver.edit.comment/3laaaaaaaad2a"),
+            ),
+        ]);
+
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].replies.len(), 1);
+        assert_eq!(
+            threads[0].replies[0].uri.as_ref(),
+            "at://did:plc:bob/sh.weaver.edit.comment/3laaaaaaaab2a"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! Compaction of long diff chains into fresh root snapshots.
+//!
+//! A document edited for months accumulates hundreds of diff records, and
+//! loading it means fetching and replaying every one. Since a root's
+//! snapshot already encodes the complete CRDT history, the chain can be
+//! collapsed at any point: export a new snapshot, publish it as a new
+//! `sh.weaver.edit.root`, and tombstone the superseded records. Loro
+//! snapshots preserve every operation, so [`load_history`](crate::load_history)
+//! still works after compaction — only the per-record checkpoints between
+//! the old root and the new one are lost.
+//!
+//! Compaction only ever touches the current account's repo. Collaborators
+//! keep their own roots and diff chains; they discover the new root
+//! through the same entry backlinks as the old one and rebuild from its
+//! snapshot, which contains their operations too (the local doc merged
+//! them before exporting).
+//!
+//! The remaining hazard is another session of the *same* account still
+//! appending diffs to the old chain. There is no lock to take on a PDS,
+//! so [`compact_edit_chain`] re-lists the old chain after publishing the
+//! new root and backs out if anything new appeared; see
+//! [`CompactionOutcome::Deferred`].
+
+use std::collections::HashSet;
+
+use jacquard::IntoStatic;
+use jacquard::prelude::*;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::string::{AtUri, Cid};
+use weaver_api::com_atproto::repo::delete_record::DeleteRecord;
+use weaver_api::com_atproto::repo::strong_ref::StrongRef;
+use weaver_common::constellation::RecordId;
+
+use crate::CrdtError;
+use crate::document::CrdtDocument;
+use crate::sync::{create_edit_root, find_diffs_for_root};
+
+/// Configuration for diff-chain compaction.
+#[derive(Clone, Copy, Debug)]
+pub struct CompactionConfig {
+    /// Number of diff records on the chain that triggers compaction.
+    ///
+    /// Chains at or below this length are left alone.
+    pub max_diffs: usize,
+}
+
+impl Default for CompactionConfig {
+    fn default() -> Self {
+        // Roughly a second of fetches on a cold load; long enough that
+        // compaction stays rare, short enough that loads stay snappy.
+        Self { max_diffs: 128 }
+    }
+}
+
+/// Outcome of a compaction attempt.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum CompactionOutcome {
+    /// The chain is at or below the threshold; nothing was changed.
+    NotNeeded {
+        /// Diff records currently on the chain.
+        diff_count: usize,
+    },
+    /// Another session of this account appended diffs while the new root
+    /// was being published. The new root was deleted again and the old
+    /// chain left intact; retry once that session has synced or gone
+    /// idle.
+    Deferred,
+    /// The chain was collapsed into a fresh root and the document now
+    /// points at it.
+    Compacted {
+        /// The new root record.
+        new_root: StrongRef<'static>,
+        /// Superseded records (old root plus diffs) that were deleted.
+        tombstoned: usize,
+        /// Superseded records whose deletion failed. These are orphans —
+        /// unreachable once the old root is gone — so failures are
+        /// logged and tolerated rather than retried.
+        delete_failures: usize,
+    },
+}
+
+/// Collapse the document's diff chain into a new root record.
+///
+/// When the chain behind the document's current edit root exceeds
+/// `config.max_diffs`, this snapshots the local doc into a new
+/// `sh.weaver.edit.root`, repoints the document's sync state at it (so
+/// the next sync writes diffs against the new root), and deletes the old
+/// root and its diffs. The old root is deleted before its diffs so that
+/// a concurrent reader either finds the complete old chain or only the
+/// new root, never a root with holes in its chain.
+///
+/// Pending local edits are included in the snapshot, so the document is
+/// marked synced on success. Call this after a successful sync — e.g.
+/// whenever a sync returns [`SyncResult::CreatedDiff`](crate::SyncResult::CreatedDiff)
+/// — rather than on a timer, so the chain length only needs checking
+/// when it has actually grown.
+pub async fn compact_edit_chain<C, D>(
+    client: &C,
+    doc: &mut D,
+    config: &CompactionConfig,
+    draft_key: &str,
+    entry_uri: Option<&AtUri<'_>>,
+    entry_cid: Option<&Cid<'_>>,
+) -> Result<CompactionOutcome, CrdtError>
+where
+    C: XrpcClient + IdentityResolver + AgentSession,
+    D: CrdtDocument,
+{
+    let old_root = doc
+        .edit_root()
+        .ok_or_else(|| CrdtError::Sync("cannot compact without an edit root".into()))?;
+
+    let diffs_before = find_diffs_for_root(client, &old_root.uri).await?;
+
+    if diffs_before.len() <= config.max_diffs {
+        return Ok(CompactionOutcome::NotNeeded {
+            diff_count: diffs_before.len(),
+        });
+    }
+
+    // Publish the replacement root first; until the old one is deleted
+    // both are discoverable and either loads to the same state.
+    let result = create_edit_root(client, doc, draft_key, entry_uri, entry_cid).await?;
+
+    let new_root = StrongRef::new()
+        .uri(result.root_uri.clone())
+        .cid(result.root_cid.clone())
+        .build();
+
+    // Re-list the old chain. Anything that appeared since the first
+    // listing is a concurrent writer on this account whose diffs the new
+    // snapshot does not contain — deleting the old chain now would drop
+    // their edits, so back out instead.
+    let diffs_after = find_diffs_for_root(client, &old_root.uri).await?;
+    if has_new_records(&diffs_before, &diffs_after) {
+        tracing::info!(
+            "compaction deferred: old chain grew from {} to {} diffs mid-compaction",
+            diffs_before.len(),
+            diffs_after.len()
+        );
+        if let Err(e) = delete_by_uri(client, &new_root.uri).await {
+            tracing::warn!("failed to delete abandoned compaction root: {}", e);
+        }
+        return Ok(CompactionOutcome::Deferred);
+    }
+
+    // Repoint before tombstoning: from here on the old chain is
+    // redundant, and the next sync must diff against the new root.
+    doc.set_edit_root(Some(new_root.clone()));
+    doc.set_last_diff(None);
+    doc.mark_synced();
+
+    // Old root first — once it is gone the chain is undiscoverable and
+    // leftover diffs are harmless orphans if any deletion below fails.
+    let mut tombstoned = 0;
+    let mut delete_failures = 0;
+    match delete_by_uri(client, &old_root.uri).await {
+        Ok(()) => tombstoned += 1,
+        Err(e) => {
+            tracing::warn!("failed to delete superseded root {}: {}", old_root.uri, e);
+            delete_failures += 1;
+        }
+    }
+
+    for diff_id in &diffs_before {
+        match delete_record_id(client, diff_id).await {
+            Ok(()) => tombstoned += 1,
+            Err(e) => {
+                tracing::warn!("failed to delete superseded diff {}: {}", diff_id.rkey, e);
+                delete_failures += 1;
+            }
+        }
+    }
+
+    Ok(CompactionOutcome::Compacted {
+        new_root,
+        tombstoned,
+        delete_failures,
+    })
+}
+
+/// Whether `after` contains any record not present in `before`.
+fn has_new_records(before: &[RecordId<'_>], after: &[RecordId<'_>]) -> bool {
+    let seen: HashSet<(&str, &str)> = before
+        .iter()
+        .map(|id| (id.did.as_ref(), id.rkey.as_ref()))
+        .collect();
+    after
+        .iter()
+        .any(|id| !seen.contains(&(id.did.as_ref(), id.rkey.as_ref())))
+}
+
+/// Delete a record in the current account's repo by its AT-URI.
+async fn delete_by_uri<C>(client: &C, uri: &AtUri<'_>) -> Result<(), CrdtError>
+where
+    C: XrpcClient + AgentSession,
+{
+    let (collection, rkey) = match (uri.collection(), uri.rkey()) {
+        (Some(c), Some(r)) => (c.clone().into_static(), r.clone().into_static()),
+        _ => {
+            return Err(CrdtError::InvalidUri(format!(
+                "cannot delete record without collection and rkey: {}",
+                uri
+            )));
+        }
+    };
+
+    let did = crate::sync::get_current_did(client).await?;
+
+    let request = DeleteRecord::new()
+        .repo(AtIdentifier::Did(did))
+        .collection(collection)
+        .rkey(rkey)
+        .build();
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    response
+        .into_output()
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Delete a record in the current account's repo by its record ID.
+async fn delete_record_id<C>(client: &C, id: &RecordId<'_>) -> Result<(), CrdtError>
+where
+    C: XrpcClient + AgentSession,
+{
+    let did = crate::sync::get_current_did(client).await?;
+
+    let request = DeleteRecord::new()
+        .repo(AtIdentifier::Did(did))
+        .collection(id.collection.clone().into_static())
+        .rkey(id.rkey.clone().into_static())
+        .build();
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    response
+        .into_output()
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jacquard::types::recordkey::RecordKey;
+    use jacquard::types::string::{Did, Nsid};
+
+    fn record(did: &'static str, rkey: &'static str) -> RecordId<'static> {
+        RecordId {
+            did: Did::new_static(did).unwrap(),
+            collection: Nsid::raw(crate::sync::DIFF_NSID).into_static(),
+            rkey: RecordKey::any(rkey).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_has_new_records_detects_growth() {
+        let before = vec![record("did:plc:alice", "3laaaaaaaaa2a")];
+        let after = vec![
+            record("did:plc:alice", "3laaaaaaaaa2a"),
+            record("did:plc:alice", "3laaaaaaaab2a"),
+        ];
+        assert!(has_new_records(&before, &after));
+    }
+
+    #[test]
+    fn test_has_new_records_ignores_unchanged_or_shrunk_chains() {
+        let a = record("did:plc:alice", "3laaaaaaaaa2a");
+        let b = record("did:plc:alice", "3laaaaaaaab2a");
+        // Identical listings.
+        assert!(!has_new_records(
+            std::slice::from_ref(&a),
+            std::slice::from_ref(&a)
+        ));
+        // A record disappearing (e.g. a concurrent compaction already
+        // tombstoned it) is not a reason to back out.
+        assert!(!has_new_records(&[a.clone(), b], &[a]));
+    }
+}
@@ -3,7 +3,10 @@
 //! Provides shared types for collab coordination that can be used by both
 //! Rust UI frameworks (Dioxus) and JS bindings.
 
+use std::collections::{BTreeSet, VecDeque};
+
 use smol_str::SmolStr;
+use thiserror::Error;
 
 /// Session record TTL in minutes.
 pub const SESSION_TTL_MINUTES: u32 = 15;
@@ -14,6 +17,24 @@ pub const SESSION_REFRESH_INTERVAL_MS: u32 = 5 * 60 * 1000; // 5 minutes
 /// How often to poll for new peers (ms).
 pub const PEER_DISCOVERY_INTERVAL_MS: u32 = 30 * 1000; // 30 seconds
 
+/// Maximum concurrent collab sessions a single DID may advertise.
+///
+/// The index enforces the same cap when serving peer discovery, so sessions
+/// beyond this are invisible to other participants anyway.
+pub const MAX_SESSIONS_PER_DID: usize = 4;
+
+/// Session record creations allowed within [`SESSION_CREATE_WINDOW_MS`].
+pub const SESSION_CREATE_LIMIT: usize = 20;
+
+/// Window for [`SESSION_CREATE_LIMIT`] (one hour).
+pub const SESSION_CREATE_WINDOW_MS: u64 = 60 * 60 * 1000;
+
+/// Edit-diff record creations allowed within [`DIFF_CREATE_WINDOW_MS`].
+pub const DIFF_CREATE_LIMIT: usize = 30;
+
+/// Window for [`DIFF_CREATE_LIMIT`] (one minute).
+pub const DIFF_CREATE_WINDOW_MS: u64 = 60 * 1000;
+
 /// Coordinator state machine states.
 ///
 /// Tracks the lifecycle of a collab session from initialization through
@@ -72,6 +93,214 @@ impl CoordinatorState {
     }
 }
 
+/// Tracks which collaborator DIDs are currently authorized for a session.
+///
+/// Peer discovery filters out unauthorized session records, but a peer that
+/// already joined the gossip swarm keeps streaming ops after its invite is
+/// revoked server-side. The coordinator re-validates invite+accept pairs on
+/// the session refresh interval and feeds the result here; message handling
+/// consults [`is_authorized`](Self::is_authorized) before admitting remote
+/// ops.
+///
+/// Until the first validation pass completes the tracker admits everyone, so
+/// Constellation latency at startup cannot drop ops from legitimate peers.
+/// Admission at connect time is still gated by peer discovery filtering.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PeerAuthorization {
+    /// DIDs with a valid invite+accept pair (plus the resource owner).
+    authorized: BTreeSet<SmolStr>,
+    /// DIDs whose authorization lapsed after having been validated.
+    revoked: BTreeSet<SmolStr>,
+    /// Whether at least one validation pass has completed.
+    validated: bool,
+}
+
+impl PeerAuthorization {
+    /// Create a tracker that admits everyone until the first refresh.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the authorized set with a fresh validation result.
+    ///
+    /// DIDs that were authorized before but are missing from the new list
+    /// are flagged as revoked; reappearing in a later refresh clears the
+    /// flag. Returns the DIDs newly revoked by this refresh so callers can
+    /// surface them.
+    pub fn refresh(&mut self, dids: impl IntoIterator<Item = SmolStr>) -> Vec<SmolStr> {
+        let next: BTreeSet<SmolStr> = dids.into_iter().collect();
+        let newly_revoked: Vec<SmolStr> = self
+            .authorized
+            .iter()
+            .filter(|did| !next.contains(*did))
+            .cloned()
+            .collect();
+        self.revoked.extend(newly_revoked.iter().cloned());
+        for did in &next {
+            self.revoked.remove(did);
+        }
+        self.authorized = next;
+        self.validated = true;
+        newly_revoked
+    }
+
+    /// Whether ops from this DID should be admitted.
+    pub fn is_authorized(&self, did: &str) -> bool {
+        !self.validated || self.authorized.contains(did)
+    }
+
+    /// Whether this DID was authorized at some point and later revoked.
+    pub fn is_revoked(&self, did: &str) -> bool {
+        self.revoked.contains(did)
+    }
+}
+
+/// Why a quota check refused to record an event.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QuotaExceeded {
+    /// Too many concurrent sessions advertised for this DID.
+    #[error("too many concurrent collab sessions (limit {limit})")]
+    ConcurrentSessions {
+        /// The concurrent session cap that was hit.
+        limit: usize,
+    },
+    /// Session records are being created too quickly.
+    #[error("session creation rate limit reached, retry in {retry_after_ms}ms")]
+    SessionCreateRate {
+        /// Millis until the next creation would be admitted.
+        retry_after_ms: u64,
+    },
+    /// Edit-diff records are being created too quickly.
+    #[error("edit diff rate limit reached, retry in {retry_after_ms}ms")]
+    DiffCreateRate {
+        /// Millis until the next creation would be admitted.
+        retry_after_ms: u64,
+    },
+}
+
+/// Sliding-window event counter backing [`CollabQuota`].
+///
+/// Callers pass the current time explicitly (unix millis) so the window
+/// works identically on native and wasm and stays deterministic in tests.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateWindow {
+    max_events: usize,
+    window_ms: u64,
+    /// Timestamps of counted events, oldest first.
+    events: VecDeque<u64>,
+}
+
+impl RateWindow {
+    /// Create a window admitting at most `max_events` per `window_ms`.
+    pub fn new(max_events: usize, window_ms: u64) -> Self {
+        Self {
+            max_events,
+            window_ms,
+            events: VecDeque::new(),
+        }
+    }
+
+    /// Drop events that have aged out of the window.
+    fn prune(&mut self, now_ms: u64) {
+        while let Some(&oldest) = self.events.front() {
+            if oldest + self.window_ms <= now_ms {
+                self.events.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Record an event if the window has capacity.
+    ///
+    /// Returns false without recording when the limit is reached, so a
+    /// refused attempt does not extend the throttle.
+    pub fn try_acquire(&mut self, now_ms: u64) -> bool {
+        self.prune(now_ms);
+        if self.events.len() >= self.max_events {
+            return false;
+        }
+        self.events.push_back(now_ms);
+        true
+    }
+
+    /// Millis until the oldest counted event leaves the window.
+    ///
+    /// Returns 0 when the window has capacity right now.
+    pub fn retry_after_ms(&mut self, now_ms: u64) -> u64 {
+        self.prune(now_ms);
+        if self.events.len() < self.max_events {
+            return 0;
+        }
+        self.events
+            .front()
+            .map(|oldest| (oldest + self.window_ms).saturating_sub(now_ms))
+            .unwrap_or(0)
+    }
+}
+
+/// Client-side usage quota for collab record creation.
+///
+/// A stuck reconnect loop or runaway autosync can flood a PDS repo with
+/// session and diff records faster than TTL cleanup removes them. The
+/// coordinator consults this before every record creation; the index
+/// enforces matching limits server-side, so staying under them here keeps
+/// well-behaved clients from ever hitting a server refusal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollabQuota {
+    session_creates: RateWindow,
+    diff_creates: RateWindow,
+}
+
+impl Default for CollabQuota {
+    fn default() -> Self {
+        Self {
+            session_creates: RateWindow::new(SESSION_CREATE_LIMIT, SESSION_CREATE_WINDOW_MS),
+            diff_creates: RateWindow::new(DIFF_CREATE_LIMIT, DIFF_CREATE_WINDOW_MS),
+        }
+    }
+}
+
+impl CollabQuota {
+    /// Create a quota with the default limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check and record a session record creation.
+    ///
+    /// `active_sessions` is the number of live sessions this DID already
+    /// advertises (0 when unknown; the index backstops the concurrent cap).
+    pub fn try_create_session(
+        &mut self,
+        now_ms: u64,
+        active_sessions: usize,
+    ) -> Result<(), QuotaExceeded> {
+        if active_sessions >= MAX_SESSIONS_PER_DID {
+            return Err(QuotaExceeded::ConcurrentSessions {
+                limit: MAX_SESSIONS_PER_DID,
+            });
+        }
+        if !self.session_creates.try_acquire(now_ms) {
+            return Err(QuotaExceeded::SessionCreateRate {
+                retry_after_ms: self.session_creates.retry_after_ms(now_ms),
+            });
+        }
+        Ok(())
+    }
+
+    /// Check and record an edit-diff record creation.
+    pub fn try_create_diff(&mut self, now_ms: u64) -> Result<(), QuotaExceeded> {
+        if !self.diff_creates.try_acquire(now_ms) {
+            return Err(QuotaExceeded::DiffCreateRate {
+                retry_after_ms: self.diff_creates.retry_after_ms(now_ms),
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Compute the gossip topic hash for a resource URI.
 ///
 /// The topic is a blake3 hash of the resource URI bytes, used to identify
@@ -99,10 +328,126 @@ mod tests {
     #[test]
     fn test_coordinator_state_is_active() {
         assert!(!CoordinatorState::Initializing.is_active());
-        assert!(CoordinatorState::Active {
-            session_uri: "at://test".into()
+        assert!(
+            CoordinatorState::Active {
+                session_uri: "at://test".into()
+            }
+            .is_active()
+        );
+    }
+
+    #[test]
+    fn test_peer_authorization_admits_all_before_first_refresh() {
+        let auth = PeerAuthorization::new();
+        assert!(auth.is_authorized("did:plc:anyone"));
+        assert!(!auth.is_revoked("did:plc:anyone"));
+    }
+
+    #[test]
+    fn test_peer_authorization_refresh_gates_unknown_dids() {
+        let mut auth = PeerAuthorization::new();
+        let newly_revoked = auth.refresh(vec!["did:plc:alice".into()]);
+        assert!(newly_revoked.is_empty());
+        assert!(auth.is_authorized("did:plc:alice"));
+        assert!(!auth.is_authorized("did:plc:mallory"));
+        // Never-authorized DIDs are denied but not flagged as revoked.
+        assert!(!auth.is_revoked("did:plc:mallory"));
+    }
+
+    #[test]
+    fn test_peer_authorization_revocation_flags_lapsed_dids() {
+        let mut auth = PeerAuthorization::new();
+        auth.refresh(vec!["did:plc:alice".into(), "did:plc:bob".into()]);
+        let newly_revoked = auth.refresh(vec!["did:plc:alice".into()]);
+        assert_eq!(newly_revoked, vec![SmolStr::from("did:plc:bob")]);
+        assert!(!auth.is_authorized("did:plc:bob"));
+        assert!(auth.is_revoked("did:plc:bob"));
+        assert!(auth.is_authorized("did:plc:alice"));
+    }
+
+    #[test]
+    fn test_peer_authorization_reauthorization_clears_revoked_flag() {
+        let mut auth = PeerAuthorization::new();
+        auth.refresh(vec!["did:plc:bob".into()]);
+        auth.refresh(Vec::<SmolStr>::new());
+        assert!(auth.is_revoked("did:plc:bob"));
+        let newly_revoked = auth.refresh(vec!["did:plc:bob".into()]);
+        assert!(newly_revoked.is_empty());
+        assert!(auth.is_authorized("did:plc:bob"));
+        assert!(!auth.is_revoked("did:plc:bob"));
+    }
+
+    #[test]
+    fn test_rate_window_admits_until_limit() {
+        let mut window = RateWindow::new(2, 1000);
+        assert!(window.try_acquire(0));
+        assert!(window.try_acquire(100));
+        assert!(!window.try_acquire(200));
+        assert_eq!(window.retry_after_ms(200), 800);
+    }
+
+    #[test]
+    fn test_rate_window_refills_as_events_age_out() {
+        let mut window = RateWindow::new(1, 1000);
+        assert!(window.try_acquire(0));
+        assert!(!window.try_acquire(999));
+        assert!(window.try_acquire(1000));
+    }
+
+    #[test]
+    fn test_rate_window_refused_attempts_do_not_extend_throttle() {
+        let mut window = RateWindow::new(1, 1000);
+        assert!(window.try_acquire(0));
+        // Hammering while limited must not push the refill time out.
+        assert!(!window.try_acquire(500));
+        assert!(!window.try_acquire(900));
+        assert!(window.try_acquire(1000));
+    }
+
+    #[test]
+    fn test_quota_concurrent_session_cap() {
+        let mut quota = CollabQuota::new();
+        assert_eq!(
+            quota.try_create_session(0, MAX_SESSIONS_PER_DID),
+            Err(QuotaExceeded::ConcurrentSessions {
+                limit: MAX_SESSIONS_PER_DID
+            })
+        );
+        assert!(
+            quota
+                .try_create_session(0, MAX_SESSIONS_PER_DID - 1)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_quota_session_create_rate() {
+        let mut quota = CollabQuota::new();
+        for i in 0..SESSION_CREATE_LIMIT {
+            assert!(quota.try_create_session(i as u64, 0).is_ok());
+        }
+        match quota.try_create_session(SESSION_CREATE_LIMIT as u64, 0) {
+            Err(QuotaExceeded::SessionCreateRate { retry_after_ms }) => {
+                assert!(retry_after_ms > 0);
+            }
+            other => panic!("expected session rate refusal, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_quota_diff_create_rate() {
+        let mut quota = CollabQuota::new();
+        for i in 0..DIFF_CREATE_LIMIT {
+            assert!(quota.try_create_diff(i as u64).is_ok());
+        }
+        match quota.try_create_diff(DIFF_CREATE_LIMIT as u64) {
+            Err(QuotaExceeded::DiffCreateRate { retry_after_ms }) => {
+                assert!(retry_after_ms > 0);
+            }
+            other => panic!("expected diff rate refusal, got {:?}", other),
         }
-        .is_active());
+        // Sessions and diffs are counted independently.
+        assert!(quota.try_create_session(0, 0).is_ok());
     }
 
     #[test]
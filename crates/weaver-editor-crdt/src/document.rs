@@ -16,6 +16,12 @@ pub struct SyncState {
 
     /// Version vector at the time of last sync.
     pub last_synced_version: Option<VersionVector>,
+
+    /// Number of updates waiting in the offline queue.
+    ///
+    /// Non-zero means edits are persisted locally but not yet on the PDS;
+    /// UIs can surface this as an "offline, N pending" indicator.
+    pub queued_diffs: usize,
 }
 
 impl SyncState {
@@ -65,6 +71,43 @@ pub trait CrdtDocument {
 
     /// Check if there are changes since last sync.
     fn has_unsynced_changes(&self) -> bool;
+
+    /// Number of updates waiting in the offline queue.
+    ///
+    /// Defaults to zero for implementors that do not queue offline edits.
+    fn queued_diffs(&self) -> usize {
+        0
+    }
+
+    /// Record the offline queue depth (see [`SyncState::queued_diffs`]).
+    ///
+    /// Default is a no-op for implementors that do not queue offline edits.
+    fn set_queued_diffs(&mut self, _depth: usize) {}
+
+    /// Export a portable snapshot of this document with metadata.
+    ///
+    /// See [`DocumentSnapshot`](crate::DocumentSnapshot) for the backup
+    /// and transfer semantics.
+    fn export_document_snapshot(&self) -> crate::DocumentSnapshot
+    where
+        Self: Sized,
+    {
+        crate::DocumentSnapshot::capture(self)
+    }
+
+    /// Merge a portable snapshot's operations into this document.
+    ///
+    /// Sync pointers stay untouched; the snapshot's operations merge
+    /// through the normal CRDT import path.
+    fn import_document_snapshot(
+        &mut self,
+        snapshot: &crate::DocumentSnapshot,
+    ) -> Result<(), crate::CrdtError>
+    where
+        Self: Sized,
+    {
+        snapshot.apply_to(self)
+    }
 }
 
 // Blanket implementation for LoroTextBuffer with embedded SyncState
@@ -156,4 +199,12 @@ impl CrdtDocument for SimpleCrdtDocument {
             Some(last) => self.buffer.version() != *last,
         }
     }
+
+    fn queued_diffs(&self) -> usize {
+        self.sync_state.queued_diffs
+    }
+
+    fn set_queued_diffs(&mut self, depth: usize) {
+        self.sync_state.queued_diffs = depth;
+    }
 }
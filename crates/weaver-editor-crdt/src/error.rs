@@ -37,6 +37,10 @@ pub enum CrdtError {
     /// Loro CRDT error.
     #[error("loro error: {0}")]
     Loro(String),
+
+    /// Local persistence error (offline queue file / IndexedDB).
+    #[error("storage error: {0}")]
+    Storage(String),
 }
 
 impl From<loro::LoroError> for CrdtError {
@@ -0,0 +1,343 @@
+//! Browse and restore document history from the edit record chain.
+//!
+//! The edit chain (sh.weaver.edit.root snapshot plus its diffs) already
+//! encodes the full history of a document; this module makes it
+//! navigable. [`load_history`] fetches the chain and replays it one
+//! record at a time, capturing a version checkpoint per record.
+//! [`DocumentHistory`] then exposes the text at any checkpoint (via
+//! Loro's checkout) and who authored each step (the repo the record
+//! lives in). [`restore_version`] brings an old version back as a fresh
+//! edit, so the restore itself becomes a normal diff record and history
+//! stays append-only.
+
+use std::collections::BTreeMap;
+
+use jacquard::cowstr::ToCowStr;
+use jacquard::prelude::*;
+use jacquard::smol_str::format_smolstr;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::string::{AtUri, Cid, Did};
+use jacquard::{CowStr, IntoStatic};
+use loro::{Frontiers, LoroDoc, VersionVector};
+use weaver_api::sh_weaver::edit::diff::Diff;
+use weaver_api::sh_weaver::edit::root::Root;
+use weaver_common::agent::WeaverExt;
+use weaver_editor_core::TextBuffer;
+
+use crate::CrdtError;
+use crate::buffer::LoroTextBuffer;
+use crate::document::SimpleCrdtDocument;
+use crate::sync::{DIFF_NSID, SyncResult, find_diffs_for_root, sync_to_pds};
+
+/// Text container key; must match what `LoroTextBuffer` uses.
+const TEXT_CONTAINER: &str = "content";
+
+/// One step in a document's history: the state after applying a record.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// URI of the record that produced this state. `None` for the root
+    /// snapshot itself.
+    pub uri: Option<AtUri<'static>>,
+    /// DID of the author — the repo the record lives in. `None` when the
+    /// record URI is unknown (e.g. locally built history).
+    pub author: Option<Did<'static>>,
+    /// Version vector after applying this record.
+    pub version: VersionVector,
+    /// Frontiers after applying this record, for checkout.
+    frontiers: Frontiers,
+}
+
+/// A document's history, replayable to any recorded version.
+///
+/// Holds a fully imported [`LoroDoc`] plus one [`HistoryEntry`] per edit
+/// record. Reading an old version checks the doc out in detached mode and
+/// returns it to the latest state afterwards, so the history can be
+/// browsed repeatedly without re-importing.
+pub struct DocumentHistory {
+    doc: LoroDoc,
+    entries: Vec<HistoryEntry>,
+}
+
+impl DocumentHistory {
+    /// Build history by replaying a root snapshot and its diffs in order.
+    ///
+    /// `diffs` must be ordered oldest first (diff rkeys are TIDs, so
+    /// lexicographic order is chronological order).
+    pub fn from_records<I, B>(
+        root_uri: Option<&AtUri<'_>>,
+        root_snapshot: &[u8],
+        diffs: I,
+    ) -> Result<Self, CrdtError>
+    where
+        I: IntoIterator<Item = (Option<AtUri<'static>>, B)>,
+        B: AsRef<[u8]>,
+    {
+        let doc = LoroDoc::new();
+        doc.import(root_snapshot)
+            .map_err(|e| CrdtError::Import(format!("root snapshot: {}", e)))?;
+
+        let mut entries = vec![HistoryEntry {
+            uri: root_uri.map(|u| u.clone().into_static()),
+            author: root_uri.and_then(author_of),
+            version: doc.oplog_vv(),
+            frontiers: doc.oplog_frontiers(),
+        }];
+
+        for (uri, update) in diffs {
+            doc.import(update.as_ref())
+                .map_err(|e| CrdtError::Import(format!("diff: {}", e)))?;
+            entries.push(HistoryEntry {
+                author: uri.as_ref().and_then(author_of),
+                uri,
+                version: doc.oplog_vv(),
+                frontiers: doc.oplog_frontiers(),
+            });
+        }
+
+        Ok(Self { doc, entries })
+    }
+
+    /// The recorded steps, oldest first.
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.entries
+    }
+
+    /// Number of recorded steps (root snapshot included).
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the history has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The document text at step `index`.
+    ///
+    /// Checks the document out at that version and returns it to the
+    /// latest state before returning, so this can be called in any order.
+    pub fn text_at(&self, index: usize) -> Result<String, CrdtError> {
+        let entry = self
+            .entries
+            .get(index)
+            .ok_or_else(|| CrdtError::Sync(format!("no history entry at index {}", index)))?;
+
+        self.doc
+            .checkout(&entry.frontiers)
+            .map_err(|e| CrdtError::Loro(format!("checkout: {}", e)))?;
+        let text = self.doc.get_text(TEXT_CONTAINER).to_string();
+        self.doc.checkout_to_latest();
+
+        Ok(text)
+    }
+
+    /// The document text at the latest step.
+    pub fn latest_text(&self) -> String {
+        self.doc.get_text(TEXT_CONTAINER).to_string()
+    }
+}
+
+/// DID of the repo a record lives in.
+fn author_of(uri: &AtUri<'_>) -> Option<Did<'static>> {
+    match uri.authority() {
+        AtIdentifier::Did(did) => Some(did.clone().into_static()),
+        _ => None,
+    }
+}
+
+/// Load the full history for an edit root from the PDS.
+///
+/// Fetches the root snapshot and every diff in the chain (ordered by
+/// rkey, i.e. chronologically) and replays them into a
+/// [`DocumentHistory`].
+pub async fn load_history<C>(
+    client: &C,
+    root_uri: &AtUri<'_>,
+) -> Result<DocumentHistory, CrdtError>
+where
+    C: WeaverExt,
+{
+    let root_did = author_of(root_uri)
+        .ok_or_else(|| CrdtError::InvalidUri("root URI has no DID authority".into()))?;
+
+    let root_response = client
+        .get_record::<Root>(root_uri)
+        .await
+        .map_err(|e| CrdtError::Xrpc(format!("fetch root: {}", e)))?;
+
+    let root_output = root_response
+        .into_output()
+        .map_err(|e| CrdtError::Xrpc(format!("parse root: {}", e)))?;
+
+    let root_snapshot = client
+        .fetch_blob(&root_did, root_output.value.snapshot.blob().cid())
+        .await
+        .map_err(|e| CrdtError::Xrpc(format!("fetch snapshot blob: {}", e)))?;
+
+    // Order diffs by rkey; TIDs sort chronologically.
+    let mut diffs_by_rkey: BTreeMap<CowStr<'static>, (Option<AtUri<'static>>, Vec<u8>)> =
+        BTreeMap::new();
+
+    for diff_id in find_diffs_for_root(client, root_uri).await? {
+        let rkey_str: &str = diff_id.rkey.as_ref();
+
+        let diff_uri = AtUri::new(&format_smolstr!(
+            "at://{}/{}/{}",
+            diff_id.did,
+            DIFF_NSID,
+            rkey_str
+        ))
+        .map_err(|e| CrdtError::InvalidUri(format!("diff URI: {}", e)))?
+        .into_static();
+
+        let diff_response = client
+            .get_record::<Diff>(&diff_uri)
+            .await
+            .map_err(|e| CrdtError::Xrpc(format!("fetch diff: {}", e)))?;
+
+        let diff_output = diff_response
+            .into_output()
+            .map_err(|e| CrdtError::Xrpc(format!("parse diff: {}", e)))?;
+
+        let bytes = if let Some(ref inline) = diff_output.value.inline_diff {
+            inline.to_vec()
+        } else if let Some(ref snapshot) = diff_output.value.snapshot {
+            client
+                .fetch_blob(&diff_id.did, snapshot.blob().cid())
+                .await
+                .map_err(|e| CrdtError::Xrpc(format!("fetch diff blob: {}", e)))?
+                .to_vec()
+        } else {
+            tracing::warn!("Diff has neither inline_diff nor snapshot, skipping");
+            continue;
+        };
+
+        diffs_by_rkey.insert(rkey_str.to_cowstr().into_static(), (Some(diff_uri), bytes));
+    }
+
+    DocumentHistory::from_records(Some(root_uri), &root_snapshot, diffs_by_rkey.into_values())
+}
+
+/// Replace a buffer's content with text from an old version.
+///
+/// This is a forward edit, not a rollback: the CRDT keeps every
+/// operation, and the replacement merges cleanly with concurrent edits.
+pub fn restore_text(buffer: &mut LoroTextBuffer, text: &str) {
+    let len = buffer.len_chars();
+    buffer.replace(0..len, text);
+}
+
+/// Restore an old version as a new diff record.
+///
+/// Applies the text at `index` to the live document as a fresh edit and
+/// syncs it, so the restore lands on the PDS as an ordinary diff and the
+/// chain stays append-only — the restored-from history remains intact.
+pub async fn restore_version<C>(
+    client: &C,
+    doc: &mut SimpleCrdtDocument,
+    history: &DocumentHistory,
+    index: usize,
+    draft_key: &str,
+    entry_uri: Option<&AtUri<'_>>,
+    entry_cid: Option<&Cid<'_>>,
+) -> Result<SyncResult, CrdtError>
+where
+    C: XrpcClient + IdentityResolver + AgentSession,
+{
+    let text = history.text_at(index)?;
+    restore_text(doc.buffer_mut(), &text);
+    sync_to_pds(client, doc, draft_key, entry_uri, entry_cid).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build (snapshot, updates) for "Hello" -> "Hello World" -> "Hello!".
+    fn build_chain() -> (Vec<u8>, Vec<Vec<u8>>) {
+        let mut buffer = LoroTextBuffer::new();
+        buffer.insert(0, "Hello");
+        let snapshot = buffer.export_snapshot();
+
+        let v1 = buffer.version();
+        buffer.insert(5, " World");
+        let update1 = buffer.export_updates_since(&v1).expect("changes exist");
+
+        let v2 = buffer.version();
+        buffer.replace(5..11, "!");
+        let update2 = buffer.export_updates_since(&v2).expect("changes exist");
+
+        (snapshot, vec![update1, update2])
+    }
+
+    #[test]
+    fn test_text_at_each_version() {
+        let (snapshot, updates) = build_chain();
+        let history = DocumentHistory::from_records(
+            None,
+            &snapshot,
+            updates.into_iter().map(|u| (None, u)),
+        )
+        .unwrap();
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.text_at(0).unwrap(), "Hello");
+        assert_eq!(history.text_at(1).unwrap(), "Hello World");
+        assert_eq!(history.text_at(2).unwrap(), "Hello!");
+        // Out-of-order reads work; the doc returns to latest in between.
+        assert_eq!(history.text_at(0).unwrap(), "Hello");
+        assert_eq!(history.latest_text(), "Hello!");
+    }
+
+    #[test]
+    fn test_text_at_out_of_range() {
+        let (snapshot, _) = build_chain();
+        let history = DocumentHistory::from_records(
+            None,
+            &snapshot,
+            Vec::<(Option<AtUri<'static>>, Vec<u8>)>::new(),
+        )
+        .unwrap();
+        assert!(history.text_at(1).is_err());
+    }
+
+    #[test]
+    fn test_restore_text_is_forward_edit() {
+        let (snapshot, updates) = build_chain();
+        let history = DocumentHistory::from_records(
+            None,
+            &snapshot,
+            updates.into_iter().map(|u| (None, u)),
+        )
+        .unwrap();
+
+        let merged = history.doc.export(loro::ExportMode::Snapshot).unwrap();
+        let mut buffer = LoroTextBuffer::from_snapshot(&merged).unwrap();
+        let before = buffer.version();
+
+        restore_text(&mut buffer, &history.text_at(1).unwrap());
+        assert_eq!(buffer.to_string(), "Hello World");
+        // The restore is a new operation on top of existing history.
+        assert!(buffer.export_updates_since(&before).is_some());
+    }
+
+    #[test]
+    fn test_author_attribution() {
+        let uri = AtUri::new("at://did:plc:abc123/sh.weaver.edit.diff/3jzfcijpj2z2a")
+            .unwrap()
+            .into_static();
+        let (snapshot, updates) = build_chain();
+        let history = DocumentHistory::from_records(
+            None,
+            &snapshot,
+            updates.into_iter().take(1).map(|u| (Some(uri.clone()), u)),
+        )
+        .unwrap();
+
+        assert!(history.entries()[0].author.is_none());
+        assert_eq!(
+            history.entries()[1].author.as_ref().map(|d| d.to_string()),
+            Some("did:plc:abc123".to_string())
+        );
+    }
+}
@@ -4,36 +4,54 @@
 //! - `LoroTextBuffer`: Loro-backed text buffer implementing `TextBuffer` + `UndoManager`
 //! - `CrdtDocument`: Trait for documents that can sync to AT Protocol PDS
 //! - Generic sync logic for edit records (root/diff/draft)
+//! - Persistent offline queue for edits made while the PDS is unreachable
+//! - History browsing and restore over the diff chain
+//! - Comment threads anchored to stable CRDT positions
+//! - Compaction of long diff chains into fresh root snapshots
+//! - Portable document snapshots for backup and transfer
 //! - Worker implementation for off-main-thread CRDT operations
 //! - Collab coordination types and helpers
 
 mod buffer;
+mod comments;
+mod compact;
 mod coordinator;
 mod document;
 mod error;
+mod history;
+mod queue;
+mod snapshot;
 mod sync;
 
 pub mod worker;
 
 pub use buffer::LoroTextBuffer;
+pub use comments::{
+    CommentAnchor, CommentThread, CommentView, add_comment, list_threads, resolve_thread,
+};
+pub use compact::{CompactionConfig, CompactionOutcome, compact_edit_chain};
 pub use coordinator::{
-    CoordinatorState, PEER_DISCOVERY_INTERVAL_MS, SESSION_REFRESH_INTERVAL_MS, SESSION_TTL_MINUTES,
+    CollabQuota, CoordinatorState, DIFF_CREATE_LIMIT, DIFF_CREATE_WINDOW_MS, MAX_SESSIONS_PER_DID,
+    PEER_DISCOVERY_INTERVAL_MS, PeerAuthorization, QuotaExceeded, RateWindow, SESSION_CREATE_LIMIT,
+    SESSION_CREATE_WINDOW_MS, SESSION_REFRESH_INTERVAL_MS, SESSION_TTL_MINUTES,
     compute_collab_topic,
 };
 pub use document::{CrdtDocument, SimpleCrdtDocument, SyncState};
 pub use error::CrdtError;
+pub use history::{DocumentHistory, HistoryEntry, load_history, restore_text, restore_version};
+pub use queue::{OfflineQueue, QueuedDiff, load_queue, now_ms, persist_queue};
+pub use snapshot::{DocumentSnapshot, SNAPSHOT_FORMAT_VERSION};
 pub use sync::{
-    CreateRootResult, PdsEditState, RemoteDraft, SyncResult,
-    build_draft_uri, create_diff, create_edit_root,
-    find_all_edit_roots, find_diffs_for_root, find_edit_root_for_draft,
-    list_drafts, load_all_edit_states, load_edit_state_from_draft,
-    load_edit_state_from_entry, sync_to_pds,
+    CreateRootResult, PdsEditState, RemoteDraft, SyncResult, build_draft_uri, create_diff,
+    create_edit_root, find_all_edit_roots, find_diffs_for_root, find_edit_root_for_draft,
+    flush_queue, list_drafts, load_all_edit_states, load_edit_state_from_draft,
+    load_edit_state_from_entry, sync_or_queue, sync_to_pds,
 };
 
 // Re-export worker types
-pub use worker::{WorkerInput, WorkerOutput};
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 pub use worker::EditorReactor;
+pub use worker::{WorkerInput, WorkerOutput};
 
 // Re-export Loro types that consumers need
 pub use loro::{ExportMode, LoroDoc, LoroText, VersionVector};
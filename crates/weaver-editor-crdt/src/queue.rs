@@ -0,0 +1,326 @@
+//! Persistent outbound queue for offline edits.
+//!
+//! When the PDS is unreachable, [`sync_to_pds`](crate::sync_to_pds) fails
+//! and the exported updates would only survive as long as the in-memory
+//! document. This module queues those updates durably — IndexedDB on wasm,
+//! a JSON file on native — so they outlive the tab/process and can be
+//! replayed as diff records once connectivity returns
+//! (see [`flush_queue`](crate::flush_queue)).
+//!
+//! Entries are strictly FIFO: diffs chain through `prev` references, so a
+//! later update must never be recorded before an earlier one. Each entry
+//! carries the document's version vector at queue time, which makes the
+//! queue auditable and lets recovery tooling detect already-applied
+//! updates.
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::CrdtError;
+
+/// A single queued update awaiting upload.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueuedDiff {
+    /// Monotonic sequence number, assigned at queue time.
+    pub seq: u64,
+    /// Encoded Loro version vector when the update was queued.
+    pub version: Vec<u8>,
+    /// The exported update bytes (same payload a diff record carries).
+    pub update: Vec<u8>,
+    /// Unix millis when the update was queued.
+    pub queued_at_ms: u64,
+}
+
+/// FIFO queue of updates that could not be synced.
+///
+/// Pure bookkeeping; persistence goes through [`load_queue`] and
+/// [`persist_queue`]. Callers push when a sync fails with a transport
+/// error and acknowledge entries as [`flush_queue`](crate::flush_queue)
+/// uploads them.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct OfflineQueue {
+    entries: VecDeque<QueuedDiff>,
+    next_seq: u64,
+}
+
+impl OfflineQueue {
+    /// Create a new empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue an update, returning its sequence number.
+    pub fn push(&mut self, update: Vec<u8>, version: Vec<u8>, queued_at_ms: u64) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        self.entries.push_back(QueuedDiff {
+            seq,
+            version,
+            update,
+            queued_at_ms,
+        });
+        seq
+    }
+
+    /// The oldest pending entry, which must be uploaded first.
+    pub fn peek(&self) -> Option<&QueuedDiff> {
+        self.entries.front()
+    }
+
+    /// Drop the entry with `seq` after it has been uploaded.
+    ///
+    /// Only the head can be acknowledged; out-of-order acknowledgement
+    /// would break the diff `prev` chain, so anything else is ignored.
+    /// Returns true if the entry was removed.
+    pub fn acknowledge(&mut self, seq: u64) -> bool {
+        if self.entries.front().is_some_and(|e| e.seq == seq) {
+            self.entries.pop_front();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Number of pending entries.
+    pub fn depth(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there is nothing to flush.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Current time in unix millis, usable on both native and wasm.
+pub fn now_ms() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+// === Native persistence: JSON file ===
+
+/// Load the queue from a JSON file, or an empty queue if none exists yet.
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub async fn load_queue(path: &std::path::Path) -> Result<OfflineQueue, CrdtError> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => serde_json::from_str(&json)
+            .map_err(|e| CrdtError::Storage(format!("parse queue file: {}", e))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(OfflineQueue::new()),
+        Err(e) => Err(CrdtError::Storage(format!("read queue file: {}", e))),
+    }
+}
+
+/// Persist the queue to a JSON file, creating parent directories.
+///
+/// Queues are small (failed syncs, not full history), so synchronous IO and
+/// whole-file rewrites are fine here.
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub async fn persist_queue(queue: &OfflineQueue, path: &std::path::Path) -> Result<(), CrdtError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| CrdtError::Storage(format!("create queue dir: {}", e)))?;
+    }
+    let json = serde_json::to_string(queue)
+        .map_err(|e| CrdtError::Storage(format!("serialize queue: {}", e)))?;
+    std::fs::write(path, json).map_err(|e| CrdtError::Storage(format!("write queue file: {}", e)))
+}
+
+// === Wasm persistence: IndexedDB ===
+//
+// localStorage is synchronous and size-capped, and the editor worker has no
+// access to it at all; IndexedDB works in both window and worker contexts.
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+mod idb {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use wasm_bindgen::closure::Closure;
+    use wasm_bindgen::{JsCast, JsValue};
+
+    use super::OfflineQueue;
+    use crate::CrdtError;
+
+    const DB_NAME: &str = "weaver-editor";
+    const DB_VERSION: u32 = 1;
+    const STORE_NAME: &str = "offline_queue";
+    const QUEUE_KEY: &str = "queue";
+
+    fn storage_err(context: &str, value: JsValue) -> CrdtError {
+        CrdtError::Storage(format!("{}: {:?}", context, value))
+    }
+
+    /// IndexedDB factory for the current context (window or worker).
+    fn idb_factory() -> Result<web_sys::IdbFactory, CrdtError> {
+        let global = js_sys::global();
+        if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+            if let Ok(Some(factory)) = window.indexed_db() {
+                return Ok(factory);
+            }
+        }
+        if let Some(scope) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+            if let Ok(Some(factory)) = scope.indexed_db() {
+                return Ok(factory);
+            }
+        }
+        Err(CrdtError::Storage("indexedDB unavailable".into()))
+    }
+
+    /// Wait for an IdbRequest to settle, returning its result.
+    async fn await_request(request: &web_sys::IdbRequest) -> Result<JsValue, CrdtError> {
+        let (tx, rx) = tokio::sync::oneshot::channel::<Result<(), CrdtError>>();
+        let tx = Rc::new(RefCell::new(Some(tx)));
+
+        let tx_success = tx.clone();
+        let onsuccess = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+            if let Some(tx) = tx_success.borrow_mut().take() {
+                let _ = tx.send(Ok(()));
+            }
+        });
+        let tx_error = tx.clone();
+        let onerror = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+            if let Some(tx) = tx_error.borrow_mut().take() {
+                let _ = tx.send(Err(CrdtError::Storage("indexedDB request failed".into())));
+            }
+        });
+
+        request.set_onsuccess(Some(onsuccess.as_ref().unchecked_ref()));
+        request.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+
+        let settled = rx
+            .await
+            .map_err(|_| CrdtError::Storage("indexedDB request dropped".into()))?;
+
+        // Detach before the closures drop so a late event cannot call into
+        // freed memory.
+        request.set_onsuccess(None);
+        request.set_onerror(None);
+
+        settled?;
+        request
+            .result()
+            .map_err(|e| storage_err("request result", e))
+    }
+
+    /// Open (and if needed create) the editor database.
+    async fn open_db() -> Result<web_sys::IdbDatabase, CrdtError> {
+        let factory = idb_factory()?;
+        let open_request = factory
+            .open_with_u32(DB_NAME, DB_VERSION)
+            .map_err(|e| storage_err("open database", e))?;
+
+        // Create the object store on first open / version bump.
+        let upgrade_request = open_request.clone();
+        let onupgradeneeded = Closure::<dyn FnMut(web_sys::Event)>::new(move |_| {
+            if let Ok(result) = upgrade_request.result() {
+                if let Ok(db) = result.dyn_into::<web_sys::IdbDatabase>() {
+                    if !db.object_store_names().contains(STORE_NAME) {
+                        let _ = db.create_object_store(STORE_NAME);
+                    }
+                }
+            }
+        });
+        open_request.set_onupgradeneeded(Some(onupgradeneeded.as_ref().unchecked_ref()));
+
+        let result = await_request(&open_request).await;
+        open_request.set_onupgradeneeded(None);
+
+        result?
+            .dyn_into::<web_sys::IdbDatabase>()
+            .map_err(|e| storage_err("open result", e))
+    }
+
+    /// Load the queue from IndexedDB, or an empty queue if none is stored.
+    pub async fn load_queue() -> Result<OfflineQueue, CrdtError> {
+        let db = open_db().await?;
+        let transaction = db
+            .transaction_with_str(STORE_NAME)
+            .map_err(|e| storage_err("open transaction", e))?;
+        let store = transaction
+            .object_store(STORE_NAME)
+            .map_err(|e| storage_err("open store", e))?;
+        let request = store
+            .get(&JsValue::from_str(QUEUE_KEY))
+            .map_err(|e| storage_err("get queue", e))?;
+
+        let value = await_request(&request).await?;
+        match value.as_string() {
+            Some(json) => serde_json::from_str(&json)
+                .map_err(|e| CrdtError::Storage(format!("parse stored queue: {}", e))),
+            None => Ok(OfflineQueue::new()),
+        }
+    }
+
+    /// Persist the queue to IndexedDB, replacing any stored copy.
+    pub async fn persist_queue(queue: &OfflineQueue) -> Result<(), CrdtError> {
+        let json = serde_json::to_string(queue)
+            .map_err(|e| CrdtError::Storage(format!("serialize queue: {}", e)))?;
+
+        let db = open_db().await?;
+        let transaction = db
+            .transaction_with_str_and_mode(STORE_NAME, web_sys::IdbTransactionMode::Readwrite)
+            .map_err(|e| storage_err("open transaction", e))?;
+        let store = transaction
+            .object_store(STORE_NAME)
+            .map_err(|e| storage_err("open store", e))?;
+        let request = store
+            .put_with_key(&JsValue::from_str(&json), &JsValue::from_str(QUEUE_KEY))
+            .map_err(|e| storage_err("put queue", e))?;
+
+        await_request(&request).await?;
+        Ok(())
+    }
+}
+
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub use idb::{load_queue, persist_queue};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fifo_order_and_depth() {
+        let mut queue = OfflineQueue::new();
+        assert!(queue.is_empty());
+
+        let first = queue.push(vec![1], vec![], 1000);
+        let second = queue.push(vec![2], vec![], 2000);
+        assert_eq!(queue.depth(), 2);
+        assert_eq!(queue.peek().map(|e| e.seq), Some(first));
+
+        assert!(queue.acknowledge(first));
+        assert_eq!(queue.peek().map(|e| e.seq), Some(second));
+        assert!(queue.acknowledge(second));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_out_of_order_acknowledge_is_rejected() {
+        let mut queue = OfflineQueue::new();
+        let first = queue.push(vec![1], vec![], 0);
+        let second = queue.push(vec![2], vec![], 0);
+
+        // Acknowledging past the head would break the diff chain.
+        assert!(!queue.acknowledge(second));
+        assert_eq!(queue.depth(), 2);
+        assert!(queue.acknowledge(first));
+    }
+
+    #[test]
+    fn test_sequence_numbers_survive_roundtrip() {
+        let mut queue = OfflineQueue::new();
+        queue.push(vec![1], vec![], 0);
+
+        let json = serde_json::to_string(&queue).unwrap();
+        let mut restored: OfflineQueue = serde_json::from_str(&json).unwrap();
+
+        // New entries must not reuse sequence numbers after a reload.
+        let next = restored.push(vec![2], vec![], 0);
+        assert_eq!(next, 1);
+    }
+}
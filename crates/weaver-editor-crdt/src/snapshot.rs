@@ -0,0 +1,174 @@
+//! Portable document snapshots for backup and transfer.
+//!
+//! The edit chain on a PDS is the canonical history of a document, but it
+//! is only readable online and only by resolving every record. A
+//! [`DocumentSnapshot`] packages the binary Loro snapshot — which already
+//! contains the full operation history — together with enough metadata to
+//! identify where and when it was taken. That makes it a self-contained
+//! artifact: back it up, hand it to another account, or feed it to
+//! support tooling that replays history offline via
+//! [`DocumentHistory`](crate::DocumentHistory).
+//!
+//! Importing merges; it never overwrites. The snapshot's operations are
+//! applied through the normal CRDT import path, so importing into a
+//! non-empty document converges the same way a live sync would.
+
+use serde::{Deserialize, Serialize};
+
+use crate::CrdtError;
+use crate::document::CrdtDocument;
+use crate::queue::now_ms;
+
+/// Current [`DocumentSnapshot`] wire format version.
+///
+/// Bump when the serialized shape changes incompatibly; [`from_bytes`]
+/// (DocumentSnapshot::from_bytes) rejects versions it does not know.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// A portable export of a CRDT document: binary Loro snapshot plus
+/// metadata.
+///
+/// The sync pointers (`edit_root_uri`, `last_diff_uri`) are informational
+/// — they record where the document lived when exported. Importing does
+/// not adopt them, because they reference records in the exporting
+/// account's repo; the importing document keeps (or later creates) its
+/// own chain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DocumentSnapshot {
+    /// Wire format version; see [`SNAPSHOT_FORMAT_VERSION`].
+    pub format_version: u32,
+    /// Binary Loro snapshot containing the full operation history.
+    pub snapshot: Vec<u8>,
+    /// Encoded Loro version vector at export time.
+    pub version: Vec<u8>,
+    /// Unix millis when the snapshot was taken.
+    pub exported_at_ms: u64,
+    /// URI of the edit root at export time, if the document had synced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edit_root_uri: Option<String>,
+    /// URI of the newest diff at export time, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_diff_uri: Option<String>,
+}
+
+impl DocumentSnapshot {
+    /// Build a snapshot from raw Loro exports, without sync metadata.
+    ///
+    /// For contexts that hold a bare buffer rather than a
+    /// [`CrdtDocument`] (e.g. the JS collab editor); prefer
+    /// [`capture`](Self::capture) when sync state is available.
+    pub fn new(snapshot: Vec<u8>, version: Vec<u8>) -> Self {
+        Self {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            snapshot,
+            version,
+            exported_at_ms: now_ms(),
+            edit_root_uri: None,
+            last_diff_uri: None,
+        }
+    }
+
+    /// Capture a document's current state, including its sync pointers.
+    pub fn capture<D>(doc: &D) -> Self
+    where
+        D: CrdtDocument + ?Sized,
+    {
+        Self {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            snapshot: doc.export_snapshot(),
+            version: doc.version().encode(),
+            exported_at_ms: now_ms(),
+            edit_root_uri: doc.edit_root().map(|r| r.uri.to_string()),
+            last_diff_uri: doc.last_diff().map(|r| r.uri.to_string()),
+        }
+    }
+
+    /// Merge this snapshot's operations into `doc`.
+    ///
+    /// Sync pointers are deliberately left untouched; see the type-level
+    /// docs.
+    pub fn apply_to<D>(&self, doc: &mut D) -> Result<(), CrdtError>
+    where
+        D: CrdtDocument + ?Sized,
+    {
+        if self.format_version > SNAPSHOT_FORMAT_VERSION {
+            return Err(CrdtError::Import(format!(
+                "unsupported snapshot format version {} (newest known is {})",
+                self.format_version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+        doc.import(&self.snapshot)
+    }
+
+    /// Serialize for storage or transfer.
+    ///
+    /// JSON, matching the offline queue's persistence format.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, CrdtError> {
+        serde_json::to_vec(self).map_err(|e| CrdtError::Serialization(format!("snapshot: {}", e)))
+    }
+
+    /// Deserialize bytes produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CrdtError> {
+        let snapshot: Self = serde_json::from_slice(bytes)
+            .map_err(|e| CrdtError::Serialization(format!("snapshot: {}", e)))?;
+        if snapshot.format_version > SNAPSHOT_FORMAT_VERSION {
+            return Err(CrdtError::Import(format!(
+                "unsupported snapshot format version {} (newest known is {})",
+                snapshot.format_version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::SimpleCrdtDocument;
+    use weaver_editor_core::TextBuffer;
+
+    #[test]
+    fn test_snapshot_round_trips_through_bytes() {
+        let mut doc = SimpleCrdtDocument::new();
+        doc.buffer_mut().push("hello snapshot");
+
+        let snapshot = doc.export_document_snapshot();
+        let bytes = snapshot.to_bytes().unwrap();
+        let restored = DocumentSnapshot::from_bytes(&bytes).unwrap();
+
+        assert_eq!(restored.format_version, SNAPSHOT_FORMAT_VERSION);
+        assert_eq!(restored.snapshot, snapshot.snapshot);
+        assert_eq!(restored.version, snapshot.version);
+    }
+
+    #[test]
+    fn test_import_merges_into_existing_document() {
+        let mut source = SimpleCrdtDocument::new();
+        source.buffer_mut().push("exported text");
+        let snapshot = source.export_document_snapshot();
+
+        // Importing into a fresh document reproduces the content.
+        let mut fresh = SimpleCrdtDocument::new();
+        fresh.import_document_snapshot(&snapshot).unwrap();
+        assert_eq!(fresh.buffer().to_string(), "exported text");
+
+        // Importing again is idempotent — CRDT merge, not replace.
+        fresh.import_document_snapshot(&snapshot).unwrap();
+        assert_eq!(fresh.buffer().to_string(), "exported text");
+    }
+
+    #[test]
+    fn test_unknown_format_version_is_rejected() {
+        let mut doc = SimpleCrdtDocument::new();
+        doc.buffer_mut().push("future");
+
+        let mut snapshot = doc.export_document_snapshot();
+        snapshot.format_version = SNAPSHOT_FORMAT_VERSION + 1;
+
+        let mut target = SimpleCrdtDocument::new();
+        assert!(target.import_document_snapshot(&snapshot).is_err());
+
+        let bytes = snapshot.to_bytes().unwrap();
+        assert!(DocumentSnapshot::from_bytes(&bytes).is_err());
+    }
+}
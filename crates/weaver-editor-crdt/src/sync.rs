@@ -19,7 +19,9 @@ use jacquard::url::Url;
 use jacquard::{CowStr, IntoStatic, to_data};
 use loro::{ExportMode, LoroDoc};
 use weaver_api::com_atproto::repo::create_record::CreateRecord;
+use weaver_api::com_atproto::repo::put_record::PutRecord;
 use weaver_api::com_atproto::repo::strong_ref::StrongRef;
+use weaver_api::sh_weaver::edit::comment::Comment;
 use weaver_api::sh_weaver::edit::diff::Diff;
 use weaver_api::sh_weaver::edit::draft::Draft;
 use weaver_api::sh_weaver::edit::root::Root;
@@ -29,10 +31,12 @@ use weaver_common::constellation::{GetBacklinksQuery, RecordId};
 
 use crate::CrdtError;
 use crate::document::CrdtDocument;
+use crate::queue::{OfflineQueue, now_ms};
 
 const ROOT_NSID: &str = "sh.weaver.edit.root";
-const DIFF_NSID: &str = "sh.weaver.edit.diff";
+pub(crate) const DIFF_NSID: &str = "sh.weaver.edit.diff";
 const DRAFT_NSID: &str = "sh.weaver.edit.draft";
+pub(crate) const COMMENT_NSID: &str = "sh.weaver.edit.comment";
 const CONSTELLATION_URL: &str = "https://constellation.microcosm.blue";
 
 /// Result of a sync operation.
@@ -50,6 +54,11 @@ pub enum SyncResult {
     },
     /// No changes to sync.
     NoChanges,
+    /// The PDS was unreachable; updates were queued locally instead.
+    Queued {
+        /// Pending entries in the offline queue after this attempt.
+        depth: usize,
+    },
 }
 
 /// Result of creating an edit root.
@@ -63,7 +72,7 @@ pub struct CreateRootResult {
 }
 
 /// Build a DocRef for either a published entry or an unpublished draft.
-fn build_doc_ref(
+pub(crate) fn build_doc_ref(
     did: &Did<'_>,
     draft_key: &str,
     entry_uri: Option<&AtUri<'_>>,
@@ -108,7 +117,7 @@ fn extract_draft_rkey(draft_key: &str) -> String {
 }
 
 /// Get current DID from session.
-async fn get_current_did<C>(client: &C) -> Result<Did<'static>, CrdtError>
+pub(crate) async fn get_current_did<C>(client: &C) -> Result<Did<'static>, CrdtError>
 where
     C: AgentSession,
 {
@@ -282,6 +291,31 @@ where
         None => return Ok(None),
     };
 
+    put_diff_record(
+        client, updates, root_uri, root_cid, prev_diff, draft_key, entry_uri, entry_cid,
+    )
+    .await
+    .map(Some)
+}
+
+/// Upload raw update bytes as a diff record.
+///
+/// The record-building half of [`create_diff`], shared with
+/// [`flush_queue`] which replays updates exported at queue time rather
+/// than from the live document.
+async fn put_diff_record<C>(
+    client: &C,
+    updates: Vec<u8>,
+    root_uri: &AtUri<'_>,
+    root_cid: &Cid<'_>,
+    prev_diff: Option<(&AtUri<'_>, &Cid<'_>)>,
+    draft_key: &str,
+    entry_uri: Option<&AtUri<'_>>,
+    entry_cid: Option<&Cid<'_>>,
+) -> Result<(AtUri<'static>, Cid<'static>), CrdtError>
+where
+    C: XrpcClient + IdentityResolver + AgentSession,
+{
     let did = get_current_did(client).await?;
 
     // Threshold for inline vs blob storage (8KB max for inline per lexicon)
@@ -351,7 +385,7 @@ where
         .into_output()
         .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
 
-    Ok(Some((output.uri.into_static(), output.cid.into_static())))
+    Ok((output.uri.into_static(), output.cid.into_static()))
 }
 
 /// Sync the document to the PDS.
@@ -412,6 +446,135 @@ where
     }
 }
 
+/// Sync the document, falling back to the offline queue when the PDS is
+/// unreachable.
+///
+/// Behaves like [`sync_to_pds`], except that a transport failure does not
+/// lose the edits: the pending updates are captured into `queue` (with the
+/// document's version vector) and the call returns
+/// [`SyncResult::Queued`]. Any backlog already in the queue is flushed
+/// first so diff records land in edit order.
+///
+/// The caller owns persistence — after this returns, write `queue` out
+/// with [`persist_queue`](crate::persist_queue) — and drives retry:
+/// calling this again (e.g. from an online event or a timer) flushes the
+/// backlog once the PDS is reachable.
+///
+/// The first sync of a document cannot be queued: root creation uploads a
+/// full snapshot and nothing references it yet, so a transport failure
+/// before a root exists is returned as-is and the changes simply remain
+/// unsynced.
+pub async fn sync_or_queue<C, D>(
+    client: &C,
+    doc: &mut D,
+    queue: &mut OfflineQueue,
+    draft_key: &str,
+    entry_uri: Option<&AtUri<'_>>,
+    entry_cid: Option<&Cid<'_>>,
+) -> Result<SyncResult, CrdtError>
+where
+    C: XrpcClient + IdentityResolver + AgentSession,
+    D: CrdtDocument,
+{
+    // Drain the backlog first; a fresh diff uploaded ahead of queued ones
+    // would break the prev chain's edit order.
+    if !queue.is_empty() {
+        match flush_queue(client, doc, queue, draft_key, entry_uri, entry_cid).await {
+            Ok(flushed) => {
+                tracing::debug!("flushed {} queued diffs before syncing", flushed);
+            }
+            Err(e) => {
+                // Still offline; capture the new edits alongside the backlog.
+                tracing::debug!("flush failed, queueing current changes: {}", e);
+                queue_pending_updates(doc, queue);
+                doc.set_queued_diffs(queue.depth());
+                return Ok(SyncResult::Queued {
+                    depth: queue.depth(),
+                });
+            }
+        }
+    }
+
+    match sync_to_pds(client, doc, draft_key, entry_uri, entry_cid).await {
+        Ok(result) => {
+            doc.set_queued_diffs(queue.depth());
+            Ok(result)
+        }
+        // Only transport failures are queueable, and only once a root
+        // exists — queued entries are replayed as diff records, which need
+        // a root to reference.
+        Err(CrdtError::Xrpc(e)) if doc.edit_root().is_some() => {
+            tracing::debug!("sync failed, queueing changes: {}", e);
+            queue_pending_updates(doc, queue);
+            doc.set_queued_diffs(queue.depth());
+            Ok(SyncResult::Queued {
+                depth: queue.depth(),
+            })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Capture pending updates into the queue and mark the document synced.
+///
+/// Marking synced here is what keeps later exports incremental: the queued
+/// bytes already cover everything up to this version, so exporting them
+/// again would upload duplicates (harmless to the CRDT, but wasteful).
+fn queue_pending_updates<D>(doc: &mut D, queue: &mut OfflineQueue)
+where
+    D: CrdtDocument,
+{
+    if let Some(updates) = doc.export_updates_since_sync() {
+        queue.push(updates, doc.version().encode(), now_ms());
+        doc.mark_synced();
+    }
+}
+
+/// Upload queued updates as diff records, oldest first.
+///
+/// Stops at the first failure, leaving that entry and everything after it
+/// queued for the next attempt. Returns the number of entries uploaded.
+/// Requires an edit root; queue only via [`sync_or_queue`], which enforces
+/// that.
+pub async fn flush_queue<C, D>(
+    client: &C,
+    doc: &mut D,
+    queue: &mut OfflineQueue,
+    draft_key: &str,
+    entry_uri: Option<&AtUri<'_>>,
+    entry_cid: Option<&Cid<'_>>,
+) -> Result<usize, CrdtError>
+where
+    C: XrpcClient + IdentityResolver + AgentSession,
+    D: CrdtDocument,
+{
+    let root = doc
+        .edit_root()
+        .ok_or_else(|| CrdtError::Sync("cannot flush offline queue without an edit root".into()))?;
+
+    let mut flushed = 0;
+    while let Some(entry) = queue.peek() {
+        let seq = entry.seq;
+        let updates = entry.update.clone();
+
+        let prev = doc.last_diff();
+        let prev_refs = prev.as_ref().map(|p| (&p.uri, &p.cid));
+
+        let (uri, cid) = put_diff_record(
+            client, updates, &root.uri, &root.cid, prev_refs, draft_key, entry_uri, entry_cid,
+        )
+        .await?;
+
+        let diff_ref = StrongRef::new().uri(uri).cid(cid).build();
+        doc.set_last_diff(Some(diff_ref));
+        queue.acknowledge(seq);
+        flushed += 1;
+    }
+
+    doc.set_queued_diffs(queue.depth());
+    Ok(flushed)
+}
+
 /// Find all edit roots for an entry using weaver-index.
 #[cfg(feature = "use-index")]
 pub async fn find_all_edit_roots<C>(
@@ -540,6 +703,145 @@ where
     Ok(all_diffs)
 }
 
+/// Create a comment record in the current session's repo.
+pub(crate) async fn create_comment_record<C>(
+    client: &C,
+    comment: &Comment<'_>,
+) -> Result<(AtUri<'static>, Cid<'static>), CrdtError>
+where
+    C: XrpcClient + AgentSession,
+{
+    let did = get_current_did(client).await?;
+
+    let comment_data =
+        to_data(comment).map_err(|e| CrdtError::Serialization(format!("comment: {}", e)))?;
+
+    // Generate TID for the comment rkey
+    let comment_tid = Ticker::new().next(None);
+    let rkey = RecordKey::any(comment_tid.as_str())
+        .map_err(|e| CrdtError::InvalidUri(format!("rkey: {}", e)))?;
+
+    let collection =
+        Nsid::new(COMMENT_NSID).map_err(|e| CrdtError::InvalidUri(format!("nsid: {}", e)))?;
+
+    let request = CreateRecord::new()
+        .repo(AtIdentifier::Did(did))
+        .collection(collection)
+        .rkey(rkey)
+        .record(comment_data)
+        .build();
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    let output = response
+        .into_output()
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    Ok((output.uri.into_static(), output.cid.into_static()))
+}
+
+/// Rewrite an existing comment record in place.
+///
+/// Used to flip the `resolved` flag on thread roots; the PDS rejects
+/// writes to repos other than the author's, so no extra permission
+/// check is needed here.
+pub(crate) async fn update_comment_record<C>(
+    client: &C,
+    uri: &AtUri<'_>,
+    comment: &Comment<'_>,
+) -> Result<(), CrdtError>
+where
+    C: XrpcClient + AgentSession,
+{
+    let comment_data =
+        to_data(comment).map_err(|e| CrdtError::Serialization(format!("comment: {}", e)))?;
+
+    let rkey = uri
+        .rkey()
+        .ok_or_else(|| CrdtError::InvalidUri(format!("comment URI missing rkey: {}", uri)))?
+        .clone()
+        .into_static();
+
+    let collection =
+        Nsid::new(COMMENT_NSID).map_err(|e| CrdtError::InvalidUri(format!("nsid: {}", e)))?;
+
+    let request = PutRecord::new()
+        .repo(uri.authority().clone().into_static())
+        .collection(collection)
+        .rkey(rkey)
+        .record(comment_data)
+        .build();
+
+    let response = client
+        .send(request)
+        .await
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    response
+        .into_output()
+        .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Find all comments for a document using Constellation backlinks.
+///
+/// `draft` selects which DocRef arm the comments reference: unpublished
+/// drafts are linked via `doc.value.draft_key`, published entries via
+/// `doc.value.entry.uri`.
+pub(crate) async fn find_comments_for_doc<C>(
+    client: &C,
+    doc_uri: &AtUri<'_>,
+    draft: bool,
+) -> Result<Vec<RecordId<'static>>, CrdtError>
+where
+    C: XrpcClient,
+{
+    let constellation_url =
+        Url::parse(CONSTELLATION_URL).map_err(|e| CrdtError::InvalidUri(e.to_string()))?;
+
+    let source_path = if draft {
+        "doc.value.draft_key"
+    } else {
+        "doc.value.entry.uri"
+    };
+
+    let mut all_comments = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let query = GetBacklinksQuery {
+            subject: Uri::At(doc_uri.clone().into_static()),
+            source: format_smolstr!("{}:{}", COMMENT_NSID, source_path).into(),
+            cursor: cursor.map(Into::into),
+            did: vec![],
+            limit: 100,
+        };
+
+        let response = client
+            .xrpc(constellation_url.clone())
+            .send(&query)
+            .await
+            .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+        let output = response
+            .into_output()
+            .map_err(|e| CrdtError::Xrpc(e.to_string()))?;
+
+        all_comments.extend(output.records.into_iter().map(|r| r.into_static()));
+
+        match output.cursor {
+            Some(c) => cursor = Some(c.to_string()),
+            None => break,
+        }
+    }
+
+    Ok(all_comments)
+}
+
 // ============================================================================
 // Loading functions
 // ============================================================================
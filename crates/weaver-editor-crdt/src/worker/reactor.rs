@@ -76,6 +76,25 @@ pub enum WorkerInput {
     },
     /// Stop collab session
     StopCollab,
+    /// Local awareness state changed (cursor, selection, viewport).
+    ///
+    /// Throttled in the worker; send freely on every change.
+    BroadcastAwareness {
+        /// Cursor position
+        position: usize,
+        /// Selection range if any
+        selection: Option<(usize, usize)>,
+        /// Visible character range if known
+        viewport: Option<(usize, usize)>,
+    },
+    /// Authorized collaborator DIDs after (re-)validating invite+accept pairs.
+    ///
+    /// Sent by the coordinator on the session refresh interval so ops from
+    /// peers whose invites were revoked server-side stop being admitted.
+    SetAuthorizedPeers {
+        /// DIDs with valid authorization (including the resource owner)
+        dids: Vec<SmolStr>,
+    },
 }
 
 /// Output messages from the editor worker.
@@ -128,6 +147,11 @@ pub enum WorkerOutput {
     CollabStopped,
     /// A new peer connected (coordinator should send BroadcastJoin)
     PeerConnected,
+    /// A peer's authorization lapsed; its ops are being ignored
+    PeerRevoked {
+        /// DID whose invite+accept pair no longer validates
+        did: SmolStr,
+    },
 }
 
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
@@ -138,14 +162,16 @@ mod worker_impl {
     use gloo_worker::reactor::{ReactorScope, reactor};
     use weaver_common::transport::CollaboratorInfo;
 
+    #[cfg(feature = "collab")]
+    use crate::coordinator::PeerAuthorization;
     #[cfg(feature = "collab")]
     use jacquard::smol_str::ToSmolStr;
     #[cfg(feature = "collab")]
     use std::sync::Arc;
     #[cfg(feature = "collab")]
     use weaver_common::transport::{
-        CollabMessage, CollabNode, CollabSession, PresenceTracker, SessionEvent, TopicId,
-        parse_node_id,
+        AWARENESS_TIMEOUT, AwarenessThrottle, CollabMessage, CollabNode, CollabSession,
+        PresenceTracker, SessionEvent, TopicId, parse_node_id,
     };
 
     /// Internal event from gossip handler task to main reactor loop.
@@ -170,6 +196,12 @@ mod worker_impl {
         #[cfg(feature = "collab")]
         let mut collab_event_rx: Option<tokio::sync::mpsc::UnboundedReceiver<CollabEvent>> = None;
         #[cfg(feature = "collab")]
+        let mut awareness_throttle = AwarenessThrottle::new();
+        // Shared with the gossip handler task so revocation takes effect on
+        // the next message without restarting the session.
+        #[cfg(feature = "collab")]
+        let peer_auth = Arc::new(std::sync::Mutex::new(PeerAuthorization::new()));
+        #[cfg(feature = "collab")]
         const OUR_COLOR: u32 = 0x4ECDC4FF;
 
         // Helper enum for racing coordinator messages vs collab events
@@ -395,17 +427,44 @@ mod worker_impl {
                                     collab_event_rx = Some(event_rx);
 
                                     // Spawn event handler task that sends via channel
+                                    let peer_auth = peer_auth.clone();
+                                    let ack_session = session.clone();
                                     wasm_bindgen_futures::spawn_local(async move {
                                         let mut presence = PresenceTracker::new();
 
                                         while let Some(Ok(event)) = events.next().await {
                                             match event {
                                                 SessionEvent::Message { from, message } => {
+                                                    // Any inbound message counts as liveness,
+                                                    // not just awareness traffic.
+                                                    presence.record_activity(&from);
                                                     match message {
                                                         CollabMessage::LoroUpdate {
                                                             data, ..
                                                         } => {
-                                                            if event_tx
+                                                            // A Join-attributed peer whose invite
+                                                            // was revoked keeps gossiping until it
+                                                            // drops off; gate its ops on the last
+                                                            // validation pass. Unattributed peers
+                                                            // pass through since gossip relays ops
+                                                            // from nodes we never handshook with.
+                                                            let lapsed = presence
+                                                                .get(&from)
+                                                                .is_some_and(|collab| {
+                                                                    peer_auth.lock().is_ok_and(
+                                                                        |auth| {
+                                                                            !auth.is_authorized(
+                                                                                &collab.did,
+                                                                            )
+                                                                        },
+                                                                    )
+                                                                });
+                                                            if lapsed {
+                                                                tracing::warn!(
+                                                                    %from,
+                                                                    "Ignoring update from peer with lapsed authorization"
+                                                                );
+                                                            } else if event_tx
                                                                 .send(CollabEvent::RemoteUpdates {
                                                                     data,
                                                                 })
@@ -422,6 +481,23 @@ mod worker_impl {
                                                             display_name,
                                                         } => {
                                                             tracing::info!(%from, %did, %display_name, "Received Join message");
+                                                            // Deny presence admission to DIDs the
+                                                            // last validation pass rejected, so a
+                                                            // revoked peer cannot re-attribute its
+                                                            // ops by re-joining.
+                                                            let denied = peer_auth
+                                                                .lock()
+                                                                .is_ok_and(|auth| {
+                                                                    !auth.is_authorized(&did)
+                                                                });
+                                                            if denied {
+                                                                tracing::warn!(
+                                                                    %from,
+                                                                    %did,
+                                                                    "Ignoring Join from unauthorized peer"
+                                                                );
+                                                                continue;
+                                                            }
                                                             presence.add_collaborator(
                                                                 from,
                                                                 did,
@@ -463,6 +539,40 @@ mod worker_impl {
                                                             presence.update_cursor(
                                                                 &from, position, selection,
                                                             );
+                                                            presence.prune_stale_cursors(
+                                                                AWARENESS_TIMEOUT,
+                                                            );
+                                                            if event_tx
+                                                                .send(CollabEvent::PresenceChanged(
+                                                                    presence_to_snapshot(&presence),
+                                                                ))
+                                                                .is_err()
+                                                            {
+                                                                tracing::warn!(
+                                                                    "Collab event channel closed"
+                                                                );
+                                                                return;
+                                                            }
+                                                        }
+                                                        CollabMessage::Awareness {
+                                                            position,
+                                                            selection,
+                                                            viewport,
+                                                            color,
+                                                        } => {
+                                                            presence.update_awareness(
+                                                                &from,
+                                                                position,
+                                                                selection,
+                                                                viewport,
+                                                                Some(color),
+                                                            );
+                                                            // Expire cursors of peers that went
+                                                            // quiet while we're processing live
+                                                            // traffic anyway.
+                                                            presence.prune_stale_cursors(
+                                                                AWARENESS_TIMEOUT,
+                                                            );
                                                             if event_tx
                                                                 .send(CollabEvent::PresenceChanged(
                                                                     presence_to_snapshot(&presence),
@@ -475,6 +585,24 @@ mod worker_impl {
                                                                 return;
                                                             }
                                                         }
+                                                        CollabMessage::Heartbeat { seq } => {
+                                                            // Answer probes so the sender can
+                                                            // estimate RTT and loss. Gossip has
+                                                            // no unicast, so the ack is broadcast
+                                                            // and filtered by seq on their side.
+                                                            if let Err(e) = ack_session
+                                                                .broadcast(
+                                                                    &CollabMessage::HeartbeatAck {
+                                                                        seq,
+                                                                    },
+                                                                )
+                                                                .await
+                                                            {
+                                                                tracing::warn!(
+                                                                    "Failed to ack heartbeat: {e}"
+                                                                );
+                                                            }
+                                                        }
                                                         _ => {}
                                                     }
                                                 }
@@ -565,6 +693,30 @@ mod worker_impl {
                             }
                         }
 
+                        #[cfg(feature = "collab")]
+                        WorkerInput::BroadcastAwareness {
+                            position,
+                            selection,
+                            viewport,
+                        } => {
+                            if let Some(ref session) = collab_session {
+                                // The coordinator sends on every change;
+                                // the throttle keeps gossip traffic at a
+                                // sane rate and drops exact repeats.
+                                if awareness_throttle.should_send(position, selection, viewport) {
+                                    let msg = CollabMessage::Awareness {
+                                        position,
+                                        selection,
+                                        viewport,
+                                        color: OUR_COLOR,
+                                    };
+                                    if let Err(e) = session.broadcast(&msg).await {
+                                        tracing::warn!("Awareness broadcast failed: {e}");
+                                    }
+                                }
+                            }
+                        }
+
                         #[cfg(feature = "collab")]
                         WorkerInput::AddPeers { peers } => {
                             tracing::info!(count = peers.len(), "Worker: received AddPeers");
@@ -603,6 +755,26 @@ mod worker_impl {
                             }
                         }
 
+                        #[cfg(feature = "collab")]
+                        WorkerInput::SetAuthorizedPeers { dids } => {
+                            let newly_revoked = match peer_auth.lock() {
+                                Ok(mut auth) => auth.refresh(dids),
+                                Err(_) => Vec::new(),
+                            };
+                            for did in newly_revoked {
+                                tracing::warn!(
+                                    %did,
+                                    "Worker: peer authorization lapsed, ignoring its ops"
+                                );
+                                if let Err(e) = scope.send(WorkerOutput::PeerRevoked { did }).await
+                                {
+                                    tracing::error!(
+                                        "Failed to send PeerRevoked to coordinator: {e}"
+                                    );
+                                }
+                            }
+                        }
+
                         #[cfg(feature = "collab")]
                         WorkerInput::StopCollab => {
                             collab_session = None;
@@ -634,6 +806,10 @@ mod worker_impl {
                         #[cfg(not(feature = "collab"))]
                         WorkerInput::BroadcastCursor { .. } => {}
                         #[cfg(not(feature = "collab"))]
+                        WorkerInput::BroadcastAwareness { .. } => {}
+                        #[cfg(not(feature = "collab"))]
+                        WorkerInput::SetAuthorizedPeers { .. } => {}
+                        #[cfg(not(feature = "collab"))]
                         WorkerInput::StopCollab => {
                             if let Err(e) = scope.send(WorkerOutput::CollabStopped).await {
                                 tracing::error!("Failed to send CollabStopped to coordinator: {e}");
@@ -755,6 +931,8 @@ mod worker_impl {
                     WorkerInput::AddPeers { .. } => {}
                     WorkerInput::BroadcastJoin { .. } => {}
                     WorkerInput::BroadcastCursor { .. } => {}
+                    WorkerInput::BroadcastAwareness { .. } => {}
+                    WorkerInput::SetAuthorizedPeers { .. } => {}
                     WorkerInput::StopCollab => {
                         if let Err(e) = scope.send(WorkerOutput::CollabStopped).await {
                             tracing::error!("Failed to send CollabStopped to coordinator: {e}");
@@ -769,6 +947,7 @@ mod worker_impl {
     #[cfg(feature = "collab")]
     fn presence_to_snapshot(tracker: &PresenceTracker) -> PresenceSnapshot {
         use jacquard::smol_str::ToSmolStr;
+        use weaver_common::transport::{ConnectionState, Liveness};
         let collaborators = tracker
             .collaborators()
             .map(|c| CollaboratorInfo {
@@ -778,6 +957,14 @@ mod worker_impl {
                 color: c.color,
                 cursor_position: c.cursor.as_ref().map(|cur| cur.position),
                 selection: c.cursor.as_ref().and_then(|cur| cur.selection),
+                viewport: c.cursor.as_ref().and_then(|cur| cur.viewport),
+                connection_state: match tracker.liveness(&c.node_id) {
+                    Some(Liveness::Stale) => ConnectionState::Stale,
+                    Some(Liveness::Disconnected) => ConnectionState::Disconnected,
+                    _ => ConnectionState::Active,
+                },
+                rtt_ms: c.connection.rtt.map(|rtt| rtt.as_millis() as u32),
+                packet_loss: (c.connection.packet_loss > 0.0).then_some(c.connection.packet_loss),
             })
             .collect();
 
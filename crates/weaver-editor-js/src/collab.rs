@@ -16,9 +16,12 @@ use weaver_editor_core::{
     CursorPlatform, EditorDocument, EditorImageResolver, PlainEditor, RenderCache, TextBuffer,
     apply_formatting, execute_action_with_clipboard, render_paragraphs_incremental,
 };
-use weaver_editor_crdt::{LoroTextBuffer, VersionVector};
+use weaver_editor_crdt::{CrdtDocument, LoroTextBuffer, VersionVector};
 
 use crate::actions::{ActionKind, parse_action};
+use crate::subscriptions::{
+    EventKind, EventSubscriptions, JsSubscription, SelectionSnapshot, SyncStateSnapshot,
+};
 use crate::types::{
     EntryEmbeds, EntryJson, FinalizedImage, JsParagraphRender, JsResolvedContent, PendingImage,
 };
@@ -48,6 +51,7 @@ pub struct JsCollabEditor {
     // Mount state
     editor_id: Option<String>,
     on_change: Option<js_sys::Function>,
+    subscriptions: EventSubscriptions,
 
     // Collab state
     resource_uri: String,
@@ -90,6 +94,7 @@ impl JsCollabEditor {
             paragraphs: Vec::new(),
             editor_id: None,
             on_change: None,
+            subscriptions: EventSubscriptions::new(),
             resource_uri: resource_uri.to_string(),
             collab_topic: Some(topic),
             on_session_needed: None,
@@ -124,6 +129,7 @@ impl JsCollabEditor {
             paragraphs: Vec::new(),
             editor_id: None,
             on_change: None,
+            subscriptions: EventSubscriptions::new(),
             resource_uri: resource_uri.to_string(),
             collab_topic: Some(topic),
             on_session_needed: None,
@@ -158,6 +164,7 @@ impl JsCollabEditor {
             paragraphs: Vec::new(),
             editor_id: None,
             on_change: None,
+            subscriptions: EventSubscriptions::new(),
             resource_uri: resource_uri.to_string(),
             collab_topic: Some(topic),
             on_session_needed: None,
@@ -235,6 +242,43 @@ impl JsCollabEditor {
         self.doc.buffer().export_snapshot()
     }
 
+    /// Export a portable document snapshot: the binary Loro snapshot
+    /// wrapped with format version and export metadata.
+    ///
+    /// Suitable for backups and transferring a document between
+    /// accounts; feed the bytes back to `importDocument`.
+    #[wasm_bindgen(js_name = exportDocument)]
+    pub fn export_document(&self) -> Result<Vec<u8>, JsError> {
+        let snapshot = weaver_editor_crdt::DocumentSnapshot::new(
+            self.doc.buffer().export_snapshot(),
+            self.doc.buffer().version().encode(),
+        );
+        snapshot
+            .to_bytes()
+            .map_err(|e| JsError::new(&format!("Export failed: {}", e)))
+    }
+
+    /// Import a portable document snapshot produced by `exportDocument`.
+    ///
+    /// Merges the snapshot's full history into this document (CRDT
+    /// merge, not replace), so importing into a non-empty editor
+    /// converges like a live sync.
+    #[wasm_bindgen(js_name = importDocument)]
+    pub fn import_document(&mut self, data: &[u8]) -> Result<(), JsError> {
+        let snapshot = weaver_editor_crdt::DocumentSnapshot::from_bytes(data)
+            .map_err(|e| JsError::new(&format!("Invalid document snapshot: {}", e)))?;
+
+        self.doc
+            .buffer_mut()
+            .import(&snapshot.snapshot)
+            .map_err(|e| JsError::new(&format!("Import failed: {}", e)))?;
+
+        self.render_and_update_dom();
+        self.notify_change();
+
+        Ok(())
+    }
+
     /// Export updates since a given version.
     ///
     /// Returns null if no changes since that version.
@@ -491,6 +535,7 @@ impl JsCollabEditor {
         self.doc.set_cursor_offset(offset);
         // Sync Loro cursor for CRDT-aware tracking
         self.doc.buffer().sync_cursor(offset);
+        self.emit_selection_change();
     }
 
     #[wasm_bindgen(js_name = getLength)]
@@ -510,6 +555,40 @@ impl JsCollabEditor {
         self.doc.can_redo()
     }
 
+    // === Subscriptions ===
+
+    /// Subscribe to content changes (local edits and remote imports).
+    ///
+    /// The callback fires with no arguments; read the new state through
+    /// accessors. Returns a handle whose `unsubscribe()` stops the
+    /// notifications.
+    #[wasm_bindgen(js_name = onChange)]
+    pub fn on_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions.subscribe(EventKind::Change, callback)
+    }
+
+    /// Subscribe to cursor/selection changes.
+    ///
+    /// The callback receives `{ cursorOffset, anchor, head }`; `anchor`
+    /// and `head` are null when there is no active selection. Only fires
+    /// when the selection actually moves.
+    #[wasm_bindgen(js_name = onSelectionChange)]
+    pub fn on_selection_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions
+            .subscribe(EventKind::SelectionChange, callback)
+    }
+
+    /// Subscribe to CRDT sync state changes.
+    ///
+    /// The callback receives `{ hasUnsyncedChanges, queuedDiffs, hasRoot }`
+    /// and only fires when one of those actually flips — e.g. when a local
+    /// edit creates unsynced changes or a sync marks them flushed.
+    #[wasm_bindgen(js_name = onSyncStateChange)]
+    pub fn on_sync_state_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions
+            .subscribe(EventKind::SyncStateChange, callback)
+    }
+
     // === Mounting ===
 
     #[wasm_bindgen]
@@ -768,6 +847,32 @@ impl JsCollabEditor {
             let this = JsValue::null();
             let _ = callback.call0(&this);
         }
+        self.subscriptions.emit_change();
+        // Edits move the cursor and dirty the sync state, so both
+        // notifications piggyback here; the registry dedupes no-ops.
+        self.emit_selection_change();
+        self.emit_sync_state_change();
+    }
+
+    /// Emit a selection event if the cursor or selection moved.
+    fn emit_selection_change(&self) {
+        let selection = self.doc.selection();
+        self.subscriptions.emit_selection_change(SelectionSnapshot {
+            cursor_offset: self.doc.cursor_offset(),
+            anchor: selection.map(|s| s.anchor),
+            head: selection.map(|s| s.head),
+        });
+    }
+
+    /// Emit a sync state event if the sync state changed.
+    pub(crate) fn emit_sync_state_change(&self) {
+        let buffer = self.doc.buffer();
+        self.subscriptions
+            .emit_sync_state_change(SyncStateSnapshot {
+                has_unsynced_changes: buffer.has_unsynced_changes(),
+                queued_diffs: buffer.queued_diffs(),
+                has_root: buffer.edit_root().is_some(),
+            });
     }
 
     fn build_embeds(&self) -> Option<EntryEmbeds> {
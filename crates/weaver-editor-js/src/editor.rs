@@ -16,6 +16,7 @@ use weaver_editor_core::{
 };
 
 use crate::actions::{ActionKind, parse_action};
+use crate::subscriptions::{EventKind, EventSubscriptions, JsSubscription, SelectionSnapshot};
 use crate::types::{
     EntryEmbeds, EntryJson, FinalizedImage, JsParagraphRender, JsResolvedContent, PendingImage,
 };
@@ -37,6 +38,7 @@ pub struct JsEditor {
     // Mount state
     pub(crate) editor_id: Option<String>,
     pub(crate) on_change: Option<js_sys::Function>,
+    pub(crate) subscriptions: EventSubscriptions,
 
     // Metadata
     title: String,
@@ -67,6 +69,7 @@ impl JsEditor {
             paragraphs: Vec::new(),
             editor_id: None,
             on_change: None,
+            subscriptions: EventSubscriptions::new(),
             title: String::new(),
             path: String::new(),
             tags: Vec::new(),
@@ -92,6 +95,7 @@ impl JsEditor {
             paragraphs: Vec::new(),
             editor_id: None,
             on_change: None,
+            subscriptions: EventSubscriptions::new(),
             title: String::new(),
             path: String::new(),
             tags: Vec::new(),
@@ -120,6 +124,7 @@ impl JsEditor {
             paragraphs: Vec::new(),
             editor_id: None,
             on_change: None,
+            subscriptions: EventSubscriptions::new(),
             title: entry.title,
             path: entry.path,
             tags: entry.tags.unwrap_or_default(),
@@ -365,6 +370,7 @@ impl JsEditor {
     #[wasm_bindgen(js_name = setCursorOffset)]
     pub fn set_cursor_offset(&mut self, offset: usize) {
         self.doc.set_cursor_offset(offset);
+        self.emit_selection_change();
     }
 
     /// Get the document length in characters.
@@ -387,6 +393,29 @@ impl JsEditor {
         self.doc.can_redo()
     }
 
+    // === Subscriptions ===
+
+    /// Subscribe to content changes.
+    ///
+    /// The callback fires after every edit, with no arguments; call
+    /// `getMarkdown()` (or other accessors) to read the new state.
+    /// Returns a handle whose `unsubscribe()` stops the notifications.
+    #[wasm_bindgen(js_name = onChange)]
+    pub fn on_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions.subscribe(EventKind::Change, callback)
+    }
+
+    /// Subscribe to cursor/selection changes.
+    ///
+    /// The callback receives `{ cursorOffset, anchor, head }`; `anchor`
+    /// and `head` are null when there is no active selection. Only fires
+    /// when the selection actually moves.
+    #[wasm_bindgen(js_name = onSelectionChange)]
+    pub fn on_selection_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions
+            .subscribe(EventKind::SelectionChange, callback)
+    }
+
     // === Mounting ===
 
     /// Mount the editor into a container element.
@@ -563,12 +592,25 @@ impl JsEditor {
         }
     }
 
-    /// Notify the onChange callback.
+    /// Notify the onChange callback and event subscriptions.
     pub(crate) fn notify_change(&self) {
         if let Some(ref callback) = self.on_change {
             let this = JsValue::null();
             let _ = callback.call0(&this);
         }
+        self.subscriptions.emit_change();
+        // Edits move the cursor, so piggyback selection notification here.
+        self.emit_selection_change();
+    }
+
+    /// Emit a selection event if the cursor or selection moved.
+    pub(crate) fn emit_selection_change(&self) {
+        let selection = self.doc.selection();
+        self.subscriptions.emit_selection_change(SelectionSnapshot {
+            cursor_offset: self.doc.cursor_offset(),
+            anchor: selection.map(|s| s.anchor),
+            head: selection.map(|s| s.head),
+        });
     }
 }
 
@@ -388,6 +388,10 @@ impl JsEditor {
             &syntax_spans,
             &self.paragraphs,
         );
+
+        // DOM-driven cursor movement (clicks, arrow keys) lands here, so
+        // this is where selection subscribers hear about it.
+        self.emit_selection_change();
     }
 }
 
@@ -0,0 +1,364 @@
+//! Headless document API for non-DOM environments.
+//!
+//! Bots, migrations, and test harnesses need the same editing semantics as
+//! the mounted editor, but run in Node where there is no DOM to mount into.
+//! This wraps the core editor without any `web_sys` element handling:
+//! actions mutate the document directly, HTML comes back as strings, and
+//! clipboard actions go through an in-memory clipboard.
+
+use std::cell::RefCell;
+
+use wasm_bindgen::prelude::*;
+
+use weaver_editor_core::{
+    ClipboardPlatform, EditorDocument, EditorImageResolver, EditorRope, PlainEditor, RenderCache,
+    Selection, TextBuffer, UndoableBuffer, apply_formatting, execute_action_with_clipboard,
+    render_markdown_to_html, render_paragraphs_incremental,
+};
+
+use crate::actions::{ActionKind, parse_action};
+use crate::subscriptions::{EventKind, EventSubscriptions, JsSubscription, SelectionSnapshot};
+use crate::types::{JsParagraphRender, JsResolvedContent};
+
+type InnerEditor = PlainEditor<UndoableBuffer<EditorRope>>;
+
+/// In-memory clipboard so cut/copy/paste actions work without a browser.
+#[derive(Default)]
+struct MemoryClipboard {
+    text: RefCell<Option<String>>,
+}
+
+impl ClipboardPlatform for MemoryClipboard {
+    fn write_text(&self, text: &str) {
+        *self.text.borrow_mut() = Some(text.to_string());
+    }
+
+    fn write_html(&self, _html: &str, plain_text: &str) {
+        // No MIME-typed clipboard headlessly; keep the markdown source.
+        *self.text.borrow_mut() = Some(plain_text.to_string());
+    }
+
+    fn read_text(&self) -> Option<String> {
+        self.text.borrow().clone()
+    }
+}
+
+/// Headless editor document exposed to JavaScript.
+///
+/// Shares the action vocabulary and rendering pipeline with `JsEditor`, so
+/// tooling built against this behaves identically to the mounted editor.
+#[wasm_bindgen]
+pub struct JsHeadlessDocument {
+    doc: InnerEditor,
+    cache: RenderCache,
+    resolved_content: weaver_common::ResolvedContent,
+    image_resolver: EditorImageResolver,
+    entry_index: weaver_common::EntryIndex,
+    paragraphs: Vec<weaver_editor_core::ParagraphRender>,
+    clipboard: MemoryClipboard,
+    subscriptions: EventSubscriptions,
+}
+
+#[wasm_bindgen]
+impl JsHeadlessDocument {
+    /// Create a new empty document.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::from_markdown("")
+    }
+
+    /// Create a document from markdown content.
+    #[wasm_bindgen(js_name = fromMarkdown)]
+    pub fn from_markdown(content: &str) -> Self {
+        let rope = EditorRope::from_str(content);
+        let buffer = UndoableBuffer::new(rope, 100);
+        let doc = PlainEditor::new(buffer);
+
+        Self {
+            doc,
+            cache: RenderCache::default(),
+            resolved_content: weaver_common::ResolvedContent::new(),
+            image_resolver: EditorImageResolver::new(),
+            entry_index: weaver_common::EntryIndex::new(),
+            paragraphs: Vec::new(),
+            clipboard: MemoryClipboard::default(),
+            subscriptions: EventSubscriptions::new(),
+        }
+    }
+
+    /// Set pre-resolved embed content.
+    #[wasm_bindgen(js_name = setResolvedContent)]
+    pub fn set_resolved_content(&mut self, content: JsResolvedContent) {
+        self.resolved_content = content.into_inner();
+    }
+
+    // === Content access ===
+
+    /// Get the markdown content.
+    #[wasm_bindgen(js_name = getMarkdown)]
+    pub fn get_markdown(&self) -> String {
+        self.doc.content_string()
+    }
+
+    /// Replace the entire content with new markdown.
+    ///
+    /// Recorded as a single edit, so it can be undone.
+    #[wasm_bindgen(js_name = setMarkdown)]
+    pub fn set_markdown(&mut self, content: &str) {
+        let len = self.doc.len_chars();
+        // Replace moves the cursor past the inserted text.
+        self.doc.replace(0..len, content);
+        self.notify_change();
+    }
+
+    /// Render the document to clean HTML.
+    ///
+    /// Uses the same renderer as publishing, without editor artifacts
+    /// (syntax spans, zero-width characters).
+    #[wasm_bindgen(js_name = getHtml)]
+    pub fn get_html(&self) -> Result<String, JsError> {
+        let markdown = self.doc.content_string();
+        render_markdown_to_html(&markdown).ok_or_else(|| JsError::new("Failed to render markdown"))
+    }
+
+    // === Actions ===
+
+    /// Execute an editor action.
+    ///
+    /// Accepts the same action objects as `JsEditor.executeAction`.
+    /// Cut/copy/paste operate on this document's in-memory clipboard.
+    #[wasm_bindgen(js_name = executeAction)]
+    pub fn execute_action(&mut self, action: JsValue) -> Result<(), JsError> {
+        let js_action = parse_action(action)?;
+        let kind = js_action.to_action_kind();
+
+        match kind {
+            ActionKind::Editor(editor_action) => {
+                execute_action_with_clipboard(&mut self.doc, &editor_action, &self.clipboard);
+            }
+            ActionKind::Format(format_action) => {
+                apply_formatting(&mut self.doc, format_action);
+            }
+        }
+
+        self.notify_change();
+        Ok(())
+    }
+
+    // === Clipboard ===
+
+    /// Read the in-memory clipboard (filled by cut/copy actions).
+    #[wasm_bindgen(js_name = getClipboardText)]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        self.clipboard.read_text()
+    }
+
+    /// Set the in-memory clipboard (read by paste actions).
+    #[wasm_bindgen(js_name = setClipboardText)]
+    pub fn set_clipboard_text(&mut self, text: &str) {
+        self.clipboard.write_text(text);
+    }
+
+    // === Offset queries ===
+
+    /// Get the document length in characters.
+    #[wasm_bindgen(js_name = getLength)]
+    pub fn get_length(&self) -> usize {
+        self.doc.len_chars()
+    }
+
+    /// Get the document length in bytes (UTF-8).
+    #[wasm_bindgen(js_name = getLengthBytes)]
+    pub fn get_length_bytes(&self) -> usize {
+        self.doc.len_bytes()
+    }
+
+    /// Convert a char offset to a byte offset.
+    #[wasm_bindgen(js_name = charToByte)]
+    pub fn char_to_byte(&self, char_offset: usize) -> usize {
+        self.doc.char_to_byte(char_offset)
+    }
+
+    /// Convert a byte offset to a char offset.
+    #[wasm_bindgen(js_name = byteToChar)]
+    pub fn byte_to_char(&self, byte_offset: usize) -> usize {
+        self.doc.byte_to_char(byte_offset)
+    }
+
+    /// Get the text in a char range.
+    #[wasm_bindgen(js_name = sliceText)]
+    pub fn slice_text(&self, start: usize, end: usize) -> Result<String, JsError> {
+        self.doc
+            .slice(start..end)
+            .map(|s| s.to_string())
+            .ok_or_else(|| JsError::new("Range out of bounds"))
+    }
+
+    /// Get the rendered paragraph containing a char offset, or null.
+    ///
+    /// Paragraphs carry editor-fidelity HTML and char ranges, matching
+    /// `JsEditor.getParagraphs`.
+    #[wasm_bindgen(js_name = paragraphAt)]
+    pub fn paragraph_at(&mut self, char_offset: usize) -> Result<JsValue, JsError> {
+        self.render();
+        let para = self
+            .paragraphs
+            .iter()
+            .find(|p| p.char_range.contains(&char_offset))
+            .map(JsParagraphRender::from);
+        serde_wasm_bindgen::to_value(&para)
+            .map_err(|e| JsError::new(&format!("Serialization error: {}", e)))
+    }
+
+    /// Get all rendered paragraphs as JS objects.
+    #[wasm_bindgen(js_name = getParagraphs)]
+    pub fn get_paragraphs(&mut self) -> Result<JsValue, JsError> {
+        self.render();
+        let js_paras: Vec<JsParagraphRender> = self
+            .paragraphs
+            .iter()
+            .map(JsParagraphRender::from)
+            .collect();
+        serde_wasm_bindgen::to_value(&js_paras)
+            .map_err(|e| JsError::new(&format!("Serialization error: {}", e)))
+    }
+
+    // === Cursor/selection ===
+
+    /// Get the current cursor offset.
+    #[wasm_bindgen(js_name = getCursorOffset)]
+    pub fn get_cursor_offset(&self) -> usize {
+        self.doc.cursor_offset()
+    }
+
+    /// Set the cursor offset.
+    #[wasm_bindgen(js_name = setCursorOffset)]
+    pub fn set_cursor_offset(&mut self, offset: usize) {
+        self.doc.set_cursor_offset(offset);
+        self.emit_selection_change();
+    }
+
+    /// Get the selection as `{ cursorOffset, anchor, head }`, or null.
+    #[wasm_bindgen(js_name = getSelection)]
+    pub fn get_selection(&self) -> Result<JsValue, JsError> {
+        let selection = self.doc.selection().map(|s| SelectionSnapshot {
+            cursor_offset: self.doc.cursor_offset(),
+            anchor: Some(s.anchor),
+            head: Some(s.head),
+        });
+        serde_wasm_bindgen::to_value(&selection)
+            .map_err(|e| JsError::new(&format!("Serialization error: {}", e)))
+    }
+
+    /// Set the selection; the cursor moves to `head`.
+    #[wasm_bindgen(js_name = setSelection)]
+    pub fn set_selection(&mut self, anchor: usize, head: usize) {
+        self.doc.set_selection(Some(Selection::new(anchor, head)));
+        self.doc.set_cursor_offset(head);
+        self.emit_selection_change();
+    }
+
+    /// Clear the selection, leaving the cursor in place.
+    #[wasm_bindgen(js_name = clearSelection)]
+    pub fn clear_selection(&mut self) {
+        self.doc.set_selection(None);
+        self.emit_selection_change();
+    }
+
+    // === Undo/redo ===
+
+    /// Check if undo is available.
+    #[wasm_bindgen(js_name = canUndo)]
+    pub fn can_undo(&self) -> bool {
+        self.doc.can_undo()
+    }
+
+    /// Check if redo is available.
+    #[wasm_bindgen(js_name = canRedo)]
+    pub fn can_redo(&self) -> bool {
+        self.doc.can_redo()
+    }
+
+    // === Entry index (for wikilinks) ===
+
+    /// Add an entry to the wikilink index.
+    #[wasm_bindgen(js_name = addEntryToIndex)]
+    pub fn add_entry_to_index(&mut self, title: &str, path: &str, canonical_url: &str) {
+        self.entry_index
+            .add_entry(title, path, canonical_url.to_string());
+    }
+
+    /// Clear the entry index.
+    #[wasm_bindgen(js_name = clearEntryIndex)]
+    pub fn clear_entry_index(&mut self) {
+        self.entry_index = weaver_common::EntryIndex::new();
+    }
+
+    // === Subscriptions ===
+
+    /// Subscribe to content changes.
+    ///
+    /// Returns a handle whose `unsubscribe()` stops the notifications.
+    #[wasm_bindgen(js_name = onChange)]
+    pub fn on_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions.subscribe(EventKind::Change, callback)
+    }
+
+    /// Subscribe to cursor/selection changes.
+    ///
+    /// The callback receives `{ cursorOffset, anchor, head }`.
+    #[wasm_bindgen(js_name = onSelectionChange)]
+    pub fn on_selection_change_subscription(&self, callback: js_sys::Function) -> JsSubscription {
+        self.subscriptions
+            .subscribe(EventKind::SelectionChange, callback)
+    }
+}
+
+impl Default for JsHeadlessDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Internal methods (not exposed to JS)
+impl JsHeadlessDocument {
+    /// Re-render paragraphs through the incremental cache.
+    ///
+    /// Unlike the mounted editor this happens lazily, on paragraph queries,
+    /// since there is no DOM to keep in sync after every action.
+    fn render(&mut self) {
+        let cursor_offset = self.doc.cursor_offset();
+        let last_edit = self.doc.last_edit();
+
+        let result = render_paragraphs_incremental(
+            self.doc.buffer(),
+            Some(&self.cache),
+            cursor_offset,
+            last_edit.as_ref(),
+            Some(&self.image_resolver),
+            Some(&self.entry_index),
+            &self.resolved_content,
+        );
+
+        self.paragraphs = result.paragraphs;
+        self.cache = result.cache;
+        self.doc.set_last_edit(None); // Clear after using
+    }
+
+    /// Notify change subscriptions.
+    fn notify_change(&self) {
+        self.subscriptions.emit_change();
+        // Edits move the cursor, so piggyback selection notification here.
+        self.emit_selection_change();
+    }
+
+    /// Emit a selection event if the cursor or selection moved.
+    fn emit_selection_change(&self) {
+        let selection = self.doc.selection();
+        self.subscriptions.emit_selection_change(SelectionSnapshot {
+            cursor_offset: self.doc.cursor_offset(),
+            anchor: selection.map(|s| s.anchor),
+            head: selection.map(|s| s.head),
+        });
+    }
+}
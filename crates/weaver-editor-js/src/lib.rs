@@ -10,6 +10,8 @@
 mod actions;
 mod editor;
 mod events;
+mod headless;
+mod subscriptions;
 mod types;
 
 #[cfg(feature = "collab")]
@@ -17,6 +19,8 @@ mod collab;
 
 pub use actions::*;
 pub use editor::*;
+pub use headless::JsHeadlessDocument;
+pub use subscriptions::JsSubscription;
 pub use types::*;
 
 #[cfg(feature = "collab")]
@@ -0,0 +1,162 @@
+//! Event subscriptions for the TypeScript view layer.
+//!
+//! The editors expose imperative calls; framework wrappers (React, Svelte)
+//! need push-style change notification to bind state without polling. This
+//! module provides callback registration with unsubscribe handles, emitted
+//! from the same internal paths that drive DOM updates.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use wasm_bindgen::prelude::*;
+
+/// Which event stream a subscription is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EventKind {
+    /// Document content changed (local edit or remote import).
+    Change,
+    /// Cursor offset or selection range changed.
+    SelectionChange,
+    /// CRDT sync state changed (unsynced edits, queue depth, edit root).
+    SyncStateChange,
+}
+
+/// Cursor/selection payload for `SelectionChange` events.
+///
+/// `anchor`/`head` are null when there is no active selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SelectionSnapshot {
+    pub cursor_offset: usize,
+    pub anchor: Option<usize>,
+    pub head: Option<usize>,
+}
+
+/// Sync state payload for `SyncStateChange` events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SyncStateSnapshot {
+    /// Whether there are local edits not yet synced to the PDS.
+    pub has_unsynced_changes: bool,
+    /// Number of diffs waiting in the offline queue.
+    pub queued_diffs: usize,
+    /// Whether an edit root exists (synced at least once).
+    pub has_root: bool,
+}
+
+#[derive(Default)]
+struct SubscriptionStore {
+    next_id: u64,
+    callbacks: HashMap<u64, (EventKind, js_sys::Function)>,
+    /// Last emitted snapshots, used to suppress no-op notifications.
+    last_selection: Option<SelectionSnapshot>,
+    last_sync_state: Option<SyncStateSnapshot>,
+}
+
+/// Callback registry shared between an editor and its subscription handles.
+#[derive(Clone, Default)]
+pub(crate) struct EventSubscriptions {
+    store: Rc<RefCell<SubscriptionStore>>,
+}
+
+impl EventSubscriptions {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a callback and return its unsubscribe handle.
+    pub(crate) fn subscribe(&self, kind: EventKind, callback: js_sys::Function) -> JsSubscription {
+        let mut store = self.store.borrow_mut();
+        let id = store.next_id;
+        store.next_id += 1;
+        store.callbacks.insert(id, (kind, callback));
+
+        JsSubscription {
+            store: Rc::downgrade(&self.store),
+            id,
+        }
+    }
+
+    /// Emit a content-change event (no payload).
+    pub(crate) fn emit_change(&self) {
+        self.emit(EventKind::Change, &JsValue::NULL);
+    }
+
+    /// Emit a selection-change event if the selection actually moved.
+    pub(crate) fn emit_selection_change(&self, snapshot: SelectionSnapshot) {
+        {
+            let mut store = self.store.borrow_mut();
+            if store.last_selection == Some(snapshot) {
+                return;
+            }
+            store.last_selection = Some(snapshot);
+        }
+
+        let payload = serde_wasm_bindgen::to_value(&snapshot).unwrap_or(JsValue::NULL);
+        self.emit(EventKind::SelectionChange, &payload);
+    }
+
+    /// Emit a sync-state event if the sync state actually changed.
+    pub(crate) fn emit_sync_state_change(&self, snapshot: SyncStateSnapshot) {
+        {
+            let mut store = self.store.borrow_mut();
+            if store.last_sync_state == Some(snapshot) {
+                return;
+            }
+            store.last_sync_state = Some(snapshot);
+        }
+
+        let payload = serde_wasm_bindgen::to_value(&snapshot).unwrap_or(JsValue::NULL);
+        self.emit(EventKind::SyncStateChange, &payload);
+    }
+
+    fn emit(&self, kind: EventKind, payload: &JsValue) {
+        // Clone the callbacks out before calling: a callback may subscribe
+        // or unsubscribe reentrantly, which would otherwise panic on the
+        // RefCell borrow.
+        let callbacks: Vec<js_sys::Function> = self
+            .store
+            .borrow()
+            .callbacks
+            .values()
+            .filter(|(k, _)| *k == kind)
+            .map(|(_, f)| f.clone())
+            .collect();
+
+        let this = JsValue::null();
+        for callback in callbacks {
+            let _ = callback.call1(&this, payload);
+        }
+    }
+}
+
+/// Handle returned from `onChange`/`onSelectionChange`/`onSyncStateChange`.
+///
+/// Call `unsubscribe()` to stop receiving events. Dropping the handle
+/// without unsubscribing keeps the subscription alive for the lifetime
+/// of the editor.
+#[wasm_bindgen]
+pub struct JsSubscription {
+    store: Weak<RefCell<SubscriptionStore>>,
+    id: u64,
+}
+
+#[wasm_bindgen]
+impl JsSubscription {
+    /// Remove this subscription. Safe to call more than once.
+    #[wasm_bindgen]
+    pub fn unsubscribe(&self) {
+        if let Some(store) = self.store.upgrade() {
+            store.borrow_mut().callbacks.remove(&self.id);
+        }
+    }
+
+    /// Whether this subscription is still registered.
+    #[wasm_bindgen(js_name = isActive)]
+    pub fn is_active(&self) -> bool {
+        self.store
+            .upgrade()
+            .is_some_and(|store| store.borrow().callbacks.contains_key(&self.id))
+    }
+}
@@ -53,6 +53,30 @@ impl EmbedWorkerHost {
         self.bridge.send(EmbedWorkerInput::FetchEmbeds { uris });
     }
 
+    /// Request preview cards for external http(s) URLs.
+    ///
+    /// The worker scrapes OpenGraph/oEmbed metadata, caching the results.
+    /// Cards arrive via the callback as `UrlPreviews` messages, cache hits
+    /// first and fetched cards as they resolve.
+    pub fn fetch_url_previews(&self, urls: Vec<String>) {
+        if urls.is_empty() {
+            return;
+        }
+        self.bridge
+            .send(EmbedWorkerInput::FetchUrlPreviews { urls });
+    }
+
+    /// Route url-preview fetches through a CORS proxy or app-server
+    /// endpoint.
+    ///
+    /// The prefix is prepended to the percent-encoded target URL, e.g.
+    /// "https://app.weaver.sh/unfurl?url=". `None` fetches directly,
+    /// which only works for CORS-permissive sites.
+    pub fn set_url_preview_proxy(&self, prefix: Option<String>) {
+        self.bridge
+            .send(EmbedWorkerInput::SetUrlPreviewProxy { prefix });
+    }
+
     /// Clear the worker's embed cache.
     pub fn clear_cache(&self) {
         self.bridge.send(EmbedWorkerInput::ClearCache);
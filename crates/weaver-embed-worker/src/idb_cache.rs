@@ -0,0 +1,149 @@
+//! IndexedDB-backed persistent cache for rendered embeds.
+//!
+//! Unlike the in-memory layer, entries here survive page reloads. Each
+//! entry carries a stored-at timestamp; the worker decides freshness so
+//! stale entries can be served immediately and revalidated in the
+//! background. All failures degrade to a cache miss — IndexedDB being
+//! unavailable (private browsing, quota) must never break embed fetching.
+
+use js_sys::{Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use web_sys::{IdbDatabase, IdbFactory, IdbRequest, IdbTransactionMode};
+
+const DB_NAME: &str = "weaver-embed-cache";
+const DB_VERSION: u32 = 1;
+const STORE: &str = "embeds";
+
+/// One persisted embed: rendered HTML plus when it was written.
+pub(crate) struct StoredEmbed {
+    pub html: String,
+    /// Milliseconds since the epoch (`Date.now()`), comparable across
+    /// page loads, unlike `performance.now()`.
+    pub stored_at: f64,
+}
+
+/// The IndexedDB factory from the worker global scope.
+///
+/// Workers have no `Window`, so go through `js_sys::global()` directly.
+fn factory() -> Option<IdbFactory> {
+    Reflect::get(&js_sys::global(), &JsValue::from_str("indexedDB"))
+        .ok()?
+        .dyn_into::<IdbFactory>()
+        .ok()
+}
+
+/// Adapt an IDB request's success/error events into an awaitable future.
+fn await_request(request: IdbRequest) -> wasm_bindgen_futures::JsFuture {
+    wasm_bindgen_futures::JsFuture::from(Promise::new(&mut |resolve, reject| {
+        let req = request.clone();
+        let on_success = Closure::once_into_js(move || {
+            let result = req.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+        });
+        request.set_onsuccess(Some(on_success.unchecked_ref()));
+
+        let on_error = Closure::once_into_js(move || {
+            let _ = reject.call1(
+                &JsValue::UNDEFINED,
+                &JsValue::from_str("IndexedDB request failed"),
+            );
+        });
+        request.set_onerror(Some(on_error.unchecked_ref()));
+    }))
+}
+
+/// Open (and if necessary create) the embed cache database.
+async fn open() -> Option<IdbDatabase> {
+    let request = factory()?.open_with_u32(DB_NAME, DB_VERSION).ok()?;
+
+    let req = request.clone();
+    let on_upgrade = Closure::once_into_js(move || {
+        let Ok(result) = req.result() else {
+            return;
+        };
+        let Ok(db) = result.dyn_into::<IdbDatabase>() else {
+            return;
+        };
+        if !db.object_store_names().contains(STORE) {
+            let _ = db.create_object_store(STORE);
+        }
+    });
+    request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+
+    await_request(request.unchecked_into::<IdbRequest>())
+        .await
+        .ok()?
+        .dyn_into::<IdbDatabase>()
+        .ok()
+}
+
+/// Look up a persisted embed by AT URI string.
+pub(crate) async fn get(uri: &str) -> Option<StoredEmbed> {
+    let db = open().await?;
+    let tx = db.transaction_with_str(STORE).ok()?;
+    let store = tx.object_store(STORE).ok()?;
+    let request = store.get(&JsValue::from_str(uri)).ok()?;
+    let value = await_request(request).await.ok()?;
+    if value.is_undefined() || value.is_null() {
+        return None;
+    }
+
+    let html = Reflect::get(&value, &JsValue::from_str("html"))
+        .ok()?
+        .as_string()?;
+    let stored_at = Reflect::get(&value, &JsValue::from_str("storedAt"))
+        .ok()?
+        .as_f64()?;
+    Some(StoredEmbed { html, stored_at })
+}
+
+/// Persist a freshly rendered embed, stamped with the current time.
+pub(crate) async fn put(uri: &str, html: &str) {
+    let Some(db) = open().await else {
+        return;
+    };
+    let Ok(tx) = db.transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite) else {
+        return;
+    };
+    let Ok(store) = tx.object_store(STORE) else {
+        return;
+    };
+
+    let entry = js_sys::Object::new();
+    let _ = Reflect::set(&entry, &JsValue::from_str("html"), &JsValue::from_str(html));
+    let _ = Reflect::set(
+        &entry,
+        &JsValue::from_str("storedAt"),
+        &JsValue::from_f64(js_sys::Date::now()),
+    );
+
+    match store.put_with_key(&entry, &JsValue::from_str(uri)) {
+        Ok(request) => {
+            if await_request(request).await.is_err() {
+                tracing::debug!("Failed to persist embed for {uri}");
+            }
+        }
+        Err(_) => tracing::debug!("Failed to persist embed for {uri}"),
+    }
+}
+
+/// Drop every persisted embed (e.g. on session change).
+pub(crate) async fn clear() {
+    let Some(db) = open().await else {
+        return;
+    };
+    let Ok(tx) = db.transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite) else {
+        return;
+    };
+    let Ok(store) = tx.object_store(STORE) else {
+        return;
+    };
+    match store.clear() {
+        Ok(request) => {
+            if await_request(request).await.is_err() {
+                tracing::debug!("Failed to clear persistent embed cache");
+            }
+        }
+        Err(_) => tracing::debug!("Failed to clear persistent embed cache"),
+    }
+}
@@ -1,4 +1,5 @@
-//! Web worker for fetching and caching AT Protocol embeds.
+//! Web worker for fetching and caching AT Protocol embeds and external
+//! link previews.
 //!
 //! This crate provides:
 //! - `EmbedWorker`: The worker implementation (runs in worker thread)
@@ -29,19 +30,52 @@ use std::collections::HashMap;
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum EmbedWorkerInput {
     /// Request embeds for a list of AT URIs.
-    /// Worker returns cached results immediately and fetches missing ones.
+    /// Worker returns cached results immediately and fetches missing ones,
+    /// coalescing requests that arrive close together into batched fetches.
     FetchEmbeds {
         /// AT URIs to fetch (e.g., "at://did:plc:xxx/app.bsky.feed.post/yyy")
         uris: Vec<String>,
     },
+    /// Request preview cards for external http(s) URLs.
+    /// Worker scrapes OpenGraph metadata (and an oEmbed endpoint when the
+    /// page advertises one), returning cached cards immediately and
+    /// fetched ones as they resolve.
+    FetchUrlPreviews {
+        /// Absolute http(s) URLs to unfurl.
+        urls: Vec<String>,
+    },
+    /// Route url-preview fetches through a CORS proxy or app-server
+    /// endpoint. The prefix is prepended to the percent-encoded target
+    /// URL (e.g. "https://app.weaver.sh/unfurl?url="); `None` fetches
+    /// directly, which only works for CORS-permissive sites.
+    SetUrlPreviewProxy { prefix: Option<String> },
     /// Clear the cache (e.g., on session change).
     ClearCache,
 }
 
+/// Structured preview card for an external http(s) link, assembled from
+/// OpenGraph meta tags and oEmbed metadata. The renderer turns these into
+/// external-embed HTML.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct UrlPreviewCard {
+    /// The URL the card describes (as requested, before any proxying).
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Absolute URL of the preview image, when the page provides one.
+    pub image: Option<String>,
+    pub site_name: Option<String>,
+    /// Provider-supplied embed markup from oEmbed (e.g. a video player).
+    /// Consumers must sandbox this before injecting it anywhere.
+    pub embed_html: Option<String>,
+}
+
 /// Output messages from the embed worker.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum EmbedWorkerOutput {
-    /// Embed results (may be partial if some failed).
+    /// Embed results, delivered progressively: cache hits arrive in one
+    /// immediate message, fetched URIs follow as they resolve. A single
+    /// `FetchEmbeds` request may therefore produce several of these.
     Embeds {
         /// Successfully fetched/cached embeds: uri -> rendered HTML.
         results: HashMap<String, String>,
@@ -50,10 +84,26 @@ pub enum EmbedWorkerOutput {
         /// Timing info in milliseconds.
         fetch_ms: f64,
     },
+    /// Preview cards for external URLs, delivered progressively like
+    /// `Embeds`: cache hits in one immediate message, fetched cards as
+    /// they resolve.
+    UrlPreviews {
+        /// Successfully fetched/cached cards: url -> card data.
+        results: HashMap<String, UrlPreviewCard>,
+        /// URLs that failed to unfurl.
+        errors: HashMap<String, String>,
+        /// Timing info in milliseconds.
+        fetch_ms: f64,
+    },
     /// Cache was cleared.
     CacheCleared,
 }
 
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+mod idb_cache;
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+mod unfurl;
+
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
 mod worker_impl {
     use super::*;
@@ -63,15 +113,153 @@ mod worker_impl {
     use jacquard::identity::JacquardResolver;
     use jacquard::prelude::*;
     use jacquard::types::string::AtUri;
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+    use std::rc::Rc;
     use std::time::Duration;
     use weaver_common::cache;
 
+    /// How long to wait for more `FetchEmbeds` requests before flushing a
+    /// coalesced batch, in milliseconds.
+    const COALESCE_WINDOW_MS: u32 = 25;
+    /// Upper bound on concurrent single-URI fetches per flush.
+    const MAX_CONCURRENT_FETCHES: usize = 4;
+    /// How long a persisted embed counts as fresh, matching the in-memory
+    /// TTL. Older entries are served stale and revalidated in the
+    /// background.
+    const FRESH_TTL_MS: f64 = 3600.0 * 1000.0;
+
+    /// A URI queued for fetching and the handlers waiting on it.
+    struct Waiters {
+        uri: AtUri<'static>,
+        handlers: Vec<HandlerId>,
+        /// Stale HTML already delivered to the handlers from the
+        /// persistent cache; set when this fetch is a revalidation.
+        stale_html: Option<String>,
+    }
+
+    /// URIs keyed by their string form, shared across handler invocations
+    /// and the spawned fetch tasks.
+    type WaiterMap = Rc<RefCell<HashMap<String, Waiters>>>;
+
+    /// External URLs currently being unfurled and the handlers waiting on
+    /// each. Previews have no stale-serving path, so plain handler lists
+    /// suffice here.
+    type UrlWaiterMap = Rc<RefCell<HashMap<String, Vec<HandlerId>>>>;
+
     /// Embed worker with persistent cache.
     pub struct EmbedWorker {
         /// Cached rendered embeds with TTL and max capacity.
         cache: cache::Cache<AtUri<'static>, String>,
+        /// Cached preview cards for external URLs.
+        url_cache: cache::Cache<String, UrlPreviewCard>,
         /// Unauthenticated session for public API calls.
         session: UnauthenticatedSession<JacquardResolver>,
+        /// Cache misses waiting for the next coalesced flush.
+        pending: WaiterMap,
+        /// URIs currently being fetched; later requests for the same URI
+        /// register as extra waiters instead of refetching.
+        in_flight: WaiterMap,
+        /// External URLs currently being unfurled.
+        url_in_flight: UrlWaiterMap,
+        /// Proxy prefix for url-preview fetches, settable by the host.
+        preview_proxy: Rc<RefCell<Option<String>>>,
+        /// Whether a flush of the pending set is already scheduled.
+        flush_scheduled: Rc<Cell<bool>>,
+    }
+
+    /// Key previews into the shared IndexedDB store without colliding
+    /// with at:// embed keys.
+    fn preview_idb_key(url: &str) -> String {
+        format!("preview:{url}")
+    }
+
+    /// Respond to every handler waiting on an external URL and drop it
+    /// from the in-flight set.
+    fn respond_for_url(
+        scope: &WorkerScope<EmbedWorker>,
+        url_in_flight: &UrlWaiterMap,
+        url: &str,
+        outcome: Result<UrlPreviewCard, String>,
+        fetch_start: f64,
+    ) {
+        let Some(handlers) = url_in_flight.borrow_mut().remove(url) else {
+            return;
+        };
+
+        let mut results = HashMap::new();
+        let mut errors = HashMap::new();
+        match outcome {
+            Ok(card) => {
+                results.insert(url.to_string(), card);
+            }
+            Err(e) => {
+                errors.insert(url.to_string(), e);
+            }
+        }
+
+        let fetch_ms = weaver_common::perf::now() - fetch_start;
+        for id in handlers {
+            scope.respond(
+                id,
+                EmbedWorkerOutput::UrlPreviews {
+                    results: results.clone(),
+                    errors: errors.clone(),
+                    fetch_ms,
+                },
+            );
+        }
+    }
+
+    /// Respond to every handler waiting on a URI and drop it from the
+    /// in-flight set.
+    fn respond_for_uri(
+        scope: &WorkerScope<EmbedWorker>,
+        in_flight: &WaiterMap,
+        uri_str: &str,
+        outcome: Result<String, String>,
+        fetch_start: f64,
+    ) {
+        let Some(waiters) = in_flight.borrow_mut().remove(uri_str) else {
+            return;
+        };
+
+        // Stale-while-revalidate: when the handlers already saw a stale
+        // copy, only emit a follow-up message if the content actually
+        // changed, and swallow background revalidation failures.
+        if let Some(ref stale) = waiters.stale_html {
+            match &outcome {
+                Ok(html) if html == stale => return,
+                Err(e) => {
+                    tracing::debug!("Embed revalidation failed for {uri_str}: {e}");
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        let mut results = HashMap::new();
+        let mut errors = HashMap::new();
+        match outcome {
+            Ok(html) => {
+                results.insert(uri_str.to_string(), html);
+            }
+            Err(e) => {
+                errors.insert(uri_str.to_string(), e);
+            }
+        }
+
+        let fetch_ms = weaver_common::perf::now() - fetch_start;
+        for id in waiters.handlers {
+            scope.respond(
+                id,
+                EmbedWorkerOutput::Embeds {
+                    results: results.clone(),
+                    errors: errors.clone(),
+                    fetch_ms,
+                },
+            );
+        }
     }
 
     impl Worker for EmbedWorker {
@@ -83,7 +271,13 @@ mod worker_impl {
             Self {
                 // Cache up to 500 embeds, TTL of 1 hour.
                 cache: cache::new_cache(500, Duration::from_secs(3600)),
+                url_cache: cache::new_cache(500, Duration::from_secs(3600)),
                 session: UnauthenticatedSession::default(),
+                pending: Rc::new(RefCell::new(HashMap::new())),
+                in_flight: Rc::new(RefCell::new(HashMap::new())),
+                url_in_flight: Rc::new(RefCell::new(HashMap::new())),
+                preview_proxy: Rc::new(RefCell::new(None)),
+                flush_scheduled: Rc::new(Cell::new(false)),
             }
         }
 
@@ -94,9 +288,9 @@ mod worker_impl {
                 EmbedWorkerInput::FetchEmbeds { uris } => {
                     let mut results = HashMap::new();
                     let mut errors = HashMap::new();
-                    let mut to_fetch = Vec::new();
+                    let mut misses = Vec::new();
 
-                    // Parse URIs and check cache.
+                    // Parse URIs and check the in-memory cache.
                     for uri_str in uris {
                         let at_uri = match AtUri::new_owned(uri_str.clone()) {
                             Ok(u) => u,
@@ -108,13 +302,15 @@ mod worker_impl {
 
                         if let Some(html) = cache::get(&self.cache, &at_uri) {
                             results.insert(uri_str, html);
-                        } else {
-                            to_fetch.push((uri_str, at_uri));
+                            continue;
                         }
+
+                        misses.push((uri_str, at_uri));
                     }
 
-                    // If nothing to fetch, respond immediately.
-                    if to_fetch.is_empty() {
+                    // Memory hits and parse errors go out immediately; the
+                    // rest follows as separate partial responses.
+                    if !results.is_empty() || !errors.is_empty() {
                         scope.respond(
                             id,
                             EmbedWorkerOutput::Embeds {
@@ -123,61 +319,367 @@ mod worker_impl {
                                 fetch_ms: 0.0,
                             },
                         );
+                    }
+                    if misses.is_empty() {
                         return;
                     }
 
-                    // Fetch missing embeds asynchronously.
-                    let session = self.session.clone();
+                    // The persistent layer is async, so the rest of the
+                    // pipeline (IndexedDB lookup, queueing, flush) runs in
+                    // a task.
                     let worker_cache = self.cache.clone();
+                    let pending = self.pending.clone();
+                    let in_flight = self.in_flight.clone();
+                    let flush_scheduled = self.flush_scheduled.clone();
+                    let session = self.session.clone();
                     let scope = scope.clone();
 
                     wasm_bindgen_futures::spawn_local(async move {
-                        // Use weaver-index when use-index feature is enabled.
-                        #[cfg(feature = "use-index")]
-                        {
-                            use jacquard::xrpc::XrpcClient;
-                            use jacquard::url::Url;
-                            if let Ok(url) = Url::parse("https://index.weaver.sh") {
-                                session.set_base_uri(url).await;
+                        let mut cached_results = HashMap::new();
+                        let mut to_queue = Vec::new();
+
+                        for (uri_str, at_uri) in misses {
+                            match idb_cache::get(&uri_str).await {
+                                Some(entry)
+                                    if js_sys::Date::now() - entry.stored_at < FRESH_TTL_MS =>
+                                {
+                                    // Fresh on disk: promote to memory and
+                                    // serve without refetching.
+                                    cache::insert(&worker_cache, at_uri, entry.html.clone());
+                                    cached_results.insert(uri_str, entry.html);
+                                }
+                                Some(entry) => {
+                                    // Stale: serve immediately, revalidate
+                                    // in the background.
+                                    cached_results.insert(uri_str.clone(), entry.html.clone());
+                                    to_queue.push((uri_str, at_uri, Some(entry.html)));
+                                }
+                                None => to_queue.push((uri_str, at_uri, None)),
                             }
                         }
 
-                        let fetch_start = weaver_common::perf::now();
+                        if !cached_results.is_empty() {
+                            scope.respond(
+                                id,
+                                EmbedWorkerOutput::Embeds {
+                                    results: cached_results,
+                                    errors: HashMap::new(),
+                                    fetch_ms: 0.0,
+                                },
+                            );
+                        }
+                        if to_queue.is_empty() {
+                            return;
+                        }
 
-                        for (uri_str, at_uri) in to_fetch {
-                            match weaver_renderer::atproto::fetch_and_render(&at_uri, &session)
-                                .await
-                            {
-                                Ok(html) => {
-                                    cache::insert(&worker_cache, at_uri, html.clone());
-                                    results.insert(uri_str, html);
+                        // Queue the fetches, deduping against work already
+                        // in progress or coalescing in the current window.
+                        {
+                            let mut pending_map = pending.borrow_mut();
+                            let mut in_flight_map = in_flight.borrow_mut();
+                            for (uri_str, at_uri, stale_html) in to_queue {
+                                if let Some(waiters) = in_flight_map.get_mut(&uri_str) {
+                                    waiters.handlers.push(id);
+                                    continue;
                                 }
-                                Err(e) => {
-                                    errors.insert(uri_str, format!("{:?}", e));
+                                if let Some(waiters) = pending_map.get_mut(&uri_str) {
+                                    waiters.handlers.push(id);
+                                    continue;
                                 }
+                                pending_map.insert(
+                                    uri_str,
+                                    Waiters {
+                                        uri: at_uri,
+                                        handlers: vec![id],
+                                        stale_html,
+                                    },
+                                );
                             }
                         }
 
-                        let fetch_ms = weaver_common::perf::now() - fetch_start;
+                        schedule_flush(
+                            &scope,
+                            &session,
+                            &worker_cache,
+                            &pending,
+                            &in_flight,
+                            &flush_scheduled,
+                        );
+                    });
+                }
+
+                EmbedWorkerInput::FetchUrlPreviews { urls } => {
+                    let mut results = HashMap::new();
+                    let mut errors = HashMap::new();
+                    let mut misses = Vec::new();
+
+                    for url in urls {
+                        if !(url.starts_with("https://") || url.starts_with("http://")) {
+                            errors.insert(url, "not an http(s) URL".to_string());
+                            continue;
+                        }
+                        if let Some(card) = cache::get(&self.url_cache, &url) {
+                            results.insert(url, card);
+                            continue;
+                        }
+                        misses.push(url);
+                    }
+
+                    if !results.is_empty() || !errors.is_empty() {
                         scope.respond(
                             id,
-                            EmbedWorkerOutput::Embeds {
+                            EmbedWorkerOutput::UrlPreviews {
                                 results,
                                 errors,
-                                fetch_ms,
+                                fetch_ms: 0.0,
                             },
                         );
+                    }
+                    if misses.is_empty() {
+                        return;
+                    }
+
+                    let url_cache = self.url_cache.clone();
+                    let url_in_flight = self.url_in_flight.clone();
+                    let preview_proxy = self.preview_proxy.clone();
+                    let scope = scope.clone();
+
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let mut cached_results = HashMap::new();
+                        let mut to_fetch = Vec::new();
+
+                        // The persistent store keeps cards as JSON under a
+                        // prefixed key so they share the embed store. Stale
+                        // or unreadable entries are treated as misses:
+                        // preview cards are small enough to just refetch.
+                        for url in misses {
+                            let entry = idb_cache::get(&preview_idb_key(&url)).await;
+                            match entry {
+                                Some(entry)
+                                    if js_sys::Date::now() - entry.stored_at < FRESH_TTL_MS =>
+                                {
+                                    match serde_json::from_str::<UrlPreviewCard>(&entry.html) {
+                                        Ok(card) => {
+                                            cache::insert(&url_cache, url.clone(), card.clone());
+                                            cached_results.insert(url, card);
+                                        }
+                                        Err(_) => to_fetch.push(url),
+                                    }
+                                }
+                                _ => to_fetch.push(url),
+                            }
+                        }
+
+                        if !cached_results.is_empty() {
+                            scope.respond(
+                                id,
+                                EmbedWorkerOutput::UrlPreviews {
+                                    results: cached_results,
+                                    errors: HashMap::new(),
+                                    fetch_ms: 0.0,
+                                },
+                            );
+                        }
+
+                        // Dedupe against unfurls already in progress.
+                        let queue: VecDeque<String> = {
+                            let mut in_flight = url_in_flight.borrow_mut();
+                            to_fetch
+                                .into_iter()
+                                .filter(|url| {
+                                    if let Some(handlers) = in_flight.get_mut(url) {
+                                        handlers.push(id);
+                                        false
+                                    } else {
+                                        in_flight.insert(url.clone(), vec![id]);
+                                        true
+                                    }
+                                })
+                                .collect()
+                        };
+                        if queue.is_empty() {
+                            return;
+                        }
+
+                        let fetch_start = weaver_common::perf::now();
+                        let queue = Rc::new(RefCell::new(queue));
+                        let pool_size = MAX_CONCURRENT_FETCHES.min(queue.borrow().len());
+                        for _ in 0..pool_size {
+                            let queue = queue.clone();
+                            let url_cache = url_cache.clone();
+                            let url_in_flight = url_in_flight.clone();
+                            let preview_proxy = preview_proxy.clone();
+                            let scope = scope.clone();
+                            wasm_bindgen_futures::spawn_local(async move {
+                                loop {
+                                    let next = queue.borrow_mut().pop_front();
+                                    let Some(url) = next else {
+                                        break;
+                                    };
+                                    let proxy = preview_proxy.borrow().clone();
+                                    let outcome = unfurl::unfurl(&url, proxy.as_deref()).await;
+                                    if let Ok(card) = &outcome {
+                                        if let Ok(json) = serde_json::to_string(card) {
+                                            idb_cache::put(&preview_idb_key(&url), &json).await;
+                                        }
+                                        cache::insert(&url_cache, url.clone(), card.clone());
+                                    }
+                                    respond_for_url(
+                                        &scope,
+                                        &url_in_flight,
+                                        &url,
+                                        outcome,
+                                        fetch_start,
+                                    );
+                                }
+                            });
+                        }
                     });
                 }
 
+                EmbedWorkerInput::SetUrlPreviewProxy { prefix } => {
+                    *self.preview_proxy.borrow_mut() = prefix;
+                }
+
                 EmbedWorkerInput::ClearCache => {
-                    // mini-moka doesn't have a clear method, so we just respond.
-                    // The cache will naturally expire entries via TTL.
-                    scope.respond(id, EmbedWorkerOutput::CacheCleared);
+                    // mini-moka has no clear method — in-memory entries just
+                    // expire via TTL — but the persistent layer does.
+                    let scope = scope.clone();
+                    wasm_bindgen_futures::spawn_local(async move {
+                        idb_cache::clear().await;
+                        scope.respond(id, EmbedWorkerOutput::CacheCleared);
+                    });
                 }
             }
         }
     }
+
+    /// Schedule a coalesced flush of the pending set after the batching
+    /// window, unless one is already scheduled.
+    fn schedule_flush(
+        scope: &WorkerScope<EmbedWorker>,
+        session: &UnauthenticatedSession<JacquardResolver>,
+        worker_cache: &cache::Cache<AtUri<'static>, String>,
+        pending: &WaiterMap,
+        in_flight: &WaiterMap,
+        flush_scheduled: &Rc<Cell<bool>>,
+    ) {
+        if pending.borrow().is_empty() || flush_scheduled.get() {
+            return;
+        }
+        flush_scheduled.set(true);
+
+        let scope = scope.clone();
+        let session = session.clone();
+        let worker_cache = worker_cache.clone();
+        let pending = pending.clone();
+        let in_flight = in_flight.clone();
+        let flush_scheduled = flush_scheduled.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            gloo_timers::future::TimeoutFuture::new(COALESCE_WINDOW_MS).await;
+            flush_scheduled.set(false);
+
+            // Move the window's URIs into the in-flight set so
+            // later requests attach as waiters instead of
+            // refetching.
+            let batch: Vec<(String, AtUri<'static>)> = {
+                let mut pending = pending.borrow_mut();
+                let mut in_flight_map = in_flight.borrow_mut();
+                pending
+                    .drain()
+                    .map(|(uri_str, waiters)| {
+                        let at_uri = waiters.uri.clone();
+                        in_flight_map.insert(uri_str.clone(), waiters);
+                        (uri_str, at_uri)
+                    })
+                    .collect()
+            };
+            if batch.is_empty() {
+                return;
+            }
+
+            // Use weaver-index when use-index feature is enabled.
+            #[cfg(feature = "use-index")]
+            {
+                use jacquard::url::Url;
+                use jacquard::xrpc::XrpcClient;
+                if let Ok(url) = Url::parse("https://index.weaver.sh") {
+                    session.set_base_uri(url).await;
+                }
+            }
+
+            let fetch_start = weaver_common::perf::now();
+
+            // Posts go through the appview's batch endpoint in one
+            // call; everything else is fetched individually with
+            // bounded concurrency.
+            let mut posts = Vec::new();
+            let mut singles = VecDeque::new();
+            for (uri_str, at_uri) in batch {
+                if at_uri.collection().map(|c| c.as_ref()) == Some("app.bsky.feed.post") {
+                    posts.push(at_uri);
+                } else {
+                    singles.push_back((uri_str, at_uri));
+                }
+            }
+
+            if !posts.is_empty() {
+                let session = session.clone();
+                let worker_cache = worker_cache.clone();
+                let scope = scope.clone();
+                let in_flight = in_flight.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    let rendered =
+                        weaver_renderer::atproto::fetch_and_render_posts(posts, &session).await;
+                    for (at_uri, result) in rendered {
+                        let uri_str = at_uri.as_str().to_string();
+                        let outcome = match result {
+                            Ok(html) => {
+                                idb_cache::put(&uri_str, &html).await;
+                                cache::insert(&worker_cache, at_uri, html.clone());
+                                Ok(html)
+                            }
+                            Err(e) => Err(format!("{:?}", e)),
+                        };
+                        respond_for_uri(&scope, &in_flight, &uri_str, outcome, fetch_start);
+                    }
+                });
+            }
+
+            // Drain the remaining URIs from a shared queue with a
+            // small pool of concurrent tasks.
+            let queue = Rc::new(RefCell::new(singles));
+            let pool_size = MAX_CONCURRENT_FETCHES.min(queue.borrow().len());
+            for _ in 0..pool_size {
+                let queue = queue.clone();
+                let session = session.clone();
+                let worker_cache = worker_cache.clone();
+                let scope = scope.clone();
+                let in_flight = in_flight.clone();
+                wasm_bindgen_futures::spawn_local(async move {
+                    loop {
+                        let next = queue.borrow_mut().pop_front();
+                        let Some((uri_str, at_uri)) = next else {
+                            break;
+                        };
+                        let outcome =
+                            match weaver_renderer::atproto::fetch_and_render(&at_uri, &session)
+                                .await
+                            {
+                                Ok(html) => {
+                                    idb_cache::put(&uri_str, &html).await;
+                                    cache::insert(&worker_cache, at_uri, html.clone());
+                                    Ok(html)
+                                }
+                                Err(e) => Err(format!("{:?}", e)),
+                            };
+                        respond_for_uri(&scope, &in_flight, &uri_str, outcome, fetch_start);
+                    }
+                });
+            }
+        });
+    }
 }
 
 #[cfg(all(target_family = "wasm", target_os = "unknown"))]
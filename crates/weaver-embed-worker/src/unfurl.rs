@@ -0,0 +1,273 @@
+//! OpenGraph and oEmbed unfurling for external links.
+//!
+//! Fetches a page (optionally through a configurable CORS proxy), scrapes
+//! OpenGraph/Twitter meta tags with a small hand-rolled scanner — pulling
+//! a full HTML parser into the worker binary is not worth it for `<meta>`
+//! tags — and follows an oEmbed discovery link when the page advertises
+//! one. All scanning works on byte offsets into an ASCII-lowercased copy,
+//! which stay valid for the original because ASCII lowercasing preserves
+//! lengths.
+
+use js_sys::{Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::UrlPreviewCard;
+
+/// What scraping a page found: a partial card plus the oEmbed endpoint
+/// advertised by the page, if any.
+pub(crate) struct PageMeta {
+    pub card: UrlPreviewCard,
+    pub oembed_url: Option<String>,
+}
+
+/// Fetch and assemble a preview card for an external URL.
+pub(crate) async fn unfurl(url: &str, proxy: Option<&str>) -> Result<UrlPreviewCard, String> {
+    let html = fetch_text(&proxied(url, proxy)).await?;
+    let PageMeta {
+        mut card,
+        oembed_url,
+    } = parse_page(&html, url);
+
+    if let Some(oembed_url) = oembed_url {
+        // oEmbed failures degrade to the OpenGraph-only card.
+        if let Ok(json) = fetch_text(&proxied(&oembed_url, proxy)).await {
+            merge_oembed(&mut card, &json);
+        }
+    }
+
+    if card.title.is_none() && card.description.is_none() && card.embed_html.is_none() {
+        return Err(format!("no preview metadata found at {url}"));
+    }
+    Ok(card)
+}
+
+/// Apply the configured proxy prefix, if any. The prefix is expected to
+/// end where the encoded target URL goes, e.g.
+/// `https://app.weaver.sh/unfurl?url=`.
+fn proxied(url: &str, proxy: Option<&str>) -> String {
+    match proxy {
+        Some(prefix) => format!(
+            "{prefix}{}",
+            String::from(js_sys::encode_uri_component(url))
+        ),
+        None => url.to_string(),
+    }
+}
+
+/// Fetch a URL as text using the worker global's `fetch`.
+async fn fetch_text(url: &str) -> Result<String, String> {
+    let global = js_sys::global();
+    let fetch_fn: js_sys::Function = Reflect::get(&global, &JsValue::from_str("fetch"))
+        .ok()
+        .and_then(|f| f.dyn_into().ok())
+        .ok_or_else(|| "fetch unavailable in worker scope".to_string())?;
+
+    let promise: Promise = fetch_fn
+        .call1(&global, &JsValue::from_str(url))
+        .map_err(|_| format!("fetch failed for {url}"))?
+        .unchecked_into();
+    let resp: web_sys::Response = JsFuture::from(promise)
+        .await
+        .map_err(|_| format!("network error fetching {url}"))?
+        .dyn_into()
+        .map_err(|_| "fetch returned a non-Response value".to_string())?;
+
+    if !resp.ok() {
+        return Err(format!("{url} returned {}", resp.status()));
+    }
+
+    let text = resp
+        .text()
+        .map_err(|_| format!("response body unavailable for {url}"))?;
+    JsFuture::from(text)
+        .await
+        .map_err(|_| format!("failed to read body from {url}"))?
+        .as_string()
+        .ok_or_else(|| format!("response body from {url} was not text"))
+}
+
+/// Scrape OpenGraph/Twitter meta tags, the `<title>` fallback, and the
+/// oEmbed discovery link out of a page.
+pub(crate) fn parse_page(html: &str, url: &str) -> PageMeta {
+    let lower = html.to_ascii_lowercase();
+    let mut card = UrlPreviewCard {
+        url: url.to_string(),
+        ..Default::default()
+    };
+
+    for tag in tags(html, &lower, "meta") {
+        let Some(key) = attr_value(tag, "property").or_else(|| attr_value(tag, "name")) else {
+            continue;
+        };
+        let Some(content) = attr_value(tag, "content") else {
+            continue;
+        };
+        if content.is_empty() {
+            continue;
+        }
+        // First occurrence wins; pages list `og:` tags before `twitter:`
+        // fallbacks, so precedence follows document order.
+        match key.to_ascii_lowercase().as_str() {
+            "og:title" | "twitter:title" => {
+                if card.title.is_none() {
+                    card.title = Some(content);
+                }
+            }
+            "og:description" | "twitter:description" | "description" => {
+                if card.description.is_none() {
+                    card.description = Some(content);
+                }
+            }
+            "og:image" | "og:image:url" | "twitter:image" => {
+                if card.image.is_none() {
+                    card.image = Some(content);
+                }
+            }
+            "og:site_name" => {
+                if card.site_name.is_none() {
+                    card.site_name = Some(content);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if card.title.is_none() {
+        card.title = title_element(html, &lower);
+    }
+
+    let mut oembed_url = None;
+    for tag in tags(html, &lower, "link") {
+        let Some(ty) = attr_value(tag, "type") else {
+            continue;
+        };
+        if ty.eq_ignore_ascii_case("application/json+oembed") {
+            oembed_url = attr_value(tag, "href");
+            break;
+        }
+    }
+
+    PageMeta { card, oembed_url }
+}
+
+/// Fold an oEmbed response into the card. oEmbed owns the embeddable
+/// `html` payload; for everything else, OpenGraph values already found
+/// take precedence.
+pub(crate) fn merge_oembed(card: &mut UrlPreviewCard, json: &str) {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return;
+    };
+    let field = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+    };
+    if card.title.is_none() {
+        card.title = field("title");
+    }
+    if card.site_name.is_none() {
+        card.site_name = field("provider_name");
+    }
+    if card.image.is_none() {
+        card.image = field("thumbnail_url");
+    }
+    card.embed_html = field("html");
+}
+
+/// Collect the raw text of every `<{name} …>` tag (exclusive of the
+/// closing `>`), matching case-insensitively via the lowered copy.
+fn tags<'a>(html: &'a str, lower: &str, name: &str) -> Vec<&'a str> {
+    let needle = format!("<{name}");
+    let mut out = Vec::new();
+    let mut from = 0;
+    while let Some(rel) = lower[from..].find(&needle) {
+        let start = from + rel;
+        let Some(end_rel) = lower[start..].find('>') else {
+            break;
+        };
+        let end = start + end_rel;
+        // Require a delimiter after the name so `<link` does not match
+        // some longer element name.
+        match lower.as_bytes().get(start + needle.len()) {
+            Some(b) if b.is_ascii_whitespace() || *b == b'/' || *b == b'>' => {
+                out.push(&html[start..end]);
+            }
+            _ => {}
+        }
+        from = end + 1;
+    }
+    out
+}
+
+/// Pull an attribute value out of a tag's raw text, handling quoted and
+/// bare values and decoding the entities attribute values commonly carry.
+fn attr_value(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut from = 0;
+    while let Some(rel) = lower[from..].find(name) {
+        let start = from + rel;
+        from = start + name.len();
+        // Require a word boundary before the name so `name` does not
+        // match inside `site_name` or similar.
+        if start > 0 {
+            let prev = bytes[start - 1];
+            if prev.is_ascii_alphanumeric() || prev == b'-' || prev == b'_' || prev == b':' {
+                continue;
+            }
+        }
+        let mut i = start + name.len();
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() || bytes[i] != b'=' {
+            continue;
+        }
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            return None;
+        }
+        let (quote, value_start) = match bytes[i] {
+            q @ (b'"' | b'\'') => (Some(q), i + 1),
+            _ => (None, i),
+        };
+        let value_end = match quote {
+            Some(q) => value_start + lower[value_start..].find(q as char)?,
+            None => {
+                let mut j = value_start;
+                while j < bytes.len() && !bytes[j].is_ascii_whitespace() && bytes[j] != b'/' {
+                    j += 1;
+                }
+                j
+            }
+        };
+        return Some(decode_entities(&tag[value_start..value_end]));
+    }
+    None
+}
+
+/// Extract and clean the document `<title>` as a last-resort card title.
+fn title_element(html: &str, lower: &str) -> Option<String> {
+    let start = lower.find("<title")?;
+    let text_start = start + lower[start..].find('>')? + 1;
+    let text_end = text_start + lower[text_start..].find("</title")?;
+    let title = decode_entities(html[text_start..text_end].trim());
+    (!title.is_empty()).then_some(title)
+}
+
+/// Decode the handful of named entities meta content commonly carries.
+/// Anything more exotic passes through untouched.
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
@@ -0,0 +1,42 @@
+-- Static mirror deploy settings, keyed by notebook
+-- Written by the admin endpoints, read by the static mirror rebuild task
+
+CREATE TABLE IF NOT EXISTS static_mirrors (
+    -- Notebook identity (matches notebooks table)
+    did String,
+    rkey String,
+
+    -- Filesystem destination for the rendered site
+    output_dir String,
+
+    -- Optional deploy hook POSTed after a successful rebuild
+    webhook_url String DEFAULT '',
+
+    -- Disabled mirrors keep their settings but are skipped by the task
+    enabled UInt8 DEFAULT 1,
+
+    -- Timestamps
+    updated_at DateTime64(3) DEFAULT now64(3),
+    indexed_at DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = ReplacingMergeTree(indexed_at)
+ORDER BY (did, rkey);
+
+-- Deploy watermarks for static mirrors
+-- A mirror is stale when the newest entry indexed for its notebook is
+-- more recent than deployed_entry_at
+
+CREATE TABLE IF NOT EXISTS static_mirror_deploys (
+    -- Notebook identity (matches static_mirrors)
+    did String,
+    rkey String,
+
+    -- indexed_at of the newest entry covered by the last deploy
+    deployed_entry_at DateTime64(3) DEFAULT toDateTime64(0, 3),
+
+    -- Timestamps
+    deployed_at DateTime64(3) DEFAULT now64(3),
+    indexed_at DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = ReplacingMergeTree(indexed_at)
+ORDER BY (did, rkey)
@@ -0,0 +1,26 @@
+-- Full-text search table for entries
+-- Populated by MV from raw_records. Plain text is extracted from the entry
+-- markdown at insert time so search queries never scan raw markdown, and the
+-- token bloom filter index lets ClickHouse skip granules without the terms.
+
+CREATE TABLE IF NOT EXISTS entry_search (
+    did String,
+    rkey String,
+    cid String,
+
+    title String DEFAULT '',
+    tags Array(String) DEFAULT [],
+
+    -- Markdown stripped down to searchable prose
+    plain_text String DEFAULT '',
+
+    event_time DateTime64(3),
+    indexed_at DateTime64(3) DEFAULT now64(3),
+
+    -- Soft delete (epoch = not deleted)
+    deleted_at DateTime64(3) DEFAULT toDateTime64(0, 3),
+
+    INDEX idx_entry_search_tokens plain_text TYPE tokenbf_v1(32768, 3, 0) GRANULARITY 4
+)
+ENGINE = ReplacingMergeTree(indexed_at)
+ORDER BY (did, rkey, event_time, cid)
@@ -0,0 +1,38 @@
+-- Populate entry_search from raw_records
+-- The replaceRegexpAll chain strips markdown syntax (fenced code, inline
+-- code, images, wiki links, regular links, HTML tags, heading/quote/list
+-- markers, emphasis) and collapses whitespace, leaving plain prose.
+-- Doing this in the MV keeps a single ingest path (raw_records) and means
+-- the standard incremental-MV backfill repopulates search after a reload.
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS entry_search_mv TO entry_search AS
+SELECT
+    did,
+    rkey,
+    cid,
+    coalesce(record.title, '') as title,
+    JSONExtract(toString(record), 'tags', 'Array(String)') as tags,
+    replaceRegexpAll(
+        replaceRegexpAll(
+            replaceRegexpAll(
+                replaceRegexpAll(
+                    replaceRegexpAll(
+                        replaceRegexpAll(
+                            replaceRegexpAll(
+                                replaceRegexpAll(
+                                    replaceRegexpAll(
+                                        toString(coalesce(record.content, '')),
+                                        '(?s)```.*?```', ' '),
+                                    '`([^`]*)`', '\\1'),
+                                '!\\[([^\\]]*)\\]\\([^)]*\\)', '\\1'),
+                            '\\[\\[([^\\]|]+)(\\|[^\\]]*)?\\]\\]', '\\1'),
+                        '\\[([^\\]]*)\\]\\([^)]*\\)', '\\1'),
+                    '<[^>]+>', ' '),
+                '(?m)^[#>]+\\s*', ''),
+            '(?m)^\\s*[-*+]\\s+', ''),
+        '\\s+', ' ') as plain_text,
+    event_time,
+    indexed_at,
+    if(operation = 'delete', event_time, toDateTime64(0, 3)) as deleted_at
+FROM raw_records
+WHERE collection = 'sh.weaver.notebook.entry'
@@ -0,0 +1,31 @@
+-- Activity timeline table
+-- Populated by MV from raw_records
+--
+-- A narrow companion to entries ordered by event time, so the global
+-- discovery feed can scan recent activity without walking the
+-- (did, rkey)-ordered entries table. Rows are joined back to entries at
+-- query time for hydration, which also drops deleted entries.
+
+CREATE TABLE IF NOT EXISTS timeline (
+    -- Recency-first ordering: feed queries are always "latest N".
+    event_time DateTime64(3),
+
+    -- Identity
+    did String,
+    rkey String,
+    cid String,
+
+    -- Whether this event first published the entry or updated it.
+    action LowCardinality(String),
+
+    -- Filterable facets
+    tags Array(String) DEFAULT [],
+    -- BCP-47 tag from the record; empty when the record declares none.
+    -- The entry lexicon does not require a language field, so this is
+    -- best-effort.
+    language LowCardinality(String) DEFAULT '',
+
+    indexed_at DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = ReplacingMergeTree(indexed_at)
+ORDER BY (event_time, did, rkey, cid)
@@ -0,0 +1,19 @@
+-- Populate timeline from raw_records
+--
+-- Deletes are not written here: feed queries join timeline back to
+-- entries and the entries-side deleted_at filter already hides deleted
+-- rows, so tombstones in the timeline would only add noise.
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS timeline_mv TO timeline AS
+SELECT
+    event_time,
+    did,
+    rkey,
+    cid,
+    if(operation = 'create', 'published', 'updated') as action,
+    JSONExtract(toString(record), 'tags', 'Array(String)') as tags,
+    JSONExtractString(toString(record), 'language') as language,
+    indexed_at
+FROM raw_records
+WHERE collection = 'sh.weaver.notebook.entry'
+  AND operation != 'delete'
@@ -0,0 +1,20 @@
+-- Backfill progress tracking
+--
+-- One row per (did, collection) being backfilled. The cursor is the
+-- com.atproto.repo.listRecords pagination cursor, saved after each page
+-- so an interrupted backfill resumes mid-collection instead of starting
+-- over.
+
+CREATE TABLE IF NOT EXISTS backfill_cursors (
+    did String,
+    collection String,
+    -- listRecords cursor; empty means start from the beginning.
+    cursor String DEFAULT '',
+    -- 'in_progress', 'done', or 'failed'.
+    status LowCardinality(String),
+    records_indexed UInt64 DEFAULT 0,
+    error String DEFAULT '',
+    updated_at DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = ReplacingMergeTree(updated_at)
+ORDER BY (did, collection)
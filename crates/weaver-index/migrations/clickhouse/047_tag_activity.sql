@@ -0,0 +1,29 @@
+-- Tag application table
+-- Populated by MV from raw_records
+--
+-- One row per tag carried by an entry or notebook, so tag pages and
+-- popularity rankings can aggregate by tag without array-scanning the
+-- entries and notebooks tables. Counts are computed over distinct
+-- (kind, did, rkey) so record updates do not inflate them; deletes are
+-- not reflected here, so aggregates are slight overcounts for resources
+-- that were later removed.
+
+CREATE TABLE IF NOT EXISTS tag_activity (
+    -- Tag-first ordering: queries are always per-tag scans or GROUP BY tag.
+    tag String,
+
+    -- 'entry' or 'notebook'.
+    kind LowCardinality(String),
+
+    -- Identity of the tagged resource
+    did String,
+    rkey String,
+    cid String,
+
+    -- When the tag was applied (record create/update time).
+    event_time DateTime64(3),
+
+    indexed_at DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = ReplacingMergeTree(indexed_at)
+ORDER BY (tag, kind, did, rkey)
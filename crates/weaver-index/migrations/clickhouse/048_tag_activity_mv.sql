@@ -0,0 +1,20 @@
+-- Populate tag_activity from raw_records
+--
+-- Only author-applied tags (the tags array on the record itself) are
+-- indexed here. Deletes are not written: tag pages join back to
+-- entries/notebooks whose deleted_at filter hides removed resources.
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS tag_activity_mv TO tag_activity AS
+SELECT
+    tag,
+    if(collection = 'sh.weaver.notebook.entry', 'entry', 'notebook') as kind,
+    did,
+    rkey,
+    cid,
+    event_time,
+    indexed_at
+FROM raw_records
+ARRAY JOIN JSONExtract(toString(record), 'tags', 'Array(String)') as tag
+WHERE collection IN ('sh.weaver.notebook.entry', 'sh.weaver.notebook.book')
+  AND operation != 'delete'
+  AND tag != ''
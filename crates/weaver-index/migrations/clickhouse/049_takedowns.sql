@@ -0,0 +1,21 @@
+-- Moderation takedowns, keyed by subject
+-- Written by the admin endpoints, loaded into the in-memory list that
+-- read endpoints consult; raw_records is left untouched so lifting a
+-- takedown needs no reindex
+
+CREATE TABLE IF NOT EXISTS takedowns (
+    -- The suppressed subject: a DID ('did:...') hides a whole account,
+    -- an AT URI ('at://did:.../collection/rkey') hides a single record
+    subject String,
+
+    -- Inactive rows lift a takedown without losing the audit trail
+    active UInt8 DEFAULT 1,
+
+    -- Operator-facing note on why the subject was suppressed
+    reason String DEFAULT '',
+
+    -- Timestamps
+    updated_at DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = ReplacingMergeTree(updated_at)
+ORDER BY subject
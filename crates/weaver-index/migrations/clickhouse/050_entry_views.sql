@@ -0,0 +1,28 @@
+-- Entry view beacons
+-- Written by the analytics beacon route, read by the summary endpoint
+--
+-- One row per beacon. View beacons (kind = 'view') count page views;
+-- reading beacons (kind = 'read') carry time-on-page and do not add to
+-- view counts, so the page can send both without double counting.
+-- Referrers are reduced to an origin before insert and rows expire
+-- after 90 days, so this holds no long-term per-reader data.
+
+CREATE TABLE IF NOT EXISTS entry_views (
+    -- Entry identity (matches entries table)
+    did String,
+    rkey String,
+
+    -- 'view' or 'read'.
+    kind LowCardinality(String),
+
+    -- Referrer origin (scheme and host only), '' for direct visits
+    referrer String DEFAULT '',
+
+    -- Time spent reading, 0 for view beacons
+    reading_ms UInt64 DEFAULT 0,
+
+    event_time DateTime64(3) DEFAULT now64(3)
+)
+ENGINE = MergeTree
+ORDER BY (did, rkey, event_time)
+TTL toDateTime(event_time) + INTERVAL 90 DAY
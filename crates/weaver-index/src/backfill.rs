@@ -0,0 +1,342 @@
+//! Repo backfill subsystem.
+//!
+//! The firehose indexer only sees events emitted while it is connected;
+//! repos that existed before the index came up are invisible to it. This
+//! module walks a repo's existing records via com.atproto.repo.listRecords
+//! and writes them through the same raw_records ingest path as the live
+//! indexer, so every derived table (entries, notebooks, search, timeline)
+//! picks them up through its materialized view. Per-collection listRecords
+//! cursors are persisted in the backfill_cursors table so an interrupted
+//! run resumes where it left off.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use jacquard::IntoStatic;
+use jacquard::client::UnauthenticatedSession;
+use jacquard::cowstr::ToCowStr;
+use jacquard::identity::JacquardResolver;
+use jacquard::prelude::{IdentityResolver, XrpcExt};
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::string::{Did, Nsid};
+use smol_str::{SmolStr, ToSmolStr};
+use tracing::{debug, info, warn};
+
+use weaver_api::com_atproto::repo::describe_repo::DescribeRepo;
+use weaver_api::com_atproto::repo::list_records::ListRecords;
+
+use crate::clickhouse::{Client, InserterConfig, RawRecordInsert, ResilientRecordInserter};
+use crate::config::IndexerConfig;
+use crate::error::{BackfillError, Result};
+
+/// Page size for listRecords calls (the protocol maximum).
+const PAGE_SIZE: i64 = 100;
+
+/// Summary of a backfill run across one or more repos.
+#[derive(Debug, Default)]
+pub struct BackfillSummary {
+    pub repos_completed: usize,
+    pub repos_failed: usize,
+    pub records_indexed: u64,
+}
+
+/// Walks existing repos and ingests their records.
+pub struct Backfiller {
+    client: Arc<Client>,
+    resolver: UnauthenticatedSession<JacquardResolver>,
+    config: IndexerConfig,
+}
+
+impl Backfiller {
+    pub fn new(
+        client: Client,
+        resolver: UnauthenticatedSession<JacquardResolver>,
+        config: IndexerConfig,
+    ) -> Self {
+        Self {
+            client: Arc::new(client),
+            resolver,
+            config,
+        }
+    }
+
+    /// Backfill a batch of repos, continuing past individual failures.
+    ///
+    /// Failures are recorded in backfill_cursors (and the summary) so a
+    /// rerun with the same DID list picks up only what's missing.
+    pub async fn backfill_dids(&self, dids: &[Did<'static>]) -> Result<BackfillSummary> {
+        let mut summary = BackfillSummary::default();
+
+        for did in dids {
+            match self.backfill_repo(did).await {
+                Ok(count) => {
+                    summary.repos_completed += 1;
+                    summary.records_indexed += count;
+                }
+                Err(e) => {
+                    warn!(did = %did, error = ?e, "repo backfill failed");
+                    summary.repos_failed += 1;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Backfill a single repo: enumerate its collections, filter to the
+    /// ones this indexer tracks, and ingest each one.
+    pub async fn backfill_repo(&self, did: &Did<'_>) -> Result<u64> {
+        let pds_url =
+            self.resolver
+                .pds_for_did(did)
+                .await
+                .map_err(|e| BackfillError::ResolvePds {
+                    did: did.to_string(),
+                    message: e.to_string(),
+                })?;
+
+        let request = DescribeRepo::new()
+            .repo(AtIdentifier::Did(did.clone()))
+            .build();
+        let response = self
+            .resolver
+            .xrpc(pds_url.clone())
+            .send(&request)
+            .await
+            .map_err(|e| BackfillError::DescribeRepo {
+                did: did.to_string(),
+                message: e.to_string(),
+            })?;
+        let described = response
+            .into_output()
+            .map_err(|e| BackfillError::DescribeRepo {
+                did: did.to_string(),
+                message: e.to_string(),
+            })?;
+
+        let mut total = 0;
+        for collection in &described.collections {
+            let collection = collection.as_ref();
+            if !self.config.collections.matches(collection) {
+                continue;
+            }
+
+            match self
+                .backfill_collection(did, pds_url.clone(), collection)
+                .await
+            {
+                Ok(count) => total += count,
+                Err(e) => {
+                    // Flag the failure but keep the saved cursor so a
+                    // retry resumes instead of restarting the collection.
+                    let saved = self
+                        .client
+                        .get_backfill_cursor(did.as_str(), collection)
+                        .await
+                        .ok()
+                        .flatten();
+                    let (cursor, indexed) = saved
+                        .map(|r| (r.cursor.to_string(), r.records_indexed))
+                        .unwrap_or_default();
+                    if let Err(save_err) = self
+                        .client
+                        .save_backfill_cursor(
+                            did.as_str(),
+                            collection,
+                            &cursor,
+                            "failed",
+                            indexed,
+                            &e.to_string(),
+                        )
+                        .await
+                    {
+                        warn!(error = ?save_err, "failed to record backfill failure");
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        info!(did = %did, records = total, "repo backfill complete");
+        Ok(total)
+    }
+
+    /// Ingest every record in one collection of a repo, resuming from a
+    /// previously saved cursor when one exists.
+    async fn backfill_collection(
+        &self,
+        did: &Did<'_>,
+        pds_url: jacquard::url::Url,
+        collection: &str,
+    ) -> Result<u64> {
+        let saved = self
+            .client
+            .get_backfill_cursor(did.as_str(), collection)
+            .await?;
+
+        let mut cursor = match &saved {
+            Some(row) if row.status == "done" => {
+                debug!(did = %did, collection, "collection already backfilled, skipping");
+                return Ok(0);
+            }
+            Some(row) if !row.cursor.is_empty() => {
+                info!(
+                    did = %did,
+                    collection,
+                    records_indexed = row.records_indexed,
+                    "resuming backfill from saved cursor"
+                );
+                Some(row.cursor.as_str().to_cowstr().into_static())
+            }
+            _ => None,
+        };
+        let mut indexed: u64 = saved.map(|r| r.records_indexed).unwrap_or(0);
+        let started_at = indexed;
+
+        let mut inserter =
+            ResilientRecordInserter::new(self.client.inner().clone(), InserterConfig::default());
+
+        loop {
+            let request = ListRecords::new()
+                .repo(AtIdentifier::Did(did.clone()))
+                .collection(Nsid::raw(collection))
+                .limit(PAGE_SIZE)
+                .maybe_cursor(cursor.clone())
+                .build();
+
+            let response = self
+                .resolver
+                .xrpc(pds_url.clone())
+                .send(&request)
+                .await
+                .map_err(|e| BackfillError::ListRecords {
+                    did: did.to_string(),
+                    collection: collection.to_string(),
+                    message: e.to_string(),
+                })?;
+            let list = response
+                .into_output()
+                .map_err(|e| BackfillError::ListRecords {
+                    did: did.to_string(),
+                    collection: collection.to_string(),
+                    message: e.to_string(),
+                })?;
+
+            for record in &list.records {
+                let rkey = match record.uri.rkey() {
+                    Some(r) => r.as_ref().to_smolstr(),
+                    None => {
+                        warn!(uri = %record.uri, "record uri missing rkey, skipping");
+                        continue;
+                    }
+                };
+
+                let json =
+                    serde_json::to_string(&record.value).map_err(|e| BackfillError::Serialize {
+                        uri: record.uri.to_string(),
+                        source: e,
+                    })?;
+                let event_time = record_event_time(&json);
+
+                // Backfilled rows predate the firehose, so there is no
+                // commit seq or rev; is_live = false marks them for the
+                // raw_records consumers that care about provenance.
+                inserter
+                    .write(RawRecordInsert {
+                        did: did.as_str().to_smolstr(),
+                        collection: collection.to_smolstr(),
+                        rkey,
+                        cid: record.cid.as_ref().to_smolstr(),
+                        rev: SmolStr::default(),
+                        record: json.to_smolstr(),
+                        operation: SmolStr::new_static("create"),
+                        seq: 0,
+                        event_time,
+                        is_live: false,
+                        validation_state: SmolStr::new_static("unchecked"),
+                    })
+                    .await?;
+                indexed += 1;
+            }
+
+            inserter.commit().await?;
+
+            match list.cursor {
+                Some(c) => {
+                    cursor = Some(c.into_static());
+                    // Persist progress after every page so a crash loses
+                    // at most one page of work.
+                    self.client
+                        .save_backfill_cursor(
+                            did.as_str(),
+                            collection,
+                            cursor.as_deref().unwrap_or(""),
+                            "in_progress",
+                            indexed,
+                            "",
+                        )
+                        .await?;
+                }
+                None => break,
+            }
+        }
+
+        inserter.end().await?;
+
+        self.client
+            .save_backfill_cursor(did.as_str(), collection, "", "done", indexed, "")
+            .await?;
+
+        debug!(
+            did = %did,
+            collection,
+            records = indexed - started_at,
+            "collection backfill complete"
+        );
+        Ok(indexed - started_at)
+    }
+}
+
+/// Best event time for a backfilled record.
+///
+/// The firehose stamps events with commit time; for backfill the closest
+/// equivalent is the record's own updatedAt/createdAt, falling back to
+/// now so derived tables still get a valid ordering key.
+fn record_event_time(json: &str) -> DateTime<Utc> {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(json) {
+        for field in ["updatedAt", "createdAt"] {
+            if let Some(ts) = value.get(field).and_then(|v| v.as_str()) {
+                if let Ok(dt) = DateTime::parse_from_rfc3339(ts) {
+                    return dt.with_timezone(&Utc);
+                }
+            }
+        }
+    }
+    Utc::now()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_time_prefers_updated_at() {
+        let json = r#"{"createdAt":"2024-01-01T00:00:00Z","updatedAt":"2024-06-01T12:00:00Z"}"#;
+        let t = record_event_time(json);
+        assert_eq!(t.to_rfc3339(), "2024-06-01T12:00:00+00:00");
+    }
+
+    #[test]
+    fn event_time_falls_back_to_created_at() {
+        let json = r#"{"createdAt":"2024-01-01T00:00:00Z"}"#;
+        let t = record_event_time(json);
+        assert_eq!(t.to_rfc3339(), "2024-01-01T00:00:00+00:00");
+    }
+
+    #[test]
+    fn event_time_falls_back_to_now_on_garbage() {
+        let before = Utc::now();
+        let t = record_event_time("not json");
+        assert!(t >= before);
+    }
+}
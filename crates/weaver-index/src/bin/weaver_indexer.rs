@@ -1,8 +1,8 @@
 use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
-use tracing::{error, info, warn};
 use jacquard::client::UnauthenticatedSession;
+use tracing::{error, info, warn};
 use weaver_index::clickhouse::InserterConfig;
 use weaver_index::clickhouse::{Client, Migrator};
 use weaver_index::config::{
@@ -11,8 +11,8 @@ use weaver_index::config::{
 use weaver_index::firehose::FirehoseConsumer;
 use weaver_index::server::{AppState, ServerConfig, TelemetryConfig, telemetry};
 use weaver_index::{
-    DraftTitleTaskConfig, FirehoseIndexer, ServiceIdentity, TapIndexer, load_cursor,
-    run_draft_title_task,
+    DraftTitleTaskConfig, FirehoseIndexer, ServiceIdentity, StaticMirrorTaskConfig, TakedownList,
+    TapIndexer, load_cursor, run_draft_title_task, run_static_mirror_task, run_takedown_refresh,
 };
 
 #[derive(Parser)]
@@ -47,6 +47,16 @@ enum Command {
 
     /// Start only the indexer (no HTTP server)
     Index,
+
+    /// Backfill existing repos into the index
+    Backfill {
+        /// DIDs of repos to backfill
+        dids: Vec<String>,
+
+        /// File with one DID per line (blank lines and # comments ignored)
+        #[arg(long)]
+        from_file: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -65,9 +75,73 @@ async fn main() -> miette::Result<()> {
         Command::Run => run_full().await,
         Command::Serve => run_server_only().await,
         Command::Index => run_indexer_only().await,
+        Command::Backfill { dids, from_file } => run_backfill(dids, from_file).await,
     }
 }
 
+async fn run_backfill(dids: Vec<String>, from_file: Option<PathBuf>) -> miette::Result<()> {
+    use jacquard::types::string::Did;
+    use weaver_index::Backfiller;
+
+    let ch_config = ClickHouseConfig::from_env()?;
+    let indexer_config = IndexerConfig::from_env();
+
+    let mut raw_dids = dids;
+    if let Some(path) = from_file {
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| miette::miette!("failed to read {}: {}", path.display(), e))?;
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            raw_dids.push(line.to_string());
+        }
+    }
+
+    if raw_dids.is_empty() {
+        return Err(miette::miette!(
+            "no DIDs to backfill; pass them as arguments or via --from-file"
+        ));
+    }
+
+    // Validate up front so one typo doesn't waste a long run.
+    let mut parsed = Vec::with_capacity(raw_dids.len());
+    for raw in raw_dids {
+        let did = Did::new_owned(raw.clone())
+            .map_err(|e| miette::miette!("invalid DID '{}': {}", raw, e))?;
+        parsed.push(did);
+    }
+
+    info!(
+        "Connecting to ClickHouse at {} (database: {})",
+        ch_config.url, ch_config.database
+    );
+    let client = Client::new(&ch_config)?;
+    let resolver = UnauthenticatedSession::new_public();
+
+    let backfiller = Backfiller::new(client, resolver, indexer_config);
+
+    info!(repos = parsed.len(), "starting backfill");
+    let summary = backfiller.backfill_dids(&parsed).await?;
+
+    info!(
+        repos_completed = summary.repos_completed,
+        repos_failed = summary.repos_failed,
+        records_indexed = summary.records_indexed,
+        "backfill finished"
+    );
+
+    if summary.repos_failed > 0 {
+        return Err(miette::miette!(
+            "{} repo(s) failed to backfill; rerun the same command to retry",
+            summary.repos_failed
+        ));
+    }
+
+    Ok(())
+}
+
 async fn run_migrate(dry_run: bool, reset: bool) -> miette::Result<()> {
     let config = ClickHouseConfig::from_env()?;
     info!(
@@ -195,7 +269,12 @@ async fn run_full() -> miette::Result<()> {
                 firehose_config.relay_url, firehose_config.cursor
             );
             let consumer = FirehoseConsumer::new(firehose_config);
-            let indexer = FirehoseIndexer::new(indexer_client, consumer, indexer_config).await?;
+            // Share the server's identity cache so handle renames seen on
+            // the firehose take effect for in-flight resolution.
+            let indexer = FirehoseIndexer::new(indexer_client, consumer, indexer_config)
+                .await?
+                .with_identity_cache(state.identity_cache.clone())
+                .with_takedowns(state.takedowns.clone());
             info!("Starting firehose indexer");
             tokio::spawn(async move { indexer.run().await })
         }
@@ -215,12 +294,22 @@ async fn run_full() -> miette::Result<()> {
     };
 
     // Spawn background tasks
+    tokio::spawn(run_takedown_refresh(
+        task_client.clone(),
+        state.takedowns.clone(),
+    ));
     let resolver = UnauthenticatedSession::new_public();
     tokio::spawn(run_draft_title_task(
+        task_client.clone(),
+        resolver.clone(),
+        DraftTitleTaskConfig::default(),
+    ));
+    tokio::spawn(run_static_mirror_task(
         task_client,
         resolver,
-        DraftTitleTaskConfig::default(),
+        StaticMirrorTaskConfig::default(),
     ));
+    tokio::spawn(weaver_index::observability::run_sampler(state.clone()));
 
     // Run server, monitoring indexer health
     tokio::select! {
@@ -252,7 +341,14 @@ async fn run_indexer_only() -> miette::Result<()> {
     let client = Client::new(&ch_config)?;
 
     match source_mode {
-        SourceMode::Firehose => run_firehose_indexer(client, indexer_config).await,
+        SourceMode::Firehose => {
+            // Ingest-side takedown enforcement; the refresh keeps the
+            // list in sync with takedowns issued on the serving nodes.
+            let takedowns = std::sync::Arc::new(TakedownList::new());
+            let refresh_client = std::sync::Arc::new(Client::new(&ch_config)?);
+            tokio::spawn(run_takedown_refresh(refresh_client, takedowns.clone()));
+            run_firehose_indexer(client, indexer_config, takedowns).await
+        }
         SourceMode::Tap => {
             let tap_config = TapConfig::from_env()?;
             run_tap_indexer(client, tap_config, indexer_config).await
@@ -260,7 +356,11 @@ async fn run_indexer_only() -> miette::Result<()> {
     }
 }
 
-async fn run_firehose_indexer(client: Client, indexer_config: IndexerConfig) -> miette::Result<()> {
+async fn run_firehose_indexer(
+    client: Client,
+    indexer_config: IndexerConfig,
+    takedowns: std::sync::Arc<TakedownList>,
+) -> miette::Result<()> {
     let mut firehose_config = FirehoseConfig::from_env()?;
 
     // Load cursor from ClickHouse if not overridden by env var
@@ -276,7 +376,9 @@ async fn run_firehose_indexer(client: Client, indexer_config: IndexerConfig) ->
     );
     let consumer = FirehoseConsumer::new(firehose_config);
 
-    let indexer = FirehoseIndexer::new(client, consumer, indexer_config).await?;
+    let indexer = FirehoseIndexer::new(client, consumer, indexer_config)
+        .await?
+        .with_takedowns(takedowns);
 
     info!("Starting firehose indexer");
     indexer.run().await?;
@@ -341,6 +443,15 @@ async fn run_server_only() -> miette::Result<()> {
     let client = Client::new(&ch_config)?;
 
     let state = AppState::new(client, shard_config, server_config.service_did.clone());
+
+    // Takedowns issued on other nodes only reach this one via the
+    // refresh loop, so it runs even without a local indexer.
+    let refresh_client = std::sync::Arc::new(Client::new(&ch_config)?);
+    tokio::spawn(run_takedown_refresh(
+        refresh_client,
+        state.takedowns.clone(),
+    ));
+
     weaver_index::server::run(state, server_config, did_doc).await?;
 
     Ok(())
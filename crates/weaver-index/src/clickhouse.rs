@@ -7,8 +7,11 @@ mod schema;
 pub use client::{Client, TableSize};
 pub use migrations::{DbObject, MigrationResult, Migrator, ObjectType};
 pub use queries::{
-    CollaboratorRow, EditChainNode, EditHeadRow, EditNodeRow, EntryRow, HandleMappingRow,
-    NotebookRow, ProfileCountsRow, ProfileRow, ProfileWithCounts, StaleDraftRow,
+    BackfillCursorRow, CollaboratorRow, EditChainNode, EditHeadRow, EditNodeRow, EntryRow,
+    EntrySearchRow, EntryViewStatsRow, ExportedRecordRow, HandleMappingRow, NotebookRow,
+    ProfileCountsRow, ProfileRow, ProfileWithCounts, PurgedTable, ReadingTimeBucketRow,
+    ReferrerStatsRow, SessionRow, StaleDraftRow, StaleMirrorRow, StaticMirrorRow, TagStatsRow,
+    TakedownRow, TimelineEntryRow, query_terms,
 };
 pub use resilient_inserter::{InserterConfig, ResilientRecordInserter};
 pub use schema::{
@@ -2,17 +2,33 @@
 //!
 //! These modules add query methods to the ClickHouse Client via impl blocks.
 
+mod analytics;
+mod backfill;
 mod collab;
 mod collab_state;
 mod contributors;
 mod edit;
+mod feed;
 mod identity;
+mod mirrors;
 mod notebooks;
+mod privacy;
 mod profiles;
+mod search;
+mod tags;
+mod takedowns;
 
-pub use collab::PermissionRow;
+pub use analytics::{EntryViewStatsRow, ReadingTimeBucketRow, ReferrerStatsRow};
+pub use backfill::BackfillCursorRow;
+pub use collab::{PermissionRow, SessionRow};
 pub use collab_state::{CollaboratorRow, EditHeadRow};
 pub use edit::{EditChainNode, EditNodeRow, StaleDraftRow};
+pub use feed::TimelineEntryRow;
 pub use identity::HandleMappingRow;
+pub use mirrors::{StaleMirrorRow, StaticMirrorRow};
 pub use notebooks::{EntryRow, NotebookRow};
+pub use privacy::{ExportedRecordRow, PurgedTable};
 pub use profiles::{ProfileCountsRow, ProfileRow, ProfileWithCounts};
+pub use search::{EntrySearchRow, query_terms};
+pub use tags::TagStatsRow;
+pub use takedowns::TakedownRow;
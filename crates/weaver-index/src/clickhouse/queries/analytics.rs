@@ -0,0 +1,182 @@
+//! View analytics queries backed by the entry_views table
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// View counts and reading-time quantiles for one entry
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct EntryViewStatsRow {
+    pub rkey: SmolStr,
+    /// Current entry title from the entries table, '' when unknown
+    pub title: String,
+    pub view_count: u64,
+    pub p50_reading_ms: u64,
+    pub p90_reading_ms: u64,
+}
+
+/// View count for one referrer origin
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct ReferrerStatsRow {
+    pub referrer: SmolStr,
+    pub view_count: u64,
+}
+
+/// One bucket of the reading-time histogram
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct ReadingTimeBucketRow {
+    pub floor_ms: u64,
+    pub view_count: u64,
+}
+
+impl Client {
+    /// Record one analytics beacon.
+    pub async fn insert_entry_view(
+        &self,
+        did: &str,
+        rkey: &str,
+        kind: &str,
+        referrer: &str,
+        reading_ms: u64,
+    ) -> Result<(), IndexError> {
+        let query = r#"
+            INSERT INTO entry_views (did, rkey, kind, referrer, reading_ms)
+            VALUES (?, ?, ?, ?, ?)
+        "#;
+
+        self.inner()
+            .query(query)
+            .bind(did)
+            .bind(rkey)
+            .bind(kind)
+            .bind(referrer)
+            .bind(reading_ms)
+            .execute()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to insert entry view".into(),
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    /// Per-entry view counts and reading-time quantiles for one account.
+    ///
+    /// Joins against the entries table for display titles; entries whose
+    /// records were never indexed (or were deleted) still appear, with an
+    /// empty title. Quantiles are over reading beacons only and come back
+    /// as 0 when none exist, matching "no data" in the dashboard.
+    pub async fn entry_view_stats(
+        &self,
+        did: &str,
+        days: u32,
+    ) -> Result<Vec<EntryViewStatsRow>, IndexError> {
+        let query = r#"
+            SELECT
+                v.rkey AS rkey,
+                any(e.title) AS title,
+                countIf(v.kind = 'view') AS view_count,
+                toUInt64(ifNotFinite(quantileIf(0.5)(v.reading_ms, v.kind = 'read'), 0)) AS p50_reading_ms,
+                toUInt64(ifNotFinite(quantileIf(0.9)(v.reading_ms, v.kind = 'read'), 0)) AS p90_reading_ms
+            FROM entry_views v
+            LEFT JOIN (
+                SELECT did, rkey, title FROM entries FINAL
+            ) AS e ON e.did = v.did AND e.rkey = v.rkey
+            WHERE v.did = ?
+              AND v.event_time >= subtractDays(now64(3), ?)
+            GROUP BY v.rkey
+            ORDER BY view_count DESC, rkey ASC
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .bind(days)
+            .fetch_all::<EntryViewStatsRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get entry view stats".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// Top referrer origins for one account's entries.
+    pub async fn entry_view_referrers(
+        &self,
+        did: &str,
+        days: u32,
+        limit: u32,
+    ) -> Result<Vec<ReferrerStatsRow>, IndexError> {
+        let query = r#"
+            SELECT
+                referrer,
+                countIf(kind = 'view') AS view_count
+            FROM entry_views
+            WHERE did = ?
+              AND referrer != ''
+              AND event_time >= subtractDays(now64(3), ?)
+            GROUP BY referrer
+            HAVING view_count > 0
+            ORDER BY view_count DESC, referrer ASC
+            LIMIT ?
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .bind(days)
+            .bind(limit)
+            .fetch_all::<ReferrerStatsRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get entry view referrers".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// Reading-time histogram across one account's entries.
+    ///
+    /// Buckets are fixed so the dashboard can label them: under 30s, then
+    /// 30s/1m/2m/5m/10m/20m floors. Only reading beacons contribute.
+    pub async fn reading_time_distribution(
+        &self,
+        did: &str,
+        days: u32,
+    ) -> Result<Vec<ReadingTimeBucketRow>, IndexError> {
+        let query = r#"
+            SELECT
+                roundDown(reading_ms, [0, 30000, 60000, 120000, 300000, 600000, 1200000]) AS floor_ms,
+                count() AS view_count
+            FROM entry_views
+            WHERE did = ?
+              AND kind = 'read'
+              AND event_time >= subtractDays(now64(3), ?)
+            GROUP BY floor_ms
+            ORDER BY floor_ms ASC
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .bind(days)
+            .fetch_all::<ReadingTimeBucketRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get reading time distribution".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,89 @@
+//! Backfill cursor queries
+//!
+//! Progress rows for the repo backfill subsystem (see `crate::backfill`),
+//! stored in the backfill_cursors table (migration 046). One row per
+//! (did, collection); ReplacingMergeTree keeps the latest by updated_at.
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// Per-(did, collection) backfill progress.
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct BackfillCursorRow {
+    pub did: SmolStr,
+    pub collection: SmolStr,
+    pub cursor: SmolStr,
+    pub status: SmolStr,
+    pub records_indexed: u64,
+    pub error: SmolStr,
+}
+
+impl Client {
+    /// Get the saved backfill cursor for one (did, collection) pair.
+    pub async fn get_backfill_cursor(
+        &self,
+        did: &str,
+        collection: &str,
+    ) -> Result<Option<BackfillCursorRow>, IndexError> {
+        let query = r#"
+            SELECT did, collection, cursor, status, records_indexed, error
+            FROM backfill_cursors FINAL
+            WHERE did = ? AND collection = ?
+            LIMIT 1
+        "#;
+
+        let row = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .bind(collection)
+            .fetch_optional::<BackfillCursorRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get backfill cursor".into(),
+                source: e,
+            })?;
+
+        Ok(row)
+    }
+
+    /// Save backfill progress for one (did, collection) pair.
+    ///
+    /// Inserts a new version row; ReplacingMergeTree collapses to the
+    /// latest on merge and reads use FINAL.
+    pub async fn save_backfill_cursor(
+        &self,
+        did: &str,
+        collection: &str,
+        cursor: &str,
+        status: &str,
+        records_indexed: u64,
+        error: &str,
+    ) -> Result<(), IndexError> {
+        let query = r#"
+            INSERT INTO backfill_cursors (did, collection, cursor, status, records_indexed, error)
+            VALUES (?, ?, ?, ?, ?, ?)
+        "#;
+
+        self.inner()
+            .query(query)
+            .bind(did)
+            .bind(collection)
+            .bind(cursor)
+            .bind(status)
+            .bind(records_indexed)
+            .bind(error)
+            .execute()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to save backfill cursor".into(),
+                source: e,
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! Aggregated activity feed queries
+//!
+//! Backed by the timeline table (see migrations 044/045), a narrow
+//! event-time-ordered index over entry activity maintained by the firehose
+//! indexer. Rows are joined back to entries for hydration, so deleted
+//! entries drop out without timeline tombstones.
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// A feed item: the entry row plus the timeline event time that ranks it.
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct TimelineEntryRow {
+    pub did: SmolStr,
+    pub rkey: SmolStr,
+    pub cid: SmolStr,
+    pub uri: SmolStr,
+    pub title: SmolStr,
+    pub path: SmolStr,
+    pub tags: Vec<SmolStr>,
+    pub author_dids: Vec<SmolStr>,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub indexed_at: chrono::DateTime<chrono::Utc>,
+    pub record: SmolStr,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub event_time: chrono::DateTime<chrono::Utc>,
+}
+
+impl Client {
+    /// Aggregated network-wide activity feed: recently published or
+    /// updated entries, newest first.
+    ///
+    /// Optional filters narrow by author DID, tag, or declared language.
+    /// `cursor` is an event-time millis bound for keyset pagination.
+    /// Unlisted and not-yet-published entries are excluded, matching the
+    /// other feed queries; collaborative copies and repeat events for the
+    /// same entry are deduped by rkey, keeping the most recent event.
+    pub async fn get_activity_feed(
+        &self,
+        actor_did: Option<&str>,
+        tag: Option<&str>,
+        language: Option<&str>,
+        limit: u32,
+        cursor: Option<i64>,
+    ) -> Result<Vec<TimelineEntryRow>, IndexError> {
+        let actor_condition = if actor_did.is_some() {
+            "AND t.did = ?"
+        } else {
+            ""
+        };
+        let tag_condition = if tag.is_some() {
+            "AND has(t.tags, ?)"
+        } else {
+            ""
+        };
+        let language_condition = if language.is_some() {
+            "AND t.language = ?"
+        } else {
+            ""
+        };
+        let cursor_condition = if cursor.is_some() {
+            "AND t.event_time < fromUnixTimestamp64Milli(?)"
+        } else {
+            ""
+        };
+
+        let query = format!(
+            r#"
+            SELECT did, rkey, cid, uri, title, path, tags, author_dids, created_at, updated_at, indexed_at, record, event_time
+            FROM (
+                SELECT e.did AS did, e.rkey AS rkey, e.cid AS cid, e.uri AS uri,
+                       e.title AS title, e.path AS path, e.tags AS tags,
+                       e.author_dids AS author_dids, e.created_at AS created_at,
+                       e.updated_at AS updated_at, e.indexed_at AS indexed_at,
+                       e.record AS record, t.event_time AS event_time,
+                       ROW_NUMBER() OVER (PARTITION BY t.rkey ORDER BY t.event_time DESC) as rn
+                FROM timeline t FINAL
+                INNER JOIN entries e FINAL ON
+                    e.did = t.did
+                    AND e.rkey = t.rkey
+                    AND e.deleted_at = toDateTime64(0, 3)
+                WHERE JSONExtractString(toString(e.record), 'visibility') != 'unlisted'
+                  AND (JSONExtractString(toString(e.record), 'publishedAt') = ''
+                       OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(e.record), 'publishedAt'), 3) <= now64(3))
+                  {actor_condition}
+                  {tag_condition}
+                  {language_condition}
+                  {cursor_condition}
+            )
+            WHERE rn = 1
+            ORDER BY event_time DESC
+            LIMIT ?
+            "#
+        );
+
+        let mut q = self.inner().query(&query);
+        if let Some(did) = actor_did {
+            q = q.bind(did);
+        }
+        if let Some(t) = tag {
+            q = q.bind(t);
+        }
+        if let Some(l) = language {
+            q = q.bind(l);
+        }
+        if let Some(c) = cursor {
+            q = q.bind(c);
+        }
+
+        let rows = q
+            .bind(limit)
+            .fetch_all::<TimelineEntryRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get activity feed".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+}
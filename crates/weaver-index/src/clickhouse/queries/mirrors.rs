@@ -0,0 +1,201 @@
+//! Static mirror settings and deploy watermark queries
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// Static mirror settings row from the static_mirrors table
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct StaticMirrorRow {
+    pub did: SmolStr,
+    pub rkey: SmolStr,
+    pub output_dir: String,
+    pub webhook_url: String,
+    pub enabled: u8,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A configured mirror whose notebook has entries newer than its last deploy
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct StaleMirrorRow {
+    pub did: SmolStr,
+    pub rkey: SmolStr,
+    pub output_dir: String,
+    pub webhook_url: String,
+    /// indexed_at of the newest live entry in the notebook
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub latest_entry_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Client {
+    /// Create or update static mirror settings for a notebook.
+    pub async fn upsert_static_mirror(
+        &self,
+        did: &str,
+        rkey: &str,
+        output_dir: &str,
+        webhook_url: &str,
+        enabled: bool,
+    ) -> Result<(), IndexError> {
+        let query = r#"
+            INSERT INTO static_mirrors (did, rkey, output_dir, webhook_url, enabled)
+            VALUES (?, ?, ?, ?, ?)
+        "#;
+
+        self.inner()
+            .query(query)
+            .bind(did)
+            .bind(rkey)
+            .bind(output_dir)
+            .bind(webhook_url)
+            .bind(enabled as u8)
+            .execute()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to upsert static mirror".into(),
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    /// Get static mirror settings for a notebook.
+    pub async fn get_static_mirror(
+        &self,
+        did: &str,
+        rkey: &str,
+    ) -> Result<Option<StaticMirrorRow>, IndexError> {
+        let query = r#"
+            SELECT
+                did,
+                rkey,
+                output_dir,
+                webhook_url,
+                enabled,
+                updated_at
+            FROM static_mirrors FINAL
+            WHERE did = ?
+              AND rkey = ?
+            LIMIT 1
+        "#;
+
+        let row = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .bind(rkey)
+            .fetch_optional::<StaticMirrorRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get static mirror".into(),
+                source: e,
+            })?;
+
+        Ok(row)
+    }
+
+    /// List all configured static mirrors (enabled or not).
+    pub async fn list_static_mirrors(&self) -> Result<Vec<StaticMirrorRow>, IndexError> {
+        let query = r#"
+            SELECT
+                did,
+                rkey,
+                output_dir,
+                webhook_url,
+                enabled,
+                updated_at
+            FROM static_mirrors FINAL
+            ORDER BY (did, rkey)
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .fetch_all::<StaticMirrorRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to list static mirrors".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// Find enabled mirrors whose notebooks gained or changed entries since
+    /// the last deploy.
+    ///
+    /// A missing deploy watermark reads as epoch zero via the LEFT JOIN
+    /// defaults, so freshly configured mirrors always show up as stale.
+    pub async fn get_stale_static_mirrors(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<StaleMirrorRow>, IndexError> {
+        let query = r#"
+            SELECT
+                m.did AS did,
+                m.rkey AS rkey,
+                m.output_dir AS output_dir,
+                m.webhook_url AS webhook_url,
+                max(e.indexed_at) AS latest_entry_at
+            FROM static_mirrors m FINAL
+            INNER JOIN notebook_entries ne FINAL ON
+                ne.notebook_did = m.did
+                AND ne.notebook_rkey = m.rkey
+            INNER JOIN entries e FINAL ON
+                e.did = ne.entry_did
+                AND e.rkey = ne.entry_rkey
+                AND e.deleted_at = toDateTime64(0, 3)
+            LEFT JOIN static_mirror_deploys d FINAL ON
+                d.did = m.did
+                AND d.rkey = m.rkey
+            WHERE m.enabled = 1
+            GROUP BY m.did, m.rkey, m.output_dir, m.webhook_url, d.deployed_entry_at
+            HAVING latest_entry_at > d.deployed_entry_at
+            LIMIT ?
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(limit)
+            .fetch_all::<StaleMirrorRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get stale static mirrors".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// Record a successful deploy by advancing the mirror's watermark.
+    pub async fn mark_mirror_deployed(
+        &self,
+        did: &str,
+        rkey: &str,
+        latest_entry_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), IndexError> {
+        let query = r#"
+            INSERT INTO static_mirror_deploys (did, rkey, deployed_entry_at)
+            VALUES (?, ?, fromUnixTimestamp64Milli(?))
+        "#;
+
+        self.inner()
+            .query(query)
+            .bind(did)
+            .bind(rkey)
+            .bind(latest_entry_at.timestamp_millis())
+            .execute()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to mark mirror deployed".into(),
+                source: e,
+            })?;
+
+        Ok(())
+    }
+}
@@ -139,6 +139,8 @@ impl Client {
     ///
     /// Uses notebook_entries table to get entries that belong to this notebook.
     /// Deduplicates entries by rkey, keeping the most recently updated version.
+    /// Unlisted and not-yet-published entries are excluded; they stay reachable
+    /// through direct fetches like `get_entry`.
     pub async fn list_notebook_entries(
         &self,
         notebook_did: &str,
@@ -167,6 +169,9 @@ impl Client {
                 e.did = ne.entry_did
                 AND e.rkey = ne.entry_rkey
                 AND e.deleted_at = toDateTime64(0, 3)
+                AND JSONExtractString(toString(e.record), 'visibility') != 'unlisted'
+                AND (JSONExtractString(toString(e.record), 'publishedAt') = ''
+                     OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(e.record), 'publishedAt'), 3) <= now64(3))
             WHERE ne.notebook_did = ?
               AND ne.notebook_rkey = ?
               AND ne.position > ?
@@ -437,6 +442,7 @@ impl Client {
     ///
     /// Returns entries owned by the given DID, ordered by created_at DESC.
     /// Cursor is created_at timestamp in milliseconds.
+    /// Unlisted and not-yet-published entries are excluded.
     pub async fn list_actor_entries(
         &self,
         did: &str,
@@ -452,6 +458,9 @@ impl Client {
                     FROM entries FINAL
                     WHERE did = ?
                       AND deleted_at = toDateTime64(0, 3)
+                      AND JSONExtractString(toString(record), 'visibility') != 'unlisted'
+                      AND (JSONExtractString(toString(record), 'publishedAt') = ''
+                           OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(record), 'publishedAt'), 3) <= now64(3))
                       AND created_at < fromUnixTimestamp64Milli(?)
                 )
                 WHERE rn = 1
@@ -467,6 +476,9 @@ impl Client {
                     FROM entries FINAL
                     WHERE did = ?
                       AND deleted_at = toDateTime64(0, 3)
+                      AND JSONExtractString(toString(record), 'visibility') != 'unlisted'
+                      AND (JSONExtractString(toString(record), 'publishedAt') = ''
+                           OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(record), 'publishedAt'), 3) <= now64(3))
                 )
                 WHERE rn = 1
                 ORDER BY created_at DESC
@@ -614,6 +626,7 @@ impl Client {
     ///
     /// Returns entries ordered by created_at DESC (chronological).
     /// Cursor is created_at timestamp in milliseconds.
+    /// Unlisted and not-yet-published entries are excluded.
     pub async fn get_entry_feed(
         &self,
         algorithm: &str,
@@ -629,6 +642,9 @@ impl Client {
                            ROW_NUMBER() OVER (PARTITION BY rkey ORDER BY updated_at DESC) as rn
                     FROM entries FINAL
                     WHERE deleted_at = toDateTime64(0, 3)
+                      AND JSONExtractString(toString(record), 'visibility') != 'unlisted'
+                      AND (JSONExtractString(toString(record), 'publishedAt') = ''
+                           OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(record), 'publishedAt'), 3) <= now64(3))
                       AND hasAny(tags, ?)
                       AND created_at < fromUnixTimestamp64Milli(?)
                 )
@@ -644,6 +660,9 @@ impl Client {
                            ROW_NUMBER() OVER (PARTITION BY rkey ORDER BY updated_at DESC) as rn
                     FROM entries FINAL
                     WHERE deleted_at = toDateTime64(0, 3)
+                      AND JSONExtractString(toString(record), 'visibility') != 'unlisted'
+                      AND (JSONExtractString(toString(record), 'publishedAt') = ''
+                           OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(record), 'publishedAt'), 3) <= now64(3))
                       AND hasAny(tags, ?)
                 )
                 WHERE rn = 1
@@ -658,6 +677,9 @@ impl Client {
                            ROW_NUMBER() OVER (PARTITION BY rkey ORDER BY updated_at DESC) as rn
                     FROM entries FINAL
                     WHERE deleted_at = toDateTime64(0, 3)
+                      AND JSONExtractString(toString(record), 'visibility') != 'unlisted'
+                      AND (JSONExtractString(toString(record), 'publishedAt') = ''
+                           OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(record), 'publishedAt'), 3) <= now64(3))
                       AND created_at < fromUnixTimestamp64Milli(?)
                 )
                 WHERE rn = 1
@@ -672,6 +694,9 @@ impl Client {
                            ROW_NUMBER() OVER (PARTITION BY rkey ORDER BY updated_at DESC) as rn
                     FROM entries FINAL
                     WHERE deleted_at = toDateTime64(0, 3)
+                      AND JSONExtractString(toString(record), 'visibility') != 'unlisted'
+                      AND (JSONExtractString(toString(record), 'publishedAt') = ''
+                           OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(record), 'publishedAt'), 3) <= now64(3))
                 )
                 WHERE rn = 1
                 ORDER BY created_at DESC
@@ -0,0 +1,242 @@
+//! Account data export and purge queries.
+//!
+//! Everything the appview stores about an account is keyed by DID, either
+//! directly (raw event tables, operator settings) or through materialized
+//! views fed by them. Export reads the raw tables so the user gets the
+//! complete server-held history; purge issues `ALTER TABLE ... DELETE`
+//! mutations against every plain table with a DID column.
+
+use clickhouse::Row;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// Plain tables with a `did` column, purged row-by-row on deletion requests.
+///
+/// Materialized views that own their storage (profiles, edit_heads,
+/// collaborators, permissions, contributors) cannot be mutated through the
+/// view name; they re-derive from these source tables and are rebuilt as
+/// part of the operator retention runbook instead.
+const PURGE_TABLES: &[&str] = &[
+    "raw_records",
+    "raw_identity_events",
+    "raw_account_events",
+    "account_rev_state",
+    "handle_mappings",
+    "profiles_weaver",
+    "profiles_bsky",
+    "profile_counts",
+    "notebooks",
+    "notebook_counts",
+    "entries",
+    "entry_counts",
+    "drafts",
+    "draft_titles",
+    "edit_nodes",
+    "collab_invites",
+    "collab_accepts",
+    "collab_sessions",
+    "static_mirrors",
+    "static_mirror_deploys",
+];
+
+/// One raw firehose event stored for an account, as kept in raw_records.
+#[derive(Debug, Clone, Row, Serialize, Deserialize)]
+pub struct ExportedRecordRow {
+    pub collection: SmolStr,
+    pub rkey: SmolStr,
+    pub cid: SmolStr,
+    pub rev: SmolStr,
+    /// Record body as the JSON string it was ingested with.
+    pub record: String,
+    pub operation: SmolStr,
+    pub seq: u64,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub event_time: chrono::DateTime<chrono::Utc>,
+}
+
+/// Per-table result of a purge: how many rows matched the DID.
+///
+/// Mutations are asynchronous in ClickHouse, so the count reflects what was
+/// scheduled for deletion, not what has already been rewritten on disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct PurgedTable {
+    pub table: &'static str,
+    pub matched_rows: u64,
+}
+
+#[derive(Row, Deserialize)]
+struct CountRow {
+    cnt: u64,
+}
+
+impl Client {
+    /// All raw firehose events stored for a DID, oldest first.
+    pub async fn export_account_records(
+        &self,
+        did: &str,
+    ) -> Result<Vec<ExportedRecordRow>, IndexError> {
+        let query = r#"
+            SELECT
+                collection,
+                rkey,
+                cid,
+                rev,
+                toString(record) AS record,
+                operation,
+                seq,
+                event_time
+            FROM raw_records
+            WHERE did = ?
+            ORDER BY seq ASC
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .fetch_all::<ExportedRecordRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to export account records".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// All identity events (handle changes) stored for a DID, oldest first.
+    pub async fn export_identity_events(
+        &self,
+        did: &str,
+    ) -> Result<Vec<crate::clickhouse::RawIdentityEvent>, IndexError> {
+        let query = r#"
+            SELECT did, handle, seq, event_time
+            FROM raw_identity_events
+            WHERE did = ?
+            ORDER BY seq ASC
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .fetch_all::<crate::clickhouse::RawIdentityEvent>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to export identity events".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// All account status events stored for a DID, oldest first.
+    pub async fn export_account_events(
+        &self,
+        did: &str,
+    ) -> Result<Vec<crate::clickhouse::RawAccountEvent>, IndexError> {
+        let query = r#"
+            SELECT did, active, status, seq, event_time
+            FROM raw_account_events
+            WHERE did = ?
+            ORDER BY seq ASC
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(did)
+            .fetch_all::<crate::clickhouse::RawAccountEvent>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to export account events".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// Schedule deletion of every row the appview holds for a DID.
+    ///
+    /// Returns the matched row count per table. Tables without matches are
+    /// included so the caller can show a complete accounting.
+    pub async fn purge_account_data(&self, did: &str) -> Result<Vec<PurgedTable>, IndexError> {
+        let mut purged = Vec::with_capacity(PURGE_TABLES.len() + 1);
+
+        for table in PURGE_TABLES {
+            let count_query = format!("SELECT count(*) AS cnt FROM {} WHERE did = ?", table);
+            let row = self
+                .inner()
+                .query(&count_query)
+                .bind(did)
+                .fetch_one::<CountRow>()
+                .await
+                .map_err(|e| ClickHouseError::Query {
+                    message: format!("failed to count rows in {} for purge", table),
+                    source: e,
+                })?;
+
+            if row.cnt > 0 {
+                let delete_query = format!("ALTER TABLE {} DELETE WHERE did = ?", table);
+                self.inner()
+                    .query(&delete_query)
+                    .bind(did)
+                    .execute()
+                    .await
+                    .map_err(|e| ClickHouseError::Query {
+                        message: format!("failed to purge rows from {}", table),
+                        source: e,
+                    })?;
+            }
+
+            purged.push(PurgedTable {
+                table,
+                matched_rows: row.cnt,
+            });
+        }
+
+        // notebook_entries keys membership rows by both sides of the link,
+        // so it needs its own predicate.
+        let count_query = r#"
+            SELECT count(*) AS cnt
+            FROM notebook_entries
+            WHERE entry_did = ? OR notebook_did = ?
+        "#;
+        let row = self
+            .inner()
+            .query(count_query)
+            .bind(did)
+            .bind(did)
+            .fetch_one::<CountRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to count rows in notebook_entries for purge".into(),
+                source: e,
+            })?;
+
+        if row.cnt > 0 {
+            self.inner()
+                .query(
+                    "ALTER TABLE notebook_entries DELETE WHERE entry_did = ? OR notebook_did = ?",
+                )
+                .bind(did)
+                .bind(did)
+                .execute()
+                .await
+                .map_err(|e| ClickHouseError::Query {
+                    message: "failed to purge rows from notebook_entries".into(),
+                    source: e,
+                })?;
+        }
+
+        purged.push(PurgedTable {
+            table: "notebook_entries",
+            matched_rows: row.cnt,
+        });
+
+        Ok(purged)
+    }
+}
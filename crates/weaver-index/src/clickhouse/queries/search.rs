@@ -0,0 +1,168 @@
+//! Full-text entry search queries
+//!
+//! Backed by the entry_search table (see migrations 042/043), which holds
+//! plain text extracted from entry markdown at ingest. ClickHouse has no
+//! built-in relevance model, so scoring is substring-count based with a
+//! heavy weight on title matches; good enough to surface obviously-relevant
+//! entries first without a separate search service.
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// Maximum number of query terms considered; extras are ignored rather than
+/// rejected so pathological queries stay cheap.
+const MAX_TERMS: usize = 8;
+
+/// Search hit: the entry row plus score and a snippet around the first match.
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct EntrySearchRow {
+    pub did: SmolStr,
+    pub rkey: SmolStr,
+    pub cid: SmolStr,
+    pub uri: SmolStr,
+    pub title: SmolStr,
+    pub path: SmolStr,
+    pub tags: Vec<SmolStr>,
+    pub author_dids: Vec<SmolStr>,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub indexed_at: chrono::DateTime<chrono::Utc>,
+    pub record: SmolStr,
+    pub score: u64,
+    pub snippet: SmolStr,
+}
+
+/// Split a raw query string into search terms.
+///
+/// Whitespace-delimited, capped at [`MAX_TERMS`]. Matching uses substring
+/// functions rather than regex, so no escaping is needed.
+pub fn query_terms(q: &str) -> Vec<&str> {
+    q.split_whitespace().take(MAX_TERMS).collect()
+}
+
+impl Client {
+    /// Full-text search over entries.
+    ///
+    /// All terms must match (in title or body); results are ordered by
+    /// score descending, then recency. `cursor` is a result offset.
+    /// Unlisted and not-yet-published entries are excluded, matching the
+    /// feed queries. Callers should dedupe by rkey (collaborative entries
+    /// exist in multiple repos), so fetch more than `limit` if needed.
+    pub async fn search_entries(
+        &self,
+        q: &str,
+        author_did: Option<&str>,
+        tags: Option<&[&str]>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<EntrySearchRow>, IndexError> {
+        let terms = query_terms(q);
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // Title hits are worth far more than body hits.
+        let score_expr = terms
+            .iter()
+            .map(|_| {
+                "(countSubstringsCaseInsensitiveUTF8(s.title, ?) * 10 \
+                 + countSubstringsCaseInsensitiveUTF8(s.plain_text, ?))"
+            })
+            .collect::<Vec<_>>()
+            .join(" + ");
+
+        let term_conditions = terms
+            .iter()
+            .map(|_| {
+                "(positionCaseInsensitiveUTF8(s.plain_text, ?) > 0 \
+                 OR positionCaseInsensitiveUTF8(s.title, ?) > 0)"
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ");
+
+        let author_condition = if author_did.is_some() {
+            "AND s.did = ?"
+        } else {
+            ""
+        };
+        let tags_condition = if tags.is_some() {
+            "AND hasAny(s.tags, ?)"
+        } else {
+            ""
+        };
+
+        let query = format!(
+            r#"
+            SELECT
+                e.did AS did,
+                e.rkey AS rkey,
+                e.cid AS cid,
+                e.uri AS uri,
+                e.title AS title,
+                e.path AS path,
+                e.tags AS tags,
+                e.author_dids AS author_dids,
+                e.created_at AS created_at,
+                e.updated_at AS updated_at,
+                e.indexed_at AS indexed_at,
+                e.record AS record,
+                {score_expr} AS score,
+                substringUTF8(
+                    s.plain_text,
+                    greatest(1, toInt64(positionCaseInsensitiveUTF8(s.plain_text, ?)) - 60),
+                    180
+                ) AS snippet
+            FROM entry_search s FINAL
+            INNER JOIN entries e FINAL ON
+                e.did = s.did
+                AND e.rkey = s.rkey
+                AND e.deleted_at = toDateTime64(0, 3)
+            WHERE s.deleted_at = toDateTime64(0, 3)
+              AND JSONExtractString(toString(e.record), 'visibility') != 'unlisted'
+              AND (JSONExtractString(toString(e.record), 'publishedAt') = ''
+                   OR parseDateTime64BestEffortOrZero(JSONExtractString(toString(e.record), 'publishedAt'), 3) <= now64(3))
+              {author_condition}
+              {tags_condition}
+              AND {term_conditions}
+            ORDER BY score DESC, e.updated_at DESC
+            LIMIT ? OFFSET ?
+            "#
+        );
+
+        // Bind order must follow placeholder order in the query text:
+        // score terms, snippet anchor, author, tags, term conditions, paging.
+        let mut query = self.inner().query(&query);
+        for term in &terms {
+            query = query.bind(*term).bind(*term);
+        }
+        query = query.bind(terms[0]);
+        if let Some(did) = author_did {
+            query = query.bind(did);
+        }
+        if let Some(t) = tags {
+            query = query.bind(t);
+        }
+        for term in &terms {
+            query = query.bind(*term).bind(*term);
+        }
+
+        let rows = query
+            .bind(limit)
+            .bind(offset)
+            .fetch_all::<EntrySearchRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to search entries".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+}
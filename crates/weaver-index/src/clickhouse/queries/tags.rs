@@ -0,0 +1,90 @@
+//! Tag aggregation queries backed by the tag_activity table
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// Aggregated usage statistics for a single tag
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct TagStatsRow {
+    pub tag: SmolStr,
+    /// Distinct resources carrying this tag across all kinds
+    pub use_count: u64,
+    pub entry_count: u64,
+    pub notebook_count: u64,
+    /// Distinct resources tagged within the last 30 days
+    pub recent_use_count: u64,
+}
+
+impl Client {
+    /// Get the most-used tags overall, ordered by total use count.
+    ///
+    /// Cursor is an offset into the ranking. Counts are distinct over
+    /// (kind, did, rkey) so record updates do not inflate them.
+    pub async fn get_popular_tags(
+        &self,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<TagStatsRow>, IndexError> {
+        let query = r#"
+            SELECT
+                tag,
+                uniqExact((kind, did, rkey)) as use_count,
+                uniqExactIf((kind, did, rkey), kind = 'entry') as entry_count,
+                uniqExactIf((kind, did, rkey), kind = 'notebook') as notebook_count,
+                uniqExactIf((kind, did, rkey), event_time >= subtractDays(now64(3), 30)) as recent_use_count
+            FROM tag_activity FINAL
+            GROUP BY tag
+            ORDER BY use_count DESC, tag ASC
+            LIMIT ? OFFSET ?
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all::<TagStatsRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get popular tags".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+
+    /// Get usage statistics for a single tag.
+    ///
+    /// Returns None for tags that have never been applied.
+    pub async fn get_tag_stats(&self, tag: &str) -> Result<Option<TagStatsRow>, IndexError> {
+        let query = r#"
+            SELECT
+                tag,
+                uniqExact((kind, did, rkey)) as use_count,
+                uniqExactIf((kind, did, rkey), kind = 'entry') as entry_count,
+                uniqExactIf((kind, did, rkey), kind = 'notebook') as notebook_count,
+                uniqExactIf((kind, did, rkey), event_time >= subtractDays(now64(3), 30)) as recent_use_count
+            FROM tag_activity FINAL
+            WHERE tag = ?
+            GROUP BY tag
+            LIMIT 1
+        "#;
+
+        let row = self
+            .inner()
+            .query(query)
+            .bind(tag)
+            .fetch_optional::<TagStatsRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get tag stats".into(),
+                source: e,
+            })?;
+
+        Ok(row)
+    }
+}
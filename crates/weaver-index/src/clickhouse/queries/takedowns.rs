@@ -0,0 +1,99 @@
+//! Moderation takedown queries
+
+use clickhouse::Row;
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+use crate::error::{ClickHouseError, IndexError};
+
+/// Takedown row from the takedowns table
+#[derive(Debug, Clone, Row, Deserialize)]
+pub struct TakedownRow {
+    pub subject: SmolStr,
+    pub active: u8,
+    pub reason: String,
+    #[serde(with = "clickhouse::serde::chrono::datetime64::millis")]
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl Client {
+    /// Record a takedown (or lift one) for a DID or AT URI.
+    pub async fn set_takedown(
+        &self,
+        subject: &str,
+        active: bool,
+        reason: &str,
+    ) -> Result<(), IndexError> {
+        let query = r#"
+            INSERT INTO takedowns (subject, active, reason)
+            VALUES (?, ?, ?)
+        "#;
+
+        self.inner()
+            .query(query)
+            .bind(subject)
+            .bind(active as u8)
+            .bind(reason)
+            .execute()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to set takedown".into(),
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    /// Get the current takedown state for one subject.
+    pub async fn get_takedown(&self, subject: &str) -> Result<Option<TakedownRow>, IndexError> {
+        let query = r#"
+            SELECT
+                subject,
+                active,
+                reason,
+                updated_at
+            FROM takedowns FINAL
+            WHERE subject = ?
+            LIMIT 1
+        "#;
+
+        let row = self
+            .inner()
+            .query(query)
+            .bind(subject)
+            .fetch_optional::<TakedownRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to get takedown".into(),
+                source: e,
+            })?;
+
+        Ok(row)
+    }
+
+    /// List all takedowns, active and lifted (the admin audit view).
+    pub async fn list_takedowns(&self) -> Result<Vec<TakedownRow>, IndexError> {
+        let query = r#"
+            SELECT
+                subject,
+                active,
+                reason,
+                updated_at
+            FROM takedowns FINAL
+            ORDER BY subject
+        "#;
+
+        let rows = self
+            .inner()
+            .query(query)
+            .fetch_all::<TakedownRow>()
+            .await
+            .map_err(|e| ClickHouseError::Query {
+                message: "failed to list takedowns".into(),
+                source: e,
+            })?;
+
+        Ok(rows)
+    }
+}
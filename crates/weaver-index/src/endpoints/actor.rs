@@ -171,22 +171,51 @@ pub async fn get_profile(
 ///
 /// For handles: tries handle_mappings first, falls back to external resolver.
 /// For DIDs: returns as-is.
+///
+/// Taken-down accounts resolve to not-found. This is the chokepoint
+/// every actor- and URI-addressed endpoint goes through, so an account
+/// takedown suppresses the whole account in one place; acknowledging
+/// the takedown here would leak moderation state.
 pub async fn resolve_actor<'a>(
     state: &AppState,
     actor: &AtIdentifier<'a>,
+) -> Result<Did<'static>, XrpcErrorResponse> {
+    let did = resolve_actor_did(state, actor).await?;
+    if state.takedowns.is_did_taken_down(did.as_str()) {
+        return Err(XrpcErrorResponse::not_found("Account not found"));
+    }
+    Ok(did)
+}
+
+/// Resolution without the takedown check (shared by [`resolve_actor`]).
+async fn resolve_actor_did<'a>(
+    state: &AppState,
+    actor: &AtIdentifier<'a>,
 ) -> Result<Did<'static>, XrpcErrorResponse> {
     match actor {
         AtIdentifier::Did(did) => Ok(did.clone().into_static()),
         AtIdentifier::Handle(handle) => {
             let handle_str = handle.as_str();
 
-            // Try handle_mappings first
+            // In-memory cache first: O(1), refreshed by #identity events.
+            if let Some(cached_did) = state.identity_cache.get_did(handle_str) {
+                crate::observability::record_identity_cache_lookup(true);
+                let did = Did::new(&cached_did).map_err(|e| {
+                    tracing::error!("Invalid DID in identity cache: {}", e);
+                    XrpcErrorResponse::internal_error("Invalid DID stored")
+                })?;
+                return Ok(did.into_static());
+            }
+            crate::observability::record_identity_cache_lookup(false);
+
+            // Try handle_mappings next
             match state.clickhouse.resolve_handle(handle_str).await {
                 Ok(Some(mapping)) => {
                     let did = Did::new(&mapping.did).map_err(|e| {
                         tracing::error!("Invalid DID in handle_mappings: {}", e);
                         XrpcErrorResponse::internal_error("Invalid DID stored")
                     })?;
+                    state.identity_cache.insert(handle_str, &mapping.did);
                     return Ok(did.into_static());
                 }
                 Ok(None) => {
@@ -203,6 +232,8 @@ pub async fn resolve_actor<'a>(
                 XrpcErrorResponse::invalid_request(format!("Could not resolve handle: {}", handle))
             })?;
 
+            state.identity_cache.insert(handle_str, resolved.as_str());
+
             // Cache the result (fire-and-forget)
             let clickhouse = state.clickhouse.clone();
             let handle_owned = handle_str.to_string();
@@ -263,7 +294,13 @@ pub async fn get_actor_notebooks(
 
     // Check if there are more
     let has_more = notebook_rows.len() > limit as usize;
-    let notebook_rows: Vec<_> = notebook_rows.into_iter().take(limit as usize).collect();
+    // The account itself already passed resolve_actor; this drops
+    // individually taken-down records.
+    let notebook_rows: Vec<_> = notebook_rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !super::is_suppressed(&state, &r.did, &r.uri))
+        .collect();
 
     // Collect author DIDs for hydration
     let mut all_author_dids: HashSet<&str> = HashSet::new();
@@ -364,7 +401,11 @@ pub async fn get_actor_entries(
 
     // Check if there are more
     let has_more = entry_rows.len() > limit as usize;
-    let entry_rows: Vec<_> = entry_rows.into_iter().take(limit as usize).collect();
+    let entry_rows: Vec<_> = entry_rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !super::is_suppressed(&state, &r.did, &r.uri))
+        .collect();
 
     // Batch fetch contributors for all entries
     let entry_keys: Vec<(&str, &str)> = entry_rows
@@ -0,0 +1,58 @@
+//! Operator authentication for the plain JSON `/admin/*` routes.
+//!
+//! The admin surface configures filesystem paths, deploy webhooks, and
+//! moderation state, so every handler under it checks the operator token
+//! before doing anything else. No configured token means no admin
+//! surface at all, rather than an open one.
+
+use axum::http::HeaderMap;
+
+use crate::endpoints::repo::XrpcErrorResponse;
+
+/// Admin requests must present this header matching `WEAVER_ADMIN_TOKEN`.
+const ADMIN_TOKEN_HEADER: &str = "x-weaver-admin-token";
+
+/// Require the operator token on an admin request.
+pub fn require_admin(headers: &HeaderMap) -> Result<(), XrpcErrorResponse> {
+    let expected = std::env::var("WEAVER_ADMIN_TOKEN").unwrap_or_default();
+    let presented = headers
+        .get(ADMIN_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok());
+    if token_matches(&expected, presented) {
+        Ok(())
+    } else {
+        Err(XrpcErrorResponse::auth_required("Admin token required"))
+    }
+}
+
+fn token_matches(expected: &str, presented: Option<&str>) -> bool {
+    if expected.is_empty() {
+        return false;
+    }
+    let Some(presented) = presented else {
+        return false;
+    };
+    // Constant-time compare; the token is a bearer credential.
+    ring::constant_time::verify_slices_are_equal(presented.as_bytes(), expected.as_bytes()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_matches_only_exact_token() {
+        assert!(token_matches("secret", Some("secret")));
+        assert!(!token_matches("secret", Some("secre")));
+        assert!(!token_matches("secret", Some("secrets")));
+        assert!(!token_matches("secret", None));
+    }
+
+    #[test]
+    fn empty_expected_token_matches_nothing() {
+        // An unset or empty token must close the admin surface, not open it.
+        assert!(!token_matches("", Some("")));
+        assert!(!token_matches("", Some("anything")));
+        assert!(!token_matches("", None));
+    }
+}
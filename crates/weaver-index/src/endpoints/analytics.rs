@@ -0,0 +1,154 @@
+//! View analytics endpoints.
+//!
+//! `POST /analytics/beacon` ingests view and reading-time beacons from
+//! entry pages. It needs no auth — views are anonymous by design and the
+//! table holds no per-reader data — but it validates the target URI and
+//! re-reduces referrers to an origin so a hand-crafted beacon cannot
+//! smuggle full URLs into storage. This is a plain JSON route, not an
+//! XRPC lexicon, so `navigator.sendBeacon` can hit it.
+//!
+//! `sh.weaver.analytics.getSummary` returns the aggregates and is
+//! private: service auth is required and the viewer must be the actor
+//! whose analytics are requested.
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use jacquard::IntoStatic;
+use jacquard_axum::ExtractXrpc;
+use jacquard_axum::service_auth::ExtractOptionalServiceAuth;
+use weaver_common::analytics::{
+    AnalyticsSummary, EntryViewStats, GetAnalyticsSummary, ReadingTimeBucket, ReferrerStats,
+    ViewBeacon, referrer_origin,
+};
+
+use crate::endpoints::actor::resolve_actor;
+use crate::endpoints::repo::XrpcErrorResponse;
+use crate::endpoints::resolve_uri;
+use crate::server::AppState;
+
+/// Entry record collection beacons may target.
+const ENTRY_COLLECTION: &str = "sh.weaver.notebook.entry";
+
+/// Reading times above this are parked tabs, not reading; clamp so one
+/// forgotten tab cannot drag the quantiles.
+const MAX_READING_MS: u64 = 2 * 60 * 60 * 1000;
+
+/// Handle `POST /analytics/beacon`
+pub async fn record_view(
+    State(state): State<AppState>,
+    Json(beacon): Json<ViewBeacon<'static>>,
+) -> Result<StatusCode, XrpcErrorResponse> {
+    let resolved = resolve_uri(&state, &beacon.uri).await?;
+    if resolved.collection != ENTRY_COLLECTION {
+        return Err(XrpcErrorResponse::invalid_request(
+            "Beacons may only target entry records",
+        ));
+    }
+
+    // Trust nothing about the referrer: reduce it server-side even though
+    // the app already did, since the route is open.
+    let referrer = beacon
+        .referrer
+        .as_deref()
+        .and_then(referrer_origin)
+        .unwrap_or_default();
+
+    let (kind, reading_ms) = match beacon.reading_ms {
+        Some(ms) => ("read", ms.min(MAX_READING_MS)),
+        None => ("view", 0),
+    };
+
+    state
+        .clickhouse
+        .insert_entry_view(&resolved.did, &resolved.rkey, kind, &referrer, reading_ms)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to insert entry view: {}", e);
+            XrpcErrorResponse::internal_error("Database insert failed")
+        })?;
+
+    // Beacon senders ignore the response; 202 says "queued" honestly.
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Handle sh.weaver.analytics.getSummary
+///
+/// Returns per-entry view counts, referrer origins, and the reading-time
+/// histogram for one account. Requires authentication; only the account
+/// itself may read its analytics.
+pub async fn get_summary(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(viewer): ExtractOptionalServiceAuth,
+    ExtractXrpc(args): ExtractXrpc<GetAnalyticsSummary>,
+) -> Result<Json<AnalyticsSummary<'static>>, XrpcErrorResponse> {
+    let viewer =
+        viewer.ok_or_else(|| XrpcErrorResponse::auth_required("Authentication required"))?;
+
+    let actor_did = resolve_actor(&state, &args.actor).await?;
+    if viewer.did().as_str() != actor_did.as_str() {
+        return Err(XrpcErrorResponse::forbidden(
+            "Cannot view another account's analytics",
+        ));
+    }
+
+    let days = args.days.clamp(1, 90);
+
+    let entries = state
+        .clickhouse
+        .entry_view_stats(actor_did.as_str(), days)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get entry view stats: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let referrers = state
+        .clickhouse
+        .entry_view_referrers(actor_did.as_str(), days, 25)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get referrer stats: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let reading_time = state
+        .clickhouse
+        .reading_time_distribution(actor_did.as_str(), days)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get reading time distribution: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let summary = AnalyticsSummary {
+        did: actor_did.clone(),
+        days,
+        entries: entries
+            .into_iter()
+            .map(|row| EntryViewStats {
+                rkey: row.rkey.to_string().into(),
+                title: row.title.into(),
+                view_count: row.view_count,
+                p50_reading_ms: row.p50_reading_ms,
+                p90_reading_ms: row.p90_reading_ms,
+            })
+            .collect(),
+        referrers: referrers
+            .into_iter()
+            .map(|row| ReferrerStats {
+                referrer: row.referrer.to_string().into(),
+                view_count: row.view_count,
+            })
+            .collect(),
+        reading_time: reading_time
+            .into_iter()
+            .map(|row| ReadingTimeBucket {
+                floor_ms: row.floor_ms,
+                view_count: row.view_count,
+            })
+            .collect(),
+    };
+
+    Ok(Json(summary.into_static()))
+}
@@ -9,6 +9,7 @@ use jacquard::types::datetime::Datetime;
 use jacquard::types::string::{AtUri, Cid, Did, Handle};
 use jacquard_axum::ExtractXrpc;
 use jacquard_axum::service_auth::ExtractOptionalServiceAuth;
+use smol_str::SmolStr;
 
 use weaver_api::com_atproto::repo::strong_ref::StrongRef;
 use weaver_api::sh_weaver::actor::ProfileViewBasic;
@@ -23,10 +24,10 @@ use weaver_api::sh_weaver::collab::get_resource_sessions::{
 };
 use weaver_api::sh_weaver::collab::{CollaborationStateView, ParticipantStateView, SessionView};
 
-use crate::clickhouse::{CollaboratorRow, ProfileRow};
+use crate::clickhouse::{CollaboratorRow, ProfileRow, SessionRow};
 use crate::endpoints::actor::Viewer;
-use crate::endpoints::{non_empty_str, resolve_uri};
 use crate::endpoints::repo::XrpcErrorResponse;
+use crate::endpoints::{non_empty_str, resolve_uri};
 use crate::server::AppState;
 
 /// Handle sh.weaver.collab.getResourceParticipants
@@ -331,6 +332,29 @@ fn build_participant_state(
         .build())
 }
 
+/// Maximum concurrent sessions a single DID may advertise for a resource.
+///
+/// Mirrors `MAX_SESSIONS_PER_DID` in `weaver-editor-crdt`. The client
+/// enforces it too, but a runaway client flooding session records must not
+/// amplify into peer discovery (and relay connections) for everyone else.
+const MAX_SESSIONS_PER_DID: usize = 4;
+
+/// Keep only the newest sessions per DID, up to [`MAX_SESSIONS_PER_DID`].
+fn cap_sessions_per_did(mut rows: Vec<SessionRow>) -> Vec<SessionRow> {
+    // Newest first, so the cap keeps the most recently created sessions.
+    rows.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let mut counts: HashMap<SmolStr, usize> = HashMap::new();
+    let mut kept = Vec::with_capacity(rows.len());
+    for row in rows {
+        let count = counts.entry(row.did.clone()).or_insert(0);
+        *count += 1;
+        if *count <= MAX_SESSIONS_PER_DID {
+            kept.push(row);
+        }
+    }
+    kept
+}
+
 /// Handle sh.weaver.collab.getResourceSessions
 ///
 /// Returns active real-time collaboration sessions for a resource.
@@ -344,15 +368,18 @@ pub async fn get_resource_sessions(
     // Resolve URI and get canonical form
     let resolved = resolve_uri(&state, &args.resource).await?;
 
-    // Get active sessions
-    let session_rows = state
-        .clickhouse
-        .get_resource_sessions(&resolved.canonical_uri)
-        .await
-        .map_err(|e| {
-            tracing::error!("Failed to get resource sessions: {}", e);
-            XrpcErrorResponse::internal_error("Database query failed")
-        })?;
+    // Get active sessions, capped per DID so a flooding client cannot
+    // amplify into everyone's peer discovery.
+    let session_rows = cap_sessions_per_did(
+        state
+            .clickhouse
+            .get_resource_sessions(&resolved.canonical_uri)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to get resource sessions: {}", e);
+                XrpcErrorResponse::internal_error("Database query failed")
+            })?,
+    );
 
     if session_rows.is_empty() {
         return Ok(Json(
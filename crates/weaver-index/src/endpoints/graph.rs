@@ -0,0 +1,446 @@
+//! sh.weaver.graph.* endpoint handlers
+//!
+//! Tag aggregation queries backed by the tag_activity table.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{Json, extract::State};
+use jacquard::IntoStatic;
+use jacquard::cowstr::ToCowStr;
+use jacquard::types::string::{AtUri, Cid, Did, Handle, Uri};
+use jacquard::types::value::Data;
+use jacquard_axum::ExtractXrpc;
+use jacquard_axum::service_auth::ExtractOptionalServiceAuth;
+use smol_str::SmolStr;
+use weaver_api::sh_weaver::actor::{ProfileDataView, ProfileDataViewInner, ProfileView};
+use weaver_api::sh_weaver::graph::{
+    TagView,
+    get_popular_tags::{GetPopularTagsOutput, GetPopularTagsRequest},
+    get_tagged_resources::{
+        GetTaggedResourcesOutput, GetTaggedResourcesOutputResourcesItem, GetTaggedResourcesRequest,
+    },
+};
+use weaver_api::sh_weaver::notebook::{AuthorListView, EntryView, NotebookView};
+
+use crate::clickhouse::{EntryRow, NotebookRow, ProfileRow, TagStatsRow};
+use crate::endpoints::actor::Viewer;
+use crate::endpoints::repo::XrpcErrorResponse;
+use crate::server::AppState;
+
+/// Handle sh.weaver.graph.getPopularTags
+///
+/// Returns the most-used tags across the network, ranked by total use
+/// count. Cursor is an offset into the ranking.
+pub async fn get_popular_tags(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(viewer): ExtractOptionalServiceAuth,
+    ExtractXrpc(args): ExtractXrpc<GetPopularTagsRequest>,
+) -> Result<Json<GetPopularTagsOutput<'static>>, XrpcErrorResponse> {
+    let _viewer: Viewer = viewer;
+
+    let limit = args.limit.unwrap_or(50).clamp(1, 100) as u32;
+    let offset: u32 = match args.cursor.as_deref() {
+        Some(c) => c
+            .parse()
+            .map_err(|_| XrpcErrorResponse::invalid_request("Invalid cursor format"))?,
+        None => 0,
+    };
+
+    let rows = state
+        .clickhouse
+        .get_popular_tags(limit + 1, offset)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get popular tags: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Check if there are more
+    let has_more = rows.len() > limit as usize;
+    let rows: Vec<_> = rows.into_iter().take(limit as usize).collect();
+
+    let tags: Vec<TagView<'static>> = rows.iter().map(tag_stats_to_view).collect();
+
+    let next_cursor = if has_more {
+        Some((offset + limit).to_cowstr().into_static())
+    } else {
+        None
+    };
+
+    Ok(Json(
+        GetPopularTagsOutput {
+            tags,
+            cursor: next_cursor,
+            extra_data: None,
+        }
+        .into_static(),
+    ))
+}
+
+/// Handle sh.weaver.graph.getTaggedResources
+///
+/// Returns entries and notebooks carrying a tag, newest first, along
+/// with aggregated stats for the tag itself.
+pub async fn get_tagged_resources(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(viewer): ExtractOptionalServiceAuth,
+    ExtractXrpc(args): ExtractXrpc<GetTaggedResourcesRequest>,
+) -> Result<Json<GetTaggedResourcesOutput<'static>>, XrpcErrorResponse> {
+    let _viewer: Viewer = viewer;
+
+    let tag = args.tag.as_ref();
+    let limit = args.limit.unwrap_or(50).clamp(1, 100) as u32;
+    let cursor = parse_cursor(args.cursor.as_deref())?;
+    let sort = args.sort.as_deref().unwrap_or("recent");
+    let resource_type = args.resource_type.as_deref().unwrap_or("all");
+
+    // Only author-applied tags (the tags array on the record itself) are
+    // indexed today. Community tag applications are not, so excluding
+    // author tags leaves nothing to return; includeCommunityTags is a
+    // no-op either way.
+    let include_author_tags = args.include_author_tags.unwrap_or(true);
+
+    let tag_filter = [tag];
+    let tags_arg = Some(&tag_filter[..]);
+
+    let entry_rows: Vec<EntryRow> =
+        if include_author_tags && (resource_type == "all" || resource_type == "entry") {
+            state
+                .clickhouse
+                .get_entry_feed(sort, tags_arg, limit + 1, cursor)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get tagged entries: {}", e);
+                    XrpcErrorResponse::internal_error("Database query failed")
+                })?
+        } else {
+            Vec::new()
+        };
+
+    let notebook_rows: Vec<NotebookRow> =
+        if include_author_tags && (resource_type == "all" || resource_type == "notebook") {
+            state
+                .clickhouse
+                .get_notebook_feed(sort, tags_arg, limit + 1, cursor)
+                .await
+                .map_err(|e| {
+                    tracing::error!("Failed to get tagged notebooks: {}", e);
+                    XrpcErrorResponse::internal_error("Database query failed")
+                })?
+        } else {
+            Vec::new()
+        };
+
+    // Suppressed rows read as absence; pages may come up short.
+    let entry_rows: Vec<EntryRow> = entry_rows
+        .into_iter()
+        .filter(|r| !super::is_suppressed(&state, &r.did, &r.uri))
+        .collect();
+    let notebook_rows: Vec<NotebookRow> = notebook_rows
+        .into_iter()
+        .filter(|r| !super::is_suppressed(&state, &r.did, &r.uri))
+        .collect();
+
+    // Batch fetch contributors for entries (evidence-based author lists)
+    let entry_keys: Vec<(&str, &str)> = entry_rows
+        .iter()
+        .map(|e| (e.did.as_str(), e.rkey.as_str()))
+        .collect();
+    let contributors_map = state
+        .clickhouse
+        .get_entry_contributors_batch(&entry_keys)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to batch fetch contributors: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Collect all author DIDs for profile hydration
+    let mut all_author_dids: HashSet<&str> = HashSet::new();
+    for contributors in contributors_map.values() {
+        for did in contributors {
+            all_author_dids.insert(did.as_str());
+        }
+    }
+    for nb in &notebook_rows {
+        for did in &nb.author_dids {
+            all_author_dids.insert(did.as_str());
+        }
+    }
+
+    // Batch fetch profiles
+    let author_dids_vec: Vec<&str> = all_author_dids.into_iter().collect();
+    let profiles = state
+        .clickhouse
+        .get_profiles_batch(&author_dids_vec)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to batch fetch profiles: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let profile_map: HashMap<&str, &ProfileRow> =
+        profiles.iter().map(|p| (p.did.as_str(), p)).collect();
+
+    // Merge both kinds newest-first; both queries already honour the
+    // same created_at cursor, so one shared keyset cursor works.
+    let mut merged: Vec<(i64, GetTaggedResourcesOutputResourcesItem<'static>)> =
+        Vec::with_capacity(entry_rows.len() + notebook_rows.len());
+
+    for entry_row in &entry_rows {
+        let entry_key = (entry_row.did.clone(), entry_row.rkey.clone());
+        let contributors = contributors_map
+            .get(&entry_key)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+
+        let entry_view = build_entry_view_with_authors(entry_row, contributors, &profile_map)?;
+        merged.push((
+            entry_row.created_at.timestamp_millis(),
+            GetTaggedResourcesOutputResourcesItem::EntryView(Box::new(entry_view)),
+        ));
+    }
+
+    for nb_row in &notebook_rows {
+        let notebook_view = build_notebook_view(nb_row, &profile_map)?;
+        merged.push((
+            nb_row.created_at.timestamp_millis(),
+            GetTaggedResourcesOutputResourcesItem::NotebookView(Box::new(notebook_view)),
+        ));
+    }
+
+    merged.sort_by(|a, b| b.0.cmp(&a.0));
+    let has_more = merged.len() > limit as usize;
+    merged.truncate(limit as usize);
+
+    let next_cursor = if has_more {
+        merged.last().map(|(ts, _)| ts.to_cowstr().into_static())
+    } else {
+        None
+    };
+
+    let resources: Vec<GetTaggedResourcesOutputResourcesItem<'static>> =
+        merged.into_iter().map(|(_, item)| item).collect();
+
+    // Aggregated stats for the tag itself; absent for never-used tags.
+    let tag_view = state
+        .clickhouse
+        .get_tag_stats(tag)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get tag stats: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?
+        .as_ref()
+        .map(tag_stats_to_view);
+
+    Ok(Json(
+        GetTaggedResourcesOutput {
+            resources,
+            cursor: next_cursor,
+            tag: tag_view,
+            extra_data: None,
+        }
+        .into_static(),
+    ))
+}
+
+/// Convert a TagStatsRow to a TagView
+fn tag_stats_to_view(row: &TagStatsRow) -> TagView<'static> {
+    TagView::new()
+        .tag(row.tag.to_cowstr().into_static())
+        .use_count(row.use_count as i64)
+        .entry_count(row.entry_count as i64)
+        .notebook_count(row.notebook_count as i64)
+        .recent_use_count(row.recent_use_count as i64)
+        .build()
+}
+
+/// Build a NotebookView from a NotebookRow using the profile map
+fn build_notebook_view(
+    nb_row: &NotebookRow,
+    profile_map: &HashMap<&str, &ProfileRow>,
+) -> Result<NotebookView<'static>, XrpcErrorResponse> {
+    let notebook_uri = AtUri::new(&nb_row.uri).map_err(|e| {
+        tracing::error!("Invalid notebook URI in db: {}", e);
+        XrpcErrorResponse::internal_error("Invalid URI stored")
+    })?;
+
+    let notebook_cid = Cid::new(nb_row.cid.as_bytes()).map_err(|e| {
+        tracing::error!("Invalid notebook CID in db: {}", e);
+        XrpcErrorResponse::internal_error("Invalid CID stored")
+    })?;
+
+    let authors = hydrate_authors(&nb_row.author_dids, profile_map)?;
+    let record = parse_record_json(&nb_row.record)?;
+
+    let notebook = NotebookView::new()
+        .uri(notebook_uri.into_static())
+        .cid(notebook_cid.into_static())
+        .authors(authors)
+        .record(record)
+        .indexed_at(nb_row.indexed_at.fixed_offset())
+        .maybe_title(non_empty_cowstr(&nb_row.title))
+        .maybe_path(non_empty_cowstr(&nb_row.path))
+        .build();
+
+    Ok(notebook)
+}
+
+/// Build an EntryView from an EntryRow with explicit author list (evidence-based contributors)
+fn build_entry_view_with_authors(
+    entry_row: &EntryRow,
+    author_dids: &[SmolStr],
+    profile_map: &HashMap<&str, &ProfileRow>,
+) -> Result<EntryView<'static>, XrpcErrorResponse> {
+    let entry_uri = AtUri::new(&entry_row.uri).map_err(|e| {
+        tracing::error!("Invalid entry URI in db: {}", e);
+        XrpcErrorResponse::internal_error("Invalid URI stored")
+    })?;
+
+    let entry_cid = Cid::new(entry_row.cid.as_bytes()).map_err(|e| {
+        tracing::error!("Invalid entry CID in db: {}", e);
+        XrpcErrorResponse::internal_error("Invalid CID stored")
+    })?;
+
+    let authors = hydrate_authors(author_dids, profile_map)?;
+    let record = parse_record_json(&entry_row.record)?;
+
+    let entry_view = EntryView::new()
+        .uri(entry_uri.into_static())
+        .cid(entry_cid.into_static())
+        .authors(authors)
+        .record(record)
+        .indexed_at(entry_row.indexed_at.fixed_offset())
+        .maybe_title(non_empty_cowstr(&entry_row.title))
+        .maybe_path(non_empty_cowstr(&entry_row.path))
+        .build();
+
+    Ok(entry_view)
+}
+
+/// Hydrate author list from DIDs using profile map
+fn hydrate_authors(
+    author_dids: &[SmolStr],
+    profile_map: &HashMap<&str, &ProfileRow>,
+) -> Result<Vec<AuthorListView<'static>>, XrpcErrorResponse> {
+    let mut authors = Vec::with_capacity(author_dids.len());
+
+    for (idx, did_str) in author_dids.iter().enumerate() {
+        let profile_data = if let Some(profile) = profile_map.get(did_str.as_str()) {
+            profile_to_data_view(profile)?
+        } else {
+            // No profile found - create minimal view with just the DID
+            let did = Did::new(did_str).map_err(|e| {
+                tracing::error!("Invalid DID in author_dids: {}", e);
+                XrpcErrorResponse::internal_error("Invalid DID stored")
+            })?;
+
+            let inner_profile = ProfileView::new()
+                .did(did.into_static())
+                .handle(
+                    Handle::new(did_str)
+                        .unwrap_or_else(|_| Handle::new("unknown.invalid").unwrap()),
+                )
+                .build();
+
+            ProfileDataView::new()
+                .inner(ProfileDataViewInner::ProfileView(Box::new(inner_profile)))
+                .build()
+        };
+
+        let author_view = AuthorListView::new()
+            .index(idx as i64)
+            .record(profile_data.into_static())
+            .build();
+
+        authors.push(author_view);
+    }
+
+    Ok(authors)
+}
+
+/// Convert ProfileRow to ProfileDataView
+fn profile_to_data_view(
+    profile: &ProfileRow,
+) -> Result<ProfileDataView<'static>, XrpcErrorResponse> {
+    let did = Did::new(&profile.did).map_err(|e| {
+        tracing::error!("Invalid DID in profile: {}", e);
+        XrpcErrorResponse::internal_error("Invalid DID stored")
+    })?;
+
+    let handle = if profile.handle.is_empty() {
+        // Use DID as fallback handle (not ideal but functional)
+        Handle::new(&profile.did).unwrap_or_else(|_| Handle::new("unknown.invalid").unwrap())
+    } else {
+        Handle::new(&profile.handle).map_err(|e| {
+            tracing::error!("Invalid handle in profile: {}", e);
+            XrpcErrorResponse::internal_error("Invalid handle stored")
+        })?
+    };
+
+    // Build avatar URL from CID if present
+    let avatar = if !profile.avatar_cid.is_empty() {
+        let url = format!(
+            "https://cdn.bsky.app/img/avatar/plain/{}/{}@jpeg",
+            profile.did, profile.avatar_cid
+        );
+        Uri::new_owned(url).ok()
+    } else {
+        None
+    };
+
+    // Build banner URL from CID if present
+    let banner = if !profile.banner_cid.is_empty() {
+        let url = format!(
+            "https://cdn.bsky.app/img/banner/plain/{}/{}@jpeg",
+            profile.did, profile.banner_cid
+        );
+        Uri::new_owned(url).ok()
+    } else {
+        None
+    };
+
+    let inner_profile = ProfileView::new()
+        .did(did.into_static())
+        .handle(handle.into_static())
+        .maybe_display_name(non_empty_cowstr(&profile.display_name))
+        .maybe_description(non_empty_cowstr(&profile.description))
+        .maybe_avatar(avatar)
+        .maybe_banner(banner)
+        .build();
+
+    let profile_data = ProfileDataView::new()
+        .inner(ProfileDataViewInner::ProfileView(Box::new(inner_profile)))
+        .build();
+
+    Ok(profile_data)
+}
+
+/// Convert SmolStr to Option<CowStr> if non-empty
+fn non_empty_cowstr(s: &SmolStr) -> Option<jacquard::CowStr<'static>> {
+    if s.is_empty() {
+        None
+    } else {
+        Some(s.to_cowstr().into_static())
+    }
+}
+
+/// Parse record JSON string into owned Data
+fn parse_record_json(json: &str) -> Result<Data<'static>, XrpcErrorResponse> {
+    let data: Data<'_> = serde_json::from_str(json).map_err(|e| {
+        tracing::error!("Failed to parse record JSON: {}", e);
+        XrpcErrorResponse::internal_error("Invalid record JSON stored")
+    })?;
+    Ok(data.into_static())
+}
+
+/// Parse cursor string to i64 timestamp millis
+fn parse_cursor(cursor: Option<&str>) -> Result<Option<i64>, XrpcErrorResponse> {
+    cursor
+        .map(|c| {
+            c.parse::<i64>()
+                .map_err(|_| XrpcErrorResponse::invalid_request("Invalid cursor format"))
+        })
+        .transpose()
+}
@@ -0,0 +1,270 @@
+//! Admin endpoints for static mirror settings.
+//!
+//! Operators configure which notebooks get a static mirror here; the
+//! background rebuild task in [`crate::tasks`] picks the settings up on
+//! its next pass. These are plain JSON routes, not XRPC lexicons, and
+//! every handler requires the operator token (see
+//! [`crate::endpoints::admin`]).
+
+use std::path::{Component, Path, PathBuf};
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use jacquard::types::string::AtUri;
+use serde::{Deserialize, Serialize};
+
+use crate::clickhouse::StaticMirrorRow;
+use crate::endpoints::admin::require_admin;
+use crate::endpoints::repo::XrpcErrorResponse;
+use crate::endpoints::resolve_uri;
+use crate::server::AppState;
+
+/// Settings payload for creating or updating a mirror
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PutMirrorRequest {
+    /// AT URI of the notebook to mirror
+    pub uri: String,
+    /// Absolute filesystem path the rendered site is written to
+    pub output_dir: String,
+    /// Deploy hook POSTed after each successful rebuild
+    #[serde(default)]
+    pub webhook_url: String,
+    /// Disabled mirrors keep their settings but are skipped by the task
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Root directory mirror output paths must live under, from
+/// `WEAVER_MIRROR_ROOT`.
+fn mirror_root() -> Option<PathBuf> {
+    let root = std::env::var("WEAVER_MIRROR_ROOT").ok()?;
+    if root.is_empty() {
+        return None;
+    }
+    let root = PathBuf::from(root);
+    root.is_absolute().then_some(root)
+}
+
+/// Check that `dir` is strictly inside `root`.
+///
+/// Compared lexically rather than canonicalized: the output directory
+/// usually does not exist yet. `..` and `.` components are rejected so
+/// the prefix comparison cannot be escaped, and the root itself is
+/// excluded because the rebuild task replaces the directory's contents.
+fn within_mirror_root(root: &Path, dir: &Path) -> bool {
+    if !dir.is_absolute() {
+        return false;
+    }
+    if dir
+        .components()
+        .any(|c| matches!(c, Component::ParentDir | Component::CurDir))
+    {
+        return false;
+    }
+    dir.starts_with(root) && dir != root
+}
+
+/// Query parameters for looking up one mirror
+#[derive(Debug, Deserialize)]
+pub struct MirrorQuery {
+    /// AT URI of the mirrored notebook
+    pub uri: String,
+}
+
+/// Mirror settings as returned to the operator
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorView {
+    pub uri: String,
+    pub output_dir: String,
+    pub webhook_url: String,
+    pub enabled: bool,
+    pub updated_at: String,
+}
+
+impl MirrorView {
+    fn from_row(row: StaticMirrorRow) -> Self {
+        Self {
+            uri: format!("at://{}/sh.weaver.notebook.book/{}", row.did, row.rkey),
+            output_dir: row.output_dir,
+            webhook_url: row.webhook_url,
+            enabled: row.enabled != 0,
+            updated_at: row.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response for listing all configured mirrors
+#[derive(Debug, Serialize)]
+pub struct ListMirrorsResponse {
+    pub mirrors: Vec<MirrorView>,
+}
+
+/// Resolve a notebook AT URI to (did, rkey), rejecting other collections.
+async fn resolve_notebook_uri(
+    state: &AppState,
+    uri: &str,
+) -> Result<(String, String), XrpcErrorResponse> {
+    let uri = AtUri::new(uri)
+        .map_err(|e| XrpcErrorResponse::invalid_request(format!("Invalid AT URI: {}", e)))?;
+
+    let resolved = resolve_uri(state, &uri).await?;
+
+    if resolved.collection != "sh.weaver.notebook.book" {
+        return Err(XrpcErrorResponse::invalid_request(
+            "URI must reference a sh.weaver.notebook.book record",
+        ));
+    }
+
+    Ok((resolved.did.to_string(), resolved.rkey.to_string()))
+}
+
+/// Create or update static mirror settings for a notebook
+pub async fn put_mirror(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<PutMirrorRequest>,
+) -> Result<Json<MirrorView>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    let (did, rkey) = resolve_notebook_uri(&state, &body.uri).await?;
+
+    // Mirrors write whole directory trees wherever this points, so an
+    // unconstrained path would be an arbitrary filesystem write primitive.
+    let Some(root) = mirror_root() else {
+        return Err(XrpcErrorResponse::invalid_request(
+            "Static mirrors are disabled: WEAVER_MIRROR_ROOT is not configured",
+        ));
+    };
+    if !within_mirror_root(&root, Path::new(&body.output_dir)) {
+        return Err(XrpcErrorResponse::invalid_request(
+            "outputDir must be an absolute path inside the configured mirror root",
+        ));
+    }
+
+    if !body.webhook_url.is_empty()
+        && !body.webhook_url.starts_with("http://")
+        && !body.webhook_url.starts_with("https://")
+    {
+        return Err(XrpcErrorResponse::invalid_request(
+            "webhookUrl must be an http(s) URL",
+        ));
+    }
+
+    // The notebook must actually be indexed before a mirror makes sense.
+    let notebook = state
+        .clickhouse
+        .get_notebook(&did, &rkey)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to look up notebook: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    if notebook.is_none() {
+        return Err(XrpcErrorResponse::not_found("Notebook not found"));
+    }
+
+    state
+        .clickhouse
+        .upsert_static_mirror(
+            &did,
+            &rkey,
+            &body.output_dir,
+            &body.webhook_url,
+            body.enabled,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to upsert static mirror: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let row = state
+        .clickhouse
+        .get_static_mirror(&did, &rkey)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read back static mirror: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?
+        .ok_or_else(|| XrpcErrorResponse::internal_error("Mirror settings not persisted"))?;
+
+    Ok(Json(MirrorView::from_row(row)))
+}
+
+/// Get static mirror settings for one notebook
+pub async fn get_mirror(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<MirrorQuery>,
+) -> Result<Json<MirrorView>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    let (did, rkey) = resolve_notebook_uri(&state, &query.uri).await?;
+
+    let row = state
+        .clickhouse
+        .get_static_mirror(&did, &rkey)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get static mirror: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?
+        .ok_or_else(|| XrpcErrorResponse::not_found("No mirror configured for this notebook"))?;
+
+    Ok(Json(MirrorView::from_row(row)))
+}
+
+/// List all configured static mirrors
+pub async fn list_mirrors(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ListMirrorsResponse>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    let rows = state.clickhouse.list_static_mirrors().await.map_err(|e| {
+        tracing::error!("Failed to list static mirrors: {}", e);
+        XrpcErrorResponse::internal_error("Database query failed")
+    })?;
+
+    Ok(Json(ListMirrorsResponse {
+        mirrors: rows.into_iter().map(MirrorView::from_row).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn within_mirror_root_accepts_subdirectories() {
+        let root = Path::new("/srv/mirrors");
+        assert!(within_mirror_root(root, Path::new("/srv/mirrors/alice")));
+        assert!(within_mirror_root(
+            root,
+            Path::new("/srv/mirrors/alice/notebook")
+        ));
+    }
+
+    #[test]
+    fn within_mirror_root_rejects_escapes() {
+        let root = Path::new("/srv/mirrors");
+        // The root itself, siblings, and traversal must all be refused.
+        assert!(!within_mirror_root(root, Path::new("/srv/mirrors")));
+        assert!(!within_mirror_root(root, Path::new("/srv/other")));
+        assert!(!within_mirror_root(root, Path::new("/srv/mirrors-evil")));
+        assert!(!within_mirror_root(
+            root,
+            Path::new("/srv/mirrors/../../etc")
+        ));
+        assert!(!within_mirror_root(root, Path::new("/srv/mirrors/./x")));
+        assert!(!within_mirror_root(root, Path::new("relative/path")));
+    }
+}
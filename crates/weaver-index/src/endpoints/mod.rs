@@ -12,12 +12,19 @@ use self::actor::resolve_actor;
 use self::repo::XrpcErrorResponse;
 
 pub mod actor;
+pub mod admin;
+pub mod analytics;
 pub mod bsky;
 pub mod collab;
 pub mod edit;
+pub mod graph;
 pub mod identity;
+pub mod mirrors;
 pub mod notebook;
+pub mod privacy;
 pub mod repo;
+pub mod shards;
+pub mod takedowns;
 
 /// Resolved AT URI components with canonical DID-based URI.
 pub struct ResolvedUri {
@@ -54,6 +61,12 @@ pub async fn resolve_uri(
     // Construct canonical DID-based URI for DB lookup
     let canonical_uri = format!("at://{}/{}/{}", did, collection, rkey.as_ref());
 
+    // Record-level takedowns read as absence, exactly like a deleted
+    // record (account-level ones already failed resolve_actor above).
+    if state.takedowns.is_taken_down(&did, &canonical_uri) {
+        return Err(XrpcErrorResponse::not_found("Record not found"));
+    }
+
     Ok(ResolvedUri {
         did: SmolStr::new(&did),
         collection: SmolStr::new(collection.as_ref()),
@@ -62,6 +75,15 @@ pub async fn resolve_uri(
     })
 }
 
+/// Whether a row is suppressed by a moderation takedown.
+///
+/// Feed and list handlers filter fetched rows with this before
+/// hydration. Cursor math runs on the raw rows, so pages may come up
+/// short when suppressed content is dropped, but cursors stay stable.
+pub fn is_suppressed(state: &AppState, did: &str, uri: &str) -> bool {
+    state.takedowns.is_taken_down(did, uri)
+}
+
 /// Convert SmolStr to Option<CowStr> if non-empty
 pub fn non_empty_str(s: &SmolStr) -> Option<CowStr<'static>> {
     if s.is_empty() {
@@ -5,7 +5,7 @@ use std::collections::{HashMap, HashSet};
 use axum::{Json, extract::State};
 use jacquard::IntoStatic;
 use jacquard::cowstr::ToCowStr;
-use jacquard::types::string::{AtUri, Cid, Did, Handle, Uri};
+use jacquard::types::string::{AtUri, AtprotoStr, Cid, Did, Handle, Uri};
 use jacquard::types::value::Data;
 use jacquard_axum::ExtractXrpc;
 use jacquard_axum::service_auth::ExtractOptionalServiceAuth;
@@ -18,15 +18,18 @@ use weaver_api::sh_weaver::notebook::{
     get_entry::{GetEntryOutput, GetEntryRequest},
     get_entry_feed::{GetEntryFeedOutput, GetEntryFeedRequest},
     get_entry_notebooks::{GetEntryNotebooksOutput, GetEntryNotebooksRequest, NotebookRef},
+    get_feed::{GetFeedOutput, GetFeedRequest},
     get_notebook::{GetNotebookOutput, GetNotebookRequest},
     get_notebook_feed::{GetNotebookFeedOutput, GetNotebookFeedRequest},
     resolve_entry::{ResolveEntryOutput, ResolveEntryRequest},
     resolve_global_notebook::{ResolveGlobalNotebookOutput, ResolveGlobalNotebookRequest},
     resolve_notebook::{ResolveNotebookOutput, ResolveNotebookRequest},
+    search_entries::{SearchEntriesOutput, SearchEntriesRequest},
 };
 
 use crate::clickhouse::{EntryRow, ProfileRow};
 use crate::endpoints::actor::{Viewer, resolve_actor};
+use crate::endpoints::is_suppressed;
 use crate::endpoints::repo::XrpcErrorResponse;
 use crate::server::AppState;
 
@@ -82,7 +85,12 @@ pub async fn resolve_notebook(
 
     // Check if there are more entries
     let has_more = entry_rows.len() > limit as usize;
-    let entry_rows: Vec<_> = entry_rows.into_iter().take(limit as usize).collect();
+    // Suppressed entries read as absence; the page may come up short.
+    let entry_rows: Vec<_> = entry_rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !is_suppressed(&state, &r.did, &r.uri))
+        .collect();
 
     // Collect all unique author DIDs for batch hydration
     // Start with evidence-based notebook contributors
@@ -192,8 +200,11 @@ pub async fn resolve_notebook(
     // Build BookEntryViews with prev/next navigation
     let mut entries: Vec<BookEntryView<'static>> = Vec::with_capacity(entry_views.len());
     for (idx, entry_view) in entry_views.iter().enumerate() {
-        let prev = (idx > 0)
-            .then(|| BookEntryRef::new().entry(entry_views[idx - 1].clone()).build());
+        let prev = (idx > 0).then(|| {
+            BookEntryRef::new()
+                .entry(entry_views[idx - 1].clone())
+                .build()
+        });
         let next = entry_views
             .get(idx + 1)
             .map(|e| BookEntryRef::new().entry(e.clone()).build());
@@ -726,7 +737,11 @@ pub async fn get_notebook_feed(
 
     // Check if there are more
     let has_more = notebook_rows.len() > limit as usize;
-    let notebook_rows: Vec<_> = notebook_rows.into_iter().take(limit as usize).collect();
+    let notebook_rows: Vec<_> = notebook_rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !is_suppressed(&state, &r.did, &r.uri))
+        .collect();
 
     // Collect author DIDs for hydration
     let mut all_author_dids: HashSet<&str> = HashSet::new();
@@ -835,7 +850,11 @@ pub async fn get_entry_feed(
 
     // Check if there are more
     let has_more = entry_rows.len() > limit as usize;
-    let entry_rows: Vec<_> = entry_rows.into_iter().take(limit as usize).collect();
+    let entry_rows: Vec<_> = entry_rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !is_suppressed(&state, &r.did, &r.uri))
+        .collect();
 
     // Batch fetch contributors for all entries
     let entry_keys: Vec<(&str, &str)> = entry_rows
@@ -909,6 +928,330 @@ pub async fn get_entry_feed(
     ))
 }
 
+/// Handle sh.weaver.notebook.getFeed
+///
+/// Aggregated network-wide activity feed backed by the timeline table:
+/// recently published or updated entries, newest first, with optional
+/// actor, tag, and language filters.
+pub async fn get_feed(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(viewer): ExtractOptionalServiceAuth,
+    ExtractXrpc(args): ExtractXrpc<GetFeedRequest>,
+) -> Result<Json<GetFeedOutput<'static>>, XrpcErrorResponse> {
+    let _viewer: Viewer = viewer;
+
+    let limit = args.limit.unwrap_or(50).clamp(1, 100) as u32;
+    let cursor = parse_cursor(args.cursor.as_deref())?;
+
+    let actor_did = match &args.actor {
+        Some(actor) => Some(resolve_actor(&state, actor).await?),
+        None => None,
+    };
+
+    let rows = state
+        .clickhouse
+        .get_activity_feed(
+            actor_did.as_ref().map(|d| d.as_str()),
+            args.tag.as_deref(),
+            args.language.as_deref(),
+            limit + 1,
+            cursor,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to get activity feed: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Check if there are more
+    let has_more = rows.len() > limit as usize;
+    let rows: Vec<_> = rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !is_suppressed(&state, &r.did, &r.uri))
+        .collect();
+
+    // Batch fetch contributors for all entries
+    let entry_keys: Vec<(&str, &str)> = rows
+        .iter()
+        .map(|r| (r.did.as_str(), r.rkey.as_str()))
+        .collect();
+    let contributors_map = state
+        .clickhouse
+        .get_entry_contributors_batch(&entry_keys)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to batch fetch contributors: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Collect all contributor DIDs for profile hydration
+    let mut all_author_dids: HashSet<&str> = HashSet::new();
+    for contributors in contributors_map.values() {
+        for did in contributors {
+            all_author_dids.insert(did.as_str());
+        }
+    }
+
+    // Batch fetch profiles
+    let author_dids_vec: Vec<&str> = all_author_dids.into_iter().collect();
+    let profiles = state
+        .clickhouse
+        .get_profiles_batch(&author_dids_vec)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to batch fetch profiles: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let profile_map: HashMap<&str, &ProfileRow> =
+        profiles.iter().map(|p| (p.did.as_str(), p)).collect();
+
+    // Build FeedEntryViews
+    let mut feed: Vec<FeedEntryView<'static>> = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let entry_key = (row.did.clone(), row.rkey.clone());
+        let contributors = contributors_map
+            .get(&entry_key)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+
+        let entry_row = EntryRow {
+            did: row.did.clone(),
+            rkey: row.rkey.clone(),
+            cid: row.cid.clone(),
+            uri: row.uri.clone(),
+            title: row.title.clone(),
+            path: row.path.clone(),
+            tags: row.tags.clone(),
+            author_dids: row.author_dids.clone(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            indexed_at: row.indexed_at,
+            record: row.record.clone(),
+        };
+
+        let entry_view = build_entry_view_with_authors(&entry_row, contributors, &profile_map)?;
+
+        feed.push(FeedEntryView::new().entry(entry_view).build());
+    }
+
+    // Build cursor for pagination (timeline event_time millis)
+    let next_cursor = if has_more {
+        rows.last()
+            .map(|r| r.event_time.timestamp_millis().to_cowstr().into_static())
+    } else {
+        None
+    };
+
+    Ok(Json(
+        GetFeedOutput {
+            feed,
+            cursor: next_cursor,
+            extra_data: None,
+        }
+        .into_static(),
+    ))
+}
+
+/// Handle sh.weaver.notebook.searchEntries
+///
+/// Full-text search over entry titles and body text, backed by the
+/// entry_search table. Results carry a highlighted snippet in extra_data.
+pub async fn search_entries(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(viewer): ExtractOptionalServiceAuth,
+    ExtractXrpc(args): ExtractXrpc<SearchEntriesRequest>,
+) -> Result<Json<SearchEntriesOutput<'static>>, XrpcErrorResponse> {
+    let _viewer: Viewer = viewer;
+
+    let limit = args.limit.unwrap_or(25).clamp(1, 100) as u32;
+    // Cursor is a raw result offset; score ordering has no natural keyset.
+    let offset: u32 = args
+        .cursor
+        .as_deref()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(0);
+
+    let author_did = match &args.author {
+        Some(actor) => Some(resolve_actor(&state, actor).await?),
+        None => None,
+    };
+
+    let tags_vec: Vec<&str> = args
+        .tags
+        .as_ref()
+        .map(|t| t.iter().map(|s| s.as_ref()).collect())
+        .unwrap_or_default();
+    let tags = if tags_vec.is_empty() {
+        None
+    } else {
+        Some(tags_vec.as_slice())
+    };
+
+    let rows = state
+        .clickhouse
+        .search_entries(
+            args.q.as_ref(),
+            author_did.as_ref().map(|d| d.as_str()),
+            tags,
+            limit + 1,
+            offset,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to search entries: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Cursor math counts raw rows; dedupe below may return slightly fewer.
+    let has_more = rows.len() > limit as usize;
+    let rows: Vec<_> = rows
+        .into_iter()
+        .take(limit as usize)
+        .filter(|r| !is_suppressed(&state, &r.did, &r.uri))
+        .collect();
+
+    // Dedupe by rkey (collaborative entries exist in multiple repos),
+    // keeping the first hit since rows are already score-ordered.
+    let mut seen: HashSet<SmolStr> = HashSet::new();
+    let rows: Vec<_> = rows
+        .into_iter()
+        .filter(|r| seen.insert(r.rkey.clone()))
+        .collect();
+
+    // Batch fetch contributors for evidence-based author lists
+    let entry_keys: Vec<(&str, &str)> = rows
+        .iter()
+        .map(|r| (r.did.as_str(), r.rkey.as_str()))
+        .collect();
+    let contributors_map = state
+        .clickhouse
+        .get_entry_contributors_batch(&entry_keys)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to batch fetch contributors: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let mut all_author_dids: HashSet<&str> = HashSet::new();
+    for contributors in contributors_map.values() {
+        for did in contributors {
+            all_author_dids.insert(did.as_str());
+        }
+    }
+
+    let author_dids_vec: Vec<&str> = all_author_dids.into_iter().collect();
+    let profiles = state
+        .clickhouse
+        .get_profiles_batch(&author_dids_vec)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to batch fetch profiles: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let profile_map: HashMap<&str, &ProfileRow> =
+        profiles.iter().map(|p| (p.did.as_str(), p)).collect();
+
+    let terms = crate::clickhouse::query_terms(args.q.as_ref());
+
+    let mut entries: Vec<EntryView<'static>> = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let entry_key = (row.did.clone(), row.rkey.clone());
+        let contributors = contributors_map
+            .get(&entry_key)
+            .map(|v| v.as_slice())
+            .unwrap_or(&[]);
+
+        let entry_row = EntryRow {
+            did: row.did.clone(),
+            rkey: row.rkey.clone(),
+            cid: row.cid.clone(),
+            uri: row.uri.clone(),
+            title: row.title.clone(),
+            path: row.path.clone(),
+            tags: row.tags.clone(),
+            author_dids: row.author_dids.clone(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+            indexed_at: row.indexed_at,
+            record: row.record.clone(),
+        };
+
+        let mut entry_view = build_entry_view_with_authors(&entry_row, contributors, &profile_map)?;
+
+        // Attach the match snippet via extra_data; the lexicon output has no
+        // snippet field and the record would be the wrong place for it.
+        let mut extra = std::collections::BTreeMap::new();
+        extra.insert(
+            SmolStr::new_static("snippet"),
+            Data::String(AtprotoStr::String(
+                highlight_terms(&row.snippet, &terms).into(),
+            )),
+        );
+        entry_view.extra_data = Some(extra);
+
+        entries.push(entry_view);
+    }
+
+    let next_cursor = if has_more {
+        Some((offset + limit).to_string().into())
+    } else {
+        None
+    };
+
+    Ok(Json(
+        SearchEntriesOutput {
+            cursor: next_cursor,
+            entries,
+            extra_data: None,
+        }
+        .into_static(),
+    ))
+}
+
+/// Wrap query term matches in a snippet with `**` emphasis markers.
+///
+/// Matching is case-insensitive and char-boundary safe; longest matching
+/// term wins at each position so overlapping terms don't nest markers.
+fn highlight_terms(snippet: &str, terms: &[&str]) -> String {
+    fn match_len_at(text: &str, term: &str) -> Option<usize> {
+        let mut chars = text.chars();
+        let mut len = 0;
+        for tc in term.chars() {
+            let c = chars.next()?;
+            if !c.to_lowercase().eq(tc.to_lowercase()) {
+                return None;
+            }
+            len += c.len_utf8();
+        }
+        Some(len)
+    }
+
+    let mut out = String::with_capacity(snippet.len() + 16);
+    let mut i = 0;
+    while i < snippet.len() {
+        let rest = &snippet[i..];
+        let hit = terms
+            .iter()
+            .filter(|t| !t.is_empty())
+            .filter_map(|t| match_len_at(rest, t))
+            .max();
+        if let Some(len) = hit {
+            out.push_str("**");
+            out.push_str(&rest[..len]);
+            out.push_str("**");
+            i += len;
+        } else {
+            let c = rest.chars().next().expect("index is on a char boundary");
+            out.push(c);
+            i += c.len_utf8();
+        }
+    }
+    out
+}
+
 /// Handle sh.weaver.notebook.getBookEntry
 ///
 /// Returns an entry at a specific index within a notebook, with prev/next navigation.
@@ -1073,6 +1416,18 @@ pub async fn get_entry_notebooks(
             XrpcErrorResponse::internal_error("Database query failed")
         })?;
 
+    // Notebooks suppressed by a takedown are dropped from the list.
+    let notebook_refs: Vec<_> = notebook_refs
+        .into_iter()
+        .filter(|(did, rkey)| {
+            !is_suppressed(
+                &state,
+                did,
+                &format!("at://{}/sh.weaver.notebook.book/{}", did, rkey),
+            )
+        })
+        .collect();
+
     if notebook_refs.is_empty() {
         return Ok(Json(
             GetEntryNotebooksOutput {
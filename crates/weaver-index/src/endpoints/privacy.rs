@@ -0,0 +1,236 @@
+//! Account data export and deletion endpoints (GDPR-style).
+//!
+//! `GET /account/export` bundles everything the appview holds for a DID —
+//! indexed record history, identity and account events — into one JSON
+//! document suitable for download. The data is all derived from the
+//! public firehose, so export needs no auth; anything operator-entered
+//! (static mirror settings and the like) stays behind the token-gated
+//! `/admin/*` routes instead.
+//!
+//! `POST /account/delete` schedules a purge of that data and requires
+//! service auth from the account being purged. Purged accounts are
+//! re-indexed if their repo is still live on the firehose; the flow is
+//! meant for accounts that have deleted or deactivated their PDS repo.
+//! These are plain JSON routes, not XRPC lexicons.
+
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::header;
+use axum::response::IntoResponse;
+use jacquard::types::ident::AtIdentifier;
+use jacquard::types::value::Data;
+use jacquard_axum::service_auth::ExtractOptionalServiceAuth;
+use serde::{Deserialize, Serialize};
+
+use crate::clickhouse::{ExportedRecordRow, PurgedTable};
+use crate::endpoints::actor::resolve_actor;
+use crate::endpoints::repo::XrpcErrorResponse;
+use crate::server::AppState;
+
+/// Query parameters for the export endpoint
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Handle or DID of the account to export
+    pub actor: String,
+}
+
+/// Request body for the deletion endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccountDataRequest {
+    /// Handle or DID of the account to purge (must match the caller)
+    pub actor: String,
+}
+
+/// One indexed record event in the export
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordEventView {
+    pub collection: String,
+    pub rkey: String,
+    pub cid: String,
+    pub rev: String,
+    pub operation: String,
+    pub seq: u64,
+    pub event_time: String,
+    /// Parsed record body; absent for delete events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub record: Option<Data<'static>>,
+}
+
+/// One identity (handle) event in the export
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityEventView {
+    pub handle: String,
+    pub seq: u64,
+    pub event_time: String,
+}
+
+/// One account status event in the export
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountEventView {
+    pub active: bool,
+    pub status: String,
+    pub seq: u64,
+    pub event_time: String,
+}
+
+/// The full export bundle
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountExport {
+    pub did: String,
+    pub generated_at: String,
+    pub records: Vec<RecordEventView>,
+    pub identity_events: Vec<IdentityEventView>,
+    pub account_events: Vec<AccountEventView>,
+}
+
+/// Response for a deletion request
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteAccountDataResponse {
+    pub did: String,
+    /// Per-table row counts scheduled for deletion. ClickHouse mutations
+    /// are asynchronous, so rows may remain briefly after this returns.
+    pub purged: Vec<PurgedTable>,
+}
+
+fn record_event_view(row: ExportedRecordRow) -> RecordEventView {
+    // Delete events carry no body; anything unparseable is dropped rather
+    // than failing the whole export.
+    let record = if row.record.is_empty() {
+        None
+    } else {
+        serde_json::from_str::<Data<'static>>(&row.record).ok()
+    };
+
+    RecordEventView {
+        collection: row.collection.to_string(),
+        rkey: row.rkey.to_string(),
+        cid: row.cid.to_string(),
+        rev: row.rev.to_string(),
+        operation: row.operation.to_string(),
+        seq: row.seq,
+        event_time: row.event_time.to_rfc3339(),
+        record,
+    }
+}
+
+/// Resolve a handle-or-DID string from a plain JSON payload.
+async fn resolve_actor_str(state: &AppState, actor: &str) -> Result<String, XrpcErrorResponse> {
+    let ident = AtIdentifier::new_owned(actor.to_string())
+        .map_err(|_| XrpcErrorResponse::invalid_request("actor must be a handle or DID"))?;
+    let did = resolve_actor(state, &ident).await?;
+    Ok(did.as_str().to_string())
+}
+
+/// Export everything the appview holds for an account as one JSON bundle
+pub async fn export_account_data(
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse, XrpcErrorResponse> {
+    let did = resolve_actor_str(&state, &query.actor).await?;
+
+    let records = state
+        .clickhouse
+        .export_account_records(&did)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to export account records: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let identity_events = state
+        .clickhouse
+        .export_identity_events(&did)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to export identity events: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    let account_events = state
+        .clickhouse
+        .export_account_events(&did)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to export account events: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Static mirror settings are deliberately absent: output paths and
+    // deploy webhook URLs are operator credentials, not account data,
+    // and this endpoint is unauthenticated. They stay behind the
+    // token-gated /admin/mirrors routes.
+    let export = AccountExport {
+        did: did.clone(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        records: records.into_iter().map(record_event_view).collect(),
+        identity_events: identity_events
+            .into_iter()
+            .map(|e| IdentityEventView {
+                handle: e.handle.to_string(),
+                seq: e.seq,
+                event_time: e.event_time.to_rfc3339(),
+            })
+            .collect(),
+        account_events: account_events
+            .into_iter()
+            .map(|e| AccountEventView {
+                active: e.active != 0,
+                status: e.status.to_string(),
+                seq: e.seq,
+                event_time: e.event_time.to_rfc3339(),
+            })
+            .collect(),
+    };
+
+    // DIDs contain colons, which are unfriendly in filenames.
+    let filename = format!("weaver-export-{}.json", did.replace(':', "-"));
+    Ok((
+        [(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", filename),
+        )],
+        Json(export),
+    ))
+}
+
+/// Purge everything the appview holds for the authenticated account
+pub async fn delete_account_data(
+    State(state): State<AppState>,
+    ExtractOptionalServiceAuth(viewer): ExtractOptionalServiceAuth,
+    Json(body): Json<DeleteAccountDataRequest>,
+) -> Result<Json<DeleteAccountDataResponse>, XrpcErrorResponse> {
+    let viewer =
+        viewer.ok_or_else(|| XrpcErrorResponse::auth_required("Authentication required"))?;
+
+    let did = resolve_actor_str(&state, &body.actor).await?;
+
+    // Only the account itself may request a purge.
+    if viewer.did().as_str() != did {
+        return Err(XrpcErrorResponse::forbidden(
+            "Cannot delete another account's data",
+        ));
+    }
+
+    let purged = state
+        .clickhouse
+        .purge_account_data(&did)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to purge account data: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    tracing::info!(
+        did = %did,
+        rows = purged.iter().map(|p| p.matched_rows).sum::<u64>(),
+        "Scheduled account data purge"
+    );
+
+    Ok(Json(DeleteAccountDataResponse { did, purged }))
+}
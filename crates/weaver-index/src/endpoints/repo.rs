@@ -101,6 +101,12 @@ pub async fn get_record(
     let collection = args.collection.as_str();
     let rkey: &str = args.rkey.as_ref();
 
+    // Taken-down content reads as absence, exactly like a deleted record.
+    let uri_check = format!("at://{}/{}/{}", did, collection, rkey);
+    if state.takedowns.is_taken_down(did.as_str(), &uri_check) {
+        return Err(XrpcErrorResponse::not_found("Record not found"));
+    }
+
     // Query ClickHouse for the record
     let cached = state
         .clickhouse
@@ -167,7 +173,13 @@ pub async fn get_record(
                 let record_json = serde_json::to_string(&upstream.value).unwrap_or_default();
                 if !record_json.is_empty() {
                     if let Err(e) = clickhouse
-                        .insert_record(&did_str, &collection_str, &rkey_str, upstream_cid, &record_json)
+                        .insert_record(
+                            &did_str,
+                            &collection_str,
+                            &rkey_str,
+                            upstream_cid,
+                            &record_json,
+                        )
                         .await
                     {
                         tracing::warn!("Failed to update stale cache entry: {}", e);
@@ -275,6 +287,13 @@ pub async fn list_records(
     // Convert rows to Record output
     let mut records = Vec::with_capacity(rows.len());
     for row in &rows {
+        // Suppressed records are skipped; cursor math below still runs
+        // on the raw rows so pagination stays stable.
+        let takedown_uri = format!("at://{}/{}/{}", did, collection, row.rkey);
+        if state.takedowns.is_taken_down(did.as_str(), &takedown_uri) {
+            continue;
+        }
+
         let value: Data<'_> = serde_json::from_str(&row.record).map_err(|e| {
             tracing::error!("Failed to parse record JSON: {}", e);
             XrpcErrorResponse::internal_error("Failed to parse stored record")
@@ -298,8 +317,9 @@ pub async fn list_records(
         );
     }
 
-    // Cursor is the rkey of the last record, if we have more
-    let next_cursor = if records.len() == limit as usize {
+    // Cursor is the rkey of the last raw row, if we have more (rows,
+    // not records: takedown filtering must not stall pagination)
+    let next_cursor = if rows.len() == limit as usize {
         rows.last().map(|r| r.rkey.clone().into())
     } else {
         None
@@ -0,0 +1,140 @@
+//! Admin endpoints for SQLite shard rebalancing.
+//!
+//! Operators move ranges of hash buckets to another volume here; the
+//! actual migration runs as a background task with dual-write during
+//! cutover (see [`crate::sqlite::rebalance`]). These are plain JSON
+//! routes, not XRPC lexicons, and every handler requires the operator
+//! token (see [`crate::endpoints::admin`]).
+
+use std::path::PathBuf;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoints::admin::require_admin;
+use crate::endpoints::repo::XrpcErrorResponse;
+use crate::server::AppState;
+use crate::sqlite::rebalance::RebalanceReport;
+
+/// Request to move a range of shard buckets to another volume
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceRequest {
+    /// First bucket to move, as two hex digits (e.g. "00")
+    pub start_bucket: String,
+    /// Last bucket to move, inclusive (e.g. "7f")
+    pub end_bucket: String,
+    /// Absolute base path of the volume shards move to
+    pub target_base: String,
+}
+
+/// Rebalance state as returned to the operator
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceStatusResponse {
+    pub running: bool,
+    pub last_report: Option<RebalanceReport>,
+    pub last_error: Option<String>,
+}
+
+/// Parse a bucket name as it appears on disk (two hex digits)
+fn parse_bucket(s: &str) -> Result<u8, XrpcErrorResponse> {
+    if s.len() != 2 {
+        return Err(XrpcErrorResponse::invalid_request(
+            "Buckets are two hex digits (00-ff)",
+        ));
+    }
+    u8::from_str_radix(s, 16)
+        .map_err(|_| XrpcErrorResponse::invalid_request("Buckets are two hex digits (00-ff)"))
+}
+
+fn status_response(state: &AppState) -> RebalanceStatusResponse {
+    let progress = state.shards.rebalance_progress();
+    RebalanceStatusResponse {
+        running: progress.running,
+        last_report: progress.last_report,
+        last_error: progress.last_error,
+    }
+}
+
+/// Start moving a bucket range to another volume
+pub async fn start_rebalance(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<RebalanceRequest>,
+) -> Result<Json<RebalanceStatusResponse>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    let start = parse_bucket(&body.start_bucket)?;
+    let end = parse_bucket(&body.end_bucket)?;
+    if start > end {
+        return Err(XrpcErrorResponse::invalid_request(
+            "startBucket must not exceed endBucket",
+        ));
+    }
+
+    let target_base = PathBuf::from(&body.target_base);
+    // Relative paths would resolve against the indexer's working
+    // directory, which is never what the operator meant.
+    if !target_base.is_absolute() {
+        return Err(XrpcErrorResponse::invalid_request(
+            "targetBase must be an absolute path",
+        ));
+    }
+    if target_base == state.shards.base_path() {
+        return Err(XrpcErrorResponse::invalid_request(
+            "targetBase must differ from the current shard base path",
+        ));
+    }
+
+    if !state.shards.try_begin_rebalance() {
+        return Err(XrpcErrorResponse::invalid_request(
+            "A rebalance is already running",
+        ));
+    }
+
+    let shards = state.shards.clone();
+    let buckets: Vec<u8> = (start..=end).collect();
+    // Filesystem-bound; keep it off the async workers.
+    tokio::task::spawn_blocking(move || {
+        let result = shards.rebalance_buckets(&buckets, &target_base);
+        if let Err(e) = &result {
+            tracing::error!("Shard rebalance failed: {}", e);
+        }
+        shards.finish_rebalance(result);
+    });
+
+    Ok(Json(status_response(&state)))
+}
+
+/// Get the state of the current or most recent rebalance
+pub async fn rebalance_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<RebalanceStatusResponse>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    Ok(Json(status_response(&state)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bucket_accepts_disk_names() {
+        assert_eq!(parse_bucket("00").unwrap(), 0);
+        assert_eq!(parse_bucket("7f").unwrap(), 0x7f);
+        assert_eq!(parse_bucket("ff").unwrap(), 0xff);
+    }
+
+    #[test]
+    fn parse_bucket_rejects_other_shapes() {
+        assert!(parse_bucket("").is_err());
+        assert!(parse_bucket("0").is_err());
+        assert!(parse_bucket("100").is_err());
+        assert!(parse_bucket("zz").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Admin endpoints for moderation takedowns.
+//!
+//! Operators suppress accounts or individual records here; enforcement
+//! happens in the read endpoints via the in-memory list in
+//! [`crate::takedowns`]. These are plain JSON routes, not XRPC lexicons,
+//! and every handler requires the operator token (see
+//! [`crate::endpoints::admin`]).
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use serde::{Deserialize, Serialize};
+
+use crate::clickhouse::TakedownRow;
+use crate::endpoints::admin::require_admin;
+use crate::endpoints::repo::XrpcErrorResponse;
+use crate::server::AppState;
+
+/// Request to take down (or reinstate) a subject
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PutTakedownRequest {
+    /// A DID ('did:...') or an AT URI in canonical DID form
+    /// ('at://did:.../collection/rkey')
+    pub subject: String,
+    /// Lifting a takedown keeps the row for the audit trail
+    #[serde(default = "default_active")]
+    pub active: bool,
+    /// Operator-facing note on why
+    #[serde(default)]
+    pub reason: String,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Takedown state as returned to the operator
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakedownView {
+    pub subject: String,
+    pub active: bool,
+    pub reason: String,
+    pub updated_at: String,
+}
+
+impl TakedownView {
+    fn from_row(row: TakedownRow) -> Self {
+        Self {
+            subject: row.subject.to_string(),
+            active: row.active != 0,
+            reason: row.reason,
+            updated_at: row.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Response for listing all takedowns (active and lifted)
+#[derive(Debug, Serialize)]
+pub struct ListTakedownsResponse {
+    pub takedowns: Vec<TakedownView>,
+}
+
+/// Validate a takedown subject.
+///
+/// URIs must be in canonical DID form with collection and rkey, since
+/// that is the shape read endpoints compare against; a handle-form URI
+/// would be accepted and then silently match nothing.
+fn validate_subject(subject: &str) -> Result<(), XrpcErrorResponse> {
+    if subject.starts_with("did:") && !subject.contains('/') {
+        return Ok(());
+    }
+    if let Some(rest) = subject.strip_prefix("at://") {
+        let mut parts = rest.split('/');
+        let authority = parts.next().unwrap_or("");
+        let collection = parts.next().unwrap_or("");
+        let rkey = parts.next().unwrap_or("");
+        if authority.starts_with("did:")
+            && !collection.is_empty()
+            && !rkey.is_empty()
+            && parts.next().is_none()
+        {
+            return Ok(());
+        }
+    }
+    Err(XrpcErrorResponse::invalid_request(
+        "subject must be a DID or an at:// URI in canonical DID form",
+    ))
+}
+
+/// Take down or reinstate a subject
+pub async fn put_takedown(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(body): Json<PutTakedownRequest>,
+) -> Result<Json<TakedownView>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    validate_subject(&body.subject)?;
+
+    state
+        .clickhouse
+        .set_takedown(&body.subject, body.active, &body.reason)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to set takedown: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?;
+
+    // Enforce immediately on this node; the refresh loop converges the
+    // others.
+    state.takedowns.set(&body.subject, body.active);
+
+    let row = state
+        .clickhouse
+        .get_takedown(&body.subject)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to read back takedown: {}", e);
+            XrpcErrorResponse::internal_error("Database query failed")
+        })?
+        .ok_or_else(|| XrpcErrorResponse::internal_error("Takedown not persisted"))?;
+
+    Ok(Json(TakedownView::from_row(row)))
+}
+
+/// List all takedowns, active and lifted
+pub async fn list_takedowns(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ListTakedownsResponse>, XrpcErrorResponse> {
+    require_admin(&headers)?;
+
+    let rows = state.clickhouse.list_takedowns().await.map_err(|e| {
+        tracing::error!("Failed to list takedowns: {}", e);
+        XrpcErrorResponse::internal_error("Database query failed")
+    })?;
+
+    Ok(Json(ListTakedownsResponse {
+        takedowns: rows.into_iter().map(TakedownView::from_row).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_subject_accepts_dids_and_canonical_uris() {
+        assert!(validate_subject("did:plc:abc123").is_ok());
+        assert!(validate_subject("did:web:example.com").is_ok());
+        assert!(validate_subject("at://did:plc:abc123/sh.weaver.notebook.entry/xyz").is_ok());
+    }
+
+    #[test]
+    fn validate_subject_rejects_handles_and_partial_uris() {
+        assert!(validate_subject("alice.example.com").is_err());
+        assert!(validate_subject("at://alice.example.com/sh.weaver.notebook.entry/xyz").is_err());
+        assert!(validate_subject("at://did:plc:abc123").is_err());
+        assert!(validate_subject("at://did:plc:abc123/sh.weaver.notebook.entry").is_err());
+        assert!(
+            validate_subject("at://did:plc:abc123/sh.weaver.notebook.entry/xyz/extra").is_err()
+        );
+        assert!(validate_subject("").is_err());
+    }
+}
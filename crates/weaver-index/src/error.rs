@@ -30,6 +30,10 @@ pub enum IndexError {
     #[diagnostic(transparent)]
     Sqlite(#[from] SqliteError),
 
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Backfill(#[from] BackfillError),
+
     #[error("resource not found: {resource}")]
     #[diagnostic(code(index::not_found))]
     NotFound { resource: String },
@@ -89,6 +93,10 @@ pub enum SqliteError {
     #[diagnostic(code(sqlite::query))]
     Query { message: String },
 
+    #[error("shard rebalance failed: {message}")]
+    #[diagnostic(code(sqlite::rebalance))]
+    Rebalance { message: String },
+
     #[error("shard lock poisoned")]
     #[diagnostic(code(sqlite::lock))]
     LockPoisoned,
@@ -153,6 +161,34 @@ pub enum FirehoseError {
     UnknownEvent { event_type: String },
 }
 
+/// Repo backfill errors
+#[derive(Debug, Error, Diagnostic)]
+pub enum BackfillError {
+    #[error("failed to resolve PDS for {did}: {message}")]
+    #[diagnostic(code(backfill::resolve_pds))]
+    ResolvePds { did: String, message: String },
+
+    #[error("failed to describe repo {did}: {message}")]
+    #[diagnostic(code(backfill::describe_repo))]
+    DescribeRepo { did: String, message: String },
+
+    #[error("failed to list {collection} records for {did}: {message}")]
+    #[diagnostic(code(backfill::list_records))]
+    ListRecords {
+        did: String,
+        collection: String,
+        message: String,
+    },
+
+    #[error("failed to serialize record {uri}")]
+    #[diagnostic(code(backfill::serialize))]
+    Serialize {
+        uri: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
 /// CAR file parsing errors
 #[derive(Debug, Error, Diagnostic)]
 pub enum CarError {
@@ -181,7 +217,10 @@ pub enum ConfigError {
 
     #[error("invalid configuration value for {field}: {message}")]
     #[diagnostic(code(config::invalid))]
-    Invalid { field: &'static str, message: String },
+    Invalid {
+        field: &'static str,
+        message: String,
+    },
 
     #[error("failed to parse URL: {url}")]
     #[diagnostic(code(config::url_parse))]
@@ -0,0 +1,139 @@
+//! In-memory handle/DID identity cache.
+//!
+//! The handle_mappings table in ClickHouse is the durable identity store,
+//! but hitting it (or worse, the network resolver) on every
+//! handle-addressed request is slow. This cache keeps recent resolutions
+//! in memory with a TTL so repeated lookups are O(1). When the firehose
+//! indexer shares a process with the server it pushes #identity events
+//! straight into the cache, so renamed handles take effect immediately;
+//! when they run separately the TTL bounds staleness instead.
+
+use std::time::Duration;
+
+use mini_moka::sync::Cache;
+use smol_str::SmolStr;
+
+/// How long a cached resolution is trusted without a refresh.
+const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+/// Entries per direction; identities are tiny, so this is generous.
+const DEFAULT_CAPACITY: u64 = 100_000;
+
+/// Bidirectional handle <-> DID cache with TTL expiry.
+pub struct IdentityCache {
+    handle_to_did: Cache<SmolStr, SmolStr>,
+    did_to_handle: Cache<SmolStr, SmolStr>,
+}
+
+impl IdentityCache {
+    pub fn new() -> Self {
+        Self::with_ttl(DEFAULT_TTL, DEFAULT_CAPACITY)
+    }
+
+    pub fn with_ttl(ttl: Duration, capacity: u64) -> Self {
+        Self {
+            handle_to_did: Cache::builder()
+                .max_capacity(capacity)
+                .time_to_live(ttl)
+                .build(),
+            did_to_handle: Cache::builder()
+                .max_capacity(capacity)
+                .time_to_live(ttl)
+                .build(),
+        }
+    }
+
+    /// Look up the DID for a handle.
+    pub fn get_did(&self, handle: &str) -> Option<SmolStr> {
+        self.handle_to_did.get(&SmolStr::new(handle))
+    }
+
+    /// Look up the current handle for a DID.
+    pub fn get_handle(&self, did: &str) -> Option<SmolStr> {
+        self.did_to_handle.get(&SmolStr::new(did))
+    }
+
+    /// Record a resolved mapping in both directions.
+    pub fn insert(&self, handle: &str, did: &str) {
+        self.handle_to_did
+            .insert(SmolStr::new(handle), SmolStr::new(did));
+        self.did_to_handle
+            .insert(SmolStr::new(did), SmolStr::new(handle));
+    }
+
+    /// Apply a firehose #identity event.
+    ///
+    /// The event carries the DID's current handle. If the handle changed,
+    /// the old handle's forward mapping is dropped so a renamed handle
+    /// can't resolve to the wrong account for the rest of its TTL.
+    pub fn apply_identity_event(&self, did: &str, handle: Option<&str>) {
+        let did_key = SmolStr::new(did);
+
+        if let Some(old_handle) = self.did_to_handle.get(&did_key) {
+            if handle != Some(old_handle.as_str()) {
+                self.handle_to_did.invalidate(&old_handle);
+            }
+        }
+
+        match handle {
+            Some(h) => self.insert(h, did),
+            None => self.did_to_handle.invalidate(&did_key),
+        }
+    }
+}
+
+impl Default for IdentityCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_lookup_both_directions() {
+        let cache = IdentityCache::new();
+        cache.insert("alice.example.com", "did:plc:alice");
+
+        assert_eq!(
+            cache.get_did("alice.example.com").as_deref(),
+            Some("did:plc:alice")
+        );
+        assert_eq!(
+            cache.get_handle("did:plc:alice").as_deref(),
+            Some("alice.example.com")
+        );
+    }
+
+    #[test]
+    fn identity_event_drops_old_handle_on_rename() {
+        let cache = IdentityCache::new();
+        cache.insert("old.example.com", "did:plc:alice");
+
+        cache.apply_identity_event("did:plc:alice", Some("new.example.com"));
+
+        assert_eq!(cache.get_did("old.example.com"), None);
+        assert_eq!(
+            cache.get_did("new.example.com").as_deref(),
+            Some("did:plc:alice")
+        );
+        assert_eq!(
+            cache.get_handle("did:plc:alice").as_deref(),
+            Some("new.example.com")
+        );
+    }
+
+    #[test]
+    fn identity_event_without_handle_invalidates_reverse_mapping() {
+        let cache = IdentityCache::new();
+        cache.insert("alice.example.com", "did:plc:alice");
+
+        cache.apply_identity_event("did:plc:alice", None);
+
+        assert_eq!(cache.get_handle("did:plc:alice"), None);
+        // Forward mapping is also gone: the rename check dropped it.
+        assert_eq!(cache.get_did("alice.example.com"), None);
+    }
+}
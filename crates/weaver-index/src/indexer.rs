@@ -19,6 +19,8 @@ use crate::firehose::{
     Account, ExtractedRecord, FirehoseConsumer, Identity, MessageStream, SubscribeReposMessage,
     extract_records,
 };
+use crate::identity_cache::IdentityCache;
+use crate::takedowns::TakedownList;
 
 /// Default consumer ID for cursor tracking
 const CONSUMER_ID: &str = "main";
@@ -167,6 +169,12 @@ pub struct FirehoseIndexer {
     consumer: FirehoseConsumer,
     rev_cache: RevCache,
     config: IndexerConfig,
+    /// Shared identity cache to refresh on #identity events (when the
+    /// server runs in the same process).
+    identity_cache: Option<Arc<IdentityCache>>,
+    /// Shared takedown list; commits from suppressed accounts are
+    /// dropped at ingest so delisted content cannot resurface.
+    takedowns: Option<Arc<TakedownList>>,
 }
 
 impl FirehoseIndexer {
@@ -186,9 +194,24 @@ impl FirehoseIndexer {
             consumer,
             rev_cache,
             config,
+            identity_cache: None,
+            takedowns: None,
         })
     }
 
+    /// Attach a shared identity cache so #identity events refresh it live.
+    pub fn with_identity_cache(mut self, cache: Arc<IdentityCache>) -> Self {
+        self.identity_cache = Some(cache);
+        self
+    }
+
+    /// Attach a shared takedown list so suppressed accounts are dropped
+    /// at ingest.
+    pub fn with_takedowns(mut self, takedowns: Arc<TakedownList>) -> Self {
+        self.takedowns = Some(takedowns);
+        self
+    }
+
     /// Save cursor to ClickHouse
     async fn save_cursor(&self, seq: u64, event_time: DateTime<Utc>) -> Result<()> {
         let query = format!(
@@ -288,6 +311,10 @@ impl FirehoseIndexer {
                 _ => {}
             }
 
+            // How far this consumer trails realtime, per event so the
+            // gauge tracks backfill-after-restart catching up.
+            crate::observability::record_firehose_lag(last_event_time);
+
             match msg {
                 SubscribeReposMessage::Commit(commit) => {
                     let did = commit.repo.as_ref();
@@ -299,6 +326,16 @@ impl FirehoseIndexer {
                         continue;
                     }
 
+                    // Taken-down accounts are tombstoned at ingest: new
+                    // writes are dropped so delisted content cannot
+                    // resurface through the live path.
+                    if let Some(takedowns) = &self.takedowns {
+                        if takedowns.is_did_taken_down(did) {
+                            skipped += 1;
+                            continue;
+                        }
+                    }
+
                     // Extract and write records
                     for record in extract_records(&commit).await? {
                         // Collection filter - skip early before JSON conversion
@@ -346,6 +383,12 @@ impl FirehoseIndexer {
                     processed += 1;
                 }
                 SubscribeReposMessage::Identity(identity) => {
+                    if let Some(cache) = &self.identity_cache {
+                        cache.apply_identity_event(
+                            identity.did.as_ref(),
+                            identity.handle.as_ref().map(|h| h.as_ref()),
+                        );
+                    }
                     write_identity(&identity, &mut identities).await?;
                 }
                 SubscribeReposMessage::Account(account) => {
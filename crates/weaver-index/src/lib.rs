@@ -1,21 +1,30 @@
+pub mod backfill;
 pub mod clickhouse;
 pub mod config;
 pub mod endpoints;
 pub mod error;
 pub mod firehose;
+pub mod identity_cache;
 pub mod indexer;
+pub mod observability;
 pub mod parallel_tap;
 pub mod server;
 pub mod service_identity;
 pub mod sqlite;
+pub mod takedowns;
 pub mod tap;
 pub mod tasks;
 
+pub use backfill::{BackfillSummary, Backfiller};
 pub use config::Config;
 pub use error::{IndexError, Result};
+pub use identity_cache::IdentityCache;
 pub use indexer::{FirehoseIndexer, load_cursor};
 pub use parallel_tap::TapIndexer;
 pub use server::{AppState, ServerConfig};
 pub use service_identity::ServiceIdentity;
 pub use sqlite::{ShardKey, ShardRouter, SqliteShard};
-pub use tasks::{run_draft_title_task, DraftTitleTaskConfig};
+pub use takedowns::{TakedownList, run_takedown_refresh};
+pub use tasks::{
+    DraftTitleTaskConfig, StaticMirrorTaskConfig, run_draft_title_task, run_static_mirror_task,
+};
@@ -0,0 +1,114 @@
+//! Metric recording for the indexer and appview.
+//!
+//! The Prometheus exporter itself lives in [`weaver_common::telemetry`]
+//! and is already served at `/metrics`; this module only records. All
+//! metric names are defined here so the set of series we emit can be
+//! reviewed in one place:
+//!
+//! - `indexer_requests_total{endpoint, status}` / `indexer_request_duration_seconds{endpoint}`
+//!   from the axum middleware.
+//! - `indexer_firehose_lag_seconds` from the indexer loop.
+//! - `indexer_identity_cache_lookups_total{outcome}` from handle resolution.
+//! - `indexer_open_shards` and `indexer_table_bytes{table}` /
+//!   `indexer_table_rows{table}` from the periodic sampler.
+
+use std::time::{Duration, Instant};
+
+use axum::extract::{MatchedPath, Request};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use weaver_common::telemetry::{counter, gauge, histogram};
+
+use crate::server::AppState;
+
+/// Axum middleware recording per-endpoint request counts and latencies.
+///
+/// Labels use the matched route template rather than the raw path:
+/// raw paths would blow up series cardinality (they embed DIDs, rkeys,
+/// and whatever crawlers probe for).
+pub async fn track_requests(
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let endpoint = matched_path
+        .map(|p| p.as_str().to_owned())
+        .unwrap_or_else(|| "unmatched".to_owned());
+    let start = Instant::now();
+
+    let response = next.run(request).await;
+
+    counter!(
+        "indexer_requests_total",
+        "endpoint" => endpoint.clone(),
+        "status" => response.status().as_u16().to_string(),
+    )
+    .increment(1);
+    histogram!("indexer_request_duration_seconds", "endpoint" => endpoint)
+        .record(start.elapsed().as_secs_f64());
+
+    response
+}
+
+/// Record how far the firehose consumer trails realtime.
+///
+/// Negative lag (clock skew between us and the relay) is clamped to
+/// zero rather than reported, since a "negative lag" alert is noise.
+pub fn record_firehose_lag(event_time: DateTime<Utc>) {
+    let lag_ms = (Utc::now() - event_time).num_milliseconds().max(0);
+    gauge!("indexer_firehose_lag_seconds").set(lag_ms as f64 / 1000.0);
+}
+
+/// Record an identity cache lookup outcome.
+///
+/// The hit rate is derived from the two labelled series at query time;
+/// recording a precomputed ratio would lose the volume information.
+pub fn record_identity_cache_lookup(hit: bool) {
+    let outcome = if hit { "hit" } else { "miss" };
+    counter!("indexer_identity_cache_lookups_total", "outcome" => outcome).increment(1);
+}
+
+/// Tables worth watching for growth; the raw firehose tables dominate
+/// disk, the rest are the hydration tables queries actually hit.
+const SAMPLED_TABLES: &[&str] = &[
+    "raw_records",
+    "raw_identity_events",
+    "raw_account_events",
+    "entries",
+    "notebooks",
+    "timeline",
+    "entry_search",
+    "tag_activity",
+];
+
+/// How often the sampler refreshes gauges that have no natural
+/// recording point.
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Periodically sample gauges without a natural recording point: the
+/// open shard count and ClickHouse table sizes. Runs forever; spawn it
+/// alongside the other background tasks.
+pub async fn run_sampler(state: AppState) {
+    let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+    loop {
+        interval.tick().await;
+
+        gauge!("indexer_open_shards").set(state.shards.shard_count() as f64);
+
+        match state.clickhouse.table_sizes(SAMPLED_TABLES).await {
+            Ok(sizes) => {
+                for size in sizes {
+                    gauge!("indexer_table_bytes", "table" => size.table.clone())
+                        .set(size.compressed_bytes as f64);
+                    gauge!("indexer_table_rows", "table" => size.table.clone())
+                        .set(size.row_count as f64);
+                }
+            }
+            Err(e) => {
+                // Stale gauges are better than a dead sampler; keep going.
+                tracing::warn!("Table size sampling failed: {}", e);
+            }
+        }
+    }
+}
@@ -6,7 +6,7 @@ use axum::{
     extract::State,
     http::{StatusCode, header},
     response::{Html, IntoResponse},
-    routing::get,
+    routing::{get, post},
 };
 use jacquard::api::com_atproto::repo::{
     get_record::GetRecordRequest, list_records::ListRecordsRequest,
@@ -35,19 +35,30 @@ use weaver_api::sh_weaver::collab::get_resource_sessions::GetResourceSessionsReq
 use weaver_api::sh_weaver::edit::get_contributors::GetContributorsRequest;
 use weaver_api::sh_weaver::edit::get_edit_history::GetEditHistoryRequest;
 use weaver_api::sh_weaver::edit::list_drafts::ListDraftsRequest;
+use weaver_api::sh_weaver::graph::get_popular_tags::GetPopularTagsRequest;
+use weaver_api::sh_weaver::graph::get_tagged_resources::GetTaggedResourcesRequest;
 use weaver_api::sh_weaver::notebook::{
     get_book_entry::GetBookEntryRequest, get_entry::GetEntryRequest,
     get_entry_feed::GetEntryFeedRequest, get_entry_notebooks::GetEntryNotebooksRequest,
-    get_notebook::GetNotebookRequest, get_notebook_feed::GetNotebookFeedRequest,
-    resolve_entry::ResolveEntryRequest,
-    resolve_global_notebook::ResolveGlobalNotebookRequest, resolve_notebook::ResolveNotebookRequest,
+    get_feed::GetFeedRequest, get_notebook::GetNotebookRequest,
+    get_notebook_feed::GetNotebookFeedRequest, resolve_entry::ResolveEntryRequest,
+    resolve_global_notebook::ResolveGlobalNotebookRequest,
+    resolve_notebook::ResolveNotebookRequest, search_entries::SearchEntriesRequest,
 };
 
+use weaver_common::analytics::GetAnalyticsSummary;
+
 use crate::clickhouse::Client;
 use crate::config::ShardConfig;
-use crate::endpoints::{actor, bsky, collab, edit, identity, notebook, repo};
+use crate::endpoints::{
+    actor, analytics, bsky, collab, edit, graph, identity, mirrors, notebook, privacy, repo,
+    shards, takedowns,
+};
 use crate::error::{IndexError, ServerError};
+use crate::identity_cache::IdentityCache;
+use crate::observability;
 use crate::sqlite::ShardRouter;
+use crate::takedowns::TakedownList;
 
 pub use weaver_common::telemetry::{self, TelemetryConfig};
 
@@ -60,8 +71,12 @@ pub struct AppState {
     pub clickhouse: Arc<Client>,
     pub shards: Arc<ShardRouter>,
     pub resolver: Resolver,
+    /// In-memory handle/DID cache in front of handle_mappings
+    pub identity_cache: Arc<IdentityCache>,
     /// Our service DID (expected audience for service auth JWTs)
     pub service_did: Did<'static>,
+    /// Active moderation takedowns, enforced by all read endpoints
+    pub takedowns: Arc<TakedownList>,
 }
 
 impl AppState {
@@ -70,7 +85,9 @@ impl AppState {
             clickhouse: Arc::new(clickhouse),
             shards: Arc::new(ShardRouter::new(shard_config.base_path)),
             resolver: UnauthenticatedSession::new_public(),
+            identity_cache: Arc::new(IdentityCache::new()),
             service_did,
+            takedowns: Arc::new(TakedownList::new()),
         }
     }
 }
@@ -106,6 +123,27 @@ pub fn router(state: AppState, did_doc: DidDocument<'static>) -> Router {
         )
         .route("/xrpc/_health", get(health))
         .route("/metrics", get(metrics))
+        // Static mirror admin (plain JSON, not XRPC)
+        .route(
+            "/admin/mirrors",
+            get(mirrors::list_mirrors).put(mirrors::put_mirror),
+        )
+        .route("/admin/mirror", get(mirrors::get_mirror))
+        // Shard rebalance admin (plain JSON, not XRPC)
+        .route(
+            "/admin/shards/rebalance",
+            get(shards::rebalance_status).post(shards::start_rebalance),
+        )
+        // Moderation takedown admin (plain JSON, not XRPC)
+        .route(
+            "/admin/takedowns",
+            get(takedowns::list_takedowns).put(takedowns::put_takedown),
+        )
+        // Account data export and deletion (plain JSON, not XRPC)
+        .route("/account/export", get(privacy::export_account_data))
+        .route("/account/delete", post(privacy::delete_account_data))
+        // View analytics beacon (plain JSON, not XRPC)
+        .route("/analytics/beacon", post(analytics::record_view))
         // com.atproto.identity.* endpoints
         .merge(ResolveHandleRequest::into_router(identity::resolve_handle))
         // com.atproto.repo.* endpoints (record cache)
@@ -133,6 +171,7 @@ pub fn router(state: AppState, did_doc: DidDocument<'static>) -> Router {
             notebook::get_notebook_feed,
         ))
         .merge(GetEntryFeedRequest::into_router(notebook::get_entry_feed))
+        .merge(GetFeedRequest::into_router(notebook::get_feed))
         .merge(GetBookEntryRequest::into_router(notebook::get_book_entry))
         .merge(GetEntryNotebooksRequest::into_router(
             notebook::get_entry_notebooks,
@@ -140,6 +179,12 @@ pub fn router(state: AppState, did_doc: DidDocument<'static>) -> Router {
         .merge(ResolveGlobalNotebookRequest::into_router(
             notebook::resolve_global_notebook,
         ))
+        .merge(SearchEntriesRequest::into_router(notebook::search_entries))
+        // sh.weaver.graph.* endpoints
+        .merge(GetPopularTagsRequest::into_router(graph::get_popular_tags))
+        .merge(GetTaggedResourcesRequest::into_router(
+            graph::get_tagged_resources,
+        ))
         // sh.weaver.collab.* endpoints
         .merge(GetResourceParticipantsRequest::into_router(
             collab::get_resource_participants,
@@ -154,6 +199,9 @@ pub fn router(state: AppState, did_doc: DidDocument<'static>) -> Router {
         .merge(GetEditHistoryRequest::into_router(edit::get_edit_history))
         .merge(GetContributorsRequest::into_router(edit::get_contributors))
         .merge(ListDraftsRequest::into_router(edit::list_drafts))
+        // sh.weaver.analytics.* endpoints
+        .merge(GetAnalyticsSummary::into_router(analytics::get_summary))
+        .layer(axum::middleware::from_fn(observability::track_requests))
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive().max_age(std::time::Duration::from_secs(86400)))
         .with_state(state)
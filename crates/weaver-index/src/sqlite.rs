@@ -11,6 +11,13 @@ use rusqlite_migration::{M, Migrations};
 use smol_str::SmolStr;
 
 use crate::error::{IndexError, SqliteError};
+use crate::sqlite::rebalance::RebalanceProgress;
+
+pub mod rebalance;
+
+/// Marker file left in a bucket directory after its shards moved to
+/// another volume; contains the new base path.
+const RELOCATED_MARKER: &str = ".relocated";
 
 /// Key for shard routing - (collection, rkey) tuple
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
@@ -27,11 +34,15 @@ impl ShardKey {
         }
     }
 
-    fn hash_prefix(&self) -> String {
+    /// Hash bucket this key falls into (0..=255, the on-disk directory fan-out)
+    pub fn bucket(&self) -> u8 {
         let mut hasher = DefaultHasher::new();
         self.hash(&mut hasher);
-        let hash = hasher.finish();
-        format!("{:02x}", (hash & 0xFF) as u8)
+        (hasher.finish() & 0xFF) as u8
+    }
+
+    fn hash_prefix(&self) -> String {
+        format!("{:02x}", self.bucket())
     }
 
     /// Directory path: {base}/{hash(collection,rkey)[0..2]}/{rkey}/
@@ -51,7 +62,10 @@ impl ShardKey {
 /// A single SQLite shard for a resource
 pub struct SqliteShard {
     conn: Mutex<Connection>,
-    path: PathBuf,
+    /// Secondary connection that receives every write during a rebalance
+    /// cutover, plus the path of its database file.
+    mirror: Mutex<Option<(Connection, PathBuf)>>,
+    path: Mutex<PathBuf>,
     last_accessed: Mutex<Instant>,
 }
 
@@ -89,7 +103,8 @@ impl SqliteShard {
 
         Ok(Self {
             conn: Mutex::new(conn),
-            path: db_path,
+            mirror: Mutex::new(None),
+            path: Mutex::new(db_path),
             last_accessed: Mutex::new(Instant::now()),
         })
     }
@@ -102,8 +117,8 @@ impl SqliteShard {
         ])
     }
 
-    pub fn path(&self) -> &Path {
-        &self.path
+    pub fn path(&self) -> PathBuf {
+        self.path.lock().map(|p| p.clone()).expect("poisoned")
     }
 
     pub fn touch(&self) {
@@ -132,18 +147,167 @@ impl SqliteShard {
     }
 
     /// Execute a write operation on the shard
+    ///
+    /// The closure is `Fn` rather than `FnOnce` because during a rebalance
+    /// cutover it is replayed against the mirror connection so the copy
+    /// stays in sync until promotion.
     pub fn write<F, T>(&self, f: F) -> Result<T, IndexError>
     where
-        F: FnOnce(&Connection) -> Result<T, rusqlite::Error>,
+        F: Fn(&Connection) -> Result<T, rusqlite::Error>,
     {
         self.touch();
         let conn = self.conn.lock().map_err(|_| SqliteError::LockPoisoned)?;
-        f(&conn).map_err(|e| {
-            SqliteError::Query {
-                message: e.to_string(),
+        let result = f(&conn).map_err(|e| SqliteError::Query {
+            message: e.to_string(),
+        })?;
+
+        let mirror = self.mirror.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        if let Some((mirror_conn, _)) = mirror.as_ref() {
+            f(mirror_conn).map_err(|e| SqliteError::Rebalance {
+                message: format!("dual-write to mirror failed: {}", e),
+            })?;
+        }
+
+        Ok(result)
+    }
+
+    /// Start mirroring this shard to a new directory.
+    ///
+    /// Takes a consistent snapshot of the database into `dest_dir` and
+    /// opens a mirror connection; every subsequent [`Self::write`] applies
+    /// to both copies until [`Self::promote_mirror`] swaps them.
+    pub fn start_mirror(&self, dest_dir: &Path) -> Result<(), IndexError> {
+        fs::create_dir_all(dest_dir).map_err(|e| SqliteError::Io {
+            path: dest_dir.to_path_buf(),
+            source: e,
+        })?;
+        let dest_db = dest_dir.join(Self::DB_FILENAME);
+
+        // Hold the primary lock across the snapshot so no write lands
+        // between the copy and the mirror becoming active.
+        let conn = self.conn.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        let mut mirror = self.mirror.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        if mirror.is_some() {
+            return Err(SqliteError::Rebalance {
+                message: "mirror already active".into(),
             }
-            .into()
-        })
+            .into());
+        }
+
+        // VACUUM INTO refuses to overwrite; a stale copy from an aborted
+        // run is replaced rather than failing the whole rebalance.
+        match fs::remove_file(&dest_db) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(SqliteError::Io {
+                    path: dest_db,
+                    source: e,
+                }
+                .into());
+            }
+        }
+
+        conn.execute("VACUUM INTO ?1", [dest_db.to_string_lossy().as_ref()])
+            .map_err(|e| SqliteError::Rebalance {
+                message: format!("snapshot to {} failed: {}", dest_db.display(), e),
+            })?;
+
+        // The snapshot carries the schema and user_version, so no
+        // migrations run here.
+        let mirror_conn = Connection::open(&dest_db).map_err(|e| SqliteError::Open {
+            path: dest_db.clone(),
+            source: e,
+        })?;
+        mirror_conn
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(|e| SqliteError::Pragma {
+                pragma: "journal_mode",
+                source: e,
+            })?;
+
+        *mirror = Some((mirror_conn, dest_db));
+        Ok(())
+    }
+
+    /// Compare per-table row counts between the primary and the mirror.
+    ///
+    /// Returns the total number of rows verified; any mismatch is an error
+    /// and the cutover should be abandoned.
+    pub fn verify_mirror(&self) -> Result<u64, IndexError> {
+        let conn = self.conn.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        let mirror = self.mirror.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        let (mirror_conn, _) = mirror.as_ref().ok_or_else(|| SqliteError::Rebalance {
+            message: "no active mirror to verify".into(),
+        })?;
+
+        let mut total = 0u64;
+        for table in Self::user_tables(&conn)? {
+            let source_count = Self::count_rows(&conn, &table)?;
+            let target_count = Self::count_rows(mirror_conn, &table)?;
+            if source_count != target_count {
+                return Err(SqliteError::Rebalance {
+                    message: format!(
+                        "row count mismatch in {}: source {}, target {}",
+                        table, source_count, target_count
+                    ),
+                }
+                .into());
+            }
+            total += source_count;
+        }
+
+        Ok(total)
+    }
+
+    /// Make the mirror the primary connection.
+    ///
+    /// Returns the path of the old database file so the caller can remove
+    /// the stale copy; writes no longer reach it after this returns.
+    pub fn promote_mirror(&self) -> Result<PathBuf, IndexError> {
+        let mut conn = self.conn.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        let mut mirror = self.mirror.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        let (new_conn, new_path) = mirror.take().ok_or_else(|| SqliteError::Rebalance {
+            message: "no active mirror to promote".into(),
+        })?;
+
+        // Dropping the old connection checkpoints and closes the source file.
+        let _old_conn = std::mem::replace(&mut *conn, new_conn);
+
+        let mut path = self.path.lock().map_err(|_| SqliteError::LockPoisoned)?;
+        let old_path = std::mem::replace(&mut *path, new_path);
+        Ok(old_path)
+    }
+
+    /// List user tables (everything from our migrations, nothing internal)
+    fn user_tables(conn: &Connection) -> Result<Vec<String>, IndexError> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+            )
+            .map_err(|e| SqliteError::Query {
+                message: e.to_string(),
+            })?;
+        let tables = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| SqliteError::Query {
+                message: e.to_string(),
+            })?;
+        Ok(tables)
+    }
+
+    fn count_rows(conn: &Connection, table: &str) -> Result<u64, IndexError> {
+        // Table names come from sqlite_master on our own schema, so
+        // quoting the identifier is safe.
+        let query = format!("SELECT COUNT(*) FROM \"{}\"", table);
+        conn.query_row(&query, [], |row| row.get::<_, u64>(0))
+            .map_err(|e| {
+                SqliteError::Query {
+                    message: e.to_string(),
+                }
+                .into()
+            })
     }
 }
 
@@ -151,6 +315,10 @@ impl SqliteShard {
 pub struct ShardRouter {
     base_path: PathBuf,
     shards: DashMap<ShardKey, std::sync::Arc<SqliteShard>>,
+    /// Serializes shard opens against bucket moves so a rebalance cannot
+    /// pull a directory out from under a concurrent open (or vice versa).
+    relocation_lock: Mutex<()>,
+    rebalance: Mutex<RebalanceProgress>,
 }
 
 impl ShardRouter {
@@ -158,9 +326,15 @@ impl ShardRouter {
         Self {
             base_path: base_path.into(),
             shards: DashMap::new(),
+            relocation_lock: Mutex::new(()),
+            rebalance: Mutex::new(RebalanceProgress::default()),
         }
     }
 
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
     /// Get or create a shard for the given key
     pub fn get_or_create(&self, key: &ShardKey) -> Result<std::sync::Arc<SqliteShard>, IndexError> {
         // Fast path: already cached
@@ -169,8 +343,18 @@ impl ShardRouter {
             return Ok(shard.clone());
         }
 
-        // Slow path: create new shard
-        let dir = key.dir_path(&self.base_path);
+        // Slow path: open under the relocation lock, re-checking the cache
+        // in case another thread opened the same shard meanwhile.
+        let _guard = self
+            .relocation_lock
+            .lock()
+            .map_err(|_| SqliteError::LockPoisoned)?;
+        if let Some(shard) = self.shards.get(key) {
+            shard.touch();
+            return Ok(shard.clone());
+        }
+
+        let dir = self.resolve_dir(key)?;
         let shard = std::sync::Arc::new(SqliteShard::open(&dir)?);
         self.shards.insert(key.clone(), shard.clone());
 
@@ -203,4 +387,29 @@ impl ShardRouter {
     pub fn evict(&self, key: &ShardKey) -> Option<std::sync::Arc<SqliteShard>> {
         self.shards.remove(key).map(|(_, shard)| shard)
     }
+
+    /// Resolve the directory for a key, following a relocation marker if
+    /// the key's bucket has been moved to another volume.
+    fn resolve_dir(&self, key: &ShardKey) -> Result<PathBuf, IndexError> {
+        let prefix = key.hash_prefix();
+        let marker = self.base_path.join(&prefix).join(RELOCATED_MARKER);
+        match fs::read_to_string(&marker) {
+            Ok(target) => Ok(PathBuf::from(target.trim()).join(&prefix).join(key.rkey())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(key.dir_path(&self.base_path)),
+            Err(e) => Err(SqliteError::Io {
+                path: marker,
+                source: e,
+            }
+            .into()),
+        }
+    }
+
+    /// Cached shard keys that fall into the given bucket
+    fn cached_keys_in_bucket(&self, bucket: u8) -> Vec<ShardKey> {
+        self.shards
+            .iter()
+            .filter(|entry| entry.key().bucket() == bucket)
+            .map(|entry| entry.key().clone())
+            .collect()
+    }
 }
@@ -0,0 +1,318 @@
+//! Online shard rebalancing.
+//!
+//! Shards are per-resource SQLite files fanned out over 256 hash buckets
+//! (`{base}/00..ff/{rkey}/store.sqlite`). Rebalancing moves whole buckets
+//! to another volume without taking writes offline:
+//!
+//! 1. Shards with no open connection are moved directory-by-directory
+//!    under the router's relocation lock, and a marker file is written so
+//!    new opens resolve to the target volume.
+//! 2. Shards that are open are snapshotted to the target, dual-written
+//!    during the copy, verified by per-table row counts, and then cut
+//!    over by swapping the live connection.
+//!
+//! Moving buckets back to the original base path is the same operation
+//! in reverse, so "split" and "merge" are both just bucket moves.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use smol_str::SmolStr;
+
+use super::{RELOCATED_MARKER, ShardRouter};
+use crate::error::{IndexError, SqliteError};
+
+/// Outcome of a completed rebalance run
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceReport {
+    /// Base path shards were moved to
+    pub target_base: String,
+    /// Buckets moved, as their directory names
+    pub buckets: Vec<String>,
+    /// Shards moved while closed (plain directory moves)
+    pub shards_moved: u64,
+    /// Shards cut over while open (snapshot + dual-write + promote)
+    pub shards_cut_over: u64,
+    /// Rows compared between source and target during cutover
+    pub rows_verified: u64,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Rebalance state as reported to the admin API
+#[derive(Debug, Clone, Default)]
+pub struct RebalanceProgress {
+    pub running: bool,
+    pub last_report: Option<RebalanceReport>,
+    pub last_error: Option<String>,
+}
+
+impl ShardRouter {
+    /// Mark a rebalance as running. Returns false if one already is.
+    pub fn try_begin_rebalance(&self) -> bool {
+        match self.rebalance.lock() {
+            Ok(mut progress) => {
+                if progress.running {
+                    false
+                } else {
+                    progress.running = true;
+                    true
+                }
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Record the outcome of a rebalance started with
+    /// [`Self::try_begin_rebalance`].
+    pub fn finish_rebalance(&self, result: Result<RebalanceReport, IndexError>) {
+        if let Ok(mut progress) = self.rebalance.lock() {
+            progress.running = false;
+            match result {
+                Ok(report) => {
+                    progress.last_report = Some(report);
+                    progress.last_error = None;
+                }
+                Err(e) => {
+                    progress.last_error = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    /// Current rebalance state for the admin API
+    pub fn rebalance_progress(&self) -> RebalanceProgress {
+        self.rebalance.lock().map(|p| p.clone()).unwrap_or_default()
+    }
+
+    /// Move the given buckets to `target_base`.
+    ///
+    /// Blocking (filesystem-bound); run it on a blocking task. Safe to
+    /// rerun after a failure: already-relocated buckets are skipped and
+    /// stale partial copies are overwritten.
+    pub fn rebalance_buckets(
+        &self,
+        buckets: &[u8],
+        target_base: &Path,
+    ) -> Result<RebalanceReport, IndexError> {
+        let started_at = chrono::Utc::now();
+        let mut moved_buckets = Vec::with_capacity(buckets.len());
+        let mut shards_moved = 0u64;
+        let mut shards_cut_over = 0u64;
+        let mut rows_verified = 0u64;
+
+        for &bucket in buckets {
+            let prefix = format!("{:02x}", bucket);
+            let source_bucket = self.base_path.join(&prefix);
+            let target_bucket = target_base.join(&prefix);
+
+            // Reruns after a partial failure skip buckets that already
+            // point at this target; pointing elsewhere is an operator
+            // error we refuse to compound.
+            let marker = source_bucket.join(RELOCATED_MARKER);
+            match fs::read_to_string(&marker) {
+                Ok(existing) => {
+                    if Path::new(existing.trim()) == target_base {
+                        moved_buckets.push(prefix);
+                        continue;
+                    }
+                    return Err(SqliteError::Rebalance {
+                        message: format!(
+                            "bucket {} is already relocated to {}",
+                            prefix,
+                            existing.trim()
+                        ),
+                    }
+                    .into());
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(SqliteError::Io {
+                        path: marker,
+                        source: e,
+                    }
+                    .into());
+                }
+            }
+
+            fs::create_dir_all(&target_bucket).map_err(|e| SqliteError::Io {
+                path: target_bucket.clone(),
+                source: e,
+            })?;
+
+            // Phase 1: under the relocation lock, move shards with no open
+            // connection and flip new opens to the target. Open shards are
+            // skipped here; the dual-write cutover below handles them.
+            {
+                let _guard = self
+                    .relocation_lock
+                    .lock()
+                    .map_err(|_| SqliteError::LockPoisoned)?;
+
+                let open_rkeys: HashSet<SmolStr> = self
+                    .cached_keys_in_bucket(bucket)
+                    .into_iter()
+                    .map(|key| key.rkey.clone())
+                    .collect();
+
+                if source_bucket.is_dir() {
+                    let entries = fs::read_dir(&source_bucket).map_err(|e| SqliteError::Io {
+                        path: source_bucket.clone(),
+                        source: e,
+                    })?;
+                    for entry in entries {
+                        let entry = entry.map_err(|e| SqliteError::Io {
+                            path: source_bucket.clone(),
+                            source: e,
+                        })?;
+                        let path = entry.path();
+                        if !path.is_dir() {
+                            continue;
+                        }
+                        let name = entry.file_name();
+                        if open_rkeys.contains(name.to_string_lossy().as_ref()) {
+                            continue;
+                        }
+                        move_dir(&path, &target_bucket.join(&name))?;
+                        shards_moved += 1;
+                    }
+                } else {
+                    // Nothing stored here yet; create the directory so the
+                    // marker has somewhere to live.
+                    fs::create_dir_all(&source_bucket).map_err(|e| SqliteError::Io {
+                        path: source_bucket.clone(),
+                        source: e,
+                    })?;
+                }
+
+                fs::write(&marker, format!("{}\n", target_base.display())).map_err(|e| {
+                    SqliteError::Io {
+                        path: marker.clone(),
+                        source: e,
+                    }
+                })?;
+            }
+
+            // Phase 2: cut over open shards with dual-write. Writes keep
+            // flowing throughout; each shard is only locked for its own
+            // snapshot and swap.
+            for key in self.cached_keys_in_bucket(bucket) {
+                let Some(shard) = self.get(&key) else {
+                    continue;
+                };
+                let dest_dir = target_bucket.join(key.rkey());
+                shard.start_mirror(&dest_dir)?;
+                rows_verified += shard.verify_mirror()?;
+                let old_db = shard.promote_mirror()?;
+                shards_cut_over += 1;
+
+                // The stale source copy no longer receives writes.
+                if let Some(old_dir) = old_db.parent() {
+                    if let Err(e) = fs::remove_dir_all(old_dir) {
+                        if e.kind() != std::io::ErrorKind::NotFound {
+                            tracing::warn!(
+                                "Failed to remove stale shard copy {}: {}",
+                                old_dir.display(),
+                                e
+                            );
+                        }
+                    }
+                }
+            }
+
+            // Phase 3: sweep anything left behind, e.g. a shard evicted
+            // from the cache between the phases. Whenever a target copy
+            // already exists it is authoritative (moved or promoted), so
+            // the source leftover is just removed.
+            {
+                let _guard = self
+                    .relocation_lock
+                    .lock()
+                    .map_err(|_| SqliteError::LockPoisoned)?;
+
+                let entries = fs::read_dir(&source_bucket).map_err(|e| SqliteError::Io {
+                    path: source_bucket.clone(),
+                    source: e,
+                })?;
+                for entry in entries {
+                    let entry = entry.map_err(|e| SqliteError::Io {
+                        path: source_bucket.clone(),
+                        source: e,
+                    })?;
+                    let path = entry.path();
+                    if !path.is_dir() {
+                        continue;
+                    }
+                    let target_dir = target_bucket.join(entry.file_name());
+                    if target_dir.exists() {
+                        fs::remove_dir_all(&path).map_err(|e| SqliteError::Io {
+                            path: path.clone(),
+                            source: e,
+                        })?;
+                    } else {
+                        move_dir(&path, &target_dir)?;
+                        shards_moved += 1;
+                    }
+                }
+            }
+
+            moved_buckets.push(prefix);
+        }
+
+        Ok(RebalanceReport {
+            target_base: target_base.display().to_string(),
+            buckets: moved_buckets,
+            shards_moved,
+            shards_cut_over,
+            rows_verified,
+            started_at,
+            finished_at: chrono::Utc::now(),
+        })
+    }
+}
+
+/// Move a shard directory, falling back to copy-and-remove when the
+/// target is on a different filesystem (rename fails with EXDEV).
+fn move_dir(source: &Path, target: &Path) -> Result<(), IndexError> {
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+
+    fs::create_dir_all(target).map_err(|e| SqliteError::Io {
+        path: target.to_path_buf(),
+        source: e,
+    })?;
+
+    // Shard directories are flat: just the database file plus any
+    // WAL/shm companions.
+    let entries = fs::read_dir(source).map_err(|e| SqliteError::Io {
+        path: source.to_path_buf(),
+        source: e,
+    })?;
+    for entry in entries {
+        let entry = entry.map_err(|e| SqliteError::Io {
+            path: source.to_path_buf(),
+            source: e,
+        })?;
+        let from = entry.path();
+        if !from.is_file() {
+            continue;
+        }
+        let to = target.join(entry.file_name());
+        fs::copy(&from, &to).map_err(|e| SqliteError::Io {
+            path: to,
+            source: e,
+        })?;
+    }
+
+    fs::remove_dir_all(source).map_err(|e| {
+        SqliteError::Io {
+            path: source.to_path_buf(),
+            source: e,
+        }
+        .into()
+    })
+}
@@ -0,0 +1,173 @@
+//! In-memory moderation takedown list.
+//!
+//! The takedowns table in ClickHouse is the durable store, but read
+//! endpoints sit on the hot path and cannot afford a lookup per row.
+//! This list keeps the active subjects in memory: the admin endpoint
+//! applies changes immediately on the node that served the request,
+//! and the background refresh converges every other node within
+//! [`REFRESH_INTERVAL`]. Suppressed content reads as absence — raw
+//! rows stay in ClickHouse, so lifting a takedown needs no reindex.
+
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use smol_str::SmolStr;
+
+use crate::clickhouse::Client;
+
+/// How long other nodes may keep serving a subject after its takedown
+/// before the refresh loop picks the change up.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Active takedown subjects, split by kind so lookups stay O(1).
+#[derive(Debug, Default)]
+pub struct TakedownList {
+    /// Suppressed accounts ('did:...' subjects).
+    dids: RwLock<HashSet<SmolStr>>,
+    /// Suppressed individual records ('at://...' subjects, in canonical
+    /// DID form to match the URIs stored in ClickHouse).
+    uris: RwLock<HashSet<SmolStr>>,
+}
+
+impl TakedownList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a whole account is suppressed.
+    pub fn is_did_taken_down(&self, did: &str) -> bool {
+        self.dids.read().map(|s| s.contains(did)).unwrap_or(false)
+    }
+
+    /// Whether a record is suppressed, individually or through its
+    /// account.
+    pub fn is_taken_down(&self, did: &str, uri: &str) -> bool {
+        self.is_did_taken_down(did) || self.uris.read().map(|s| s.contains(uri)).unwrap_or(false)
+    }
+
+    /// Apply one takedown change.
+    ///
+    /// Subjects are classified by shape; the admin endpoint validates
+    /// before writing, so anything that is not an at:// URI is a DID.
+    pub fn set(&self, subject: &str, active: bool) {
+        let set = if subject.starts_with("at://") {
+            &self.uris
+        } else {
+            &self.dids
+        };
+        if let Ok(mut set) = set.write() {
+            if active {
+                set.insert(SmolStr::new(subject));
+            } else {
+                set.remove(subject);
+            }
+        }
+    }
+
+    /// Replace the whole list with the given active subjects.
+    pub fn replace_all<I>(&self, subjects: I)
+    where
+        I: IntoIterator<Item = SmolStr>,
+    {
+        let mut dids = HashSet::new();
+        let mut uris = HashSet::new();
+        for subject in subjects {
+            if subject.starts_with("at://") {
+                uris.insert(subject);
+            } else {
+                dids.insert(subject);
+            }
+        }
+        if let Ok(mut set) = self.dids.write() {
+            *set = dids;
+        }
+        if let Ok(mut set) = self.uris.write() {
+            *set = uris;
+        }
+    }
+}
+
+/// Periodically reload active takedowns from ClickHouse.
+///
+/// This is what makes takedowns converge on nodes that did not serve
+/// the admin request (and after restarts). Runs forever; spawn it
+/// alongside the other background tasks.
+pub async fn run_takedown_refresh(client: Arc<Client>, takedowns: Arc<TakedownList>) {
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        match client.list_takedowns().await {
+            Ok(rows) => {
+                takedowns.replace_all(
+                    rows.into_iter()
+                        .filter(|row| row.active != 0)
+                        .map(|row| row.subject),
+                );
+            }
+            Err(e) => {
+                // A stale list is better than a dead refresh; keep going.
+                tracing::warn!("Takedown refresh failed: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn did_takedown_suppresses_account_and_its_records() {
+        let list = TakedownList::new();
+        list.set("did:plc:alice", true);
+
+        assert!(list.is_did_taken_down("did:plc:alice"));
+        assert!(list.is_taken_down(
+            "did:plc:alice",
+            "at://did:plc:alice/sh.weaver.notebook.entry/abc"
+        ));
+        assert!(!list.is_did_taken_down("did:plc:bob"));
+    }
+
+    #[test]
+    fn uri_takedown_suppresses_one_record_only() {
+        let list = TakedownList::new();
+        list.set("at://did:plc:alice/sh.weaver.notebook.entry/abc", true);
+
+        assert!(list.is_taken_down(
+            "did:plc:alice",
+            "at://did:plc:alice/sh.weaver.notebook.entry/abc"
+        ));
+        assert!(!list.is_taken_down(
+            "did:plc:alice",
+            "at://did:plc:alice/sh.weaver.notebook.entry/def"
+        ));
+        assert!(!list.is_did_taken_down("did:plc:alice"));
+    }
+
+    #[test]
+    fn lifting_a_takedown_removes_it() {
+        let list = TakedownList::new();
+        list.set("did:plc:alice", true);
+        list.set("did:plc:alice", false);
+
+        assert!(!list.is_did_taken_down("did:plc:alice"));
+    }
+
+    #[test]
+    fn replace_all_drops_subjects_no_longer_active() {
+        let list = TakedownList::new();
+        list.set("did:plc:alice", true);
+        list.set("at://did:plc:bob/sh.weaver.notebook.entry/abc", true);
+
+        list.replace_all([SmolStr::new("did:plc:carol")]);
+
+        assert!(!list.is_did_taken_down("did:plc:alice"));
+        assert!(!list.is_taken_down(
+            "did:plc:bob",
+            "at://did:plc:bob/sh.weaver.notebook.entry/abc"
+        ));
+        assert!(list.is_did_taken_down("did:plc:carol"));
+    }
+}
@@ -1,5 +1,7 @@
 //! Background tasks for the indexer
 
 mod draft_titles;
+mod static_mirror;
 
-pub use draft_titles::{run_draft_title_task, DraftTitleTaskConfig};
+pub use draft_titles::{DraftTitleTaskConfig, run_draft_title_task};
+pub use static_mirror::{StaticMirrorTaskConfig, run_static_mirror_task};
@@ -0,0 +1,329 @@
+//! Background task for rebuilding static notebook mirrors.
+//!
+//! Watches the static_mirrors settings table against the firehose-fed
+//! entries index: when a configured notebook gains or changes entries
+//! after its deploy watermark, the task materializes the notebook as a
+//! markdown vault, renders it into the configured output directory,
+//! fires the deploy webhook, and advances the watermark.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use jacquard::client::UnauthenticatedSession;
+use jacquard::identity::JacquardResolver;
+use tracing::{debug, error, info, warn};
+use weaver_renderer::static_site::StaticSiteWriter;
+
+use crate::clickhouse::{Client, StaleMirrorRow};
+use crate::error::IndexError;
+
+use weaver_api::sh_weaver::notebook::entry::Entry;
+
+/// Upper bound on entries rendered per notebook.
+///
+/// Matches the fetch ceiling of list_notebook_entries; a notebook larger
+/// than this gets a truncated mirror rather than an unbounded query.
+const MAX_MIRROR_ENTRIES: u32 = 1000;
+
+/// Configuration for the static mirror rebuild task
+#[derive(Debug, Clone)]
+pub struct StaticMirrorTaskConfig {
+    /// How often to check for stale mirrors
+    pub interval: Duration,
+    /// Maximum mirrors to rebuild per run
+    pub batch_size: i64,
+}
+
+impl Default for StaticMirrorTaskConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+            batch_size: 10,
+        }
+    }
+}
+
+/// Run the static mirror rebuild task in a loop
+pub async fn run_static_mirror_task(
+    client: Arc<Client>,
+    resolver: UnauthenticatedSession<JacquardResolver>,
+    config: StaticMirrorTaskConfig,
+) {
+    info!(
+        interval_secs = config.interval.as_secs(),
+        batch_size = config.batch_size,
+        "starting static mirror rebuild task"
+    );
+
+    let http = reqwest::Client::new();
+
+    loop {
+        match process_stale_mirrors(&client, &resolver, &http, config.batch_size).await {
+            Ok(count) => {
+                if count > 0 {
+                    info!(rebuilt = count, "static mirror rebuild complete");
+                } else {
+                    debug!("no stale static mirrors to rebuild");
+                }
+            }
+            Err(e) => {
+                error!(error = ?e, "static mirror rebuild failed");
+            }
+        }
+
+        tokio::time::sleep(config.interval).await;
+    }
+}
+
+/// Process a batch of stale mirrors
+async fn process_stale_mirrors(
+    client: &Client,
+    resolver: &UnauthenticatedSession<JacquardResolver>,
+    http: &reqwest::Client,
+    batch_size: i64,
+) -> Result<usize, IndexError> {
+    let stale = client.get_stale_static_mirrors(batch_size).await?;
+
+    if stale.is_empty() {
+        return Ok(0);
+    }
+
+    debug!(count = stale.len(), "found stale static mirrors");
+
+    let mut rebuilt = 0;
+    for mirror in stale {
+        match rebuild_mirror(client, resolver, &mirror).await {
+            Ok(entry_count) => {
+                info!(
+                    did = %mirror.did,
+                    rkey = %mirror.rkey,
+                    entries = entry_count,
+                    output_dir = %mirror.output_dir,
+                    "rebuilt static mirror"
+                );
+
+                if !mirror.webhook_url.is_empty() {
+                    fire_deploy_webhook(http, &mirror).await;
+                }
+
+                // Advance the watermark even if the webhook failed: the
+                // mirror content is current and the hook fires again on
+                // the next entry change.
+                client
+                    .mark_mirror_deployed(&mirror.did, &mirror.rkey, mirror.latest_entry_at)
+                    .await?;
+
+                rebuilt += 1;
+            }
+            Err(e) => {
+                warn!(
+                    did = %mirror.did,
+                    rkey = %mirror.rkey,
+                    error = ?e,
+                    "failed to rebuild static mirror"
+                );
+            }
+        }
+    }
+
+    Ok(rebuilt)
+}
+
+/// Render one notebook's entries into its configured output directory
+async fn rebuild_mirror(
+    client: &Client,
+    resolver: &UnauthenticatedSession<JacquardResolver>,
+    mirror: &StaleMirrorRow,
+) -> Result<usize, IndexError> {
+    let entries = client
+        .list_notebook_entries(&mirror.did, &mirror.rkey, MAX_MIRROR_ENTRIES, None)
+        .await?;
+
+    if entries.is_empty() {
+        return Err(IndexError::NotFound {
+            resource: format!("entries for notebook {}:{}", mirror.did, mirror.rkey),
+        });
+    }
+
+    // Materialize the indexed entries as a vault the renderer can walk.
+    // Rebuilt from scratch each time so deleted entries disappear.
+    let vault = mirror_vault_dir(&mirror.did, &mirror.rkey);
+    if vault.exists() {
+        tokio::fs::remove_dir_all(&vault)
+            .await
+            .map_err(|e| vault_io_error(&vault, e))?;
+    }
+    tokio::fs::create_dir_all(&vault)
+        .await
+        .map_err(|e| vault_io_error(&vault, e))?;
+
+    let mut written = 0;
+    for row in &entries {
+        // Entry content lives inline in the record, so the mirror renders
+        // entirely from indexed data without touching the PDS.
+        let data: jacquard::types::value::Data<'_> =
+            serde_json::from_str(&row.record).map_err(|e| IndexError::NotFound {
+                resource: format!("parse entry record {}:{}: {}", row.did, row.rkey, e),
+            })?;
+        let entry: Entry = jacquard::from_data(&data).map_err(|e| IndexError::NotFound {
+            resource: format!("deserialize entry {}:{}: {}", row.did, row.rkey, e),
+        })?;
+
+        let Some(relative) = sanitize_entry_path(&row.path, &row.rkey) else {
+            warn!(
+                did = %row.did,
+                rkey = %row.rkey,
+                path = %row.path,
+                "entry path escapes the vault, skipping"
+            );
+            continue;
+        };
+
+        let file = vault.join(relative);
+        if let Some(parent) = file.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| vault_io_error(parent, e))?;
+        }
+        tokio::fs::write(&file, entry.content.as_ref())
+            .await
+            .map_err(|e| vault_io_error(&file, e))?;
+        written += 1;
+    }
+
+    if written == 0 {
+        return Err(IndexError::NotFound {
+            resource: format!(
+                "renderable entries for notebook {}:{}",
+                mirror.did, mirror.rkey
+            ),
+        });
+    }
+
+    let destination = PathBuf::from(&mirror.output_dir);
+    tokio::fs::create_dir_all(&destination)
+        .await
+        .map_err(|e| vault_io_error(&destination, e))?;
+
+    let writer = StaticSiteWriter::new(vault, destination, Some(resolver.clone()));
+    writer.run().await.map_err(|e| IndexError::NotFound {
+        resource: format!(
+            "render mirror for notebook {}:{}: {}",
+            mirror.did, mirror.rkey, e
+        ),
+    })?;
+
+    Ok(written)
+}
+
+/// POST the deploy hook, logging but never failing the deploy
+async fn fire_deploy_webhook(http: &reqwest::Client, mirror: &StaleMirrorRow) {
+    match http.post(&mirror.webhook_url).body("").send().await {
+        Ok(response) => {
+            if response.status().is_success() {
+                debug!(
+                    did = %mirror.did,
+                    rkey = %mirror.rkey,
+                    status = %response.status(),
+                    "deploy webhook fired"
+                );
+            } else {
+                warn!(
+                    did = %mirror.did,
+                    rkey = %mirror.rkey,
+                    status = %response.status(),
+                    "deploy webhook returned error status"
+                );
+            }
+        }
+        Err(e) => {
+            warn!(
+                did = %mirror.did,
+                rkey = %mirror.rkey,
+                error = ?e,
+                "deploy webhook request failed"
+            );
+        }
+    }
+}
+
+/// Scratch vault location for one notebook's mirror
+fn mirror_vault_dir(did: &str, rkey: &str) -> PathBuf {
+    // DIDs contain ':' which some filesystems reject; flatten to '-'.
+    let safe_did = did.replace(':', "-");
+    std::env::temp_dir().join(format!("weaver-mirror-{}-{}", safe_did, rkey))
+}
+
+/// Map an entry's declared path to a relative vault file, or reject it.
+///
+/// Entry paths come off the firehose unvalidated, so absolute paths and
+/// parent-directory components must not escape the scratch vault.
+fn sanitize_entry_path(path: &str, rkey: &str) -> Option<PathBuf> {
+    use std::path::Component;
+
+    let name = if path.is_empty() { rkey } else { path };
+
+    let mut relative = PathBuf::new();
+    for component in std::path::Path::new(name).components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::CurDir => {}
+            // Absolute paths and ".." both escape the vault.
+            Component::RootDir | Component::Prefix(_) | Component::ParentDir => return None,
+        }
+    }
+
+    if relative.as_os_str().is_empty() {
+        return None;
+    }
+
+    if relative.extension().is_none_or(|ext| ext != "md") {
+        relative.set_extension("md");
+    }
+
+    Some(relative)
+}
+
+/// Wrap a filesystem failure with the path it hit
+fn vault_io_error(path: &std::path::Path, e: std::io::Error) -> IndexError {
+    IndexError::NotFound {
+        resource: format!("mirror filesystem path {}: {}", path.display(), e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_entry_path_appends_md() {
+        assert_eq!(
+            sanitize_entry_path("notes/intro", "rkey1"),
+            Some(PathBuf::from("notes/intro.md"))
+        );
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_keeps_md_extension() {
+        assert_eq!(
+            sanitize_entry_path("intro.md", "rkey1"),
+            Some(PathBuf::from("intro.md"))
+        );
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_falls_back_to_rkey() {
+        assert_eq!(
+            sanitize_entry_path("", "3jzfcijpj2z2a"),
+            Some(PathBuf::from("3jzfcijpj2z2a.md"))
+        );
+    }
+
+    #[test]
+    fn test_sanitize_entry_path_rejects_traversal() {
+        assert_eq!(sanitize_entry_path("../../etc/passwd", "rkey1"), None);
+        assert_eq!(sanitize_entry_path("/etc/passwd", "rkey1"), None);
+        assert_eq!(sanitize_entry_path("notes/../../escape", "rkey1"), None);
+    }
+}
@@ -122,3 +122,42 @@ pub fn render_faceted_text(text: &str, facets_json: JsValue) -> Result<String, J
     weaver_renderer::facet::render_faceted_html(text, &facets)
         .map_err(|e| JsError::new(&e.to_string()))
 }
+
+/// Generate the CSS variable stylesheet matching rendered output.
+///
+/// All HTML produced by the render functions is styled through a stable set
+/// of CSS variables and class names (see weaver-renderer's `css` module
+/// docs). This returns the variable definitions so embedders can style
+/// records without parsing our HTML; override individual variables (or pass
+/// custom colour schemes) to restyle.
+///
+/// # Arguments
+/// * `light_scheme` - Optional `sh.weaver.notebook.colourScheme` colours JSON
+///   for light mode (defaults to rose-pine dawn)
+/// * `dark_scheme` - Optional colours JSON for dark mode (defaults to rose-pine)
+#[wasm_bindgen]
+pub fn generate_theme_css(
+    light_scheme: Option<JsValue>,
+    dark_scheme: Option<JsValue>,
+) -> Result<String, JsError> {
+    use jacquard::IntoStatic;
+    use weaver_renderer::theme::{ColourSchemeColours, default_resolved_theme};
+
+    let mut theme = default_resolved_theme();
+
+    if let Some(v) = light_scheme {
+        let deserializer = Deserializer::from(v);
+        let colours = ColourSchemeColours::deserialize(deserializer)
+            .map_err(|e| JsError::new(&format!("Invalid light scheme JSON: {}", e)))?;
+        theme.light_scheme = colours.into_static();
+    }
+
+    if let Some(v) = dark_scheme {
+        let deserializer = Deserializer::from(v);
+        let colours = ColourSchemeColours::deserialize(deserializer)
+            .map_err(|e| JsError::new(&format!("Invalid dark scheme JSON: {}", e)))?;
+        theme.dark_scheme = colours.into_static();
+    }
+
+    Ok(weaver_renderer::css::generate_theme_css(&theme))
+}
@@ -0,0 +1,189 @@
+//! Accessibility linting for markdown sources.
+//!
+//! A single parser pass over a document collecting the problems screen
+//! reader users hit most: heading levels that skip (breaking the outline
+//! assistive tech navigates by), images without alt text, and links with
+//! no accessible name. Warnings are structured so callers can decide how
+//! to surface them — the CLI prints them during `--a11y-check` publishes
+//! and the editor logs them as a publish preflight.
+
+use crate::default_md_options;
+use markdown_weaver::{Event, Parser, Tag, TagEnd};
+
+/// One accessibility problem found in a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct A11yWarning {
+    pub kind: A11yWarningKind,
+    /// 1-based source line the problem starts on.
+    pub line: usize,
+}
+
+/// The kinds of problems [`check_markdown`] detects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum A11yWarningKind {
+    /// A heading more than one level deeper than the previous one.
+    SkippedHeadingLevel { previous: u8, found: u8 },
+    /// An image with no alt text.
+    MissingAltText { src: String },
+    /// A link whose content has no text an assistive reader can announce.
+    EmptyLink { href: String },
+}
+
+impl std::fmt::Display for A11yWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            A11yWarningKind::SkippedHeadingLevel { previous, found } => {
+                if *previous == 0 {
+                    write!(f, "first heading is h{} (expected h1)", found)
+                } else {
+                    write!(f, "heading jumps from h{} to h{}", previous, found)
+                }
+            }
+            A11yWarningKind::MissingAltText { src } => {
+                write!(f, "image missing alt text: {}", src)
+            }
+            A11yWarningKind::EmptyLink { href } => {
+                write!(f, "link has no accessible text: {}", href)
+            }
+        }
+    }
+}
+
+/// Lint a markdown document for accessibility problems.
+///
+/// Uses [`default_md_options`], so the outline and alt text checked here
+/// are exactly what the renderers will emit.
+pub fn check_markdown(contents: &str) -> Vec<A11yWarning> {
+    let mut warnings = Vec::new();
+
+    // 0 = no heading seen yet; the document outline should start at h1.
+    let mut previous_level: u8 = 0;
+    // (href, accumulated accessible text, source offset) of an open link.
+    let mut open_link: Option<(String, String, usize)> = None;
+    // Alt text accumulates between Image start and end events.
+    let mut open_image: Option<(String, String, usize)> = None;
+
+    let parser = Parser::new_ext(contents, default_md_options()).into_offset_iter();
+    for (event, range) in parser {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                let found = level as u8;
+                if found > previous_level + 1 {
+                    warnings.push(A11yWarning {
+                        kind: A11yWarningKind::SkippedHeadingLevel {
+                            previous: previous_level,
+                            found,
+                        },
+                        line: line_of(contents, range.start),
+                    });
+                }
+                previous_level = found;
+            }
+            Event::Start(Tag::Image { ref dest_url, .. }) => {
+                open_image = Some((dest_url.to_string(), String::new(), range.start));
+            }
+            Event::End(TagEnd::Image) => {
+                if let Some((src, alt, offset)) = open_image.take() {
+                    if alt.trim().is_empty() {
+                        warnings.push(A11yWarning {
+                            kind: A11yWarningKind::MissingAltText { src },
+                            line: line_of(contents, offset),
+                        });
+                    }
+                }
+            }
+            Event::Start(Tag::Link { ref dest_url, .. }) => {
+                open_link = Some((dest_url.to_string(), String::new(), range.start));
+            }
+            Event::End(TagEnd::Link) => {
+                if let Some((href, text, offset)) = open_link.take() {
+                    if text.trim().is_empty() {
+                        warnings.push(A11yWarning {
+                            kind: A11yWarningKind::EmptyLink { href },
+                            line: line_of(contents, offset),
+                        });
+                    }
+                }
+            }
+            Event::Text(ref text) | Event::Code(ref text) => {
+                // An image inside a link names the link through its alt
+                // text, so the same characters feed both accumulators.
+                if let Some((_, alt, _)) = open_image.as_mut() {
+                    alt.push_str(text);
+                }
+                if let Some((_, link_text, _)) = open_link.as_mut() {
+                    link_text.push_str(text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    warnings
+}
+
+/// 1-based line number of a byte offset.
+fn line_of(contents: &str, offset: usize) -> usize {
+    contents[..offset.min(contents.len())]
+        .bytes()
+        .filter(|b| *b == b'\n')
+        .count()
+        + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_skipped_heading_level() {
+        let warnings = check_markdown("# one\n\n### three\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].kind,
+            A11yWarningKind::SkippedHeadingLevel {
+                previous: 1,
+                found: 3
+            }
+        );
+        assert_eq!(warnings[0].line, 3);
+    }
+
+    #[test]
+    fn test_stepping_back_up_is_fine() {
+        let warnings = check_markdown("# one\n\n## two\n\n# one again\n\n## two\n");
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_detects_missing_alt_text() {
+        let warnings = check_markdown("![](images/cover.png)\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].kind,
+            A11yWarningKind::MissingAltText {
+                src: "images/cover.png".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_detects_empty_link() {
+        let warnings = check_markdown("[](https://example.com)\n");
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(
+            warnings[0].kind,
+            A11yWarningKind::EmptyLink {
+                href: "https://example.com".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_image_alt_names_enclosing_link() {
+        // A linked image with alt text gives the link an accessible name.
+        let warnings = check_markdown("[![diagram](d.png)](https://example.com)\n");
+        assert!(warnings.is_empty());
+    }
+}
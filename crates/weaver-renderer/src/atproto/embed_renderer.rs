@@ -30,6 +30,15 @@ use weaver_api::app_bsky::{
 use weaver_api::sh_weaver::actor::ProfileDataViewInner;
 use weaver_common::agent::WeaverExt;
 
+/// Sanitize record HTML before it leaves this module.
+///
+/// Everything rendered here came out of someone else's repo, so it goes
+/// through the untrusted policy: stripped to an allow-list of tags and
+/// attributes with URL schemes filtered. See [`crate::sanitize`].
+fn sanitized(html: String) -> String {
+    crate::sanitize::sanitize_html(&html, &crate::sanitize::SanitizePolicy::untrusted())
+}
+
 /// Fetch and render a profile record as HTML
 ///
 /// Resolves handle to DID if needed, then fetches profile data from
@@ -79,6 +88,61 @@ where
     render_post_view(&post_view, uri)
 }
 
+/// Batch variant of [`fetch_and_render_post`]: one `getPosts` call per chunk of URIs.
+///
+/// Returns an entry per requested URI in order. Posts the appview omits
+/// (deleted, blocked, not found) come back as individual errors rather than
+/// failing the whole batch.
+pub async fn fetch_and_render_posts<A>(
+    uris: Vec<AtUri<'static>>,
+    agent: &A,
+) -> Vec<(AtUri<'static>, Result<String, AtProtoPreprocessError>)>
+where
+    A: AgentSessionExt,
+{
+    // app.bsky.feed.getPosts caps the uris parameter at 25 entries.
+    const GET_POSTS_MAX_URIS: usize = 25;
+
+    let mut out = Vec::with_capacity(uris.len());
+
+    for chunk in uris.chunks(GET_POSTS_MAX_URIS) {
+        let request = GetPosts::new().uris(chunk.to_vec()).build();
+        let output = match agent.send(request).await {
+            Ok(response) => response.into_output().map_err(|e| format!("{:?}", e)),
+            Err(e) => Err(format!("getting posts from appview {:?}", e)),
+        };
+
+        match output {
+            Ok(output) => {
+                for uri in chunk {
+                    let result = output
+                        .posts
+                        .iter()
+                        .find(|post| post.uri.as_str() == uri.as_str())
+                        .map(|post| render_post_view(post, uri))
+                        .unwrap_or_else(|| {
+                            Err(AtProtoPreprocessError::FetchFailed(
+                                "Post not found".to_string(),
+                            ))
+                        });
+                    out.push((uri.clone(), result));
+                }
+            }
+            Err(message) => {
+                // The whole call failed; surface the same error for each URI.
+                for uri in chunk {
+                    out.push((
+                        uri.clone(),
+                        Err(AtProtoPreprocessError::FetchFailed(message.clone())),
+                    ));
+                }
+            }
+        }
+    }
+
+    out
+}
+
 /// Fetch and render an unknown record type generically
 ///
 /// This fetches the record as untyped Data and probes for likely meaningful fields.
@@ -184,7 +248,7 @@ where
 
     html.push_str("</div>");
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 /// Fetch and render a notebook entry with full markdown rendering
@@ -280,7 +344,7 @@ where
 
     html.push_str("</div>");
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 /// Fetch and render a Leaflet document as HTML
@@ -417,7 +481,7 @@ where
 
     html.push_str("</div>");
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 #[cfg(feature = "pckt")]
@@ -574,7 +638,7 @@ where
 
     html.push_str("</div>");
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 /// Fetch and render any AT URI, dispatching to the appropriate renderer based on collection.
@@ -655,7 +719,7 @@ pub fn render_record(
 ) -> Result<String, AtProtoPreprocessError> {
     let collection = uri.collection().map(|c| c.as_ref());
 
-    match collection {
+    let html = match collection {
         // No collection = just an identity reference, try as profile
         None => render_profile_from_data(data, uri),
 
@@ -709,7 +773,9 @@ pub fn render_record(
 
         // Default: generic rendering
         _ => render_generic_record(data, uri),
-    }
+    }?;
+
+    Ok(sanitized(html))
 }
 
 /// Try to render profile data by detecting the view type.
@@ -1509,7 +1575,7 @@ pub fn render_profile_data_view(
         }
     }
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 /// Render a Bluesky post from PostView (rich appview data).
@@ -1582,7 +1648,7 @@ pub fn render_post_view<'a>(
     html.push_str("</span>");
     html.push_str("</span>");
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 /// Render a generic record by probing Data for meaningful fields.
@@ -1670,7 +1736,7 @@ pub fn render_generic_record(
 
     html.push_str("</span>");
 
-    Ok(html)
+    Ok(sanitized(html))
 }
 
 // =============================================================================
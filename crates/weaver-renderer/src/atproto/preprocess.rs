@@ -345,6 +345,39 @@ impl<A: AgentSession + IdentityResolver> NotebookContext for AtProtoPreprocessCo
                 id,
                 attrs,
             } => {
+                // Data file embeds render as tables, inlined as content the
+                // same way the record embeds below carry their HTML.
+                if crate::csv::is_tabular_path(dest_url) {
+                    if let Some(file_path) =
+                        lookup_filename_in_vault(dest_url.as_ref(), &self.vault_contents)
+                    {
+                        match crate::csv::render_table_file(
+                            &file_path,
+                            crate::csv::DEFAULT_ROW_LIMIT,
+                            dest_url,
+                        ) {
+                            Ok(table) => {
+                                let mut new_attrs =
+                                    attrs.clone().unwrap_or_else(|| WeaverAttributes {
+                                        classes: vec![],
+                                        attrs: vec![],
+                                    });
+                                new_attrs.attrs.push(("content".into(), table.into()));
+                                return Tag::Embed {
+                                    embed_type: *embed_type,
+                                    dest_url: dest_url.clone(),
+                                    title: title.clone(),
+                                    id: id.clone(),
+                                    attrs: Some(new_attrs),
+                                };
+                            }
+                            Err(e) => {
+                                eprintln!("Failed to read data embed {:?}: {}", file_path, e);
+                            }
+                        }
+                    }
+                }
+
                 // Resolve embed using LinkUri helper
                 let resolved = LinkUri::resolve(dest_url.as_ref(), &*self.agent).await;
 
@@ -86,7 +86,7 @@ pub struct ClientWriter<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>, W: St
 
     embed_provider: Option<E>,
 
-    code_buffer: Option<(Option<String>, String)>, // (lang, content)
+    code_buffer: Option<(crate::fence::FenceInfo, String)>, // (fence info, content)
 
     /// Pending WeaverBlock attrs to apply to the next block element
     pending_block_attrs: Option<WeaverAttributes<'static>>,
@@ -662,19 +662,15 @@ impl<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>, W: StrWrite, E: EmbedCon
                 }
                 match info {
                     CodeBlockKind::Fenced(info) => {
-                        let lang = info.split(' ').next().unwrap();
-                        let lang_opt = if lang.is_empty() {
-                            None
-                        } else {
-                            Some(lang.to_string())
-                        };
-                        // Start buffering
-                        self.code_buffer = Some((lang_opt, String::new()));
+                        // Start buffering; the info string may carry a
+                        // title and highlight ranges beyond the language.
+                        self.code_buffer =
+                            Some((crate::fence::parse_fence_info(&info), String::new()));
                         Ok(())
                     }
                     CodeBlockKind::Indented => {
                         // Start buffering with no language
-                        self.code_buffer = Some((None, String::new()));
+                        self.code_buffer = Some((Default::default(), String::new()));
                         Ok(())
                     }
                 }
@@ -975,12 +971,12 @@ impl<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>, W: StrWrite, E: EmbedCon
                     static SYNTAX_SET: LazyLock<SyntaxSet> =
                         LazyLock::new(|| SyntaxSet::load_defaults_newlines());
 
-                    if let Some((lang, buffer)) = self.code_buffer.take() {
-                        if let Some(ref lang_str) = lang {
+                    if let Some((fence, buffer)) = self.code_buffer.take() {
+                        if fence.lang.is_some() || fence.has_decorations() {
                             let mut temp_output = String::new();
-                            match crate::code_pretty::highlight(
+                            match crate::code_pretty::highlight_with_info(
                                 &SYNTAX_SET,
-                                Some(lang_str),
+                                &fence,
                                 &buffer,
                                 &mut temp_output,
                             ) {
@@ -989,7 +985,10 @@ impl<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>, W: StrWrite, E: EmbedCon
                                 }
                                 Err(_) => {
                                     self.write("<pre><code class=\"language-")?;
-                                    escape_html(&mut self.writer, lang_str)?;
+                                    escape_html(
+                                        &mut self.writer,
+                                        fence.lang.as_deref().unwrap_or(""),
+                                    )?;
                                     self.write("\">")?;
                                     escape_html_body_text(&mut self.writer, &buffer)?;
                                     self.write("</code></pre>\n")?;
@@ -1007,8 +1006,8 @@ impl<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>, W: StrWrite, E: EmbedCon
                 }
                 #[cfg(not(feature = "syntax-highlighting"))]
                 {
-                    if let Some((lang, buffer)) = self.code_buffer.take() {
-                        if let Some(ref lang_str) = lang {
+                    if let Some((fence, buffer)) = self.code_buffer.take() {
+                        if let Some(ref lang_str) = fence.lang {
                             self.write("<pre><code class=\"language-")?;
                             escape_html(&mut self.writer, lang_str)?;
                             self.write("\">")?;
@@ -1048,6 +1047,13 @@ impl<'a, I: Iterator<Item = (Event<'a>, Range<usize>)>, W: StrWrite, E: EmbedCon
             TagEnd::Embed => Ok(()),
             TagEnd::WeaverBlock(_) => {
                 self.in_non_writing_block = false;
+                // Directives are page-rendering instructions with no
+                // representation in AT Protocol records; drop them rather
+                // than leaking them onto the next block as classes.
+                if crate::directive::is_directive(&self.weaver_block_buffer) {
+                    self.weaver_block_buffer.clear();
+                    return Ok(());
+                }
                 // Parse the buffered text for attrs and store for next block
                 if !self.weaver_block_buffer.is_empty() {
                     let parsed = Self::parse_weaver_attrs(&self.weaver_block_buffer);
@@ -1,4 +1,5 @@
-use markdown_weaver_escape::StrWrite;
+use crate::fence::FenceInfo;
+use markdown_weaver_escape::{StrWrite, escape_html};
 // use syntect::highlighting::ThemeSet;
 // use syntect::html::css_for_theme_with_class_style;
 use syntect::html::{ClassStyle, ClassedHTMLGenerator};
@@ -48,6 +49,115 @@ where
 
 pub const CSS_PREFIX: &str = "wvc-";
 
+/// Highlight a code block with fence decorations: a header carrying the
+/// title and a copy-button container, plus per-line spans so highlighted
+/// ranges can be styled.
+///
+/// Fences without decorations fall through to [`highlight`], keeping the
+/// common case's markup unchanged.
+pub fn highlight_with_info<M>(
+    syn_set: &SyntaxSet,
+    info: &FenceInfo,
+    code: impl AsRef<str>,
+    writer: &mut M,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>>
+where
+    M: StrWrite,
+    <M as StrWrite>::Error: std::error::Error + Send + Sync + 'static,
+{
+    if !info.has_decorations() {
+        return highlight(syn_set, info.lang.as_deref(), code, writer);
+    }
+
+    let lang_syn = if let Some(lang) = &info.lang {
+        syn_set
+            .find_syntax_by_token(lang)
+            .unwrap_or_else(|| syn_set.find_syntax_plain_text())
+    } else {
+        syn_set
+            .find_syntax_by_first_line(code.as_ref())
+            .unwrap_or_else(|| syn_set.find_syntax_plain_text())
+    };
+
+    writer.write_str("<div class=\"wvc-codeblock\"><div class=\"wvc-code-header\">")?;
+    if let Some(title) = &info.title {
+        writer.write_str("<span class=\"wvc-code-title\">")?;
+        escape_html(&mut *writer, title)?;
+        writer.write_str("</span>")?;
+    }
+    writer.write_str(
+        "<button class=\"wvc-code-copy\" type=\"button\" aria-label=\"copy code\"></button></div>",
+    )?;
+    writer.write_str("<pre><code class=\"wvc-code language-")?;
+    writer.write_str(&lang_syn.name)?;
+    writer.write_str("\">")?;
+
+    let mut html_gen = ClassedHTMLGenerator::new_with_class_style(
+        lang_syn,
+        syn_set,
+        ClassStyle::SpacedPrefixed { prefix: CSS_PREFIX },
+    );
+    for line in LinesWithEndings::from(code.as_ref()) {
+        html_gen
+            .parse_html_for_line_which_includes_newline(line)
+            .unwrap();
+    }
+    for (index, line) in balance_lines(&html_gen.finalize()).iter().enumerate() {
+        if info.is_highlighted(index + 1) {
+            writer.write_str("<span class=\"wvc-line wvc-line-hl\">")?;
+        } else {
+            writer.write_str("<span class=\"wvc-line\">")?;
+        }
+        writer.write_str(line)?;
+        writer.write_str("</span>\n")?;
+    }
+    writer.write_str("</code></pre></div>")?;
+    Ok(())
+}
+
+/// Splits highlighter output into per-line fragments with balanced spans.
+///
+/// Syntect keeps scope spans open across line boundaries, so wrapping
+/// lines naively would close foreign spans. Open spans are closed at the
+/// end of each line and reopened at the start of the next; the scan only
+/// has to understand the `<span ...>`/`</span>` markup syntect itself
+/// emits.
+fn balance_lines(html: &str) -> Vec<String> {
+    let mut open: Vec<String> = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in html.split_inclusive('\n') {
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let mut fragment: String = open.concat();
+        fragment.push_str(line);
+
+        let mut at = 0;
+        while let Some(pos) = line[at..].find('<') {
+            let pos = at + pos;
+            if line[pos..].starts_with("</span>") {
+                open.pop();
+                at = pos + "</span>".len();
+            } else if line[pos..].starts_with("<span") {
+                let end = line[pos..]
+                    .find('>')
+                    .map(|e| pos + e + 1)
+                    .unwrap_or(line.len());
+                open.push(line[pos..end].to_string());
+                at = end;
+            } else {
+                at = pos + 1;
+            }
+        }
+
+        for _ in &open {
+            fragment.push_str("</span>");
+        }
+        lines.push(fragment);
+    }
+
+    lines
+}
+
 pub fn highlight_code<M>(
     lang: Option<&str>,
     code: impl AsRef<str>,
@@ -85,3 +195,17 @@ where
     writer.write_str("</code></pre>")?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_lines_reopens_spans() {
+        // A span left open across the line break must be closed on line
+        // one and reopened on line two.
+        let lines = balance_lines("<span class=\"a\">one\ntwo</span>");
+        assert_eq!(lines[0], "<span class=\"a\">one</span>");
+        assert_eq!(lines[1], "<span class=\"a\">two</span>");
+    }
+}
@@ -1,38 +1,74 @@
-use crate::theme::{ResolvedTheme, ThemeDarkCodeTheme, ThemeLightCodeTheme};
-use miette::IntoDiagnostic;
+//! CSS generation and the theming contract for rendered output.
+//!
+//! All HTML produced by weaver-renderer (notebook pages, AT Protocol record
+//! embeds, facets, code blocks, callouts and margin notes) is styled
+//! exclusively through a fixed set of CSS custom properties and class names,
+//! so embedders can restyle output by overriding variables instead of
+//! parsing our HTML.
+//!
+//! # Variable contract
+//!
+//! Colours, set per light/dark scheme: `--color-base`, `--color-surface`,
+//! `--color-overlay`, `--color-text`, `--color-muted`, `--color-subtle`,
+//! `--color-emphasis`, `--color-primary`, `--color-secondary`,
+//! `--color-tertiary`, `--color-error`, `--color-warning`,
+//! `--color-success`, `--color-border`, `--color-link`,
+//! `--color-highlight`.
+//!
+//! Fonts: `--font-body`, `--font-heading`, `--font-mono`, `--font-ui`.
+//!
+//! Spacing: `--spacing-base`, `--spacing-line-height`, `--spacing-scale`.
+//!
+//! # Class namespaces
+//!
+//! - `.notebook-content` scopes all rendered notebook markup.
+//! - `.embed-*` and `.atproto-*` cover AT Protocol record embeds.
+//! - `.embed-field-*`, `.aturi-*` and `.did-*` cover record field and
+//!   facet highlighting.
+//! - `wvc-`-prefixed classes cover syntax highlighting spans (see
+//!   `code_pretty::CSS_PREFIX`).
+//! - `.sidenote*`, `.aside` and `.margin-toggle` cover callouts and margin
+//!   notes.
+//!
+//! These names and variables are stable; renaming any of them is a breaking
+//! change for embedders. [`generate_theme_css`] emits only the variable
+//! definitions for a theme, for embedders who bring their own structural
+//! rules; [`generate_base_css`] layers ours on top.
+
+use crate::theme::ResolvedTheme;
 use smol_str::format_smolstr;
+use weaver_api::sh_weaver::notebook::colour_scheme::ColourSchemeColours;
+use weaver_api::sh_weaver::notebook::theme::{Font, FontValue};
+
+#[cfg(feature = "syntax-css")]
+use crate::theme::{ThemeDarkCodeTheme, ThemeLightCodeTheme};
+#[cfg(feature = "syntax-css")]
+use miette::IntoDiagnostic;
+#[cfg(feature = "syntax-css")]
 use std::io::Cursor;
+#[cfg(feature = "syntax-css")]
 use syntect::highlighting::ThemeSet;
+#[cfg(feature = "syntax-css")]
 use syntect::html::{ClassStyle, css_for_theme_with_class_style};
+#[cfg(feature = "syntax-css")]
 use weaver_api::com_atproto::sync::get_blob::GetBlob;
-use weaver_api::sh_weaver::notebook::theme::FontValue;
+#[cfg(feature = "syntax-css")]
 use weaver_common::jacquard::client::BasicClient;
+#[cfg(feature = "syntax-css")]
 use weaver_common::jacquard::prelude::*;
+#[cfg(feature = "syntax-css")]
 use weaver_common::jacquard::xrpc::XrpcExt;
 
 // Embed rose-pine themes at compile time
+#[cfg(feature = "syntax-css")]
 const ROSE_PINE_THEME: &str = include_str!("../themes/rose-pine.tmTheme");
+#[cfg(feature = "syntax-css")]
 const ROSE_PINE_DAWN_THEME: &str = include_str!("../themes/rose-pine-dawn.tmTheme");
 
-pub fn generate_base_css(theme: &ResolvedTheme) -> String {
-    let dark = &theme.dark_scheme;
-    let light = &theme.light_scheme;
-    let fonts = &theme.fonts;
-    let spacing = &theme.spacing;
-
+/// Render a font list as a CSS font-family stack, skipping blob-backed fonts.
+fn font_stack(fonts: &[Font<'_>]) -> String {
     // interim until handle fonts from blobs
-    let body = fonts
-        .body
-        .iter()
-        .filter_map(|f| match &f.value {
-            FontValue::FontName(cow_str) => Some(format_smolstr!("'{cow_str}'")),
-            FontValue::FontFile(_font_file) => None,
-            FontValue::Unknown(_data) => None,
-        })
-        .collect::<Vec<_>>()
-        .join(",");
-    let monospace = fonts
-        .monospace
+    fonts
         .iter()
         .filter_map(|f| match &f.value {
             FontValue::FontName(cow_str) => Some(format_smolstr!("'{cow_str}'")),
@@ -40,29 +76,13 @@ pub fn generate_base_css(theme: &ResolvedTheme) -> String {
             FontValue::Unknown(_data) => None,
         })
         .collect::<Vec<_>>()
-        .join(",");
-    let heading = fonts
-        .heading
-        .iter()
-        .filter_map(|f| match &f.value {
-            FontValue::FontName(cow_str) => Some(format_smolstr!("'{cow_str}'")),
-            FontValue::FontFile(_font_file) => None,
-            FontValue::Unknown(_data) => None,
-        })
-        .collect::<Vec<_>>()
-        .join(",");
+        .join(",")
+}
 
+/// Render one colour scheme as `--color-*` declarations.
+fn colour_variables(colours: &ColourSchemeColours<'_>) -> String {
     format!(
-        r#"/* CSS Reset */
-*, *::before, *::after {{
-    box-sizing: border-box;
-    margin: 0;
-    padding: 0;
-}}
-
-/* CSS Variables - Light Mode (default) */
-:root {{
-    --color-base: {};
+        "    --color-base: {};
     --color-surface: {};
     --color-overlay: {};
     --color-text: {};
@@ -78,46 +98,103 @@ pub fn generate_base_css(theme: &ResolvedTheme) -> String {
     --color-border: {};
     --color-link: {};
     --color-highlight: {};
+",
+        colours.base,
+        colours.surface,
+        colours.overlay,
+        colours.text,
+        colours.muted,
+        colours.subtle,
+        colours.emphasis,
+        colours.primary,
+        colours.secondary,
+        colours.tertiary,
+        colours.error,
+        colours.warning,
+        colours.success,
+        colours.border,
+        colours.link,
+        colours.highlight,
+    )
+}
 
-    --font-body: {};
-    --font-heading: {};
-    --font-mono: {};
+/// Generate only the CSS variable definitions for a theme.
+///
+/// This is the variable half of the theming contract (see the module docs):
+/// a `:root` block with the light scheme, fonts and spacing, plus a
+/// `prefers-color-scheme: dark` override for the dark scheme. A `data-theme`
+/// attribute of `"light"` or `"dark"` on the root element forces the
+/// matching scheme regardless of the system preference, so embedders with a
+/// theme toggle get correct colours without regenerating CSS. Embedders who
+/// want our variables with their own structural rules should use this;
+/// [`generate_base_css`] builds on it.
+pub fn generate_theme_css(theme: &ResolvedTheme) -> String {
+    let body = font_stack(&theme.fonts.body);
+    let heading = font_stack(&theme.fonts.heading);
+    let monospace = font_stack(&theme.fonts.monospace);
+    let spacing = &theme.spacing;
+
+    format!(
+        r#"/* CSS Variables - Light Mode (default) */
+:root {{
+{light_colours}
+    --font-body: {body};
+    --font-heading: {heading};
+    --font-mono: {monospace};
+    /* UI chrome (labels, buttons) shares the heading stack. */
+    --font-ui: {heading};
 
-    --spacing-base: {};
-    --spacing-line-height: {};
-    --spacing-scale: {};
+    --spacing-base: {base_size};
+    --spacing-line-height: {line_height};
+    --spacing-scale: {scale};
 }}
 
 /* CSS Variables - Dark Mode */
 @media (prefers-color-scheme: dark) {{
     :root {{
-        --color-base: {};
-        --color-surface: {};
-        --color-overlay: {};
-        --color-text: {};
-        --color-muted: {};
-        --color-subtle: {};
-        --color-emphasis: {};
-        --color-primary: {};
-        --color-secondary: {};
-        --color-tertiary: {};
-        --color-error: {};
-        --color-warning: {};
-        --color-success: {};
-        --color-border: {};
-        --color-link: {};
-        --color-highlight: {};
-    }}
+{dark_colours}    }}
 }}
 
-/* Base Styles */
-html {{
+/* CSS Variables - explicit preference via `data-theme`.
+   These follow the media query and carry higher specificity, so a user's
+   stored choice beats the system preference in either direction. */
+:root[data-theme="light"] {{
+{light_colours}}}
+
+:root[data-theme="dark"] {{
+{dark_colours}}}
+"#,
+        light_colours = colour_variables(&theme.light_scheme),
+        dark_colours = colour_variables(&theme.dark_scheme),
+        base_size = spacing.base_size,
+        line_height = spacing.line_height,
+        scale = spacing.scale,
+    )
+}
+
+/// Reset applied before the structural rules.
+const CSS_RESET: &str = r#"/* CSS Reset */
+*, *::before, *::after {
+    box-sizing: border-box;
+    margin: 0;
+    padding: 0;
+}
+
+"#;
+
+/// Structural rules for all rendered output.
+///
+/// References only the variables and class names documented in the module
+/// docs; anything theme-dependent must go through a variable so
+/// [`generate_theme_css`] alone can restyle it.
+const BASE_STRUCTURE_CSS: &str = r#"/* Base Styles */
+html {
     font-size: var(--spacing-base);
     line-height: var(--spacing-line-height);
-}}
+}
 
 /* Scoped to notebook-content container */
-.notebook-content {{
+.notebook-content {
     font-family: var(--font-body);
     color: var(--color-text);
     background-color: var(--color-base);
@@ -127,129 +204,129 @@ html {{
     overflow-wrap: break-word;
     counter-reset: sidenote-counter;
     max-width: 95ch;
-}}
+}
 
 /* When sidenotes exist, body padding creates the gutter */
 /* Left padding shrinks first as viewport narrows, right stays for sidenotes */
-body:has(.sidenote) {{
+body:has(.sidenote) {
     padding-inline-start: clamp(1rem, calc((100vw - 95ch - 15.5rem - 2rem) / 2), 15.5rem);
     padding-inline-end: 15.5rem;
-}}
+}
 
 /* Typography */
-h1, h2, h3, h4, h5, h6 {{
+h1, h2, h3, h4, h5, h6 {
     font-family: var(--font-heading);
     margin-top: calc(1rem * var(--spacing-scale));
     margin-bottom: 0.5rem;
     line-height: 1.2;
-}}
+}
 
-h1 {{
+h1 {
   font-size: 2rem;
   color: var(--color-secondary);
-}}
-h2 {{
+}
+h2 {
   font-size: 1.5rem;
   color: var(--color-primary);
-}}
-h3 {{
+}
+h3 {
   font-size: 1.25rem;
   color: var(--color-secondary);
-}}
-h4 {{
+}
+h4 {
   font-size: 1.2rem;
   color: var(--color-tertiary);
-}}
-h5 {{
+}
+h5 {
   font-size: 1.125rem;
   color: var(--color-secondary);
-}}
-h6 {{ font-size: 1rem; }}
+}
+h6 { font-size: 1rem; }
 
-p {{
+p {
     margin-bottom: 1rem;
     word-wrap: break-word;
     overflow-wrap: break-word;
-}}
+}
 
-a {{
+a {
     color: var(--color-link);
     text-decoration: none;
-}}
+}
 
-.notebook-content a:hover {{
+.notebook-content a:hover {
     color: var(--color-emphasis);
     text-decoration: underline;
-}}
+}
 
 /* Wikilink validation (editor) */
-.link-valid {{
+.link-valid {
     color: var(--color-link);
-}}
+}
 
-.link-broken {{
+.link-broken {
     color: var(--color-error);
     text-decoration: underline wavy;
     text-decoration-color: var(--color-error);
     opacity: 0.8;
-}}
+}
 
 /* Selection */
-::selection {{
+::selection {
     background: var(--color-highlight);
     color: var(--color-text);
-}}
+}
 
 /* Lists */
-ul, ol {{
+ul, ol {
     margin-inline-start: 1rem;
     margin-bottom: 1rem;
-}}
+}
 
-li {{
+li {
     margin-bottom: 0.25rem;
-}}
+}
 
 /* Code */
-code {{
+code {
     font-family: var(--font-mono);
     background: var(--color-surface);
     padding: 0.125rem 0.25rem;
     border-radius: 4px;
     font-size: 0.9em;
-}}
+}
 
-pre {{
+pre {
     overflow-x: auto;
     margin-bottom: 1rem;
     border-radius: 5px;
     border: 1px solid var(--color-border);
     box-sizing: border-box;
-}}
+}
 
 /* Code blocks inside pre are handled by syntax theme */
-pre code {{
+pre code {
 
     display: block;
     width: fit-content;
     min-width: 100%;
     padding: 1rem;
     background: var(--color-surface);
-}}
+}
 
 /* Math */
-.math {{
+.math {
     font-family: var(--font-mono);
-}}
+}
 
-.math-display {{
+.math-display {
     display: block;
     margin: 1rem 0;
     text-align: center;
-}}
+}
 
 /* Blockquotes */
-blockquote {{
+blockquote {
     border-inline-start: 2px solid var(--color-secondary);
     background: var(--color-surface);
     padding-inline-start: 1rem;
@@ -260,70 +337,70 @@ blockquote {{
     font-size: 0.95em;
     border-bottom-right-radius: 5px;
     border-top-right-radius: 5px;
-}}
+}
 
 /* Tables */
-table {{
+table {
     border-collapse: collapse;
     width: 100%;
     margin-bottom: 1rem;
     display: block;
     overflow-x: auto;
     max-width: 100%;
-}}
+}
 
-th, td {{
+th, td {
     border: 1px solid var(--color-border);
     padding: 0.5rem;
     text-align: start;
-}}
+}
 
-th {{
+th {
     background: var(--color-surface);
     font-weight: 600;
-}}
+}
 
-tr:hover {{
+tr:hover {
     background: var(--color-surface);
-}}
+}
 
 /* Footnotes */
-.footnote-reference {{
+.footnote-reference {
     font-size: 0.8em;
     color: var(--color-subtle);
-}}
+}
 
-.footnote-definition {{
+.footnote-definition {
     order: 9999;
     margin: 0;
     padding: 0.5rem 0;
     font-size: 0.9em;
-}}
+}
 
-.footnote-definition:first-of-type {{
+.footnote-definition:first-of-type {
     margin-top: 2rem;
     padding-top: 1rem;
     border-top: 2px solid var(--color-border);
-}}
+}
 
-.footnote-definition:first-of-type::before {{
+.footnote-definition:first-of-type::before {
     content: "Footnotes";
     display: block;
     font-weight: 600;
     font-size: 1.1em;
     color: var(--color-subtle);
     margin-bottom: 0.75rem;
-}}
+}
 
-.footnote-definition-label {{
+.footnote-definition-label {
     font-weight: 600;
     margin-inline-end: 0.5rem;
     color: var(--color-primary);
-}}
+}
 
 /* Aside blocks (via WeaverBlock prefix) - scoped to notebook content */
 .notebook-content aside,
-.notebook-content .aside {{
+.notebook-content .aside {
     float: inline-start;
     width: 40%;
     margin: 0 1.5rem 1rem 0;
@@ -332,55 +409,79 @@ tr:hover {{
     border-inline-end: 3px solid var(--color-primary);
     font-size: 0.9em;
     clear: inline-start;
-}}
+}
 
 .notebook-content aside > *:first-child,
-.notebook-content .aside > *:first-child {{
+.notebook-content .aside > *:first-child {
     margin-top: 0;
-}}
+}
 
 .notebook-content aside > *:last-child,
-.notebook-content .aside > *:last-child {{
+.notebook-content .aside > *:last-child {
     margin-bottom: 0;
-}}
+}
 
 /* Reset blockquote styling inside asides */
 .notebook-content aside > blockquote,
-.notebook-content .aside > blockquote {{
+.notebook-content .aside > blockquote {
     border-inline-start: none;
     background: transparent;
     padding: 0;
     margin: 0;
     font-size: inherit;
-}}
+}
+
+/* Directive output (via `weaver:` blocks) */
+.notebook-content .weaver-toc ol {
+    list-style: none;
+    padding-inline-start: 0;
+}
+
+.notebook-content .weaver-toc .toc-level-2 { margin-inline-start: 1em; }
+.notebook-content .weaver-toc .toc-level-3 { margin-inline-start: 2em; }
+.notebook-content .weaver-toc .toc-level-4 { margin-inline-start: 3em; }
+.notebook-content .weaver-toc .toc-level-5 { margin-inline-start: 4em; }
+.notebook-content .weaver-toc .toc-level-6 { margin-inline-start: 5em; }
+
+.notebook-content .weaver-gallery {
+    display: grid;
+    grid-template-columns: repeat(auto-fill, minmax(200px, 1fr));
+    gap: 1rem;
+}
+
+.notebook-content .weaver-gallery img {
+    width: 100%;
+    height: 100%;
+    object-fit: cover;
+}
 
 /* Indent utilities */
-.indent-1 {{ margin-inline-start: 1em; }}
-.indent-2 {{ margin-inline-start: 2em; }}
-.indent-3 {{ margin-inline-start: 3em; }}
+.indent-1 { margin-inline-start: 1em; }
+.indent-2 { margin-inline-start: 2em; }
+.indent-3 { margin-inline-start: 3em; }
 
 /* Tufte-style Sidenotes */
 /* Hide checkbox for sidenote toggle */
-.margin-toggle {{
+.margin-toggle {
     display: none;
-}}
+}
 
 /* Sidenote number marker (inline superscript) */
-.sidenote-number {{
+.sidenote-number {
     counter-increment: sidenote-counter;
-}}
+}
 
-.sidenote-number::after {{
+.sidenote-number::after {
     content: counter(sidenote-counter);
     font-size: 0.7em;
     position: relative;
     top: -0.5em;
     color: var(--color-primary);
     padding-inline-start: 0.1em;
-}}
+}
 
 /* Sidenote content (margin notes on wide screens) */
-.sidenote {{
+.sidenote {
     float: inline-end;
     clear: inline-end;
     margin-inline-end: -15.5rem;
@@ -390,31 +491,31 @@ tr:hover {{
     font-size: 0.85em;
     line-height: 1.4;
     color: var(--color-subtle);
-}}
+}
 
-.sidenote::before {{
+.sidenote::before {
     content: counter(sidenote-counter) ". ";
     color: var(--color-primary);
-}}
+}
 
 /* Mobile sidenotes: toggle behavior */
-@media (max-width: 900px) {{
+@media (max-width: 900px) {
     /* Reset sidenote gutter on mobile */
-    body:has(.sidenote) {{
+    body:has(.sidenote) {
         padding-inline-end: 0;
-    }}
+    }
 
-    aside, .aside {{
+    aside, .aside {
         float: none;
         width: 100%;
         margin: 1rem 0;
-    }}
+    }
 
-    .sidenote {{
+    .sidenote {
         display: none;
-    }}
+    }
 
-    .margin-toggle:checked + .sidenote {{
+    .margin-toggle:checked + .sidenote {
         display: block;
         float: none;
         width: 95%;
@@ -422,37 +523,37 @@ tr:hover {{
         padding: 0.5rem;
         background: var(--color-surface);
         border-inline-start: 2px solid var(--color-primary);
-    }}
+    }
 
-    label.sidenote-number {{
+    label.sidenote-number {
         cursor: pointer;
-    }}
+    }
 
-    label.sidenote-number::after {{
+    label.sidenote-number::after {
         text-decoration: underline;
-    }}
-}}
+    }
+}
 
 /* Images */
-img {{
+img {
     max-width: 100%;
     height: auto;
     display: block;
     margin: 1rem 0;
     border-radius: 4px;
-}}
+}
 
 /* Hygiene for iframes */
-.html-embed-block {{
+.html-embed-block {
     max-width: 100%;
     height: auto;
     display: block;
     margin: 1rem 0;
-}}
+}
 
 /* AT Protocol Embeds - Container */
 /* Light mode: paper with shadow, dark mode: blueprint with borders */
-.atproto-embed {{
+.atproto-embed {
     display: block;
     position: relative;
     max-width: 550px;
@@ -461,42 +562,42 @@ img {{
     background: var(--color-surface);
     border-inline-start: 2px solid var(--color-secondary);
     box-shadow: 0 1px 2px color-mix(in srgb, var(--color-text) 8%, transparent);
-}}
+}
 
-.atproto-embed:hover {{
+.atproto-embed:hover {
     border-inline-start-color: var(--color-primary);
-}}
+}
 
-@media (prefers-color-scheme: dark) {{
-    .atproto-embed {{
+@media (prefers-color-scheme: dark) {
+    .atproto-embed {
         box-shadow: none;
         border: 1px solid var(--color-border);
         border-inline-start: 2px solid var(--color-secondary);
-    }}
-}}
+    }
+}
 
-.atproto-embed-placeholder {{
+.atproto-embed-placeholder {
     color: var(--color-muted);
     font-style: italic;
-}}
+}
 
-.embed-loading {{
+.embed-loading {
     display: block;
     padding: 0.5rem 0;
     color: var(--color-subtle);
     font-family: var(--font-mono);
     font-size: 0.85rem;
-}}
+}
 
 /* Embed Author Block */
-.embed-author {{
+.embed-author {
     display: flex;
     align-items: center;
     gap: 0.75rem;
     padding-bottom: 0.5rem;
-}}
+}
 
-.embed-avatar {{
+.embed-avatar {
     width: 36px;
     height: 36px;
     max-width: 36px;
@@ -504,21 +605,21 @@ img {{
     aspect-ratio: 1;
     margin: 0;
     object-fit: cover;
-}}
+}
 
-.embed-author-info {{
+.embed-author-info {
     display: flex;
     flex-direction: column;
     gap: 0;
     min-width: 0;
-}}
+}
 
-.embed-avatar-link {{
+.embed-avatar-link {
     display: block;
     flex-shrink: 0;
-}}
+}
 
-.embed-author-name {{
+.embed-author-name {
     font-weight: 600;
     color: var(--color-text);
     overflow: hidden;
@@ -526,13 +627,13 @@ img {{
     white-space: nowrap;
     text-decoration: none;
     line-height: 1.2;
-}}
+}
 
-a.embed-author-name:hover {{
+a.embed-author-name:hover {
     color: var(--color-link);
-}}
+}
 
-.embed-author-handle {{
+.embed-author-handle {
     font-size: 0.85em;
     font-family: var(--font-mono);
     color: var(--color-subtle);
@@ -541,102 +642,102 @@ a.embed-author-name:hover {{
     text-overflow: ellipsis;
     white-space: nowrap;
     line-height: 1.2;
-}}
+}
 
-.embed-author-handle:hover {{
+.embed-author-handle:hover {
     color: var(--color-link);
-}}
+}
 
 /* Card-wide clickable link (sits behind content) */
-.embed-card-link {{
+.embed-card-link {
     position: absolute;
     inset: 0;
     z-index: 0;
-}}
+}
 
-.embed-card-link:focus {{
+.embed-card-link:focus {
     outline: 2px solid var(--color-primary);
     outline-offset: 2px;
-}}
+}
 
 /* Interactive elements sit above the card link */
 .embed-author,
 .embed-external,
 .embed-quote,
 .embed-images,
-.embed-meta {{
+.embed-meta {
     position: relative;
     z-index: 1;
-}}
+}
 
 /* Embed Content Block */
-.embed-content {{
+.embed-content {
     display: block;
     color: var(--color-text);
     line-height: 1.5;
     margin-bottom: 0.75rem;
     white-space: pre-wrap;
-}}
+}
 
 
 
-.embed-description {{
+.embed-description {
     display: block;
     color: var(--color-text);
     font-size: 0.95em;
     line-height: 1.4;
-}}
+}
 
 /* Embed Metadata Block */
-.embed-meta {{
+.embed-meta {
     display: flex;
     justify-content: space-between;
     align-items: center;
     font-size: 0.85em;
     color: var(--color-muted);
     margin-top: 0.75rem;
-}}
+}
 
-.embed-stats {{
+.embed-stats {
     display: flex;
     gap: 1rem;
     font-family: var(--font-mono);
-}}
+}
 
-.embed-stat {{
+.embed-stat {
     color: var(--color-subtle);
     font-size: 0.9em;
-}}
+}
 
-.embed-time {{
+.embed-time {
     color: var(--color-subtle);
     text-decoration: none;
     font-family: var(--font-mono);
     font-size: 0.9em;
-}}
+}
 
-.embed-time:hover {{
+.embed-time:hover {
     color: var(--color-link);
-}}
+}
 
-.embed-type {{
+.embed-type {
     font-size: 0.8em;
     color: var(--color-subtle);
     font-family: var(--font-mono);
     text-transform: uppercase;
     letter-spacing: 0.05em;
-}}
+}
 
 /* Embed URL link (shown with syntax in editor) */
-.embed-url {{
+.embed-url {
     color: var(--color-link);
     font-family: var(--font-mono);
     font-size: 0.9em;
     word-break: break-all;
-}}
+}
 
 /* External link cards */
-.embed-external {{
+.embed-external {
     display: flex;
     gap: 0.75rem;
     padding: 0.75rem;
@@ -645,47 +746,47 @@ a.embed-author-name:hover {{
     text-decoration: none;
     color: inherit;
     margin-top: 0.5rem;
-}}
+}
 
-.embed-external:hover {{
+.embed-external:hover {
     border-inline-start: 2px solid var(--color-primary);
     margin-inline-start: -1px;
-}}
+}
 
-@media (prefers-color-scheme: dark) {{
-    .embed-external {{
+@media (prefers-color-scheme: dark) {
+    .embed-external {
         border: 1px solid var(--color-border);
-    }}
+    }
 
-    .embed-external:hover {{
+    .embed-external:hover {
         border-inline-start: 2px solid var(--color-primary);
         margin-inline-start: -1px;
-    }}
-}}
+    }
+}
 
-.embed-external-thumb {{
+.embed-external-thumb {
     width: 120px;
     height: 80px;
     object-fit: cover;
     flex-shrink: 0;
-}}
+}
 
-.embed-external-info {{
+.embed-external-info {
     display: flex;
     flex-direction: column;
     gap: 0.25rem;
     min-width: 0;
-}}
+}
 
-.embed-external-title {{
+.embed-external-title {
     font-weight: 600;
     color: var(--color-text);
     overflow: hidden;
     text-overflow: ellipsis;
     white-space: nowrap;
-}}
+}
 
-.embed-external-description {{
+.embed-external-description {
     font-size: 0.9em;
     color: var(--color-muted);
     overflow: hidden;
@@ -693,92 +794,92 @@ a.embed-author-name:hover {{
     display: -webkit-box;
     -webkit-line-clamp: 2;
     -webkit-box-orient: vertical;
-}}
+}
 
-.embed-external-url {{
+.embed-external-url {
     font-size: 0.8em;
     font-family: var(--font-mono);
     color: var(--color-subtle);
-}}
+}
 
 /* Image embeds */
-.embed-images {{
+.embed-images {
     display: grid;
     gap: 4px;
     margin-top: 0.5rem;
     overflow: hidden;
-}}
+}
 
-.embed-images-1 {{
+.embed-images-1 {
     grid-template-columns: 1fr;
-}}
+}
 
-.embed-images-2 {{
+.embed-images-2 {
     grid-template-columns: 1fr 1fr;
-}}
+}
 
-.embed-images-3 {{
+.embed-images-3 {
     grid-template-columns: 1fr 1fr;
-}}
+}
 
-.embed-images-4 {{
+.embed-images-4 {
     grid-template-columns: 1fr 1fr;
-}}
+}
 
-.embed-image-link {{
+.embed-image-link {
     display: block;
     line-height: 0;
-}}
+}
 
-.embed-image {{
+.embed-image {
     width: 100%;
     height: auto;
     max-height: 500px;
     object-fit: cover;
     object-position: center;
     margin: 0;
-}}
+}
 
 /* Quoted records */
-.embed-quote {{
+.embed-quote {
     display: block;
     margin-top: 0.5rem;
     padding: 0.75rem;
     background: var(--color-overlay);
     border-inline-start: 2px solid var(--color-tertiary);
-}}
+}
 
-@media (prefers-color-scheme: dark) {{
-    .embed-quote {{
+@media (prefers-color-scheme: dark) {
+    .embed-quote {
         border: 1px solid var(--color-border);
         border-inline-start: 2px solid var(--color-tertiary);
-    }}
-}}
+    }
+}
 
-.embed-quote .embed-author {{
+.embed-quote .embed-author {
     margin-bottom: 0.5rem;
-}}
+}
 
-.embed-quote .embed-avatar {{
+.embed-quote .embed-avatar {
     width: 24px;
     height: 24px;
     min-width: 24px;
     min-height: 24px;
     max-width: 24px;
     max-height: 24px;
-}}
+}
 
-.embed-quote .embed-content {{
+.embed-quote .embed-content {
     font-size: 0.95em;
     margin-bottom: 0;
-}}
+}
 
 /* Placeholder states */
 .embed-video-placeholder,
 .embed-not-found,
 .embed-blocked,
 .embed-detached,
-.embed-unknown {{
+.embed-unknown {
     display: block;
     padding: 1rem;
     background: var(--color-overlay);
@@ -788,178 +889,178 @@ a.embed-author-name:hover {{
     margin-top: 0.5rem;
     font-family: var(--font-mono);
     font-size: 0.9em;
-}}
+}
 
-@media (prefers-color-scheme: dark) {{
+@media (prefers-color-scheme: dark) {
     .embed-video-placeholder,
     .embed-not-found,
     .embed-blocked,
     .embed-detached,
-    .embed-unknown {{
+    .embed-unknown {
         border: 1px dashed var(--color-border);
-    }}
-}}
+    }
+}
 
 /* Record card embeds (feeds, lists, labelers, starter packs) */
-.embed-record-card {{
+.embed-record-card {
     display: block;
     margin-top: 0.5rem;
     padding: 0.75rem;
     background: var(--color-overlay);
     border-inline-start: 2px solid var(--color-tertiary);
-}}
+}
 
-.embed-record-card > .embed-author-name {{
+.embed-record-card > .embed-author-name {
     display: block;
     font-size: 1.1em;
-}}
+}
 
-.embed-subtitle {{
+.embed-subtitle {
     display: block;
     font-size: 0.85em;
     color: var(--color-muted);
     margin-bottom: 0.5rem;
-}}
+}
 
-.embed-record-card .embed-description {{
+.embed-record-card .embed-description {
     display: block;
     margin: 0.5rem 0;
-}}
+}
 
-.embed-record-card .embed-stats {{
+.embed-record-card .embed-stats {
     display: block;
     margin-top: 0.25rem;
-}}
+}
 
 /* Generic record fields */
-.embed-fields {{
+.embed-fields {
     display: block;
     margin-top: 0.5rem;
     font-family: var(--font-ui);
     font-size: 0.85rem;
     color: var(--color-muted);
-}}
+}
 
-.embed-field {{
+.embed-field {
     display: block;
     margin-top: 0.25rem;
-}}
+}
 
 /* Nested fields get indentation */
-.embed-fields .embed-fields {{
+.embed-fields .embed-fields {
     display: block;
     margin-top: 0.5rem;
     margin-inline-start: 1rem;
     padding-inline-start: 0.5rem;
     border-inline-start: 1px solid var(--color-border);
-}}
+}
 
 /* Type label inside fields should be block with spacing */
-.embed-fields > .embed-author-handle {{
+.embed-fields > .embed-author-handle {
     display: block;
     margin-bottom: 0.25rem;
-}}
+}
 
-.embed-field-name {{
+.embed-field-name {
     color: var(--color-subtle);
-}}
+}
 
-.embed-field-number {{
+.embed-field-number {
     color: var(--color-tertiary);
-}}
+}
 
-.embed-field-date {{
+.embed-field-date {
     color: var(--color-muted);
-}}
+}
 
-.embed-field-count {{
+.embed-field-count {
     color: var(--color-muted);
     font-style: italic;
-}}
+}
 
-.embed-field-bool-true {{
+.embed-field-bool-true {
     color: var(--color-success);
-}}
+}
 
-.embed-field-bool-false {{
+.embed-field-bool-false {
     color: var(--color-muted);
-}}
+}
 
 .embed-field-link,
-.embed-field-aturi {{
+.embed-field-aturi {
     color: var(--color-link);
     text-decoration: none;
-}}
+}
 
 .embed-field-link:hover,
-.embed-field-aturi:hover {{
+.embed-field-aturi:hover {
     text-decoration: underline;
-}}
+}
 
-.embed-field-did {{
+.embed-field-did {
     font-family: var(--font-mono);
     font-size: 0.9em;
-}}
+}
 
 .embed-field-did .did-scheme,
-.embed-field-did .did-separator {{
+.embed-field-did .did-separator {
     color: var(--color-muted);
-}}
+}
 
-.embed-field-did .did-method {{
+.embed-field-did .did-method {
     color: var(--color-tertiary);
-}}
+}
 
-.embed-field-did .did-identifier {{
+.embed-field-did .did-identifier {
     color: var(--color-text);
-}}
+}
 
-.embed-field-nsid {{
+.embed-field-nsid {
     color: var(--color-secondary);
-}}
+}
 
-.embed-field-handle {{
+.embed-field-handle {
     color: var(--color-link);
-}}
+}
 
 /* AT URI highlighting */
-.aturi-scheme {{
+.aturi-scheme {
     color: var(--color-muted);
-}}
+}
 
-.aturi-slash {{
+.aturi-slash {
     color: var(--color-muted);
-}}
+}
 
-.aturi-authority {{
+.aturi-authority {
     color: var(--color-link);
-}}
+}
 
-.aturi-collection {{
+.aturi-collection {
     color: var(--color-secondary);
-}}
+}
 
-.aturi-rkey {{
+.aturi-rkey {
     color: var(--color-tertiary);
-}}
+}
 
 /* Generic AT Protocol record embed */
-.atproto-record > .embed-author-handle {{
+.atproto-record > .embed-author-handle {
     display: block;
     margin-bottom: 0.25rem;
-}}
+}
 
-.atproto-record > .embed-author-name {{
+.atproto-record > .embed-author-name {
     display: block;
     margin-bottom: 0.5rem;
-}}
+}
 
-.atproto-record > .embed-content {{
+.atproto-record > .embed-content {
     margin-bottom: 0.5rem;
-}}
+}
 
 /* Notebook entry embed - full width, expandable */
-.atproto-entry {{
+.atproto-entry {
     max-width: none;
     width: 100%;
     margin: 1.5rem 0;
@@ -969,20 +1070,20 @@ a.embed-author-name:hover {{
     border-inline-start: 1px solid var(--color-border);
     box-shadow: none;
     overflow: hidden;
-}}
+}
 
-.atproto-entry:hover {{
+.atproto-entry:hover {
     border-inline-start-color: var(--color-border);
-}}
+}
 
-@media (prefers-color-scheme: dark) {{
-    .atproto-entry {{
+@media (prefers-color-scheme: dark) {
+    .atproto-entry {
         border: 1px solid var(--color-border);
         border-inline-start: 1px solid var(--color-border);
-    }}
-}}
+    }
+}
 
-.embed-entry-header {{
+.embed-entry-header {
     display: flex;
     flex-wrap: wrap;
     align-items: baseline;
@@ -990,39 +1091,39 @@ a.embed-author-name:hover {{
     padding: 0.75rem 1rem;
     background: var(--color-overlay);
     border-bottom: 1px solid var(--color-border);
-}}
+}
 
-.embed-entry-title {{
+.embed-entry-title {
     font-size: 1.1em;
     font-weight: 600;
     color: var(--color-text);
-}}
+}
 
-.embed-entry-author {{
+.embed-entry-author {
     font-size: 0.85em;
     color: var(--color-muted);
-}}
+}
 
 /* Hidden checkbox for expand/collapse */
-.embed-entry-toggle {{
+.embed-entry-toggle {
     display: none;
-}}
+}
 
 /* Content wrapper - scrollable when collapsed */
-.embed-entry-content {{
+.embed-entry-content {
     max-height: 30rem;
     overflow-y: auto;
     padding: 1rem;
     transition: max-height 0.3s ease;
-}}
+}
 
 /* When checkbox is checked, expand fully */
-.embed-entry-toggle:checked ~ .embed-entry-content {{
+.embed-entry-toggle:checked ~ .embed-entry-content {
     max-height: none;
-}}
+}
 
 /* Expand/collapse button */
-.embed-entry-expand {{
+.embed-entry-expand {
     display: block;
     width: 100%;
     padding: 0.5rem;
@@ -1034,83 +1135,83 @@ a.embed-author-name:hover {{
     border-top: 1px solid var(--color-border);
     cursor: pointer;
     user-select: none;
-}}
+}
 
-.embed-entry-expand:hover {{
+.embed-entry-expand:hover {
     color: var(--color-text);
     background: var(--color-surface);
-}}
+}
 
 /* Toggle button text */
-.embed-entry-expand::before {{
+.embed-entry-expand::before {
     content: "Expand ↓";
-}}
+}
 
-.embed-entry-toggle:checked ~ .embed-entry-expand::before {{
+.embed-entry-toggle:checked ~ .embed-entry-expand::before {
     content: "Collapse ↑";
-}}
+}
 
 /* Hide expand button if content doesn't overflow (via JS class) */
-.atproto-entry.no-overflow .embed-entry-expand {{
+.atproto-entry.no-overflow .embed-entry-expand {
     display: none;
-}}
+}
 
 /* Horizontal Rule */
-hr {{
+hr {
     border: none;
     border-top: 2px solid var(--color-border);
     margin: 2rem 0;
-}}
+}
 
 /* Tablet and mobile responsiveness */
-@media (max-width: 900px) {{
-    .notebook-content {{
+@media (max-width: 900px) {
+    .notebook-content {
         padding: 1.5rem 1rem;
         max-width: 100%;
-    }}
+    }
 
-    h1 {{ font-size: 1.85rem; }}
-    h2 {{ font-size: 1.4rem; }}
-    h3 {{ font-size: 1.2rem; }}
+    h1 { font-size: 1.85rem; }
+    h2 { font-size: 1.4rem; }
+    h3 { font-size: 1.2rem; }
 
-    blockquote {{
+    blockquote {
         margin-inline-start: 0;
         margin-inline-end: 0;
-    }}
-}}
+    }
+}
 
 /* Small mobile phones */
-@media (max-width: 480px) {{
-    .notebook-content {{
+@media (max-width: 480px) {
+    .notebook-content {
         padding: 1rem 0.75rem;
-    }}
+    }
 
-    h1 {{ font-size: 1.65rem; }}
-    h2 {{ font-size: 1.3rem; }}
-    h3 {{ font-size: 1.1rem; }}
+    h1 { font-size: 1.65rem; }
+    h2 { font-size: 1.3rem; }
+    h3 { font-size: 1.1rem; }
 
-    blockquote {{
+    blockquote {
         padding-inline-start: 0.75rem;
         padding-inline-end: 0.75rem;
-    }}
-}}
+    }
+}
 
 /* Leaflet document embeds */
-.atproto-leaflet {{
+.atproto-leaflet {
     max-width: none;
     width: 100%;
     margin: 1rem 0;
-}}
+}
 
-.leaflet-document {{
+.leaflet-document {
     display: block;
-}}
+}
 
-.leaflet-text {{
+.leaflet-text {
     margin: 0.5rem 0;
-}}
+}
 
-.leaflet-button {{
+.leaflet-button {
     display: inline-block;
     padding: 0.5rem 1rem;
     background: var(--color-primary);
@@ -1118,61 +1219,90 @@ hr {{
     text-decoration: none;
     border-radius: 4px;
     margin: 0.5rem 0;
-}}
+}
 
-.leaflet-button:hover {{
+.leaflet-button:hover {
     opacity: 0.9;
-}}
+}
 
 /* Alignment utilities */
-.align-center {{ text-align: center; }}
-.align-right {{ text-align: right; }}
-.align-justify {{ text-align: justify; }}
-"#,
-        // Light mode colours
-        light.base,
-        light.surface,
-        light.overlay,
-        light.text,
-        light.muted,
-        light.subtle,
-        light.emphasis,
-        light.primary,
-        light.secondary,
-        light.tertiary,
-        light.error,
-        light.warning,
-        light.success,
-        light.border,
-        light.link,
-        light.highlight,
-        // Fonts and spacing
-        body,
-        heading,
-        monospace,
-        spacing.base_size,
-        spacing.line_height,
-        spacing.scale,
-        // Dark mode colours
-        dark.base,
-        dark.surface,
-        dark.overlay,
-        dark.text,
-        dark.muted,
-        dark.subtle,
-        dark.emphasis,
-        dark.primary,
-        dark.secondary,
-        dark.tertiary,
-        dark.error,
-        dark.warning,
-        dark.success,
-        dark.border,
-        dark.link,
-        dark.highlight,
-    )
+.align-center { text-align: center; }
+.align-right { text-align: right; }
+.align-justify { text-align: justify; }
+"#;
+
+/// Print stylesheet: pagination hints and ink-friendly output.
+///
+/// Wrapped in `@media print` so it is safe both as a `media="print"`
+/// linked stylesheet and inlined into standalone print views. Not themed:
+/// print output always uses black text on white paper.
+pub const PRINT_CSS: &str = r#"@page {
+    margin: 2cm;
 }
 
+@media print {
+    body {
+        background: #fff !important;
+        color: #000;
+    }
+
+    .notebook-content {
+        max-width: none;
+        box-shadow: none;
+        border: none;
+    }
+
+    /* Keep headings with the content that follows them, and avoid
+       splitting self-contained blocks across pages. */
+    h1, h2, h3, h4, h5, h6 {
+        break-after: avoid;
+    }
+
+    figure, blockquote, pre, table, .wvc-codeblock, .atproto-embed, .sidenote {
+        break-inside: avoid;
+    }
+
+    img {
+        max-width: 100% !important;
+    }
+
+    /* Paper has no cursor: expand external link targets after the link
+       text so they survive printing. */
+    .notebook-content a[href^="http"]::after {
+        content: " (" attr(href) ")";
+        font-size: 0.8em;
+        color: #444;
+        word-break: break-all;
+    }
+
+    /* Interactive chrome is useless on paper. */
+    .wvc-code-copy,
+    .margin-toggle,
+    .embed-entry-toggle,
+    .embed-entry-expand,
+    nav {
+        display: none !important;
+    }
+
+    /* Collapsed embeds should print in full. */
+    .embed-entry-content {
+        max-height: none !important;
+        overflow: visible !important;
+    }
+}
+"#;
+
+/// Generate the full base stylesheet for a theme: reset, theme variables
+/// and structural rules.
+pub fn generate_base_css(theme: &ResolvedTheme) -> String {
+    let mut css = String::from(CSS_RESET);
+    css.push_str(&generate_theme_css(theme));
+    css.push('\n');
+    css.push_str(BASE_STRUCTURE_CSS);
+    css
+}
+
+#[cfg(feature = "syntax-css")]
 async fn load_syntect_dark_theme(
     code_theme: &ThemeDarkCodeTheme<'_>,
 ) -> miette::Result<syntect::highlighting::Theme> {
@@ -1234,6 +1364,7 @@ async fn load_syntect_dark_theme(
     }
 }
 
+#[cfg(feature = "syntax-css")]
 async fn load_syntect_light_theme(
     code_theme: &ThemeLightCodeTheme<'_>,
 ) -> miette::Result<syntect::highlighting::Theme> {
@@ -1295,6 +1426,51 @@ async fn load_syntect_light_theme(
     }
 }
 
+/// Prefix every top-level selector in `css` with `scope`.
+///
+/// Used to re-emit the syntect-generated highlight rules under a
+/// `:root[data-theme]` ancestor, so an explicit theme choice overrides the
+/// `prefers-color-scheme` default (syntect emits flat rules and has no
+/// notion of scoping itself). Only handles the flat `selector { ... }`
+/// output syntect produces; nested at-rules are passed through untouched.
+#[cfg(feature = "syntax-css")]
+fn scope_selectors(css: &str, scope: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut depth = 0usize;
+    for line in css.lines() {
+        let trimmed = line.trim_start();
+        // Grouped selectors span several comma-terminated lines; every one
+        // needs the scope, or the unscoped members would still match at
+        // full strength outside the forced theme.
+        let is_selector = depth == 0
+            && (trimmed.ends_with('{') || trimmed.ends_with(','))
+            && !trimmed.starts_with('@')
+            && !trimmed.starts_with("/*")
+            && !trimmed.starts_with('*');
+        if is_selector {
+            result.push_str(scope);
+            result.push(' ');
+            result.push_str(trimmed);
+        } else {
+            result.push_str(line);
+        }
+        depth += line.matches('{').count();
+        depth = depth.saturating_sub(line.matches('}').count());
+        result.push('\n');
+    }
+    result
+}
+
+/// Append the attribute-scoped copies of the light and dark highlight rules.
+#[cfg(feature = "syntax-css")]
+fn append_data_theme_syntax_css(result: &mut String, light_css: &str, dark_css: &str) {
+    result.push_str("\n/* Syntax highlighting - explicit preference via `data-theme` */\n");
+    result.push_str(&scope_selectors(light_css, ":root[data-theme=\"light\"]"));
+    result.push('\n');
+    result.push_str(&scope_selectors(dark_css, ":root[data-theme=\"dark\"]"));
+}
+
+#[cfg(feature = "syntax-css")]
 pub async fn generate_syntax_css(theme: &ResolvedTheme<'_>) -> miette::Result<String> {
     // Load both themes
     let dark_syntect_theme = load_syntect_dark_theme(&theme.dark_code_theme).await?;
@@ -1326,10 +1502,12 @@ pub async fn generate_syntax_css(theme: &ResolvedTheme<'_>) -> miette::Result<St
     result.push_str("@media (prefers-color-scheme: dark) {\n");
     result.push_str(&dark_css);
     result.push_str("}\n");
+    append_data_theme_syntax_css(&mut result, &light_css, &dark_css);
 
     Ok(result)
 }
 
+#[cfg(feature = "syntax-css")]
 pub fn generate_default_css() -> miette::Result<String> {
     let mut theme_set = ThemeSet::load_defaults();
     let rose_pine = {
@@ -1374,6 +1552,113 @@ pub fn generate_default_css() -> miette::Result<String> {
     result.push_str("@media (prefers-color-scheme: dark) {\n");
     result.push_str(&dark_css);
     result.push_str("}\n");
+    append_data_theme_syntax_css(&mut result, &light_css, &dark_css);
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::default_resolved_theme;
+
+    /// The variable names embedders are allowed to rely on.
+    const CONTRACT_VARIABLES: &[&str] = &[
+        "--color-base",
+        "--color-surface",
+        "--color-overlay",
+        "--color-text",
+        "--color-muted",
+        "--color-subtle",
+        "--color-emphasis",
+        "--color-primary",
+        "--color-secondary",
+        "--color-tertiary",
+        "--color-error",
+        "--color-warning",
+        "--color-success",
+        "--color-border",
+        "--color-link",
+        "--color-highlight",
+        "--font-body",
+        "--font-heading",
+        "--font-mono",
+        "--font-ui",
+        "--spacing-base",
+        "--spacing-line-height",
+        "--spacing-scale",
+    ];
+
+    #[test]
+    fn test_theme_css_defines_all_contract_variables() {
+        let css = generate_theme_css(&default_resolved_theme());
+        for var in CONTRACT_VARIABLES {
+            assert!(
+                css.contains(&format!("{}:", var)),
+                "theme css missing contract variable {}",
+                var
+            );
+        }
+    }
+
+    #[test]
+    fn test_base_css_only_uses_contract_variables() {
+        // Structural rules must reference variables from the contract;
+        // anything else would silently fall back to an unstyled value.
+        for part in BASE_STRUCTURE_CSS.split("var(").skip(1) {
+            let name = part.split([')', ',']).next().unwrap().trim();
+            assert!(
+                CONTRACT_VARIABLES.contains(&name),
+                "structural css references undocumented variable {}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_base_css_layers_variables_and_structure() {
+        let css = generate_base_css(&default_resolved_theme());
+        assert!(css.contains("--color-base:"));
+        assert!(css.contains(".notebook-content"));
+        assert!(css.contains(".atproto-embed"));
+    }
+
+    #[test]
+    fn test_print_css_is_scoped_to_print_media() {
+        // Inlined into standalone pages, so everything except the @page
+        // rule must stay behind @media print or it would restyle screens.
+        let (page_rule, rest) = PRINT_CSS.split_once("}\n\n").unwrap();
+        assert!(page_rule.starts_with("@page {"));
+        assert!(rest.starts_with("@media print {"));
+        // Pagination hints and link expansion are the point of the sheet.
+        assert!(rest.contains("break-inside: avoid"));
+        assert!(rest.contains("attr(href)"));
+    }
+
+    #[test]
+    fn test_theme_css_emits_data_theme_overrides() {
+        let css = generate_theme_css(&default_resolved_theme());
+        assert!(css.contains(":root[data-theme=\"light\"]"));
+        assert!(css.contains(":root[data-theme=\"dark\"]"));
+        // The overrides must come after the media query to win the cascade.
+        let media = css.find("prefers-color-scheme").unwrap();
+        let forced = css.find(":root[data-theme=\"light\"]").unwrap();
+        assert!(forced > media);
+    }
+
+    #[cfg(feature = "syntax-css")]
+    #[test]
+    fn test_scope_selectors_prefixes_only_top_level_rules() {
+        let css = "/*\n * theme comment\n */\n\n.wvc-code {\n    color: #111;\n}\n.wvc-string,\n.wvc-comment {\n    color: #222;\n}\n";
+        let scoped = scope_selectors(css, ":root[data-theme=\"dark\"]");
+        assert!(scoped.contains(":root[data-theme=\"dark\"] .wvc-code {"));
+        // Every member of a selector group gets the scope, or the unscoped
+        // members would still apply outside the forced theme.
+        assert!(scoped.contains(
+            ":root[data-theme=\"dark\"] .wvc-string,\n:root[data-theme=\"dark\"] .wvc-comment {"
+        ));
+        // Declarations and comments are untouched.
+        assert!(scoped.contains("    color: #111;"));
+        assert!(scoped.contains("* theme comment"));
+    }
+}
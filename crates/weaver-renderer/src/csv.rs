@@ -0,0 +1,262 @@
+//! CSV/TSV embeds rendered as HTML tables.
+//!
+//! An Obsidian-style embed of a data file (`![[data.csv]]`) becomes a
+//! table instead of an iframe: the first record is the header row, the
+//! next [`DEFAULT_ROW_LIMIT`] records are the body, and a caption links
+//! to the raw file for readers who want the rest. Parsing streams one
+//! record at a time so a multi-megabyte export never has to fit in
+//! memory just to show its first hundred rows.
+
+use std::io::BufRead;
+use std::path::Path;
+
+/// Body rows rendered before the table is cut off with a download link.
+pub const DEFAULT_ROW_LIMIT: usize = 100;
+
+/// Whether a link destination points at a file this module can render.
+pub fn is_tabular_path(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    lower.ends_with(".csv") || lower.ends_with(".tsv")
+}
+
+/// Field delimiter implied by the file extension.
+fn delimiter_for_path(path: &str) -> char {
+    if path.to_ascii_lowercase().ends_with(".tsv") {
+        '\t'
+    } else {
+        ','
+    }
+}
+
+/// Renders a data file as a table, reading only as much as the row
+/// limit requires.
+pub fn render_table_file(
+    path: &Path,
+    row_limit: usize,
+    download_href: &str,
+) -> std::io::Result<String> {
+    let file = std::fs::File::open(path)?;
+    let delimiter = delimiter_for_path(&path.to_string_lossy());
+    render_table(
+        std::io::BufReader::new(file),
+        delimiter,
+        row_limit,
+        download_href,
+    )
+}
+
+/// Renders already-loaded contents as a table.
+///
+/// For callers that hold the text anyway (editors, inline embeds); the
+/// delimiter comes from `name` the same way [`render_table_file`]
+/// derives it from the path.
+pub fn render_table_str(
+    contents: &str,
+    name: &str,
+    row_limit: usize,
+    download_href: &str,
+) -> String {
+    render_table(
+        contents.as_bytes(),
+        delimiter_for_path(name),
+        row_limit,
+        download_href,
+    )
+    // Reading from an in-memory slice of valid UTF-8 cannot fail.
+    .expect("in-memory read failed")
+}
+
+fn render_table(
+    reader: impl BufRead,
+    delimiter: char,
+    row_limit: usize,
+    download_href: &str,
+) -> std::io::Result<String> {
+    let mut records = Records::new(reader, delimiter);
+    let mut html = String::from("<figure class=\"csv-embed\">\n<table>\n");
+
+    match records.next_record()? {
+        Some(header) => {
+            html.push_str("<thead>\n<tr>");
+            for cell in &header {
+                html.push_str("<th>");
+                html.push_str(&escape(cell));
+                html.push_str("</th>");
+            }
+            html.push_str("</tr>\n</thead>\n");
+        }
+        None => {
+            // An empty file still gets the figure so the download link
+            // shows where the (empty) data lives.
+            html.push_str("</table>\n");
+            push_caption(&mut html, false, download_href);
+            html.push_str("</figure>");
+            return Ok(html);
+        }
+    }
+
+    html.push_str("<tbody>\n");
+    let mut rows = 0;
+    let mut truncated = false;
+    while let Some(record) = records.next_record()? {
+        if rows == row_limit {
+            truncated = true;
+            break;
+        }
+        html.push_str("<tr>");
+        for cell in &record {
+            html.push_str("<td>");
+            html.push_str(&escape(cell));
+            html.push_str("</td>");
+        }
+        html.push_str("</tr>\n");
+        rows += 1;
+    }
+    html.push_str("</tbody>\n</table>\n");
+
+    push_caption(&mut html, truncated, download_href);
+    html.push_str("</figure>");
+    Ok(html)
+}
+
+fn push_caption(html: &mut String, truncated: bool, download_href: &str) {
+    html.push_str("<figcaption>");
+    if truncated {
+        html.push_str("table truncated &middot; ");
+    }
+    html.push_str("<a href=\"");
+    html.push_str(&escape(download_href));
+    html.push_str("\" download>download full data</a></figcaption>\n");
+}
+
+/// Streaming record reader over delimiter-separated text.
+///
+/// Reads line by line; a record only spans multiple reads when a quoted
+/// field contains a literal newline, so memory use is bounded by the
+/// longest single record rather than the file size.
+struct Records<R: BufRead> {
+    reader: R,
+    delimiter: char,
+    line: String,
+}
+
+impl<R: BufRead> Records<R> {
+    fn new(reader: R, delimiter: char) -> Self {
+        Self {
+            reader,
+            delimiter,
+            line: String::new(),
+        }
+    }
+
+    fn next_record(&mut self) -> std::io::Result<Option<Vec<String>>> {
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut in_quotes = false;
+        let mut saw_input = false;
+
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                break;
+            }
+            saw_input = true;
+
+            let mut chars = self.line.trim_end_matches(['\r', '\n']).chars().peekable();
+            while let Some(c) = chars.next() {
+                if in_quotes {
+                    if c == '"' {
+                        if chars.peek() == Some(&'"') {
+                            // Doubled quote is the CSV escape for one.
+                            chars.next();
+                            field.push('"');
+                        } else {
+                            in_quotes = false;
+                        }
+                    } else {
+                        field.push(c);
+                    }
+                } else if c == self.delimiter {
+                    fields.push(std::mem::take(&mut field));
+                } else if c == '"' && field.is_empty() {
+                    in_quotes = true;
+                } else {
+                    field.push(c);
+                }
+            }
+
+            if in_quotes {
+                // The quoted field continues onto the next line; keep
+                // the newline the trim above stripped.
+                field.push('\n');
+            } else {
+                break;
+            }
+        }
+
+        if !saw_input {
+            return Ok(None);
+        }
+        fields.push(field);
+        Ok(Some(fields))
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_header_and_rows() {
+        let html = render_table_str("name,score\nalice,3\nbob,5\n", "data.csv", 10, "data.csv");
+        assert!(html.contains("<th>name</th><th>score</th>"));
+        assert!(html.contains("<td>alice</td><td>3</td>"));
+        assert!(html.contains("href=\"data.csv\" download"));
+        assert!(!html.contains("truncated"));
+    }
+
+    #[test]
+    fn test_quoted_fields_keep_delimiters_and_newlines() {
+        let html = render_table_str(
+            "a,b\n\"one, two\",\"line\nbreak\"\n",
+            "data.csv",
+            10,
+            "data.csv",
+        );
+        assert!(html.contains("<td>one, two</td>"));
+        assert!(html.contains("<td>line\nbreak</td>"));
+    }
+
+    #[test]
+    fn test_row_limit_truncates() {
+        let mut contents = String::from("n\n");
+        for i in 0..5 {
+            contents.push_str(&format!("{}\n", i));
+        }
+        let html = render_table_str(&contents, "data.csv", 3, "data.csv");
+        assert!(html.contains("<td>2</td>"));
+        assert!(!html.contains("<td>3</td>"));
+        assert!(html.contains("table truncated"));
+    }
+
+    #[test]
+    fn test_tsv_uses_tab_delimiter() {
+        let html = render_table_str("a\tb\n1\t2\n", "data.tsv", 10, "data.tsv");
+        assert!(html.contains("<th>a</th><th>b</th>"));
+        assert!(html.contains("<td>1</td><td>2</td>"));
+    }
+
+    #[test]
+    fn test_cells_are_escaped() {
+        let html = render_table_str("x\n<script>\n", "data.csv", 10, "data.csv");
+        assert!(html.contains("<td>&lt;script&gt;</td>"));
+        assert!(!html.contains("<script>"));
+    }
+}
@@ -0,0 +1,346 @@
+//! Page-level `weaver:` directives.
+//!
+//! Weaver blocks normally carry classes and attributes that apply to the
+//! next block element (`{.aside, width: 300px}`). A block whose text
+//! starts with `weaver:` is a directive instead — an instruction to the
+//! renderer rather than styling:
+//!
+//! - `{weaver: toc}` — emit a table of contents at this position.
+//! - `{weaver: gallery}` — lay the next block out as an image gallery.
+//! - `{weaver: include entry=at://...}` — embed another entry here.
+//! - `{weaver: hidden}` — omit the next block from rendered output.
+//!
+//! Directives are resolved through a [`DirectiveRegistry`], so pipelines
+//! can register their own. Unknown directives degrade to nothing: they
+//! never leak onto the page as classes, and the editor's visible-syntax
+//! mode shows them as plain weaver-block syntax so authors can still see
+//! and edit them.
+
+use std::collections::HashMap;
+
+use markdown_weaver::{CowStr, Event, Parser, Tag, TagEnd, WeaverAttributes};
+use markdown_weaver_escape::escape_html;
+
+use crate::default_md_options;
+
+/// Prefix that marks a weaver block as a directive.
+pub const DIRECTIVE_PREFIX: &str = "weaver:";
+
+/// Placeholder emitted for `weaver: toc`, filled by [`fill_toc`].
+pub const TOC_PLACEHOLDER: &str = r#"<nav class="weaver-toc" data-weaver-toc></nav>"#;
+
+/// A parsed directive: lowercased name plus `key=value` arguments.
+///
+/// Bare tokens after the name become flag arguments with an empty value,
+/// so `weaver: include entry=at://... inline` parses to name `include`
+/// with args `[("entry", "at://..."), ("inline", "")]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedDirective {
+    /// Directive name (`toc`, `gallery`, ...), lowercased.
+    pub name: String,
+    /// Arguments in source order.
+    pub args: Vec<(String, String)>,
+}
+
+impl ParsedDirective {
+    /// Look up an argument value by key.
+    pub fn arg(&self, key: &str) -> Option<&str> {
+        self.args
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Whether a weaver block's text is a directive rather than attrs.
+pub fn is_directive(text: &str) -> bool {
+    text.trim_start().starts_with(DIRECTIVE_PREFIX)
+}
+
+/// Parse directive text (`weaver: name key=value ...`).
+///
+/// Returns `None` when the text is not a directive or names nothing.
+pub fn parse_directive(text: &str) -> Option<ParsedDirective> {
+    let rest = text.trim().strip_prefix(DIRECTIVE_PREFIX)?;
+    let mut tokens = rest.split_whitespace();
+    let name = tokens.next()?.to_ascii_lowercase();
+    let args = tokens
+        .map(|token| match token.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.trim_matches('"').to_string()),
+            None => (token.to_string(), String::new()),
+        })
+        .collect();
+    Some(ParsedDirective { name, args })
+}
+
+/// What a directive does to the surrounding document.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DirectiveEffect {
+    /// Emit HTML at the directive's position.
+    Emit(String),
+    /// Apply classes/attributes to the next block element, using the same
+    /// wrapper mechanism as plain weaver blocks.
+    Wrap(WeaverAttributes<'static>),
+    /// Suppress the next block element (and everything inside it).
+    HideNextBlock,
+}
+
+/// Handler resolving a parsed directive to its effect.
+///
+/// Returning `None` means the directive could not be applied (e.g. a
+/// required argument is missing); it then degrades to nothing.
+pub type DirectiveHandler = Box<dyn Fn(&ParsedDirective) -> Option<DirectiveEffect> + Send + Sync>;
+
+/// Registry mapping directive names to handlers.
+///
+/// [`Default`] registers the built-in directives (`toc`, `gallery`,
+/// `include`, `hidden`); pipelines can [`register`](Self::register) more
+/// or start from [`empty`](Self::empty).
+pub struct DirectiveRegistry {
+    handlers: HashMap<String, DirectiveHandler>,
+}
+
+impl DirectiveRegistry {
+    /// Registry with no directives at all.
+    pub fn empty() -> Self {
+        Self {
+            handlers: HashMap::new(),
+        }
+    }
+
+    /// Register a handler, replacing any existing one for `name`.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        handler: impl Fn(&ParsedDirective) -> Option<DirectiveEffect> + Send + Sync + 'static,
+    ) {
+        self.handlers.insert(name.into(), Box::new(handler));
+    }
+
+    /// Parse weaver-block text and resolve it through the registry.
+    ///
+    /// Returns `None` for non-directive text, unknown names, and handlers
+    /// that decline — all of which degrade to nothing.
+    pub fn apply(&self, text: &str) -> Option<DirectiveEffect> {
+        let directive = parse_directive(text)?;
+        let handler = self.handlers.get(&directive.name)?;
+        handler(&directive)
+    }
+}
+
+impl Default for DirectiveRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("toc", |_| {
+            Some(DirectiveEffect::Emit(TOC_PLACEHOLDER.to_string()))
+        });
+        registry.register("gallery", |_| {
+            Some(DirectiveEffect::Wrap(WeaverAttributes {
+                classes: vec![CowStr::from("weaver-gallery")],
+                attrs: vec![],
+            }))
+        });
+        registry.register("hidden", |_| Some(DirectiveEffect::HideNextBlock));
+        registry.register("include", |directive| {
+            // Fetching the entry is the embed pipeline's job; emit a
+            // placeholder carrying the URI for hydration or a post-pass.
+            let entry = directive.arg("entry").filter(|e| !e.is_empty())?;
+            let mut escaped = String::new();
+            escape_html(&mut escaped, entry).ok()?;
+            Some(DirectiveEffect::Emit(format!(
+                r#"<div class="weaver-include" data-weaver-include="{}"></div>"#,
+                escaped
+            )))
+        });
+        registry
+    }
+}
+
+/// Whether a tag opens a block element that `weaver: hidden` can suppress.
+pub fn starts_block(tag: &Tag<'_>) -> bool {
+    matches!(
+        tag,
+        Tag::Paragraph(_)
+            | Tag::Heading { .. }
+            | Tag::BlockQuote(_)
+            | Tag::CodeBlock(_)
+            | Tag::List(_)
+            | Tag::Table(_)
+            | Tag::DefinitionList
+            | Tag::HtmlBlock
+    )
+}
+
+/// A heading captured for table-of-contents rendering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TocEntry {
+    /// Heading level, 1–6.
+    pub level: u8,
+    /// Plain heading text.
+    pub text: String,
+    /// Anchor: the heading's explicit id when it has one, otherwise a
+    /// generated slug.
+    pub slug: String,
+}
+
+/// Collect headings from markdown source in one parser pass.
+pub fn collect_headings(markdown: &str) -> Vec<TocEntry> {
+    let mut entries = Vec::new();
+    let mut current: Option<TocEntry> = None;
+
+    for event in Parser::new_ext(markdown, default_md_options()) {
+        match event {
+            Event::Start(Tag::Heading { level, ref id, .. }) => {
+                current = Some(TocEntry {
+                    level: level as u8,
+                    text: String::new(),
+                    slug: id.as_ref().map(|i| i.to_string()).unwrap_or_default(),
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(mut entry) = current.take() {
+                    if entry.slug.is_empty() {
+                        entry.slug = slugify(&entry.text);
+                    }
+                    if !entry.text.trim().is_empty() {
+                        entries.push(entry);
+                    }
+                }
+            }
+            Event::Text(ref text) | Event::Code(ref text) => {
+                if let Some(entry) = current.as_mut() {
+                    entry.text.push_str(text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    entries
+}
+
+/// Render collected headings as a table of contents.
+///
+/// Produces a flat list with `toc-level-N` classes; indentation is left
+/// to CSS so nesting quirks in the source (skipped levels) cannot break
+/// the markup.
+pub fn render_toc(entries: &[TocEntry]) -> String {
+    let mut html = String::from(r#"<nav class="weaver-toc" aria-label="table of contents"><ol>"#);
+    html.push('\n');
+    for entry in entries {
+        html.push_str(&format!("<li class=\"toc-level-{}\"><a href=\"#", entry.level));
+        let _ = escape_html(&mut html, &entry.slug);
+        html.push_str("\">");
+        let _ = escape_html(&mut html, &entry.text);
+        html.push_str("</a></li>\n");
+    }
+    html.push_str("</ol></nav>");
+    html
+}
+
+/// Replace `weaver: toc` placeholders in rendered HTML with a real TOC.
+pub fn fill_toc(html: &str, entries: &[TocEntry]) -> String {
+    if !html.contains(TOC_PLACEHOLDER) {
+        return html.to_owned();
+    }
+    html.replace(TOC_PLACEHOLDER, &render_toc(entries))
+}
+
+/// Generate a GitHub-style slug from heading text.
+fn slugify(text: &str) -> String {
+    let mut slug = String::new();
+    let mut last_dash = true; // Suppress a leading dash.
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+            last_dash = false;
+        } else if !last_dash {
+            slug.push('-');
+            last_dash = true;
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_directive_name_and_args() {
+        let directive = parse_directive("weaver: include entry=at://did:plc:abc/x/1 inline")
+            .expect("should parse");
+        assert_eq!(directive.name, "include");
+        assert_eq!(directive.arg("entry"), Some("at://did:plc:abc/x/1"));
+        assert_eq!(directive.arg("inline"), Some(""));
+        assert_eq!(directive.arg("missing"), None);
+    }
+
+    #[test]
+    fn test_non_directive_text_is_rejected() {
+        assert!(parse_directive(".aside, width: 300px").is_none());
+        assert!(!is_directive(".aside"));
+        assert!(is_directive("  weaver: toc"));
+        // Prefix with no name is not a directive.
+        assert!(parse_directive("weaver: ").is_none());
+    }
+
+    #[test]
+    fn test_builtin_effects() {
+        let registry = DirectiveRegistry::default();
+        assert!(matches!(
+            registry.apply("weaver: toc"),
+            Some(DirectiveEffect::Emit(html)) if html == TOC_PLACEHOLDER
+        ));
+        assert!(matches!(
+            registry.apply("weaver: hidden"),
+            Some(DirectiveEffect::HideNextBlock)
+        ));
+        match registry.apply("weaver: gallery") {
+            Some(DirectiveEffect::Wrap(attrs)) => {
+                assert_eq!(attrs.classes, vec![CowStr::from("weaver-gallery")]);
+            }
+            other => panic!("expected wrap effect, got {:?}", other),
+        }
+        // Include without its required argument degrades to nothing.
+        assert!(registry.apply("weaver: include").is_none());
+        // Unknown directives degrade to nothing.
+        assert!(registry.apply("weaver: sparkles").is_none());
+    }
+
+    #[test]
+    fn test_include_escapes_uri() {
+        let registry = DirectiveRegistry::default();
+        match registry.apply(r#"weaver: include entry="at://did/x/1<>""#) {
+            Some(DirectiveEffect::Emit(html)) => {
+                assert!(html.contains("data-weaver-include=\"at://did/x/1&lt;&gt;\""));
+            }
+            other => panic!("expected emit effect, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_collect_headings_and_slugs() {
+        let entries =
+            collect_headings("# First Heading\n\ntext\n\n## Sub: Topic!\n\n## Second {#custom}\n");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].slug, "first-heading");
+        assert_eq!(entries[1].slug, "sub-topic");
+        assert_eq!(entries[2].slug, "custom");
+        assert_eq!(entries[1].level, 2);
+    }
+
+    #[test]
+    fn test_fill_toc_replaces_placeholder() {
+        let entries = collect_headings("# One\n\n## Two\n");
+        let page = format!("<article>{}</article>", TOC_PLACEHOLDER);
+        let filled = fill_toc(&page, &entries);
+        assert!(filled.contains(r##"<a href="#one">One</a>"##));
+        assert!(filled.contains("toc-level-2"));
+        assert!(!filled.contains("data-weaver-toc"));
+    }
+}
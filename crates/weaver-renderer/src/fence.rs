@@ -0,0 +1,104 @@
+//! Fence info-string parsing.
+//!
+//! Code fences can carry more than a language: `rust title="main.rs"
+//! {1,3-5}` names the block (usually a filename) and marks line ranges to
+//! highlight. Parsing lives outside the syntax-highlighting feature so
+//! every writer sees the same interpretation of the info string, whether
+//! or not it can act on it.
+
+/// Parsed fence info string, e.g. `rust title="main.rs" {1,3-5}`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FenceInfo {
+    pub lang: Option<String>,
+    pub title: Option<String>,
+    /// Inclusive 1-based line ranges.
+    pub highlight_lines: Vec<(usize, usize)>,
+}
+
+impl FenceInfo {
+    /// Whether the fence asked for anything beyond plain highlighting.
+    pub fn has_decorations(&self) -> bool {
+        self.title.is_some() || !self.highlight_lines.is_empty()
+    }
+
+    /// Whether a 1-based line number falls in a highlighted range.
+    pub fn is_highlighted(&self, line: usize) -> bool {
+        self.highlight_lines
+            .iter()
+            .any(|(start, end)| (*start..=*end).contains(&line))
+    }
+}
+
+/// Parses a fence info string into its language and extensions.
+///
+/// Unrecognized fragments are ignored rather than rejected, so authored
+/// syntax from other site generators degrades to a plain language tag.
+pub fn parse_fence_info(info: &str) -> FenceInfo {
+    let info = info.trim();
+    let mut parsed = FenceInfo::default();
+
+    let first = info.split_whitespace().next().unwrap_or("");
+    if !first.is_empty() && !first.starts_with('{') && !first.contains('=') {
+        parsed.lang = Some(first.to_string());
+    }
+
+    if let Some(idx) = info.find("title=\"") {
+        let value = &info[idx + "title=\"".len()..];
+        if let Some(end) = value.find('"') {
+            let title = &value[..end];
+            if !title.is_empty() {
+                parsed.title = Some(title.to_string());
+            }
+        }
+    }
+
+    if let (Some(open), Some(close)) = (info.find('{'), info.find('}')) {
+        if open < close {
+            for part in info[open + 1..close].split(',') {
+                let part = part.trim();
+                if part.is_empty() {
+                    continue;
+                }
+                let range = match part.split_once('-') {
+                    Some((start, end)) => start.trim().parse().ok().zip(end.trim().parse().ok()),
+                    None => part.parse().ok().map(|n| (n, n)),
+                };
+                if let Some((start, end)) = range {
+                    if start >= 1 && end >= start {
+                        parsed.highlight_lines.push((start, end));
+                    }
+                }
+            }
+        }
+    }
+
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fence_info_full() {
+        let info = parse_fence_info("rust title=\"main.rs\" {1,3-5}");
+        assert_eq!(info.lang.as_deref(), Some("rust"));
+        assert_eq!(info.title.as_deref(), Some("main.rs"));
+        assert_eq!(info.highlight_lines, vec![(1, 1), (3, 5)]);
+        assert!(info.is_highlighted(4));
+        assert!(!info.is_highlighted(2));
+    }
+
+    #[test]
+    fn test_parse_fence_info_plain_lang() {
+        let info = parse_fence_info("python");
+        assert_eq!(info.lang.as_deref(), Some("python"));
+        assert!(!info.has_decorations());
+    }
+
+    #[test]
+    fn test_parse_fence_info_ignores_malformed_ranges() {
+        let info = parse_fence_info("rust {5-3,zzz,2}");
+        assert_eq!(info.highlight_lines, vec![(2, 2)]);
+    }
+}
@@ -0,0 +1,313 @@
+//! Jupyter notebook (.ipynb) ingestion.
+//!
+//! Converts a notebook's cells into the extended markdown the rest of the
+//! pipeline already understands: markdown cells pass through verbatim,
+//! code cells become fenced blocks tagged with the kernel language, and
+//! their outputs follow as plain fences (streams, results, errors) or
+//! extracted image attachments. The caller decides what to do with the
+//! attachments — the static site writes them next to the page, the
+//! publish pipeline uploads them as blobs like any other vault image.
+
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use serde::Deserialize;
+
+/// A notebook converted to markdown plus its binary cell outputs.
+#[derive(Debug, Clone)]
+pub struct ConvertedNotebook {
+    pub markdown: String,
+    /// Image outputs in cell order; `markdown` references them by name.
+    pub attachments: Vec<NotebookAttachment>,
+}
+
+/// One image output extracted from a code cell.
+#[derive(Debug, Clone)]
+pub struct NotebookAttachment {
+    /// Filename the markdown references, unique within the notebook.
+    pub name: String,
+    pub mime: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(thiserror::Error, Debug, miette::Diagnostic)]
+#[non_exhaustive]
+pub enum IpynbError {
+    #[error("not a Jupyter notebook: {0}")]
+    #[diagnostic(code(weaver_renderer::ipynb::parse))]
+    Parse(#[from] serde_json::Error),
+
+    #[error("unsupported notebook format version {0} (need 4 or later)")]
+    #[diagnostic(code(weaver_renderer::ipynb::version))]
+    UnsupportedVersion(u32),
+}
+
+/// Raw notebook structure; only the fields the conversion needs.
+#[derive(Deserialize)]
+struct RawNotebook {
+    cells: Vec<RawCell>,
+    nbformat: u32,
+    #[serde(default)]
+    metadata: RawMetadata,
+}
+
+#[derive(Deserialize, Default)]
+struct RawMetadata {
+    #[serde(default)]
+    language_info: Option<RawLanguageInfo>,
+    #[serde(default)]
+    kernelspec: Option<RawKernelSpec>,
+}
+
+#[derive(Deserialize)]
+struct RawLanguageInfo {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawKernelSpec {
+    language: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawCell {
+    cell_type: String,
+    #[serde(default)]
+    source: SourceText,
+    #[serde(default)]
+    outputs: Vec<RawOutput>,
+}
+
+#[derive(Deserialize)]
+struct RawOutput {
+    output_type: String,
+    #[serde(default)]
+    text: SourceText,
+    #[serde(default)]
+    data: std::collections::BTreeMap<String, serde_json::Value>,
+    #[serde(default)]
+    ename: Option<String>,
+    #[serde(default)]
+    evalue: Option<String>,
+}
+
+/// Notebook JSON stores text either as one string or as a line array.
+#[derive(Deserialize, Default)]
+#[serde(untagged)]
+enum SourceText {
+    #[default]
+    Empty,
+    One(String),
+    Lines(Vec<String>),
+}
+
+impl SourceText {
+    fn joined(&self) -> String {
+        match self {
+            SourceText::Empty => String::new(),
+            SourceText::One(s) => s.clone(),
+            SourceText::Lines(lines) => lines.concat(),
+        }
+    }
+}
+
+/// Image MIME types extracted as attachments, in preference order.
+const IMAGE_MIMES: &[(&str, &str)] = &[
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/svg+xml", "svg"),
+];
+
+/// Convert notebook JSON into markdown and extracted image attachments.
+pub fn convert_ipynb(source: &str) -> Result<ConvertedNotebook, IpynbError> {
+    let notebook: RawNotebook = serde_json::from_str(source)?;
+    if notebook.nbformat < 4 {
+        return Err(IpynbError::UnsupportedVersion(notebook.nbformat));
+    }
+
+    // Kernel language tags the code fences so syntax highlighting works.
+    let lang = notebook
+        .metadata
+        .language_info
+        .and_then(|l| l.name)
+        .or(notebook.metadata.kernelspec.and_then(|k| k.language))
+        .unwrap_or_default();
+
+    let mut markdown = String::new();
+    let mut attachments = Vec::new();
+
+    for cell in &notebook.cells {
+        match cell.cell_type.as_str() {
+            "markdown" => {
+                push_block(&mut markdown, cell.source.joined().trim_end());
+            }
+            "code" => {
+                let source = cell.source.joined();
+                if !source.trim().is_empty() {
+                    push_block(
+                        &mut markdown,
+                        &format!("```{}\n{}\n```", lang, source.trim_end()),
+                    );
+                }
+                for output in &cell.outputs {
+                    convert_output(output, &mut markdown, &mut attachments);
+                }
+            }
+            // Raw cells have no defined rendering; skip them like nbconvert
+            // does by default.
+            _ => {}
+        }
+    }
+
+    Ok(ConvertedNotebook {
+        markdown,
+        attachments,
+    })
+}
+
+fn convert_output(
+    output: &RawOutput,
+    markdown: &mut String,
+    attachments: &mut Vec<NotebookAttachment>,
+) {
+    match output.output_type.as_str() {
+        "stream" => {
+            let text = output.text.joined();
+            if !text.trim().is_empty() {
+                push_block(markdown, &format!("```\n{}\n```", text.trim_end()));
+            }
+        }
+        "execute_result" | "display_data" => {
+            // Prefer an image over its text fallback; repr strings of
+            // figure objects are noise once the figure itself is shown.
+            if let Some(attachment) = extract_image(output, attachments.len()) {
+                push_block(
+                    markdown,
+                    &format!("![output {}]({})", attachments.len() + 1, attachment.name),
+                );
+                attachments.push(attachment);
+            } else if let Some(text) = output.data.get("text/plain") {
+                let text = value_text(text);
+                if !text.trim().is_empty() {
+                    push_block(markdown, &format!("```\n{}\n```", text.trim_end()));
+                }
+            }
+        }
+        "error" => {
+            let name = output.ename.as_deref().unwrap_or("error");
+            let value = output.evalue.as_deref().unwrap_or("");
+            push_block(markdown, &format!("```\n{}: {}\n```", name, value));
+        }
+        _ => {}
+    }
+}
+
+/// Pull the first recognized image MIME out of an output's data bundle.
+fn extract_image(output: &RawOutput, index: usize) -> Option<NotebookAttachment> {
+    for (mime, ext) in IMAGE_MIMES {
+        let Some(value) = output.data.get(*mime) else {
+            continue;
+        };
+        // SVG arrives as text, rasters as base64; both may be line arrays.
+        let text = value_text(value);
+        let data = if *mime == "image/svg+xml" {
+            text.into_bytes()
+        } else {
+            // Notebook base64 payloads wrap lines; strip whitespace first.
+            let compact: String = text.split_whitespace().collect();
+            match BASE64.decode(compact) {
+                Ok(data) => data,
+                Err(_) => continue,
+            }
+        };
+        return Some(NotebookAttachment {
+            name: format!("output-{}.{}", index + 1, ext),
+            mime: (*mime).to_string(),
+            data,
+        });
+    }
+    None
+}
+
+/// Flatten a JSON string or string array into text.
+fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts
+            .iter()
+            .filter_map(|p| p.as_str())
+            .collect::<Vec<_>>()
+            .concat(),
+        _ => String::new(),
+    }
+}
+
+/// Append a block with a blank line separating it from the previous one.
+fn push_block(markdown: &mut String, block: &str) {
+    if block.is_empty() {
+        return;
+    }
+    if !markdown.is_empty() {
+        markdown.push_str("\n\n");
+    }
+    markdown.push_str(block);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converts_cells_in_order() {
+        let source = r##"{
+            "nbformat": 4,
+            "metadata": {"language_info": {"name": "python"}},
+            "cells": [
+                {"cell_type": "markdown", "source": ["# Title\n", "\n", "Intro.\n"]},
+                {"cell_type": "code", "source": "print(1 + 1)", "outputs": [
+                    {"output_type": "stream", "text": ["2\n"]}
+                ]}
+            ]
+        }"##;
+        let converted = convert_ipynb(source).unwrap();
+        assert_eq!(
+            converted.markdown,
+            "# Title\n\nIntro.\n\n```python\nprint(1 + 1)\n```\n\n```\n2\n```"
+        );
+        assert!(converted.attachments.is_empty());
+    }
+
+    #[test]
+    fn test_extracts_image_outputs() {
+        // 1x1 transparent PNG.
+        let png = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+        let source = format!(
+            r#"{{
+                "nbformat": 4,
+                "metadata": {{}},
+                "cells": [
+                    {{"cell_type": "code", "source": "plot()", "outputs": [
+                        {{"output_type": "display_data", "data": {{
+                            "image/png": "{png}",
+                            "text/plain": ["<Figure>"]
+                        }}}}
+                    ]}}
+                ]
+            }}"#
+        );
+        let converted = convert_ipynb(&source).unwrap();
+        assert_eq!(converted.attachments.len(), 1);
+        assert_eq!(converted.attachments[0].name, "output-1.png");
+        assert_eq!(converted.attachments[0].mime, "image/png");
+        assert!(converted.markdown.contains("![output 1](output-1.png)"));
+        // The text/plain repr must not also render.
+        assert!(!converted.markdown.contains("<Figure>"));
+    }
+
+    #[test]
+    fn test_rejects_old_format() {
+        let source = r#"{"nbformat": 3, "metadata": {}, "cells": []}"#;
+        assert!(matches!(
+            convert_ipynb(source),
+            Err(IpynbError::UnsupportedVersion(3))
+        ));
+    }
+}
@@ -24,17 +24,23 @@ use std::sync::LazyLock;
 use std::sync::RwLock;
 use std::task::Poll;
 
+pub mod a11y;
 pub mod atproto;
 pub mod base_html;
 #[cfg(feature = "syntax-highlighting")]
 pub mod code_pretty;
-#[cfg(feature = "syntax-css")]
 pub mod css;
+pub mod csv;
+pub mod directive;
 pub mod facet;
+pub mod fence;
+pub mod ipynb;
 pub mod leaflet;
 pub mod math;
+pub mod metadata;
 #[cfg(feature = "pckt")]
 pub mod pckt;
+pub mod sanitize;
 #[cfg(all(not(target_family = "wasm"), feature = "syntax-highlighting"))]
 pub mod static_site;
 pub mod theme;
@@ -0,0 +1,301 @@
+//! Metadata-only parse fast path for listings.
+//!
+//! Listings (draft lists, CLI vault scans, index backfills) only need a
+//! handful of facts about an entry — title, excerpt, cover image — but the
+//! full pipeline renders HTML, resolves links and uploads blobs to get
+//! them. [`parse_metadata`] makes a single pass over the parser events and
+//! extracts those facts without building any output, so callers can list
+//! hundreds of entries without paying for hundreds of renders.
+
+use markdown_weaver::{Event, Parser, Tag, TagEnd};
+use yaml_rust2::Yaml;
+
+use crate::{Frontmatter, default_md_options};
+
+/// Maximum excerpt length in chars before truncation.
+const EXCERPT_MAX_CHARS: usize = 300;
+
+/// Default summary budget; OG descriptions cut off around this length.
+pub const SUMMARY_MAX_CHARS: usize = 200;
+
+/// Cheap summary of a markdown entry, extracted without rendering.
+///
+/// Produced by [`parse_metadata`]. Everything here comes from a single
+/// parser pass over the source; no links are resolved and no HTML is
+/// built.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct EntryMetadata {
+    /// Parsed YAML frontmatter (empty if the entry has none).
+    pub frontmatter: Frontmatter,
+    /// Frontmatter `title` key, falling back to the first heading.
+    pub title: Option<String>,
+    /// Destination of the first image or image embed, for cover thumbnails.
+    pub first_image: Option<String>,
+    /// Plain text of the first non-empty paragraph, truncated to
+    /// `EXCERPT_MAX_CHARS` chars.
+    pub excerpt: Option<String>,
+    /// Whitespace-separated word count of the body (frontmatter excluded).
+    pub word_count: usize,
+    /// Outgoing link and embed destinations, deduplicated in document
+    /// order. Page-internal anchors (`#...`) are excluded.
+    pub links: Vec<String>,
+}
+
+/// Extract [`EntryMetadata`] from markdown source in one parser pass.
+///
+/// Uses [`default_md_options`], so wikilinks, Obsidian embeds and YAML
+/// frontmatter are recognized the same way the renderers see them.
+pub fn parse_metadata(markdown: &str) -> EntryMetadata {
+    let mut meta = EntryMetadata::default();
+
+    let mut in_metadata = false;
+    // Text of the first heading, kept separate so a frontmatter title can
+    // still win once the metadata block is seen.
+    let mut heading_title: Option<String> = None;
+    let mut capturing_heading = false;
+    // Accumulates the current candidate paragraph until its End event;
+    // blank paragraphs are discarded and the next one is tried.
+    let mut paragraph_text: Option<String> = None;
+
+    for event in Parser::new_ext(markdown, default_md_options()) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::MetadataBlock(_) | Tag::WeaverBlock(..) => in_metadata = true,
+                Tag::Heading { .. } if heading_title.is_none() => {
+                    capturing_heading = true;
+                    heading_title = Some(String::new());
+                }
+                Tag::Paragraph(_) if meta.excerpt.is_none() => {
+                    paragraph_text = Some(String::new());
+                }
+                Tag::Image { ref dest_url, .. } => {
+                    if meta.first_image.is_none() {
+                        meta.first_image = Some(dest_url.to_string());
+                    }
+                }
+                Tag::Link { ref dest_url, .. } | Tag::Embed { ref dest_url, .. } => {
+                    let dest = dest_url.as_ref();
+                    if !dest.is_empty()
+                        && !dest.starts_with('#')
+                        && !meta.links.iter().any(|l| l == dest)
+                    {
+                        meta.links.push(dest.to_owned());
+                    }
+                }
+                _ => {}
+            },
+            Event::End(tag_end) => match tag_end {
+                TagEnd::MetadataBlock(_) | TagEnd::WeaverBlock(_) => in_metadata = false,
+                TagEnd::Heading(_) => capturing_heading = false,
+                TagEnd::Paragraph(_) => {
+                    if let Some(text) = paragraph_text.take() {
+                        let trimmed = text.trim();
+                        if !trimmed.is_empty() {
+                            meta.excerpt = Some(truncate_excerpt(trimmed));
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) => {
+                if in_metadata {
+                    meta.frontmatter = Frontmatter::new(&text);
+                    continue;
+                }
+                meta.word_count += text.split_whitespace().count();
+                if capturing_heading {
+                    if let Some(title) = heading_title.as_mut() {
+                        title.push_str(&text);
+                    }
+                }
+                if let Some(para) = paragraph_text.as_mut() {
+                    para.push_str(&text);
+                }
+            }
+            Event::Code(code) => {
+                meta.word_count += code.split_whitespace().count();
+                if let Some(para) = paragraph_text.as_mut() {
+                    para.push_str(&code);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some(para) = paragraph_text.as_mut() {
+                    para.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let contents = meta.frontmatter.contents();
+    meta.title = contents
+        .read()
+        .ok()
+        .and_then(|yaml| frontmatter_title(&yaml))
+        .or_else(|| heading_title.filter(|t| !t.trim().is_empty()));
+
+    meta
+}
+
+impl EntryMetadata {
+    /// Entry summary for cards, OG descriptions and feeds.
+    ///
+    /// A frontmatter `summary:` key wins verbatim — it is the author's
+    /// chosen description. Otherwise the first paragraph is truncated to
+    /// `max_chars` at a sentence boundary (see [`truncate_at_sentence`]).
+    /// [`SUMMARY_MAX_CHARS`] is a reasonable default budget.
+    pub fn summary(&self, max_chars: usize) -> Option<String> {
+        let contents = self.frontmatter.contents();
+        if let Some(summary) = contents
+            .read()
+            .ok()
+            .and_then(|yaml| frontmatter_summary(&yaml))
+        {
+            return Some(summary);
+        }
+        self.excerpt
+            .as_deref()
+            .map(|excerpt| truncate_at_sentence(excerpt, max_chars))
+    }
+}
+
+/// Read the `title` key from the first frontmatter document.
+fn frontmatter_title(docs: &[Yaml]) -> Option<String> {
+    let doc = docs.first()?;
+    doc["title"].as_str().map(str::to_owned)
+}
+
+/// Read the `summary` key from the first frontmatter document.
+fn frontmatter_summary(docs: &[Yaml]) -> Option<String> {
+    let doc = docs.first()?;
+    doc["summary"]
+        .as_str()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+}
+
+/// Truncate on a char boundary, appending an ellipsis if anything was cut.
+fn truncate_excerpt(text: &str) -> String {
+    if text.chars().count() <= EXCERPT_MAX_CHARS {
+        return text.to_owned();
+    }
+    let mut excerpt: String = text.chars().take(EXCERPT_MAX_CHARS).collect();
+    // Avoid cutting mid-word; drop the trailing fragment.
+    if let Some(last_space) = excerpt.rfind(char::is_whitespace) {
+        excerpt.truncate(last_space);
+    }
+    excerpt.push('…');
+    excerpt
+}
+
+/// Truncate to `max_chars`, preferring a sentence boundary.
+///
+/// Keeps the last complete sentence that fits the budget so summaries read
+/// as a finished thought; when the text has no sentence end, falls back to
+/// a word-boundary cut with an ellipsis.
+pub fn truncate_at_sentence(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_owned();
+    }
+    let head: String = text.chars().take(max_chars).collect();
+    if let Some((idx, _)) = head.rmatch_indices(['.', '!', '?']).next() {
+        // The matched punctuation is ASCII, so idx + 1 stays on a char
+        // boundary.
+        return head[..=idx].trim_end().to_owned();
+    }
+    let mut truncated = head;
+    if let Some(last_space) = truncated.rfind(char::is_whitespace) {
+        truncated.truncate(last_space);
+    }
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_title_wins_over_heading() {
+        let meta = parse_metadata("---\ntitle: From Frontmatter\n---\n\n# From Heading\n\nBody.\n");
+        assert_eq!(meta.title.as_deref(), Some("From Frontmatter"));
+    }
+
+    #[test]
+    fn test_heading_title_fallback() {
+        let meta = parse_metadata("# First Heading\n\n## Second Heading\n\nBody.\n");
+        assert_eq!(meta.title.as_deref(), Some("First Heading"));
+    }
+
+    #[test]
+    fn test_excerpt_is_first_nonempty_paragraph() {
+        let meta = parse_metadata("# Title\n\nFirst paragraph with `code` inline.\n\nSecond.\n");
+        assert_eq!(
+            meta.excerpt.as_deref(),
+            Some("First paragraph with code inline.")
+        );
+    }
+
+    #[test]
+    fn test_excerpt_truncates_on_word_boundary() {
+        let long = "word ".repeat(100);
+        let meta = parse_metadata(&long);
+        let excerpt = meta.excerpt.unwrap();
+        assert!(excerpt.ends_with('…'));
+        assert!(excerpt.chars().count() <= EXCERPT_MAX_CHARS + 1);
+    }
+
+    #[test]
+    fn test_first_image_and_links() {
+        let meta = parse_metadata(
+            "![alt](images/cover.png)\n\nSee [a](https://example.com) and [b](other.md) \
+             and [a again](https://example.com) and [anchor](#section).\n",
+        );
+        assert_eq!(meta.first_image.as_deref(), Some("images/cover.png"));
+        assert_eq!(meta.links, vec!["https://example.com", "other.md"]);
+    }
+
+    #[test]
+    fn test_word_count_excludes_frontmatter() {
+        let meta = parse_metadata("---\ntitle: one two three four five\n---\n\nonly four words here\n");
+        assert_eq!(meta.word_count, 4);
+    }
+
+    #[test]
+    fn test_summary_frontmatter_override() {
+        let meta = parse_metadata("---\nsummary: Hand-written description.\n---\n\nLong body text.\n");
+        assert_eq!(
+            meta.summary(SUMMARY_MAX_CHARS).as_deref(),
+            Some("Hand-written description.")
+        );
+    }
+
+    #[test]
+    fn test_summary_truncates_at_sentence_boundary() {
+        let meta = parse_metadata("First sentence here. Second one follows. Third runs long.\n");
+        assert_eq!(
+            meta.summary(45).as_deref(),
+            Some("First sentence here. Second one follows.")
+        );
+    }
+
+    #[test]
+    fn test_truncate_at_sentence_word_fallback() {
+        // No sentence end in budget: fall back to a word cut with ellipsis.
+        assert_eq!(truncate_at_sentence("one two three four", 13), "one two…");
+        // Short text passes through untouched.
+        assert_eq!(truncate_at_sentence("short. text", 100), "short. text");
+    }
+
+    #[test]
+    fn test_empty_document() {
+        let meta = parse_metadata("");
+        assert_eq!(meta.title, None);
+        assert_eq!(meta.excerpt, None);
+        assert_eq!(meta.first_image, None);
+        assert_eq!(meta.word_count, 0);
+        assert!(meta.links.is_empty());
+    }
+}
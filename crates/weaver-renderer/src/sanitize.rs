@@ -0,0 +1,456 @@
+//! HTML sanitization for rendered third-party records.
+//!
+//! Record embeds and faceted text carry author-controlled fragments: link
+//! URLs, alt text, and in the case of whitewind or leaflet documents whole
+//! bodies of inline HTML. Everything rendered from someone else's repo is
+//! passed through [`sanitize_html`] before it reaches a page, so a hostile
+//! record degrades to inert markup instead of running script in a reader's
+//! browser.
+//!
+//! The sanitizer is allow-list based: unknown tags are stripped (their text
+//! content survives, except for script-like elements whose content is
+//! dropped wholesale), unknown attributes are dropped, and URL-bearing
+//! attributes are checked against an allowed scheme list. Trusted contexts
+//! — the static site rendering its own markdown — can opt out with
+//! [`SanitizePolicy::trusted`].
+
+use std::fmt::Write;
+
+/// Tags our own record renderers emit, plus common inline formatting.
+///
+/// `iframe` is here because leaflet website blocks render as iframes; its
+/// `src` still goes through scheme filtering like any other URL.
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "b",
+    "blockquote",
+    "br",
+    "code",
+    "del",
+    "div",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "iframe",
+    "img",
+    "input",
+    "label",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "pre",
+    "s",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "th",
+    "thead",
+    "tr",
+    "u",
+    "ul",
+];
+
+/// Attributes kept on allowed tags. Anything beginning with `on` (event
+/// handlers) is rejected by default-deny; `style` is allowed but its value
+/// is restricted by [`style_allowed`].
+const ALLOWED_ATTRS: &[&str] = &[
+    "allowfullscreen",
+    "alt",
+    "aria-label",
+    "class",
+    "contenteditable",
+    "data-aturi",
+    "datetime",
+    "dir",
+    "for",
+    "frameborder",
+    "height",
+    "href",
+    "id",
+    "lang",
+    "loading",
+    "rel",
+    "scrolling",
+    "src",
+    "start",
+    "style",
+    "target",
+    "title",
+    "type",
+    "width",
+];
+
+/// CSS properties allowed in `style` attributes: the layout hints our own
+/// renderers emit for images and iframes, nothing that can load a URL or
+/// reposition content over the rest of the page.
+const ALLOWED_STYLE_PROPS: &[&str] = &[
+    "aspect-ratio",
+    "border",
+    "height",
+    "max-height",
+    "max-width",
+    "object-fit",
+    "width",
+];
+
+/// Schemes allowed in `href` and `src`. Relative URLs and fragments are
+/// always allowed; `javascript:`, `data:`, and friends are not.
+const ALLOWED_SCHEMES: &[&str] = &["http", "https", "mailto", "at", "did"];
+
+/// Elements whose text content is dangerous on its own and is dropped
+/// along with the tags.
+const DROP_CONTENT_TAGS: &[&str] = &["script", "style", "title", "textarea", "noscript"];
+
+/// How aggressively to sanitize rendered HTML.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SanitizePolicy {
+    pass_through: bool,
+}
+
+impl SanitizePolicy {
+    /// The default policy for records fetched from other repos: allow-list
+    /// filtering of tags, attributes, and URL schemes.
+    pub fn untrusted() -> Self {
+        Self {
+            pass_through: false,
+        }
+    }
+
+    /// Pass HTML through unchanged. Only appropriate for content the site
+    /// author wrote themselves, like the static site's own markdown.
+    pub fn trusted() -> Self {
+        Self { pass_through: true }
+    }
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self::untrusted()
+    }
+}
+
+/// Sanitize an HTML fragment under the given policy.
+///
+/// This operates on the rendered output rather than the source record, so
+/// it catches HTML regardless of which renderer produced it — markdown
+/// passthrough, facet processing, or inline HTML in a blog entry.
+pub fn sanitize_html(html: &str, policy: &SanitizePolicy) -> String {
+    if policy.pass_through {
+        return html.to_string();
+    }
+
+    let mut out = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(lt) = rest.find('<') {
+        out.push_str(&rest[..lt]);
+        rest = &rest[lt..];
+
+        if rest.starts_with("<!--") {
+            // Comments can smuggle conditional markup; drop them whole. An
+            // unterminated comment swallows the remainder, matching how
+            // browsers parse it.
+            match rest.find("-->") {
+                Some(end) => rest = &rest[end + 3..],
+                None => rest = "",
+            }
+            continue;
+        }
+
+        let Some((tag, after)) = parse_tag(rest) else {
+            // A bare `<` that never forms a tag is treated as text.
+            out.push_str("&lt;");
+            rest = &rest[1..];
+            continue;
+        };
+
+        if DROP_CONTENT_TAGS.contains(&tag.name.as_str()) {
+            rest = skip_element_content(after, &tag.name);
+            continue;
+        }
+
+        if !ALLOWED_TAGS.contains(&tag.name.as_str()) {
+            // Unknown tag: strip the markup, keep whatever text follows.
+            rest = after;
+            continue;
+        }
+
+        write_tag(&mut out, &tag);
+        rest = after;
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// A parsed tag: name, closing flag, and surviving attributes.
+struct Tag {
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    attrs: Vec<(String, Option<String>)>,
+}
+
+/// Parse one tag starting at `<`. Returns the tag and the remainder after
+/// its `>`, or `None` if the input is not a well-formed-enough tag.
+fn parse_tag(input: &str) -> Option<(Tag, &str)> {
+    let mut chars = input[1..].char_indices().peekable();
+    let mut pos = 1;
+
+    let closing = input[1..].starts_with('/');
+    if closing {
+        chars.next();
+        pos += 1;
+    }
+
+    let name_start = pos;
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_ascii_alphanumeric() || c == '-' {
+            chars.next();
+            pos = 1 + i + c.len_utf8();
+        } else {
+            break;
+        }
+    }
+    if pos == name_start {
+        return None;
+    }
+    let name = input[name_start..pos].to_ascii_lowercase();
+
+    let mut attrs = Vec::new();
+    let remainder = &input[pos..];
+    let end = remainder.find('>')?;
+    let attr_src = remainder[..end].trim_end_matches('/');
+    let self_closing = remainder[..end].trim_end().ends_with('/');
+
+    if !closing {
+        parse_attrs(attr_src, &mut attrs);
+    }
+
+    Some((
+        Tag {
+            name,
+            closing,
+            self_closing,
+            attrs,
+        },
+        &remainder[end + 1..],
+    ))
+}
+
+/// Parse attributes, keeping only allow-listed names with acceptable
+/// values. Quoting styles are normalized to double quotes on output.
+fn parse_attrs(mut src: &str, attrs: &mut Vec<(String, Option<String>)>) {
+    loop {
+        src = src.trim_start();
+        if src.is_empty() {
+            return;
+        }
+
+        let name_end = src
+            .find(|c: char| c == '=' || c.is_ascii_whitespace())
+            .unwrap_or(src.len());
+        let name = src[..name_end].to_ascii_lowercase();
+        src = src[name_end..].trim_start();
+
+        let value = if let Some(stripped) = src.strip_prefix('=') {
+            let stripped = stripped.trim_start();
+            if let Some(rest) = stripped.strip_prefix('"') {
+                let end = rest.find('"').unwrap_or(rest.len());
+                src = rest.get(end + 1..).unwrap_or("");
+                Some(rest[..end].to_string())
+            } else if let Some(rest) = stripped.strip_prefix('\'') {
+                let end = rest.find('\'').unwrap_or(rest.len());
+                src = rest.get(end + 1..).unwrap_or("");
+                Some(rest[..end].to_string())
+            } else {
+                let end = stripped
+                    .find(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or(stripped.len());
+                src = &stripped[end..];
+                Some(stripped[..end].to_string())
+            }
+        } else {
+            None
+        };
+
+        if name.is_empty() {
+            continue;
+        }
+        if !ALLOWED_ATTRS.contains(&name.as_str()) {
+            continue;
+        }
+        if (name == "href" || name == "src") && !url_allowed(value.as_deref().unwrap_or("")) {
+            continue;
+        }
+        if name == "style" && !style_allowed(value.as_deref().unwrap_or("")) {
+            continue;
+        }
+        attrs.push((name, value));
+    }
+}
+
+/// Whether a URL is safe to keep: relative, fragment, query, or an
+/// allow-listed scheme.
+fn url_allowed(url: &str) -> bool {
+    let url = url.trim();
+    // A colon before any path/query/fragment delimiter means a scheme;
+    // anything else is a relative reference and stays.
+    let scheme_end = match url.find([':', '/', '?', '#']) {
+        Some(i) if url.as_bytes()[i] == b':' => i,
+        _ => return true,
+    };
+    let scheme = url[..scheme_end].to_ascii_lowercase();
+    ALLOWED_SCHEMES.contains(&scheme.as_str())
+}
+
+/// Whether a `style` value sticks to the allowed layout properties.
+///
+/// Functions (`url(...)`, `expression(...)`) are rejected outright; every
+/// declaration's property must be allow-listed.
+fn style_allowed(style: &str) -> bool {
+    if style.contains('(') {
+        return false;
+    }
+    style.split(';').all(|decl| {
+        let decl = decl.trim();
+        if decl.is_empty() {
+            return true;
+        }
+        match decl.split_once(':') {
+            Some((prop, _)) => {
+                ALLOWED_STYLE_PROPS.contains(&prop.trim().to_ascii_lowercase().as_str())
+            }
+            None => false,
+        }
+    })
+}
+
+/// Serialize a surviving tag, enforcing `rel="noopener noreferrer"` on
+/// links so an embedded record can never reach back into the opener.
+fn write_tag(out: &mut String, tag: &Tag) {
+    out.push('<');
+    if tag.closing {
+        out.push('/');
+    }
+    out.push_str(&tag.name);
+
+    let force_rel = !tag.closing && tag.name == "a";
+    for (name, value) in &tag.attrs {
+        if force_rel && name == "rel" {
+            continue;
+        }
+        out.push(' ');
+        out.push_str(name);
+        if let Some(value) = value {
+            out.push_str("=\"");
+            for c in value.chars() {
+                match c {
+                    '"' => out.push_str("&quot;"),
+                    '&' => out.push_str("&amp;"),
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    _ => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+    }
+    if force_rel && tag.attrs.iter().any(|(name, _)| name == "href") {
+        let _ = write!(out, " rel=\"noopener noreferrer\"");
+    }
+    if tag.self_closing {
+        out.push_str(" /");
+    }
+    out.push('>');
+}
+
+/// Skip past the matching close tag of a content-dropping element. If the
+/// close tag never appears, the rest of the fragment is dropped — safer
+/// than emitting half of a script body as text.
+fn skip_element_content<'a>(input: &'a str, name: &str) -> &'a str {
+    let close = format!("</{}", name);
+    let lower = input.to_ascii_lowercase();
+    match lower.find(&close) {
+        Some(start) => match input[start..].find('>') {
+            Some(end) => &input[start + end + 1..],
+            None => "",
+        },
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sanitize(html: &str) -> String {
+        sanitize_html(html, &SanitizePolicy::untrusted())
+    }
+
+    #[test]
+    fn test_strips_script_and_content() {
+        assert_eq!(
+            sanitize("before<script>alert(1)</script>after"),
+            "beforeafter"
+        );
+    }
+
+    #[test]
+    fn test_drops_event_handlers_and_style() {
+        assert_eq!(
+            sanitize("<img src=\"https://example.com/a.png\" onerror=\"alert(1)\" style=\"x\" />"),
+            "<img src=\"https://example.com/a.png\" />"
+        );
+    }
+
+    #[test]
+    fn test_filters_url_schemes() {
+        assert_eq!(
+            sanitize("<a href=\"javascript:alert(1)\">x</a>"),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize("<a href=\"/local\">x</a>"),
+            "<a href=\"/local\" rel=\"noopener noreferrer\">x</a>"
+        );
+    }
+
+    #[test]
+    fn test_enforces_noopener_over_author_rel() {
+        assert_eq!(
+            sanitize("<a href=\"https://example.com\" rel=\"opener\">x</a>"),
+            "<a href=\"https://example.com\" rel=\"noopener noreferrer\">x</a>"
+        );
+    }
+
+    #[test]
+    fn test_unknown_tag_keeps_text() {
+        assert_eq!(sanitize("<widget>hello</widget>"), "hello");
+    }
+
+    #[test]
+    fn test_trusted_policy_passes_through() {
+        let html = "<script>ours()</script>";
+        assert_eq!(sanitize_html(html, &SanitizePolicy::trusted()), html);
+    }
+
+    #[test]
+    fn test_own_embed_markup_survives() {
+        let html = "<span class=\"embed-record-card\"><input type=\"checkbox\" class=\"embed-entry-toggle\" id=\"t\" /><img class=\"embed-avatar\" src=\"https://cdn.example/a.jpg\" alt=\"\" width=\"42\" height=\"42\" /></span>";
+        assert_eq!(sanitize(html), html);
+    }
+}
@@ -5,8 +5,11 @@
 //! URLs in the notebook are mostly unaltered. It is compatible with GitHub or Cloudflare Pages
 //! and other similar static hosting services.
 
+pub mod cache;
 pub mod context;
 pub mod document;
+pub mod manifest;
+pub mod templates;
 pub mod writer;
 
 use crate::utils::VaultBrokenLinkCallback;
@@ -15,6 +18,7 @@ use crate::{
     static_site::{
         context::StaticSiteContext,
         document::{CssMode, write_document_footer, write_document_head},
+        templates::SiteTemplates,
         writer::StaticPageWriter,
     },
     theme::default_resolved_theme,
@@ -51,6 +55,7 @@ bitflags! {
         const CREATE_PAGES_BY_TITLE = 1 << 10;
         const NORMALIZE_DIR_NAMES = 1 << 11;
         const ADD_TOC_TO_PAGES = 1 << 12;
+        const EMIT_PRINT_VIEW = 1 << 13;
     }
 }
 
@@ -67,11 +72,28 @@ impl Default for StaticSiteOptions {
     }
 }
 
+/// Progress events emitted while rendering a multi-file site.
+#[derive(Debug, Clone)]
+pub enum RenderProgress {
+    /// Rendering started with this many files queued.
+    Started { total: usize },
+    /// One file finished rendering or copying.
+    FileDone {
+        path: PathBuf,
+        elapsed: std::time::Duration,
+    },
+}
+
+/// Callback observing [`RenderProgress`] events; called from worker tasks.
+pub type ProgressFn = Arc<dyn Fn(RenderProgress) + Send + Sync>;
+
 pub struct StaticSiteWriter<A>
 where
     A: AgentSession,
 {
     context: StaticSiteContext<A>,
+    jobs: usize,
+    progress: Option<ProgressFn>,
 }
 
 impl<A> StaticSiteWriter<A>
@@ -80,7 +102,35 @@ where
 {
     pub fn new(root: PathBuf, destination: PathBuf, session: Option<A>) -> Self {
         let context = StaticSiteContext::new(root, destination, session);
-        Self { context }
+        Self {
+            context,
+            jobs: default_jobs(),
+            progress: None,
+        }
+    }
+
+    /// Cap the number of files rendered concurrently.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Observe per-file progress while rendering.
+    pub fn with_progress(mut self, progress: ProgressFn) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Merge theme template overrides into the built-in layout.
+    pub fn with_templates(mut self, templates: SiteTemplates) -> Self {
+        self.context = self.context.with_templates(templates);
+        self
+    }
+
+    /// Exclude files matching these gitignore-style globs from the site.
+    pub fn with_ignore_globs(mut self, globs: Vec<String>) -> Self {
+        self.context = self.context.with_ignore_globs(globs);
+        self
     }
 }
 
@@ -95,7 +145,23 @@ where
                 self.context.root.display()
             ));
         }
-        let contents = vault_contents(&self.context.root, WalkOptions::new())?;
+        let mut contents = vault_contents(&self.context.root, WalkOptions::new())?;
+
+        // Drop anything matched by workspace-level ignore globs before any
+        // titles or links are gathered, so excluded files never leak into
+        // the index or the manifest.
+        if !self.context.ignore_globs.is_empty() {
+            let mut builder = ignore::gitignore::GitignoreBuilder::new(&self.context.root);
+            for glob in self.context.ignore_globs.iter() {
+                builder.add_line(None, glob).into_diagnostic()?;
+            }
+            let matcher = builder.build().into_diagnostic()?;
+            contents.retain(|path| {
+                !matcher
+                    .matched_path_or_any_parents(path, path.is_dir())
+                    .is_ignore()
+            });
+        }
 
         self.context.dir_contents = Some(contents.into());
 
@@ -139,7 +205,11 @@ where
         // Generate CSS files for multi-file mode
         self.generate_css_files().await?;
 
-        for file in self
+        // Copy theme-supplied CSS/JS so the links written into every page
+        // head resolve.
+        self.copy_theme_assets().await?;
+
+        let files: Vec<PathBuf> = self
             .context
             .dir_contents
             .as_ref()
@@ -147,75 +217,52 @@ where
             .clone()
             .into_iter()
             .filter(|file| file.starts_with(&self.context.start_at))
-        {
-            let context = self.context.clone();
-            let relative_path = file
-                .strip_prefix(context.start_at.clone())
-                .expect("file should always be nested under root")
-                .to_path_buf();
-
-            // Check if this is a markdown file
-            let is_markdown = file
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .map(|ext| ext == "md" || ext == "markdown")
-                .unwrap_or(false);
-
-            if !is_markdown {
-                // Copy non-markdown files directly
-                let output_path = if context
-                    .options
-                    .contains(StaticSiteOptions::FLATTEN_STRUCTURE)
-                {
-                    let path_str = relative_path.to_string_lossy();
-                    let (parent, fname) = flatten_dir_to_just_one_parent(&path_str);
-                    let parent = if parent.is_empty() { "entry" } else { parent };
-                    context
-                        .destination
-                        .join(String::from(parent))
-                        .join(String::from(fname))
-                } else {
-                    context.destination.join(relative_path.clone())
-                };
-
-                // Create parent directory if needed
-                if let Some(parent) = output_path.parent() {
-                    tokio::fs::create_dir_all(parent).await.into_diagnostic()?;
-                }
+            .collect();
+
+        // Split the batch into stale and fresh against the persisted build
+        // cache; fresh files keep the outputs already on disk.
+        let (stale, next_cache) = self.plan_incremental(&files).await?;
+        let skipped = files.len() - stale.len();
+        if skipped > 0 {
+            tracing::debug!("Skipping {} unchanged files", skipped);
+        }
 
-                tokio::fs::copy(&file, &output_path)
-                    .await
-                    .into_diagnostic()?;
-                return Ok(());
-            }
+        if let Some(progress) = &self.progress {
+            progress(RenderProgress::Started { total: stale.len() });
+        }
 
-            // Process markdown files
-            // Check if this is the designated index file
-            if let Some(index) = &context.index_file {
-                if &relative_path == index {
-                    let output_path = context.destination.join("index.html");
-                    return write_page(context.clone(), file, output_path).await;
+        // Bounded worker pool over the walker output. Each worker pulls the
+        // next file from a shared queue, so slow pages (blob fetches, embed
+        // resolution) don't stall the rest of the batch.
+        let queue = Arc::new(std::sync::Mutex::new(std::collections::VecDeque::from(
+            stale,
+        )));
+        let mut handles = Vec::with_capacity(self.jobs);
+        for _ in 0..self.jobs {
+            let queue = queue.clone();
+            let context = self.context.clone();
+            let progress = self.progress.clone();
+            handles.push(tokio::spawn(async move {
+                loop {
+                    // Scope the lock so the guard is dropped before awaiting.
+                    let file = { queue.lock().unwrap().pop_front() };
+                    let Some(file) = file else { break };
+                    let start = std::time::Instant::now();
+                    process_file(context.clone(), &file).await?;
+                    if let Some(progress) = &progress {
+                        progress(RenderProgress::FileDone {
+                            path: file,
+                            elapsed: start.elapsed(),
+                        });
+                    }
                 }
-            }
-
-            if context
-                .options
-                .contains(StaticSiteOptions::FLATTEN_STRUCTURE)
-            {
-                let path_str = relative_path.to_string_lossy();
-                let (parent, fname) = flatten_dir_to_just_one_parent(&path_str);
-                let parent = if parent.is_empty() { "entry" } else { parent };
-                let output_path = context
-                    .destination
-                    .join(String::from(parent))
-                    .join(String::from(fname));
-
-                write_page(context.clone(), file.clone(), output_path).await?;
-            } else {
-                let output_path = context.destination.join(relative_path.clone());
-
-                write_page(context.clone(), file.clone(), output_path).await?;
-            }
+                Ok::<(), miette::Report>(())
+            }));
+        }
+        for handle in handles {
+            handle
+                .await
+                .map_err(|e| miette::miette!("render worker panicked: {}", e))??;
         }
 
         // Generate default index if requested and no custom index specified
@@ -228,9 +275,100 @@ where
             self.generate_default_index().await?;
         }
 
+        // Emit the machine-readable manifest last so it reflects the titles
+        // and frontmatter gathered while rendering.
+        manifest::write_site_manifest(&self.context).await?;
+
+        // Persist the cache only after everything above succeeded, so a
+        // failed run re-renders rather than trusting half-written output.
+        next_cache.save(&self.context.destination).await?;
+
         Ok(())
     }
 
+    /// Decides which files need rendering and builds the cache snapshot to
+    /// persist if this run succeeds.
+    ///
+    /// Titles of skipped pages are seeded into the shared title map so the
+    /// default index and the manifest still see every page.
+    async fn plan_incremental(
+        &self,
+        files: &[PathBuf],
+    ) -> Result<(Vec<PathBuf>, cache::BuildCache), miette::Report> {
+        let previous = cache::BuildCache::load(&self.context.destination).await;
+        let mut next = cache::BuildCache::empty();
+        let mut stale = Vec::new();
+
+        let vault: Vec<PathBuf> = self
+            .context
+            .dir_contents
+            .as_ref()
+            .map(|c| c.to_vec())
+            .unwrap_or_default();
+
+        // Current titles of every markdown file, gathered up front so each
+        // page's dependency snapshot reflects this run, not the last one.
+        let mut titles = std::collections::BTreeMap::new();
+        for file in files {
+            if is_markdown_file(file) {
+                let contents = tokio::fs::read_to_string(file).await.into_diagnostic()?;
+                if let Some(title) = crate::metadata::parse_metadata(&contents).title {
+                    titles.insert(file.clone(), title);
+                }
+            }
+        }
+
+        for file in files {
+            let relative = file
+                .strip_prefix(&self.context.start_at)
+                .expect("file should always be nested under root");
+            let rel_str = cache::path_to_cache_string(relative);
+            let markdown = is_markdown_file(file);
+
+            let state = if markdown {
+                let contents = tokio::fs::read_to_string(file).await.into_diagnostic()?;
+                cache::page_state(&contents, &vault, &self.context.start_at, &titles)
+            } else {
+                let data = tokio::fs::read(file).await.into_diagnostic()?;
+                cache::asset_state(&data)
+            };
+
+            let output_rel = if markdown && self.context.index_file.as_deref() == Some(relative) {
+                PathBuf::from("index.html")
+            } else {
+                // Notebooks render to pages, so they get the .html output
+                // path even though they hash like assets.
+                let renders_to_page = markdown || is_ipynb_file(file);
+                manifest::output_rel_path(self.context.options, relative, renders_to_page)
+            };
+            let output = self.context.destination.join(output_rel);
+
+            // A fresh page still needs a re-render if print views were
+            // enabled after it was last built.
+            let print_view_missing = (markdown || is_ipynb_file(file))
+                && self
+                    .context
+                    .options
+                    .contains(StaticSiteOptions::EMIT_PRINT_VIEW)
+                && !output.with_extension("print.html").exists();
+
+            if !print_view_missing && previous.is_fresh(&rel_str, &state, &output) {
+                // The renderer never visits this page, so its title has to
+                // come from the cheap metadata pass instead.
+                if let Some(title) = &state.title {
+                    self.context
+                        .titles
+                        .insert(file.clone(), CowStr::Boxed(title.clone().into_boxed_str()));
+                }
+            } else {
+                stale.push(file.clone());
+            }
+            next.pages.insert(rel_str, state);
+        }
+
+        Ok((stale, next))
+    }
+
     #[cfg(feature = "syntax-css")]
     async fn generate_css_files(&self) -> Result<(), miette::Report> {
         use crate::css::{generate_base_css, generate_syntax_css};
@@ -255,6 +393,11 @@ where
             .await
             .into_diagnostic()?;
 
+        // Write print.css
+        tokio::fs::write(css_dir.join("print.css"), crate::css::PRINT_CSS)
+            .await
+            .into_diagnostic()?;
+
         Ok(())
     }
 
@@ -265,6 +408,30 @@ where
         ))
     }
 
+    async fn copy_theme_assets(&self) -> Result<(), miette::Report> {
+        let Some(templates) = &self.context.templates else {
+            return Ok(());
+        };
+        if templates.assets.is_empty() {
+            return Ok(());
+        }
+
+        let theme_dir = self.context.destination.join("theme");
+        tokio::fs::create_dir_all(&theme_dir)
+            .await
+            .into_diagnostic()?;
+
+        for asset in &templates.assets {
+            if let Some(name) = asset.file_name() {
+                tokio::fs::copy(asset, theme_dir.join(name))
+                    .await
+                    .into_diagnostic()?;
+            }
+        }
+
+        Ok(())
+    }
+
     async fn generate_default_index(&self) -> Result<(), miette::Report> {
         let index_path = self.context.destination.join("index.html");
         let mut index_file = crate::utils::create_file(&index_path).await?;
@@ -319,12 +486,109 @@ where
         index_file.write_all(b"</ul>\n").await.into_diagnostic()?;
 
         // Write footer
-        write_document_footer(&mut index_file).await?;
+        write_document_footer(&mut index_file, self.context.templates.as_deref()).await?;
 
         Ok(())
     }
 }
 
+/// Whether a walker entry is a markdown source.
+fn is_markdown_file(file: &Path) -> bool {
+    file.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext == "md" || ext == "markdown")
+        .unwrap_or(false)
+}
+
+/// Whether a walker entry is a Jupyter notebook source.
+fn is_ipynb_file(file: &Path) -> bool {
+    file.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext == "ipynb")
+        .unwrap_or(false)
+}
+
+/// Default render parallelism: one worker per core.
+fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(std::num::NonZeroUsize::get)
+        .unwrap_or(4)
+}
+
+/// Render or copy a single vault file into the destination.
+async fn process_file<A>(context: StaticSiteContext<A>, file: &Path) -> Result<(), miette::Report>
+where
+    A: AgentSession + IdentityResolver + 'static,
+{
+    let relative_path = file
+        .strip_prefix(context.start_at.clone())
+        .expect("file should always be nested under root")
+        .to_path_buf();
+
+    // Jupyter notebooks render as pages rather than copying through.
+    if is_ipynb_file(file) {
+        let output_rel = manifest::output_rel_path(context.options, &relative_path, true);
+        let output_path = context.destination.join(output_rel);
+        return write_notebook_page(context.clone(), file, output_path).await;
+    }
+
+    if !is_markdown_file(file) {
+        // Copy non-markdown files directly
+        let output_path = if context
+            .options
+            .contains(StaticSiteOptions::FLATTEN_STRUCTURE)
+        {
+            let path_str = relative_path.to_string_lossy();
+            let (parent, fname) = flatten_dir_to_just_one_parent(&path_str);
+            let parent = if parent.is_empty() { "entry" } else { parent };
+            context
+                .destination
+                .join(String::from(parent))
+                .join(String::from(fname))
+        } else {
+            context.destination.join(relative_path.clone())
+        };
+
+        // Create parent directory if needed
+        if let Some(parent) = output_path.parent() {
+            tokio::fs::create_dir_all(parent).await.into_diagnostic()?;
+        }
+
+        tokio::fs::copy(&file, &output_path)
+            .await
+            .into_diagnostic()?;
+        return Ok(());
+    }
+
+    // Process markdown files
+    // Check if this is the designated index file
+    if let Some(index) = &context.index_file {
+        if &relative_path == index {
+            let output_path = context.destination.join("index.html");
+            return write_page(context.clone(), file, output_path).await;
+        }
+    }
+
+    if context
+        .options
+        .contains(StaticSiteOptions::FLATTEN_STRUCTURE)
+    {
+        let path_str = relative_path.to_string_lossy();
+        let (parent, fname) = flatten_dir_to_just_one_parent(&path_str);
+        let parent = if parent.is_empty() { "entry" } else { parent };
+        let output_path = context
+            .destination
+            .join(String::from(parent))
+            .join(String::from(fname));
+
+        write_page(context.clone(), file, output_path).await
+    } else {
+        let output_path = context.destination.join(relative_path.clone());
+
+        write_page(context.clone(), file, output_path).await
+    }
+}
+
 pub async fn export_page<'input, A>(
     contents: &'input str,
     context: StaticSiteContext<A>,
@@ -366,21 +630,105 @@ where
 
     // Change extension to .html
     let output_path = output_path.as_ref().with_extension("html");
-    let mut output_file = crate::utils::create_file(&output_path).await?;
     let context = context.clone_with_path(input_path);
+    write_rendered_page(context, &contents, &output_path).await
+}
+
+/// Render a Jupyter notebook as a page.
+///
+/// Cells become markdown via [`crate::ipynb::convert_ipynb`]; image
+/// outputs are written to a `<page>_files/` directory next to the page
+/// (the nbconvert convention), so the generated references resolve
+/// relative to it.
+pub async fn write_notebook_page<A>(
+    context: StaticSiteContext<A>,
+    input_path: impl AsRef<Path>,
+    output_path: impl AsRef<Path>,
+) -> Result<(), miette::Report>
+where
+    A: AgentSession + IdentityResolver,
+{
+    let source = tokio::fs::read_to_string(&input_path)
+        .await
+        .into_diagnostic()?;
+    let converted = crate::ipynb::convert_ipynb(&source)?;
+
+    let output_path = output_path.as_ref().with_extension("html");
+    let stem = output_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("notebook")
+        .to_string();
+
+    let mut markdown = converted.markdown;
+    if !converted.attachments.is_empty() {
+        let files_dir_name = format!("{}_files", stem);
+        let files_dir = output_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(&files_dir_name);
+        tokio::fs::create_dir_all(&files_dir)
+            .await
+            .into_diagnostic()?;
+        for attachment in &converted.attachments {
+            tokio::fs::write(files_dir.join(&attachment.name), &attachment.data)
+                .await
+                .into_diagnostic()?;
+            // Attachment names are generated and unique, so a plain
+            // substring rewrite cannot touch author content.
+            markdown = markdown.replace(
+                &format!("]({})", attachment.name),
+                &format!("]({}/{})", files_dir_name, attachment.name),
+            );
+        }
+    }
+
+    let context = context.clone_with_path(input_path);
+    write_rendered_page(context, &markdown, &output_path).await
+}
+
+/// Shared tail of page rendering: head, rendered body wrapped in the
+/// theme's template, footer, and the optional print view.
+async fn write_rendered_page<A>(
+    context: StaticSiteContext<A>,
+    contents: &str,
+    output_path: &Path,
+) -> Result<(), miette::Report>
+where
+    A: AgentSession + IdentityResolver,
+{
+    let mut output_file = crate::utils::create_file(output_path).await?;
 
     // Write document head
-    write_document_head(&context, &mut output_file, CssMode::Linked, &output_path).await?;
+    write_document_head(&context, &mut output_file, CssMode::Linked, output_path).await?;
 
-    // Write body content
-    let output = export_page(&contents, context).await?;
+    // Write body content, wrapped in the theme's page template if one is set
+    let output = export_page(contents, context.clone()).await?;
+    let output = match &context.templates {
+        Some(templates) => templates.apply_page(&output),
+        None => output,
+    };
     output_file
         .write_all(output.as_bytes())
         .await
         .into_diagnostic()?;
 
     // Write document footer
-    write_document_footer(&mut output_file).await?;
+    write_document_footer(&mut output_file, context.templates.as_deref()).await?;
+
+    // Optionally emit a self-contained print view alongside the page for
+    // browser PDF export. The body was already rendered above, so this
+    // only costs a second head with inline CSS.
+    if context.options.contains(StaticSiteOptions::EMIT_PRINT_VIEW) {
+        let print_path = output_path.with_extension("print.html");
+        let mut print_file = crate::utils::create_file(&print_path).await?;
+        write_document_head(&context, &mut print_file, CssMode::Inline, &print_path).await?;
+        print_file
+            .write_all(output.as_bytes())
+            .await
+            .into_diagnostic()?;
+        write_document_footer(&mut print_file, context.templates.as_deref()).await?;
+    }
 
     Ok(())
 }
@@ -405,15 +753,19 @@ where
     // Write document head with inline CSS
     write_document_head(&context, &mut output_file, CssMode::Inline, &output_path).await?;
 
-    // Write body content
-    let output = export_page(&contents, context).await?;
+    // Write body content, wrapped in the theme's page template if one is set
+    let output = export_page(&contents, context.clone()).await?;
+    let output = match &context.templates {
+        Some(templates) => templates.apply_page(&output),
+        None => output,
+    };
     output_file
         .write_all(output.as_bytes())
         .await
         .into_diagnostic()?;
 
     // Write document footer
-    write_document_footer(&mut output_file).await?;
+    write_document_footer(&mut output_file, context.templates.as_deref()).await?;
 
     Ok(())
 }
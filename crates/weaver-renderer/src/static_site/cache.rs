@@ -0,0 +1,197 @@
+//! Incremental build cache for the static site writer.
+//!
+//! Every run used to re-render the whole vault. The cache records, per
+//! source file, the blake3 hash of its contents plus the pages it links
+//! to and the titles those pages had at render time. A page is rendered
+//! again only when its own content changed, its output file is missing,
+//! or a linked page's title changed (titles feed the link text written
+//! into dependents). The cache persists under the output directory next
+//! to the manifest; an unreadable or version-mismatched cache simply
+//! triggers a full rebuild, never an error.
+
+use crate::metadata::parse_metadata;
+use crate::utils::lookup_filename_in_vault;
+use miette::IntoDiagnostic;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Cache filename within the destination directory.
+pub const CACHE_FILENAME: &str = "weaver-buildcache.json";
+
+/// Bumped whenever the rendered output for unchanged input could differ
+/// (template handling, flatten logic, etc.); a mismatch discards the cache.
+// 2: .ipynb files render as pages instead of copying through.
+const CACHE_VERSION: u32 = 2;
+
+/// Persisted per-file render state from the previous successful build.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BuildCache {
+    pub version: u32,
+    /// Source-relative path (forward slashes) to state at last render.
+    pub pages: BTreeMap<String, PageState>,
+}
+
+/// Everything that, if changed, requires re-rendering one file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageState {
+    /// Blake3 hash of the source bytes, hex-encoded.
+    pub blake3: String,
+    /// Entry title (markdown files only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Source-relative paths of vault pages this file links to, mapped to
+    /// the titles they had when this file was last rendered.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub deps: BTreeMap<String, Option<String>>,
+}
+
+impl BuildCache {
+    /// Creates an empty cache at the current version.
+    pub fn empty() -> Self {
+        Self {
+            version: CACHE_VERSION,
+            pages: BTreeMap::new(),
+        }
+    }
+
+    /// Loads the cache from the destination directory.
+    ///
+    /// Missing, corrupt, or outdated caches yield an empty cache so the
+    /// worst failure mode is a full rebuild.
+    pub async fn load(destination: &Path) -> Self {
+        let path = destination.join(CACHE_FILENAME);
+        let Ok(bytes) = tokio::fs::read(&path).await else {
+            return Self::empty();
+        };
+        match serde_json::from_slice::<Self>(&bytes) {
+            Ok(cache) if cache.version == CACHE_VERSION => cache,
+            _ => Self::empty(),
+        }
+    }
+
+    /// Writes the cache into the destination directory.
+    pub async fn save(&self, destination: &Path) -> Result<(), miette::Report> {
+        let json = serde_json::to_string_pretty(self).into_diagnostic()?;
+        tokio::fs::write(destination.join(CACHE_FILENAME), json)
+            .await
+            .into_diagnostic()?;
+        Ok(())
+    }
+
+    /// Whether a file can keep its existing output.
+    ///
+    /// Fresh means: the output exists, the source hash matches, and every
+    /// dependency still carries the title it had at the last render.
+    pub fn is_fresh(&self, source: &str, current: &PageState, output: &Path) -> bool {
+        if !output.exists() {
+            return false;
+        }
+        self.pages.get(source) == Some(current)
+    }
+}
+
+/// Computes the current [`PageState`] for a markdown source.
+///
+/// Dependencies are the source's outbound links that resolve to vault
+/// files, each paired with that file's *current* title so a comparison
+/// against the cached state catches upstream title changes.
+pub(crate) fn page_state(
+    contents: &str,
+    vault: &[PathBuf],
+    start_at: &Path,
+    titles: &BTreeMap<PathBuf, String>,
+) -> PageState {
+    let meta = parse_metadata(contents);
+
+    let mut deps = BTreeMap::new();
+    for link in &meta.links {
+        let Some(target) = internal_link_target(link) else {
+            continue;
+        };
+        if let Some(path) = lookup_filename_in_vault(&target, vault) {
+            let rel = path.strip_prefix(start_at).unwrap_or(path.as_path());
+            deps.insert(path_to_cache_string(rel), titles.get(path).cloned());
+        }
+    }
+
+    PageState {
+        blake3: weaver_common::blake3::hash(contents.as_bytes())
+            .to_hex()
+            .to_string(),
+        title: meta.title,
+        deps,
+    }
+}
+
+/// Computes the current [`PageState`] for a non-markdown asset.
+pub(crate) fn asset_state(data: &[u8]) -> PageState {
+    PageState {
+        blake3: weaver_common::blake3::hash(data).to_hex().to_string(),
+        title: None,
+        deps: BTreeMap::new(),
+    }
+}
+
+/// The vault-relative target of an internal link, or `None` for external
+/// destinations (schemes, anchors).
+fn internal_link_target(link: &str) -> Option<String> {
+    if link.starts_with('#') || link.contains("://") || link.starts_with("mailto:") {
+        return None;
+    }
+    // Drop section anchors and percent-encoded spaces so the lookup sees
+    // the filename as it exists on disk.
+    let target = link.split('#').next().unwrap_or(link);
+    let target = target.replace("%20", " ");
+    if target.is_empty() {
+        return None;
+    }
+    Some(target)
+}
+
+/// Forward-slash string form of a relative path, stable across platforms.
+pub(crate) fn path_to_cache_string(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_state_tracks_dep_titles() {
+        let vault = vec![PathBuf::from("/vault/a.md"), PathBuf::from("/vault/b.md")];
+        let mut titles = BTreeMap::new();
+        titles.insert(PathBuf::from("/vault/b.md"), "B title".to_string());
+
+        let state = page_state("see [b](b.md)\n", &vault, Path::new("/vault"), &titles);
+        assert_eq!(state.deps.get("b.md"), Some(&Some("B title".to_string())));
+
+        // A changed dependency title makes the states differ.
+        let mut titles = titles;
+        titles.insert(PathBuf::from("/vault/b.md"), "Renamed".to_string());
+        let renamed = page_state("see [b](b.md)\n", &vault, Path::new("/vault"), &titles);
+        assert_ne!(state, renamed);
+        assert_eq!(state.blake3, renamed.blake3);
+    }
+
+    #[test]
+    fn test_is_fresh_requires_existing_output() {
+        let mut cache = BuildCache::empty();
+        let state = asset_state(b"data");
+        cache.pages.insert("img.png".to_string(), state.clone());
+        // Output path that does not exist on disk.
+        assert!(!cache.is_fresh("img.png", &state, Path::new("/nonexistent/img.png")));
+    }
+
+    #[test]
+    fn test_external_links_are_not_deps() {
+        let state = page_state(
+            "[x](https://example.com) [y](#anchor)\n",
+            &[],
+            Path::new("/vault"),
+            &BTreeMap::new(),
+        );
+        assert!(state.deps.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
-use crate::static_site::{StaticSiteOptions};
+use crate::static_site::StaticSiteOptions;
+use crate::static_site::templates::SiteTemplates;
 use crate::theme::ResolvedTheme;
-use crate::{Frontmatter, NotebookContext,default_md_options};
+use crate::{Frontmatter, NotebookContext, default_md_options};
 use dashmap::DashMap;
 use markdown_weaver::{CowStr, EmbedType, Tag, WeaverAttributes};
 use std::{
@@ -40,6 +41,8 @@ pub struct StaticSiteContext<A: AgentSession> {
     agent: Option<Arc<Agent<A>>>,
 
     pub theme: Option<Arc<ResolvedTheme<'static>>>,
+    pub templates: Option<Arc<SiteTemplates>>,
+    pub ignore_globs: Arc<[String]>,
     pub katex_source: Option<KaTeXSource>,
     pub syntax_set: Arc<SyntaxSet>,
     pub index_file: Option<PathBuf>,
@@ -62,6 +65,8 @@ impl<A: AgentSession> Clone for StaticSiteContext<A> {
             client: self.client.clone(),
             agent: self.agent.clone(),
             theme: self.theme.clone(),
+            templates: self.templates.clone(),
+            ignore_globs: self.ignore_globs.clone(),
             katex_source: self.katex_source.clone(),
             syntax_set: self.syntax_set.clone(),
             index_file: self.index_file.clone(),
@@ -86,6 +91,8 @@ impl<A: AgentSession> StaticSiteContext<A> {
             client: self.client.clone(),
             agent: self.agent.clone(),
             theme: self.theme.clone(),
+            templates: self.templates.clone(),
+            ignore_globs: self.ignore_globs.clone(),
             katex_source: self.katex_source.clone(),
             syntax_set: self.syntax_set.clone(),
             index_file: self.index_file.clone(),
@@ -116,6 +123,8 @@ impl<A: AgentSession> StaticSiteContext<A> {
             client: Some(reqwest::Client::default()),
             agent: self.agent.clone(),
             theme: self.theme.clone(),
+            templates: self.templates.clone(),
+            ignore_globs: self.ignore_globs.clone(),
             katex_source: self.katex_source.clone(),
             syntax_set: self.syntax_set.clone(),
             index_file: self.index_file.clone(),
@@ -139,6 +148,8 @@ impl<A: AgentSession> StaticSiteContext<A> {
             client: Some(reqwest::Client::default()),
             agent: session.map(|session| Arc::new(Agent::new(session))),
             theme: Some(Arc::new(default_resolved_theme())),
+            templates: None,
+            ignore_globs: Arc::from([]),
             katex_source: None,
             syntax_set: Arc::new(SyntaxSet::load_defaults_newlines()),
             index_file: None,
@@ -150,6 +161,16 @@ impl<A: AgentSession> StaticSiteContext<A> {
         self
     }
 
+    pub fn with_templates(mut self, templates: SiteTemplates) -> Self {
+        self.templates = Some(Arc::new(templates));
+        self
+    }
+
+    pub fn with_ignore_globs(mut self, globs: Vec<String>) -> Self {
+        self.ignore_globs = globs.into();
+        self
+    }
+
     pub fn current_path(&self) -> &PathBuf {
         if let Some(dir_contents) = &self.dir_contents {
             &dir_contents[self.position]
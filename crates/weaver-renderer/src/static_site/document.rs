@@ -1,6 +1,7 @@
 #[cfg(feature = "syntax-css")]
 use crate::css::{generate_base_css, generate_syntax_css};
 use crate::static_site::context::{KaTeXSource, StaticSiteContext};
+use crate::static_site::templates::SiteTemplates;
 use crate::theme::default_resolved_theme;
 use miette::IntoDiagnostic;
 use weaver_common::jacquard::client::AgentSession;
@@ -97,6 +98,16 @@ pub async fn write_document_head<A: AgentSession>(
                 )
                 .await
                 .into_diagnostic()?;
+            writer
+                .write_all(
+                    format!(
+                        "  <link rel=\"stylesheet\" href=\"{}css/print.css\" media=\"print\">\n",
+                        relative_to_root
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .into_diagnostic()?;
         }
         #[cfg(feature = "syntax-css")]
         CssMode::Inline => {
@@ -117,6 +128,15 @@ pub async fn write_document_head<A: AgentSession>(
                 .await
                 .into_diagnostic()?;
             writer.write_all(b"  </style>\n").await.into_diagnostic()?;
+
+            // PRINT_CSS is wrapped in @media print, so inlining it is
+            // inert on screen.
+            writer.write_all(b"  <style>\n").await.into_diagnostic()?;
+            writer
+                .write_all(crate::css::PRINT_CSS.as_bytes())
+                .await
+                .into_diagnostic()?;
+            writer.write_all(b"  </style>\n").await.into_diagnostic()?;
         }
         #[cfg(not(feature = "syntax-css"))]
         CssMode::Inline => {
@@ -127,6 +147,47 @@ pub async fn write_document_head<A: AgentSession>(
         }
     }
 
+    // Theme-supplied assets; copied into theme/ in the destination when
+    // linked, inlined wholesale in standalone mode.
+    if let Some(templates) = &context.templates {
+        for asset in &templates.assets {
+            let Some(name) = asset.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let is_css = name.ends_with(".css");
+            match css_mode {
+                CssMode::Linked => {
+                    let tag = if is_css {
+                        format!(
+                            "  <link rel=\"stylesheet\" href=\"{}theme/{}\">\n",
+                            relative_to_root, name
+                        )
+                    } else {
+                        format!(
+                            "  <script defer src=\"{}theme/{}\"></script>\n",
+                            relative_to_root, name
+                        )
+                    };
+                    writer.write_all(tag.as_bytes()).await.into_diagnostic()?;
+                }
+                CssMode::Inline => {
+                    let contents = tokio::fs::read_to_string(asset).await.into_diagnostic()?;
+                    let (open, close) = if is_css {
+                        (b"  <style>\n" as &[u8], b"  </style>\n" as &[u8])
+                    } else {
+                        (b"  <script>\n" as &[u8], b"  </script>\n" as &[u8])
+                    };
+                    writer.write_all(open).await.into_diagnostic()?;
+                    writer
+                        .write_all(contents.as_bytes())
+                        .await
+                        .into_diagnostic()?;
+                    writer.write_all(close).await.into_diagnostic()?;
+                }
+            }
+        }
+    }
+
     // KaTeX if enabled
     if let Some(ref katex) = context.katex_source {
         match katex {
@@ -167,6 +228,16 @@ pub async fn write_document_head<A: AgentSession>(
         .write_all(b"<body style=\"background: var(--color-base); min-height: 100vh;\">\n")
         .await
         .into_diagnostic()?;
+
+    // Site-wide header fragment (masthead, nav) from the theme, if any.
+    if let Some(header) = context.templates.as_ref().and_then(|t| t.header.as_deref()) {
+        writer
+            .write_all(header.as_bytes())
+            .await
+            .into_diagnostic()?;
+        writer.write_all(b"\n").await.into_diagnostic()?;
+    }
+
     writer
         .write_all(b"<div class=\"notebook-content\">\n")
         .await
@@ -177,10 +248,21 @@ pub async fn write_document_head<A: AgentSession>(
 
 pub async fn write_document_footer(
     writer: &mut (impl tokio::io::AsyncWrite + Unpin),
+    templates: Option<&SiteTemplates>,
 ) -> miette::Result<()> {
     use tokio::io::AsyncWriteExt;
 
     writer.write_all(b"</div>\n").await.into_diagnostic()?;
+
+    // Site-wide footer fragment from the theme, if any.
+    if let Some(footer) = templates.and_then(|t| t.footer.as_deref()) {
+        writer
+            .write_all(footer.as_bytes())
+            .await
+            .into_diagnostic()?;
+        writer.write_all(b"\n").await.into_diagnostic()?;
+    }
+
     writer.write_all(b"</body>\n").await.into_diagnostic()?;
     writer.write_all(b"</html>\n").await.into_diagnostic()?;
 
@@ -0,0 +1,310 @@
+//! Machine-readable site manifest.
+//!
+//! After a static site build, `StaticSiteWriter` emits a `weaver-manifest.json`
+//! into the destination directory describing everything it produced: entries
+//! with their output paths, titles, tags, and AT URIs, the outbound link graph
+//! between entries, and content hashes for copied assets. External tooling
+//! (search indexers, newsletter generators, CI link checkers) can consume this
+//! instead of scraping the generated HTML.
+
+use crate::static_site::{StaticSiteOptions, context::StaticSiteContext};
+use crate::utils::flatten_dir_to_just_one_parent;
+use markdown_weaver::{Event, Parser, Tag};
+use miette::IntoDiagnostic;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use weaver_common::jacquard::client::AgentSession;
+use yaml_rust2::Yaml;
+
+/// Filename of the manifest written into the site destination.
+pub const MANIFEST_FILENAME: &str = "weaver-manifest.json";
+
+/// Top-level structure serialized to `weaver-manifest.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SiteManifest {
+    /// Manifest format version, bumped on breaking shape changes.
+    pub version: u32,
+    /// Rendered markdown entries, in walk order.
+    pub entries: Vec<ManifestEntry>,
+    /// Non-markdown files copied into the site verbatim.
+    pub assets: Vec<ManifestAsset>,
+}
+
+/// One rendered markdown entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestEntry {
+    /// Source path relative to the notebook root, forward slashes.
+    pub source: String,
+    /// Output path relative to the destination, forward slashes.
+    pub path: String,
+    /// Title recorded during rendering (first heading or frontmatter).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Tags from the entry's frontmatter.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// AT URI of the published record, if the frontmatter carries one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at_uri: Option<String>,
+    /// Outbound link destinations as written in the source, local and remote.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+}
+
+/// One copied (non-markdown) asset.
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestAsset {
+    /// Source path relative to the notebook root, forward slashes.
+    pub source: String,
+    /// Output path relative to the destination, forward slashes.
+    pub path: String,
+    /// Blake3 hash of the file contents, hex-encoded.
+    pub blake3: String,
+}
+
+/// Computes the destination-relative output path for a source file, mirroring
+/// the flatten logic used when pages and assets are written.
+pub(crate) fn output_rel_path(
+    options: StaticSiteOptions,
+    relative: &Path,
+    is_markdown: bool,
+) -> PathBuf {
+    let out = if options.contains(StaticSiteOptions::FLATTEN_STRUCTURE) {
+        let path_str = relative.to_string_lossy();
+        let (parent, fname) = flatten_dir_to_just_one_parent(&path_str);
+        let parent = if parent.is_empty() { "entry" } else { parent };
+        PathBuf::from(parent).join(fname)
+    } else {
+        relative.to_path_buf()
+    };
+    if is_markdown {
+        out.with_extension("html")
+    } else {
+        out
+    }
+}
+
+/// Collects outbound link destinations from markdown source.
+///
+/// Parses the source a second time rather than threading collection through
+/// the renderer; link extraction is cheap relative to full rendering and this
+/// keeps the manifest independent of the event pipeline.
+pub(crate) fn collect_links(contents: &str, options: markdown_weaver::Options) -> Vec<String> {
+    let parser = Parser::new_ext(contents, options);
+    let mut links = Vec::new();
+    for event in parser {
+        if let Event::Start(Tag::Link { dest_url, .. } | Tag::Embed { dest_url, .. }) = event {
+            let dest = dest_url.to_string();
+            // Skip intra-page anchors; they carry no graph information.
+            if !dest.is_empty() && !dest.starts_with('#') && !links.contains(&dest) {
+                links.push(dest);
+            }
+        }
+    }
+    links
+}
+
+/// Reads the `tags` key out of parsed frontmatter.
+///
+/// Accepts either a YAML sequence of strings or a single comma-separated
+/// string, matching what notebook authors actually write.
+pub(crate) fn frontmatter_tags(yaml: &[Yaml]) -> Vec<String> {
+    let Some(tags) = yaml.first().and_then(|doc| doc["tags"].as_vec().cloned()) else {
+        if let Some(tags) = yaml.first().and_then(|doc| doc["tags"].as_str()) {
+            return tags
+                .split(',')
+                .map(|t| t.trim().to_owned())
+                .filter(|t| !t.is_empty())
+                .collect();
+        }
+        return Vec::new();
+    };
+    tags.iter()
+        .filter_map(|t| t.as_str())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Reads the AT URI of the published record from frontmatter, if present.
+///
+/// Both `at-uri` and `at_uri` spellings are accepted.
+pub(crate) fn frontmatter_at_uri(yaml: &[Yaml]) -> Option<String> {
+    let doc = yaml.first()?;
+    doc["at-uri"]
+        .as_str()
+        .or_else(|| doc["at_uri"].as_str())
+        .filter(|uri| uri.starts_with("at://"))
+        .map(str::to_owned)
+}
+
+fn path_to_manifest_string(path: &Path) -> String {
+    // Normalize separators so the manifest is stable across platforms.
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Builds the manifest from the context after all pages have been written.
+///
+/// Titles and frontmatter are read from the maps populated during rendering;
+/// markdown sources are re-read only to extract the link graph.
+pub async fn build_manifest<A>(
+    context: &StaticSiteContext<A>,
+) -> Result<SiteManifest, miette::Report>
+where
+    A: AgentSession,
+{
+    let mut entries = Vec::new();
+    let mut assets = Vec::new();
+
+    let Some(contents) = context.dir_contents.clone() else {
+        return Ok(SiteManifest {
+            version: 1,
+            entries,
+            assets,
+        });
+    };
+
+    for file in contents
+        .iter()
+        .filter(|file| file.starts_with(&context.start_at))
+    {
+        let relative = file
+            .strip_prefix(&context.start_at)
+            .expect("file should always be nested under root");
+
+        let is_markdown = file
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext == "md" || ext == "markdown")
+            .unwrap_or(false);
+
+        if !is_markdown {
+            let data = tokio::fs::read(file).await.into_diagnostic()?;
+            let hash = weaver_common::blake3::hash(&data);
+            assets.push(ManifestAsset {
+                source: path_to_manifest_string(relative),
+                path: path_to_manifest_string(&output_rel_path(context.options, relative, false)),
+                blake3: hash.to_hex().to_string(),
+            });
+            continue;
+        }
+
+        let path = if context.index_file.as_deref() == Some(relative) {
+            PathBuf::from("index.html")
+        } else {
+            output_rel_path(context.options, relative, true)
+        };
+
+        let title = context
+            .titles
+            .get(file.as_path())
+            .map(|t| t.value().to_string());
+
+        let (tags, at_uri) = if let Some(frontmatter) = context.frontmatter.get(file.as_path()) {
+            let yaml = frontmatter.contents();
+            let yaml = yaml.read().expect("frontmatter lock poisoned");
+            (frontmatter_tags(&yaml), frontmatter_at_uri(&yaml))
+        } else {
+            (Vec::new(), None)
+        };
+
+        let source = tokio::fs::read_to_string(file).await.into_diagnostic()?;
+        let links = collect_links(&source, context.md_options);
+
+        entries.push(ManifestEntry {
+            source: path_to_manifest_string(relative),
+            path: path_to_manifest_string(&path),
+            title,
+            tags,
+            at_uri,
+            links,
+        });
+    }
+
+    Ok(SiteManifest {
+        version: 1,
+        entries,
+        assets,
+    })
+}
+
+/// Builds the manifest and writes it to `weaver-manifest.json` in the
+/// destination directory.
+pub async fn write_site_manifest<A>(context: &StaticSiteContext<A>) -> Result<(), miette::Report>
+where
+    A: AgentSession,
+{
+    let manifest = build_manifest(context).await?;
+    let json = serde_json::to_string_pretty(&manifest).into_diagnostic()?;
+    tokio::fs::write(context.destination.join(MANIFEST_FILENAME), json)
+        .await
+        .into_diagnostic()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_md_options;
+    use yaml_rust2::YamlLoader;
+
+    #[test]
+    fn test_output_rel_path_flattened() {
+        let options = StaticSiteOptions::default();
+        assert_eq!(
+            output_rel_path(options, Path::new("a/b/c.md"), true),
+            PathBuf::from("b/c.html")
+        );
+        assert_eq!(
+            output_rel_path(options, Path::new("top.md"), true),
+            PathBuf::from("entry/top.html")
+        );
+        assert_eq!(
+            output_rel_path(options, Path::new("img/pic.png"), false),
+            PathBuf::from("img/pic.png")
+        );
+    }
+
+    #[test]
+    fn test_output_rel_path_unflattened() {
+        let options = StaticSiteOptions::empty();
+        assert_eq!(
+            output_rel_path(options, Path::new("a/b/c.md"), true),
+            PathBuf::from("a/b/c.html")
+        );
+    }
+
+    #[test]
+    fn test_collect_links_dedupes_and_skips_anchors() {
+        let md = "[one](./other.md) and [two](https://example.com) \
+                  and [again](./other.md) and [frag](#section)";
+        let links = collect_links(md, default_md_options());
+        assert_eq!(links, vec!["./other.md", "https://example.com"]);
+    }
+
+    #[test]
+    fn test_frontmatter_tags_sequence_and_string() {
+        let seq = YamlLoader::load_from_str("tags:\n  - rust\n  - atproto\n").unwrap();
+        assert_eq!(frontmatter_tags(&seq), vec!["rust", "atproto"]);
+
+        let csv = YamlLoader::load_from_str("tags: rust, atproto").unwrap();
+        assert_eq!(frontmatter_tags(&csv), vec!["rust", "atproto"]);
+
+        let none = YamlLoader::load_from_str("title: hello").unwrap();
+        assert!(frontmatter_tags(&none).is_empty());
+    }
+
+    #[test]
+    fn test_frontmatter_at_uri() {
+        let yaml =
+            YamlLoader::load_from_str("at-uri: at://did:plc:abc/sh.weaver.notebook.entry/xyz")
+                .unwrap();
+        assert_eq!(
+            frontmatter_at_uri(&yaml).as_deref(),
+            Some("at://did:plc:abc/sh.weaver.notebook.entry/xyz")
+        );
+
+        // Non-AT values are rejected rather than passed through.
+        let bad = YamlLoader::load_from_str("at-uri: https://example.com").unwrap();
+        assert!(frontmatter_at_uri(&bad).is_none());
+    }
+}
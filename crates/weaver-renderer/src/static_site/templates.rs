@@ -0,0 +1,155 @@
+//! Theme template overrides for the static site renderer.
+//!
+//! A theme directory supplies optional fragments that are merged with the
+//! built-in layout: `header.html` is written right after `<body>` opens,
+//! `footer.html` right before `</body>`, and `page.html` wraps each rendered
+//! entry (its `{{ content }}` placeholder is replaced with the rendered
+//! HTML). Any `*.css` / `*.js` files at the theme root are copied into a
+//! `theme/` directory in the destination and referenced from every page.
+
+use miette::IntoDiagnostic;
+use std::path::{Path, PathBuf};
+
+/// Placeholder replaced with the rendered entry HTML in `page.html`.
+pub const CONTENT_PLACEHOLDER: &str = "{{ content }}";
+
+/// Filenames recognized at the root of a theme directory.
+pub const HEADER_TEMPLATE: &str = "header.html";
+pub const FOOTER_TEMPLATE: &str = "footer.html";
+pub const PAGE_TEMPLATE: &str = "page.html";
+
+/// Template overrides loaded from a theme directory.
+///
+/// Every fragment is optional; a missing file falls back to the built-in
+/// layout, so a theme only has to override what it changes.
+#[derive(Debug, Clone, Default)]
+pub struct SiteTemplates {
+    /// Markup written right after `<body>` opens (masthead, nav).
+    pub header: Option<String>,
+    /// Markup written right before `</body>`.
+    pub footer: Option<String>,
+    /// Wrapper applied around each rendered entry. Must contain
+    /// [`CONTENT_PLACEHOLDER`].
+    pub page: Option<String>,
+    /// Extra stylesheets and scripts copied into `theme/` in the
+    /// destination and referenced from every page head.
+    pub assets: Vec<PathBuf>,
+}
+
+impl SiteTemplates {
+    /// Load template overrides from a theme directory.
+    pub fn load(dir: &Path) -> miette::Result<Self> {
+        if !dir.is_dir() {
+            return Err(miette::miette!(
+                "Theme directory not found: {}",
+                dir.display()
+            ));
+        }
+
+        let page = read_fragment(dir, PAGE_TEMPLATE)?;
+        if let Some(page) = &page {
+            if !page.contains(CONTENT_PLACEHOLDER) {
+                return Err(miette::miette!(
+                    "{} must contain the {} placeholder",
+                    PAGE_TEMPLATE,
+                    CONTENT_PLACEHOLDER
+                ));
+            }
+        }
+
+        let mut assets = Vec::new();
+        for entry in std::fs::read_dir(dir).into_diagnostic()? {
+            let path = entry.into_diagnostic()?.path();
+            let is_asset = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| ext == "css" || ext == "js")
+                .unwrap_or(false);
+            if path.is_file() && is_asset {
+                assets.push(path);
+            }
+        }
+        // Sort for a deterministic link order across platforms.
+        assets.sort();
+
+        Ok(Self {
+            header: read_fragment(dir, HEADER_TEMPLATE)?,
+            footer: read_fragment(dir, FOOTER_TEMPLATE)?,
+            page,
+            assets,
+        })
+    }
+
+    /// Wrap rendered entry HTML in the page template, if one is set.
+    pub fn apply_page(&self, content: &str) -> String {
+        match &self.page {
+            Some(template) => template.replace(CONTENT_PLACEHOLDER, content),
+            None => content.to_string(),
+        }
+    }
+}
+
+fn read_fragment(dir: &Path, name: &str) -> miette::Result<Option<String>> {
+    let path = dir.join(name);
+    if path.exists() {
+        Ok(Some(std::fs::read_to_string(&path).into_diagnostic()?))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_page_replaces_placeholder() {
+        let templates = SiteTemplates {
+            page: Some("<main>{{ content }}</main>".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(templates.apply_page("<p>hi</p>"), "<main><p>hi</p></main>");
+    }
+
+    #[test]
+    fn test_apply_page_without_template_is_passthrough() {
+        let templates = SiteTemplates::default();
+        assert_eq!(templates.apply_page("<p>hi</p>"), "<p>hi</p>");
+    }
+
+    #[test]
+    fn test_load_rejects_page_without_placeholder() {
+        let dir = std::env::temp_dir().join(format!(
+            "weaver-theme-test-{}-{}",
+            std::process::id(),
+            line!()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(PAGE_TEMPLATE), "<main>no placeholder</main>").unwrap();
+
+        let result = SiteTemplates::load(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_load_collects_fragments_and_assets() {
+        let dir = std::env::temp_dir().join(format!(
+            "weaver-theme-test-{}-{}",
+            std::process::id(),
+            line!()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(HEADER_TEMPLATE), "<nav></nav>").unwrap();
+        std::fs::write(dir.join("custom.css"), "body {}").unwrap();
+        std::fs::write(dir.join("extra.js"), "// noop.").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let templates = SiteTemplates::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(templates.header.as_deref(), Some("<nav></nav>"));
+        assert!(templates.footer.is_none());
+        assert_eq!(templates.assets.len(), 2);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{NotebookProcessor, base_html::TableState, static_site::context::StaticSiteContext};
 use dashmap::DashMap;
 use markdown_weaver::{
-    Alignment, BlockQuoteKind, CodeBlockKind, CowStr, EmbedType, Event, LinkType,
-    ParagraphContext, Tag, WeaverAttributes,
+    Alignment, BlockQuoteKind, CodeBlockKind, CowStr, EmbedType, Event, LinkType, ParagraphContext,
+    Tag, WeaverAttributes,
 };
 use markdown_weaver_escape::{StrWrite, escape_href, escape_html, escape_html_body_text};
 use n0_future::StreamExt;
@@ -16,8 +16,12 @@ enum WrapperElement {
     Div,
 }
 
-pub struct StaticPageWriter<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession, W: StrWrite>
-{
+pub struct StaticPageWriter<
+    'input,
+    I: Iterator<Item = (Event<'input>, Range<usize>)>,
+    A: AgentSession,
+    W: StrWrite,
+> {
     context: NotebookProcessor<'input, I, StaticSiteContext<A>>,
     writer: W,
     /// Source text for gap detection
@@ -33,10 +37,14 @@ pub struct StaticPageWriter<'input, I: Iterator<Item = (Event<'input>, Range<usi
     table_cell_index: usize,
     numbers: DashMap<CowStr<'input>, usize>,
 
-    code_buffer: Option<(Option<String>, String)>, // (lang, content)
+    code_buffer: Option<(crate::fence::FenceInfo, String)>, // (fence info, content)
 
     /// Pending WeaverBlock attrs to apply to the next block element
     pending_block_attrs: Option<WeaverAttributes<'static>>,
+    /// Resolves `weaver:` directives to their effects
+    directives: crate::directive::DirectiveRegistry,
+    /// Next block element is suppressed (`weaver: hidden`)
+    hide_next_block: bool,
     /// Type of wrapper element currently open, and the block depth at which it was opened
     active_wrapper: Option<(WrapperElement, usize)>,
     /// Current block nesting depth (for wrapper close tracking)
@@ -77,6 +85,8 @@ impl<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession,
             numbers: DashMap::new(),
             code_buffer: None,
             pending_block_attrs: None,
+            directives: crate::directive::DirectiveRegistry::default(),
+            hide_next_block: false,
             active_wrapper: None,
             block_depth: 0,
             weaver_block_buffer: String::new(),
@@ -236,6 +246,16 @@ impl<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession,
         }
     }
 
+    /// Merge attrs into the pending set applied to the next block element.
+    fn merge_pending_attrs(&mut self, parsed: WeaverAttributes<'static>) {
+        if let Some(ref mut existing) = self.pending_block_attrs {
+            existing.classes.extend(parsed.classes);
+            existing.attrs.extend(parsed.attrs);
+        } else {
+            self.pending_block_attrs = Some(parsed);
+        }
+    }
+
     /// Close active wrapper element if one is open and we're at the right depth
     fn close_wrapper(&mut self) -> Result<(), W::Error> {
         if let Some((wrapper, open_depth)) = self.active_wrapper.take() {
@@ -252,7 +272,11 @@ impl<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession,
         Ok(())
     }
 
-    fn end_tag(&mut self, tag: markdown_weaver::TagEnd, range: Range<usize>) -> Result<(), W::Error> {
+    fn end_tag(
+        &mut self,
+        tag: markdown_weaver::TagEnd,
+        range: Range<usize>,
+    ) -> Result<(), W::Error> {
         use markdown_weaver::TagEnd;
         match tag {
             TagEnd::HtmlBlock => {}
@@ -320,13 +344,13 @@ impl<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession,
                 self.close_wrapper()?;
             }
             TagEnd::CodeBlock => {
-                if let Some((lang, buffer)) = self.code_buffer.take() {
-                    if let Some(ref lang_str) = lang {
+                if let Some((fence, buffer)) = self.code_buffer.take() {
+                    if fence.lang.is_some() || fence.has_decorations() {
                         // Use a temporary String buffer for syntect
                         let mut temp_output = String::new();
-                        match crate::code_pretty::highlight(
+                        match crate::code_pretty::highlight_with_info(
                             &self.context.context.syntax_set,
-                            Some(lang_str),
+                            &fence,
                             &buffer,
                             &mut temp_output,
                         ) {
@@ -336,7 +360,7 @@ impl<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession,
                             Err(_) => {
                                 // Fallback to plain code block
                                 self.write("<pre><code class=\"language-")?;
-                                escape_html(&mut self.writer, lang_str)?;
+                                escape_html(&mut self.writer, fence.lang.as_deref().unwrap_or(""))?;
                                 self.write("\">")?;
                                 escape_html_body_text(&mut self.writer, &buffer)?;
                                 self.write("</code></pre>\n")?;
@@ -399,26 +423,32 @@ impl<'input, I: Iterator<Item = (Event<'input>, Range<usize>)>, A: AgentSession,
             TagEnd::Embed => (), // shouldn't happen, handled in start
             TagEnd::WeaverBlock(_) => {
                 self.in_non_writing_block = false;
-                eprintln!(
-                    "[TagEnd::WeaverBlock] buffer: {:?}",
-                    self.weaver_block_buffer
-                );
-                // Parse the buffered text for attrs and store for next block
                 if !self.weaver_block_buffer.is_empty() {
-                    let parsed = Self::parse_weaver_attrs(&self.weaver_block_buffer);
-                    eprintln!("[TagEnd::WeaverBlock] parsed: {:?}", parsed);
-                    self.weaver_block_buffer.clear();
-                    // Merge with any existing pending attrs or set new
-                    if let Some(ref mut existing) = self.pending_block_attrs {
-                        existing.classes.extend(parsed.classes);
-                        existing.attrs.extend(parsed.attrs);
+                    let buffer = std::mem::take(&mut self.weaver_block_buffer);
+                    if crate::directive::is_directive(&buffer) {
+                        match self.directives.apply(&buffer) {
+                            Some(crate::directive::DirectiveEffect::Emit(html)) => {
+                                if !self.end_newline {
+                                    self.write_newline()?;
+                                }
+                                self.write(&html)?;
+                                self.write_newline()?;
+                            }
+                            Some(crate::directive::DirectiveEffect::Wrap(parsed)) => {
+                                self.merge_pending_attrs(parsed);
+                            }
+                            Some(crate::directive::DirectiveEffect::HideNextBlock) => {
+                                self.hide_next_block = true;
+                            }
+                            // Unknown or unapplicable directives degrade to
+                            // nothing rather than leaking onto the page.
+                            Some(_) | None => {}
+                        }
                     } else {
-                        self.pending_block_attrs = Some(parsed);
+                        // Parse the buffered text for attrs and store for next block
+                        let parsed = Self::parse_weaver_attrs(&buffer);
+                        self.merge_pending_attrs(parsed);
                     }
-                    eprintln!(
-                        "[TagEnd::WeaverBlock] pending_block_attrs now: {:?}",
-                        self.pending_block_attrs
-                    );
                 }
             }
             TagEnd::FootnoteDefinition => {
@@ -473,11 +503,21 @@ impl<
         Ok(())
     }
 
-    async fn process_event(&mut self, event: Event<'input>, range: Range<usize>) -> Result<(), W::Error> {
+    async fn process_event(
+        &mut self,
+        event: Event<'input>,
+        range: Range<usize>,
+    ) -> Result<(), W::Error> {
         use markdown_weaver::Event::*;
         match event {
             Start(tag) => {
                 println!("Start tag: {:?}", tag);
+                if self.hide_next_block && crate::directive::starts_block(&tag) {
+                    // `weaver: hidden` suppresses the whole block, children
+                    // included.
+                    self.hide_next_block = false;
+                    return self.skip_block().await;
+                }
                 self.start_tag(tag, range).await?;
             }
             End(tag) => {
@@ -562,7 +602,9 @@ impl<
                 self.write("<input disabled=\"\" type=\"checkbox\" checked=\"\" aria-label=\"Completed task\"/>\n")?;
             }
             TaskListMarker(false) => {
-                self.write("<input disabled=\"\" type=\"checkbox\" aria-label=\"Incomplete task\"/>\n")?;
+                self.write(
+                    "<input disabled=\"\" type=\"checkbox\" aria-label=\"Incomplete task\"/>\n",
+                )?;
             }
             WeaverBlock(text) => {
                 // Buffer WeaverBlock content for parsing on End
@@ -573,6 +615,26 @@ impl<
         Ok(())
     }
 
+    /// Consume events until the end of the current block, writing nothing.
+    /// The block's Start event has already been consumed by the caller.
+    async fn skip_block(&mut self) -> Result<(), W::Error> {
+        use markdown_weaver::Event::*;
+        let mut nest = 0;
+        while let Some((event, _range)) = self.context.next().await {
+            match event {
+                Start(_) => nest += 1,
+                End(_) => {
+                    if nest == 0 {
+                        break;
+                    }
+                    nest -= 1;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
     // run raw text, consuming end tag
     async fn raw_text(&mut self) -> Result<(), W::Error> {
         use markdown_weaver::Event::*;
@@ -779,19 +841,15 @@ impl<
                 }
                 match info {
                     CodeBlockKind::Fenced(info) => {
-                        let lang = info.split(' ').next().unwrap();
-                        let lang_opt = if lang.is_empty() {
-                            None
-                        } else {
-                            Some(lang.to_string())
-                        };
-                        // Start buffering
-                        self.code_buffer = Some((lang_opt, String::new()));
+                        // Start buffering; the info string may carry a
+                        // title and highlight ranges beyond the language.
+                        self.code_buffer =
+                            Some((crate::fence::parse_fence_info(&info), String::new()));
                         Ok(())
                     }
                     CodeBlockKind::Indented => {
                         // Start buffering with no language
-                        self.code_buffer = Some((None, String::new()));
+                        self.code_buffer = Some((Default::default(), String::new()));
                         Ok(())
                     }
                 }
@@ -917,7 +975,40 @@ impl<
                 attrs,
             } => {
                 //println!("Embed {:?}: {} - {}", embed_type, title, dest_url);
-                if let Some(attrs) = attrs {
+                if crate::csv::is_tabular_path(&dest_url) && crate::utils::is_local_path(&dest_url)
+                {
+                    // Data files render inline as tables. The raw file is
+                    // copied through as an asset, so the download link in
+                    // the caption can point straight at the embed target.
+                    let file_path = if crate::utils::is_relative_link(&dest_url) {
+                        self.context
+                            .context
+                            .root
+                            .join(std::path::Path::new(&dest_url as &str))
+                    } else {
+                        std::path::PathBuf::from(&dest_url as &str)
+                    };
+                    match crate::csv::render_table_file(
+                        &file_path,
+                        crate::csv::DEFAULT_ROW_LIMIT,
+                        &dest_url,
+                    ) {
+                        Ok(table) => {
+                            self.write(&table)?;
+                            self.write_newline()?;
+                        }
+                        Err(err) => {
+                            tracing::warn!(
+                                "Failed to read data embed {}: {}",
+                                file_path.display(),
+                                err
+                            );
+                            self.write("<p class=\"csv-embed-missing\">could not load ")?;
+                            escape_html(&mut self.writer, &dest_url)?;
+                            self.write("</p>\n")?;
+                        }
+                    }
+                } else if let Some(attrs) = attrs {
                     if let Some((_, content)) = attrs
                         .attrs
                         .iter()
@@ -11,6 +11,10 @@ use crate::RenderError;
 
 type FilterFn = dyn Fn(&DirEntry) -> bool + Send + Sync + 'static;
 
+/// Per-vault ignore file, honored in addition to
+/// [`WalkOptions::ignore_filename`] and using the same gitignore syntax.
+pub const WEAVER_IGNORE_FILENAME: &str = ".weaverignore";
+
 /// `WalkOptions` specifies how an Obsidian vault directory is scanned for eligible files to export.
 #[derive(Clone)]
 #[allow(clippy::exhaustive_structs)]
@@ -70,6 +74,7 @@ impl<'a> WalkOptions<'a> {
             .parents(true)
             .hidden(self.ignore_hidden)
             .add_custom_ignore_filename(self.ignore_filename)
+            .add_custom_ignore_filename(WEAVER_IGNORE_FILENAME)
             .require_git(true)
             .git_ignore(self.honor_gitignore)
             .git_global(self.honor_gitignore)
@@ -107,7 +112,81 @@ pub fn vault_contents(root: &Path, opts: WalkOptions<'_>) -> Result<Vec<PathBuf>
         if metadata.is_dir() {
             continue;
         }
+
+        // Frontmatter can exclude an entry without any ignore file
+        // mentioning it; such files are invisible to both rendering and
+        // publishing since everything goes through this walk.
+        if is_markdown(path) {
+            if let Some(reason) = frontmatter_skip_reason(path) {
+                tracing::debug!("Skipping {}: {}", path.display(), reason);
+                continue;
+            }
+        }
         contents.push(path.to_path_buf());
     }
     Ok(contents)
 }
+
+fn is_markdown(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("md") | Some("markdown")
+    )
+}
+
+/// Check a markdown file's frontmatter for an exclusion flag.
+///
+/// An unreadable file is not skipped here; whatever stage reads it next
+/// produces the better error.
+fn frontmatter_skip_reason(path: &Path) -> Option<&'static str> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    skip_reason_from_contents(&contents)
+}
+
+/// The skip reason declared by a document's frontmatter, if any.
+///
+/// Both `draft: true` and `publish: false` exclude a file; authors
+/// migrating from other tools use either spelling.
+fn skip_reason_from_contents(contents: &str) -> Option<&'static str> {
+    let rest = contents.strip_prefix("---")?;
+    let (block, _) = rest.split_once("\n---")?;
+    let docs = yaml_rust2::YamlLoader::load_from_str(block).ok()?;
+    let doc = docs.first()?;
+
+    if doc["draft"].as_bool() == Some(true) {
+        return Some("frontmatter marks it as a draft");
+    }
+    if doc["publish"].as_bool() == Some(false) {
+        return Some("frontmatter sets publish: false");
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draft_frontmatter_skips() {
+        let contents = "---\ntitle: wip\ndraft: true\n---\n\n# Heading\n";
+        assert!(skip_reason_from_contents(contents).is_some());
+    }
+
+    #[test]
+    fn test_publish_false_skips() {
+        let contents = "---\npublish: false\n---\n\ntext\n";
+        assert!(skip_reason_from_contents(contents).is_some());
+    }
+
+    #[test]
+    fn test_published_and_plain_files_kept() {
+        assert_eq!(
+            skip_reason_from_contents("---\ndraft: false\npublish: true\n---\n"),
+            None
+        );
+        // No frontmatter at all.
+        assert_eq!(skip_reason_from_contents("# Heading\n"), None);
+        // `draft` as a non-boolean is content, not a flag.
+        assert_eq!(skip_reason_from_contents("---\ndraft: maybe\n---\n"), None);
+    }
+}